@@ -0,0 +1,83 @@
+//! Tokenization throughput on scripts dominated by indentation, comments and
+//! identifiers, like most real-world code.
+//!
+//! Run with `cargo bench --bench tokenizer`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use embedded_ecmascript::lexical_grammar::{
+    get_next_token,
+    get_next_token_with_grammar,
+    GoalSymbols,
+    Lexer,
+    Token,
+    Tokenizer,
+};
+use embedded_ecmascript::SourceCodeError;
+
+const FRAGMENT: &str = r#"
+/**
+ * Merge default settings into a user configuration.
+ *
+ * @param {Object} configuration - user supplied settings
+ * @returns {Object} a new object with every setting defined
+ */
+function mergeConfiguration(configuration, defaults = DEFAULT_SETTINGS) {
+    // Skip the work entirely for an empty configuration.
+    if (configuration === undefined || configuration === null) {
+        return Object.assign({}, defaults);
+    }
+    const merged = {};
+    for (const [key, value] of Object.entries(defaults)) {
+        merged[key] = key in configuration ? configuration[key] : value;
+    }
+    merged.timeout = Math.max(merged.timeout | 0, 1_000) / 2;
+    merged.label = `settings for ${configuration.name ?? "anonymous"} user`;
+    return merged; // callers may freeze it
+}
+"#;
+
+fn script() -> String {
+    FRAGMENT.repeat(200)
+}
+
+fn bench_lexer(criterion: &mut Criterion) {
+    let source = script();
+    let mut group = criterion.benchmark_group("lexer");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("script", |bencher| {
+        bencher.iter(|| Lexer::new(black_box(&source)).map(Result::unwrap).count());
+    });
+    group.finish();
+}
+
+type NextToken = for<'src> fn(&'src str, GoalSymbols) -> Result<(Token<'src>, &'src str), SourceCodeError>;
+
+fn tokenize_with(source: &str, next_token: NextToken) -> usize {
+    let mut tokenizer = Tokenizer::new();
+    let mut tail = source;
+    let mut count = 0;
+    while !tail.is_empty() {
+        let (token, rest) = next_token(tail, tokenizer.goal()).unwrap();
+        tokenizer.observe(&token);
+        tail = rest;
+        count += 1;
+    }
+    count
+}
+
+/// Compare the scanner with the `pest` grammar it replaced.
+fn bench_scanner_against_grammar(criterion: &mut Criterion) {
+    let source = FRAGMENT.repeat(10);
+    let mut group = criterion.benchmark_group("next_token");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("scanner", |bencher| {
+        bencher.iter(|| tokenize_with(black_box(&source), get_next_token));
+    });
+    group.bench_function("grammar", |bencher| {
+        bencher.iter(|| tokenize_with(black_box(&source), get_next_token_with_grammar));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lexer, bench_scanner_against_grammar);
+criterion_main!(benches);
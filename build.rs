@@ -0,0 +1,86 @@
+//! Generation of Unicode property tables of the lexical grammar from
+//! `unicode/properties.txt`.
+//!
+//! Each property becomes a two-level table: an index of a chunk for each
+//! 512 code points, and distinct chunks as bitmaps. Most chunks repeat, so
+//! a lookup takes two array accesses while the largest table takes about
+//! 13 kilobytes.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const PROPERTIES: &str = "unicode/properties.txt";
+
+/// Properties to generate tables of, with names of the tables.
+const TABLES: [(&str, &str); 3] = [("ID_Start", "ID_START"), ("ID_Continue", "ID_CONTINUE"), ("Space_Separator", "SPACE_SEPARATOR")];
+
+const CHUNK_BITS: usize = 512;
+const WORDS_PER_CHUNK: usize = CHUNK_BITS / 64;
+const CODE_POINT_COUNT: usize = 0x11_0000;
+
+type Chunk = [u64; WORDS_PER_CHUNK];
+
+fn main() {
+    println!("cargo:rerun-if-changed={PROPERTIES}");
+    let text = fs::read_to_string(PROPERTIES).expect("unicode/properties.txt is readable");
+    let mut code = String::new();
+    for (property, table) in TABLES {
+        let (index, chunks) = build_table(&text, property);
+        writeln!(code, "/// `{property}` of `{PROPERTIES}`.").unwrap();
+        writeln!(code, "pub(super) static {table}: Table = Table {{").unwrap();
+        writeln!(code, "    index: &{index:?},").unwrap();
+        writeln!(code, "    chunks: &[").unwrap();
+        for chunk in chunks {
+            let words: Vec<String> = chunk.iter().map(|word| format!("{word:#018x}")).collect();
+            writeln!(code, "        [{}],", words.join(", ")).unwrap();
+        }
+        writeln!(code, "    ],").unwrap();
+        writeln!(code, "}};").unwrap();
+    }
+    let out_dir = env::var_os("OUT_DIR").expect("Cargo sets OUT_DIR for build scripts");
+    fs::write(Path::new(&out_dir).join("unicode_tables.rs"), code).expect("OUT_DIR is writable");
+}
+
+/// Split a bitmap of code points with `property` into chunks, keeping each
+/// distinct chunk once.
+fn build_table(text: &str, property: &str) -> (Vec<u8>, Vec<Chunk>) {
+    let mut bitmap = vec![[0_u64; WORDS_PER_CHUNK]; CODE_POINT_COUNT / CHUNK_BITS];
+    for (first, last) in ranges(text, property) {
+        for code_point in first..=last {
+            let bit = code_point % CHUNK_BITS;
+            bitmap[code_point / CHUNK_BITS][bit / 64] |= 1 << (bit % 64);
+        }
+    }
+    let mut chunks = Vec::new();
+    let mut positions = HashMap::new();
+    let index = bitmap
+        .into_iter()
+        .map(|chunk| {
+            let position = *positions.entry(chunk).or_insert_with(|| {
+                chunks.push(chunk);
+                chunks.len() - 1
+            });
+            u8::try_from(position).expect("a property has at most 256 distinct chunks")
+        })
+        .collect();
+    (index, chunks)
+}
+
+/// Code point ranges of lines `XXXX..YYYY ; property` or `XXXX ; property`.
+fn ranges<'a>(text: &'a str, property: &'a str) -> impl Iterator<Item = (usize, usize)> + 'a {
+    text.lines().filter_map(move |line| {
+        let line = line.split('#').next().unwrap_or_default();
+        let (code_points, name) = line.split_once(';')?;
+        if name.trim() != property {
+            return None;
+        }
+        let parse = |code_point: &str| usize::from_str_radix(code_point.trim(), 16).expect("a code point is hexadecimal");
+        Some(match code_points.split_once("..") {
+            Some((first, last)) => (parse(first), parse(last)),
+            None => (parse(code_points), parse(code_points)),
+        })
+    })
+}
@@ -0,0 +1,1251 @@
+//! Printer of a grammar tree back into ECMAScript source text.
+//!
+//! The output parses into the same tree, up to source ranges, so a script
+//! can be parsed, transformed and printed again. Grouping is derived from
+//! the tree rather than copied from the source: parentheses are added
+//! wherever an operand binds looser than its position allows, like in
+//! `(a + b) * c` built from two separate nodes, while
+//! [`ParenthesizedExpression`] nodes keep their own.
+//!
+//! Semicolons are always written, so no statement relies on
+//! <https://262.ecma-international.org/14.0/#sec-automatic-semicolon-insertion>.
+
+use crate::grammar::{
+    Argument,
+    ArrayElement,
+    Binding,
+    BindingElement,
+    BindingProperty,
+    BinaryExpression,
+    BinaryOperator,
+    Class,
+    ClassElement,
+    ClassElementName,
+    ConciseBody,
+    Declaration,
+    Decorator,
+    DeferredBody,
+    Expression,
+    ForInOfStatement,
+    ForInit,
+    ForTarget,
+    FormalParameters,
+    Function,
+    LabelledItem,
+    Literal,
+    MemberProperty,
+    MetaProperty,
+    MethodDefinition,
+    MethodKind,
+    ParenthesizedExpression,
+    PropertyDefinition,
+    PropertyName,
+    Script,
+    Statement,
+    StatementListItem,
+    TemplateLiteral,
+    UnaryOperator,
+    UpdateOperator,
+    VariableDeclarationList,
+    VariableKind,
+};
+use crate::grammar::AssignmentOperator;
+
+/// Layout of generated source text.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Formatting {
+    /// Only white space needed to separate tokens, all on a single line.
+    Compact,
+    /// A statement per line, blocks indented by a given number of spaces,
+    /// and spaces around operators.
+    Readable { indent: usize },
+}
+
+impl Default for Formatting {
+    fn default() -> Self {
+        Self::Readable { indent: 4 }
+    }
+}
+
+/// Print a script.
+///
+/// Readable output ends with a line break unless the script is empty.
+///
+/// # Panics
+///
+/// Will panic if the script has a [`DeferredBody`] not parsed yet since
+/// a tree does not keep source text.
+#[must_use]
+pub fn generate_script(script: &Script, formatting: Formatting) -> String {
+    let mut generator = Generator::new(formatting);
+    for (index, item) in script.body.iter().enumerate() {
+        if index > 0 {
+            generator.newline();
+        }
+        generator.statement_list_item(item);
+    }
+    if formatting != Formatting::Compact && !script.body.is_empty() {
+        generator.output.push('\n');
+    }
+    generator.output
+}
+
+/// Print an expression alone, as [`crate::grammar::Parser::parse_expression`]
+/// would accept it.
+///
+/// # Panics
+///
+/// Will panic if the expression has a [`DeferredBody`] not parsed yet.
+#[must_use]
+pub fn generate_expression(expression: &Expression, formatting: Formatting) -> String {
+    let mut generator = Generator::new(formatting);
+    generator.expression(expression, COMMA);
+    generator.output
+}
+
+// Binding strength of expressions, from `Expression` to `PrimaryExpression`.
+// An operand written at a position that requires a stronger one is
+// parenthesized.
+const COMMA: u8 = 0;
+const ASSIGNMENT: u8 = 1;
+const CONDITIONAL: u8 = 2;
+// Binary operators take `BinaryOperator::precedence() + CONDITIONAL`, from
+// `ShortCircuitExpression` up to `ExponentiationExpression`.
+const SHORT_CIRCUIT: u8 = CONDITIONAL + 1;
+const UNARY: u8 = 14;
+const UPDATE: u8 = 15;
+/// `NewExpression` without arguments and `OptionalExpression`: a
+/// `LeftHandSideExpression` that cannot be continued with `.`, `[` or `(`.
+const LEFT_HAND_SIDE: u8 = 16;
+const CALL: u8 = 17;
+const MEMBER: u8 = 18;
+const PRIMARY: u8 = 19;
+
+const fn binary_precedence(operator: BinaryOperator) -> u8 {
+    operator.precedence() + CONDITIONAL
+}
+
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::This(_)
+        | Expression::IdentifierReference(_)
+        | Expression::Literal(..)
+        | Expression::ArrayLiteral(_)
+        | Expression::ObjectLiteral(_)
+        | Expression::FunctionExpression(_)
+        | Expression::ClassExpression(_)
+        | Expression::RegularExpressionLiteral(..)
+        | Expression::TemplateLiteral(_)
+        | Expression::ParenthesizedExpression(_) => PRIMARY,
+        // `a().b` and ``a()`b` `` are `CallExpression`s, so they cannot be
+        // a callee of `new`.
+        Expression::MemberExpression(member) if precedence(&member.object) == CALL => CALL,
+        Expression::TaggedTemplate(tagged) if precedence(&tagged.tag) == CALL => CALL,
+        Expression::MemberExpression(_)
+        | Expression::SuperProperty(_)
+        | Expression::TaggedTemplate(_)
+        | Expression::MetaProperty(..) => MEMBER,
+        Expression::NewExpression(new) if new.arguments.is_some() => MEMBER,
+        Expression::CallExpression(_) | Expression::SuperCall(_) | Expression::ImportCall(_) => CALL,
+        Expression::NewExpression(_) | Expression::OptionalExpression(_) => LEFT_HAND_SIDE,
+        Expression::UpdateExpression(update) if !update.prefix => UPDATE,
+        Expression::UpdateExpression(_) | Expression::UnaryExpression(_) | Expression::AwaitExpression(_) => UNARY,
+        Expression::BinaryExpression(binary) => binary_precedence(binary.operator),
+        Expression::PrivateInExpression(_) => binary_precedence(BinaryOperator::In),
+        Expression::ConditionalExpression(_) => CONDITIONAL,
+        Expression::YieldExpression(_) | Expression::ArrowFunction(_) | Expression::AssignmentExpression(_) => {
+            ASSIGNMENT
+        },
+        Expression::CommaExpression(_) => COMMA,
+    }
+}
+
+/// > It is a Syntax Error if `??` is mixed with `&&` or `||` without
+/// > parentheses.
+///
+/// Paraphrased from
+/// <https://262.ecma-international.org/14.0/#prod-CoalesceExpression>.
+fn mixes_coalescence(operator: BinaryOperator, operand: &Expression) -> bool {
+    let Expression::BinaryExpression(operand) = operand else {
+        return false;
+    };
+    let is_logical = |operator| matches!(operator, BinaryOperator::And | BinaryOperator::Or);
+    (operator == BinaryOperator::NullishCoalescence && is_logical(operand.operator))
+        || (is_logical(operator) && operand.operator == BinaryOperator::NullishCoalescence)
+}
+
+/// The expression whose first token starts the whole expression, unless
+/// some operand in between gets parenthesized.
+fn leftmost(expression: &Expression) -> &Expression {
+    match expression {
+        Expression::MemberExpression(member) => leftmost(&member.object),
+        Expression::CallExpression(call) => leftmost(&call.callee),
+        Expression::TaggedTemplate(tagged) => leftmost(&tagged.tag),
+        Expression::OptionalExpression(chain) => leftmost(&chain.expression),
+        Expression::UpdateExpression(update) if !update.prefix => leftmost(&update.operand),
+        Expression::BinaryExpression(binary) => leftmost(&binary.left),
+        Expression::ConditionalExpression(conditional) => leftmost(&conditional.condition),
+        Expression::AssignmentExpression(assignment) => leftmost(&assignment.target),
+        Expression::CommaExpression(comma) => comma.expressions.first().map_or(expression, leftmost),
+        _ => expression,
+    }
+}
+
+/// Whether an expression starts with `let` followed by something, like
+/// `let[a]`, which would be read as a declaration.
+fn starts_with_let(expression: &Expression) -> bool {
+    let first = leftmost(expression);
+    !std::ptr::eq(first, expression)
+        && matches!(first, Expression::IdentifierReference(reference) if reference.name == "let")
+}
+
+/// Whether `else` written after a statement would be taken by an `if`
+/// nested in it rather than by an enclosing one.
+fn ends_with_open_if(statement: &Statement) -> bool {
+    match statement {
+        Statement::IfStatement(statement) => statement.alternative.as_deref().is_none_or(ends_with_open_if),
+        Statement::WhileStatement(statement) => ends_with_open_if(&statement.body),
+        Statement::WithStatement(statement) => ends_with_open_if(&statement.body),
+        Statement::ForStatement(statement) => ends_with_open_if(&statement.body),
+        Statement::ForInStatement(statement) | Statement::ForOfStatement(statement) => {
+            ends_with_open_if(&statement.body)
+        },
+        Statement::LabelledStatement(statement) => {
+            matches!(&*statement.body, LabelledItem::Statement(body) if ends_with_open_if(body))
+        },
+        _ => false,
+    }
+}
+
+/// Whether two adjacent characters of different tokens would be read as
+/// one token or a comment, like `a` and `b` of `typeof b` or `+` and `+`
+/// of `a + +b`.
+fn merges(last: char, first: char) -> bool {
+    let is_identifier_part = |codepoint: char| {
+        codepoint.is_alphanumeric() || matches!(codepoint, '_' | '$' | '\\') || !codepoint.is_ascii()
+    };
+    (is_identifier_part(last) && is_identifier_part(first))
+        // `/` of a division followed by a regular expression literal.
+        || matches!((last, first), ('+', '+') | ('-', '-') | ('/', '/' | '*') | ('<', '!'))
+}
+
+struct Generator {
+    formatting: Formatting,
+    output: String,
+    depth: usize,
+    /// Whether a bare `in` operator would end `ForStatement` initialization
+    /// being printed.
+    forbid_in: bool,
+}
+
+impl Generator {
+    const fn new(formatting: Formatting) -> Self {
+        Self { formatting, output: String::new(), depth: 0, forbid_in: false }
+    }
+
+    fn token(&mut self, text: &str) {
+        if let (Some(last), Some(first)) = (self.output.chars().next_back(), text.chars().next()) {
+            if merges(last, first) {
+                self.output.push(' ');
+            }
+        }
+        self.output.push_str(text);
+    }
+
+    /// Write a space that only separates tokens for a reader.
+    fn space(&mut self) {
+        if self.formatting != Formatting::Compact {
+            self.output.push(' ');
+        }
+    }
+
+    fn newline(&mut self) {
+        if let Formatting::Readable { indent } = self.formatting {
+            self.output.push('\n');
+            self.output.extend(std::iter::repeat_n(' ', indent * self.depth));
+        }
+    }
+
+    /// Write a punctuator separating two operands, like `=` or `?`.
+    fn operator(&mut self, text: &str) {
+        self.space();
+        self.token(text);
+        self.space();
+    }
+
+    /// Write `,` between items of a list.
+    fn comma(&mut self) {
+        self.token(",");
+        self.space();
+    }
+
+    fn parenthesized(&mut self, write: impl FnOnce(&mut Self)) {
+        let forbid_in = std::mem::replace(&mut self.forbid_in, false);
+        self.token("(");
+        write(self);
+        self.token(")");
+        self.forbid_in = forbid_in;
+    }
+
+    /*********************************************
+     *
+     * Statements
+     *
+     *********************************************/
+
+    fn statement_list_item(&mut self, item: &StatementListItem) {
+        match item {
+            StatementListItem::Statement(statement) => self.statement(statement),
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => self.function(function),
+            StatementListItem::Declaration(Declaration::ClassDeclaration(class)) => self.class(class),
+            StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) => {
+                self.variable_declaration_list(list);
+                self.token(";");
+            },
+        }
+    }
+
+    /// Write `{ StatementList }`.
+    fn block(&mut self, items: &[StatementListItem]) {
+        self.token("{");
+        if items.is_empty() {
+            self.token("}");
+            return;
+        }
+        self.depth += 1;
+        for item in items {
+            self.newline();
+            self.statement_list_item(item);
+        }
+        self.depth -= 1;
+        self.newline();
+        self.token("}");
+    }
+
+    /// Write a single statement as a block.
+    fn braced(&mut self, statement: &Statement) {
+        self.token("{");
+        self.depth += 1;
+        self.newline();
+        self.statement(statement);
+        self.depth -= 1;
+        self.newline();
+        self.token("}");
+    }
+
+    /// Write a statement nested in another one, like a body of a loop.
+    fn substatement(&mut self, statement: &Statement) {
+        if !matches!(statement, Statement::EmptyStatement(_)) {
+            self.space();
+        }
+        self.statement(statement);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::BlockStatement(block) => self.block(&block.statements),
+                Statement::VariableStatement(list) => {
+                    self.variable_declaration_list(list);
+                    self.token(";");
+                },
+                Statement::EmptyStatement(_) => self.token(";"),
+                Statement::ExpressionStatement(expression, _) => {
+                    // `ExpressionStatement : [lookahead ∉ { {, function,
+                    // async function, class, let [ }] Expression ;`
+                    let first = leftmost(expression);
+                    if matches!(first, Expression::ObjectLiteral(_) | Expression::FunctionExpression(_)
+                        | Expression::ClassExpression(_)) || starts_with_let(expression)
+                    {
+                        self.parenthesized(|generator| generator.expression(expression, COMMA));
+                    } else {
+                        self.expression(expression, COMMA);
+                    }
+                    self.token(";");
+                },
+                Statement::IfStatement(statement) => {
+                    self.token("if");
+                    self.space();
+                    self.parenthesized(|generator| generator.expression(&statement.condition, COMMA));
+                    let Some(alternative) = &statement.alternative else {
+                        self.substatement(&statement.consequent);
+                        return;
+                    };
+                    let is_open = ends_with_open_if(&statement.consequent);
+                    if is_open {
+                        self.space();
+                        self.braced(&statement.consequent);
+                    } else {
+                        self.substatement(&statement.consequent);
+                    }
+                    if is_open || matches!(*statement.consequent, Statement::BlockStatement(_)) {
+                        self.space();
+                    } else {
+                        self.newline();
+                    }
+                    self.token("else");
+                    self.substatement(alternative);
+                },
+                Statement::DoWhileStatement(statement) => {
+                    self.token("do");
+                    self.substatement(&statement.body);
+                    if matches!(*statement.body, Statement::BlockStatement(_)) {
+                        self.space();
+                    } else {
+                        self.newline();
+                    }
+                    self.token("while");
+                    self.space();
+                    self.parenthesized(|generator| generator.expression(&statement.condition, COMMA));
+                    self.token(";");
+                },
+                Statement::WhileStatement(statement) => {
+                    self.token("while");
+                    self.space();
+                    self.parenthesized(|generator| generator.expression(&statement.condition, COMMA));
+                    self.substatement(&statement.body);
+                },
+                Statement::ForStatement(statement) => {
+                    self.token("for");
+                    self.space();
+                    self.parenthesized(|generator| {
+                        let forbid_in = std::mem::replace(&mut generator.forbid_in, true);
+                        match &statement.init {
+                            Some(ForInit::Expression(expression)) if starts_with_let(expression) => {
+                                generator.parenthesized(|generator| generator.expression(expression, COMMA));
+                            },
+                            Some(ForInit::Expression(expression)) => generator.expression(expression, COMMA),
+                            Some(ForInit::VariableDeclarationList(list)) => generator.variable_declaration_list(list),
+                            None => {},
+                        }
+                        generator.forbid_in = forbid_in;
+                        generator.token(";");
+                        if let Some(condition) = &statement.condition {
+                            generator.space();
+                            generator.expression(condition, COMMA);
+                        }
+                        generator.token(";");
+                        if let Some(update) = &statement.update {
+                            generator.space();
+                            generator.expression(update, COMMA);
+                        }
+                    });
+                    self.substatement(&statement.body);
+                },
+                Statement::ForInStatement(statement) => self.for_in_of_statement(statement, false),
+                Statement::ForOfStatement(statement) => self.for_in_of_statement(statement, true),
+                Statement::ContinueStatement(statement) => self.jump("continue", statement.label.as_deref()),
+                Statement::BreakStatement(statement) => self.jump("break", statement.label.as_deref()),
+                Statement::ReturnStatement(statement) => {
+                    self.token("return");
+                    if let Some(argument) = &statement.argument {
+                        self.space();
+                        self.expression(argument, COMMA);
+                    }
+                    self.token(";");
+                },
+                Statement::WithStatement(statement) => {
+                    self.token("with");
+                    self.space();
+                    self.parenthesized(|generator| generator.expression(&statement.object, COMMA));
+                    self.substatement(&statement.body);
+                },
+                Statement::SwitchStatement(statement) => {
+                    self.token("switch");
+                    self.space();
+                    self.parenthesized(|generator| generator.expression(&statement.discriminant, COMMA));
+                    self.space();
+                    self.token("{");
+                    self.depth += 1;
+                    for case in &statement.cases {
+                        self.newline();
+                        if let Some(test) = &case.test {
+                            self.token("case");
+                            self.space();
+                            self.expression(test, COMMA);
+                        } else {
+                            self.token("default");
+                        }
+                        self.token(":");
+                        self.depth += 1;
+                        for item in &case.statements {
+                            self.newline();
+                            self.statement_list_item(item);
+                        }
+                        self.depth -= 1;
+                    }
+                    self.depth -= 1;
+                    if !statement.cases.is_empty() {
+                        self.newline();
+                    }
+                    self.token("}");
+                },
+                Statement::LabelledStatement(statement) => {
+                    self.token(&statement.label);
+                    self.token(":");
+                    match &*statement.body {
+                        LabelledItem::Statement(body) => self.substatement(body),
+                        LabelledItem::FunctionDeclaration(function) => {
+                            self.space();
+                            self.function(function);
+                        },
+                    }
+                },
+                Statement::ThrowStatement(statement) => {
+                    self.token("throw");
+                    self.space();
+                    self.expression(&statement.argument, COMMA);
+                    self.token(";");
+                },
+                Statement::TryStatement(statement) => {
+                    self.token("try");
+                    self.space();
+                    self.block(&statement.block.statements);
+                    if let Some(handler) = &statement.handler {
+                        self.space();
+                        self.token("catch");
+                        self.space();
+                        if let Some(parameter) = &handler.parameter {
+                            self.parenthesized(|generator| generator.binding(parameter));
+                            self.space();
+                        }
+                        self.block(&handler.body.statements);
+                    }
+                    if let Some(finalizer) = &statement.finalizer {
+                        self.space();
+                        self.token("finally");
+                        self.space();
+                        self.block(&finalizer.statements);
+                    }
+                },
+                Statement::DebuggerStatement(_) => {
+                    self.token("debugger");
+                    self.token(";");
+                },
+            }
+        });
+    }
+
+    fn for_in_of_statement(&mut self, statement: &ForInOfStatement, is_of: bool) {
+        self.token("for");
+        if statement.is_await {
+            self.token("await");
+        }
+        self.space();
+        self.parenthesized(|generator| {
+            match &statement.target {
+                // `for ( [lookahead ≠ let [] LeftHandSideExpression in` and
+                // `for ( [lookahead ∉ { let, async of }] LeftHandSideExpression of`
+                ForTarget::Expression(expression) if starts_with_let(expression) || (is_of && matches!(
+                    leftmost(expression),
+                    Expression::IdentifierReference(reference) if reference.name == "let" || (
+                        reference.name == "async" && matches!(expression, Expression::IdentifierReference(_))
+                    )
+                )) => generator.parenthesized(|generator| generator.expression(expression, LEFT_HAND_SIDE)),
+                ForTarget::Expression(expression) => generator.expression(expression, LEFT_HAND_SIDE),
+                ForTarget::ForDeclaration { kind, binding, .. } => {
+                    generator.variable_kind(*kind);
+                    generator.binding(binding);
+                },
+            }
+            generator.space();
+            generator.token(if is_of { "of" } else { "in" });
+            generator.space();
+            generator.expression(&statement.source, if is_of { ASSIGNMENT } else { COMMA });
+        });
+        self.substatement(&statement.body);
+    }
+
+    /// Write `continue` or `break` with an optional label.
+    fn jump(&mut self, keyword: &str, label: Option<&str>) {
+        self.token(keyword);
+        if let Some(label) = label {
+            self.space();
+            self.token(label);
+        }
+        self.token(";");
+    }
+
+    fn variable_kind(&mut self, kind: VariableKind) {
+        self.token(match kind {
+            VariableKind::Var => "var",
+            VariableKind::Let => "let",
+            VariableKind::Const => "const",
+            VariableKind::Using => "using",
+            VariableKind::AwaitUsing => "await using",
+        });
+        self.space();
+    }
+
+    /// Write declarations without a trailing `;`.
+    fn variable_declaration_list(&mut self, list: &VariableDeclarationList) {
+        self.variable_kind(list.kind);
+        for (index, declaration) in list.declarations.iter().enumerate() {
+            if index > 0 {
+                self.comma();
+            }
+            self.binding(&declaration.target);
+            self.initializer(declaration.initializer.as_ref());
+        }
+    }
+
+    fn initializer(&mut self, initializer: Option<&Expression>) {
+        if let Some(initializer) = initializer {
+            self.operator("=");
+            self.expression(initializer, ASSIGNMENT);
+        }
+    }
+
+    fn binding(&mut self, binding: &Binding) {
+        crate::grow_stack(|| {
+            match binding {
+                Binding::BindingIdentifier(identifier) => self.token(&identifier.name),
+                Binding::ObjectBindingPattern(pattern) => {
+                    self.token("{");
+                    let count = pattern.properties.len() + usize::from(pattern.rest.is_some());
+                    if count > 0 {
+                        self.space();
+                    }
+                    for (index, property) in pattern.properties.iter().enumerate() {
+                        if index > 0 {
+                            self.comma();
+                        }
+                        match property {
+                            BindingProperty::SingleNameBinding { name, initializer } => {
+                                self.token(&name.name);
+                                self.initializer(initializer.as_ref());
+                            },
+                            BindingProperty::Property { name, value } => {
+                                self.property_name(name);
+                                self.token(":");
+                                self.space();
+                                self.binding_element(value);
+                            },
+                        }
+                    }
+                    if let Some(rest) = &pattern.rest {
+                        if !pattern.properties.is_empty() {
+                            self.comma();
+                        }
+                        self.token("...");
+                        self.token(&rest.name);
+                    }
+                    if count > 0 {
+                        self.space();
+                    }
+                    self.token("}");
+                },
+                Binding::ArrayBindingPattern(pattern) => {
+                    self.token("[");
+                    for (index, element) in pattern.elements.iter().enumerate() {
+                        if index > 0 {
+                            self.comma();
+                        }
+                        if let Some(element) = element {
+                            self.binding_element(element);
+                        }
+                    }
+                    if let Some(rest) = &pattern.rest {
+                        if !pattern.elements.is_empty() {
+                            self.comma();
+                        }
+                        self.token("...");
+                        self.binding(rest);
+                    } else if matches!(pattern.elements.last(), Some(None)) {
+                        // A trailing hole needs a comma of its own.
+                        self.token(",");
+                    }
+                    self.token("]");
+                },
+            }
+        });
+    }
+
+    fn binding_element(&mut self, element: &BindingElement) {
+        self.binding(&element.target);
+        self.initializer(element.initializer.as_ref());
+    }
+
+    /*********************************************
+     *
+     * Functions and classes
+     *
+     *********************************************/
+
+    fn function(&mut self, function: &Function) {
+        if function.is_async {
+            self.token("async");
+            self.space();
+        }
+        self.token("function");
+        if function.is_generator {
+            self.token("*");
+        }
+        self.space();
+        if let Some(name) = &function.name {
+            self.token(&name.name);
+        }
+        self.formal_parameters(&function.parameters);
+        self.space();
+        self.function_body(&function.body, function.deferred_body.as_ref());
+    }
+
+    fn function_body(&mut self, body: &[StatementListItem], deferred_body: Option<&DeferredBody>) {
+        assert!(deferred_body.is_none(), "a deferred function body must be parsed before printing");
+        self.block(body);
+    }
+
+    fn formal_parameters(&mut self, parameters: &FormalParameters) {
+        self.parenthesized(|generator| {
+            for (index, parameter) in parameters.parameters.iter().enumerate() {
+                if index > 0 {
+                    generator.comma();
+                }
+                generator.binding_element(parameter);
+            }
+            if let Some(rest) = &parameters.rest {
+                if !parameters.parameters.is_empty() {
+                    generator.comma();
+                }
+                generator.token("...");
+                generator.binding(rest);
+            }
+        });
+    }
+
+    fn method_definition(&mut self, method: &MethodDefinition) {
+        match method.kind {
+            MethodKind::Method => {},
+            MethodKind::Generator => self.token("*"),
+            MethodKind::Async => {
+                self.token("async");
+                self.space();
+            },
+            MethodKind::AsyncGenerator => {
+                self.token("async");
+                self.space();
+                self.token("*");
+            },
+            MethodKind::Getter => {
+                self.token("get");
+                self.space();
+            },
+            MethodKind::Setter => {
+                self.token("set");
+                self.space();
+            },
+        }
+        self.class_element_name(&method.name);
+        self.formal_parameters(&method.parameters);
+        self.space();
+        self.function_body(&method.body, method.deferred_body.as_ref());
+    }
+
+    fn class_element_name(&mut self, name: &ClassElementName) {
+        match name {
+            ClassElementName::PropertyName(name) => self.property_name(name),
+            ClassElementName::PrivateIdentifier(name, _) => self.token(&name.to_string()),
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.decorators(&class.decorators);
+        self.token("class");
+        if let Some(name) = &class.name {
+            self.space();
+            self.token(&name.name);
+        }
+        if let Some(heritage) = &class.heritage {
+            self.space();
+            self.token("extends");
+            self.space();
+            self.expression(heritage, LEFT_HAND_SIDE);
+        }
+        self.space();
+        self.token("{");
+        if class.elements.is_empty() {
+            self.token("}");
+            return;
+        }
+        self.depth += 1;
+        for element in &class.elements {
+            self.newline();
+            self.class_element(element);
+        }
+        self.depth -= 1;
+        self.newline();
+        self.token("}");
+    }
+
+    fn class_element(&mut self, element: &ClassElement) {
+        let is_static = match element {
+            ClassElement::MethodDefinition { decorators, is_static, .. }
+            | ClassElement::FieldDefinition { decorators, is_static, .. } => {
+                self.decorators(decorators);
+                *is_static
+            },
+            ClassElement::ClassStaticBlock(..) => true,
+        };
+        if is_static {
+            self.token("static");
+            self.space();
+        }
+        match element {
+            ClassElement::MethodDefinition { method, .. } => self.method_definition(method),
+            ClassElement::FieldDefinition { name, initializer, .. } => {
+                self.class_element_name(name);
+                self.initializer(initializer.as_deref());
+                self.token(";");
+            },
+            ClassElement::ClassStaticBlock(body, _) => self.block(body),
+        }
+    }
+
+    fn decorators(&mut self, decorators: &[Decorator]) {
+        for decorator in decorators {
+            self.token("@");
+            if is_decorator_expression(&decorator.expression) {
+                self.expression(&decorator.expression, CALL);
+            } else {
+                self.parenthesized(|generator| generator.expression(&decorator.expression, COMMA));
+            }
+            self.space();
+        }
+    }
+
+    /*********************************************
+     *
+     * Expressions
+     *
+     *********************************************/
+
+    /// Write an expression at a position that requires at least a given
+    /// binding strength.
+    fn expression(&mut self, expression: &Expression, required: u8) {
+        crate::grow_stack(|| {
+            let needs_parentheses = precedence(expression) < required || (self.forbid_in && matches!(
+                expression,
+                Expression::BinaryExpression(BinaryExpression { operator: BinaryOperator::In, .. })
+                    | Expression::PrivateInExpression(_)
+            ));
+            // `in` inside brackets and braces cannot end a head of a loop.
+            let is_delimited = matches!(
+                expression,
+                Expression::ArrayLiteral(_)
+                    | Expression::ObjectLiteral(_)
+                    | Expression::FunctionExpression(_)
+                    | Expression::ClassExpression(_)
+            );
+            let forbid_in = self.forbid_in;
+            self.forbid_in &= !is_delimited;
+            if needs_parentheses {
+                self.parenthesized(|generator| generator.unparenthesized(expression));
+            } else {
+                self.unparenthesized(expression);
+            }
+            self.forbid_in = forbid_in;
+        });
+    }
+
+    fn operand(&mut self, operator: BinaryOperator, operand: &Expression, required: u8) {
+        if mixes_coalescence(operator, operand) {
+            self.parenthesized(|generator| generator.expression(operand, COMMA));
+        } else {
+            self.expression(operand, required);
+        }
+    }
+
+    fn unparenthesized(&mut self, expression: &Expression) {
+        match expression {
+            Expression::This(_) => self.token("this"),
+            Expression::IdentifierReference(reference) => self.token(&reference.name),
+            Expression::Literal(literal, _) => match literal {
+                Literal::NullLiteral => self.token("null"),
+                Literal::BooleanLiteral(value) => self.token(if *value { "true" } else { "false" }),
+                Literal::NumericLiteral(literal) => self.token(&literal.to_string()),
+                Literal::StringLiteral(literal) => self.token(&literal.to_string()),
+            },
+            Expression::ArrayLiteral(array) => {
+                self.token("[");
+                for (index, element) in array.elements.iter().enumerate() {
+                    if index > 0 {
+                        self.comma();
+                    }
+                    match element {
+                        ArrayElement::Elision(_) => {},
+                        ArrayElement::AssignmentExpression(element) => self.expression(element, ASSIGNMENT),
+                        ArrayElement::SpreadElement(element, _) => {
+                            self.token("...");
+                            self.expression(element, ASSIGNMENT);
+                        },
+                    }
+                }
+                if matches!(array.elements.last(), Some(ArrayElement::Elision(_))) {
+                    // A trailing hole needs a comma of its own.
+                    self.token(",");
+                }
+                self.token("]");
+            },
+            Expression::ObjectLiteral(object) => {
+                self.token("{");
+                if object.properties.is_empty() {
+                    self.token("}");
+                    return;
+                }
+                self.space();
+                for (index, property) in object.properties.iter().enumerate() {
+                    if index > 0 {
+                        self.comma();
+                    }
+                    self.property_definition(property);
+                }
+                self.space();
+                self.token("}");
+            },
+            Expression::FunctionExpression(function) => self.function(function),
+            Expression::ClassExpression(class) => self.class(class),
+            Expression::RegularExpressionLiteral(literal, _) => self.token(&literal.to_string()),
+            Expression::TemplateLiteral(template) => self.template_literal(template),
+            Expression::ParenthesizedExpression(ParenthesizedExpression { expression, .. }) => {
+                self.parenthesized(|generator| generator.expression(expression, COMMA));
+            },
+            Expression::MemberExpression(member) => {
+                self.expression(&member.object, CALL);
+                if member.optional {
+                    self.token("?.");
+                }
+                match &member.property {
+                    MemberProperty::IdentifierName(name, _) => {
+                        // `1.a` would be read as a numeric literal `1.`.
+                        if !member.optional && self.output.ends_with(|codepoint: char| codepoint.is_ascii_digit())
+                            && is_integer_literal(&member.object)
+                        {
+                            self.output.push(' ');
+                        }
+                        if !member.optional {
+                            self.token(".");
+                        }
+                        self.token(name);
+                    },
+                    MemberProperty::PrivateIdentifier(name, _) => {
+                        if !member.optional {
+                            self.token(".");
+                        }
+                        self.token(&name.to_string());
+                    },
+                    MemberProperty::Expression(property) => {
+                        let forbid_in = std::mem::replace(&mut self.forbid_in, false);
+                        self.token("[");
+                        self.expression(property, COMMA);
+                        self.token("]");
+                        self.forbid_in = forbid_in;
+                    },
+                }
+            },
+            Expression::SuperProperty(property) => {
+                self.token("super");
+                match &property.property {
+                    MemberProperty::IdentifierName(name, _) => {
+                        self.token(".");
+                        self.token(name);
+                    },
+                    MemberProperty::PrivateIdentifier(name, _) => {
+                        self.token(".");
+                        self.token(&name.to_string());
+                    },
+                    MemberProperty::Expression(property) => {
+                        let forbid_in = std::mem::replace(&mut self.forbid_in, false);
+                        self.token("[");
+                        self.expression(property, COMMA);
+                        self.token("]");
+                        self.forbid_in = forbid_in;
+                    },
+                }
+            },
+            Expression::MetaProperty(property, _) => self.token(match property {
+                MetaProperty::NewTarget => "new.target",
+                MetaProperty::ImportMeta => "import.meta",
+            }),
+            Expression::TaggedTemplate(tagged) => {
+                self.expression(&tagged.tag, CALL);
+                self.template_literal(&tagged.template);
+            },
+            Expression::CallExpression(call) => {
+                self.expression(&call.callee, CALL);
+                if call.optional {
+                    self.token("?.");
+                }
+                self.arguments(&call.arguments);
+            },
+            Expression::SuperCall(call) => {
+                self.token("super");
+                self.arguments(&call.arguments);
+            },
+            Expression::ImportCall(call) => {
+                self.token("import");
+                self.parenthesized(|generator| {
+                    generator.expression(&call.specifier, ASSIGNMENT);
+                    if let Some(options) = &call.options {
+                        generator.comma();
+                        generator.expression(options, ASSIGNMENT);
+                    }
+                });
+            },
+            Expression::OptionalExpression(chain) => self.expression(&chain.expression, CALL),
+            Expression::NewExpression(new) => {
+                self.token("new");
+                self.space();
+                self.expression(&new.callee, MEMBER);
+                if let Some(arguments) = &new.arguments {
+                    self.arguments(arguments);
+                }
+            },
+            Expression::UpdateExpression(update) => {
+                let operator = match update.operator {
+                    UpdateOperator::Increment => "++",
+                    UpdateOperator::Decrement => "--",
+                };
+                if update.prefix {
+                    self.token(operator);
+                    self.expression(&update.operand, UNARY);
+                } else {
+                    self.expression(&update.operand, LEFT_HAND_SIDE);
+                    self.token(operator);
+                }
+            },
+            Expression::UnaryExpression(unary) => {
+                let (operator, is_keyword) = match unary.operator {
+                    UnaryOperator::Delete => ("delete", true),
+                    UnaryOperator::Void => ("void", true),
+                    UnaryOperator::Typeof => ("typeof", true),
+                    UnaryOperator::Plus => ("+", false),
+                    UnaryOperator::Minus => ("-", false),
+                    UnaryOperator::BitNot => ("~", false),
+                    UnaryOperator::Not => ("!", false),
+                };
+                self.token(operator);
+                if is_keyword {
+                    self.space();
+                }
+                self.expression(&unary.operand, UNARY);
+            },
+            Expression::AwaitExpression(expression) => {
+                self.token("await");
+                self.space();
+                self.expression(&expression.argument, UNARY);
+            },
+            Expression::BinaryExpression(binary) => {
+                let precedence = binary_precedence(binary.operator);
+                // `**` is right-associative and does not take a unary
+                // operand on the left.
+                let (left, right) = if binary.operator == BinaryOperator::Exponentiation {
+                    (UPDATE, precedence)
+                } else {
+                    (precedence, precedence + 1)
+                };
+                self.operand(binary.operator, &binary.left, left);
+                self.operator(binary_operator(binary.operator));
+                self.operand(binary.operator, &binary.right, right);
+            },
+            Expression::PrivateInExpression(expression) => {
+                self.token(&expression.name.to_string());
+                self.operator("in");
+                self.expression(&expression.object, binary_precedence(BinaryOperator::LeftShift));
+            },
+            Expression::ConditionalExpression(conditional) => {
+                self.expression(&conditional.condition, SHORT_CIRCUIT);
+                self.operator("?");
+                self.expression(&conditional.consequent, ASSIGNMENT);
+                self.operator(":");
+                self.expression(&conditional.alternative, ASSIGNMENT);
+            },
+            Expression::YieldExpression(expression) => {
+                self.token("yield");
+                if expression.is_delegate {
+                    self.token("*");
+                }
+                if let Some(argument) = &expression.argument {
+                    self.space();
+                    self.expression(argument, ASSIGNMENT);
+                }
+            },
+            Expression::ArrowFunction(function) => {
+                if function.is_async {
+                    self.token("async");
+                    self.space();
+                }
+                self.formal_parameters(&function.parameters);
+                self.operator("=>");
+                match &function.body {
+                    ConciseBody::Expression(body) if matches!(leftmost(body), Expression::ObjectLiteral(_)) => {
+                        self.parenthesized(|generator| generator.expression(body, COMMA));
+                    },
+                    ConciseBody::Expression(body) => self.expression(body, ASSIGNMENT),
+                    ConciseBody::FunctionBody(body, _) => self.function_body(body, function.deferred_body.as_ref()),
+                }
+            },
+            Expression::AssignmentExpression(assignment) => {
+                self.expression(&assignment.target, LEFT_HAND_SIDE);
+                self.operator(assignment_operator(assignment.operator));
+                self.expression(&assignment.value, ASSIGNMENT);
+            },
+            Expression::CommaExpression(comma) => {
+                for (index, expression) in comma.expressions.iter().enumerate() {
+                    if index > 0 {
+                        self.comma();
+                    }
+                    self.expression(expression, ASSIGNMENT);
+                }
+            },
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Argument]) {
+        self.parenthesized(|generator| {
+            for (index, argument) in arguments.iter().enumerate() {
+                if index > 0 {
+                    generator.comma();
+                }
+                match argument {
+                    Argument::AssignmentExpression(argument) => generator.expression(argument, ASSIGNMENT),
+                    Argument::SpreadElement(argument, _) => {
+                        generator.token("...");
+                        generator.expression(argument, ASSIGNMENT);
+                    },
+                }
+            }
+        });
+    }
+
+    fn property_definition(&mut self, property: &PropertyDefinition) {
+        match property {
+            PropertyDefinition::IdentifierReference(reference) => self.token(&reference.name),
+            PropertyDefinition::Property { name, value } => {
+                self.property_name(name);
+                self.token(":");
+                self.space();
+                self.expression(value, ASSIGNMENT);
+            },
+            PropertyDefinition::MethodDefinition(method) => self.method_definition(method),
+            PropertyDefinition::SpreadProperty(argument, _) => {
+                self.token("...");
+                self.expression(argument, ASSIGNMENT);
+            },
+            PropertyDefinition::CoverInitializedName { name, initializer } => {
+                self.token(&name.name);
+                self.initializer(Some(initializer));
+            },
+        }
+    }
+
+    fn property_name(&mut self, name: &PropertyName) {
+        match name {
+            PropertyName::IdentifierName(name, _) => self.token(name),
+            PropertyName::StringLiteral(literal, _) => self.token(&literal.to_string()),
+            PropertyName::NumericLiteral(literal, _) => self.token(&literal.to_string()),
+            PropertyName::ComputedPropertyName(expression, _) => {
+                let forbid_in = std::mem::replace(&mut self.forbid_in, false);
+                self.token("[");
+                self.expression(expression, ASSIGNMENT);
+                self.token("]");
+                self.forbid_in = forbid_in;
+            },
+        }
+    }
+
+    fn template_literal(&mut self, template: &TemplateLiteral) {
+        let forbid_in = std::mem::replace(&mut self.forbid_in, false);
+        self.token("`");
+        for (index, string) in template.strings.iter().enumerate() {
+            if index > 0 {
+                self.output.push('}');
+            }
+            self.output.push_str(&string.raw);
+            if let Some(substitution) = template.substitutions.get(index) {
+                self.output.push_str("${");
+                self.expression(substitution, COMMA);
+            }
+        }
+        self.output.push('`');
+        self.forbid_in = forbid_in;
+    }
+}
+
+/// Whether an expression is written after `@` without parentheses, like
+/// `a.b` or `a.#b(c)`.
+fn is_decorator_expression(expression: &Expression) -> bool {
+    let callee = match expression {
+        Expression::CallExpression(call) if !call.optional => &call.callee,
+        Expression::ParenthesizedExpression(_) => return true,
+        _ => expression,
+    };
+    let mut object = callee;
+    while let Expression::MemberExpression(member) = object {
+        if member.optional || matches!(member.property, MemberProperty::Expression(_)) {
+            return false;
+        }
+        object = &member.object;
+    }
+    matches!(object, Expression::IdentifierReference(_))
+}
+
+/// Whether an expression is a numeric literal that a `.` would continue,
+/// like `1` rather than `1.5` or `0x1`.
+fn is_integer_literal(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Literal(Literal::NumericLiteral(literal), _)
+            if literal.to_string().bytes().all(|byte| byte.is_ascii_digit() || byte == b'_')
+    )
+}
+
+const fn binary_operator(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Exponentiation => "**",
+        BinaryOperator::Multiplication => "*",
+        BinaryOperator::Division => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Addition => "+",
+        BinaryOperator::Subtraction => "-",
+        BinaryOperator::LeftShift => "<<",
+        BinaryOperator::RightShift => ">>",
+        BinaryOperator::UnsignedRightShift => ">>>",
+        BinaryOperator::Less => "<",
+        BinaryOperator::More => ">",
+        BinaryOperator::LessOrEqual => "<=",
+        BinaryOperator::MoreOrEqual => ">=",
+        BinaryOperator::Instanceof => "instanceof",
+        BinaryOperator::In => "in",
+        BinaryOperator::LooseEquality => "==",
+        BinaryOperator::LooseInequality => "!=",
+        BinaryOperator::StrictEquality => "===",
+        BinaryOperator::StrictInequality => "!==",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::And => "&&",
+        BinaryOperator::Or => "||",
+        BinaryOperator::NullishCoalescence => "??",
+    }
+}
+
+const fn assignment_operator(operator: AssignmentOperator) -> &'static str {
+    match operator {
+        AssignmentOperator::Assignment => "=",
+        AssignmentOperator::MultiplicationAssignment => "*=",
+        AssignmentOperator::DivisionAssignment => "/=",
+        AssignmentOperator::ModuloAssignment => "%=",
+        AssignmentOperator::AdditionAssignment => "+=",
+        AssignmentOperator::SubtractionAssignment => "-=",
+        AssignmentOperator::LeftShiftAssignment => "<<=",
+        AssignmentOperator::RightShiftAssignment => ">>=",
+        AssignmentOperator::UnsignedRightShiftAssignment => ">>>=",
+        AssignmentOperator::BitAndAssignment => "&=",
+        AssignmentOperator::BitXorAssignment => "^=",
+        AssignmentOperator::BitOrAssignment => "|=",
+        AssignmentOperator::ExponentiationAssignment => "**=",
+        AssignmentOperator::AndAssignment => "&&=",
+        AssignmentOperator::OrAssignment => "||=",
+        AssignmentOperator::NullishCoalescenceAssignment => "??=",
+    }
+}
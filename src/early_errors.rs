@@ -0,0 +1,1059 @@
+//! Early errors found by walking a complete grammar tree.
+//!
+//! Implements rules of <https://262.ecma-international.org/14.0/#early-error>
+//! that relate distant parts of source text, like two declarations of the
+//! same name in a block, or that depend on how an expression is used, like
+//! an assignment target. Rules local to a production are checked by
+//! [`crate::grammar::Parser`] while parsing; the parser runs this walk
+//! itself at the end of [`crate::grammar::Parser::parse_script`].
+
+use crate::grammar::{
+    Argument,
+    ArrayElement,
+    AssignmentOperator,
+    AssignmentTargetType,
+    Binding,
+    BindingElement,
+    BindingIdentifier,
+    BindingProperty,
+    Block,
+    BreakStatement,
+    CatchClause,
+    Class,
+    ClassElement,
+    ClassElementName,
+    ConciseBody,
+    ContinueStatement,
+    Declaration,
+    ExportDeclaration,
+    Expression,
+    ForInit,
+    ForTarget,
+    FormalParameters,
+    Function,
+    IdentifierReference,
+    LabelledItem,
+    LabelledStatement,
+    MemberProperty,
+    MethodDefinition,
+    Module,
+    ModuleExportName,
+    ModuleItem,
+    PropertyDefinition,
+    PropertyName,
+    Script,
+    Statement,
+    StatementListItem,
+    VariableDeclarationList,
+    VariableKind,
+};
+use crate::{Diagnostics, ErrorCode, Label, SourceCodeError};
+use std::ops::Range;
+
+/// Report early errors of a parsed script or module.
+///
+/// Top-level function declarations of a module are lexically scoped, while
+/// in a script they are `var`-scoped.
+pub fn check_script(source: &str, script: &Script, is_module: bool, diagnostics: &mut Diagnostics) {
+    let mut checker = Checker { source, is_strict: script.is_strict, jump_targets: JumpTargets::default(), diagnostics };
+    let scope = if is_module { Scope::Block } else { Scope::TopLevel };
+    checker.statement_list(&script.body, scope, &[]);
+}
+
+/// Report early errors of a parsed module.
+///
+/// Besides rules of statements, checks imported and exported names:
+///
+/// > It is a Syntax Error if the LexicallyDeclaredNames of ModuleItemList
+/// > contains any duplicate entries.
+/// > It is a Syntax Error if any element of the LexicallyDeclaredNames of
+/// > ModuleItemList also occurs in the VarDeclaredNames of ModuleItemList.
+/// > It is a Syntax Error if the ExportedNames of ModuleItemList contains
+/// > any duplicate entries.
+/// > It is a Syntax Error if any element of the ExportedBindings of
+/// > ModuleItemList does not also occur in either the VarDeclaredNames of
+/// > ModuleItemList, or the LexicallyDeclaredNames of ModuleItemList.
+pub fn check_module(source: &str, module: &Module, diagnostics: &mut Diagnostics) {
+    let mut checker = Checker { source, is_strict: true, jump_targets: JumpTargets::default(), diagnostics };
+    let lexical_names = module.lexically_declared_names();
+    let var_names = module.var_declared_names();
+    for (index, name) in lexical_names.iter().enumerate() {
+        if let Some(first) = lexical_names[..index].iter().find(|first| first.name == name.name) {
+            checker.report_redeclaration(first, name);
+        } else if let Some(var_name) = var_names.iter().find(|var_name| var_name.name == name.name) {
+            checker.report_redeclaration(name, var_name);
+        }
+    }
+    let mut exported_names: Vec<(&str, Range<usize>)> = Vec::new();
+    for item in &module.body {
+        let ModuleItem::ExportDeclaration(declaration) = item else { continue };
+        match declaration {
+            ExportDeclaration::ExportAll { exported: Some(exported), .. } => {
+                exported_names.push((&exported.name, exported.range.clone()));
+            },
+            ExportDeclaration::ExportAll { exported: None, .. } => {},
+            ExportDeclaration::NamedExports { specifiers, module_request, .. } => {
+                for specifier in specifiers {
+                    exported_names.push((&specifier.exported.name, specifier.exported.range.clone()));
+                    if module_request.is_some() {
+                        continue;
+                    }
+                    // > It is a Syntax Error if ReferencedBindings of
+                    // > NamedExports contains any StringLiterals.
+                    let local = &specifier.local;
+                    if local.is_string_literal {
+                        checker.report(local.range.clone(), "a string literal cannot name a local binding".to_owned());
+                    } else if !lexical_names.iter().chain(&var_names).any(|name| name.name == local.name) {
+                        checker.report(local.range.clone(), format!("`{}` is not declared in the module", local.name));
+                    }
+                }
+            },
+            ExportDeclaration::Declaration(item, _) => {
+                let names = item.lexically_declared_names().into_iter().chain(item.var_declared_names());
+                exported_names.extend(names.map(|name| (name.name.as_str(), name.range.clone())));
+            },
+            ExportDeclaration::DefaultDeclaration(_, range) | ExportDeclaration::DefaultExpression(_, range) => {
+                exported_names.push(("default", range.clone()));
+            },
+        }
+    }
+    for (index, (name, range)) in exported_names.iter().enumerate() {
+        if let Some((_, first)) = exported_names[..index].iter().find(|(first, _)| first == name) {
+            let mut error = SourceCodeError::new(source, range.clone(), ErrorCode::EarlyError, format!("`{name}` is already exported"));
+            error.labels.push(Label { range: first.clone(), message: "first exported here".to_owned() });
+            checker.diagnostics.report(error);
+        }
+    }
+    for item in &module.body {
+        match item {
+            ModuleItem::StatementListItem(item) | ModuleItem::ExportDeclaration(ExportDeclaration::Declaration(item, _)) => {
+                checker.statement_list_item(item);
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::DefaultDeclaration(declaration, _)) => match declaration {
+                Declaration::FunctionDeclaration(function) => checker.function(function),
+                Declaration::ClassDeclaration(class) => checker.class(class),
+                Declaration::LexicalDeclaration(list) => checker.lexical_declaration(list),
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::DefaultExpression(expression, _)) => checker.expression(expression),
+            ModuleItem::ImportDeclaration(_) | ModuleItem::ExportDeclaration(_) => {},
+        }
+    }
+}
+
+/// Report early errors of a function body parsed apart from its function,
+/// like one skipped by [`crate::grammar::Parser::with_deferred_function_bodies`].
+///
+/// Conflicts of its declarations with parameters are not reported since
+/// the parameters are not at hand.
+pub fn check_function_body(source: &str, body: &[StatementListItem], is_strict: bool, diagnostics: &mut Diagnostics) {
+    let mut checker = Checker { source, is_strict, jump_targets: JumpTargets::default(), diagnostics };
+    checker.statement_list(body, Scope::TopLevel, &[]);
+}
+
+/// Early errors of a script or a module checked one top-level statement at
+/// a time, so the caller can drop each statement once it is checked.
+///
+/// Reports the same problems as [`check_script`] and [`check_module`],
+/// keeping only names declared and exported at the top level between
+/// statements.
+pub(crate) struct ScriptChecker {
+    is_module: bool,
+    is_strict: bool,
+    lexical_names: Vec<BindingIdentifier>,
+    /// First declarations of each `var`-scoped name.
+    var_names: Vec<BindingIdentifier>,
+    /// Names exported by a module so far.
+    exported_names: Vec<(String, Range<usize>)>,
+    /// Local bindings of `export { a }` not declared before the export,
+    /// which a later declaration may still bind.
+    local_exports: Vec<ModuleExportName>,
+}
+
+impl ScriptChecker {
+    pub(crate) const fn new(is_module: bool, is_strict: bool) -> Self {
+        Self {
+            is_module,
+            is_strict,
+            lexical_names: Vec::new(),
+            var_names: Vec::new(),
+            exported_names: Vec::new(),
+            local_exports: Vec::new(),
+        }
+    }
+
+    /// Check a next top-level statement against itself and ones before it.
+    pub(crate) fn check(&mut self, source: &str, item: &StatementListItem, diagnostics: &mut Diagnostics) {
+        let mut checker = Checker { source, is_strict: self.is_strict, jump_targets: JumpTargets::default(), diagnostics };
+        let scope = if self.is_module { Scope::Block } else { Scope::TopLevel };
+        let item = std::slice::from_ref(item);
+        checker.statement_list(item, scope, &[]);
+        self.declare(&mut checker, lexically_declared_names(item, scope), var_declared_names(item, scope));
+    }
+
+    /// Check a next item of a module body against itself and ones before
+    /// it, like [`check_module`] does.
+    pub(crate) fn check_module_item(&mut self, source: &str, item: &ModuleItem, diagnostics: &mut Diagnostics) {
+        let mut checker = Checker { source, is_strict: true, jump_targets: JumpTargets::default(), diagnostics };
+        match item {
+            ModuleItem::ImportDeclaration(declaration) => {
+                let names = declaration
+                    .default_binding
+                    .iter()
+                    .chain(&declaration.namespace_binding)
+                    .chain(declaration.named_imports.iter().map(|specifier| &specifier.local));
+                self.declare(&mut checker, names.collect(), Vec::new());
+            },
+            ModuleItem::StatementListItem(item) => self.check(source, item, checker.diagnostics),
+            ModuleItem::ExportDeclaration(ExportDeclaration::ExportAll { exported, .. }) => {
+                if let Some(exported) = exported {
+                    self.export(&mut checker, &exported.name, exported.range.clone());
+                }
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::NamedExports { specifiers, module_request, .. }) => {
+                for specifier in specifiers {
+                    self.export(&mut checker, &specifier.exported.name, specifier.exported.range.clone());
+                    if module_request.is_some() {
+                        continue;
+                    }
+                    // > It is a Syntax Error if ReferencedBindings of
+                    // > NamedExports contains any StringLiterals.
+                    let local = &specifier.local;
+                    if local.is_string_literal {
+                        checker.report(local.range.clone(), "a string literal cannot name a local binding".to_owned());
+                    } else if !self.lexical_names.iter().chain(&self.var_names).any(|name| name.name == local.name) {
+                        self.local_exports.push(local.clone());
+                    }
+                }
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::Declaration(item, _)) => {
+                for name in item.lexically_declared_names().into_iter().chain(item.var_declared_names()) {
+                    self.export(&mut checker, &name.name, name.range.clone());
+                }
+                self.check(source, item, checker.diagnostics);
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::DefaultDeclaration(declaration, range)) => {
+                self.export(&mut checker, "default", range.clone());
+                match declaration {
+                    Declaration::FunctionDeclaration(function) => checker.function(function),
+                    Declaration::ClassDeclaration(class) => checker.class(class),
+                    Declaration::LexicalDeclaration(list) => checker.lexical_declaration(list),
+                }
+                self.declare(&mut checker, declaration.bound_names(), Vec::new());
+            },
+            ModuleItem::ExportDeclaration(ExportDeclaration::DefaultExpression(expression, range)) => {
+                self.export(&mut checker, "default", range.clone());
+                checker.expression(expression);
+            },
+        }
+    }
+
+    /// Report local bindings of `export { a }` that no top-level
+    /// declaration of the module binds, once all items are checked.
+    pub(crate) fn finish_module(&mut self, source: &str, diagnostics: &mut Diagnostics) {
+        let mut checker = Checker { source, is_strict: true, jump_targets: JumpTargets::default(), diagnostics };
+        for local in std::mem::take(&mut self.local_exports) {
+            if !self.lexical_names.iter().chain(&self.var_names).any(|name| name.name == local.name) {
+                checker.report(local.range, format!("`{}` is not declared in the module", local.name));
+            }
+        }
+    }
+
+    /// Record names declared by a next top-level item, reporting ones
+    /// declared before it.
+    fn declare(&mut self, checker: &mut Checker, lexical_names: Vec<&BindingIdentifier>, var_names: Vec<&BindingIdentifier>) {
+        for name in &lexical_names {
+            if let Some(first) = self.lexical_names.iter().chain(&self.var_names).find(|first| first.name == name.name) {
+                checker.report_redeclaration(first, name);
+            }
+        }
+        for name in &var_names {
+            if let Some(first) = self.lexical_names.iter().find(|first| first.name == name.name) {
+                checker.report_redeclaration(first, name);
+            }
+        }
+        self.lexical_names.extend(lexical_names.into_iter().cloned());
+        for name in var_names {
+            if !self.var_names.iter().any(|first| first.name == name.name) {
+                self.var_names.push(name.clone());
+            }
+        }
+    }
+
+    /// Record a name exported by a module, reporting one exported before.
+    fn export(&mut self, checker: &mut Checker, name: &str, range: Range<usize>) {
+        if let Some((_, first)) = self.exported_names.iter().find(|(first, _)| first == name) {
+            let mut error = SourceCodeError::new(checker.source, range, ErrorCode::EarlyError, format!("`{name}` is already exported"));
+            error.labels.push(Label { range: first.clone(), message: "first exported here".to_owned() });
+            checker.diagnostics.report(error);
+        } else {
+            self.exported_names.push((name.to_owned(), range));
+        }
+    }
+}
+
+/// Where a statement list is, which decides how its function declarations
+/// are scoped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Scope {
+    /// A body of a script, a function or a class static block, where
+    /// function declarations are `var`-scoped.
+    TopLevel,
+    /// A block, a `switch` case block or a module body.
+    Block,
+}
+
+/// Statements that `break` and `continue` can target from the statement
+/// being walked, without crossing a function boundary.
+#[derive(Clone, Debug, Default)]
+struct JumpTargets {
+    /// Labels of enclosing statements, each with whether it labels an
+    /// iteration statement.
+    labels: Vec<(String, bool)>,
+    /// Whether there is an enclosing iteration statement.
+    in_iteration: bool,
+    /// Whether there is an enclosing iteration or `switch` statement.
+    in_breakable: bool,
+}
+
+/************************************************
+ *
+ * Declared names
+ *
+ ************************************************/
+
+/// `LexicallyDeclaredNames` of a statement list, or
+/// `TopLevelLexicallyDeclaredNames` for [`Scope::TopLevel`].
+fn lexically_declared_names<'tree>(
+    statements: impl IntoIterator<Item = &'tree StatementListItem>,
+    scope: Scope
+) -> Vec<&'tree BindingIdentifier> {
+    statements
+        .into_iter()
+        .flat_map(|item| match scope {
+            Scope::TopLevel => item.top_level_lexically_declared_names(),
+            Scope::Block => item.lexically_declared_names(),
+        })
+        .collect()
+}
+
+/// `VarDeclaredNames` of a statement list, or `TopLevelVarDeclaredNames`
+/// for [`Scope::TopLevel`].
+fn var_declared_names<'tree>(
+    statements: impl IntoIterator<Item = &'tree StatementListItem>,
+    scope: Scope
+) -> Vec<&'tree BindingIdentifier> {
+    statements
+        .into_iter()
+        .flat_map(|item| match scope {
+            Scope::TopLevel => item.top_level_var_declared_names(),
+            Scope::Block => item.var_declared_names(),
+        })
+        .collect()
+}
+
+/// A plain `FunctionDeclaration`, which is not a generator or an async
+/// function.
+const fn is_plain_function(function: &Function) -> bool {
+    !function.is_generator && !function.is_async
+}
+
+/************************************************
+ *
+ * Tree walk
+ *
+ ************************************************/
+
+struct Checker<'src, 'diagnostics> {
+    source: &'src str,
+    /// Whether the code being walked is strict mode code.
+    is_strict: bool,
+    jump_targets: JumpTargets,
+    diagnostics: &'diagnostics mut Diagnostics,
+}
+
+impl Checker<'_, '_> {
+    fn report(&mut self, range: Range<usize>, message: String) {
+        self.diagnostics.report(SourceCodeError::new(self.source, range, ErrorCode::EarlyError, message));
+    }
+
+    /// Report a name declared twice, pointing to its first declaration too.
+    fn report_redeclaration(&mut self, first: &BindingIdentifier, second: &BindingIdentifier) {
+        let (first, second) = if first.range.start <= second.range.start { (first, second) } else { (second, first) };
+        let mut error = SourceCodeError::new(
+            self.source,
+            second.range.clone(),
+            ErrorCode::EarlyError,
+            format!("`{}` is already declared", second.name),
+        );
+        error.labels.push(Label { range: first.range.clone(), message: "first declared here".to_owned() });
+        self.diagnostics.report(error);
+    }
+
+    /// Walk code with a given strictness.
+    fn with_strictness(&mut self, is_strict: bool, walk: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.is_strict, is_strict);
+        walk(self);
+        self.is_strict = outer;
+    }
+
+    /// Walk code with given targets of `break` and `continue`.
+    fn with_jump_targets(&mut self, jump_targets: JumpTargets, walk: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.jump_targets, jump_targets);
+        walk(self);
+        self.jump_targets = outer;
+    }
+
+    /// Walk a body of an iteration statement.
+    fn loop_body(&mut self, body: &Statement) {
+        let jump_targets = JumpTargets { in_iteration: true, in_breakable: true, ..self.jump_targets.clone() };
+        self.with_jump_targets(jump_targets, |checker| checker.statement(body));
+    }
+
+    /// Walk a statement list checking its declarations against each other
+    /// and against names bound around it, like parameters of a function.
+    ///
+    /// > It is a Syntax Error if the LexicallyDeclaredNames of StatementList
+    /// > contains any duplicate entries.
+    /// > It is a Syntax Error if any element of the LexicallyDeclaredNames
+    /// > of StatementList also occurs in the VarDeclaredNames of
+    /// > StatementList.
+    fn statement_list<'tree>(
+        &mut self,
+        statements: impl IntoIterator<Item = &'tree StatementListItem> + Clone,
+        scope: Scope,
+        outer_names: &[&BindingIdentifier]
+    ) {
+        let lexical_names = lexically_declared_names(statements.clone(), scope);
+        for (index, name) in lexical_names.iter().enumerate() {
+            let Some(first) = lexical_names[..index].iter().find(|first| first.name == name.name) else {
+                continue;
+            };
+            // Annex B allows duplicate plain function declarations in
+            // blocks of non-strict code, see
+            // <https://262.ecma-international.org/14.0/#sec-block-duplicates-allowed-static-semantics>.
+            let functions_only = [first, name].iter().all(|name| is_plain_function_name(statements.clone(), name));
+            if !(cfg!(feature = "annex-b") && !self.is_strict && scope == Scope::Block && functions_only) {
+                self.report_redeclaration(first, name);
+            }
+        }
+        let var_names = var_declared_names(statements.clone(), scope);
+        for (index, name) in lexical_names.iter().enumerate() {
+            // A conflict with a lexical redeclaration is reported above.
+            if lexical_names[..index].iter().any(|first| first.name == name.name) {
+                continue;
+            }
+            if let Some(var_name) = var_names.iter().find(|var_name| var_name.name == name.name) {
+                self.report_redeclaration(name, var_name);
+            }
+        }
+        for name in outer_names {
+            if let Some(lexical_name) = lexical_names.iter().find(|lexical_name| lexical_name.name == name.name) {
+                self.report_redeclaration(name, lexical_name);
+            }
+        }
+        for statement in statements {
+            self.statement_list_item(statement);
+        }
+    }
+
+    fn statement_list_item(&mut self, item: &StatementListItem) {
+        match item {
+            StatementListItem::Statement(statement) => self.statement(statement),
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => self.function(function),
+            StatementListItem::Declaration(Declaration::ClassDeclaration(class)) => self.class(class),
+            StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) => {
+                self.lexical_declaration(list);
+            },
+        }
+    }
+
+    /// Walk `let`, `const` or `using` declarations.
+    ///
+    /// > It is a Syntax Error if the BoundNames of BindingList contains
+    /// > "let".
+    fn lexical_declaration(&mut self, list: &VariableDeclarationList) {
+        for name in list.bound_names() {
+            if name.name == "let" {
+                self.report(name.range.clone(), "`let` cannot be a lexically bound name".to_owned());
+            }
+        }
+        self.variable_declaration_list(list);
+    }
+
+    fn variable_declaration_list(&mut self, list: &VariableDeclarationList) {
+        for declaration in &list.declarations {
+            self.binding(&declaration.target);
+            if let Some(initializer) = &declaration.initializer {
+                self.expression(initializer);
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.statement_list(&block.statements, Scope::Block, &[]);
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::BlockStatement(block) => self.block(block),
+                Statement::VariableStatement(list) => self.variable_declaration_list(list),
+                Statement::EmptyStatement(_) | Statement::DebuggerStatement(_) => {},
+                Statement::ContinueStatement(statement) => self.continue_statement(statement),
+                Statement::BreakStatement(statement) => self.break_statement(statement),
+                Statement::ExpressionStatement(expression, _) => self.expression(expression),
+                Statement::IfStatement(statement) => {
+                    self.expression(&statement.condition);
+                    self.statement(&statement.consequent);
+                    if let Some(alternative) = &statement.alternative {
+                        self.statement(alternative);
+                    }
+                },
+                Statement::DoWhileStatement(statement) => {
+                    self.loop_body(&statement.body);
+                    self.expression(&statement.condition);
+                },
+                Statement::WhileStatement(statement) => {
+                    self.expression(&statement.condition);
+                    self.loop_body(&statement.body);
+                },
+                Statement::ForStatement(statement) => {
+                    match &statement.init {
+                        Some(ForInit::VariableDeclarationList(list)) if list.kind != VariableKind::Var => {
+                            let names = list.bound_names();
+                            self.loop_declaration(&names, &statement.body);
+                            self.variable_declaration_list(list);
+                        },
+                        Some(ForInit::VariableDeclarationList(list)) => self.variable_declaration_list(list),
+                        Some(ForInit::Expression(expression)) => self.expression(expression),
+                        None => {},
+                    }
+                    if let Some(condition) = &statement.condition {
+                        self.expression(condition);
+                    }
+                    if let Some(update) = &statement.update {
+                        self.expression(update);
+                    }
+                    self.loop_body(&statement.body);
+                },
+                Statement::ForInStatement(statement) | Statement::ForOfStatement(statement) => {
+                    match &statement.target {
+                        ForTarget::Expression(target) => {
+                            self.assignment_target(target, true);
+                            self.expression(target);
+                        },
+                        ForTarget::ForDeclaration { kind: VariableKind::Var, binding, .. } => self.binding(binding),
+                        ForTarget::ForDeclaration { binding, .. } => {
+                            self.loop_declaration(&binding.bound_names(), &statement.body);
+                            self.binding(binding);
+                        },
+                    }
+                    self.expression(&statement.source);
+                    self.loop_body(&statement.body);
+                },
+                Statement::ReturnStatement(statement) => {
+                    if let Some(argument) = &statement.argument {
+                        self.expression(argument);
+                    }
+                },
+                Statement::WithStatement(statement) => {
+                    self.expression(&statement.object);
+                    self.statement(&statement.body);
+                },
+                Statement::SwitchStatement(statement) => {
+                    self.expression(&statement.discriminant);
+                    for case in &statement.cases {
+                        if let Some(test) = &case.test {
+                            self.expression(test);
+                        }
+                    }
+                    let jump_targets = JumpTargets { in_breakable: true, ..self.jump_targets.clone() };
+                    self.with_jump_targets(jump_targets, |checker| {
+                        // Clauses share a single `CaseBlock` scope.
+                        let statements = statement.cases.iter().flat_map(|case| &case.statements);
+                        checker.statement_list(statements, Scope::Block, &[]);
+                    });
+                },
+                Statement::LabelledStatement(statement) => self.labelled_statement(statement),
+                Statement::ThrowStatement(statement) => self.expression(&statement.argument),
+                Statement::TryStatement(statement) => {
+                    self.block(&statement.block);
+                    if let Some(handler) = &statement.handler {
+                        self.catch_clause(handler);
+                    }
+                    if let Some(finalizer) = &statement.finalizer {
+                        self.block(finalizer);
+                    }
+                },
+            }
+        });
+    }
+
+    /// Walk a labelled statement.
+    ///
+    /// > It is a Syntax Error if ContainsDuplicateLabels of StatementList
+    /// > with argument « » is true.
+    fn labelled_statement(&mut self, statement: &LabelledStatement) {
+        if self.jump_targets.labels.iter().any(|(label, _)| *label == statement.label) {
+            let message = format!("label `{}` is already declared by an enclosing statement", statement.label);
+            self.report(statement.range.clone(), message);
+        }
+        // A label of a label chain ending with a loop targets the loop.
+        let mut item = &*statement.body;
+        while let LabelledItem::Statement(Statement::LabelledStatement(nested)) = item {
+            item = &nested.body;
+        }
+        let is_iteration = matches!(
+            item,
+            LabelledItem::Statement(
+                Statement::DoWhileStatement(_)
+                    | Statement::WhileStatement(_)
+                    | Statement::ForStatement(_)
+                    | Statement::ForInStatement(_)
+                    | Statement::ForOfStatement(_)
+            )
+        );
+        let mut jump_targets = self.jump_targets.clone();
+        jump_targets.labels.push((statement.label.clone(), is_iteration));
+        self.with_jump_targets(jump_targets, |checker| match &*statement.body {
+            LabelledItem::Statement(statement) => checker.statement(statement),
+            LabelledItem::FunctionDeclaration(function) => checker.function(function),
+        });
+    }
+
+    /// > It is a Syntax Error if ContainsUndefinedBreakTarget of
+    /// > StatementList with argument « » is true.
+    /// > It is a Syntax Error if this BreakStatement is not nested, directly
+    /// > or indirectly (but not crossing function or static initialization
+    /// > block boundaries), within an IterationStatement or
+    /// > a SwitchStatement.
+    fn break_statement(&mut self, statement: &BreakStatement) {
+        match &statement.label {
+            Some(label) if !self.jump_targets.labels.iter().any(|(target, _)| target == label) => {
+                self.report(statement.range.clone(), format!("label `{label}` is not declared"));
+            },
+            None if !self.jump_targets.in_breakable => {
+                let message = "`break` must be inside a loop or a `switch` statement".to_owned();
+                self.report(statement.range.clone(), message);
+            },
+            _ => {},
+        }
+    }
+
+    /// > It is a Syntax Error if ContainsUndefinedContinueTarget of
+    /// > StatementList with arguments « » and « » is true.
+    /// > It is a Syntax Error if this ContinueStatement is not nested,
+    /// > directly or indirectly (but not crossing function or static
+    /// > initialization block boundaries), within an IterationStatement.
+    fn continue_statement(&mut self, statement: &ContinueStatement) {
+        if !self.jump_targets.in_iteration {
+            self.report(statement.range.clone(), "`continue` must be inside a loop".to_owned());
+            return;
+        }
+        let Some(label) = &statement.label else {
+            return;
+        };
+        match self.jump_targets.labels.iter().find(|(target, _)| target == label) {
+            None => self.report(statement.range.clone(), format!("label `{label}` is not declared")),
+            Some((_, false)) => {
+                self.report(statement.range.clone(), format!("label `{label}` does not denote a loop"));
+            },
+            Some((_, true)) => {},
+        }
+    }
+
+    /// Check lexical bindings of a loop head against each other and against
+    /// `var` declarations of its body.
+    ///
+    /// > It is a Syntax Error if the BoundNames of ForDeclaration contains
+    /// > "let".
+    /// > It is a Syntax Error if any element of the BoundNames of
+    /// > ForDeclaration also occurs in the VarDeclaredNames of Statement.
+    /// > It is a Syntax Error if the BoundNames of ForDeclaration contains
+    /// > any duplicate entries.
+    fn loop_declaration(&mut self, names: &[&BindingIdentifier], body: &Statement) {
+        for (index, name) in names.iter().enumerate() {
+            if name.name == "let" {
+                self.report(name.range.clone(), "`let` cannot be a lexically bound name".to_owned());
+            }
+            if let Some(first) = names[..index].iter().find(|first| first.name == name.name) {
+                self.report_redeclaration(first, name);
+            }
+        }
+        let var_names = body.var_declared_names();
+        for name in names {
+            if let Some(var_name) = var_names.iter().find(|var_name| var_name.name == name.name) {
+                self.report_redeclaration(name, var_name);
+            }
+        }
+    }
+
+    /// Walk <https://262.ecma-international.org/14.0/#prod-Catch>.
+    ///
+    /// > It is a Syntax Error if BoundNames of CatchParameter contains any
+    /// > duplicate elements.
+    /// > It is a Syntax Error if any element of the BoundNames of
+    /// > CatchParameter also occurs in the LexicallyDeclaredNames of Block.
+    /// > It is a Syntax Error if any element of the BoundNames of
+    /// > CatchParameter also occurs in the VarDeclaredNames of Block.
+    ///
+    /// Annex B allows `var` to redeclare a single identifier parameter
+    /// unless it is bound by `for`-`of`, see
+    /// <https://262.ecma-international.org/14.0/#sec-variablestatements-in-catch-blocks>.
+    fn catch_clause(&mut self, handler: &CatchClause) {
+        let Some(parameter) = &handler.parameter else {
+            self.block(&handler.body);
+            return;
+        };
+        self.binding(parameter);
+        let names = parameter.bound_names();
+        for (index, name) in names.iter().enumerate() {
+            if let Some(first) = names[..index].iter().find(|first| first.name == name.name) {
+                self.report_redeclaration(first, name);
+            }
+        }
+        let for_of_only = cfg!(feature = "annex-b") && matches!(parameter, Binding::BindingIdentifier(_));
+        let mut var_names = Vec::new();
+        for item in &handler.body.statements {
+            item.collect_var_declared_names(for_of_only, &mut var_names);
+        }
+        for name in &names {
+            if let Some(var_name) = var_names.iter().find(|var_name| var_name.name == name.name) {
+                self.report_redeclaration(name, var_name);
+            }
+        }
+        self.statement_list(&handler.body.statements, Scope::Block, &names);
+    }
+
+    fn binding(&mut self, binding: &Binding) {
+        crate::grow_stack(|| {
+            match binding {
+                Binding::BindingIdentifier(_) => {},
+                Binding::ObjectBindingPattern(pattern) => {
+                    for property in &pattern.properties {
+                        match property {
+                            BindingProperty::SingleNameBinding { initializer, .. } => {
+                                if let Some(initializer) = initializer {
+                                    self.expression(initializer);
+                                }
+                            },
+                            BindingProperty::Property { name, value } => {
+                                self.property_name(name);
+                                self.binding_element(value);
+                            },
+                        }
+                    }
+                },
+                Binding::ArrayBindingPattern(pattern) => {
+                    for element in pattern.elements.iter().flatten() {
+                        self.binding_element(element);
+                    }
+                    if let Some(rest) = &pattern.rest {
+                        self.binding(rest);
+                    }
+                },
+            }
+        });
+    }
+
+    fn binding_element(&mut self, element: &BindingElement) {
+        self.binding(&element.target);
+        if let Some(initializer) = &element.initializer {
+            self.expression(initializer);
+        }
+    }
+
+    /// Walk parameters and a body of a function-like node.
+    ///
+    /// > It is a Syntax Error if any element of the BoundNames of
+    /// > FormalParameters also occurs in the LexicallyDeclaredNames of
+    /// > FunctionBody.
+    fn function_like(&mut self, parameters: &FormalParameters, body: &[StatementListItem], is_strict: bool) {
+        // Jumps never leave a function.
+        self.with_jump_targets(JumpTargets::default(), |checker| {
+            checker.with_strictness(is_strict, |checker| {
+                for parameter in &parameters.parameters {
+                    checker.binding_element(parameter);
+                }
+                if let Some(rest) = &parameters.rest {
+                    checker.binding(rest);
+                }
+                checker.statement_list(body, Scope::TopLevel, &parameters.bound_names());
+            });
+        });
+    }
+
+    fn function(&mut self, function: &Function) {
+        self.function_like(&function.parameters, &function.body, function.is_strict);
+    }
+
+    fn method_definition(&mut self, method: &MethodDefinition) {
+        if let ClassElementName::PropertyName(name) = &method.name {
+            self.property_name(name);
+        }
+        self.function_like(&method.parameters, &method.body, method.is_strict);
+    }
+
+    /// Walk a class; all of it is strict mode code.
+    fn class(&mut self, class: &Class) {
+        self.with_strictness(true, |checker| {
+            for decorator in &class.decorators {
+                checker.expression(&decorator.expression);
+            }
+            if let Some(heritage) = &class.heritage {
+                checker.expression(heritage);
+            }
+            for element in &class.elements {
+                match element {
+                    ClassElement::MethodDefinition { decorators, method, .. } => {
+                        for decorator in decorators {
+                            checker.expression(&decorator.expression);
+                        }
+                        checker.method_definition(method);
+                    },
+                    ClassElement::FieldDefinition { decorators, name, initializer, .. } => {
+                        for decorator in decorators {
+                            checker.expression(&decorator.expression);
+                        }
+                        if let ClassElementName::PropertyName(name) = name {
+                            checker.property_name(name);
+                        }
+                        if let Some(initializer) = initializer {
+                            checker.expression(initializer);
+                        }
+                    },
+                    ClassElement::ClassStaticBlock(statements, _) => {
+                        checker.with_jump_targets(JumpTargets::default(), |checker| {
+                            checker.statement_list(statements.iter(), Scope::TopLevel, &[]);
+                        });
+                    },
+                }
+            }
+        });
+    }
+
+    fn property_name(&mut self, name: &PropertyName) {
+        if let PropertyName::ComputedPropertyName(expression, _) = name {
+            self.expression(expression);
+        }
+    }
+
+    /// Check an expression assigned to, by `=` if `allows_pattern`.
+    ///
+    /// > If LeftHandSideExpression is neither an ObjectLiteral nor an
+    /// > ArrayLiteral, it is a Syntax Error if AssignmentTargetType of
+    /// > LeftHandSideExpression is not simple.
+    fn assignment_target(&mut self, target: &Expression, allows_pattern: bool) {
+        match target {
+            Expression::ObjectLiteral(object) if allows_pattern => {
+                for (index, property) in object.properties.iter().enumerate() {
+                    match property {
+                        PropertyDefinition::IdentifierReference(reference)
+                        | PropertyDefinition::CoverInitializedName { name: reference, .. } => {
+                            self.identifier_target(reference);
+                        },
+                        PropertyDefinition::Property { value, .. } => self.destructuring_target(value),
+                        // > It is a Syntax Error if DestructuringAssignmentTarget
+                        // > is an ArrayLiteral or an ObjectLiteral.
+                        PropertyDefinition::SpreadProperty(target, range) => {
+                            if index + 1 < object.properties.len() {
+                                self.report(range.clone(), "rest property must be last in a pattern".to_owned());
+                            }
+                            self.assignment_target(target, false);
+                        },
+                        PropertyDefinition::MethodDefinition(method) => {
+                            self.report(method.range.clone(), "invalid destructuring assignment target".to_owned());
+                        },
+                    }
+                }
+            },
+            Expression::ArrayLiteral(array) if allows_pattern => {
+                for (index, element) in array.elements.iter().enumerate() {
+                    match element {
+                        ArrayElement::Elision(_) => {},
+                        ArrayElement::AssignmentExpression(target) => self.destructuring_target(target),
+                        ArrayElement::SpreadElement(target, range) => {
+                            if index + 1 < array.elements.len() {
+                                self.report(range.clone(), "rest element must be last in a pattern".to_owned());
+                            }
+                            if matches!(target, Expression::AssignmentExpression(_)) {
+                                let message = "rest element cannot have an initializer".to_owned();
+                                self.report(target.range(), message);
+                            } else {
+                                self.assignment_target(target, true);
+                            }
+                        },
+                    }
+                }
+            },
+            Expression::ParenthesizedExpression(expression) => self.assignment_target(&expression.expression, false),
+            Expression::IdentifierReference(reference) => self.identifier_target(reference),
+            _ if target.assignment_target_type(self.is_strict) == AssignmentTargetType::Simple => {},
+            _ => self.report(target.range(), "invalid assignment target".to_owned()),
+        }
+    }
+
+    /// Check `DestructuringAssignmentTarget Initializer_opt` of
+    /// an assignment pattern.
+    fn destructuring_target(&mut self, target: &Expression) {
+        match target {
+            Expression::AssignmentExpression(assignment) if assignment.operator == AssignmentOperator::Assignment => {
+                self.assignment_target(&assignment.target, true);
+            },
+            _ => self.assignment_target(target, true),
+        }
+    }
+
+    /// > It is a Syntax Error if AssignmentTargetType of IdentifierReference
+    /// > is not simple.
+    fn identifier_target(&mut self, reference: &IdentifierReference) {
+        if reference.assignment_target_type(self.is_strict) == AssignmentTargetType::Invalid {
+            let message = format!("`{}` cannot be assigned to in strict mode code", reference.name);
+            self.report(reference.range.clone(), message);
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        crate::grow_stack(|| {
+            match expression {
+                Expression::This(_)
+                | Expression::IdentifierReference(_)
+                | Expression::Literal(..)
+                | Expression::RegularExpressionLiteral(..)
+                | Expression::MetaProperty(..) => {},
+                Expression::ArrayLiteral(array) => {
+                    for element in &array.elements {
+                        match element {
+                            ArrayElement::Elision(_) => {},
+                            ArrayElement::AssignmentExpression(element) | ArrayElement::SpreadElement(element, _) => {
+                                self.expression(element);
+                            },
+                        }
+                    }
+                },
+                Expression::ObjectLiteral(object) => {
+                    for property in &object.properties {
+                        match property {
+                            PropertyDefinition::IdentifierReference(_) => {},
+                            PropertyDefinition::Property { name, value } => {
+                                self.property_name(name);
+                                self.expression(value);
+                            },
+                            PropertyDefinition::MethodDefinition(method) => self.method_definition(method),
+                            PropertyDefinition::SpreadProperty(value, _) => self.expression(value),
+                            PropertyDefinition::CoverInitializedName { initializer, .. } => self.expression(initializer),
+                        }
+                    }
+                },
+                Expression::FunctionExpression(function) => self.function(function),
+                Expression::ClassExpression(class) => self.class(class),
+                Expression::TemplateLiteral(template) => {
+                    for substitution in &template.substitutions {
+                        self.expression(substitution);
+                    }
+                },
+                Expression::ParenthesizedExpression(expression) => self.expression(&expression.expression),
+                Expression::MemberExpression(member) => {
+                    self.expression(&member.object);
+                    if let MemberProperty::Expression(property) = &member.property {
+                        self.expression(property);
+                    }
+                },
+                Expression::SuperProperty(property) => {
+                    if let MemberProperty::Expression(property) = &property.property {
+                        self.expression(property);
+                    }
+                },
+                Expression::TaggedTemplate(tagged) => {
+                    self.expression(&tagged.tag);
+                    for substitution in &tagged.template.substitutions {
+                        self.expression(substitution);
+                    }
+                },
+                Expression::CallExpression(call) => {
+                    self.expression(&call.callee);
+                    for argument in &call.arguments {
+                        self.argument(argument);
+                    }
+                },
+                Expression::SuperCall(call) => {
+                    for argument in &call.arguments {
+                        self.argument(argument);
+                    }
+                },
+                Expression::ImportCall(call) => {
+                    self.expression(&call.specifier);
+                    if let Some(options) = &call.options {
+                        self.expression(options);
+                    }
+                },
+                Expression::OptionalExpression(chain) => self.expression(&chain.expression),
+                Expression::NewExpression(new) => {
+                    self.expression(&new.callee);
+                    for argument in new.arguments.iter().flatten() {
+                        self.argument(argument);
+                    }
+                },
+                Expression::UpdateExpression(update) => {
+                    // > It is an early Syntax Error if AssignmentTargetType of
+                    // > LeftHandSideExpression is not simple.
+                    self.assignment_target(&update.operand, false);
+                    self.expression(&update.operand);
+                },
+                Expression::UnaryExpression(unary) => self.expression(&unary.operand),
+                Expression::AwaitExpression(expression) => self.expression(&expression.argument),
+                Expression::BinaryExpression(binary) => {
+                    self.expression(&binary.left);
+                    self.expression(&binary.right);
+                },
+                Expression::PrivateInExpression(expression) => self.expression(&expression.object),
+                Expression::ConditionalExpression(conditional) => {
+                    self.expression(&conditional.condition);
+                    self.expression(&conditional.consequent);
+                    self.expression(&conditional.alternative);
+                },
+                Expression::YieldExpression(expression) => {
+                    if let Some(argument) = &expression.argument {
+                        self.expression(argument);
+                    }
+                },
+                Expression::ArrowFunction(function) => match &function.body {
+                    ConciseBody::Expression(body) => {
+                        self.function_like(&function.parameters, &[], function.is_strict);
+                        self.with_strictness(function.is_strict, |checker| checker.expression(body));
+                    },
+                    ConciseBody::FunctionBody(body, _) => self.function_like(&function.parameters, body, function.is_strict),
+                },
+                Expression::AssignmentExpression(assignment) => {
+                    self.assignment_target(&assignment.target, assignment.operator == AssignmentOperator::Assignment);
+                    self.expression(&assignment.target);
+                    self.expression(&assignment.value);
+                },
+                Expression::CommaExpression(comma) => {
+                    for expression in &comma.expressions {
+                        self.expression(expression);
+                    }
+                },
+            }
+        });
+    }
+
+    fn argument(&mut self, argument: &Argument) {
+        let (Argument::AssignmentExpression(expression) | Argument::SpreadElement(expression, _)) = argument;
+        self.expression(expression);
+    }
+}
+
+/// Whether a name is bound by a plain function declaration directly in
+/// a statement list.
+fn is_plain_function_name<'tree>(
+    statements: impl IntoIterator<Item = &'tree StatementListItem>,
+    name: &BindingIdentifier
+) -> bool {
+    statements.into_iter().any(|statement| match statement {
+        StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => {
+            is_plain_function(function) && function.name.as_ref().is_some_and(|function_name| function_name.range == name.range)
+        },
+        _ => false,
+    })
+}
@@ -0,0 +1,627 @@
+//! A single handle for a host to create and drive an ECMAScript world.
+//!
+//! [`Engine`] stands for an agent of
+//! <https://262.ecma-international.org/14.0/#sec-agents>: it owns
+//! a [`Realm`] with its intrinsics and global object, and the [`Context`]
+//! with the execution context stack that code of the realm runs on, as well
+//! as the map of modules loaded into the realm.
+
+use crate::grammar::Parser;
+use crate::interpreter::{self, ModuleRecord};
+use crate::runtime::{
+    charge_code,
+    Context,
+    ErrorKind,
+    ExecutionContext,
+    FromArguments,
+    Heap,
+    HeapScope,
+    HeapStatistics,
+    Intrinsics,
+    IntoJs,
+    JsError,
+    MemoryLimits,
+    Object,
+    PropertyDescriptor,
+    PropertyKey,
+    Realm,
+    Value,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+/// A host hook providing source text of modules, like
+/// <https://262.ecma-international.org/14.0/#sec-HostLoadImportedModule>.
+///
+/// It gets a specifier an `import` or `export ... from` requests, and
+/// a specifier of the requesting module.
+pub type ModuleLoader = Box<dyn FnMut(&str, &str) -> Result<String, JsError>>;
+
+/// Options of an [`Engine`] to create, starting from those of
+/// [`Engine::new`].
+///
+/// Limits keep a rogue script from taking all memory of the device:
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+/// use embedded_ecmascript::runtime::{ErrorKind, Value};
+///
+/// let mut engine = Engine::builder().allocation_budget(1000).build();
+/// let error = engine.evaluate_script("var list = null; while (true) list = { next: list };").unwrap_err();
+/// assert_eq!(error.kind, ErrorKind::MemoryLimit);
+///
+/// // Scripts may catch it, and the budget is renewed for each evaluation.
+/// let source = "try { for (;;) list = { next: list }; } catch (error) { error instanceof RangeError }";
+/// assert_eq!(engine.evaluate_script(source), Ok(Value::Boolean(true)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EngineBuilder {
+    memory_limits: MemoryLimits,
+    defers_function_bodies: bool,
+}
+
+impl EngineBuilder {
+    /// Limit how many objects and environments of the engine may be alive,
+    /// collecting garbage once they may go over it.
+    ///
+    /// The limit counts nodes of the heap of the engine rather than bytes,
+    /// whatever their properties take, and other engines of the thread
+    /// do not count. Intrinsics of the engine take a few hundred of them.
+    /// Collections are spaced out, so the heap may go over the limit by
+    /// a quarter of it before one notices.
+    #[must_use]
+    pub const fn max_object_count(mut self, count: usize) -> Self {
+        self.memory_limits.max_object_count = Some(count);
+        self
+    }
+
+    /// Limit how many objects and environments each evaluation of a script
+    /// or a module may create.
+    #[must_use]
+    pub const fn allocation_budget(mut self, count: usize) -> Self {
+        self.memory_limits.allocation_budget = Some(count);
+        self
+    }
+
+    /// Limit how many bytes of strings and properties each evaluation of
+    /// a script or a module may create, which object counts miss:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::ErrorKind;
+    ///
+    /// let mut engine = Engine::builder().byte_budget(1 << 20).build();
+    /// let error = engine.evaluate_script("var s = 'ab'; for (var i = 0; i < 30; i++) s = s + s;").unwrap_err();
+    /// assert_eq!(error.kind, ErrorKind::MemoryLimit);
+    /// ```
+    #[must_use]
+    pub const fn byte_budget(mut self, bytes: usize) -> Self {
+        self.memory_limits.byte_budget = Some(bytes);
+        self
+    }
+
+    /// Skip function bodies when parsing scripts and modules, and parse
+    /// each body on the first call of its function.
+    ///
+    /// Most functions of a large bundle never run, so it starts much
+    /// faster, but syntax errors in a body are thrown by the first call
+    /// rather than stop the whole script from running:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::{ErrorKind, Value};
+    ///
+    /// let mut engine = Engine::builder().defer_function_bodies().build();
+    /// let source = "function broken() { return 1 + ; } function square(x) { return x * x; } square(3)";
+    /// assert_eq!(engine.evaluate_script(source), Ok(Value::Number(9.0)));
+    /// let error = engine.evaluate_script("broken()").unwrap_err();
+    /// assert_eq!(error.kind, ErrorKind::SyntaxError);
+    /// ```
+    ///
+    /// Bodies starting with a directive or using private names are parsed
+    /// right away.
+    #[must_use]
+    pub const fn defer_function_bodies(mut self) -> Self {
+        self.defers_function_bodies = true;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Engine {
+        let mut engine = Engine::new();
+        engine.context.set_memory_limits(self.memory_limits);
+        engine.defers_function_bodies = self.defers_function_bodies;
+        engine
+    }
+}
+
+/// An agent with a single realm.
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+/// use embedded_ecmascript::runtime::{PropertyKey, Value};
+///
+/// let mut engine = Engine::new();
+/// let global = engine.global_object().clone();
+/// assert!(global.create_data_property("answer", 42.0));
+/// let answer = global.get(engine.context(), &PropertyKey::from("answer"), &Value::Undefined);
+/// assert_eq!(answer, Ok(Value::Number(42.0)));
+/// ```
+pub struct Engine {
+    context: Context,
+    realm: Realm,
+    /// Modules by their specifiers, each loaded once.
+    modules: HashMap<String, ModuleRecord>,
+    module_loader: Option<ModuleLoader>,
+    /// Whether function bodies are parsed on the first call.
+    defers_function_bodies: bool,
+}
+
+impl Engine {
+    /// <https://262.ecma-international.org/14.0/#sec-initializehostdefinedrealm>:
+    /// create a realm and make an execution context of it the running one.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut context = Context::new();
+        let realm = {
+            let _heap = context.heap().enter();
+            Realm::new()
+        };
+        context.push_execution_context(ExecutionContext::new(None, realm.clone()));
+        Self { context, realm, modules: HashMap::new(), module_loader: None, defers_function_bodies: false }
+    }
+
+    #[must_use]
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    /// The state abstract operations and functions of the engine run in.
+    pub const fn context(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
+    /// The heap objects and environments of the engine belong to, which
+    /// keeps values for the host with [`Heap::root`].
+    #[must_use]
+    pub const fn heap(&self) -> &Heap {
+        self.context.heap()
+    }
+
+    /// Make objects the host creates belong to the heap of the engine
+    /// until the scope ends.
+    fn enter_heap(&self) -> HeapScope {
+        self.context.heap().enter()
+    }
+
+    #[must_use]
+    pub const fn realm(&self) -> &Realm {
+        &self.realm
+    }
+
+    #[must_use]
+    pub fn intrinsics(&self) -> &Intrinsics {
+        self.realm.intrinsics()
+    }
+
+    #[must_use]
+    pub fn global_object(&self) -> &Object {
+        self.realm.global_object()
+    }
+
+    /// Free unreachable objects and environments that refer to each other,
+    /// which scripts create with closures and circular data.
+    ///
+    /// Collections happen only when the host calls it, so it controls when
+    /// the pause happens, and cover only objects of the engine. Objects the
+    /// host holds stay alive, and [`Heap::root`] keeps them explicitly:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.evaluate_script("(function () { var a = {}; a.self = a; })()").unwrap();
+    /// engine.evaluate_script("var b = {}; b.self = b;").unwrap();
+    /// assert!(engine.collect_garbage().collected > 0);
+    /// assert_eq!(engine.evaluate_script("b.self === b"), Ok(true.into()));
+    /// ```
+    pub fn collect_garbage(&mut self) -> HeapStatistics {
+        self.context.heap().collect_garbage()
+    }
+
+    /// Create a function object of the realm calling a Rust closure with
+    /// `this` and arguments, like built-in functions do.
+    ///
+    /// The function is not a constructor; `length` is how many arguments
+    /// it expects, which scripts may read.
+    #[must_use]
+    pub fn create_function(
+        &self,
+        name: &str,
+        length: usize,
+        function: impl Fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError> + 'static,
+    ) -> Object {
+        let _heap = self.enter_heap();
+        let prototype = self.intrinsics().function_prototype.clone();
+        Object::builtin_function(Some(prototype), name, length, function)
+    }
+
+    /// Expose a Rust function to scripts as a global function.
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::Value;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_function("twice", |context, arguments| {
+    ///     let value = arguments.first().unwrap_or(&Value::Undefined).to_number(context)?;
+    ///     Ok(Value::Number(value * 2.0))
+    /// }).unwrap();
+    /// assert_eq!(engine.evaluate_script("twice(21)"), Ok(Value::Number(42.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the global object has
+    /// a non-configurable property of the name, like `undefined`.
+    pub fn register_function(
+        &mut self,
+        name: &str,
+        function: impl Fn(&mut Context, &[Value]) -> Result<Value, JsError> + 'static,
+    ) -> Result<(), JsError> {
+        let global = self.global_object().clone();
+        self.define_method(&global, name, move |context, _, arguments| function(context, arguments))
+    }
+
+    /// Expose a Rust function taking and returning Rust types as a global
+    /// function, with [`FromArguments`] and [`IntoJs`] converting them.
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::Value;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_typed_function("repeat", |_, (text, count): (String, Option<u32>)| {
+    ///     Ok(text.repeat(count.unwrap_or(1) as usize))
+    /// }).unwrap();
+    /// assert_eq!(engine.evaluate_script("repeat('ab', 2) + repeat(1)"), Ok(Value::from("abab1")));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the global object has
+    /// a non-configurable property of the name, like `undefined`.
+    pub fn register_typed_function<A: FromArguments, R: IntoJs>(
+        &mut self,
+        name: &str,
+        function: impl Fn(&mut Context, A) -> Result<R, JsError> + 'static,
+    ) -> Result<(), JsError> {
+        self.register_function(name, move |context, arguments| {
+            let arguments = A::from_arguments(arguments, context)?;
+            Ok(function(context, arguments)?.into_js(context))
+        })
+    }
+
+    /// Attach a Rust function to an object as a method, which gets the
+    /// object, or another `this` value, first.
+    ///
+    /// Like methods of built-in objects, the property is writable,
+    /// configurable and not enumerable. State of a host object is usually
+    /// captured by the closure:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::{Object, Value};
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// let mut engine = Engine::new();
+    /// let speed = Rc::new(Cell::new(60.0));
+    /// let vehicle = Object::new(Some(engine.intrinsics().object_prototype.clone()));
+    /// let current = Rc::clone(&speed);
+    /// engine.define_method(&vehicle, "getSpeed", move |_, _, _| Ok(Value::Number(current.get()))).unwrap();
+    /// engine.global_object().create_data_property("vehicle", vehicle);
+    ///
+    /// speed.set(80.0);
+    /// assert_eq!(engine.evaluate_script("vehicle.getSpeed()"), Ok(Value::Number(80.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object does not accept
+    /// the property, being non-extensible or having a non-configurable one.
+    pub fn define_method(
+        &self,
+        object: &Object,
+        name: &str,
+        function: impl Fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError> + 'static,
+    ) -> Result<(), JsError> {
+        let method = self.create_function(name, 0, function);
+        let descriptor = PropertyDescriptor::data(Value::Object(method), true, false, true);
+        if !object.define_own_property(PropertyKey::from(name), descriptor) {
+            return Err(JsError::type_error(format!("cannot define {name}")));
+        }
+        Ok(())
+    }
+
+    /// Parse and run a script in the realm of the engine, getting its
+    /// completion value.
+    ///
+    /// Declarations of the script stay in the global environment, so later
+    /// scripts see them:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::Value;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.evaluate_script("function square(x) { return x * x; } let base = 3;").unwrap();
+    /// assert_eq!(engine.evaluate_script("square(base) + 1"), Ok(Value::Number(10.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `SyntaxError` [`JsError`] if the source
+    /// text is not a correct script, with a `MemoryLimit` one if it takes
+    /// more memory than limits of the engine allow, or with an exception the
+    /// script throws.
+    pub fn evaluate_script(&mut self, source: &str) -> Result<Value, JsError> {
+        let _heap = self.enter_heap();
+        self.context.reset_allocation_budget();
+        self.charge_source(source)?;
+        let script = self
+            .parser(Parser::new(source))
+            .parse_script()
+            .map_err(|error| JsError::new(ErrorKind::SyntaxError, error.message).at(&error.range))?;
+        let source = self.defers_function_bodies.then(|| Rc::from(source));
+        interpreter::evaluate_script(&mut self.context, &self.realm.clone(), &script, source)
+    }
+
+    /// Run a script like [`Engine::evaluate_script`] does, stopping it
+    /// once it starts more calls and loop iterations than `fuel`.
+    ///
+    /// Scripts cannot catch running out of fuel, so an infinite loop of
+    /// user code cannot hang the host thread:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::{ErrorKind, Value};
+    ///
+    /// let mut engine = Engine::new();
+    /// let error = engine.evaluate_with_fuel("for (;;) try { while (true); } catch {}", 10_000).unwrap_err();
+    /// assert_eq!(error.kind, ErrorKind::OutOfFuel);
+    /// assert_eq!(engine.evaluate_with_fuel("[1, 2].length", 0), Ok(Value::Number(2.0)));
+    /// ```
+    ///
+    /// The script is not resumed later; bindings it has created stay in
+    /// the global environment.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an `OutOfFuel` [`JsError`] if the fuel is
+    /// burnt out, or with an error [`Engine::evaluate_script`] returns.
+    pub fn evaluate_with_fuel(&mut self, source: &str, fuel: u64) -> Result<Value, JsError> {
+        let previous = self.context.fuel();
+        self.context.set_fuel(Some(fuel));
+        let result = self.evaluate_script(source);
+        self.context.set_fuel(previous);
+        result
+    }
+
+    /// Run promise jobs enqueued by scripts and modules, and by jobs
+    /// themselves, until none is left.
+    ///
+    /// Jobs never run on their own, so the host decides when promise
+    /// reactions and continuations of async functions happen, like at the
+    /// end of each evaluation or each tick of an event loop:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::{Object, Value};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.register_typed_function("later", |context, (callback,): (Object,)| {
+    ///     let realm = context.current_realm().cloned();
+    ///     context.enqueue_promise_job(Box::new(move |context| callback.call(context, &Value::Undefined, &[]).map(drop)), realm);
+    ///     Ok(())
+    /// }).unwrap();
+    /// engine.evaluate_script("var log = ''; later(function () { log += 'b'; }); log += 'a';").unwrap();
+    /// assert_eq!(engine.evaluate_script("log"), Ok(Value::from("a")));
+    /// engine.run_jobs().unwrap();
+    /// assert_eq!(engine.evaluate_script("log"), Ok(Value::from("ab")));
+    /// ```
+    ///
+    /// Jobs share the allocation budget of a single evaluation.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the error of the first job that throws,
+    /// leaving later jobs queued for the next call.
+    pub fn run_jobs(&mut self) -> Result<(), JsError> {
+        let _heap = self.enter_heap();
+        self.context.reset_allocation_budget();
+        self.context.run_jobs()
+    }
+
+    /// Poll a host hook as calls and loop iterations of scripts and modules
+    /// start, stopping them with an `Interrupted` [`JsError`] once it
+    /// breaks.
+    ///
+    /// Scripts cannot catch the error, so a watchdog of another thread can
+    /// abort a script that runs too long:
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::ErrorKind;
+    /// use std::ops::ControlFlow;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mut engine = Engine::new();
+    /// let cancelled = Arc::new(AtomicBool::new(false));
+    /// let flag = Arc::clone(&cancelled);
+    /// engine.set_interrupt_handler(move || {
+    ///     if flag.load(Ordering::Relaxed) { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    /// });
+    /// let watchdog = std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_millis(10));
+    ///     cancelled.store(true, Ordering::Relaxed);
+    /// });
+    /// let error = engine.evaluate_script("for (;;) try { while (true); } catch {}").unwrap_err();
+    /// assert_eq!(error.kind, ErrorKind::Interrupted);
+    /// watchdog.join().unwrap();
+    /// ```
+    ///
+    /// The hook stays until [`Engine::clear_interrupt_handler`], so it has
+    /// to continue again for later evaluations to run.
+    pub fn set_interrupt_handler(&mut self, handler: impl Fn() -> ControlFlow<()> + 'static) {
+        self.context.set_interrupt_handler(Some(Box::new(handler)));
+    }
+
+    pub fn clear_interrupt_handler(&mut self) {
+        self.context.set_interrupt_handler(None);
+    }
+
+    /// Set how modules requested by `import` and `export ... from` are
+    /// loaded.
+    ///
+    /// Specifiers are not resolved against the requesting module, so one
+    /// specifier names one module across the engine. A loader may return
+    /// `Err` for an unknown one.
+    pub fn set_module_loader(&mut self, loader: impl FnMut(&str, &str) -> Result<String, JsError> + 'static) {
+        self.module_loader = Some(Box::new(loader));
+    }
+
+    /// Parse a module, load modules it depends on, link and evaluate them
+    /// all, getting the module namespace object of the module.
+    ///
+    /// Imported bindings are live: they see later assignments to exported
+    /// variables, even across cycles of modules importing each other.
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::{JsError, PropertyKey, Value};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_module_loader(|specifier, _| match specifier {
+    ///     "counter" => Ok("export let count = 0; export function increment() { count++; }".to_owned()),
+    ///     _ => Err(JsError::type_error(format!("unknown module {specifier}"))),
+    /// });
+    /// let source = "import { count, increment } from 'counter'; increment(); export const seen = count;";
+    /// let namespace = engine.evaluate_module("main", source).unwrap();
+    /// let seen = namespace.get(engine.context(), &PropertyKey::from("seen"), &Value::Undefined);
+    /// assert_eq!(seen, Ok(Value::Number(1.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `SyntaxError` [`JsError`] if a source text
+    /// is not a correct module or an import cannot be resolved, with
+    /// a `TypeError` if the specifier is taken or a module cannot be
+    /// loaded, with an error the loader returns, with a `MemoryLimit` one if
+    /// modules take more memory than limits of the engine allow, or with an
+    /// exception a module throws.
+    ///
+    /// Modules that fail to load or link are dropped, so the specifier may
+    /// be evaluated again. A module that throws stays in the module map,
+    /// and evaluating it again gives the same exception.
+    pub fn evaluate_module(&mut self, specifier: &str, source: &str) -> Result<Object, JsError> {
+        let _heap = self.enter_heap();
+        self.context.reset_allocation_budget();
+        if let Some(module) = self.modules.get(specifier) {
+            return Err(module
+                .evaluation_error()
+                .unwrap_or_else(|| JsError::type_error(format!("module {specifier} is already loaded"))));
+        }
+        let module = self.parse_module(specifier, source)?;
+        let mut added = vec![specifier.to_owned()];
+        let result = self.load_requested_modules(&module, &mut added).and_then(|()| module.link(&mut self.context));
+        if let Err(error) = result {
+            // Modules of a failed load are dropped, so nothing refers to
+            // them when they are loaded again.
+            for specifier in added {
+                self.modules.remove(&specifier);
+            }
+            return Err(error);
+        }
+        module.evaluate(&mut self.context)?;
+        Ok(module.namespace())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-parsemodule> adding
+    /// the module to the module map.
+    fn parse_module(&mut self, specifier: &str, source: &str) -> Result<ModuleRecord, JsError> {
+        self.charge_source(source)?;
+        let module = self
+            .parser(Parser::new_module(source))
+            .parse_module()
+            .map_err(|error| JsError::new(ErrorKind::SyntaxError, error.message).at(&error.range))?;
+        let source = self.defers_function_bodies.then(|| Rc::from(source));
+        let record = ModuleRecord::new(specifier, &self.realm, module, source);
+        self.modules.insert(specifier.to_owned(), record.clone());
+        Ok(record)
+    }
+
+    /// Set up a parser of source text the engine runs.
+    const fn parser<'src>(&self, parser: Parser<'src>) -> Parser<'src> {
+        if self.defers_function_bodies {
+            parser.with_deferred_function_bodies()
+        } else {
+            parser
+        }
+    }
+
+    /// Count a tree of a source text about to be parsed against the byte
+    /// budget, failing before the tree is built if it does not fit.
+    fn charge_source(&self, source: &str) -> Result<(), JsError> {
+        charge_code(source.len());
+        self.context.check_byte_budget()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-LoadRequestedModules>
+    /// of a whole module graph, noting specifiers of modules it adds.
+    fn load_requested_modules(&mut self, root: &ModuleRecord, added: &mut Vec<String>) -> Result<(), JsError> {
+        let mut pending = vec![root.clone()];
+        while let Some(module) = pending.pop() {
+            for specifier in module.requested_modules() {
+                if module.loaded_module(specifier).is_some() {
+                    continue;
+                }
+                let required = if let Some(required) = self.modules.get(specifier) {
+                    required.clone()
+                } else {
+                    let Some(loader) = &mut self.module_loader else {
+                        return Err(JsError::type_error(format!("cannot load module {specifier} without a module loader")));
+                    };
+                    let source = loader(specifier, module.specifier())?;
+                    let required = self.parse_module(specifier, &source)?;
+                    added.push(specifier.to_owned());
+                    pending.push(required.clone());
+                    required
+                };
+                module.set_loaded_module(specifier, required);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Engine {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Engine")
+            .field("context", &self.context)
+            .field("realm", &self.realm)
+            .field("modules", &self.modules)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
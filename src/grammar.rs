@@ -53,205 +53,129 @@
 //! > prior permission. Title to copyright in this work will at all times remain
 //! > with copyright holders.
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum Symbol {
-    // 11.1 Source Text
-    SourceCharacter(char),
-
-    // 14 ECMAScript Language: Statements and Declarations
-    Statement {
-        uses_yield: Option<usize>,
-        uses_await: Option<usize>,
-        uses_return: Option<usize>
-    },
-    // 14.2 Block
-    StatementList {
-        uses_yield: Option<usize>,
-        uses_await: Option<usize>,
-        uses_return: Option<usize>
-    },
-    StatementListItem {
-        uses_yield: Option<usize>,
-        uses_await: Option<usize>,
-        uses_return: Option<usize>
-    },
-    // 14.4 Empty Statement
-    EmptyStatement,
+use crate::lexical_grammar::{get_next_token_with_options, LexerOptions, TokenKind, Tokenizer};
+use crate::{Diagnostics, Severity, SourceCodeError};
+use std::ops::Range;
+
+mod expressions;
+mod functions;
+mod modules;
+mod parser;
+mod scripts;
+mod statements;
+
+pub use expressions::*;
+pub use functions::*;
+pub use modules::*;
+pub use parser::{
+    Parser,
+    ParserOptions,
+    Proposals,
+    Trivia,
+    TriviaAttachment,
+    TriviaPolicy,
+    DEFAULT_MAX_NESTING_DEPTH,
+};
+pub use scripts::*;
+pub use statements::*;
+
+/// Recognize a next token that is not white space, a line terminator or
+/// a comment, starting at a given offset. A code point that does not start
+/// a token is returned as `TokenKind::Unknown`.
+fn next_significant_token(
+    source: &str,
+    mut offset: usize,
+    tokenizer: &mut Tokenizer
+) -> Result<Option<(TokenKind, Range<usize>)>, SourceCodeError> {
+    let options = LexerOptions { recover_errors: true, ..LexerOptions::default() };
+    while offset < source.len() {
+        let (token, range) = get_next_token_with_options(source, offset, tokenizer.goal(), &options)?;
+        tokenizer.observe(&token);
+        match token.kind() {
+            TokenKind::WhiteSpace
+            | TokenKind::LineTerminator
+            | TokenKind::MultiLineComment
+            | TokenKind::SingleLineComment
+            | TokenKind::HashbangComment => offset = range.end,
+            kind => return Ok(Some((kind, range))),
+        }
+    }
+    Ok(None)
+}
 
-    // 16.1 Scripts
+/// A goal symbol to parse a source text with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SourceType {
+    /// <https://262.ecma-international.org/14.0/#prod-Script>, like
+    /// a `.js` file loaded by a `<script>` tag.
+    #[default]
     Script,
-    ScriptBody,
+    /// <https://262.ecma-international.org/14.0/#prod-Module>, like
+    /// a `.mjs` file.
+    Module,
+    /// A module if the text has `import` or `export` declarations, and
+    /// a script otherwise; see [`detect_source_type`].
+    Auto,
 }
 
-#[derive(Debug)]
-struct TokenStackDiff {
-    pop: usize,
-    push: Symbol
+impl SourceType {
+    /// Whether a source text is to be parsed as a module, detecting it
+    /// for [`SourceType::Auto`].
+    #[must_use]
+    pub fn is_module(self, source: &str) -> bool {
+        match self {
+            Self::Script => false,
+            Self::Module => true,
+            Self::Auto => detect_source_type(source, self) == Self::Module,
+        }
+    }
 }
 
-fn reduce_once(tokens: &Vec<Symbol>, _as_module: bool) -> Option<TokenStackDiff> {
-    match tokens.as_slice() {
-        /************************************************
-         *
-         * 14.2 Block
-         *
-         ************************************************/
-
-        // Serialize <https://262.ecma-international.org/14.0/#prod-StatementList>.
-        //
-        // ```plain
-        // StatementList[Yield, Await, Return] :
-        //     StatementListItem[?Yield, ?Await, ?Return]
-        //     StatementList[?Yield, ?Await, ?Return] StatementListItem[?Yield, ?Await, ?Return]
-        // ```
-        //
-        // Right side of the productions ends in the same way so put
-        // the longest match first.
-        [
-            ..,
-            Symbol::StatementList {
-                uses_yield: list_uses_yield,
-                uses_await: list_uses_await,
-                uses_return: list_uses_return
-            },
-            Symbol::StatementListItem {
-                uses_yield: item_uses_yield,
-                uses_await: item_uses_await,
-                uses_return: item_uses_return
-            }
-        ] => Some(TokenStackDiff {
-            pop: 2,
-            push: Symbol::StatementList {
-                uses_yield: list_uses_yield.or(*item_uses_yield),
-                uses_await: list_uses_await.or(*item_uses_await),
-                uses_return: list_uses_return.or(*item_uses_return)
-            }
-        }),
-        [
-            ..,
-            Symbol::StatementListItem { uses_yield, uses_await, uses_return }
-        ] => Some(TokenStackDiff {
-            pop: 1,
-            push: Symbol::StatementList {
-                uses_yield: *uses_yield,
-                uses_await: *uses_await,
-                uses_return: *uses_return
-            }
-        }),
-
-        // Serialize <https://262.ecma-international.org/14.0/#prod-StatementListItem>.
-        //
-        // ```plain
-        // StatementListItem[Yield, Await, Return] :
-        //     Statement[?Yield, ?Await, ?Return]
-        //     Declaration[?Yield, ?Await]
-        // ```
-        [.., Symbol::Statement { uses_yield, uses_await, uses_return } ] => Some(TokenStackDiff {
-            pop: 1,
-            push: Symbol::StatementListItem {
-                uses_yield: *uses_yield,
-                uses_await: *uses_await,
-                uses_return: *uses_return
-            }
-        }),
-
-        /************************************************
-         *
-         * 14 ECMAScript Language: Statements and Declarations
-         *
-         ************************************************/
-
-        // A match for <https://262.ecma-international.org/14.0/#prod-Statement>.
-        //
-        // ```plain
-        // Statement[Yield, Await, Return] :
-        //     BlockStatement[?Yield, ?Await, ?Return]
-        //     VariableStatement[?Yield, ?Await]
-        //     EmptyStatement
-        //     IfStatement[?Yield, ?Await, ?Return]
-        //     ExpressionStatement[?Yield, ?Await]
-        //     BreakableStatement[?Yield, ?Await, ?Return]
-        //     ContinueStatement[?Yield, ?Await]
-        //     BreakStatement[?Yield, ?Await]
-        //     [+Return] ReturnStatement[?Yield, ?Await]
-        //     WithStatement[?Yield, ?Await, ?Return]
-        //     LabelledStatement[?Yield, ?Await, ?Return]
-        //     ThrowStatement[?Yield, ?Await]
-        //     TryStatement[?Yield, ?Await, ?Return]
-        //     DebuggerStatement
-        // ```
-        [.., Symbol::EmptyStatement] => Some(TokenStackDiff {
-            pop: 1,
-            push: Symbol::Statement {
-                uses_yield: None,
-                uses_await: None,
-                uses_return: None
-            }
-        }),
-
-        /************************************************
-         *
-         * 14.4 Empty Statement
-         *
-         ************************************************/
-
-        // Serialize <https://262.ecma-international.org/14.0/#prod-EmptyStatement>.
-        //
-        // ```plain
-        // EmptyStatement :
-        //     `;`
-        // ```
-        [.., Symbol::SourceCharacter(';')] => Some(TokenStackDiff {
-            pop: 1,
-            push: Symbol::EmptyStatement
-        }),
-        
-        /************************************************
-         *
-         * 16.1 Scripts
-         *
-         ************************************************/
-
-        // Serialize <https://262.ecma-international.org/14.0/#prod-Script>.
-        //
-        // ```plain
-        // Script :
-        //     ScriptBody_opt
-        // ```
-        //
-        // The empty case is processed in a caller after all input characters
-        // are pushed into the stack and.
-        [.., Symbol::ScriptBody] => Some(TokenStackDiff {
-            pop: 1,
-            push: Symbol::Script
-        }),
-
-        // Serialize <https://262.ecma-international.org/14.0/#prod-ScriptBody>.
-        //
-        // ```plain
-        // ScriptBody :
-        //     StatementList[~Yield, ~Await, ~Return]
-        // ```
-        [.., Symbol::StatementList { uses_yield, uses_await, uses_return }] => {
-            match (uses_yield, uses_await, uses_return) {
-                (Some(_), Some(_), Some(_)) => None,
-                _ => Some(TokenStackDiff { pop: 1, push: Symbol::ScriptBody })
-            }
-        },
-
-        _ => None
+/// `true` for [`SourceType::Module`], as `as_module` arguments used before
+/// [`SourceType`] was introduced.
+impl From<bool> for SourceType {
+    fn from(as_module: bool) -> Self {
+        if as_module { Self::Module } else { Self::Script }
     }
 }
 
-fn reduce(mut eager_parse_stack: Vec<Symbol>, as_module: bool) -> Vec<Symbol> {
-    match reduce_once(&eager_parse_stack, as_module) {
-        Some(stack_diff) => {
-            eager_parse_stack.truncate(eager_parse_stack.len() - stack_diff.pop);
-            eager_parse_stack.push(stack_diff.push);
-            reduce(eager_parse_stack, as_module)
-        },
-        None => eager_parse_stack
+/// Tell whether a text of an unknown origin is a script or a module.
+///
+/// A module is detected by syntax that a script cannot have: an `import`
+/// or `export` outside of braces, parentheses and brackets, except for
+/// `import(...)` calls. Otherwise `default` is returned, or
+/// [`SourceType::Script`] if it is [`SourceType::Auto`]. Only tokens are
+/// examined, so a text with errors is detected too, up to a first token
+/// that cannot be recognized.
+#[must_use]
+pub fn detect_source_type(source: &str, default: SourceType) -> SourceType {
+    let default = if default == SourceType::Auto { SourceType::Script } else { default };
+    let mut tokenizer = Tokenizer::new();
+    let mut depth = 0_usize;
+    let mut previous = None;
+    let mut offset = 0;
+    while let Ok(Some((kind, range))) = next_significant_token(source, offset, &mut tokenizer) {
+        match kind {
+            TokenKind::OpeningBrace | TokenKind::OpeningParenthesis | TokenKind::OpeningBracket => depth += 1,
+            TokenKind::RightBracePunctuator | TokenKind::ClosingParenthesis | TokenKind::ClosingBracket => {
+                depth = depth.saturating_sub(1);
+            },
+            // A property name like `a.export`.
+            TokenKind::Export | TokenKind::Import
+                if depth > 0 || matches!(previous, Some(TokenKind::Dot | TokenKind::OptionalChainingPunctuator)) => {},
+            TokenKind::Export => return SourceType::Module,
+            TokenKind::Import => {
+                let next = next_significant_token(source, range.end, &mut tokenizer.clone());
+                if !matches!(next, Ok(Some((TokenKind::OpeningParenthesis, _)))) {
+                    return SourceType::Module;
+                }
+            },
+            _ => {},
+        }
+        previous = Some(kind);
+        offset = range.end;
     }
+    default
 }
 
 /// Parses a `.js`/`.mjs` text and performs early error checks.
@@ -261,18 +185,51 @@ fn reduce(mut eager_parse_stack: Vec<Symbol>, as_module: bool) -> Vec<Symbol> {
 ///
 /// # Errors
 ///
-/// Will return `Err` with rustc-style formatted error message string, if the
-/// source parameter does not form a correct ECMAScript 2023 script or module.
-pub fn parse(source: &str, as_module: bool) -> Result<(), Vec<Symbol>> {
-    let final_parse_stack = source.chars().fold(
-        Vec::with_capacity(512),
-        |mut accumulator, codepoint| {
-            accumulator.push(Symbol::SourceCharacter(codepoint));
-            reduce(accumulator, as_module)
-        }
-    );
-    match final_parse_stack.len() {
-        0 | 1 => Ok(()),
-        _ => Err(final_parse_stack)
+/// Will return `Err` with [`SourceCodeError`], if the source parameter does
+/// not form a correct ECMAScript 2023 script or module. If there are several
+/// problems, the first one is reported.
+pub fn parse(source: &str, source_type: impl Into<SourceType>) -> Result<(), SourceCodeError> {
+    let mut diagnostics = Diagnostics::new();
+    parse_with_diagnostics(source, source_type, &mut diagnostics);
+    diagnostics.into_vec().into_iter().find(|error| error.severity == Severity::Error).map_or(Ok(()), Err)
+}
+
+/// Parses a `.js`/`.mjs` text like [`parse`] does but reports all problems
+/// found instead of the first one.
+///
+/// Parsing does not stop on an erroneous place but continues with a text
+/// following it, so independent problems are reported separately.
+///
+/// # Errors
+///
+/// Will return `Err` with a nonempty list of [`SourceCodeError`] ordered by
+/// their position along with warnings, if the source parameter does not
+/// form a correct ECMAScript 2023 script or module.
+pub fn parse_collecting_errors(
+    source: &str,
+    source_type: impl Into<SourceType>
+) -> Result<(), Vec<SourceCodeError>> {
+    let mut diagnostics = Diagnostics::new();
+    parse_with_diagnostics(source, source_type, &mut diagnostics);
+    if diagnostics.has_errors() {
+        Err(diagnostics.into_vec())
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses a `.js`/`.mjs` text like [`parse_collecting_errors`] does,
+/// reporting problems into a sink shared with other processing steps.
+///
+/// The text is parsed by [`Parser`] with error recovery, so a statement
+/// that cannot be parsed is reported and skipped, and parsing continues
+/// with a next one. A module is parsed with the `Module` goal symbol, so
+/// it is strict mode code and may have `import` and `export` declarations;
+/// [`SourceType::Auto`] is resolved with [`detect_source_type`].
+pub fn parse_with_diagnostics(source: &str, source_type: impl Into<SourceType>, diagnostics: &mut Diagnostics) {
+    if source_type.into().is_module(source) {
+        Parser::new_module(source).parse_module_with_diagnostics(diagnostics);
+    } else {
+        Parser::new(source).parse_script_with_diagnostics(diagnostics);
     }
 }
@@ -0,0 +1,1926 @@
+//! Syntactic grammar of expressions.
+//!
+//! Implements <https://262.ecma-international.org/14.0/#sec-ecmascript-language-expressions>.
+
+use super::functions::{
+    ArrowFunction,
+    Class,
+    ClassElementName,
+    FormalParameters,
+    Function,
+    MethodDefinition,
+    MethodKind,
+};
+use super::parser::{Parameters, Parser, SuperUsage};
+use super::statements::{Binding, BindingElement, BindingIdentifier};
+use crate::lexical_grammar::{
+    CommonToken,
+    GoalSymbols,
+    NumericLiteral,
+    PrivateIdentifier,
+    RegularExpressionLiteral,
+    ContextualKeyword,
+    ReservedWord,
+    StringLiteral,
+    Template,
+    TemplateSubstitutionTail,
+    Token,
+    TokenKind,
+};
+use crate::{ErrorCode, SourceCodeError};
+use std::ops::Range;
+use std::rc::Rc;
+
+/************************************************
+ *
+ * Expression tree
+ *
+ ************************************************/
+
+/// <https://262.ecma-international.org/14.0/#prod-Expression> with all
+/// productions it nests.
+///
+/// Productions passing another one through, like
+/// `AdditiveExpression : MultiplicativeExpression`, have no node of their
+/// own, so `a + b` is a [`BinaryExpression`] of two
+/// [`IdentifierReference`]s.
+///
+/// Variants holding a token rather than a node keep byte offsets of
+/// the token next to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expression {
+    This(Range<usize>),
+    IdentifierReference(IdentifierReference),
+    Literal(Literal, Range<usize>),
+    ArrayLiteral(ArrayLiteral),
+    ObjectLiteral(ObjectLiteral),
+    // Functions are shared with code created from them, and classes are
+    // boxed since they are much larger than other expressions.
+    FunctionExpression(Rc<Function>),
+    ClassExpression(Box<Class>),
+    RegularExpressionLiteral(RegularExpressionLiteral, Range<usize>),
+    TemplateLiteral(TemplateLiteral),
+    ParenthesizedExpression(ParenthesizedExpression),
+    MemberExpression(MemberExpression),
+    SuperProperty(SuperProperty),
+    MetaProperty(MetaProperty, Range<usize>),
+    TaggedTemplate(TaggedTemplate),
+    CallExpression(CallExpression),
+    SuperCall(SuperCall),
+    ImportCall(ImportCall),
+    OptionalExpression(OptionalExpression),
+    NewExpression(NewExpression),
+    UpdateExpression(UpdateExpression),
+    UnaryExpression(UnaryExpression),
+    AwaitExpression(AwaitExpression),
+    BinaryExpression(BinaryExpression),
+    PrivateInExpression(PrivateInExpression),
+    ConditionalExpression(ConditionalExpression),
+    YieldExpression(YieldExpression),
+    ArrowFunction(Rc<ArrowFunction>),
+    AssignmentExpression(AssignmentExpression),
+    CommaExpression(CommaExpression),
+}
+
+impl Expression {
+    /// Byte offsets of the source text the expression covers.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::This(range)
+            | Self::Literal(_, range)
+            | Self::RegularExpressionLiteral(_, range)
+            | Self::MetaProperty(_, range) => range.clone(),
+            Self::IdentifierReference(node) => node.range.clone(),
+            Self::ArrayLiteral(node) => node.range.clone(),
+            Self::ObjectLiteral(node) => node.range.clone(),
+            Self::FunctionExpression(node) => node.range.clone(),
+            Self::ClassExpression(node) => node.range.clone(),
+            Self::TemplateLiteral(node) => node.range.clone(),
+            Self::ParenthesizedExpression(node) => node.range.clone(),
+            Self::MemberExpression(node) => node.range.clone(),
+            Self::SuperProperty(node) => node.range.clone(),
+            Self::TaggedTemplate(node) => node.range.clone(),
+            Self::CallExpression(node) => node.range.clone(),
+            Self::SuperCall(node) => node.range.clone(),
+            Self::ImportCall(node) => node.range.clone(),
+            Self::OptionalExpression(node) => node.range.clone(),
+            Self::NewExpression(node) => node.range.clone(),
+            Self::UpdateExpression(node) => node.range.clone(),
+            Self::UnaryExpression(node) => node.range.clone(),
+            Self::AwaitExpression(node) => node.range.clone(),
+            Self::BinaryExpression(node) => node.range.clone(),
+            Self::PrivateInExpression(node) => node.range.clone(),
+            Self::ConditionalExpression(node) => node.range.clone(),
+            Self::YieldExpression(node) => node.range.clone(),
+            Self::ArrowFunction(node) => node.range.clone(),
+            Self::AssignmentExpression(node) => node.range.clone(),
+            Self::CommaExpression(node) => node.range.clone(),
+        }
+    }
+
+    /// Whether the expression is matched by
+    /// <https://262.ecma-international.org/14.0/#prod-LeftHandSideExpression>,
+    /// so it may syntactically stand before `=` or `++`.
+    ///
+    /// Whether it is a valid assignment target is a matter of early errors.
+    #[must_use]
+    pub const fn is_left_hand_side_expression(&self) -> bool {
+        !matches!(
+            self,
+            Self::UpdateExpression(_)
+                | Self::UnaryExpression(_)
+                | Self::AwaitExpression(_)
+                | Self::BinaryExpression(_)
+                | Self::PrivateInExpression(_)
+                | Self::ConditionalExpression(_)
+                | Self::YieldExpression(_)
+                | Self::ArrowFunction(_)
+                | Self::AssignmentExpression(_)
+                | Self::CommaExpression(_)
+        )
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-assignmenttargettype>
+    /// of the expression in strict mode code if `is_strict`.
+    ///
+    /// Object and array literals are never simple; whether they can be
+    /// destructuring patterns is decided by the context.
+    #[must_use]
+    pub fn assignment_target_type(&self, is_strict: bool) -> AssignmentTargetType {
+        match self {
+            Self::IdentifierReference(reference) => reference.assignment_target_type(is_strict),
+            Self::MemberExpression(member) if !member.optional => AssignmentTargetType::Simple,
+            Self::SuperProperty(_) => AssignmentTargetType::Simple,
+            Self::ParenthesizedExpression(expression) => expression.expression.assignment_target_type(is_strict),
+            _ => AssignmentTargetType::Invalid,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-isfunctiondefinition>:
+    /// whether the expression is an anonymous or named function, class or
+    /// arrow function, possibly parenthesized.
+    #[must_use]
+    pub fn is_function_definition(&self) -> bool {
+        match self {
+            Self::FunctionExpression(_) | Self::ClassExpression(_) | Self::ArrowFunction(_) => true,
+            Self::ParenthesizedExpression(expression) => expression.expression.is_function_definition(),
+            _ => false,
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-static-semantics-assignmenttargettype>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssignmentTargetType {
+    /// A binding or a property that `=`, `+=` or `++` can assign to.
+    Simple,
+    Invalid,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-IdentifierReference>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdentifierReference {
+    /// `StringValue` of the identifier, with escapes decoded.
+    pub name: String,
+    pub range: Range<usize>,
+}
+
+impl IdentifierReference {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-assignmenttargettype>
+    /// of the reference in strict mode code if `is_strict`.
+    #[must_use]
+    pub fn assignment_target_type(&self, is_strict: bool) -> AssignmentTargetType {
+        if is_strict && matches!(self.name.as_str(), "eval" | "arguments") {
+            AssignmentTargetType::Invalid
+        } else {
+            AssignmentTargetType::Simple
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-Literal>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Literal {
+    NullLiteral,
+    BooleanLiteral(bool),
+    NumericLiteral(NumericLiteral),
+    StringLiteral(StringLiteral),
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ArrayLiteral>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArrayLiteral {
+    pub elements: Vec<ArrayElement>,
+    pub range: Range<usize>,
+}
+
+/// An item of <https://262.ecma-international.org/14.0/#prod-ElementList>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArrayElement {
+    /// A hole left by `Elision`, like the first item of `[, a]`, covering
+    /// a comma after it.
+    Elision(Range<usize>),
+    AssignmentExpression(Expression),
+    /// `... AssignmentExpression`
+    SpreadElement(Expression, Range<usize>),
+}
+
+impl ArrayElement {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Elision(range) | Self::SpreadElement(_, range) => range.clone(),
+            Self::AssignmentExpression(expression) => expression.range(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ObjectLiteral>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObjectLiteral {
+    pub properties: Vec<PropertyDefinition>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-PropertyDefinition>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PropertyDefinition {
+    /// A shorthand property like `{ a }`.
+    IdentifierReference(IdentifierReference),
+    /// `PropertyName : AssignmentExpression`
+    Property { name: PropertyName, value: Expression },
+    /// A method, getter or setter, always named by a `PropertyName`.
+    MethodDefinition(Rc<MethodDefinition>),
+    /// `... AssignmentExpression`
+    SpreadProperty(Expression, Range<usize>),
+    /// `IdentifierReference Initializer`, like `{ a = 1 }`, allowed only
+    /// in destructuring patterns and arrow function parameters.
+    CoverInitializedName { name: IdentifierReference, initializer: Expression },
+}
+
+impl PropertyDefinition {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::IdentifierReference(name) => name.range.clone(),
+            Self::Property { name, value } => name.range().start..value.range().end,
+            Self::MethodDefinition(method) => method.range.clone(),
+            Self::SpreadProperty(_, range) => range.clone(),
+            Self::CoverInitializedName { name, initializer } => name.range.start..initializer.range().end,
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-PropertyName>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PropertyName {
+    /// `StringValue` of `IdentifierName`, including reserved words.
+    IdentifierName(String, Range<usize>),
+    StringLiteral(StringLiteral, Range<usize>),
+    NumericLiteral(NumericLiteral, Range<usize>),
+    /// `[ AssignmentExpression ]`, covering the brackets.
+    ComputedPropertyName(Box<Expression>, Range<usize>),
+}
+
+impl PropertyName {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::IdentifierName(_, range)
+            | Self::StringLiteral(_, range)
+            | Self::NumericLiteral(_, range)
+            | Self::ComputedPropertyName(_, range) => range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-TemplateLiteral>
+///
+/// Strings and substitutions alternate, so there is one string more than
+/// substitutions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateLiteral {
+    pub strings: Vec<TemplateString>,
+    pub substitutions: Vec<Expression>,
+    pub range: Range<usize>,
+}
+
+/// A literal part of a template between its substitutions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateString {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>,
+    /// `None` if the part contains an invalid escape sequence, which only
+    /// a tagged template allows.
+    pub cooked: Option<Vec<u16>>,
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+    pub raw: String,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ParenthesizedExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParenthesizedExpression {
+    pub expression: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-MemberExpression> accessing
+/// a property, like `a.b` or `a[b]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberExpression {
+    pub object: Box<Expression>,
+    pub property: MemberProperty,
+    /// Whether the access is a part of an optional chain starting with
+    /// `?.`, like `a?.b` or `a?.[b]`.
+    pub optional: bool,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MemberProperty {
+    /// `. IdentifierName`
+    IdentifierName(String, Range<usize>),
+    /// `[ Expression ]`
+    Expression(Box<Expression>),
+    /// `. PrivateIdentifier`
+    PrivateIdentifier(PrivateIdentifier, Range<usize>),
+}
+
+impl MemberProperty {
+    /// Byte offsets of the name or of the expression without brackets.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::IdentifierName(_, range) | Self::PrivateIdentifier(_, range) => range.clone(),
+            Self::Expression(expression) => expression.range(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-SuperProperty>
+///
+/// `super.a` or `super[a]` accessing a property of a prototype of an object
+/// a method is defined on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SuperProperty {
+    /// Never a [`MemberProperty::PrivateIdentifier`].
+    pub property: MemberProperty,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-MetaProperty>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetaProperty {
+    /// `new.target`, allowed in functions only.
+    NewTarget,
+    /// `import.meta`, allowed in modules only.
+    ImportMeta,
+}
+
+/// `MemberExpression TemplateLiteral` or `CallExpression TemplateLiteral`
+/// calling a tag function with a template, like ``a`b${c}` ``.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaggedTemplate {
+    pub tag: Box<Expression>,
+    pub template: TemplateLiteral,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-CallExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallExpression {
+    pub callee: Box<Expression>,
+    pub arguments: Vec<Argument>,
+    /// Whether the call is a part of an optional chain starting with `?.`,
+    /// like `a?.()`.
+    pub optional: bool,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-SuperCall>
+///
+/// `super(a)` calling a constructor of a parent class.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SuperCall {
+    pub arguments: Vec<Argument>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ImportCall>
+///
+/// `import(a)` loading a module dynamically.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportCall {
+    pub specifier: Box<Expression>,
+    /// A second argument like `{ with: { type: 'json' } }`, parsed only with
+    /// [`super::Proposals::import_attributes`].
+    pub options: Option<Box<Expression>>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-OptionalExpression>
+///
+/// Wraps a whole chain like `a?.b.c`, which evaluates to `undefined`
+/// if an object before `?.` is `null` or `undefined`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptionalExpression {
+    /// The chain of [`MemberExpression`]s and [`CallExpression`]s, some of
+    /// them marked `optional`.
+    pub expression: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// An item of <https://262.ecma-international.org/14.0/#prod-ArgumentList>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Argument {
+    AssignmentExpression(Expression),
+    /// `... AssignmentExpression`
+    SpreadElement(Expression, Range<usize>),
+}
+
+impl Argument {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::AssignmentExpression(expression) => expression.range(),
+            Self::SpreadElement(_, range) => range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-NewExpression>, or
+/// `new MemberExpression Arguments` if arguments are given.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewExpression {
+    pub callee: Box<Expression>,
+    /// `None` for `new a` without parentheses.
+    pub arguments: Option<Vec<Argument>>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-UpdateExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpdateExpression {
+    pub operator: UpdateOperator,
+    /// Whether the operator goes before the operand, like `++a`.
+    pub prefix: bool,
+    pub operand: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateOperator {
+    Increment,
+    Decrement,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-UnaryExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnaryExpression {
+    pub operator: UnaryOperator,
+    pub operand: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOperator {
+    Delete,
+    Void,
+    Typeof,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    BitNot,
+    Not,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-AwaitExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AwaitExpression {
+    pub argument: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// Any of productions from
+/// <https://262.ecma-international.org/14.0/#prod-ExponentiationExpression>
+/// to <https://262.ecma-international.org/14.0/#prod-LogicalORExpression>
+/// having two operands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BinaryExpression {
+    pub operator: BinaryOperator,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// `PrivateIdentifier in ShiftExpression` of
+/// <https://262.ecma-international.org/14.0/#prod-RelationalExpression>,
+/// checking whether an object has a private member.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivateInExpression {
+    pub name: PrivateIdentifier,
+    pub object: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// Binary operators named after their punctuators in
+/// [`TokenKind`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinaryOperator {
+    Exponentiation,
+    Multiplication,
+    Division,
+    Modulo,
+    Addition,
+    Subtraction,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    Less,
+    More,
+    LessOrEqual,
+    MoreOrEqual,
+    Instanceof,
+    In,
+    LooseEquality,
+    LooseInequality,
+    StrictEquality,
+    StrictInequality,
+    BitAnd,
+    BitXor,
+    BitOr,
+    And,
+    Or,
+    /// `??` of <https://262.ecma-international.org/14.0/#prod-CoalesceExpression>.
+    NullishCoalescence,
+}
+
+impl BinaryOperator {
+    /// Binding power of the operator; operands of an operator with greater
+    /// precedence are grouped first.
+    #[must_use]
+    pub const fn precedence(self) -> u8 {
+        match self {
+            Self::Or | Self::NullishCoalescence => 1,
+            Self::And => 2,
+            Self::BitOr => 3,
+            Self::BitXor => 4,
+            Self::BitAnd => 5,
+            Self::LooseEquality | Self::LooseInequality | Self::StrictEquality | Self::StrictInequality => 6,
+            Self::Less | Self::More | Self::LessOrEqual | Self::MoreOrEqual | Self::Instanceof | Self::In => 7,
+            Self::LeftShift | Self::RightShift | Self::UnsignedRightShift => 8,
+            Self::Addition | Self::Subtraction => 9,
+            Self::Multiplication | Self::Division | Self::Modulo => 10,
+            Self::Exponentiation => 11,
+        }
+    }
+
+    const fn from_token(kind: TokenKind) -> Option<Self> {
+        let operator = match kind {
+            TokenKind::Exponentiation => Self::Exponentiation,
+            TokenKind::Multiplication => Self::Multiplication,
+            TokenKind::Division => Self::Division,
+            TokenKind::Modulo => Self::Modulo,
+            TokenKind::Addition => Self::Addition,
+            TokenKind::Subtraction => Self::Subtraction,
+            TokenKind::LeftShift => Self::LeftShift,
+            TokenKind::RightShift => Self::RightShift,
+            TokenKind::UnsignedRightShift => Self::UnsignedRightShift,
+            TokenKind::Less => Self::Less,
+            TokenKind::More => Self::More,
+            TokenKind::LessOrEqual => Self::LessOrEqual,
+            TokenKind::MoreOrEqual => Self::MoreOrEqual,
+            TokenKind::Instanceof => Self::Instanceof,
+            TokenKind::In => Self::In,
+            TokenKind::LooseEquality => Self::LooseEquality,
+            TokenKind::LooseInequality => Self::LooseInequality,
+            TokenKind::StrictEquality => Self::StrictEquality,
+            TokenKind::StrictInequality => Self::StrictInequality,
+            TokenKind::BitAnd => Self::BitAnd,
+            TokenKind::BitXor => Self::BitXor,
+            TokenKind::BitOr => Self::BitOr,
+            TokenKind::And => Self::And,
+            TokenKind::Or => Self::Or,
+            TokenKind::NullishCoalescence => Self::NullishCoalescence,
+            _ => return None,
+        };
+        Some(operator)
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ConditionalExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionalExpression {
+    pub condition: Box<Expression>,
+    pub consequent: Box<Expression>,
+    pub alternative: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-YieldExpression>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldExpression {
+    /// Whether the expression is `yield*` delegating to another iterator.
+    pub is_delegate: bool,
+    pub argument: Option<Box<Expression>>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-AssignmentExpression>
+/// with an assignment operator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssignmentExpression {
+    pub operator: AssignmentOperator,
+    pub target: Box<Expression>,
+    pub value: Box<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-AssignmentOperator> and
+/// logical assignments, named after their punctuators in [`TokenKind`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssignmentOperator {
+    Assignment,
+    MultiplicationAssignment,
+    DivisionAssignment,
+    ModuloAssignment,
+    AdditionAssignment,
+    SubtractionAssignment,
+    LeftShiftAssignment,
+    RightShiftAssignment,
+    UnsignedRightShiftAssignment,
+    BitAndAssignment,
+    BitXorAssignment,
+    BitOrAssignment,
+    ExponentiationAssignment,
+    AndAssignment,
+    OrAssignment,
+    NullishCoalescenceAssignment,
+}
+
+impl AssignmentOperator {
+    const fn from_token(kind: TokenKind) -> Option<Self> {
+        let operator = match kind {
+            TokenKind::Assignment => Self::Assignment,
+            TokenKind::MultiplicationAssignment => Self::MultiplicationAssignment,
+            TokenKind::DivisionAssignment => Self::DivisionAssignment,
+            TokenKind::ModuloAssignment => Self::ModuloAssignment,
+            TokenKind::AdditionAssignment => Self::AdditionAssignment,
+            TokenKind::SubtractionAssignment => Self::SubtractionAssignment,
+            TokenKind::LeftShiftAssignment => Self::LeftShiftAssignment,
+            TokenKind::RightShiftAssignment => Self::RightShiftAssignment,
+            TokenKind::UnsignedRightShiftAssignment => Self::UnsignedRightShiftAssignment,
+            TokenKind::BitAndAssignment => Self::BitAndAssignment,
+            TokenKind::BitXorAssignment => Self::BitXorAssignment,
+            TokenKind::BitOrAssignment => Self::BitOrAssignment,
+            TokenKind::ExponentiationAssignment => Self::ExponentiationAssignment,
+            TokenKind::AndAssignment => Self::AndAssignment,
+            TokenKind::OrAssignment => Self::OrAssignment,
+            TokenKind::NullishCoalescenceAssignment => Self::NullishCoalescenceAssignment,
+            _ => return None,
+        };
+        Some(operator)
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-Expression> with a comma
+/// operator, like `a, b`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommaExpression {
+    pub expressions: Vec<Expression>,
+    pub range: Range<usize>,
+}
+
+/************************************************
+ *
+ * 13.1 Identifiers
+ *
+ ************************************************/
+
+/// Words of <https://262.ecma-international.org/14.0/#prod-ReservedWord>
+/// except for `yield` and `await` that are identifiers in some contexts.
+const RESERVED_WORDS: [&str; 36] = [
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do",
+    "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import",
+    "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw", "true",
+    "try", "typeof", "var", "void", "while", "with",
+];
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-IdentifierReference>.
+    ///
+    /// ```plain
+    /// IdentifierReference[Yield, Await] :
+    ///     Identifier
+    ///     [~Yield] yield
+    ///     [~Await] await
+    /// ```
+    pub(super) fn parse_identifier_reference(
+        &mut self,
+        parameters: Parameters
+    ) -> Result<IdentifierReference, SourceCodeError> {
+        let (name, is_reserved_in_strict_mode) = match self.token() {
+            Some(Token::CommonToken(CommonToken::IdentifierName(name))) => {
+                (name.string_value(), name.is_reserved_in_strict_mode())
+            },
+            Some(Token::ReservedWord(ReservedWord::Yield(_))) if !parameters.allow_yield => ("yield".to_owned(), true),
+            Some(Token::ReservedWord(ReservedWord::Await(_))) if !parameters.allow_await => ("await".to_owned(), false),
+            _ => return Err(self.expected("identifier")),
+        };
+        let range = self.range();
+        self.bump();
+        // Unescaped reserved words are not tokenized as `IdentifierName`.
+        //
+        // > It is a Syntax Error if the goal symbol of the syntactic grammar
+        // > is Module and the StringValue of IdentifierName is "await".
+        // > It is a Syntax Error if StringValue of IdentifierName is the same
+        // > String value as the StringValue of any ReservedWord except for
+        // > yield or await.
+        if RESERVED_WORDS.contains(&name.as_str()) {
+            let message = format!("reserved word `{name}` cannot be an identifier even if escaped");
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        // > It is a Syntax Error if this production has a [Yield] parameter
+        // > and StringValue of Identifier is "yield".
+        // > It is a Syntax Error if this production has an [Await] parameter
+        // > and StringValue of Identifier is "await".
+        if (parameters.allow_yield && name == "yield") || (parameters.allow_await && name == "await") {
+            let message = format!("`{name}` cannot be an identifier here even if escaped");
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        // > It is a Syntax Error if this phrase is contained in strict mode
+        // > code and the StringValue of IdentifierName is one of
+        // > "implements", "interface", "let", "package", "private",
+        // > "protected", "public", "static", or "yield".
+        if is_reserved_in_strict_mode && self.is_strict() {
+            let message = format!("`{name}` cannot be an identifier in strict mode code");
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        Ok(IdentifierReference { name, range })
+    }
+
+    /// Parse `IdentifierName` after `.` or as a property name, where
+    /// reserved words are allowed.
+    pub(super) fn parse_identifier_name(&mut self) -> Result<String, SourceCodeError> {
+        let name = match self.token() {
+            Some(Token::CommonToken(CommonToken::IdentifierName(name))) => name.string_value(),
+            Some(token @ Token::ReservedWord(_)) => token.keyword_text().unwrap_or_default().to_owned(),
+            _ => return Err(self.expected("identifier")),
+        };
+        self.bump();
+        Ok(name)
+    }
+}
+
+/************************************************
+ *
+ * 13.2 Primary Expression
+ *
+ ************************************************/
+
+/// Parsed <https://262.ecma-international.org/14.0/#prod-CoverParenthesizedExpressionAndArrowParameterList>
+/// or arguments of <https://262.ecma-international.org/14.0/#prod-CoverCallExpressionAndAsyncArrowHead>
+/// waiting to be refined into a production known after `)`.
+pub(super) struct Cover {
+    pub(super) items: Vec<Argument>,
+    /// Whether a comma follows the last item, like in `(a,)`.
+    pub(super) has_trailing_comma: bool,
+    /// Byte offsets from `(` to `)` inclusive.
+    pub(super) range: Range<usize>,
+    /// Mark of `CoverInitializedName`s parsed inside, see
+    /// [`Parser::pending_cover_initialized_names`].
+    pub(super) mark: usize,
+}
+
+/// Parameters of an arrow function like `a => a`.
+fn single_parameter(name: IdentifierReference) -> FormalParameters {
+    let range = name.range;
+    let target = Binding::BindingIdentifier(BindingIdentifier { name: name.name, range: range.clone() });
+    let parameter = BindingElement { target, initializer: None, range: range.clone() };
+    FormalParameters { parameters: vec![parameter], rest: None, range }
+}
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-PrimaryExpression>.
+    fn parse_primary_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        // A `PrimaryExpression` is the only place where a `/` starts
+        // a `RegularExpressionLiteral`.
+        if matches!(self.kind(), Some(TokenKind::Division | TokenKind::DivisionAssignment)) {
+            self.rescan(GoalSymbols::InputElementRegExp);
+        }
+        let range = self.range();
+        let literal = match self.token() {
+            Some(Token::CommonToken(CommonToken::NumericLiteral(literal))) => {
+                Some(Expression::Literal(Literal::NumericLiteral(literal.clone()), range.clone()))
+            },
+            Some(Token::CommonToken(CommonToken::StringLiteral(literal))) => {
+                Some(Expression::Literal(Literal::StringLiteral(literal.clone()), range.clone()))
+            },
+            Some(Token::RegularExpressionLiteral(literal)) => {
+                Some(Expression::RegularExpressionLiteral(literal.clone(), range.clone()))
+            },
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            self.bump();
+            return Ok(literal);
+        }
+        let keyword_literal = match self.kind() {
+            Some(TokenKind::This) => Some(Expression::This(range)),
+            Some(TokenKind::Null) => Some(Expression::Literal(Literal::NullLiteral, range)),
+            Some(TokenKind::True) => Some(Expression::Literal(Literal::BooleanLiteral(true), range)),
+            Some(TokenKind::False) => Some(Expression::Literal(Literal::BooleanLiteral(false), range)),
+            _ => None,
+        };
+        if let Some(keyword_literal) = keyword_literal {
+            self.bump();
+            return Ok(keyword_literal);
+        }
+        match self.kind() {
+            Some(TokenKind::IdentifierName) if self.at_async_function() => {
+                Ok(Expression::FunctionExpression(Rc::new(self.parse_function(false, parameters)?)))
+            },
+            Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await) if self.at_potential_arrow() => {
+                self.parse_identifier_or_arrow_function(parameters)
+            },
+            Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await) => {
+                Ok(Expression::IdentifierReference(self.parse_identifier_reference(parameters)?))
+            },
+            Some(TokenKind::NoSubstitutionTemplate | TokenKind::TemplateHead) => {
+                Ok(Expression::TemplateLiteral(self.parse_template_literal(parameters, false)?))
+            },
+            Some(TokenKind::OpeningBracket) => Ok(Expression::ArrayLiteral(self.parse_array_literal(parameters)?)),
+            Some(TokenKind::OpeningBrace) => Ok(Expression::ObjectLiteral(self.parse_object_literal(parameters)?)),
+            Some(TokenKind::Function) => {
+                Ok(Expression::FunctionExpression(Rc::new(self.parse_function(false, parameters)?)))
+            },
+            Some(TokenKind::Class) => Ok(Expression::ClassExpression(Box::new(self.parse_class(false, parameters)?))),
+            Some(TokenKind::Import) => self.parse_import_meta_or_call(parameters),
+            Some(TokenKind::Super) => self.parse_super(parameters),
+            Some(TokenKind::OpeningParenthesis) => {
+                let is_potential_arrow = self.at_potential_arrow();
+                let cover = self.parse_cover(parameters)?;
+                if is_potential_arrow && self.at(TokenKind::FunctionArrow) {
+                    let start = cover.range.start;
+                    let arrow_parameters = self.refine_arrow_parameters(cover)?;
+                    let arrow_function = self.parse_arrow_function(start, false, arrow_parameters, parameters)?;
+                    return Ok(Expression::ArrowFunction(Rc::new(arrow_function)));
+                }
+                self.refine_parenthesized_expression(cover)
+            },
+            _ if self.at_decorator() => {
+                Ok(Expression::ClassExpression(Box::new(self.parse_class(false, parameters)?)))
+            },
+            _ => Err(self.expected("expression")),
+        }
+    }
+
+    /// Whether the current token starts `async [no LineTerminator here] function`.
+    pub(super) fn at_async_function(&self) -> bool {
+        self.at_contextual(ContextualKeyword::Async) && self.peek_next() == (Some(TokenKind::Function), false)
+    }
+
+    /// Parse `IdentifierReference` that may start an arrow function.
+    ///
+    /// ```plain
+    /// ArrowParameters[Yield, Await] :
+    ///     BindingIdentifier[?Yield, ?Await]
+    ///     CoverParenthesizedExpressionAndArrowParameterList[?Yield, ?Await]
+    ///
+    /// AsyncArrowFunction[In, Yield, Await] :
+    ///     async [no LineTerminator here] AsyncArrowBindingIdentifier[?Yield] [no LineTerminator here] => AsyncConciseBody[?In]
+    ///     CoverCallExpressionAndAsyncArrowHead[?Yield, ?Await] [no LineTerminator here] => AsyncConciseBody[?In]
+    /// ```
+    fn parse_identifier_or_arrow_function(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        if self.at_contextual(ContextualKeyword::Async) {
+            match self.peek_next() {
+                (Some(TokenKind::OpeningParenthesis), false) => {
+                    self.bump();
+                    return self.parse_async_arrow_head(start, parameters);
+                },
+                (Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await), false) => {
+                    self.bump();
+                    // `AsyncArrowBindingIdentifier[Yield] : BindingIdentifier[?Yield, +Await]`
+                    let name = self.parse_identifier_reference(parameters.with_await(true))?;
+                    let arrow_parameters = single_parameter(name);
+                    let arrow_function = self.parse_arrow_function(start, true, arrow_parameters, parameters)?;
+                    return Ok(Expression::ArrowFunction(Rc::new(arrow_function)));
+                },
+                _ => {},
+            }
+        }
+        let reference = self.parse_identifier_reference(parameters)?;
+        if !self.at(TokenKind::FunctionArrow) {
+            return Ok(Expression::IdentifierReference(reference));
+        }
+        let arrow_function = self.parse_arrow_function(start, false, single_parameter(reference), parameters)?;
+        Ok(Expression::ArrowFunction(Rc::new(arrow_function)))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-CoverCallExpressionAndAsyncArrowHead>
+    /// after `async` and refine it into `AsyncArrowHead` or a call of
+    /// a function named `async`.
+    fn parse_async_arrow_head(&mut self, start: usize, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let cover = self.parse_cover(parameters)?;
+        if self.at(TokenKind::FunctionArrow) {
+            let arrow_parameters = self.refine_arrow_parameters(cover)?;
+            let arrow_function = self.parse_arrow_function(start, true, arrow_parameters, parameters)?;
+            return Ok(Expression::ArrowFunction(Rc::new(arrow_function)));
+        }
+        self.check_cover_initialized_names(cover.mark)?;
+        let name = "async".to_owned();
+        let callee = Expression::IdentifierReference(IdentifierReference { name, range: start..start + 5 });
+        Ok(Expression::CallExpression(CallExpression {
+            callee: Box::new(callee),
+            arguments: cover.items,
+            optional: false,
+            range: start..cover.range.end,
+        }))
+    }
+
+    /// Parse a parenthesized list of expressions that may be a spread, like
+    /// `(a, ...b)`, and may end with a comma.
+    ///
+    /// ```plain
+    /// CoverParenthesizedExpressionAndArrowParameterList[Yield, Await] :
+    ///     ( Expression[+In, ?Yield, ?Await] )
+    ///     ( Expression[+In, ?Yield, ?Await] , )
+    ///     ( )
+    ///     ( ... BindingIdentifier[?Yield, ?Await] )
+    ///     ( ... BindingPattern[?Yield, ?Await] )
+    ///     ( Expression[+In, ?Yield, ?Await] , ... BindingIdentifier[?Yield, ?Await] )
+    ///     ( Expression[+In, ?Yield, ?Await] , ... BindingPattern[?Yield, ?Await] )
+    /// ```
+    ///
+    /// Arguments of `CoverCallExpressionAndAsyncArrowHead` are a superset of
+    /// this, so the covered items are kept as [`Argument`]s.
+    fn parse_cover(&mut self, parameters: Parameters) -> Result<Cover, SourceCodeError> {
+        let parameters = parameters.with_in(true);
+        let start = self.range().start;
+        let mark = self.pending_cover_initialized_names();
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let mut items = Vec::new();
+        let mut has_trailing_comma = false;
+        while !self.eat(TokenKind::ClosingParenthesis) {
+            let item_start = self.range().start;
+            let item = if self.eat(TokenKind::Ellipsis) {
+                let argument = self.parse_assignment_expression_or_pattern(parameters)?;
+                Argument::SpreadElement(argument, self.range_from(item_start))
+            } else {
+                Argument::AssignmentExpression(self.parse_assignment_expression_or_pattern(parameters)?)
+            };
+            items.push(item);
+            has_trailing_comma = false;
+            if !self.at(TokenKind::ClosingParenthesis) {
+                self.expect(TokenKind::Comma)?;
+                has_trailing_comma = true;
+            }
+        }
+        Ok(Cover { items, has_trailing_comma, range: self.range_from(start), mark })
+    }
+
+    /// Reinterpret a cover not followed by `=>` as
+    /// <https://262.ecma-international.org/14.0/#prod-ParenthesizedExpression>.
+    ///
+    /// > CoverParenthesizedExpressionAndArrowParameterList must cover
+    /// > a ParenthesizedExpression.
+    fn refine_parenthesized_expression(&self, cover: Cover) -> Result<Expression, SourceCodeError> {
+        if cover.items.is_empty() || cover.has_trailing_comma {
+            let closing = cover.range.end - 1..cover.range.end;
+            let message = "expected expression, found `)`".to_owned();
+            let mut error = self.error(closing, ErrorCode::UnexpectedInput, message);
+            error.expected.push("expression".to_owned());
+            return Err(error);
+        }
+        let mut expressions = Vec::with_capacity(cover.items.len());
+        for item in cover.items {
+            let Argument::AssignmentExpression(expression) = item else {
+                let message = "spread is only allowed in arrow function parameters".to_owned();
+                return Err(self.error(cover.range, ErrorCode::UnexpectedInput, message));
+            };
+            expressions.push(expression);
+        }
+        self.check_cover_initialized_names(cover.mark)?;
+        let expression = if expressions.len() == 1 {
+            expressions.swap_remove(0)
+        } else {
+            let range = expressions[0].range().start..expressions[expressions.len() - 1].range().end;
+            Expression::CommaExpression(CommaExpression { expressions, range })
+        };
+        Ok(Expression::ParenthesizedExpression(ParenthesizedExpression { expression: Box::new(expression), range: cover.range }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ArrayLiteral>.
+    fn parse_array_literal(&mut self, parameters: Parameters) -> Result<ArrayLiteral, SourceCodeError> {
+        let parameters = parameters.with_in(true);
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningBracket)?;
+        let mut elements = Vec::new();
+        loop {
+            let element_start = self.range().start;
+            match self.kind() {
+                Some(TokenKind::ClosingBracket) => break,
+                Some(TokenKind::Comma) => {
+                    self.bump();
+                    elements.push(ArrayElement::Elision(self.range_from(element_start)));
+                    continue;
+                },
+                Some(TokenKind::Ellipsis) => {
+                    self.bump();
+                    let element = self.parse_assignment_expression_or_pattern(parameters)?;
+                    elements.push(ArrayElement::SpreadElement(element, self.range_from(element_start)));
+                },
+                _ => {
+                    let element = self.parse_assignment_expression_or_pattern(parameters)?;
+                    elements.push(ArrayElement::AssignmentExpression(element));
+                },
+            }
+            if !self.at(TokenKind::ClosingBracket) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        self.bump();
+        Ok(ArrayLiteral { elements, range: self.range_from(start) })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ObjectLiteral>.
+    fn parse_object_literal(&mut self, parameters: Parameters) -> Result<ObjectLiteral, SourceCodeError> {
+        let parameters = parameters.with_in(true);
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut properties = Vec::new();
+        while !self.eat(TokenKind::RightBracePunctuator) {
+            properties.push(self.parse_property_definition(parameters)?);
+            if !self.at(TokenKind::RightBracePunctuator) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(ObjectLiteral { properties, range: self.range_from(start) })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-PropertyDefinition>.
+    fn parse_property_definition(&mut self, parameters: Parameters) -> Result<PropertyDefinition, SourceCodeError> {
+        let start = self.range().start;
+        if self.eat(TokenKind::Ellipsis) {
+            let argument = self.parse_assignment_expression(parameters)?;
+            return Ok(PropertyDefinition::SpreadProperty(argument, self.range_from(start)));
+        }
+        let kind = self.parse_method_kind();
+        let is_shorthand = kind == MethodKind::Method
+            && matches!(self.kind(), Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await))
+            && !matches!(self.peek_next().0, Some(TokenKind::Colon | TokenKind::OpeningParenthesis));
+        if is_shorthand {
+            let name = self.parse_identifier_reference(parameters)?;
+            if !self.eat(TokenKind::Assignment) {
+                return Ok(PropertyDefinition::IdentifierReference(name));
+            }
+            let initializer = self.parse_assignment_expression(parameters)?;
+            self.record_cover_initialized_name(name.range.start..self.previous_end());
+            return Ok(PropertyDefinition::CoverInitializedName { name, initializer });
+        }
+        let name = self.parse_property_name(parameters)?;
+        if kind != MethodKind::Method || self.at(TokenKind::OpeningParenthesis) {
+            let method = self.parse_method_definition(start, kind, ClassElementName::PropertyName(name), SuperUsage::Property)?;
+            return Ok(PropertyDefinition::MethodDefinition(Rc::new(method)));
+        }
+        self.expect(TokenKind::Colon)?;
+        let value = self.parse_assignment_expression_or_pattern(parameters)?;
+        Ok(PropertyDefinition::Property { name, value })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-PropertyName>.
+    pub(super) fn parse_property_name(&mut self, parameters: Parameters) -> Result<PropertyName, SourceCodeError> {
+        let range = self.range();
+        let literal = match self.token() {
+            Some(Token::CommonToken(CommonToken::StringLiteral(literal))) => {
+                Some(PropertyName::StringLiteral(literal.clone(), range.clone()))
+            },
+            Some(Token::CommonToken(CommonToken::NumericLiteral(literal))) => {
+                Some(PropertyName::NumericLiteral(literal.clone(), range.clone()))
+            },
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            self.bump();
+            return Ok(literal);
+        }
+        if self.eat(TokenKind::OpeningBracket) {
+            let expression = self.parse_assignment_expression(parameters.with_in(true))?;
+            self.expect(TokenKind::ClosingBracket)?;
+            return Ok(PropertyName::ComputedPropertyName(Box::new(expression), self.range_from(range.start)));
+        }
+        if !matches!(self.kind(), Some(TokenKind::IdentifierName)) && !self.token().is_some_and(Token::is_keyword) {
+            return Err(self.expected("property name"));
+        }
+        Ok(PropertyName::IdentifierName(self.parse_identifier_name()?, range))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-TemplateLiteral>.
+    ///
+    /// ```plain
+    /// TemplateLiteral[Yield, Await, Tagged] :
+    ///     NoSubstitutionTemplate
+    ///     SubstitutionTemplate[?Yield, ?Await, ?Tagged]
+    ///
+    /// SubstitutionTemplate[Yield, Await, Tagged] :
+    ///     TemplateHead Expression[+In, ?Yield, ?Await] TemplateSpans[?Yield, ?Await, ?Tagged]
+    /// ```
+    ///
+    /// A `}` closing a substitution is recognized as `RightBracePunctuator`
+    /// first and then rescanned with the `InputElementTemplateTail` goal
+    /// as a start of `TemplateMiddle` or `TemplateTail`. A substitution is
+    /// never empty, so `InputElementRegExpOrTemplateTail` is not needed.
+    pub(super) fn parse_template_literal(
+        &mut self,
+        parameters: Parameters,
+        is_tagged: bool
+    ) -> Result<TemplateLiteral, SourceCodeError> {
+        let start = self.range().start;
+        let mut template = TemplateLiteral { strings: Vec::new(), substitutions: Vec::new(), range: start..start };
+        let mut is_tail = match self.token() {
+            Some(Token::CommonToken(CommonToken::Template(Template::NoSubstitutionTemplate(part)))) => {
+                template.strings.push(TemplateString { cooked: part.cooked_value(), raw: part.raw_value(), range: self.range() });
+                true
+            },
+            Some(Token::CommonToken(CommonToken::Template(Template::TemplateHead(part)))) => {
+                template.strings.push(TemplateString { cooked: part.cooked_value(), raw: part.raw_value(), range: self.range() });
+                false
+            },
+            _ => return Err(self.expected("template")),
+        };
+        loop {
+            // > It is a Syntax Error if the [Tagged] parameter was not set
+            // > and NoSubstitutionTemplate Contains NotEscapeSequence.
+            let is_cooked = template.strings.last().is_some_and(|string| string.cooked.is_some());
+            if !is_tagged && !is_cooked {
+                let message = "invalid escape sequence is only allowed in a tagged template".to_owned();
+                return Err(self.error(self.range(), ErrorCode::EarlyError, message));
+            }
+            self.bump();
+            if is_tail {
+                template.range = self.range_from(start);
+                return Ok(template);
+            }
+            template.substitutions.push(self.parse_expression_with(parameters.with_in(true))?);
+            if self.at(TokenKind::RightBracePunctuator) {
+                self.rescan(GoalSymbols::InputElementTemplateTail);
+            }
+            is_tail = match self.token() {
+                Some(Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateMiddle(part))) => {
+                    template.strings.push(TemplateString { cooked: part.cooked_value(), raw: part.raw_value(), range: self.range() });
+                    false
+                },
+                Some(Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateTail(part))) => {
+                    template.strings.push(TemplateString { cooked: part.cooked_value(), raw: part.raw_value(), range: self.range() });
+                    true
+                },
+                _ => return Err(self.expected("`}`")),
+            };
+        }
+    }
+}
+
+/************************************************
+ *
+ * 13.3 Left-Hand-Side Expressions
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-LeftHandSideExpression>.
+    pub(super) fn parse_left_hand_side_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let expression = if self.at(TokenKind::New) {
+            self.parse_new_expression(parameters)?
+        } else {
+            self.parse_primary_expression(parameters)?
+        };
+        // `new a` without arguments is a `NewExpression` that nothing can
+        // follow, including `?.`.
+        if matches!(&expression, Expression::NewExpression(new) if new.arguments.is_none()) {
+            return Ok(expression);
+        }
+        self.parse_member_tail(expression, parameters, true)
+    }
+
+    /// Parse `new MemberExpression Arguments` or `new NewExpression`.
+    fn parse_new_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::New)?;
+        if self.eat(TokenKind::Dot) {
+            if !self.at_contextual(ContextualKeyword::Target) {
+                return Err(self.expected("`target`"));
+            }
+            self.bump();
+            // > It is a Syntax Error if StatementList Contains NewTarget
+            // > unless the source text containing NewTarget is eval code
+            // > that is being processed by a direct eval.
+            if !self.new_target_allowed() {
+                let message = "`new.target` is only allowed in functions".to_owned();
+                return Err(self.error(self.range_from(start), ErrorCode::EarlyError, message));
+            }
+            return Ok(Expression::MetaProperty(MetaProperty::NewTarget, self.range_from(start)));
+        }
+        // `ImportCall` and `SuperCall` are `CallExpression`s, so they cannot
+        // be constructed.
+        if matches!(self.kind(), Some(TokenKind::Import | TokenKind::Super)) && self.peek_next().0 == Some(TokenKind::OpeningParenthesis) {
+            return Err(self.unexpected());
+        }
+        let callee = if self.at(TokenKind::New) {
+            self.nested(|parser| parser.parse_new_expression(parameters))?
+        } else {
+            self.parse_primary_expression(parameters)?
+        };
+        let callee = self.parse_member_tail(callee, parameters, false)?;
+        let arguments = if self.at(TokenKind::OpeningParenthesis) {
+            Some(self.parse_arguments(parameters)?)
+        } else {
+            None
+        };
+        Ok(Expression::NewExpression(NewExpression { callee: Box::new(callee), arguments, range: self.range_from(start) }))
+    }
+
+    /// Parse `import.meta` or
+    /// <https://262.ecma-international.org/14.0/#prod-ImportCall>.
+    ///
+    /// ```plain
+    /// ImportMeta :
+    ///     import . meta
+    ///
+    /// ImportCall[Yield, Await] :
+    ///     import ( AssignmentExpression[+In, ?Yield, ?Await] )
+    /// ```
+    ///
+    /// The import attributes proposal adds:
+    ///
+    /// ```plain
+    /// ImportCall[Yield, Await] :
+    ///     import ( AssignmentExpression[+In, ?Yield, ?Await] ,opt )
+    ///     import ( AssignmentExpression[+In, ?Yield, ?Await] , AssignmentExpression[+In, ?Yield, ?Await] ,opt )
+    /// ```
+    fn parse_import_meta_or_call(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Import)?;
+        if self.eat(TokenKind::Dot) {
+            if !self.at_contextual(ContextualKeyword::Meta) {
+                return Err(self.expected("`meta`"));
+            }
+            self.bump();
+            // > It is a Syntax Error if the syntactic goal symbol is not
+            // > Module.
+            if !self.is_module() {
+                let message = "`import.meta` is only allowed in modules".to_owned();
+                return Err(self.error(self.range_from(start), ErrorCode::EarlyError, message));
+            }
+            return Ok(Expression::MetaProperty(MetaProperty::ImportMeta, self.range_from(start)));
+        }
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let specifier = Box::new(self.parse_assignment_expression(parameters.with_in(true))?);
+        let mut options = None;
+        if self.proposals().import_attributes && self.eat(TokenKind::Comma) && !self.at(TokenKind::ClosingParenthesis) {
+            options = Some(Box::new(self.parse_assignment_expression(parameters.with_in(true))?));
+            self.eat(TokenKind::Comma);
+        }
+        self.expect(TokenKind::ClosingParenthesis)?;
+        Ok(Expression::ImportCall(ImportCall { specifier, options, range: self.range_from(start) }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-SuperProperty>
+    /// or <https://262.ecma-international.org/14.0/#prod-SuperCall>.
+    ///
+    /// ```plain
+    /// SuperProperty[Yield, Await] :
+    ///     super [ Expression[+In, ?Yield, ?Await] ]
+    ///     super . IdentifierName
+    ///
+    /// SuperCall[Yield, Await] :
+    ///     super Arguments[?Yield, ?Await]
+    /// ```
+    fn parse_super(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Super)?;
+        match self.kind() {
+            Some(TokenKind::OpeningParenthesis) => {
+                let arguments = self.parse_arguments(parameters)?;
+                // > It is a Syntax Error if FunctionBody Contains SuperCall is
+                // > true.
+                //
+                // Paraphrased from rules for all code but a constructor of
+                // a class with `extends`.
+                if self.super_allowed() != SuperUsage::Call {
+                    let message = "`super()` is only allowed in constructors of classes with `extends`".to_owned();
+                    return Err(self.error(self.range_from(start), ErrorCode::EarlyError, message));
+                }
+                Ok(Expression::SuperCall(SuperCall { arguments, range: self.range_from(start) }))
+            },
+            Some(TokenKind::Dot) => {
+                self.bump();
+                let range = self.range();
+                let property = MemberProperty::IdentifierName(self.parse_identifier_name()?, range);
+                self.check_super_property(start)?;
+                Ok(Expression::SuperProperty(SuperProperty { property, range: self.range_from(start) }))
+            },
+            Some(TokenKind::OpeningBracket) => {
+                self.bump();
+                let property = self.parse_expression_with(parameters.with_in(true))?;
+                self.expect(TokenKind::ClosingBracket)?;
+                let property = MemberProperty::Expression(Box::new(property));
+                self.check_super_property(start)?;
+                Ok(Expression::SuperProperty(SuperProperty { property, range: self.range_from(start) }))
+            },
+            _ => Err(self.expected("`(`, `.` or `[`")),
+        }
+    }
+
+    /// > It is a Syntax Error if FunctionBody Contains SuperProperty is
+    /// > true.
+    ///
+    /// Paraphrased from rules for all code but methods, class field
+    /// initializers and class static blocks.
+    fn check_super_property(&self, start: usize) -> Result<(), SourceCodeError> {
+        if self.super_allowed() == SuperUsage::None {
+            let message = "`super` property is only allowed in methods".to_owned();
+            return Err(self.error(self.range_from(start), ErrorCode::EarlyError, message));
+        }
+        Ok(())
+    }
+
+    /// Parse property accesses and, if allowed, calls and optional chains
+    /// following an already parsed expression.
+    ///
+    /// An optional chain like `a?.b.c` is wrapped into
+    /// an [`OptionalExpression`] as a whole.
+    fn parse_member_tail(
+        &mut self,
+        mut expression: Expression,
+        parameters: Parameters,
+        allow_call: bool
+    ) -> Result<Expression, SourceCodeError> {
+        // `() => {}` is not a `MemberExpression` to continue.
+        if matches!(expression, Expression::ArrowFunction(_)) {
+            return Ok(expression);
+        }
+        let start = expression.range().start;
+        let mut is_optional_chain = false;
+        loop {
+            let optional = allow_call && self.eat(TokenKind::OptionalChainingPunctuator);
+            is_optional_chain |= optional;
+            expression = match self.kind() {
+                Some(TokenKind::Dot) if !optional => {
+                    self.bump();
+                    let property = self.parse_member_property_name()?;
+                    Expression::MemberExpression(MemberExpression {
+                        object: Box::new(expression),
+                        property,
+                        optional,
+                        range: self.range_from(start),
+                    })
+                },
+                Some(TokenKind::OpeningBracket) => {
+                    self.bump();
+                    let property = self.parse_expression_with(parameters.with_in(true))?;
+                    self.expect(TokenKind::ClosingBracket)?;
+                    let property = MemberProperty::Expression(Box::new(property));
+                    Expression::MemberExpression(MemberExpression {
+                        object: Box::new(expression),
+                        property,
+                        optional,
+                        range: self.range_from(start),
+                    })
+                },
+                Some(TokenKind::NoSubstitutionTemplate | TokenKind::TemplateHead) => {
+                    // > It is a Syntax Error if any source text is matched by
+                    // > OptionalChain : ?. TemplateLiteral or
+                    // > OptionalChain : OptionalChain TemplateLiteral.
+                    if is_optional_chain {
+                        let message = "tagged template cannot be used in an optional chain".to_owned();
+                        return Err(self.error(self.range(), ErrorCode::EarlyError, message));
+                    }
+                    let template = self.parse_template_literal(parameters, true)?;
+                    let range = self.range_from(start);
+                    Expression::TaggedTemplate(TaggedTemplate { tag: Box::new(expression), template, range })
+                },
+                Some(TokenKind::OpeningParenthesis) if allow_call => {
+                    let arguments = self.parse_arguments(parameters)?;
+                    Expression::CallExpression(CallExpression {
+                        callee: Box::new(expression),
+                        arguments,
+                        optional,
+                        range: self.range_from(start),
+                    })
+                },
+                _ if optional => {
+                    let property = self.parse_member_property_name()?;
+                    Expression::MemberExpression(MemberExpression {
+                        object: Box::new(expression),
+                        property,
+                        optional,
+                        range: self.range_from(start),
+                    })
+                },
+                _ if is_optional_chain => {
+                    let range = self.range_from(start);
+                    return Ok(Expression::OptionalExpression(OptionalExpression { expression: Box::new(expression), range }));
+                },
+                _ => return Ok(expression),
+            };
+        }
+    }
+
+    /// Parse an expression after `@` of a decorator.
+    ///
+    /// ```plain
+    /// DecoratorMemberExpression[Yield, Await] :
+    ///     IdentifierReference[?Yield, ?Await]
+    ///     DecoratorMemberExpression[?Yield, ?Await] . IdentifierName
+    ///     DecoratorMemberExpression[?Yield, ?Await] . PrivateIdentifier
+    ///
+    /// DecoratorCallExpression[Yield, Await] :
+    ///     DecoratorMemberExpression[?Yield, ?Await] Arguments[?Yield, ?Await]
+    ///
+    /// DecoratorParenthesizedExpression[Yield, Await] :
+    ///     ( Expression[+In, ?Yield, ?Await] )
+    /// ```
+    pub(super) fn parse_decorator_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        if self.at(TokenKind::OpeningParenthesis) {
+            let cover = self.parse_cover(parameters)?;
+            return self.refine_parenthesized_expression(cover);
+        }
+        let start = self.range().start;
+        let mut expression = Expression::IdentifierReference(self.parse_identifier_reference(parameters)?);
+        while self.eat(TokenKind::Dot) {
+            let property = self.parse_member_property_name()?;
+            expression = Expression::MemberExpression(MemberExpression {
+                object: Box::new(expression),
+                property,
+                optional: false,
+                range: self.range_from(start),
+            });
+        }
+        if !self.at(TokenKind::OpeningParenthesis) {
+            return Ok(expression);
+        }
+        let arguments = self.parse_arguments(parameters)?;
+        Ok(Expression::CallExpression(CallExpression {
+            callee: Box::new(expression),
+            arguments,
+            optional: false,
+            range: self.range_from(start),
+        }))
+    }
+
+    /// Parse `IdentifierName` or `PrivateIdentifier` after `.` or `?.`.
+    fn parse_member_property_name(&mut self) -> Result<MemberProperty, SourceCodeError> {
+        let range = self.range();
+        if let Some(Token::CommonToken(CommonToken::PrivateIdentifier(name))) = self.token() {
+            let name = name.clone();
+            self.reference_private_name(name.string_value(), range.clone())?;
+            self.bump();
+            return Ok(MemberProperty::PrivateIdentifier(name, range));
+        }
+        Ok(MemberProperty::IdentifierName(self.parse_identifier_name()?, range))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-Arguments>.
+    fn parse_arguments(&mut self, parameters: Parameters) -> Result<Vec<Argument>, SourceCodeError> {
+        let parameters = parameters.with_in(true);
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let mut arguments = Vec::new();
+        while !self.eat(TokenKind::ClosingParenthesis) {
+            let start = self.range().start;
+            let argument = if self.eat(TokenKind::Ellipsis) {
+                let argument = self.parse_assignment_expression(parameters)?;
+                Argument::SpreadElement(argument, self.range_from(start))
+            } else {
+                Argument::AssignmentExpression(self.parse_assignment_expression(parameters)?)
+            };
+            arguments.push(argument);
+            if !self.at(TokenKind::ClosingParenthesis) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(arguments)
+    }
+}
+
+/************************************************
+ *
+ * 13.4 Update Expressions
+ * 13.5 Unary Operators
+ * 13.6 Exponentiation Operator
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-UpdateExpression>.
+    fn parse_update_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let prefix_operator = match self.kind() {
+            Some(TokenKind::Increment) => Some(UpdateOperator::Increment),
+            Some(TokenKind::Decrement) => Some(UpdateOperator::Decrement),
+            _ => None,
+        };
+        if let Some(operator) = prefix_operator {
+            let operator_start = self.range().start;
+            self.bump();
+            let start = self.range().start;
+            let operand = self.nested(|parser| parser.parse_unary_expression(parameters))?;
+            self.check_update_operand(&operand, start)?;
+            return Ok(Expression::UpdateExpression(UpdateExpression {
+                operator,
+                prefix: true,
+                operand: Box::new(operand),
+                range: self.range_from(operator_start),
+            }));
+        }
+        let start = self.range().start;
+        let operand = self.parse_left_hand_side_expression(parameters)?;
+        // `LeftHandSideExpression [no LineTerminator here] ++`
+        let postfix_operator = match self.kind() {
+            Some(TokenKind::Increment) if !self.newline_before() => UpdateOperator::Increment,
+            Some(TokenKind::Decrement) if !self.newline_before() => UpdateOperator::Decrement,
+            _ => return Ok(operand),
+        };
+        self.check_update_operand(&operand, start)?;
+        self.bump();
+        Ok(Expression::UpdateExpression(UpdateExpression {
+            operator: postfix_operator,
+            prefix: false,
+            operand: Box::new(operand),
+            range: self.range_from(start),
+        }))
+    }
+
+    fn check_update_operand(&self, operand: &Expression, start: usize) -> Result<(), SourceCodeError> {
+        if operand.is_left_hand_side_expression() {
+            return Ok(());
+        }
+        let message = "operand of `++` or `--` must be a left-hand-side expression".to_owned();
+        Err(self.error(start..self.previous_end(), ErrorCode::UnexpectedInput, message))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-UnaryExpression>.
+    fn parse_unary_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let operator = match self.kind() {
+            Some(TokenKind::Delete) => UnaryOperator::Delete,
+            Some(TokenKind::Void) => UnaryOperator::Void,
+            Some(TokenKind::Typeof) => UnaryOperator::Typeof,
+            Some(TokenKind::Addition) => UnaryOperator::Plus,
+            Some(TokenKind::Subtraction) => UnaryOperator::Minus,
+            Some(TokenKind::BitNot) => UnaryOperator::BitNot,
+            Some(TokenKind::Not) => UnaryOperator::Not,
+            Some(TokenKind::Await) if parameters.allow_await => return self.parse_await_expression(parameters),
+            _ => return self.parse_update_expression(parameters),
+        };
+        let start = self.range().start;
+        self.bump();
+        let operand = self.nested(|parser| parser.parse_unary_expression(parameters))?;
+        let range = self.range_from(start);
+        // > It is a Syntax Error if the UnaryExpression is contained in
+        // > strict mode code and the derived UnaryExpression is
+        // > PrimaryExpression : IdentifierReference [...]
+        //
+        // The rule applies to parenthesized identifiers too.
+        let mut target = &operand;
+        while let Expression::ParenthesizedExpression(parenthesized) = target {
+            target = &parenthesized.expression;
+        }
+        if operator == UnaryOperator::Delete && self.is_strict() && matches!(target, Expression::IdentifierReference(_)) {
+            let message = "`delete` of an identifier is not allowed in strict mode code".to_owned();
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        // > It is a Syntax Error if the derived UnaryExpression is
+        // > [...] MemberExpression . PrivateIdentifier [...]
+        if let Expression::OptionalExpression(optional) = target {
+            target = &optional.expression;
+        }
+        if operator == UnaryOperator::Delete
+            && matches!(target, Expression::MemberExpression(member) if matches!(member.property, MemberProperty::PrivateIdentifier(..)))
+        {
+            let message = "private members cannot be deleted".to_owned();
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        Ok(Expression::UnaryExpression(UnaryExpression { operator, operand: Box::new(operand), range }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-AwaitExpression>.
+    ///
+    /// ```plain
+    /// AwaitExpression[Yield] :
+    ///     await UnaryExpression[?Yield, +Await]
+    /// ```
+    fn parse_await_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Await)?;
+        let argument = self.nested(|parser| parser.parse_unary_expression(parameters))?;
+        let range = self.range_from(start);
+        self.record_yield_or_await(range.clone());
+        Ok(Expression::AwaitExpression(AwaitExpression { argument: Box::new(argument), range }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ExponentiationExpression>.
+    ///
+    /// ```plain
+    /// ExponentiationExpression[Yield, Await] :
+    ///     UnaryExpression[?Yield, ?Await]
+    ///     UpdateExpression[?Yield, ?Await] ** ExponentiationExpression[?Yield, ?Await]
+    /// ```
+    ///
+    /// So `-a ** b` is a syntax error because of its unclear meaning.
+    fn parse_exponentiation_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        let base = self.parse_unary_expression(parameters)?;
+        if !self.at(TokenKind::Exponentiation) || matches!(base, Expression::ArrowFunction(_)) {
+            return Ok(base);
+        }
+        if matches!(base, Expression::UnaryExpression(_) | Expression::AwaitExpression(_)) {
+            let message = "unary operator before `**` must be parenthesized".to_owned();
+            return Err(self.error(start..self.previous_end(), ErrorCode::UnexpectedInput, message));
+        }
+        self.bump();
+        let exponent = self.nested(|parser| parser.parse_exponentiation_expression(parameters))?;
+        Ok(Expression::BinaryExpression(BinaryExpression {
+            operator: BinaryOperator::Exponentiation,
+            left: Box::new(base),
+            right: Box::new(exponent),
+            range: self.range_from(start),
+        }))
+    }
+}
+
+/************************************************
+ *
+ * 13.7 Multiplicative Operators
+ * ...
+ * 13.13 Binary Logical Operators
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Parse a chain of binary operators with precedence of at least
+    /// a given one, from `MultiplicativeExpression` up to
+    /// `LogicalORExpression`.
+    ///
+    /// All these productions are left-associative, like
+    ///
+    /// ```plain
+    /// AdditiveExpression[Yield, Await] :
+    ///     MultiplicativeExpression[?Yield, ?Await]
+    ///     AdditiveExpression[?Yield, ?Await] + MultiplicativeExpression[?Yield, ?Await]
+    /// ```
+    ///
+    /// so they are parsed by precedence climbing instead of a method per
+    /// production.
+    fn parse_binary_expression(
+        &mut self,
+        minimum_precedence: u8,
+        parameters: Parameters
+    ) -> Result<Expression, SourceCodeError> {
+        let is_private_in = self.at(TokenKind::PrivateIdentifier)
+            && parameters.allow_in
+            && minimum_precedence <= BinaryOperator::In.precedence();
+        let mut left = if is_private_in {
+            self.parse_private_in_expression(parameters)?
+        } else {
+            self.parse_exponentiation_expression(parameters)?
+        };
+        if matches!(left, Expression::ArrowFunction(_)) {
+            return Ok(left);
+        }
+        loop {
+            let operator = self.kind().and_then(BinaryOperator::from_token);
+            let Some(operator) = operator else {
+                return Ok(left);
+            };
+            let is_excluded = operator == BinaryOperator::In && !parameters.allow_in;
+            if operator.precedence() < minimum_precedence || is_excluded {
+                return Ok(left);
+            }
+            // `ShortCircuitExpression` is either `LogicalORExpression` or
+            // `CoalesceExpression`, so one cannot be an operand of the other
+            // without parentheses.
+            if mixes_coalescence(&left, operator) {
+                let message = "`??` cannot be mixed with `&&` or `||` without parentheses".to_owned();
+                return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+            }
+            self.bump();
+            // `CoalesceExpressionHead` is `BitwiseORExpression` on the right.
+            let right_precedence = if operator == BinaryOperator::NullishCoalescence {
+                BinaryOperator::BitOr.precedence()
+            } else {
+                operator.precedence() + 1
+            };
+            let right = self.parse_binary_expression(right_precedence, parameters)?;
+            let range = left.range().start..right.range().end;
+            left = Expression::BinaryExpression(BinaryExpression {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+                range,
+            });
+        }
+    }
+
+    /// Parse the form of
+    /// <https://262.ecma-international.org/14.0/#prod-RelationalExpression>
+    /// checking for a private member.
+    ///
+    /// ```plain
+    /// RelationalExpression[In, Yield, Await] :
+    ///     [+In] PrivateIdentifier in ShiftExpression[?Yield, ?Await]
+    /// ```
+    fn parse_private_in_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        let Some(Token::CommonToken(CommonToken::PrivateIdentifier(name))) = self.token() else {
+            return Err(self.expected("private name"));
+        };
+        let name = name.clone();
+        self.reference_private_name(name.string_value(), self.range())?;
+        self.bump();
+        self.expect(TokenKind::In)?;
+        let object = self.parse_binary_expression(BinaryOperator::LeftShift.precedence(), parameters)?;
+        Ok(Expression::PrivateInExpression(PrivateInExpression {
+            name,
+            object: Box::new(object),
+            range: self.range_from(start),
+        }))
+    }
+}
+
+/// Whether applying an operator to an already parsed left operand mixes `??`
+/// with `&&` or `||`.
+fn mixes_coalescence(left: &Expression, operator: BinaryOperator) -> bool {
+    let Expression::BinaryExpression(left) = left else {
+        return false;
+    };
+    match operator {
+        BinaryOperator::NullishCoalescence => matches!(left.operator, BinaryOperator::And | BinaryOperator::Or),
+        BinaryOperator::And | BinaryOperator::Or => left.operator == BinaryOperator::NullishCoalescence,
+        _ => false,
+    }
+}
+
+/// Whether a token may start `AssignmentExpression`, so `yield` before it
+/// has an argument.
+const fn starts_expression(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::IdentifierName
+            | TokenKind::PrivateIdentifier
+            | TokenKind::NumericLiteral
+            | TokenKind::StringLiteral
+            | TokenKind::NoSubstitutionTemplate
+            | TokenKind::TemplateHead
+            | TokenKind::Addition
+            | TokenKind::Subtraction
+            | TokenKind::BitNot
+            | TokenKind::Not
+            | TokenKind::Increment
+            | TokenKind::Decrement
+            | TokenKind::Division
+            | TokenKind::DivisionAssignment
+            | TokenKind::OpeningParenthesis
+            | TokenKind::OpeningBracket
+            | TokenKind::OpeningBrace
+            | TokenKind::Await
+            | TokenKind::Class
+            | TokenKind::Delete
+            | TokenKind::False
+            | TokenKind::Function
+            | TokenKind::Import
+            | TokenKind::New
+            | TokenKind::Null
+            | TokenKind::Super
+            | TokenKind::This
+            | TokenKind::True
+            | TokenKind::Typeof
+            | TokenKind::Void
+            | TokenKind::Yield
+    )
+}
+
+/************************************************
+ *
+ * 13.14 Conditional Operator ( ? : )
+ * 13.15 Assignment Operators
+ * 13.16 Comma Operator ( , )
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ConditionalExpression>.
+    fn parse_conditional_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let condition = self.parse_binary_expression(1, parameters)?;
+        if matches!(condition, Expression::ArrowFunction(_)) || !self.eat(TokenKind::QuestionMark) {
+            return Ok(condition);
+        }
+        let consequent = self.parse_assignment_expression(parameters.with_in(true))?;
+        self.expect(TokenKind::Colon)?;
+        let alternative = self.parse_assignment_expression(parameters)?;
+        let range = condition.range().start..alternative.range().end;
+        Ok(Expression::ConditionalExpression(ConditionalExpression {
+            condition: Box::new(condition),
+            consequent: Box::new(consequent),
+            alternative: Box::new(alternative),
+            range,
+        }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-AssignmentExpression>.
+    pub(super) fn parse_assignment_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let mark = self.pending_cover_initialized_names();
+        let expression = self.parse_assignment_expression_or_pattern(parameters)?;
+        self.check_cover_initialized_names(mark)?;
+        Ok(expression)
+    }
+
+    /// Parse `AssignmentExpression` that may turn out to be a part of
+    /// a destructuring pattern, leaving its `CoverInitializedName`s to
+    /// refine or report for a caller.
+    fn parse_assignment_expression_or_pattern(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        self.nested(|parser| parser.parse_assignment_expression_or_pattern_inner(parameters))
+    }
+
+    fn parse_assignment_expression_or_pattern_inner(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        if parameters.allow_yield && self.at(TokenKind::Yield) {
+            return self.parse_yield_expression(parameters);
+        }
+        self.mark_potential_arrow();
+        let start = self.range().start;
+        let mark = self.pending_cover_initialized_names();
+        let target = self.parse_conditional_expression(parameters)?;
+        if matches!(target, Expression::ArrowFunction(_)) {
+            return Ok(target);
+        }
+        let Some(operator) = self.kind().and_then(AssignmentOperator::from_token) else {
+            return Ok(target);
+        };
+        if !target.is_left_hand_side_expression() {
+            let message = "left side of an assignment must be a left-hand-side expression".to_owned();
+            return Err(self.error(start..self.previous_end(), ErrorCode::UnexpectedInput, message));
+        }
+        // `{ a = 1 } = b` is `ObjectAssignmentPattern` covered by
+        // an `ObjectLiteral`.
+        let is_pattern = matches!(target, Expression::ObjectLiteral(_) | Expression::ArrayLiteral(_));
+        if operator == AssignmentOperator::Assignment && is_pattern {
+            self.refine_cover_initialized_names(mark);
+        }
+        self.bump();
+        let value = self.parse_assignment_expression(parameters)?;
+        Ok(Expression::AssignmentExpression(AssignmentExpression {
+            operator,
+            target: Box::new(target),
+            value: Box::new(value),
+            range: self.range_from(start),
+        }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-YieldExpression>.
+    ///
+    /// ```plain
+    /// YieldExpression[In, Await] :
+    ///     yield
+    ///     yield [no LineTerminator here] AssignmentExpression[?In, +Yield, ?Await]
+    ///     yield [no LineTerminator here] * AssignmentExpression[?In, +Yield, ?Await]
+    /// ```
+    fn parse_yield_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Yield)?;
+        let has_argument = !self.newline_before()
+            && self.kind().is_some_and(|kind| kind == TokenKind::Multiplication || starts_expression(kind));
+        let is_delegate = has_argument && self.eat(TokenKind::Multiplication);
+        let argument = if has_argument {
+            Some(Box::new(self.parse_assignment_expression(parameters)?))
+        } else {
+            None
+        };
+        let range = self.range_from(start);
+        self.record_yield_or_await(range.clone());
+        Ok(Expression::YieldExpression(YieldExpression { is_delegate, argument, range }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-Expression>.
+    pub(super) fn parse_expression_with(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        let first = self.parse_assignment_expression(parameters)?;
+        if !self.at(TokenKind::Comma) {
+            return Ok(first);
+        }
+        let start = first.range().start;
+        let mut expressions = vec![first];
+        while self.eat(TokenKind::Comma) {
+            expressions.push(self.parse_assignment_expression(parameters)?);
+        }
+        Ok(Expression::CommaExpression(CommaExpression { expressions, range: self.range_from(start) }))
+    }
+
+    /// Parse `Expression[+In, ~Yield, ~Await]` at the current position.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the source text does
+    /// not start with an expression.
+    pub fn parse_expression(&mut self) -> Result<Expression, SourceCodeError> {
+        self.parse_expression_with(Parameters::default().with_in(true))
+    }
+}
@@ -0,0 +1,1114 @@
+//! Syntactic grammar of functions and classes.
+//!
+//! Implements <https://262.ecma-international.org/14.0/#sec-ecmascript-language-functions-and-classes>.
+
+use super::expressions::{
+    Argument,
+    ArrayElement,
+    AssignmentOperator,
+    Cover,
+    Expression,
+    IdentifierReference,
+    PropertyDefinition,
+    PropertyName,
+};
+use super::parser::{Parameters, Parser, SuperUsage};
+use super::statements::{
+    ArrayBindingPattern,
+    Binding,
+    BindingElement,
+    BindingIdentifier,
+    BindingProperty,
+    ObjectBindingPattern,
+    StatementListItem,
+};
+use crate::lexical_grammar::{CommonToken, ContextualKeyword, PrivateIdentifier, Token, TokenKind};
+use crate::early_errors::check_function_body;
+use crate::{Diagnostics, ErrorCode, SourceCodeError};
+use std::ops::Range;
+use std::rc::Rc;
+
+/************************************************
+ *
+ * Function tree
+ *
+ ************************************************/
+
+/// <https://262.ecma-international.org/14.0/#prod-FunctionDeclaration> or
+/// <https://262.ecma-international.org/14.0/#prod-FunctionExpression>, or
+/// their generator and async counterparts.
+// Each property is independent of the others, so they are flags rather
+// than a state machine.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Function {
+    /// Whether the function is a `FunctionDeclaration` binding its name in
+    /// an enclosing scope rather than a `FunctionExpression`.
+    pub is_declaration: bool,
+    /// Whether the function is `function*`.
+    pub is_generator: bool,
+    /// Whether the function is `async function`.
+    pub is_async: bool,
+    /// `None` for an anonymous function expression.
+    pub name: Option<BindingIdentifier>,
+    /// Whether the function is a plain `FunctionDeclaration` directly in
+    /// a block or a `switch` case, which Annex B also binds as `var` in
+    /// an enclosing function unless that conflicts with other declarations.
+    ///
+    /// See <https://262.ecma-international.org/14.0/#sec-block-level-function-declarations-web-legacy-compatibility-semantics>.
+    /// Always `false` without the `annex-b` feature.
+    pub is_legacy_block_function: bool,
+    /// Whether the function is strict mode code, being in strict mode code
+    /// or starting its body with a `"use strict"` directive.
+    pub is_strict: bool,
+    pub parameters: FormalParameters,
+    /// `FunctionBody`, empty if deferred.
+    pub body: Vec<StatementListItem>,
+    /// `Some` if the body is skipped by a parser with
+    /// [`Parser::with_deferred_function_bodies`].
+    pub deferred_body: Option<DeferredBody>,
+    pub range: Range<usize>,
+}
+
+/// `{ FunctionBody }` skipped by a parser with
+/// [`Parser::with_deferred_function_bodies`], to parse with
+/// [`Parser::parse_deferred_body`] when needed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeferredBody {
+    /// Covering the braces.
+    pub range: Range<usize>,
+    /// `[Yield]`, `[Await]` and `[Return]` of the body.
+    parameters: Parameters,
+    allow_new_target: bool,
+    allow_super: SuperUsage,
+    is_module: bool,
+    /// Whether the body is strict mode code; a skipped body never starts
+    /// with a directive, so it inherits this from the enclosing code.
+    is_strict: bool,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-FormalParameters>
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FormalParameters {
+    pub parameters: Vec<BindingElement>,
+    /// `FunctionRestParameter`, like `b` in `(a, ...b)`.
+    pub rest: Option<Binding>,
+    pub range: Range<usize>,
+}
+
+impl FormalParameters {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-issimpleparameterlist>:
+    /// whether all parameters are identifiers without initializers.
+    #[must_use]
+    pub fn is_simple(&self) -> bool {
+        self.rest.is_none()
+            && self.parameters.iter().all(|parameter| {
+                parameter.initializer.is_none() && matches!(parameter.target, Binding::BindingIdentifier(_))
+            })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-boundnames>
+    /// of all parameters, in source order.
+    #[must_use]
+    pub fn bound_names(&self) -> Vec<&BindingIdentifier> {
+        let targets = self.parameters.iter().map(|parameter| &parameter.target);
+        targets.chain(&self.rest).flat_map(Binding::bound_names).collect()
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ArrowFunction> or
+/// <https://262.ecma-international.org/14.0/#prod-AsyncArrowFunction>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArrowFunction {
+    /// Whether the function is
+    /// <https://262.ecma-international.org/14.0/#prod-AsyncArrowFunction>.
+    pub is_async: bool,
+    /// `ArrowParameters`; a single `BindingIdentifier` like `a` in `a => b`
+    /// is stored as a one-element list.
+    pub parameters: FormalParameters,
+    /// Whether the function is strict mode code.
+    pub is_strict: bool,
+    /// `{}` with no statements if deferred.
+    pub body: ConciseBody,
+    /// `Some` if `{ FunctionBody }` is skipped by a parser with
+    /// [`Parser::with_deferred_function_bodies`].
+    pub deferred_body: Option<DeferredBody>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ConciseBody>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConciseBody {
+    /// `ExpressionBody`, like `b` in `a => b`.
+    Expression(Box<Expression>),
+    /// `{ FunctionBody }`, covering the braces.
+    FunctionBody(Vec<StatementListItem>, Range<usize>),
+}
+
+impl ConciseBody {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Expression(expression) => expression.range(),
+            Self::FunctionBody(_, range) => range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-MethodDefinition>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MethodDefinition {
+    pub kind: MethodKind,
+    pub name: ClassElementName,
+    pub parameters: FormalParameters,
+    /// Whether the method is strict mode code, being in a class or
+    /// starting its body with a `"use strict"` directive.
+    pub is_strict: bool,
+    /// `FunctionBody`, empty if deferred.
+    pub body: Vec<StatementListItem>,
+    /// `Some` if the body is skipped by a parser with
+    /// [`Parser::with_deferred_function_bodies`].
+    pub deferred_body: Option<DeferredBody>,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MethodKind {
+    Method,
+    /// <https://262.ecma-international.org/14.0/#prod-GeneratorMethod>,
+    /// like `*a() {}`.
+    Generator,
+    /// <https://262.ecma-international.org/14.0/#prod-AsyncMethod>, like
+    /// `async a() {}`.
+    Async,
+    /// <https://262.ecma-international.org/14.0/#prod-AsyncGeneratorMethod>,
+    /// like `async *a() {}`.
+    AsyncGenerator,
+    /// `get ClassElementName ( ) { FunctionBody }`
+    Getter,
+    /// `set ClassElementName ( PropertySetParameterList ) { FunctionBody }`
+    Setter,
+}
+
+impl MethodKind {
+    /// `[Yield]` and `[Await]` of parameters and a body of the method.
+    fn function_parameters(self) -> Parameters {
+        let (is_generator, is_async) = match self {
+            Self::Generator => (true, false),
+            Self::Async => (false, true),
+            Self::AsyncGenerator => (true, true),
+            Self::Method | Self::Getter | Self::Setter => (false, false),
+        };
+        function_parameters(is_generator, is_async)
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ClassElementName>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClassElementName {
+    PropertyName(PropertyName),
+    PrivateIdentifier(PrivateIdentifier, Range<usize>),
+}
+
+impl ClassElementName {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::PropertyName(name) => name.range(),
+            Self::PrivateIdentifier(_, range) => range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ClassDeclaration> or
+/// <https://262.ecma-international.org/14.0/#prod-ClassExpression>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Class {
+    /// `@a` before `class`, always empty without
+    /// [`super::Proposals::decorators`].
+    pub decorators: Vec<Decorator>,
+    /// Whether the class is a `ClassDeclaration` binding its name in
+    /// an enclosing scope rather than a `ClassExpression`.
+    pub is_declaration: bool,
+    /// `None` for an anonymous class expression.
+    pub name: Option<BindingIdentifier>,
+    /// `ClassHeritage`, like `b` in `class a extends b {}`.
+    pub heritage: Option<Box<Expression>>,
+    /// `ClassBody` without empty `;` elements.
+    pub elements: Vec<ClassElement>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ClassElement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClassElement {
+    /// A method with decorators and `static` if any; `range` covers them
+    /// too.
+    MethodDefinition { decorators: Vec<Decorator>, is_static: bool, method: Rc<MethodDefinition>, range: Range<usize> },
+    /// `FieldDefinition ;`
+    FieldDefinition {
+        decorators: Vec<Decorator>,
+        is_static: bool,
+        name: ClassElementName,
+        initializer: Option<Rc<Expression>>,
+        range: Range<usize>,
+    },
+    /// `static { ClassStaticBlockStatementList }`
+    ClassStaticBlock(Rc<[StatementListItem]>, Range<usize>),
+}
+
+/// `@a` of the decorators proposal, parsed only with
+/// [`super::Proposals::decorators`].
+///
+/// See <https://github.com/tc39/proposal-decorators>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Decorator {
+    /// An expression after `@`: a name with `.` accesses and an optional
+    /// call at the end, or a parenthesized expression.
+    pub expression: Expression,
+    pub range: Range<usize>,
+}
+
+impl ClassElement {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::MethodDefinition { range, .. } | Self::FieldDefinition { range, .. } | Self::ClassStaticBlock(_, range) => {
+                range.clone()
+            },
+        }
+    }
+}
+
+/************************************************
+ *
+ * 15.1 Parameter Lists
+ * 15.2 Function Definitions
+ * 15.5 Generator Function Definitions
+ * 15.6 Async Generator Function Definitions
+ * 15.8 Async Function Definitions
+ *
+ ************************************************/
+
+/// `[Yield]` and `[Await]` of parameters and a body of a function.
+fn function_parameters(is_generator: bool, is_async: bool) -> Parameters {
+    Parameters::default().with_yield(is_generator).with_await(is_async)
+}
+
+impl Parser<'_> {
+    /// Parse `( FormalParameters )`.
+    ///
+    /// ```plain
+    /// FormalParameters[Yield, Await] :
+    ///     [empty]
+    ///     FunctionRestParameter[?Yield, ?Await]
+    ///     FormalParameterList[?Yield, ?Await]
+    ///     FormalParameterList[?Yield, ?Await] ,
+    ///     FormalParameterList[?Yield, ?Await] , FunctionRestParameter[?Yield, ?Await]
+    /// ```
+    fn parse_formal_parameters_with(&mut self, parameters: Parameters) -> Result<FormalParameters, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let mut formal_parameters = FormalParameters::default();
+        while !self.eat(TokenKind::ClosingParenthesis) {
+            if self.eat(TokenKind::Ellipsis) {
+                // A rest parameter is never followed by a comma.
+                formal_parameters.rest = Some(self.parse_binding(parameters)?);
+                self.expect(TokenKind::ClosingParenthesis)?;
+                break;
+            }
+            formal_parameters.parameters.push(self.parse_binding_element(parameters)?);
+            if !self.at(TokenKind::ClosingParenthesis) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        formal_parameters.range = self.range_from(start);
+        Ok(formal_parameters)
+    }
+
+    /// Parse `( FormalParameters )` rejecting `YieldExpression`s and
+    /// `AwaitExpression`s in them.
+    ///
+    /// > It is a Syntax Error if FormalParameters Contains YieldExpression
+    /// > is true.
+    /// > It is a Syntax Error if FormalParameters Contains AwaitExpression
+    /// > is true.
+    fn parse_function_parameters(&mut self, parameters: Parameters) -> Result<FormalParameters, SourceCodeError> {
+        let start = self.range().start;
+        let formal_parameters = self.parse_formal_parameters_with(parameters)?;
+        self.check_no_yield_or_await_since(start)?;
+        Ok(formal_parameters)
+    }
+
+    /// Report a `YieldExpression` or `AwaitExpression` in function
+    /// parameters starting at a given offset.
+    fn check_no_yield_or_await_since(&self, start: usize) -> Result<(), SourceCodeError> {
+        let Some(range) = self.yield_or_await_since(start) else {
+            return Ok(());
+        };
+        let message = "`yield` and `await` expressions are not allowed in parameters".to_owned();
+        Err(self.error(range, ErrorCode::EarlyError, message))
+    }
+
+    /// Report early errors of a function that depend on strictness of its
+    /// body, known only after its name and parameters are parsed.
+    ///
+    /// `requires_unique` is set for `UniqueFormalParameters` of methods and
+    /// for `ArrowFormalParameters`.
+    fn check_function_parameters(
+        &self,
+        name: Option<&BindingIdentifier>,
+        parameters: &FormalParameters,
+        (is_strict, has_use_strict): (bool, bool),
+        requires_unique: bool
+    ) -> Result<(), SourceCodeError> {
+        let is_simple = parameters.is_simple();
+        // > It is a Syntax Error if FunctionBodyContainsUseStrict of
+        // > FunctionBody is true and IsSimpleParameterList of
+        // > FormalParameters is false.
+        if has_use_strict && !is_simple {
+            let message = "a function with non-simple parameters cannot have a \"use strict\" directive".to_owned();
+            return Err(self.error(parameters.range.clone(), ErrorCode::EarlyError, message));
+        }
+        let bound_names = parameters.bound_names();
+        // A name and parameters before a directive are strict mode code
+        // too, so they are checked again.
+        if is_strict {
+            for identifier in name.into_iter().chain(bound_names.iter().copied()) {
+                self.check_strict_binding_identifier(identifier)?;
+            }
+        }
+        // > It is a Syntax Error if IsSimpleParameterList of
+        // > FormalParameterList is false and BoundNames of
+        // > FormalParameterList contains any duplicate elements.
+        //
+        // > If the source text matched by FormalParameters is strict mode
+        // > code, the Early Error rules for UniqueFormalParameters :
+        // > FormalParameters are applied.
+        if requires_unique || is_strict || !is_simple {
+            for (index, identifier) in bound_names.iter().enumerate() {
+                if bound_names[..index].iter().any(|previous| previous.name == identifier.name) {
+                    let message = format!("duplicate parameter `{}`", identifier.name);
+                    return Err(self.error(identifier.range.clone(), ErrorCode::EarlyError, message));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse `{ FunctionBody }`, or skip it if the parser defers function
+    /// bodies.
+    ///
+    /// Also returns whether the body starts with a `"use strict"`
+    /// directive.
+    fn parse_function_body_with(
+        &mut self,
+        parameters: Parameters
+    ) -> Result<(Vec<StatementListItem>, Option<DeferredBody>, bool), SourceCodeError> {
+        let parameters = parameters.with_return(true);
+        if let Some(range) = self.skip_function_body() {
+            let deferred = DeferredBody {
+                range,
+                parameters,
+                allow_new_target: self.new_target_allowed(),
+                allow_super: self.super_allowed(),
+                is_module: self.is_module(),
+                is_strict: self.is_strict(),
+            };
+            return Ok((Vec::new(), Some(deferred), false));
+        }
+        self.nested(|parser| {
+            parser.expect(TokenKind::OpeningBrace)?;
+            let outer_strict = parser.is_strict();
+            let (mut body, has_use_strict) = parser.parse_directive_prologue(true, parameters)?;
+            body.extend(parser.parse_statement_list(parameters)?);
+            parser.set_strict(outer_strict);
+            parser.expect_closing_brace()?;
+            Ok((body, None, has_use_strict))
+        })
+    }
+
+    /// Parse `FormalParameters` without parentheses at the current position,
+    /// like the parameter text given to the `Function` constructor.
+    ///
+    /// `is_generator` and `is_async` choose which of `FormalParameters`,
+    /// `GeneratorParameters` or their async counterparts is parsed. Early
+    /// errors depending on a function body, like a `"use strict"` directive
+    /// there, are not reported.
+    ///
+    /// See <https://262.ecma-international.org/14.0/#sec-createdynamicfunction>.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the source text does
+    /// not start with a parameter list.
+    pub fn parse_formal_parameters(
+        &mut self,
+        is_generator: bool,
+        is_async: bool
+    ) -> Result<FormalParameters, SourceCodeError> {
+        let parameters = function_parameters(is_generator, is_async);
+        let start = self.range().start;
+        let outer = self.enter_function();
+        let mut formal_parameters = FormalParameters::default();
+        while !self.is_at_end() && !self.at(TokenKind::ClosingParenthesis) {
+            if self.eat(TokenKind::Ellipsis) {
+                formal_parameters.rest = Some(self.parse_binding(parameters)?);
+                break;
+            }
+            formal_parameters.parameters.push(self.parse_binding_element(parameters)?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        formal_parameters.range = start..self.previous_end().max(start);
+        self.check_no_yield_or_await_since(start)?;
+        self.leave_function(outer);
+        self.check_function_parameters(None, &formal_parameters, (self.is_strict(), false), false)?;
+        Ok(formal_parameters)
+    }
+
+    /// Parse `FunctionBody` without braces at the current position, like
+    /// the body text given to the `Function` constructor, returning it and
+    /// whether it starts with a `"use strict"` directive.
+    ///
+    /// `is_generator` and `is_async` choose which of `FunctionBody`,
+    /// `GeneratorBody` or their async counterparts is parsed. The body ends
+    /// before an unmatched `}` or at the end of input.
+    ///
+    /// See <https://262.ecma-international.org/14.0/#sec-createdynamicfunction>.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the source text does
+    /// not start with a function body.
+    pub fn parse_function_body(
+        &mut self,
+        is_generator: bool,
+        is_async: bool
+    ) -> Result<(Vec<StatementListItem>, bool), SourceCodeError> {
+        let parameters = function_parameters(is_generator, is_async).with_return(true);
+        let outer = self.enter_function();
+        let outer_new_target = self.enter_new_target_scope();
+        let outer_super = self.enter_super_scope(SuperUsage::None);
+        let outer_strict = self.is_strict();
+        let (mut body, has_use_strict) = self.parse_directive_prologue(true, parameters)?;
+        body.extend(self.parse_statement_list(parameters)?);
+        let is_strict = self.is_strict();
+        self.set_strict(outer_strict);
+        self.leave_super_scope(outer_super);
+        self.leave_new_target_scope(outer_new_target);
+        self.leave_function(outer);
+        let mut early_errors = Diagnostics::new();
+        check_function_body(self.source(), &body, is_strict, &mut early_errors);
+        self.report_all(early_errors)?;
+        Ok((body, has_use_strict))
+    }
+
+    /// Parse a function body skipped before by this or another parser of
+    /// the same source text.
+    ///
+    /// Function bodies nested in it are skipped too if this parser defers
+    /// them. The parser is left right after the body.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the body is not
+    /// a correct `FunctionBody`.
+    pub fn parse_deferred_body(&mut self, body: &DeferredBody) -> Result<Vec<StatementListItem>, SourceCodeError> {
+        self.seek(body.range.start, body.is_module);
+        let outer_strict = self.is_strict();
+        self.set_strict(body.is_strict);
+        let outer = self.enter_function();
+        let outer_new_target = self.enter_new_target_scope();
+        // An arrow function takes it from the code enclosing the function.
+        self.leave_new_target_scope(body.allow_new_target);
+        let outer_super = self.enter_super_scope(body.allow_super);
+        self.expect(TokenKind::OpeningBrace)?;
+        let statements = self.parse_statement_list(body.parameters)?;
+        self.set_strict(outer_strict);
+        self.expect(TokenKind::RightBracePunctuator)?;
+        self.leave_super_scope(outer_super);
+        self.leave_new_target_scope(outer_new_target);
+        self.leave_function(outer);
+        // Tokens of a skipped body are recognized with guessed goal symbols.
+        if self.previous_end() != body.range.end {
+            let message = "function body ends elsewhere than found when it was skipped".to_owned();
+            return Err(self.error(self.range_from(body.range.start), ErrorCode::UnexpectedInput, message));
+        }
+        let mut early_errors = Diagnostics::new();
+        check_function_body(self.source(), &statements, body.is_strict, &mut early_errors);
+        self.report_all(early_errors)?;
+        Ok(statements)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-FunctionDeclaration>
+    /// or <https://262.ecma-international.org/14.0/#prod-FunctionExpression>,
+    /// or their generator and async counterparts like
+    /// <https://262.ecma-international.org/14.0/#prod-AsyncGeneratorDeclaration>.
+    ///
+    /// Only a function expression may be anonymous. A declaration binds its
+    /// name in an enclosing function, so the name follows its `[Yield]` and
+    /// `[Await]`, while an expression binds it in its own scope:
+    ///
+    /// ```plain
+    /// GeneratorDeclaration[Yield, Await, Default] :
+    ///     function * BindingIdentifier[?Yield, ?Await] ( FormalParameters[+Yield, ~Await] ) { GeneratorBody }
+    ///
+    /// GeneratorExpression :
+    ///     function * BindingIdentifier[+Yield, ~Await]opt ( FormalParameters[+Yield, ~Await] ) { GeneratorBody }
+    /// ```
+    pub(super) fn parse_function(&mut self, is_declaration: bool, parameters: Parameters) -> Result<Function, SourceCodeError> {
+        let start = self.range().start;
+        // `async [no LineTerminator here] function`
+        let is_async = self.at_async_function();
+        if is_async {
+            self.bump();
+        }
+        self.expect(TokenKind::Function)?;
+        let is_generator = self.eat(TokenKind::Multiplication);
+        let function_parameters = function_parameters(is_generator, is_async);
+        let name = if is_declaration {
+            Some(self.parse_binding_identifier(parameters)?)
+        } else if !self.at(TokenKind::OpeningParenthesis) {
+            Some(self.parse_binding_identifier(function_parameters)?)
+        } else {
+            None
+        };
+        let outer = self.enter_function();
+        let outer_new_target = self.enter_new_target_scope();
+        let outer_super = self.enter_super_scope(SuperUsage::None);
+        let formal_parameters = self.parse_function_parameters(function_parameters)?;
+        let outer_strict = self.is_strict();
+        let (body, deferred_body, has_use_strict) = self.parse_function_body_with(function_parameters)?;
+        let is_strict = outer_strict || has_use_strict;
+        self.check_function_parameters(name.as_ref(), &formal_parameters, (is_strict, has_use_strict), false)?;
+        self.leave_super_scope(outer_super);
+        self.leave_new_target_scope(outer_new_target);
+        self.leave_function(outer);
+        Ok(Function {
+            is_declaration,
+            is_generator,
+            is_async,
+            name,
+            is_legacy_block_function: false,
+            is_strict,
+            parameters: formal_parameters,
+            body,
+            deferred_body,
+            range: self.range_from(start),
+        })
+    }
+}
+
+/************************************************
+ *
+ * 15.3 Arrow Function Definitions
+ *
+ ************************************************/
+
+/// Reinterpret a cover as `ArrowFormalParameters`, if its items match.
+fn formal_parameters(cover: Cover) -> Option<FormalParameters> {
+    let mut formal_parameters = FormalParameters { range: cover.range, ..FormalParameters::default() };
+    let count = cover.items.len();
+    for (index, item) in cover.items.into_iter().enumerate() {
+        match item {
+            Argument::AssignmentExpression(expression) => formal_parameters.parameters.push(binding_element(expression)?),
+            // A rest parameter is the last one and is not followed by a comma.
+            Argument::SpreadElement(expression, _) if index + 1 == count && !cover.has_trailing_comma => {
+                formal_parameters.rest = Some(binding(expression)?);
+            },
+            Argument::SpreadElement(..) => return None,
+        }
+    }
+    Some(formal_parameters)
+}
+
+/// Reinterpret an expression as `BindingElement`, like `a = 1` in
+/// `(a = 1) => a`.
+fn binding_element(expression: Expression) -> Option<BindingElement> {
+    match expression {
+        Expression::AssignmentExpression(assignment) if assignment.operator == AssignmentOperator::Assignment => {
+            let target = binding(*assignment.target)?;
+            Some(BindingElement { target, initializer: Some(*assignment.value), range: assignment.range })
+        },
+        expression => {
+            let range = expression.range();
+            Some(BindingElement { target: binding(expression)?, initializer: None, range })
+        },
+    }
+}
+
+/// Reinterpret an expression as `BindingIdentifier` or `BindingPattern`,
+/// like `{ a, b: [c] }` in `({ a, b: [c] }) => a`.
+fn binding(expression: Expression) -> Option<Binding> {
+    match expression {
+        Expression::IdentifierReference(reference) => Some(Binding::BindingIdentifier(binding_identifier(reference))),
+        Expression::ArrayLiteral(array) => {
+            let mut pattern = ArrayBindingPattern { elements: Vec::new(), rest: None, range: array.range };
+            let count = array.elements.len();
+            for (index, element) in array.elements.into_iter().enumerate() {
+                match element {
+                    ArrayElement::Elision(_) => pattern.elements.push(None),
+                    ArrayElement::AssignmentExpression(element) => pattern.elements.push(Some(binding_element(element)?)),
+                    ArrayElement::SpreadElement(element, _) if index + 1 == count => {
+                        pattern.rest = Some(Box::new(binding(element)?));
+                    },
+                    ArrayElement::SpreadElement(..) => return None,
+                }
+            }
+            Some(Binding::ArrayBindingPattern(pattern))
+        },
+        Expression::ObjectLiteral(object) => {
+            let mut pattern = ObjectBindingPattern { properties: Vec::new(), rest: None, range: object.range };
+            let count = object.properties.len();
+            for (index, property) in object.properties.into_iter().enumerate() {
+                let property = match property {
+                    PropertyDefinition::IdentifierReference(reference) => BindingProperty::SingleNameBinding {
+                        name: binding_identifier(reference),
+                        initializer: None,
+                    },
+                    PropertyDefinition::CoverInitializedName { name, initializer } => BindingProperty::SingleNameBinding {
+                        name: binding_identifier(name),
+                        initializer: Some(initializer),
+                    },
+                    PropertyDefinition::Property { name, value } => {
+                        BindingProperty::Property { name, value: binding_element(value)? }
+                    },
+                    PropertyDefinition::SpreadProperty(Expression::IdentifierReference(reference), _) if index + 1 == count => {
+                        pattern.rest = Some(binding_identifier(reference));
+                        continue;
+                    },
+                    PropertyDefinition::SpreadProperty(..) | PropertyDefinition::MethodDefinition(_) => return None,
+                };
+                pattern.properties.push(property);
+            }
+            Some(Binding::ObjectBindingPattern(pattern))
+        },
+        _ => None,
+    }
+}
+
+/// Reinterpret `IdentifierReference` as `BindingIdentifier`.
+fn binding_identifier(reference: IdentifierReference) -> BindingIdentifier {
+    BindingIdentifier { name: reference.name, range: reference.range }
+}
+
+impl Parser<'_> {
+    /// Reinterpret `CoverParenthesizedExpressionAndArrowParameterList` or
+    /// `CoverCallExpressionAndAsyncArrowHead` followed by `=>` as arrow
+    /// function parameters.
+    ///
+    /// ```plain
+    /// ArrowFormalParameters[Yield, Await] :
+    ///     ( UniqueFormalParameters[?Yield, ?Await] )
+    ///
+    /// AsyncArrowHead :
+    ///     async [no LineTerminator here] ArrowFormalParameters[~Yield, +Await]
+    /// ```
+    ///
+    /// > It is a Syntax Error if ArrowParameters Contains YieldExpression is
+    /// > true.
+    /// > It is a Syntax Error if ArrowParameters Contains AwaitExpression is
+    /// > true.
+    pub(super) fn refine_arrow_parameters(&mut self, cover: Cover) -> Result<FormalParameters, SourceCodeError> {
+        self.check_no_yield_or_await_since(cover.range.start)?;
+        let (range, mark) = (cover.range.clone(), cover.mark);
+        let Some(formal_parameters) = formal_parameters(cover) else {
+            let message = "invalid arrow function parameters".to_owned();
+            return Err(self.error(range, ErrorCode::UnexpectedInput, message));
+        };
+        self.refine_cover_initialized_names(mark);
+        Ok(formal_parameters)
+    }
+
+    /// Parse `=> ConciseBody` of
+    /// <https://262.ecma-international.org/14.0/#prod-ArrowFunction> or
+    /// <https://262.ecma-international.org/14.0/#prod-AsyncArrowFunction>
+    /// with already parsed parameters starting at a given offset.
+    ///
+    /// ```plain
+    /// ArrowFunction[In, Yield, Await] :
+    ///     ArrowParameters[?Yield, ?Await] [no LineTerminator here] => ConciseBody[?In]
+    ///
+    /// ConciseBody[In] :
+    ///     [lookahead ≠ {] ExpressionBody[?In, ~Await]
+    ///     { FunctionBody[~Yield, ~Await] }
+    ///
+    /// AsyncConciseBody[In] :
+    ///     [lookahead ≠ {] ExpressionBody[?In, +Await]
+    ///     { AsyncFunctionBody }
+    /// ```
+    pub(super) fn parse_arrow_function(
+        &mut self,
+        start: usize,
+        is_async: bool,
+        arrow_parameters: FormalParameters,
+        parameters: Parameters
+    ) -> Result<ArrowFunction, SourceCodeError> {
+        if self.at(TokenKind::FunctionArrow) && self.newline_before() {
+            let message = "line terminator is not allowed before `=>`".to_owned();
+            return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+        }
+        self.expect(TokenKind::FunctionArrow)?;
+        let function_parameters = function_parameters(false, is_async);
+        let outer = self.enter_function();
+        let outer_strict = self.is_strict();
+        let (body, deferred_body, has_use_strict) = if self.at(TokenKind::OpeningBrace) {
+            let body_start = self.range().start;
+            let (body, deferred_body, has_use_strict) = self.parse_function_body_with(function_parameters)?;
+            (ConciseBody::FunctionBody(body, self.range_from(body_start)), deferred_body, has_use_strict)
+        } else {
+            let parameters = function_parameters.with_in(parameters.allow_in);
+            (ConciseBody::Expression(Box::new(self.parse_assignment_expression(parameters)?)), None, false)
+        };
+        let is_strict = outer_strict || has_use_strict;
+        self.check_function_parameters(None, &arrow_parameters, (is_strict, has_use_strict), true)?;
+        self.leave_function(outer);
+        Ok(ArrowFunction {
+            is_async,
+            parameters: arrow_parameters,
+            is_strict,
+            body,
+            deferred_body,
+            range: self.range_from(start),
+        })
+    }
+}
+
+/************************************************
+ *
+ * 15.4 Method Definitions
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Consume `async`, `*`, `get` or `set` if it starts a special method
+    /// rather than names a property itself, like in `{ get: 1 }` or
+    /// `{ get() {} }`.
+    pub(super) fn parse_method_kind(&mut self) -> MethodKind {
+        let kind = if self.at_contextual(ContextualKeyword::Async) {
+            MethodKind::Async
+        } else if self.at_contextual(ContextualKeyword::Get) {
+            MethodKind::Getter
+        } else if self.at_contextual(ContextualKeyword::Set) {
+            MethodKind::Setter
+        } else if self.eat(TokenKind::Multiplication) {
+            return MethodKind::Generator;
+        } else {
+            return MethodKind::Method;
+        };
+        let (next, newline_before) = self.peek_next();
+        // `async [no LineTerminator here] ClassElementName`
+        let is_name = (kind == MethodKind::Async && newline_before) || matches!(
+            next,
+            None | Some(
+                TokenKind::OpeningParenthesis
+                    | TokenKind::Assignment
+                    | TokenKind::Semicolon
+                    | TokenKind::Comma
+                    | TokenKind::Colon
+                    | TokenKind::RightBracePunctuator
+            )
+        );
+        if is_name {
+            return MethodKind::Method;
+        }
+        self.bump();
+        if kind == MethodKind::Async && self.eat(TokenKind::Multiplication) {
+            return MethodKind::AsyncGenerator;
+        }
+        kind
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ClassElementName>.
+    fn parse_class_element_name(&mut self, parameters: Parameters) -> Result<ClassElementName, SourceCodeError> {
+        if let Some(Token::CommonToken(CommonToken::PrivateIdentifier(name))) = self.token() {
+            let name = name.clone();
+            let range = self.range();
+            self.bump();
+            return Ok(ClassElementName::PrivateIdentifier(name, range));
+        }
+        Ok(ClassElementName::PropertyName(self.parse_property_name(parameters)?))
+    }
+
+    /// Parse parameters and a body of
+    /// <https://262.ecma-international.org/14.0/#prod-MethodDefinition>
+    /// following its already parsed kind and name starting at a given
+    /// offset, with `super` forms its parameters and body may contain.
+    pub(super) fn parse_method_definition(
+        &mut self,
+        start: usize,
+        kind: MethodKind,
+        name: ClassElementName,
+        allow_super: SuperUsage
+    ) -> Result<MethodDefinition, SourceCodeError> {
+        let function_parameters = kind.function_parameters();
+        let outer = self.enter_function();
+        let outer_new_target = self.enter_new_target_scope();
+        let outer_super = self.enter_super_scope(allow_super);
+        let parameters = self.parse_function_parameters(function_parameters)?;
+        let arity_error = match kind {
+            MethodKind::Getter if !parameters.parameters.is_empty() || parameters.rest.is_some() => {
+                Some("a getter must have no parameters")
+            },
+            MethodKind::Setter if parameters.parameters.len() != 1 || parameters.rest.is_some() => {
+                Some("a setter must have exactly one parameter")
+            },
+            _ => None,
+        };
+        if let Some(message) = arity_error {
+            return Err(self.error(parameters.range, ErrorCode::UnexpectedInput, message.to_owned()));
+        }
+        let outer_strict = self.is_strict();
+        let (body, deferred_body, has_use_strict) = self.parse_function_body_with(function_parameters)?;
+        let is_strict = outer_strict || has_use_strict;
+        self.check_function_parameters(None, &parameters, (is_strict, has_use_strict), true)?;
+        self.leave_super_scope(outer_super);
+        self.leave_new_target_scope(outer_new_target);
+        self.leave_function(outer);
+        Ok(MethodDefinition { kind, name, parameters, is_strict, body, deferred_body, range: self.range_from(start) })
+    }
+}
+
+/************************************************
+ *
+ * 15.7 Class Definitions
+ *
+ ************************************************/
+
+/// Whether a class element is named by a given `PropName`.
+fn is_named(name: &ClassElementName, expected: &str) -> bool {
+    match name {
+        ClassElementName::PropertyName(PropertyName::IdentifierName(name, _)) => name == expected,
+        ClassElementName::PropertyName(PropertyName::StringLiteral(literal, _)) => literal.string_value() == expected,
+        _ => false,
+    }
+}
+
+/// Names declared by elements of a class body parsed so far.
+#[derive(Default)]
+struct ClassBodyNames {
+    has_constructor: bool,
+    /// Private names with a kind of a method declaring them, `Method` for
+    /// fields too, and whether the method is static.
+    private_names: Vec<(String, MethodKind, bool)>,
+}
+
+impl ClassBodyNames {
+    /// Register a name of a class element, checking it against early errors
+    /// of <https://262.ecma-international.org/14.0/#sec-class-definitions-static-semantics-early-errors>.
+    fn declare(&mut self, element: &ClassElement) -> Result<(), &'static str> {
+        let (name, kind, is_static, is_field) = match element {
+            ClassElement::MethodDefinition { is_static, method, .. } => (&method.name, method.kind, *is_static, false),
+            ClassElement::FieldDefinition { is_static, name, .. } => (name, MethodKind::Method, *is_static, true),
+            ClassElement::ClassStaticBlock(..) => return Ok(()),
+        };
+        if let ClassElementName::PrivateIdentifier(identifier, _) = name {
+            // > It is a Syntax Error if StringValue of PrivateIdentifier is
+            // > "#constructor".
+            let name = identifier.string_value();
+            if name == "#constructor" {
+                return Err("`#constructor` is not a valid private name");
+            }
+            // > It is a Syntax Error if PrivateBoundIdentifiers of
+            // > ClassElementList contains any duplicate entries, unless the
+            // > name is used once for a getter and once for a setter and in
+            // > no other entries, and the getter and setter are either both
+            // > static or both non-static.
+            let previous: Vec<_> = self.private_names.iter().filter(|(other, ..)| *other == name).collect();
+            let is_accessor_pair = match previous[..] {
+                [] => true,
+                [(_, other_kind, other_is_static)] => {
+                    *other_is_static == is_static
+                        && matches!(
+                            (kind, other_kind),
+                            (MethodKind::Getter, MethodKind::Setter) | (MethodKind::Setter, MethodKind::Getter)
+                        )
+                },
+                _ => false,
+            };
+            if !is_accessor_pair {
+                return Err("private name is already declared");
+            }
+            self.private_names.push((name, kind, is_static));
+            return Ok(());
+        }
+        if is_field {
+            // > It is a Syntax Error if PropName of FieldDefinition is
+            // > "constructor".
+            // > It is a Syntax Error if PropName of FieldDefinition is
+            // > "prototype" or "constructor" (for static fields).
+            if is_named(name, "constructor") || (is_static && is_named(name, "prototype")) {
+                return Err("a field cannot be named `constructor` or, if static, `prototype`");
+            }
+        } else if is_static {
+            // > It is a Syntax Error if PropName of MethodDefinition is
+            // > "prototype".
+            if is_named(name, "prototype") {
+                return Err("a static method cannot be named `prototype`");
+            }
+        } else if is_named(name, "constructor") {
+            // > It is a Syntax Error if PropName of MethodDefinition is
+            // > "constructor" and SpecialMethod of MethodDefinition is true.
+            if kind != MethodKind::Method {
+                return Err("a class constructor cannot be an accessor, a generator or async");
+            }
+            // > It is a Syntax Error if PrototypePropertyNameList of
+            // > ClassElementList contains more than one occurrence of
+            // > "constructor".
+            if self.has_constructor {
+                return Err("a class may only have one constructor");
+            }
+            self.has_constructor = true;
+        }
+        Ok(())
+    }
+}
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ClassDeclaration>
+    /// or <https://262.ecma-international.org/14.0/#prod-ClassExpression>.
+    ///
+    /// Only a class expression may be anonymous.
+    pub(super) fn parse_class(&mut self, is_declaration: bool, parameters: Parameters) -> Result<Class, SourceCodeError> {
+        let start = self.range().start;
+        let decorators = self.parse_decorators(parameters)?;
+        // > All parts of a ClassDeclaration or a ClassExpression are strict
+        // > mode code.
+        let outer_strict = self.is_strict();
+        self.set_strict(true);
+        self.expect(TokenKind::Class)?;
+        let name = if is_declaration || !matches!(self.kind(), Some(TokenKind::Extends | TokenKind::OpeningBrace)) {
+            Some(self.parse_binding_identifier(parameters)?)
+        } else {
+            None
+        };
+        let heritage = if self.eat(TokenKind::Extends) {
+            Some(Box::new(self.parse_left_hand_side_expression(parameters)?))
+        } else {
+            None
+        };
+        self.expect(TokenKind::OpeningBrace)?;
+        self.enter_class_body();
+        let mut names = ClassBodyNames::default();
+        let mut elements = Vec::new();
+        while !self.at(TokenKind::RightBracePunctuator) {
+            if self.eat(TokenKind::Semicolon) {
+                continue;
+            }
+            let is_derived = heritage.is_some();
+            let element = self.nested(|parser| parser.parse_class_element(parameters, is_derived))?;
+            if let Err(message) = names.declare(&element) {
+                return Err(self.error(element.range(), ErrorCode::EarlyError, message.to_owned()));
+            }
+            elements.push(element);
+        }
+        self.set_strict(outer_strict);
+        self.expect(TokenKind::RightBracePunctuator)?;
+        let declared: Vec<_> = names.private_names.into_iter().map(|(name, ..)| name).collect();
+        self.leave_class_body(&declared)?;
+        Ok(Class { decorators, is_declaration, name, heritage, elements, range: self.range_from(start) })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ClassElement>
+    /// other than an empty `;` of a class, with `extends` if `is_derived`.
+    fn parse_class_element(&mut self, parameters: Parameters, is_derived: bool) -> Result<ClassElement, SourceCodeError> {
+        let start = self.range().start;
+        let decorators = self.parse_decorators(parameters)?;
+        let is_static = self.at_contextual(ContextualKeyword::Static)
+            && !matches!(
+                self.peek_next().0,
+                None | Some(
+                    TokenKind::OpeningParenthesis
+                        | TokenKind::Assignment
+                        | TokenKind::Semicolon
+                        | TokenKind::RightBracePunctuator
+                )
+            );
+        if is_static {
+            self.bump();
+            if self.at(TokenKind::OpeningBrace) && decorators.is_empty() {
+                let statements = self.parse_class_static_block()?;
+                return Ok(ClassElement::ClassStaticBlock(statements.into(), self.range_from(start)));
+            }
+        }
+        let method_start = self.range().start;
+        let kind = self.parse_method_kind();
+        let name = self.parse_class_element_name(parameters)?;
+        if kind != MethodKind::Method || self.at(TokenKind::OpeningParenthesis) {
+            // > It is a Syntax Error if PropName of MethodDefinition is not
+            // > "constructor" and HasDirectSuper of MethodDefinition is true.
+            // > It is a Syntax Error if ClassHeritage is not present and the
+            // > following algorithm returns true: [...] Return
+            // > HasDirectSuper of constructor.
+            let is_derived_constructor = is_derived && !is_static && is_named(&name, "constructor");
+            let allow_super = if is_derived_constructor { SuperUsage::Call } else { SuperUsage::Property };
+            let method = self.parse_method_definition(method_start, kind, name, allow_super)?;
+            return Ok(ClassElement::MethodDefinition { decorators, is_static, method: Rc::new(method), range: self.range_from(start) });
+        }
+        let initializer = if self.eat(TokenKind::Assignment) {
+            let outer_new_target = self.enter_new_target_scope();
+            let outer_super = self.enter_super_scope(SuperUsage::Property);
+            let initializer = self.parse_assignment_expression(Parameters::default().with_in(true))?;
+            self.leave_super_scope(outer_super);
+            self.leave_new_target_scope(outer_new_target);
+            Some(initializer)
+        } else {
+            None
+        };
+        self.expect_semicolon()?;
+        Ok(ClassElement::FieldDefinition { decorators, is_static, name, initializer: initializer.map(Rc::new), range: self.range_from(start) })
+    }
+
+    /// Parse `DecoratorList` of the decorators proposal, if any.
+    ///
+    /// ```plain
+    /// DecoratorList[Yield, Await] :
+    ///     DecoratorList[?Yield, ?Await]opt Decorator[?Yield, ?Await]
+    ///
+    /// Decorator[Yield, Await] :
+    ///     @ DecoratorMemberExpression[?Yield, ?Await]
+    ///     @ DecoratorParenthesizedExpression[?Yield, ?Await]
+    ///     @ DecoratorCallExpression[?Yield, ?Await]
+    /// ```
+    fn parse_decorators(&mut self, parameters: Parameters) -> Result<Vec<Decorator>, SourceCodeError> {
+        let mut decorators = Vec::new();
+        while self.at_decorator() {
+            let start = self.range().start;
+            self.bump();
+            let expression = self.parse_decorator_expression(parameters)?;
+            decorators.push(Decorator { expression, range: self.range_from(start) });
+        }
+        Ok(decorators)
+    }
+
+    /// Parse `{ ClassStaticBlockStatementList }` of
+    /// <https://262.ecma-international.org/14.0/#prod-ClassStaticBlock>.
+    ///
+    /// ```plain
+    /// ClassStaticBlockStatementList :
+    ///     StatementList[~Yield, +Await, ~Return]opt
+    /// ```
+    ///
+    /// `[+Await]` only reserves `await` here:
+    ///
+    /// > It is a Syntax Error if ClassStaticBlockStatementList Contains await
+    /// > is true.
+    fn parse_class_static_block(&mut self) -> Result<Vec<StatementListItem>, SourceCodeError> {
+        self.expect(TokenKind::OpeningBrace)?;
+        let outer = self.enter_function();
+        let outer_new_target = self.enter_new_target_scope();
+        let outer_super = self.enter_super_scope(SuperUsage::Property);
+        let statements = self.parse_statement_list(Parameters::default().with_await(true))?;
+        self.leave_super_scope(outer_super);
+        self.leave_new_target_scope(outer_new_target);
+        if let Some(range) = self.yield_or_await_since(0) {
+            let message = "`await` is not allowed in a class static block".to_owned();
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        }
+        self.leave_function(outer);
+        self.expect_closing_brace()?;
+        Ok(statements)
+    }
+}
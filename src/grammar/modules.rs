@@ -0,0 +1,580 @@
+//! Syntactic grammar of modules.
+//!
+//! Implements <https://262.ecma-international.org/14.0/#sec-modules>.
+
+use super::expressions::Expression;
+use super::parser::{Parameters, Parser};
+use super::statements::{BindingIdentifier, Declaration, Statement, StatementListItem};
+use crate::early_errors::{check_module, ScriptChecker};
+use crate::lexical_grammar::{CommonToken, ContextualKeyword, Token, TokenKind};
+use crate::{Diagnostics, ErrorCode, SourceCodeError};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#prod-Module>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Module {
+    /// `ModuleBody`, empty for an empty module.
+    pub body: Vec<ModuleItem>,
+    /// The whole source text, including white space and comments around
+    /// the body.
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ModuleItem>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModuleItem {
+    ImportDeclaration(ImportDeclaration),
+    ExportDeclaration(ExportDeclaration),
+    StatementListItem(StatementListItem),
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ImportDeclaration>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportDeclaration {
+    /// `ImportedDefaultBinding`, like `a` in `import a from 'm'`.
+    pub default_binding: Option<BindingIdentifier>,
+    /// `NameSpaceImport`, like `a` in `import * as a from 'm'`.
+    pub namespace_binding: Option<BindingIdentifier>,
+    /// `NamedImports`, like `a` in `import { a } from 'm'`.
+    pub named_imports: Vec<ImportSpecifier>,
+    /// `ModuleSpecifier`, with a string literal value given to the host as
+    /// is.
+    pub module_request: String,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ImportSpecifier>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportSpecifier {
+    /// A name exported by the imported module.
+    pub imported: ModuleExportName,
+    /// A local name bound to the imported one.
+    pub local: BindingIdentifier,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ModuleExportName>,
+/// either an `IdentifierName` or a string literal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ModuleExportName {
+    /// `StringValue` of the identifier, or `SV` of the string literal.
+    pub name: String,
+    pub is_string_literal: bool,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ExportSpecifier>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportSpecifier {
+    /// A local binding, or a name exported by another module for
+    /// `export { a } from 'm'`.
+    pub local: ModuleExportName,
+    /// A name the module exports it under.
+    pub exported: ModuleExportName,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ExportDeclaration>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExportDeclaration {
+    /// `export * from 'm'`, or `export * as a from 'm'` with
+    /// `exported`.
+    ExportAll {
+        exported: Option<ModuleExportName>,
+        module_request: String,
+        range: Range<usize>,
+    },
+    /// `export { a, b as c }`, or `export { a } from 'm'` with
+    /// `module_request`.
+    NamedExports {
+        specifiers: Vec<ExportSpecifier>,
+        module_request: Option<String>,
+        range: Range<usize>,
+    },
+    /// `export` before a variable statement or a declaration, which is
+    /// either [`Statement::VariableStatement`] or
+    /// [`StatementListItem::Declaration`].
+    Declaration(StatementListItem, Range<usize>),
+    /// `export default` before a function or a class declaration, which
+    /// may be anonymous.
+    DefaultDeclaration(Declaration, Range<usize>),
+    /// `export default` before an expression, with `;` after it, if any.
+    DefaultExpression(Expression, Range<usize>),
+}
+
+impl ExportDeclaration {
+    /// Byte offsets of the source text the declaration covers.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::ExportAll { range, .. }
+            | Self::NamedExports { range, .. }
+            | Self::Declaration(_, range)
+            | Self::DefaultDeclaration(_, range)
+            | Self::DefaultExpression(_, range) => range.clone(),
+        }
+    }
+}
+
+/// `[[ImportName]]` of
+/// <https://262.ecma-international.org/14.0/#importentry-record> and
+/// <https://262.ecma-international.org/14.0/#exportentry-record>.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ImportName {
+    Name(String),
+    /// `namespace-object` of `import * as a from 'm'`.
+    NamespaceObject,
+    /// `all` of `export * as a from 'm'`.
+    All,
+    /// `all-but-default` of `export * from 'm'`.
+    AllButDefault,
+}
+
+/// <https://262.ecma-international.org/14.0/#importentry-record>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportEntry {
+    pub module_request: String,
+    /// [`ImportName::Name`] or [`ImportName::NamespaceObject`].
+    pub import_name: ImportName,
+    pub local_name: String,
+}
+
+/// <https://262.ecma-international.org/14.0/#exportentry-record>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportEntry {
+    /// `None` for `export * from 'm'`.
+    pub export_name: Option<String>,
+    pub module_request: Option<String>,
+    pub import_name: Option<ImportName>,
+    /// A local binding, `*default*` for an anonymous default export.
+    pub local_name: Option<String>,
+}
+
+/// A local name of an anonymous default export, which code cannot refer
+/// to.
+pub const DEFAULT_EXPORT_NAME: &str = "*default*";
+
+impl Module {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-modulerequests>
+    /// in source order, each once.
+    #[must_use]
+    pub fn module_requests(&self) -> Vec<&str> {
+        let mut requests = Vec::new();
+        for item in &self.body {
+            let request = match item {
+                ModuleItem::ImportDeclaration(declaration) => &declaration.module_request,
+                ModuleItem::ExportDeclaration(
+                    ExportDeclaration::ExportAll { module_request, .. }
+                    | ExportDeclaration::NamedExports { module_request: Some(module_request), .. },
+                ) => module_request,
+                _ => continue,
+            };
+            if !requests.contains(&request.as_str()) {
+                requests.push(request.as_str());
+            }
+        }
+        requests
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-importentries>
+    #[must_use]
+    pub fn import_entries(&self) -> Vec<ImportEntry> {
+        let mut entries = Vec::new();
+        for item in &self.body {
+            let ModuleItem::ImportDeclaration(declaration) = item else { continue };
+            let entry = |import_name, local: &BindingIdentifier| ImportEntry {
+                module_request: declaration.module_request.clone(),
+                import_name,
+                local_name: local.name.clone(),
+            };
+            if let Some(local) = &declaration.default_binding {
+                entries.push(entry(ImportName::Name("default".to_owned()), local));
+            }
+            if let Some(local) = &declaration.namespace_binding {
+                entries.push(entry(ImportName::NamespaceObject, local));
+            }
+            for specifier in &declaration.named_imports {
+                entries.push(entry(ImportName::Name(specifier.imported.name.clone()), &specifier.local));
+            }
+        }
+        entries
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-exportentries>
+    #[must_use]
+    pub fn export_entries(&self) -> Vec<ExportEntry> {
+        let mut entries = Vec::new();
+        for item in &self.body {
+            let ModuleItem::ExportDeclaration(declaration) = item else { continue };
+            let local = |name: &str| ExportEntry {
+                export_name: Some(name.to_owned()),
+                module_request: None,
+                import_name: None,
+                local_name: Some(name.to_owned()),
+            };
+            match declaration {
+                ExportDeclaration::ExportAll { exported, module_request, .. } => entries.push(ExportEntry {
+                    export_name: exported.as_ref().map(|exported| exported.name.clone()),
+                    module_request: Some(module_request.clone()),
+                    import_name: Some(if exported.is_some() { ImportName::All } else { ImportName::AllButDefault }),
+                    local_name: None,
+                }),
+                ExportDeclaration::NamedExports { specifiers, module_request, .. } => {
+                    entries.extend(specifiers.iter().map(|specifier| ExportEntry {
+                        export_name: Some(specifier.exported.name.clone()),
+                        module_request: module_request.clone(),
+                        import_name: module_request.as_ref().map(|_| ImportName::Name(specifier.local.name.clone())),
+                        local_name: module_request.is_none().then(|| specifier.local.name.clone()),
+                    }));
+                },
+                ExportDeclaration::Declaration(item, _) => {
+                    let names = item.lexically_declared_names().into_iter().chain(item.var_declared_names());
+                    entries.extend(names.map(|name| local(&name.name)));
+                },
+                ExportDeclaration::DefaultDeclaration(declaration, _) => {
+                    let names = declaration.bound_names();
+                    let name = names.first().map_or(DEFAULT_EXPORT_NAME, |name| name.name.as_str());
+                    entries.push(ExportEntry { export_name: Some("default".to_owned()), ..local(name) });
+                },
+                ExportDeclaration::DefaultExpression(..) => {
+                    entries.push(ExportEntry { export_name: Some("default".to_owned()), ..local(DEFAULT_EXPORT_NAME) });
+                },
+            }
+        }
+        entries
+    }
+
+    /// Statements and declarations of the module in source order, including
+    /// exported ones but not `export default` declarations.
+    pub fn statement_list_items(&self) -> impl Iterator<Item = &StatementListItem> + Clone {
+        self.body.iter().filter_map(|item| match item {
+            ModuleItem::StatementListItem(item) | ModuleItem::ExportDeclaration(ExportDeclaration::Declaration(item, _)) => {
+                Some(item)
+            },
+            _ => None,
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of the module, including imported bindings and names of function
+    /// declarations but not `*default*`.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        let mut names = Vec::new();
+        for item in &self.body {
+            match item {
+                ModuleItem::ImportDeclaration(declaration) => {
+                    names.extend(&declaration.default_binding);
+                    names.extend(&declaration.namespace_binding);
+                    names.extend(declaration.named_imports.iter().map(|specifier| &specifier.local));
+                },
+                ModuleItem::ExportDeclaration(ExportDeclaration::DefaultDeclaration(declaration, _)) => {
+                    names.extend(declaration.bound_names());
+                },
+                ModuleItem::ExportDeclaration(ExportDeclaration::Declaration(item, _)) | ModuleItem::StatementListItem(item) => {
+                    names.extend(item.lexically_declared_names());
+                },
+                ModuleItem::ExportDeclaration(_) => {},
+            }
+        }
+        names
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-vardeclarednames>
+    /// of the module.
+    #[must_use]
+    pub fn var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        self.statement_list_items().flat_map(StatementListItem::var_declared_names).collect()
+    }
+}
+
+impl Parser<'_> {
+    /// Parse `Module` up to the end of the source text.
+    ///
+    /// ```plain
+    /// Module :
+    ///     ModuleBody_opt
+    ///
+    /// ModuleBody :
+    ///     ModuleItemList
+    /// ```
+    ///
+    /// The parser is expected to be created with [`Parser::new_module`], so
+    /// the code is strict mode code.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the source text does
+    /// not form a module.
+    pub fn parse_module(&mut self) -> Result<Module, SourceCodeError> {
+        // `ModuleItemList[~Yield, +Await, ~Return]`
+        let parameters = Parameters::default().with_await(true);
+        let mut body = Vec::new();
+        while !self.is_at_end() {
+            body.extend(self.recover(false, |parser| parser.parse_module_item(parameters))?);
+        }
+        let module = Module { body, range: 0..self.source_len() };
+        let mut early_errors = Diagnostics::new();
+        check_module(self.source(), &module, &mut early_errors);
+        self.report_all(early_errors)?;
+        Ok(module)
+    }
+
+    /// Parse `Module` like [`Parser::parse_module`] does, but recover from
+    /// problems instead of stopping at the first one, reporting them into
+    /// a sink.
+    pub fn parse_module_with_diagnostics(&mut self, diagnostics: &mut Diagnostics) -> Module {
+        self.start_recovering();
+        let module = self.parse_module();
+        diagnostics.extend(self.stop_recovering());
+        diagnostics.extend(self.warnings().iter().cloned());
+        module.unwrap_or_else(|error| {
+            diagnostics.report(error);
+            Module { body: Vec::new(), range: 0..self.source_len() }
+        })
+    }
+
+    /// Check a whole module like [`Parser::parse_module_with_diagnostics`]
+    /// does, dropping each module item once it is checked.
+    pub(super) fn validate_module(&mut self, diagnostics: &mut Diagnostics) {
+        self.start_recovering();
+        let result = self.validate_module_body();
+        diagnostics.extend(self.stop_recovering());
+        diagnostics.extend(self.warnings().iter().cloned());
+        if let Err(error) = result {
+            diagnostics.report(error);
+        }
+    }
+
+    fn validate_module_body(&mut self) -> Result<(), SourceCodeError> {
+        let parameters = Parameters::default().with_await(true);
+        let mut checker = ScriptChecker::new(true, true);
+        let mut early_errors = Diagnostics::new();
+        while !self.is_at_end() {
+            if let Some(item) = self.recover(false, |parser| parser.parse_module_item(parameters))? {
+                checker.check_module_item(self.source(), &item, &mut early_errors);
+            }
+        }
+        checker.finish_module(self.source(), &mut early_errors);
+        self.report_all(early_errors)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ModuleItem>.
+    fn parse_module_item(&mut self, parameters: Parameters) -> Result<ModuleItem, SourceCodeError> {
+        // `import(...)` and `import.meta` start expression statements.
+        if self.at(TokenKind::Import) && !matches!(self.peek_next().0, Some(TokenKind::OpeningParenthesis | TokenKind::Dot)) {
+            return Ok(ModuleItem::ImportDeclaration(self.parse_import_declaration(parameters)?));
+        }
+        if self.at(TokenKind::Export) {
+            return Ok(ModuleItem::ExportDeclaration(self.parse_export_declaration(parameters)?));
+        }
+        Ok(ModuleItem::StatementListItem(self.parse_statement_list_item(parameters)?))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ImportDeclaration>.
+    ///
+    /// ```plain
+    /// ImportDeclaration :
+    ///     import ImportClause FromClause ;
+    ///     import ModuleSpecifier ;
+    ///
+    /// ImportClause :
+    ///     ImportedDefaultBinding
+    ///     NameSpaceImport
+    ///     NamedImports
+    ///     ImportedDefaultBinding , NameSpaceImport
+    ///     ImportedDefaultBinding , NamedImports
+    /// ```
+    fn parse_import_declaration(&mut self, parameters: Parameters) -> Result<ImportDeclaration, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Import)?;
+        let mut declaration = ImportDeclaration {
+            default_binding: None,
+            namespace_binding: None,
+            named_imports: Vec::new(),
+            module_request: String::new(),
+            range: start..start,
+        };
+        if !self.at(TokenKind::StringLiteral) {
+            let has_default = !self.at(TokenKind::Multiplication) && !self.at(TokenKind::OpeningBrace);
+            if has_default {
+                declaration.default_binding = Some(self.parse_binding_identifier(parameters)?);
+            }
+            if !has_default || self.eat(TokenKind::Comma) {
+                if self.eat(TokenKind::Multiplication) {
+                    self.expect_contextual(ContextualKeyword::As, "`as`")?;
+                    declaration.namespace_binding = Some(self.parse_binding_identifier(parameters)?);
+                } else {
+                    declaration.named_imports = self.parse_named_imports(parameters)?;
+                }
+            }
+            self.expect_contextual(ContextualKeyword::From, "`from`")?;
+        }
+        declaration.module_request = self.parse_module_specifier()?;
+        self.expect_semicolon()?;
+        declaration.range = self.range_from(start);
+        Ok(declaration)
+    }
+
+    /// Parse `NamedImports` like `{ a, b as c }`.
+    fn parse_named_imports(&mut self, parameters: Parameters) -> Result<Vec<ImportSpecifier>, SourceCodeError> {
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut specifiers = Vec::new();
+        while !self.eat(TokenKind::RightBracePunctuator) {
+            // `ImportedBinding` alone, or `ModuleExportName as ImportedBinding`.
+            let specifier = if self.at(TokenKind::StringLiteral) || self.next_is_contextual(ContextualKeyword::As) {
+                let imported = self.parse_module_export_name()?;
+                self.expect_contextual(ContextualKeyword::As, "`as`")?;
+                ImportSpecifier { imported, local: self.parse_binding_identifier(parameters)? }
+            } else {
+                let local = self.parse_binding_identifier(parameters)?;
+                let imported = ModuleExportName { name: local.name.clone(), is_string_literal: false, range: local.range.clone() };
+                ImportSpecifier { imported, local }
+            };
+            specifiers.push(specifier);
+            if !self.at(TokenKind::RightBracePunctuator) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(specifiers)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ExportDeclaration>.
+    ///
+    /// ```plain
+    /// ExportDeclaration :
+    ///     export ExportFromClause FromClause ;
+    ///     export NamedExports ;
+    ///     export VariableStatement[~Yield, +Await]
+    ///     export Declaration[~Yield, +Await]
+    ///     export default HoistableDeclaration[~Yield, +Await, +Default]
+    ///     export default ClassDeclaration[~Yield, +Await, +Default]
+    ///     export default [lookahead ∉ { function, async [no LineTerminator here] function, class }] AssignmentExpression[+In, ~Yield, +Await] ;
+    /// ```
+    fn parse_export_declaration(&mut self, parameters: Parameters) -> Result<ExportDeclaration, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Export)?;
+        if self.eat(TokenKind::Multiplication) {
+            let exported = if self.at_contextual(ContextualKeyword::As) {
+                self.bump();
+                Some(self.parse_module_export_name()?)
+            } else {
+                None
+            };
+            self.expect_contextual(ContextualKeyword::From, "`from`")?;
+            let module_request = self.parse_module_specifier()?;
+            self.expect_semicolon()?;
+            return Ok(ExportDeclaration::ExportAll { exported, module_request, range: self.range_from(start) });
+        }
+        if self.at(TokenKind::OpeningBrace) {
+            let specifiers = self.parse_named_exports()?;
+            let module_request = if self.at_contextual(ContextualKeyword::From) {
+                self.bump();
+                Some(self.parse_module_specifier()?)
+            } else {
+                None
+            };
+            self.expect_semicolon()?;
+            return Ok(ExportDeclaration::NamedExports { specifiers, module_request, range: self.range_from(start) });
+        }
+        if self.eat(TokenKind::Default) {
+            // A declaration may be anonymous here, which is parsed like an
+            // expression.
+            if self.at(TokenKind::Function) || self.at_async_function() {
+                let mut function = self.parse_function(false, parameters)?;
+                function.is_declaration = true;
+                let declaration = Declaration::FunctionDeclaration(Rc::new(function));
+                return Ok(ExportDeclaration::DefaultDeclaration(declaration, self.range_from(start)));
+            }
+            if self.at(TokenKind::Class) || self.at_decorator() {
+                let mut class = self.parse_class(false, parameters)?;
+                class.is_declaration = true;
+                let declaration = Declaration::ClassDeclaration(class);
+                return Ok(ExportDeclaration::DefaultDeclaration(declaration, self.range_from(start)));
+            }
+            let expression = self.parse_assignment_expression(parameters.with_in(true))?;
+            self.expect_semicolon()?;
+            return Ok(ExportDeclaration::DefaultExpression(expression, self.range_from(start)));
+        }
+        let item = if self.at(TokenKind::Var) {
+            StatementListItem::Statement(self.parse_statement(parameters)?)
+        } else if self.at(TokenKind::Function)
+            || self.at_async_function()
+            || self.at(TokenKind::Class)
+            || self.at_decorator()
+            || self.at_lexical_declaration()
+        {
+            self.parse_statement_list_item(parameters)?
+        } else {
+            return Err(self.expected("declaration"));
+        };
+        if !matches!(item, StatementListItem::Declaration(_) | StatementListItem::Statement(Statement::VariableStatement(_))) {
+            return Err(self.error(item.range(), ErrorCode::UnexpectedInput, "expected declaration".to_owned()));
+        }
+        Ok(ExportDeclaration::Declaration(item, self.range_from(start)))
+    }
+
+    /// Parse `NamedExports` like `{ a, b as c }`.
+    fn parse_named_exports(&mut self) -> Result<Vec<ExportSpecifier>, SourceCodeError> {
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut specifiers = Vec::new();
+        while !self.eat(TokenKind::RightBracePunctuator) {
+            let local = self.parse_module_export_name()?;
+            let exported = if self.at_contextual(ContextualKeyword::As) {
+                self.bump();
+                self.parse_module_export_name()?
+            } else {
+                local.clone()
+            };
+            specifiers.push(ExportSpecifier { local, exported });
+            if !self.at(TokenKind::RightBracePunctuator) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(specifiers)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ModuleExportName>.
+    ///
+    /// > It is a Syntax Error if IsStringWellFormedUnicode(the SV of
+    /// > StringLiteral) is false.
+    fn parse_module_export_name(&mut self) -> Result<ModuleExportName, SourceCodeError> {
+        let range = self.range();
+        let Some(Token::CommonToken(CommonToken::StringLiteral(literal))) = self.token() else {
+            let name = self.parse_identifier_name()?;
+            return Ok(ModuleExportName { name, is_string_literal: false, range });
+        };
+        let Ok(name) = String::from_utf16(&literal.code_units()) else {
+            let message = "an exported name cannot contain unpaired surrogates".to_owned();
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        };
+        self.bump();
+        Ok(ModuleExportName { name, is_string_literal: true, range })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ModuleSpecifier>.
+    fn parse_module_specifier(&mut self) -> Result<String, SourceCodeError> {
+        let Some(Token::CommonToken(CommonToken::StringLiteral(literal))) = self.token() else {
+            return Err(self.expected("module specifier"));
+        };
+        let specifier = literal.string_value();
+        self.bump();
+        Ok(specifier)
+    }
+
+    /// Consume a contextual keyword or report that another token is found.
+    fn expect_contextual(&mut self, keyword: ContextualKeyword, description: &str) -> Result<(), SourceCodeError> {
+        if !self.at_contextual(keyword) {
+            return Err(self.expected(description));
+        }
+        self.bump();
+        Ok(())
+    }
+
+    /// Whether a token following the current one is a given contextual
+    /// keyword.
+    fn next_is_contextual(&self, keyword: ContextualKeyword) -> bool {
+        let mut next = self.clone();
+        next.bump();
+        next.at_contextual(keyword)
+    }
+}
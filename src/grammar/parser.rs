@@ -0,0 +1,1069 @@
+//! Recursive descent parser of the syntactic grammar.
+//!
+//! The parser pulls significant tokens from the tokenizer one at a time,
+//! skipping white space and comments. Each token is recognized with the
+//! `InputElementDiv` goal first; where the syntactic grammar permits
+//! a `RegularExpressionLiteral` or a `TemplateMiddle`, the production being
+//! parsed asks to [`Parser::rescan`] a `/` or `}` token with a goal that
+//! recognizes it. So no token after the current one is ever recognized in
+//! advance, and no heuristic like [`GoalSymbols::infer`] is needed.
+//!
+//! Productions are parsed by methods of [`Parser`] defined next to their AST
+//! nodes, one module per section of ECMA-262.
+//!
+//! Each node keeps byte offsets of the source text it covers, from its first
+//! token to its last one; [`crate::LineIndex`] maps them to lines and
+//! columns. White space and comments are not a part of the tree, but
+//! [`Parser::trivia`] attaches them to tokens that nodes start or end with.
+
+use crate::lexical_grammar::{
+    check_legacy_token,
+    get_next_token_with_options,
+    CommonToken,
+    ContextualKeyword,
+    GoalSymbols,
+    LexerOptions,
+    Token,
+    TokenKind,
+    Tokenizer,
+};
+use crate::{Diagnostics, ErrorCode, Severity, SourceCodeError};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Grammar parameters of a production being parsed, like `[+In]` in
+/// `Expression[+In, ?Yield, ?Await]`.
+///
+/// See <https://262.ecma-international.org/14.0/#sec-grammatical-parameters>.
+// Each parameter is independently present or absent, so they are flags
+// rather than a state machine.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(super) struct Parameters {
+    /// `[In]`: `in` is a relational operator rather than a part of
+    /// a `for`-`in` statement head.
+    pub(super) allow_in: bool,
+    /// `[Return]`: the code is a function body, so `return` is allowed.
+    pub(super) allow_return: bool,
+    /// `[Yield]`: the code is in a generator, so `yield` starts
+    /// a `YieldExpression` rather than names an identifier.
+    pub(super) allow_yield: bool,
+    /// `[Await]`: the code is in an async function, so `await` starts
+    /// an `AwaitExpression` rather than names an identifier.
+    pub(super) allow_await: bool,
+}
+
+impl Parameters {
+    pub(super) const fn with_in(self, allow_in: bool) -> Self {
+        let mut parameters = self;
+        parameters.allow_in = allow_in;
+        parameters
+    }
+
+    pub(super) const fn with_return(self, allow_return: bool) -> Self {
+        let mut parameters = self;
+        parameters.allow_return = allow_return;
+        parameters
+    }
+
+    pub(super) const fn with_yield(self, allow_yield: bool) -> Self {
+        let mut parameters = self;
+        parameters.allow_yield = allow_yield;
+        parameters
+    }
+
+    pub(super) const fn with_await(self, allow_await: bool) -> Self {
+        let mut parameters = self;
+        parameters.allow_await = allow_await;
+        parameters
+    }
+}
+
+/// `super` forms allowed in code being parsed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(super) enum SuperUsage {
+    /// Outside of methods, including in plain functions nested in them.
+    #[default]
+    None,
+    /// `super.a` and `super[a]`, in methods, class field initializers and
+    /// class static blocks.
+    Property,
+    /// `super(...)` too, in a constructor of a class with `extends`.
+    Call,
+}
+
+/// How deep expressions, statements, functions, classes and binding
+/// patterns may nest by default, see [`ParserOptions::max_nesting_depth`].
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 1024;
+
+/// Settings of [`Parser`] beyond choosing a goal symbol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParserOptions {
+    /// Syntax of in-flight proposals to accept in addition to ECMA-262.
+    pub proposals: Proposals,
+    /// How deep expressions, statements, functions, classes and binding
+    /// patterns may nest, like brackets in `[[[a]]]`, before the parser reports
+    /// [`ErrorCode::NestingTooDeep`] instead of overflowing the native
+    /// stack.
+    pub max_nesting_depth: usize,
+    /// How [`Parser::trivia`] attaches white space and comments to tokens,
+    /// or `None` not to record them.
+    pub trivia: Option<TriviaPolicy>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self { proposals: Proposals::default(), max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH, trivia: None }
+    }
+}
+
+/// Which token white space and comments between two tokens attach to, as
+/// formatters and documentation extractors have different conventions.
+///
+/// Trivia before the first token always leads it, and trivia after the
+/// last one always trails it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TriviaPolicy {
+    /// Trivia on the line of a previous token, up to and including the line
+    /// terminator ending the line, trails that token, and the rest leads
+    /// a next token. So an end-of-line comment describes code before it,
+    /// and an own-line comment describes code after it.
+    #[default]
+    ByLine,
+    /// All trivia leads a next token, like a documentation comment leads
+    /// a declaration.
+    Leading,
+    /// All trivia trails a previous token.
+    Trailing,
+}
+
+/// A token of white space, a line terminator or a comment, attached to
+/// a significant token next to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trivia {
+    /// One of kinds that [`TokenKind::is_trivia`] accepts.
+    pub kind: TokenKind,
+    pub range: Range<usize>,
+    pub attachment: TriviaAttachment,
+}
+
+impl Trivia {
+    /// Whether the trivia leads the first token of a node with given byte
+    /// offsets, like [`super::Statement::range`].
+    ///
+    /// Nested nodes starting with the same token all match, so it is up to
+    /// a caller which of them takes the trivia.
+    #[must_use]
+    pub fn leads(&self, node: &Range<usize>) -> bool {
+        self.attachment == TriviaAttachment::Leading(node.start)
+    }
+
+    /// Whether the trivia trails the last token of a node with given byte
+    /// offsets.
+    #[must_use]
+    pub fn trails(&self, node: &Range<usize>) -> bool {
+        self.attachment == TriviaAttachment::Trailing(node.end)
+    }
+}
+
+/// A token that [`Trivia`] attaches to, by its byte offsets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriviaAttachment {
+    /// Leads a token starting at the offset, or the end of input if the
+    /// source text has no tokens.
+    Leading(usize),
+    /// Trails a token ending at the offset.
+    Trailing(usize),
+}
+
+/// Runs of white space and comments between tokens consumed so far, for
+/// [`Parser::trivia`].
+#[derive(Clone, Debug)]
+struct TriviaRecord {
+    policy: TriviaPolicy,
+    /// Byte offsets of the runs, ends by starts. Clones of the parser
+    /// looking ahead share them, so they are not copied on each look.
+    runs: Rc<RefCell<BTreeMap<usize, usize>>>,
+}
+
+/// Stage 3 proposals that are not a part of ECMA-262 yet, all disabled by
+/// default.
+///
+/// Their syntax may still change before they are standardized, so AST nodes
+/// parsed only with a proposal enabled are marked as such in their
+/// documentation.
+// Each proposal is independently enabled, so they are flags rather than
+// a state machine.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Proposals {
+    /// <https://github.com/tc39/proposal-decorators>: `@a` before classes
+    /// and their methods and fields.
+    pub decorators: bool,
+    /// <https://github.com/tc39/proposal-import-attributes>: a second
+    /// argument of `import()`, like `import(a, { with: { type: 'json' } })`.
+    pub import_attributes: bool,
+    /// <https://github.com/tc39/proposal-explicit-resource-management>:
+    /// `using` and `await using` declarations.
+    pub explicit_resource_management: bool,
+}
+
+/// A significant token the parser looks at.
+#[derive(Clone, Debug)]
+struct Lookahead<'src> {
+    /// `None` at the end of input or if the token is malformed.
+    token: Option<Token<'src>>,
+    range: Range<usize>,
+    /// Whether a line terminator separates the token from a previous one.
+    newline_before: bool,
+    /// Start of white space and comments before the token.
+    trivia_start: usize,
+}
+
+/// A syntactic grammar parser of a `.js`/`.mjs` text.
+///
+/// Each `parse_*` method parses a production starting at the current
+/// position and leaves the parser right after it, so several productions
+/// can be parsed one after another. If the source text does not match
+/// the production, the method returns the first problem found.
+#[derive(Clone, Debug)]
+pub struct Parser<'src> {
+    source: &'src str,
+    /// Whether the source text is parsed with the `Module` goal symbol
+    /// rather than `Script`.
+    is_module: bool,
+    options: LexerOptions,
+    proposals: Proposals,
+    lookahead: Lookahead<'src>,
+    /// Why the lookahead token is missing before the end of input.
+    lexical_error: Option<SourceCodeError>,
+    /// End of the last consumed token.
+    previous_end: usize,
+    /// Private names referenced in class bodies being parsed, innermost
+    /// last, that are not yet known to be declared.
+    private_name_references: Vec<Vec<(String, Range<usize>)>>,
+    /// Start of the innermost `AssignmentExpression` being parsed, where
+    /// an arrow function may start.
+    potential_arrow_start: usize,
+    /// `CoverInitializedName`s like `a = 1` in `{ a = 1 }` not yet known to
+    /// be a part of a destructuring pattern.
+    cover_initialized_names: Vec<Range<usize>>,
+    /// Last `YieldExpression` or `AwaitExpression` parsed in the innermost
+    /// function, to reject them in its parameters.
+    last_yield_or_await: Option<Range<usize>>,
+    /// Whether the code being parsed is in a function, a class field
+    /// initializer or a class static block, where `new.target` is allowed.
+    /// Arrow functions take it from the enclosing code.
+    allow_new_target: bool,
+    /// Which `super` forms the code being parsed may contain. Arrow
+    /// functions take it from the enclosing code.
+    allow_super: SuperUsage,
+    /// Problems recovered from, if the parser is in the error-recovering
+    /// mode of [`Parser::parse_script_with_diagnostics`].
+    recovered: Option<Diagnostics>,
+    /// Whether function bodies are skipped rather than parsed, see
+    /// [`Parser::with_deferred_function_bodies`].
+    defers_function_bodies: bool,
+    /// Legacy and deprecated constructs parsed so far, see
+    /// [`Parser::warnings`].
+    warnings: Vec<SourceCodeError>,
+    /// How many productions that may nest are being parsed, see
+    /// [`Parser::nested`].
+    depth: usize,
+    /// Limit of `depth` from [`ParserOptions::max_nesting_depth`].
+    max_depth: usize,
+    /// Trivia recorded if [`ParserOptions::trivia`] is set.
+    trivia: Option<TriviaRecord>,
+}
+
+/// Parser state that a production failed halfway may leave inconsistent,
+/// saved before a statement to recover from its problems.
+struct Checkpoint {
+    private_name_scopes: usize,
+    cover_initialized_names: usize,
+    last_yield_or_await: Option<Range<usize>>,
+    allow_new_target: bool,
+    allow_super: SuperUsage,
+    is_strict: bool,
+}
+
+impl<'src> Parser<'src> {
+    /// Create a parser of a script.
+    #[must_use]
+    pub fn new(source: &'src str) -> Self {
+        Self::with_goal(source, false)
+    }
+
+    /// Create a parser of a module, where `import.meta` is allowed and all
+    /// code is strict mode code.
+    #[must_use]
+    pub fn new_module(source: &'src str) -> Self {
+        Self::with_goal(source, true)
+    }
+
+    fn with_goal(source: &'src str, is_module: bool) -> Self {
+        let mut parser = Self {
+            source,
+            is_module,
+            options: LexerOptions {
+                strict: is_module,
+                html_like_comments: !is_module && cfg!(feature = "annex-b"),
+                ..LexerOptions::default()
+            },
+            proposals: Proposals::default(),
+            lookahead: Lookahead { token: None, range: 0..0, newline_before: false, trivia_start: 0 },
+            lexical_error: None,
+            previous_end: 0,
+            private_name_references: Vec::new(),
+            potential_arrow_start: 0,
+            cover_initialized_names: Vec::new(),
+            last_yield_or_await: None,
+            allow_new_target: false,
+            allow_super: SuperUsage::None,
+            recovered: None,
+            defers_function_bodies: false,
+            warnings: Vec::new(),
+            depth: 0,
+            max_depth: DEFAULT_MAX_NESTING_DEPTH,
+            trivia: None,
+        };
+        // > The InputElementHashbangOrRegExp goal is used at the start of
+        // > a Script or Module.
+        //
+        // Only a hashbang comment is taken from it; a token that follows is
+        // rescanned with a goal the parser expects there.
+        let hashbang = get_next_token_with_options(source, 0, GoalSymbols::InputElementHashbangOrRegExp, &parser.options);
+        let start = match hashbang {
+            Ok((Token::HashbangComment(_), range)) => range.end,
+            _ => 0,
+        };
+        parser.scan(start, GoalSymbols::InputElementDiv, false);
+        parser.lookahead.trivia_start = 0;
+        parser
+    }
+
+    /// Skip function bodies instead of parsing them, leaving
+    /// [`super::DeferredBody`] in their place to parse with
+    /// [`Parser::parse_deferred_body`] when needed.
+    ///
+    /// Most functions of a large bundle never run, so a tree without their
+    /// bodies is built much faster. Only braces are matched in a skipped
+    /// body, so its problems are reported once it is parsed.
+    #[must_use]
+    pub const fn with_deferred_function_bodies(mut self) -> Self {
+        self.defers_function_bodies = true;
+        self
+    }
+
+    /// Apply settings beyond choosing a goal symbol.
+    #[must_use]
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.proposals = options.proposals;
+        self.max_depth = options.max_nesting_depth;
+        self.trivia = options.trivia.map(|policy| TriviaRecord { policy, runs: Rc::default() });
+        // `@` is a lexical error unless decorators are enabled.
+        self.rescan(GoalSymbols::InputElementDiv);
+        self
+    }
+
+    /// Whether the whole source text is parsed, except for trailing white
+    /// space and comments.
+    #[must_use]
+    pub const fn is_at_end(&self) -> bool {
+        self.lookahead.token.is_none() && self.lexical_error.is_none()
+    }
+
+    /// Check that the whole source text is parsed, except for trailing white
+    /// space and comments.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`] describing a first token
+    /// left unparsed.
+    pub fn finish(&self) -> Result<(), SourceCodeError> {
+        if self.is_at_end() {
+            return Ok(());
+        }
+        Err(self.unexpected())
+    }
+
+    /// Parse a production that may contain itself, like an expression in
+    /// brackets, one level deeper.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`ErrorCode::NestingTooDeep`] if productions
+    /// already nest as deep as options of the parser allow, or with
+    /// a problem of the production.
+    pub(super) fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, SourceCodeError>) -> Result<T, SourceCodeError> {
+        if self.depth >= self.max_depth {
+            let message = format!("code is nested more than {} levels deep", self.max_depth);
+            return Err(self.error(self.range(), ErrorCode::NestingTooDeep, message));
+        }
+        self.depth += 1;
+        let result = crate::grow_stack(|| parse(self));
+        self.depth -= 1;
+        result
+    }
+
+    /// Recognize a next significant token starting at a given offset.
+    fn scan(&mut self, mut offset: usize, goal: GoalSymbols, mut newline_before: bool) {
+        self.lexical_error = None;
+        let trivia_start = offset;
+        while offset < self.source.len() {
+            match get_next_token_with_options(self.source, offset, goal, &self.options) {
+                Ok((Token::WhiteSpace(_) | Token::HashbangComment(_), range)) => offset = range.end,
+                Ok((Token::LineTerminator(_), range)) => {
+                    newline_before = true;
+                    offset = range.end;
+                },
+                Ok((Token::Comment(comment), range)) => {
+                    newline_before |= comment.contains_line_terminator();
+                    self.warn_about_legacy_token(&Token::Comment(comment), range.clone());
+                    offset = range.end;
+                },
+                Ok((token, range)) => {
+                    self.warn_about_legacy_token(&token, range.clone());
+                    self.lookahead = Lookahead { token: Some(token), range, newline_before, trivia_start };
+                    return;
+                },
+                // ECMA-262 has no `@` punctuator, so the decorators proposal
+                // gets it as a code point that does not start any token.
+                Err(_) if self.proposals.decorators && self.source[offset..].starts_with('@') => {
+                    let range = offset..offset + 1;
+                    self.lookahead = Lookahead { token: Some(Token::Unknown('@')), range, newline_before, trivia_start };
+                    return;
+                },
+                Err(error) => {
+                    self.lexical_error = Some(error);
+                    break;
+                },
+            }
+        }
+        self.lookahead = Lookahead { token: None, range: offset..offset, newline_before, trivia_start };
+    }
+
+    /// Report a legacy token once however many times it is recognized.
+    fn warn_about_legacy_token(&mut self, token: &Token<'_>, range: Range<usize>) {
+        if self.warnings.last().is_some_and(|warning| warning.range.start >= range.start) {
+            return;
+        }
+        if let Some(warning) = check_legacy_token(token, self.source, range) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Report a deprecated construct that still parses.
+    pub(super) fn warn(&mut self, range: Range<usize>, message: String) {
+        let mut warning = self.error(range, ErrorCode::DeprecatedSyntax, message);
+        warning.severity = Severity::Warning;
+        self.warnings.push(warning);
+    }
+
+    /// Legacy and deprecated constructs parsed so far, like legacy octal
+    /// literals, HTML-like comments and `with` statements, each with
+    /// [`Severity::Warning`] and [`ErrorCode::DeprecatedSyntax`].
+    ///
+    /// Methods parsing with diagnostics report them into the sink too.
+    #[must_use]
+    pub fn warnings(&self) -> &[SourceCodeError] {
+        &self.warnings
+    }
+
+    /// White space, line terminators and comments around tokens consumed so
+    /// far, in source order, each attached to a token by
+    /// [`ParserOptions::trivia`]; empty unless it is set.
+    ///
+    /// Once the whole source text is parsed, trivia after the last token is
+    /// included too. Function bodies skipped by
+    /// [`Parser::with_deferred_function_bodies`] have their trivia recorded
+    /// once they are parsed.
+    #[must_use]
+    pub fn trivia(&self) -> Vec<Trivia> {
+        let Some(record) = &self.trivia else {
+            return Vec::new();
+        };
+        let mut runs: Vec<Range<usize>> = record.runs.borrow().iter().map(|(&start, &end)| start..end).collect();
+        if self.is_at_end() && self.lookahead.trivia_start < self.source.len() {
+            runs.push(self.lookahead.trivia_start..self.source.len());
+        }
+        let mut trivia = Vec::new();
+        for run in runs {
+            let mut pieces = Vec::new();
+            let mut offset = run.start;
+            while offset < run.end {
+                let goal = if offset == 0 { GoalSymbols::InputElementHashbangOrRegExp } else { GoalSymbols::InputElementDiv };
+                let Ok((token, range)) = get_next_token_with_options(self.source, offset, goal, &self.options) else {
+                    break;
+                };
+                let ends_line = match &token {
+                    Token::LineTerminator(_) => true,
+                    Token::Comment(comment) => comment.contains_line_terminator(),
+                    _ => false,
+                };
+                offset = range.end;
+                pieces.push((token.kind(), range, ends_line));
+            }
+            let trailing = match record.policy {
+                _ if run.start == 0 => 0,
+                _ if run.end == self.source.len() => pieces.len(),
+                TriviaPolicy::ByLine => pieces.iter().position(|(_, _, ends_line)| *ends_line).map_or(pieces.len(), |index| index + 1),
+                TriviaPolicy::Leading => 0,
+                TriviaPolicy::Trailing => pieces.len(),
+            };
+            trivia.extend(pieces.into_iter().enumerate().map(|(index, (kind, range, _))| {
+                let attachment = if index < trailing {
+                    TriviaAttachment::Trailing(run.start)
+                } else {
+                    TriviaAttachment::Leading(run.end)
+                };
+                Trivia { kind, range, attachment }
+            }));
+        }
+        trivia
+    }
+
+    /// Record white space and comments before the current token as it is
+    /// consumed, so with a goal symbol that surely recognizes it.
+    fn record_trivia(&self) {
+        let Some(record) = &self.trivia else {
+            return;
+        };
+        let Lookahead { range, trivia_start, .. } = &self.lookahead;
+        if *trivia_start < range.start {
+            record.runs.borrow_mut().entry(*trivia_start).or_insert(range.start);
+        }
+    }
+
+    /// Recognize the current token again with another goal symbol, like
+    /// `/` as a start of `RegularExpressionLiteral`.
+    pub(super) fn rescan(&mut self, goal: GoalSymbols) {
+        let trivia_start = self.lookahead.trivia_start;
+        self.scan(self.lookahead.range.start, goal, self.lookahead.newline_before);
+        self.lookahead.trivia_start = trivia_start;
+    }
+
+    pub(super) const fn token(&self) -> Option<&Token<'src>> {
+        self.lookahead.token.as_ref()
+    }
+
+    pub(super) fn kind(&self) -> Option<TokenKind> {
+        self.lookahead.token.as_ref().map(Token::kind)
+    }
+
+    pub(super) fn at(&self, kind: TokenKind) -> bool {
+        self.kind() == Some(kind)
+    }
+
+    /// Whether the current token is `@` starting a decorator.
+    pub(super) const fn at_decorator(&self) -> bool {
+        matches!(self.token(), Some(Token::Unknown('@')))
+    }
+
+    /// Which proposals the parser accepts.
+    pub(super) const fn proposals(&self) -> Proposals {
+        self.proposals
+    }
+
+    /// Whether the current token is a given contextual keyword written
+    /// without escapes.
+    pub(super) fn at_contextual(&self, keyword: ContextualKeyword) -> bool {
+        matches!(
+            self.token(),
+            Some(Token::CommonToken(CommonToken::IdentifierName(name))) if name.keyword_kind() == Some(keyword)
+        )
+    }
+
+    /// Byte offsets of the current token.
+    pub(super) fn range(&self) -> Range<usize> {
+        self.lookahead.range.clone()
+    }
+
+    /// Byte offsets from a given one to the end of the last consumed token,
+    /// covering a production parsed since then.
+    pub(super) const fn range_from(&self, start: usize) -> Range<usize> {
+        start..self.previous_end
+    }
+
+    /// Length of the whole source text in bytes.
+    pub(super) const fn source_len(&self) -> usize {
+        self.source.len()
+    }
+
+    /// Whether a line terminator precedes the current token.
+    pub(super) const fn newline_before(&self) -> bool {
+        self.lookahead.newline_before
+    }
+
+    /// Byte offset right after the last consumed token.
+    pub(super) const fn previous_end(&self) -> usize {
+        self.previous_end
+    }
+
+    /// Kind of a token following the current one and whether a line
+    /// terminator precedes it, recognized with the `InputElementDiv` goal.
+    pub(super) fn peek_next(&self) -> (Option<TokenKind>, bool) {
+        let mut next = self.clone();
+        next.bump();
+        (next.kind(), next.newline_before())
+    }
+
+    /// Consume the current token.
+    pub(super) fn bump(&mut self) -> Option<Token<'src>> {
+        self.record_trivia();
+        let token = self.lookahead.token.take();
+        self.previous_end = self.lookahead.range.end;
+        self.scan(self.previous_end, GoalSymbols::InputElementDiv, false);
+        token
+    }
+
+    /// Consume the current token if it is of a given kind.
+    pub(super) fn eat(&mut self, kind: TokenKind) -> bool {
+        let is_matched = self.at(kind);
+        if is_matched {
+            self.bump();
+        }
+        is_matched
+    }
+
+    /// Consume the current token of a given kind or report that another one
+    /// is found.
+    pub(super) fn expect(&mut self, kind: TokenKind) -> Result<(), SourceCodeError> {
+        if self.eat(kind) {
+            return Ok(());
+        }
+        let text = kind.punctuator_text().or_else(|| kind.keyword_text()).unwrap_or_default();
+        Err(self.expected(&format!("`{text}`")))
+    }
+
+    /// Consume `;` ending a statement or insert it automatically.
+    ///
+    /// > When, as the source text is parsed from left to right, a token is
+    /// > encountered that is not allowed by any production of the grammar,
+    /// > then a semicolon is automatically inserted before the offending
+    /// > token if one or more of the following conditions is true:
+    /// >
+    /// > - The offending token is separated from the previous token by at
+    /// >   least one LineTerminator.
+    /// > - The offending token is `}`.
+    /// > - The previous token is `)` and the inserted semicolon would then be
+    /// >   parsed as the terminating semicolon of a do-while statement.
+    /// >
+    /// > When, as the source text is parsed from left to right, the end of
+    /// > the input stream of tokens is encountered and the parser is unable
+    /// > to parse the input token stream as a single instance of the goal
+    /// > nonterminal, then a semicolon is automatically inserted at the end
+    /// > of the input stream.
+    ///
+    /// Semicolons of a `for` statement head are never inserted, so they are
+    /// parsed with [`Parser::expect`] instead. The `do`-`while` case is up to
+    /// a caller.
+    ///
+    /// See <https://262.ecma-international.org/14.0/#sec-rules-of-automatic-semicolon-insertion>.
+    pub(super) fn expect_semicolon(&mut self) -> Result<(), SourceCodeError> {
+        let is_inserted = self.newline_before() || self.at(TokenKind::RightBracePunctuator) || self.is_at_end();
+        if self.eat(TokenKind::Semicolon) || is_inserted {
+            return Ok(());
+        }
+        Err(self.expected("`;`"))
+    }
+
+    /// Consume `{ FunctionBody }` without parsing it, returning its range,
+    /// if the parser defers function bodies.
+    ///
+    /// Tokens are recognized with goal symbols guessed by [`Tokenizer`] up
+    /// to a matching `}`. A body with a private name is parsed as usual
+    /// since the name is checked against enclosing classes, as well as
+    /// a body with a lexical error to report it right away. So is a body
+    /// starting with a string literal since a `"use strict"` directive
+    /// affects parameters of the function too.
+    pub(super) fn skip_function_body(&mut self) -> Option<Range<usize>> {
+        if !self.defers_function_bodies || !self.at(TokenKind::OpeningBrace) {
+            return None;
+        }
+        self.record_trivia();
+        let mut tokenizer = Tokenizer::with_goal(GoalSymbols::InputElementRegExp);
+        let mut offset = self.lookahead.range.end;
+        let mut depth = 0_usize;
+        let mut is_first = true;
+        while offset < self.source.len() {
+            let (token, range) = tokenizer.next_token(self.source, offset).ok()?;
+            offset = range.end;
+            let kind = token.kind();
+            if matches!(
+                kind,
+                TokenKind::WhiteSpace | TokenKind::LineTerminator | TokenKind::MultiLineComment | TokenKind::SingleLineComment
+            ) {
+                continue;
+            }
+            if std::mem::take(&mut is_first) && kind == TokenKind::StringLiteral {
+                return None;
+            }
+            match kind {
+                TokenKind::OpeningBrace => depth += 1,
+                TokenKind::RightBracePunctuator if depth > 0 => depth -= 1,
+                TokenKind::RightBracePunctuator => {
+                    let start = self.lookahead.range.start;
+                    self.previous_end = offset;
+                    self.scan(offset, GoalSymbols::InputElementDiv, false);
+                    return Some(start..offset);
+                },
+                TokenKind::PrivateIdentifier => return None,
+                _ => {},
+            }
+        }
+        None
+    }
+
+    /// Consume `}` closing a statement list.
+    ///
+    /// When recovering from errors, a `}` missing at the end of input is
+    /// reported and assumed, so an unfinished block keeps statements parsed
+    /// in it.
+    pub(super) fn expect_closing_brace(&mut self) -> Result<(), SourceCodeError> {
+        if self.eat(TokenKind::RightBracePunctuator) {
+            return Ok(());
+        }
+        let error = self.expected("`}`");
+        let is_at_end = self.is_at_end();
+        match &mut self.recovered {
+            Some(recovered) if is_at_end => {
+                recovered.report(error);
+                Ok(())
+            },
+            _ => Err(error),
+        }
+    }
+
+    /// Start collecting problems instead of stopping at the first one.
+    pub(super) fn start_recovering(&mut self) {
+        self.recovered = Some(Diagnostics::new());
+    }
+
+    /// Stop collecting problems, returning ones collected so far.
+    pub(super) fn stop_recovering(&mut self) -> Diagnostics {
+        self.recovered.take().unwrap_or_default()
+    }
+
+    /// Pass problems found apart from parsing, like by
+    /// [`crate::early_errors`], to the error-recovering mode, or fail with
+    /// the first of them.
+    pub(super) fn report_all(&mut self, diagnostics: Diagnostics) -> Result<(), SourceCodeError> {
+        match &mut self.recovered {
+            Some(recovered) => {
+                recovered.extend(diagnostics);
+                Ok(())
+            },
+            None => diagnostics.into_iter().next().map_or(Ok(()), Err),
+        }
+    }
+
+    /// The source text being parsed.
+    pub(super) const fn source(&self) -> &'src str {
+        self.source
+    }
+
+    /// Parse a production with a given function, recovering from its
+    /// problems if the parser is in the error-recovering mode.
+    ///
+    /// On a problem, the parser skips tokens up to and including a next `;`,
+    /// or up to `}` closing an enclosing block if `is_nested` (a stray `}`
+    /// otherwise), and the production is dropped. Braces skipped along the way are balanced, so
+    /// a nested block with a problem is skipped entirely.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a problem as is if the parser is not in the
+    /// error-recovering mode.
+    pub(super) fn recover<T>(
+        &mut self,
+        is_nested: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T, SourceCodeError>,
+    ) -> Result<Option<T>, SourceCodeError> {
+        let checkpoint = Checkpoint {
+            private_name_scopes: self.private_name_references.len(),
+            cover_initialized_names: self.cover_initialized_names.len(),
+            last_yield_or_await: self.last_yield_or_await.clone(),
+            allow_new_target: self.allow_new_target,
+            allow_super: self.allow_super,
+            is_strict: self.is_strict(),
+        };
+        let error = match parse(self) {
+            Ok(node) => return Ok(Some(node)),
+            Err(error) if self.recovered.is_none() => return Err(error),
+            Err(error) => error,
+        };
+        self.private_name_references.truncate(checkpoint.private_name_scopes);
+        self.cover_initialized_names.truncate(checkpoint.cover_initialized_names);
+        self.last_yield_or_await = checkpoint.last_yield_or_await;
+        self.allow_new_target = checkpoint.allow_new_target;
+        self.allow_super = checkpoint.allow_super;
+        self.set_strict(checkpoint.is_strict);
+        // An early error found at the end of a statement leaves nothing to
+        // skip.
+        let is_complete = error.code == ErrorCode::EarlyError
+            && error.range.end <= self.previous_end
+            && self.source[..self.previous_end].ends_with([';', '}']);
+        if let Some(recovered) = &mut self.recovered {
+            recovered.report(error);
+        }
+        if !is_complete {
+            self.synchronize(is_nested);
+        }
+        Ok(None)
+    }
+
+    /// Skip tokens up to a place where a next statement may start.
+    fn synchronize(&mut self, is_nested: bool) {
+        let mut depth = 0_usize;
+        loop {
+            if let Some(error) = self.lexical_error.take() {
+                // Skip at least a code point the tokenizer stumbled on.
+                let start = self.lookahead.range.start;
+                let width = self.source[start..].chars().next().map_or(0, char::len_utf8);
+                self.scan(error.range.end.max(start + width), GoalSymbols::InputElementDiv, false);
+                continue;
+            }
+            match self.kind() {
+                None => return,
+                Some(TokenKind::OpeningBrace) => depth += 1,
+                Some(TokenKind::RightBracePunctuator) if depth > 0 => depth -= 1,
+                Some(TokenKind::RightBracePunctuator) if is_nested => return,
+                Some(TokenKind::Semicolon | TokenKind::RightBracePunctuator) if depth == 0 => {
+                    self.bump();
+                    return;
+                },
+                _ => {},
+            }
+            self.bump();
+        }
+    }
+
+    /// Remember that the current token starts an `AssignmentExpression`.
+    pub(super) const fn mark_potential_arrow(&mut self) {
+        self.potential_arrow_start = self.lookahead.range.start;
+    }
+
+    /// Whether the current token starts an `AssignmentExpression`, so it
+    /// may be the first one of `ArrowParameters`.
+    pub(super) const fn at_potential_arrow(&self) -> bool {
+        self.lookahead.range.start == self.potential_arrow_start
+    }
+
+    /// Remember a `CoverInitializedName` to report unless it turns out to be
+    /// a part of a destructuring pattern.
+    pub(super) fn record_cover_initialized_name(&mut self, range: Range<usize>) {
+        self.cover_initialized_names.push(range);
+    }
+
+    /// Number of `CoverInitializedName`s parsed so far and not refined yet,
+    /// to pass to [`Parser::refine_cover_initialized_names`] or
+    /// [`Parser::check_cover_initialized_names`] later.
+    pub(super) const fn pending_cover_initialized_names(&self) -> usize {
+        self.cover_initialized_names.len()
+    }
+
+    /// Accept `CoverInitializedName`s parsed since a given mark as a part of
+    /// a destructuring pattern.
+    pub(super) fn refine_cover_initialized_names(&mut self, mark: usize) {
+        self.cover_initialized_names.truncate(mark);
+    }
+
+    /// Report a first `CoverInitializedName` parsed since a given mark.
+    ///
+    /// > PropertyDefinition : CoverInitializedName
+    /// >
+    /// > It is a Syntax Error if any source text is matched by this
+    /// > production.
+    pub(super) fn check_cover_initialized_names(&self, mark: usize) -> Result<(), SourceCodeError> {
+        let Some(range) = self.cover_initialized_names.get(mark) else {
+            return Ok(());
+        };
+        let message = "shorthand property with an initializer is only allowed in a destructuring pattern".to_owned();
+        Err(self.error(range.clone(), ErrorCode::EarlyError, message))
+    }
+
+    /// Remember a parsed `YieldExpression` or `AwaitExpression`.
+    pub(super) const fn record_yield_or_await(&mut self, range: Range<usize>) {
+        self.last_yield_or_await = Some(range);
+    }
+
+    /// Start tracking `YieldExpression`s and `AwaitExpression`s of a nested
+    /// function, returning ones of an enclosing function to pass to
+    /// [`Parser::leave_function`] later.
+    pub(super) const fn enter_function(&mut self) -> Option<Range<usize>> {
+        self.last_yield_or_await.take()
+    }
+
+    /// Resume tracking `YieldExpression`s and `AwaitExpression`s of
+    /// an enclosing function.
+    pub(super) const fn leave_function(&mut self, outer: Option<Range<usize>>) {
+        self.last_yield_or_await = outer;
+    }
+
+    /// The last `YieldExpression` or `AwaitExpression` of the innermost
+    /// function if it starts at a given offset or later.
+    pub(super) fn yield_or_await_since(&self, start: usize) -> Option<Range<usize>> {
+        self.last_yield_or_await.clone().filter(|range| range.start >= start)
+    }
+
+    /// Whether the source text is parsed with the `Module` goal symbol.
+    pub(super) const fn is_module(&self) -> bool {
+        self.is_module
+    }
+
+    /// Continue parsing at a given offset with a given goal symbol, like
+    /// for a function body skipped before.
+    pub(super) fn seek(&mut self, offset: usize, is_module: bool) {
+        self.is_module = is_module;
+        self.previous_end = offset;
+        self.scan(offset, GoalSymbols::InputElementDiv, false);
+    }
+
+    /// Whether the code being parsed is strict mode code.
+    ///
+    /// See <https://262.ecma-international.org/14.0/#sec-strict-mode-code>.
+    pub(super) const fn is_strict(&self) -> bool {
+        self.options.strict
+    }
+
+    /// Switch between strict mode code and non-strict code, recognizing the
+    /// current token again with the tokenizer rules of the new one.
+    pub(super) fn set_strict(&mut self, is_strict: bool) {
+        if self.options.strict != is_strict {
+            self.options.strict = is_strict;
+            self.rescan(GoalSymbols::InputElementDiv);
+        }
+    }
+
+    /// Whether a string literal at given offsets is a Use Strict Directive
+    /// if it forms a whole statement of a directive prologue.
+    ///
+    /// > A Use Strict Directive is an ExpressionStatement in a Directive
+    /// > Prologue whose StringLiteral is either of the exact code point
+    /// > sequences "use strict" or 'use strict'. A Use Strict Directive may
+    /// > not contain an EscapeSequence or LineContinuation.
+    pub(super) fn is_use_strict(&self, range: Range<usize>) -> bool {
+        matches!(&self.source[range], "\"use strict\"" | "'use strict'")
+    }
+
+    /// Recognize a string literal at given offsets again as strict mode
+    /// code, reporting escape sequences that strict mode code rejects.
+    pub(super) fn check_strict_string_literal(&self, range: Range<usize>) -> Result<(), SourceCodeError> {
+        let options = LexerOptions { strict: true, ..self.options };
+        get_next_token_with_options(self.source, range.start, GoalSymbols::InputElementDiv, &options).map(|_| ())
+    }
+
+    /// Whether `new.target` is allowed in the code being parsed.
+    pub(super) const fn new_target_allowed(&self) -> bool {
+        self.allow_new_target
+    }
+
+    /// Allow `new.target` in code of a nested function or class element,
+    /// returning whether an enclosing code allows it to pass to
+    /// [`Parser::leave_new_target_scope`] later.
+    pub(super) const fn enter_new_target_scope(&mut self) -> bool {
+        std::mem::replace(&mut self.allow_new_target, true)
+    }
+
+    /// Restore whether `new.target` is allowed in an enclosing code.
+    pub(super) const fn leave_new_target_scope(&mut self, outer: bool) {
+        self.allow_new_target = outer;
+    }
+
+    /// Which `super` forms are allowed in the code being parsed.
+    pub(super) const fn super_allowed(&self) -> SuperUsage {
+        self.allow_super
+    }
+
+    /// Allow given `super` forms in code of a nested function or class
+    /// element, returning ones an enclosing code allows to pass to
+    /// [`Parser::leave_super_scope`] later.
+    pub(super) const fn enter_super_scope(&mut self, usage: SuperUsage) -> SuperUsage {
+        std::mem::replace(&mut self.allow_super, usage)
+    }
+
+    /// Restore `super` forms allowed in an enclosing code.
+    pub(super) const fn leave_super_scope(&mut self, outer: SuperUsage) {
+        self.allow_super = outer;
+    }
+
+    /// Start collecting private names referenced in a class body.
+    pub(super) fn enter_class_body(&mut self) {
+        self.private_name_references.push(Vec::new());
+    }
+
+    /// Check private names referenced in a class body against ones the
+    /// class declares, leaving the rest to enclosing classes.
+    ///
+    /// > It is a Syntax Error if AllPrivateIdentifiersValid of ClassBody
+    /// > with argument « » is false.
+    pub(super) fn leave_class_body(&mut self, declared: &[String]) -> Result<(), SourceCodeError> {
+        let references = self.private_name_references.pop().unwrap_or_default();
+        for (name, range) in references {
+            if !declared.contains(&name) {
+                self.reference_private_name(name, range)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a private name reference like `#a` in `this.#a`, so that it
+    /// is checked when an enclosing class body ends.
+    pub(super) fn reference_private_name(&mut self, name: String, range: Range<usize>) -> Result<(), SourceCodeError> {
+        let Some(references) = self.private_name_references.last_mut() else {
+            let message = format!("private name `{name}` is not declared in an enclosing class");
+            return Err(self.error(range, ErrorCode::EarlyError, message));
+        };
+        references.push((name, range));
+        Ok(())
+    }
+
+    pub(super) fn error(&self, range: Range<usize>, code: ErrorCode, message: String) -> SourceCodeError {
+        SourceCodeError::new(self.source, range, code, message)
+    }
+
+    /// Report the current token as not matching any production expected at
+    /// its place.
+    pub(super) fn unexpected(&self) -> SourceCodeError {
+        if let Some(error) = &self.lexical_error {
+            return error.clone();
+        }
+        self.error(self.range(), self.mismatch_code(), format!("unexpected {}", self.found()))
+    }
+
+    /// Report the current token as not being a described one, like
+    /// ``"`)`"`` or `"identifier"`.
+    pub(super) fn expected(&self, description: &str) -> SourceCodeError {
+        if let Some(error) = &self.lexical_error {
+            return error.clone();
+        }
+        let message = format!("expected {description}, found {}", self.found());
+        let mut error = self.error(self.range(), self.mismatch_code(), message);
+        error.expected.push(description.to_owned());
+        error
+    }
+
+    const fn mismatch_code(&self) -> ErrorCode {
+        if self.lookahead.token.is_none() {
+            ErrorCode::IncompleteInput
+        } else {
+            ErrorCode::UnexpectedInput
+        }
+    }
+
+    /// Describe the current token for an error message.
+    fn found(&self) -> String {
+        match &self.lookahead.token {
+            None => "end of input".to_owned(),
+            Some(Token::CommonToken(CommonToken::StringLiteral(_))) => "string literal".to_owned(),
+            Some(Token::CommonToken(CommonToken::Template(_)) | Token::TemplateSubstitutionTail(_)) => {
+                "template literal".to_owned()
+            },
+            Some(Token::RegularExpressionLiteral(_)) => "regular expression literal".to_owned(),
+            Some(_) => format!("`{}`", &self.source[self.range()]),
+        }
+    }
+}
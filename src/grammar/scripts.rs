@@ -0,0 +1,234 @@
+//! Syntactic grammar of scripts.
+//!
+//! Implements <https://262.ecma-international.org/14.0/#sec-scripts>.
+
+use super::expressions::{Expression, Literal};
+use super::parser::{Parameters, Parser, ParserOptions};
+use super::SourceType;
+use super::statements::{BindingIdentifier, Declaration, Statement, StatementListItem, VariableKind};
+use crate::early_errors::{check_script, ScriptChecker};
+use crate::lexical_grammar::TokenKind;
+use crate::{Diagnostics, ErrorCode, SourceCodeError};
+use std::ops::Range;
+
+/// <https://262.ecma-international.org/14.0/#prod-Script>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Script {
+    /// `ScriptBody`, empty for an empty script.
+    pub body: Vec<StatementListItem>,
+    /// Whether the script is strict mode code, being a module or starting
+    /// with a `"use strict"` directive.
+    pub is_strict: bool,
+    /// The whole source text, including white space and comments around
+    /// the body.
+    pub range: Range<usize>,
+}
+
+impl Script {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of the script, which does not include function declarations.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        self.body.iter().flat_map(StatementListItem::top_level_lexically_declared_names).collect()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-vardeclarednames>
+    /// of the script, including names of function declarations.
+    #[must_use]
+    pub fn var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        self.body.iter().flat_map(StatementListItem::top_level_var_declared_names).collect()
+    }
+}
+
+/// Settings of [`validate`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ValidationOptions {
+    pub source_type: SourceType,
+    pub parser_options: ParserOptions,
+}
+
+/// Check that a source text is a correct script or module, freeing a tree
+/// of each top-level statement once the statement is checked.
+///
+/// This is the full parser, not a separate recognizer: it reports the same
+/// problems as [`Parser::parse_script_with_diagnostics`] and builds the
+/// same tree for each statement. It does not avoid building trees; it only
+/// avoids keeping them, and only names declared at the top level are kept
+/// between statements. So memory used is bound by the largest top-level
+/// statement rather than by the whole text, and a single large statement,
+/// like a bundle wrapped into one function, costs as much as parsing it.
+///
+/// A module is checked one module item at a time the same way, reporting
+/// problems of [`Parser::parse_module_with_diagnostics`]; a local binding
+/// of `export { a }` is kept until the end, since a declaration after the
+/// export may bind it.
+///
+/// Nesting deeper than [`ParserOptions::max_nesting_depth`] of
+/// `options.parser_options` is reported as [`ErrorCode::NestingTooDeep`],
+/// so hostile input cannot exhaust the native stack.
+///
+/// # Errors
+///
+/// Will return `Err` with a nonempty list of [`SourceCodeError`], if
+/// the source text does not form a script or a module.
+pub fn validate(source: &str, options: ValidationOptions) -> Result<(), Vec<SourceCodeError>> {
+    let mut diagnostics = Diagnostics::new();
+    if options.source_type.is_module(source) {
+        Parser::new_module(source).with_options(options.parser_options).validate_module(&mut diagnostics);
+    } else {
+        Parser::new(source).with_options(options.parser_options).validate_script(&mut diagnostics);
+    }
+    if diagnostics.has_errors() {
+        Err(diagnostics.into_vec())
+    } else {
+        Ok(())
+    }
+}
+
+impl Parser<'_> {
+    /// Parse `Script` up to the end of the source text.
+    ///
+    /// ```plain
+    /// Script :
+    ///     ScriptBody_opt
+    ///
+    /// ScriptBody :
+    ///     StatementList[~Yield, ~Await, ~Return]
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if the source text does
+    /// not form a script.
+    pub fn parse_script(&mut self) -> Result<Script, SourceCodeError> {
+        let (mut body, _) = self.parse_directive_prologue(false, Parameters::default())?;
+        let is_strict = self.is_strict();
+        while !self.is_at_end() {
+            body.extend(self.recover(false, Self::parse_top_level_item)?);
+        }
+        let script = Script { body, is_strict, range: 0..self.source_len() };
+        let mut early_errors = Diagnostics::new();
+        check_script(self.source(), &script, self.is_module(), &mut early_errors);
+        self.report_all(early_errors)?;
+        Ok(script)
+    }
+
+    /// Parse a statement or a declaration of `ScriptBody`.
+    fn parse_top_level_item(&mut self) -> Result<StatementListItem, SourceCodeError> {
+        let item = self.parse_statement_list_item(Parameters::default())?;
+        // > It is a Syntax Error if the goal symbol is Script and
+        // > UsingDeclaration is not contained, either directly or
+        // > indirectly, within a Block, CaseBlock, ForStatement,
+        // > ForInOfStatement, FunctionBody, GeneratorBody,
+        // > AsyncGeneratorBody, AsyncFunctionBody, ClassStaticBlock,
+        // > or ClassBody.
+        if let StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) = &item {
+            if list.kind == VariableKind::Using && !self.is_module() {
+                let message = "`using` declarations are not allowed at the top level of a script".to_owned();
+                return Err(self.error(list.range.clone(), ErrorCode::EarlyError, message));
+            }
+        }
+        Ok(item)
+    }
+
+    /// Check a whole script like [`Parser::parse_script_with_diagnostics`]
+    /// does, dropping each top-level statement once it is checked.
+    fn validate_script(&mut self, diagnostics: &mut Diagnostics) {
+        self.start_recovering();
+        let result = self.validate_script_body();
+        diagnostics.extend(self.stop_recovering());
+        diagnostics.extend(self.warnings().iter().cloned());
+        if let Err(error) = result {
+            diagnostics.report(error);
+        }
+    }
+
+    fn validate_script_body(&mut self) -> Result<(), SourceCodeError> {
+        let (prologue, _) = self.parse_directive_prologue(false, Parameters::default())?;
+        let mut checker = ScriptChecker::new(self.is_module(), self.is_strict());
+        let mut early_errors = Diagnostics::new();
+        for item in &prologue {
+            checker.check(self.source(), item, &mut early_errors);
+        }
+        while !self.is_at_end() {
+            if let Some(item) = self.recover(false, Self::parse_top_level_item)? {
+                checker.check(self.source(), &item, &mut early_errors);
+            }
+        }
+        self.report_all(early_errors)
+    }
+
+    /// Parse statements of
+    /// <https://262.ecma-international.org/14.0/#directive-prologue> at
+    /// the start of a script or a function body, returning them and whether
+    /// they include a Use Strict Directive.
+    ///
+    /// > A Directive Prologue is the longest sequence of
+    /// > ExpressionStatements occurring as the initial StatementListItems or
+    /// > ModuleItems of a FunctionBody, a ScriptBody, or a ModuleBody and
+    /// > where each ExpressionStatement in the sequence consists entirely of
+    /// > a StringLiteral token followed by a semicolon.
+    ///
+    /// Code after a Use Strict Directive is parsed as strict mode code,
+    /// including the rest of the prologue. A statement starting with
+    /// a string literal but not being a directive ends the prologue and is
+    /// returned too.
+    pub(super) fn parse_directive_prologue(
+        &mut self,
+        is_nested: bool,
+        parameters: Parameters
+    ) -> Result<(Vec<StatementListItem>, bool), SourceCodeError> {
+        let mut statements = Vec::new();
+        let mut has_use_strict = false;
+        while self.at(TokenKind::StringLiteral) {
+            let token = self.range();
+            let Some(statement) = self.recover(is_nested, |parser| parser.parse_statement_list_item(parameters))? else {
+                break;
+            };
+            let is_directive = matches!(
+                &statement,
+                StatementListItem::Statement(Statement::ExpressionStatement(
+                    Expression::Literal(Literal::StringLiteral(_), range),
+                    _
+                )) if *range == token
+            );
+            statements.push(statement);
+            if !is_directive {
+                break;
+            }
+            if !has_use_strict && self.is_use_strict(token) {
+                has_use_strict = true;
+                // > It is a Syntax Error if [...] the source text matched by
+                // > this production is strict mode code.
+                //
+                // Directives before this one are strict mode code too.
+                for directive in &statements {
+                    self.check_strict_string_literal(directive.range())?;
+                }
+                self.set_strict(true);
+            }
+        }
+        Ok((statements, has_use_strict))
+    }
+
+    /// Parse `Script` like [`Parser::parse_script`] does, but recover from
+    /// problems instead of stopping at the first one, reporting them into
+    /// a sink.
+    ///
+    /// A statement or declaration with a problem is skipped up to a next `;`
+    /// or up to `}` closing an enclosing block, and parsing continues after
+    /// it. A `}` missing at the end of input is assumed. So the tree keeps
+    /// everything parsed correctly, which suits editors where a file being
+    /// edited is seldom correct.
+    pub fn parse_script_with_diagnostics(&mut self, diagnostics: &mut Diagnostics) -> Script {
+        self.start_recovering();
+        let script = self.parse_script();
+        diagnostics.extend(self.stop_recovering());
+        diagnostics.extend(self.warnings().iter().cloned());
+        // Each statement is recovered from, so the script itself never fails.
+        script.unwrap_or_else(|error| {
+            diagnostics.report(error);
+            Script { body: Vec::new(), is_strict: self.is_strict(), range: 0..self.source_len() }
+        })
+    }
+}
@@ -0,0 +1,1360 @@
+//! Syntactic grammar of statements and declarations.
+//!
+//! Implements <https://262.ecma-international.org/14.0/#sec-ecmascript-language-statements-and-declarations>.
+
+use super::expressions::{Expression, IdentifierReference, PropertyName};
+use super::functions::{Class, Function};
+use super::parser::{Parameters, Parser};
+use crate::lexical_grammar::{is_reserved_in_strict_mode, ContextualKeyword, TokenKind};
+use crate::{ErrorCode, SourceCodeError};
+use std::ops::Range;
+use std::rc::Rc;
+
+/************************************************
+ *
+ * Statement tree
+ *
+ ************************************************/
+
+/// <https://262.ecma-international.org/14.0/#prod-Statement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Statement {
+    BlockStatement(Block),
+    VariableStatement(VariableDeclarationList),
+    EmptyStatement(Range<usize>),
+    /// An expression with `;` after it, if any.
+    ExpressionStatement(Expression, Range<usize>),
+    IfStatement(IfStatement),
+    DoWhileStatement(DoWhileStatement),
+    WhileStatement(WhileStatement),
+    // Boxed since loop heads are several expressions long.
+    ForStatement(Box<ForStatement>),
+    ForInStatement(Box<ForInOfStatement>),
+    ForOfStatement(Box<ForInOfStatement>),
+    ContinueStatement(ContinueStatement),
+    BreakStatement(BreakStatement),
+    ReturnStatement(ReturnStatement),
+    WithStatement(WithStatement),
+    SwitchStatement(SwitchStatement),
+    LabelledStatement(LabelledStatement),
+    ThrowStatement(ThrowStatement),
+    TryStatement(TryStatement),
+    DebuggerStatement(Range<usize>),
+}
+
+impl Statement {
+    /// Byte offsets of the source text the statement covers.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::BlockStatement(node) => node.range.clone(),
+            Self::VariableStatement(node) => node.range.clone(),
+            Self::EmptyStatement(range) | Self::ExpressionStatement(_, range) | Self::DebuggerStatement(range) => {
+                range.clone()
+            },
+            Self::IfStatement(node) => node.range.clone(),
+            Self::DoWhileStatement(node) => node.range.clone(),
+            Self::WhileStatement(node) => node.range.clone(),
+            Self::ForStatement(node) => node.range.clone(),
+            Self::ForInStatement(node) | Self::ForOfStatement(node) => node.range.clone(),
+            Self::ContinueStatement(node) => node.range.clone(),
+            Self::BreakStatement(node) => node.range.clone(),
+            Self::ReturnStatement(node) => node.range.clone(),
+            Self::WithStatement(node) => node.range.clone(),
+            Self::SwitchStatement(node) => node.range.clone(),
+            Self::LabelledStatement(node) => node.range.clone(),
+            Self::ThrowStatement(node) => node.range.clone(),
+            Self::TryStatement(node) => node.range.clone(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-vardeclarednames>:
+    /// names bound by `var` in the statement, except in nested functions,
+    /// in source order.
+    #[must_use]
+    pub fn var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        let mut names = Vec::new();
+        self.collect_var_declared_names(false, &mut names);
+        names
+    }
+
+    /// Collect `VarDeclaredNames`, or only names bound by
+    /// `for (var ... of ...)` heads if `for_of_only`, which Annex B treats
+    /// apart from others when they redeclare a catch parameter.
+    pub(crate) fn collect_var_declared_names<'tree>(
+        &'tree self,
+        for_of_only: bool,
+        names: &mut Vec<&'tree BindingIdentifier>
+    ) {
+        let body = match self {
+            Self::BlockStatement(block) => {
+                for item in &block.statements {
+                    item.collect_var_declared_names(for_of_only, names);
+                }
+                return;
+            },
+            Self::VariableStatement(list) if !for_of_only => {
+                names.extend(list.bound_names());
+                return;
+            },
+            Self::IfStatement(statement) => {
+                statement.consequent.collect_var_declared_names(for_of_only, names);
+                match &statement.alternative {
+                    Some(alternative) => alternative,
+                    None => return,
+                }
+            },
+            Self::DoWhileStatement(statement) => &statement.body,
+            Self::WhileStatement(statement) => &statement.body,
+            Self::ForStatement(statement) => {
+                if let Some(ForInit::VariableDeclarationList(list)) = &statement.init {
+                    if list.kind == VariableKind::Var && !for_of_only {
+                        names.extend(list.bound_names());
+                    }
+                }
+                &statement.body
+            },
+            Self::ForInStatement(node) | Self::ForOfStatement(node) => {
+                let is_collected = !for_of_only || matches!(self, Self::ForOfStatement(_));
+                if let ForTarget::ForDeclaration { kind: VariableKind::Var, binding, .. } = &node.target {
+                    if is_collected {
+                        names.extend(binding.bound_names());
+                    }
+                }
+                &node.body
+            },
+            Self::WithStatement(statement) => &statement.body,
+            Self::SwitchStatement(statement) => {
+                for item in statement.cases.iter().flat_map(|case| &case.statements) {
+                    item.collect_var_declared_names(for_of_only, names);
+                }
+                return;
+            },
+            Self::LabelledStatement(statement) => match &*statement.body {
+                LabelledItem::Statement(statement) => statement,
+                LabelledItem::FunctionDeclaration(_) => return,
+            },
+            Self::TryStatement(statement) => {
+                let blocks = std::iter::once(&statement.block)
+                    .chain(statement.handler.as_ref().map(|handler| &handler.body))
+                    .chain(&statement.finalizer);
+                for item in blocks.flat_map(|block| &block.statements) {
+                    item.collect_var_declared_names(for_of_only, names);
+                }
+                return;
+            },
+            _ => return,
+        };
+        body.collect_var_declared_names(for_of_only, names);
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-Declaration>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Declaration {
+    FunctionDeclaration(Rc<Function>),
+    ClassDeclaration(Class),
+    /// `let` or `const` declarations, as well as `using` and `await using`
+    /// ones of the explicit resource management proposal.
+    LexicalDeclaration(VariableDeclarationList),
+}
+
+impl Declaration {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::FunctionDeclaration(node) => node.range.clone(),
+            Self::ClassDeclaration(node) => node.range.clone(),
+            Self::LexicalDeclaration(node) => node.range.clone(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-boundnames>
+    /// of the declaration.
+    #[must_use]
+    pub fn bound_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::FunctionDeclaration(function) => function.name.iter().collect(),
+            Self::ClassDeclaration(class) => class.name.iter().collect(),
+            Self::LexicalDeclaration(list) => list.bound_names(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-StatementListItem>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StatementListItem {
+    Statement(Statement),
+    Declaration(Declaration),
+}
+
+impl StatementListItem {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Statement(statement) => statement.range(),
+            Self::Declaration(declaration) => declaration.range(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of the item in a block, a `switch` case or a module, where function
+    /// declarations are lexically scoped.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::Declaration(declaration) => declaration.bound_names(),
+            Self::Statement(Statement::LabelledStatement(statement)) => statement.body.lexically_declared_names(),
+            Self::Statement(_) => Vec::new(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-toplevellexicallydeclarednames>
+    /// of the item directly in a script or a function body, where function
+    /// declarations are `var`-scoped.
+    #[must_use]
+    pub fn top_level_lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::Declaration(Declaration::FunctionDeclaration(_)) | Self::Statement(_) => Vec::new(),
+            Self::Declaration(declaration) => declaration.bound_names(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-vardeclarednames>
+    /// of the item in a block, a `switch` case or a module.
+    #[must_use]
+    pub fn var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        let mut names = Vec::new();
+        self.collect_var_declared_names(false, &mut names);
+        names
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-toplevelvardeclarednames>
+    /// of the item directly in a script or a function body, including names
+    /// of function declarations.
+    #[must_use]
+    pub fn top_level_var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::Declaration(Declaration::FunctionDeclaration(function)) => function.name.iter().collect(),
+            Self::Declaration(_) => Vec::new(),
+            Self::Statement(Statement::LabelledStatement(statement)) => statement.body.top_level_var_declared_names(),
+            Self::Statement(statement) => statement.var_declared_names(),
+        }
+    }
+
+    /// See [`Statement::collect_var_declared_names`].
+    pub(crate) fn collect_var_declared_names<'tree>(
+        &'tree self,
+        for_of_only: bool,
+        names: &mut Vec<&'tree BindingIdentifier>
+    ) {
+        if let Self::Statement(statement) = self {
+            statement.collect_var_declared_names(for_of_only, names);
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-Block>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub statements: Vec<StatementListItem>,
+    pub range: Range<usize>,
+}
+
+impl Block {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of the block, in source order.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        self.statements.iter().flat_map(StatementListItem::lexically_declared_names).collect()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-vardeclarednames>
+    /// of the block, in source order.
+    #[must_use]
+    pub fn var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        self.statements.iter().flat_map(StatementListItem::var_declared_names).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const,
+    /// `using`, parsed only with
+    /// [`super::Proposals::explicit_resource_management`].
+    Using,
+    /// `await using`, parsed only with
+    /// [`super::Proposals::explicit_resource_management`].
+    AwaitUsing,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-VariableDeclarationList>
+/// or <https://262.ecma-international.org/14.0/#prod-BindingList>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariableDeclarationList {
+    pub kind: VariableKind,
+    pub declarations: Vec<VariableDeclaration>,
+    /// From the keyword up to a trailing `;`, if any.
+    pub range: Range<usize>,
+}
+
+impl VariableDeclarationList {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-boundnames>
+    /// of all declarations, in source order.
+    #[must_use]
+    pub fn bound_names(&self) -> Vec<&BindingIdentifier> {
+        self.declarations.iter().flat_map(|declaration| declaration.target.bound_names()).collect()
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-VariableDeclaration> or
+/// <https://262.ecma-international.org/14.0/#prod-LexicalBinding>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariableDeclaration {
+    pub target: Binding,
+    pub initializer: Option<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-BindingIdentifier>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BindingIdentifier {
+    /// `StringValue` of the identifier, with escapes decoded.
+    pub name: String,
+    pub range: Range<usize>,
+}
+
+/// A name or a destructuring pattern a value is bound to, like
+/// <https://262.ecma-international.org/14.0/#prod-ForBinding>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Binding {
+    BindingIdentifier(BindingIdentifier),
+    ObjectBindingPattern(ObjectBindingPattern),
+    ArrayBindingPattern(ArrayBindingPattern),
+}
+
+impl Binding {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::BindingIdentifier(node) => node.range.clone(),
+            Self::ObjectBindingPattern(node) => node.range.clone(),
+            Self::ArrayBindingPattern(node) => node.range.clone(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-boundnames>
+    /// of the binding, in source order.
+    #[must_use]
+    pub fn bound_names(&self) -> Vec<&BindingIdentifier> {
+        let mut names = Vec::new();
+        self.collect_bound_names(&mut names);
+        names
+    }
+
+    fn collect_bound_names<'a>(&'a self, names: &mut Vec<&'a BindingIdentifier>) {
+        match self {
+            Self::BindingIdentifier(name) => names.push(name),
+            Self::ObjectBindingPattern(pattern) => {
+                for property in &pattern.properties {
+                    match property {
+                        BindingProperty::SingleNameBinding { name, .. } => names.push(name),
+                        BindingProperty::Property { value, .. } => value.target.collect_bound_names(names),
+                    }
+                }
+                names.extend(&pattern.rest);
+            },
+            Self::ArrayBindingPattern(pattern) => {
+                for element in pattern.elements.iter().flatten() {
+                    element.target.collect_bound_names(names);
+                }
+                if let Some(rest) = &pattern.rest {
+                    rest.collect_bound_names(names);
+                }
+            },
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ObjectBindingPattern>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObjectBindingPattern {
+    pub properties: Vec<BindingProperty>,
+    /// `BindingRestProperty`, like `b` in `{ a, ...b }`.
+    pub rest: Option<BindingIdentifier>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-BindingProperty>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BindingProperty {
+    /// A shorthand property like `{ a = 1 }`.
+    SingleNameBinding { name: BindingIdentifier, initializer: Option<Expression> },
+    /// `PropertyName : BindingElement`
+    Property { name: PropertyName, value: BindingElement },
+}
+
+impl BindingProperty {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::SingleNameBinding { name, initializer } => {
+                name.range.start..initializer.as_ref().map_or(name.range.end, |initializer| initializer.range().end)
+            },
+            Self::Property { name, value } => name.range().start..value.range.end,
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ArrayBindingPattern>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArrayBindingPattern {
+    /// Bound elements, `None` for holes like the first one of `[, a]`.
+    pub elements: Vec<Option<BindingElement>>,
+    /// `BindingRestElement`, like `b` in `[a, ...b]`.
+    pub rest: Option<Box<Binding>>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-BindingElement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BindingElement {
+    pub target: Binding,
+    pub initializer: Option<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-IfStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IfStatement {
+    pub condition: Expression,
+    pub consequent: Box<Statement>,
+    pub alternative: Option<Box<Statement>>,
+    pub range: Range<usize>,
+}
+
+/// `do Statement while ( Expression ) ;`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoWhileStatement {
+    pub body: Box<Statement>,
+    pub condition: Expression,
+    pub range: Range<usize>,
+}
+
+/// `while ( Expression ) Statement`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub body: Box<Statement>,
+    pub range: Range<usize>,
+}
+
+/// `for ( init ; condition ; update ) Statement`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForStatement {
+    pub init: Option<ForInit>,
+    pub condition: Option<Expression>,
+    pub update: Option<Expression>,
+    pub body: Box<Statement>,
+    pub range: Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ForInit {
+    Expression(Expression),
+    VariableDeclarationList(VariableDeclarationList),
+}
+
+impl ForInit {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Expression(expression) => expression.range(),
+            Self::VariableDeclarationList(list) => list.range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ForInOfStatement> of
+/// either `in` or `of` kind.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForInOfStatement {
+    /// Whether the statement is `for await ( ... of ... )` of an async
+    /// function.
+    pub is_await: bool,
+    pub target: ForTarget,
+    /// An object to enumerate properties of or an iterable to iterate.
+    pub source: Expression,
+    pub body: Box<Statement>,
+    pub range: Range<usize>,
+}
+
+/// What each iteration of `for`-`in` and `for`-`of` assigns to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ForTarget {
+    /// An existing `LeftHandSideExpression` like `a.b` in `for (a.b of c)`.
+    Expression(Expression),
+    /// `var ForBinding` or `ForDeclaration`, like `let a` in
+    /// `for (let a of b)`.
+    ForDeclaration { kind: VariableKind, binding: Binding, range: Range<usize> },
+}
+
+impl ForTarget {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Expression(expression) => expression.range(),
+            Self::ForDeclaration { range, .. } => range.clone(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ContinueStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContinueStatement {
+    pub label: Option<String>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-BreakStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakStatement {
+    pub label: Option<String>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ReturnStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReturnStatement {
+    pub argument: Option<Expression>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-WithStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithStatement {
+    pub object: Expression,
+    pub body: Box<Statement>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-SwitchStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwitchStatement {
+    pub discriminant: Expression,
+    pub cases: Vec<CaseClause>,
+    pub range: Range<usize>,
+}
+
+impl SwitchStatement {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of `CaseBlock`, shared by all clauses.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        let statements = self.cases.iter().flat_map(|case| &case.statements);
+        statements.flat_map(StatementListItem::lexically_declared_names).collect()
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-CaseClause> or
+/// <https://262.ecma-international.org/14.0/#prod-DefaultClause>.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseClause {
+    /// `None` for `default`.
+    pub test: Option<Expression>,
+    pub statements: Vec<StatementListItem>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-LabelledStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LabelledStatement {
+    pub label: String,
+    pub body: Box<LabelledItem>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-LabelledItem>
+#[derive(Clone, Debug, Eq, PartialEq)]
+// Boxed into `LabelledStatement` already, so a shared function does not
+// leave much unused.
+#[allow(clippy::large_enum_variant)]
+pub enum LabelledItem {
+    Statement(Statement),
+    FunctionDeclaration(Rc<Function>),
+}
+
+impl LabelledItem {
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Statement(statement) => statement.range(),
+            Self::FunctionDeclaration(function) => function.range.clone(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallydeclarednames>
+    /// of the item: the name of a labelled function declaration, if any.
+    #[must_use]
+    pub fn lexically_declared_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::FunctionDeclaration(function) => function.name.iter().collect(),
+            Self::Statement(Statement::LabelledStatement(statement)) => statement.body.lexically_declared_names(),
+            Self::Statement(_) => Vec::new(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-toplevelvardeclarednames>
+    /// of the item directly in a script or a function body.
+    #[must_use]
+    pub fn top_level_var_declared_names(&self) -> Vec<&BindingIdentifier> {
+        match self {
+            Self::FunctionDeclaration(function) => function.name.iter().collect(),
+            Self::Statement(Statement::LabelledStatement(statement)) => statement.body.top_level_var_declared_names(),
+            Self::Statement(statement) => statement.var_declared_names(),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-ThrowStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThrowStatement {
+    pub argument: Expression,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-TryStatement>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryStatement {
+    pub block: Block,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<Block>,
+    pub range: Range<usize>,
+}
+
+/// <https://262.ecma-international.org/14.0/#prod-Catch>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CatchClause {
+    /// `None` for `catch { ... }` without a parameter.
+    pub parameter: Option<Binding>,
+    pub body: Block,
+    pub range: Range<usize>,
+}
+
+/************************************************
+ *
+ * 14 ECMAScript Language: Statements and Declarations
+ *
+ ************************************************/
+
+/// Mark plain function declarations of a block or a `switch` case for
+/// Annex B hoisting, see [`super::Function::is_legacy_block_function`].
+///
+/// Strict mode code has no such hoisting.
+fn mark_legacy_block_functions(statements: &mut [StatementListItem], is_strict: bool) {
+    for statement in statements {
+        if let StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) = statement {
+            let function = Rc::get_mut(function).expect("a parsed declaration is not shared yet");
+            function.is_legacy_block_function =
+                cfg!(feature = "annex-b") && !is_strict && !function.is_generator && !function.is_async;
+        }
+    }
+}
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-StatementListItem>.
+    pub(super) fn parse_statement_list_item(
+        &mut self,
+        parameters: Parameters
+    ) -> Result<StatementListItem, SourceCodeError> {
+        if self.at(TokenKind::Function) || self.at_async_function() {
+            let function = self.parse_function(true, parameters)?;
+            return Ok(StatementListItem::Declaration(Declaration::FunctionDeclaration(Rc::new(function))));
+        }
+        if self.at(TokenKind::Class) || self.at_decorator() {
+            let class = self.parse_class(true, parameters)?;
+            return Ok(StatementListItem::Declaration(Declaration::ClassDeclaration(class)));
+        }
+        if self.at_lexical_declaration() || self.at_using_declaration(parameters, false) {
+            let mut declaration = self.parse_lexical_declaration(parameters.with_in(true))?;
+            self.expect_semicolon()?;
+            declaration.range.end = self.previous_end();
+            return Ok(StatementListItem::Declaration(Declaration::LexicalDeclaration(declaration)));
+        }
+        Ok(StatementListItem::Statement(self.parse_statement(parameters)?))
+    }
+
+    /// Parse `StatementList` items up to a closing `}`, not consuming it.
+    pub(super) fn parse_statement_list(&mut self, parameters: Parameters) -> Result<Vec<StatementListItem>, SourceCodeError> {
+        let mut statements = Vec::new();
+        while !self.at(TokenKind::RightBracePunctuator) && !self.is_at_end() {
+            statements.extend(self.recover(true, |parser| parser.parse_statement_list_item(parameters))?);
+        }
+        Ok(statements)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-Statement>.
+    pub(super) fn parse_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        self.nested(|parser| parser.parse_statement_inner(parameters))
+    }
+
+    fn parse_statement_inner(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        match self.kind() {
+            Some(TokenKind::OpeningBrace) => Ok(Statement::BlockStatement(self.parse_block(parameters)?)),
+            Some(TokenKind::Var) => {
+                self.bump();
+                let mut declarations =
+                    self.parse_variable_declaration_list(start, VariableKind::Var, parameters.with_in(true))?;
+                self.expect_semicolon()?;
+                declarations.range.end = self.previous_end();
+                Ok(Statement::VariableStatement(declarations))
+            },
+            Some(TokenKind::Semicolon) => {
+                self.bump();
+                Ok(Statement::EmptyStatement(self.range_from(start)))
+            },
+            Some(TokenKind::If) => self.parse_if_statement(parameters),
+            Some(TokenKind::Do) => self.parse_do_while_statement(parameters),
+            Some(TokenKind::While) => self.parse_while_statement(parameters),
+            Some(TokenKind::For) => self.parse_for_statement(parameters),
+            Some(TokenKind::Continue) => {
+                self.bump();
+                let label = self.parse_optional_label(parameters)?;
+                self.expect_semicolon()?;
+                Ok(Statement::ContinueStatement(ContinueStatement { label, range: self.range_from(start) }))
+            },
+            Some(TokenKind::Break) => {
+                self.bump();
+                let label = self.parse_optional_label(parameters)?;
+                self.expect_semicolon()?;
+                Ok(Statement::BreakStatement(BreakStatement { label, range: self.range_from(start) }))
+            },
+            Some(TokenKind::Return) => self.parse_return_statement(parameters),
+            Some(TokenKind::With) => {
+                // > It is a Syntax Error if the source text matched by this
+                // > production is contained in strict mode code.
+                if self.is_strict() {
+                    let message = "`with` is not allowed in strict mode code".to_owned();
+                    return Err(self.error(self.range(), ErrorCode::EarlyError, message));
+                }
+                self.warn(self.range(), "`with` statements are deprecated".to_owned());
+                self.bump();
+                let (object, body) = self.parse_parenthesized_head_and_body(parameters)?;
+                Ok(Statement::WithStatement(WithStatement { object, body, range: self.range_from(start) }))
+            },
+            Some(TokenKind::Switch) => self.parse_switch_statement(parameters),
+            Some(TokenKind::Throw) => {
+                self.bump();
+                // `throw [no LineTerminator here] Expression ;`
+                if self.newline_before() {
+                    let message = "line terminator is not allowed after `throw`".to_owned();
+                    return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+                }
+                let argument = self.parse_expression_with(parameters.with_in(true))?;
+                self.expect_semicolon()?;
+                Ok(Statement::ThrowStatement(ThrowStatement { argument, range: self.range_from(start) }))
+            },
+            Some(TokenKind::Try) => self.parse_try_statement(parameters),
+            Some(TokenKind::Debugger) => {
+                self.bump();
+                self.expect_semicolon()?;
+                Ok(Statement::DebuggerStatement(self.range_from(start)))
+            },
+            Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await)
+                if self.peek_next().0 == Some(TokenKind::Colon) => self.parse_labelled_statement(parameters),
+            _ => self.parse_expression_statement(parameters),
+        }
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-Block>.
+    fn parse_block(&mut self, parameters: Parameters) -> Result<Block, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut statements = self.parse_statement_list(parameters)?;
+        mark_legacy_block_functions(&mut statements, self.is_strict());
+        self.expect_closing_brace()?;
+        Ok(Block { statements, range: self.range_from(start) })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ExpressionStatement>.
+    ///
+    /// ```plain
+    /// ExpressionStatement[Yield, Await] :
+    ///     [lookahead ∉ { {, function, async [no LineTerminator here] function, class, let [ }]
+    ///         Expression[+In, ?Yield, ?Await] ;
+    /// ```
+    ///
+    /// `{` always starts a block, so it is not checked here.
+    fn parse_expression_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let excluded = match self.kind() {
+            Some(TokenKind::Function) => Some("function"),
+            Some(TokenKind::Class) => Some("class"),
+            _ if self.at_decorator() => Some("@"),
+            _ if self.at_async_function() => Some("async function"),
+            _ if self.at_contextual(ContextualKeyword::Let) && self.peek_next().0 == Some(TokenKind::OpeningBracket) => {
+                Some("let [")
+            },
+            _ => None,
+        };
+        if let Some(excluded) = excluded {
+            let message = format!("`{excluded}` cannot start an expression statement");
+            return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+        }
+        let expression = self.parse_expression_with(parameters.with_in(true))?;
+        self.expect_semicolon()?;
+        let range = expression.range().start..self.previous_end();
+        Ok(Statement::ExpressionStatement(expression, range))
+    }
+
+    /// Parse `( Expression ) Statement` shared by `while` and `with`.
+    fn parse_parenthesized_head_and_body(
+        &mut self,
+        parameters: Parameters
+    ) -> Result<(Expression, Box<Statement>), SourceCodeError> {
+        let head = self.parse_parenthesized_expression(parameters)?;
+        let body = self.parse_substatement(parameters)?;
+        Ok((head, Box::new(body)))
+    }
+
+    /// Parse `( Expression )` of a statement head.
+    fn parse_parenthesized_expression(&mut self, parameters: Parameters) -> Result<Expression, SourceCodeError> {
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let expression = self.parse_expression_with(parameters.with_in(true))?;
+        self.expect(TokenKind::ClosingParenthesis)?;
+        Ok(expression)
+    }
+
+    /// Parse `Statement` nested in `if`, `with` or a loop.
+    ///
+    /// > It is a Syntax Error if IsLabelledFunction(Statement) is true.
+    fn parse_substatement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let statement = self.parse_statement(parameters)?;
+        let mut labelled = &statement;
+        while let Statement::LabelledStatement(LabelledStatement { body, .. }) = labelled {
+            match &**body {
+                LabelledItem::Statement(statement) => labelled = statement,
+                LabelledItem::FunctionDeclaration(function) => {
+                    let message = "labelled function declaration cannot be a body of a statement".to_owned();
+                    return Err(self.error(function.range.clone(), ErrorCode::EarlyError, message));
+                },
+            }
+        }
+        Ok(statement)
+    }
+
+    /// Parse a clause of `if`.
+    ///
+    /// Annex B also allows a function declaration there, see
+    /// <https://262.ecma-international.org/14.0/#sec-functiondeclarations-in-ifstatement-statement-clauses>:
+    ///
+    /// > IfStatement[Yield, Await, Return] :
+    /// >     if ( Expression[+In, ?Yield, ?Await] ) FunctionDeclaration[?Yield, ?Await, ~Default] else Statement[?Yield, ?Await, ?Return]
+    /// >     if ( Expression[+In, ?Yield, ?Await] ) Statement[?Yield, ?Await, ?Return] else FunctionDeclaration[?Yield, ?Await, ~Default]
+    /// >     if ( Expression[+In, ?Yield, ?Await] ) FunctionDeclaration[?Yield, ?Await, ~Default] else FunctionDeclaration[?Yield, ?Await, ~Default]
+    /// >     if ( Expression[+In, ?Yield, ?Await] ) FunctionDeclaration[?Yield, ?Await, ~Default] [lookahead ≠ else]
+    /// >
+    /// > The above rules are only applied when parsing code that is not
+    /// > strict mode code.
+    ///
+    /// Such a declaration behaves as if it were in a block, so it is wrapped
+    /// into one.
+    fn parse_if_clause(&mut self, parameters: Parameters) -> Result<Box<Statement>, SourceCodeError> {
+        let is_function = self.at(TokenKind::Function) && self.peek_next().0 != Some(TokenKind::Multiplication);
+        if cfg!(feature = "annex-b") && !self.is_strict() && is_function {
+            let function = self.parse_function(true, parameters)?;
+            let range = function.range.clone();
+            let mut statements = vec![StatementListItem::Declaration(Declaration::FunctionDeclaration(Rc::new(function)))];
+            mark_legacy_block_functions(&mut statements, self.is_strict());
+            return Ok(Box::new(Statement::BlockStatement(Block { statements, range })));
+        }
+        Ok(Box::new(self.parse_substatement(parameters)?))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-IfStatement>.
+    fn parse_if_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::If)?;
+        let condition = self.parse_parenthesized_expression(parameters)?;
+        let consequent = self.parse_if_clause(parameters)?;
+        // An `else` belongs to the nearest `if`.
+        let alternative = if self.eat(TokenKind::Else) {
+            Some(self.parse_if_clause(parameters)?)
+        } else {
+            None
+        };
+        Ok(Statement::IfStatement(IfStatement { condition, consequent, alternative, range: self.range_from(start) }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ReturnStatement>.
+    fn parse_return_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        if !parameters.allow_return {
+            let message = "`return` outside of a function".to_owned();
+            return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+        }
+        self.expect(TokenKind::Return)?;
+        // `return [no LineTerminator here] Expression ;`
+        let has_argument = !self.newline_before()
+            && !self.is_at_end()
+            && !matches!(self.kind(), Some(TokenKind::Semicolon | TokenKind::RightBracePunctuator));
+        let argument = if has_argument {
+            Some(self.parse_expression_with(parameters.with_in(true))?)
+        } else {
+            None
+        };
+        self.expect_semicolon()?;
+        Ok(Statement::ReturnStatement(ReturnStatement { argument, range: self.range_from(start) }))
+    }
+
+    /// Parse `LabelIdentifier` of `break` and `continue`, if any.
+    ///
+    /// A label is never preceded by a line terminator, like in
+    /// `continue [no LineTerminator here] LabelIdentifier ;`.
+    fn parse_optional_label(&mut self, parameters: Parameters) -> Result<Option<String>, SourceCodeError> {
+        let is_label = matches!(self.kind(), Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await));
+        if !is_label || self.newline_before() {
+            return Ok(None);
+        }
+        Ok(Some(self.parse_identifier_reference(parameters)?.name))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-LabelledStatement>.
+    fn parse_labelled_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        let label = self.parse_identifier_reference(parameters)?.name;
+        self.expect(TokenKind::Colon)?;
+        // Generator and async declarations cannot be labelled.
+        let body = if self.at(TokenKind::Function) && self.peek_next().0 != Some(TokenKind::Multiplication) {
+            let function = self.parse_function(true, parameters)?;
+            // > It is a Syntax Error if any source text is matched by this
+            // > production.
+            //
+            // Annex B replaces the rule to allow this in non-strict code, see
+            // <https://262.ecma-international.org/14.0/#sec-labelled-function-declarations>.
+            if !cfg!(feature = "annex-b") || self.is_strict() {
+                let message = "function declaration cannot be labelled".to_owned();
+                return Err(self.error(function.range, ErrorCode::EarlyError, message));
+            }
+            LabelledItem::FunctionDeclaration(Rc::new(function))
+        } else {
+            LabelledItem::Statement(self.parse_statement(parameters)?)
+        };
+        Ok(Statement::LabelledStatement(LabelledStatement { label, body: Box::new(body), range: self.range_from(start) }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-SwitchStatement>.
+    fn parse_switch_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Switch)?;
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let discriminant = self.parse_expression_with(parameters.with_in(true))?;
+        self.expect(TokenKind::ClosingParenthesis)?;
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut cases = Vec::new();
+        let mut has_default = false;
+        while !self.eat(TokenKind::RightBracePunctuator) {
+            let case_start = self.range().start;
+            let test = if self.at(TokenKind::Default) {
+                // CaseBlock has a single place for a DefaultClause.
+                if has_default {
+                    let message = "`switch` has more than one `default` clause".to_owned();
+                    return Err(self.error(self.range(), ErrorCode::UnexpectedInput, message));
+                }
+                has_default = true;
+                self.bump();
+                None
+            } else {
+                self.expect(TokenKind::Case)?;
+                Some(self.parse_expression_with(parameters.with_in(true))?)
+            };
+            self.expect(TokenKind::Colon)?;
+            let mut statements = Vec::new();
+            while !matches!(
+                self.kind(),
+                Some(TokenKind::Case | TokenKind::Default | TokenKind::RightBracePunctuator) | None
+            ) {
+                statements.extend(self.recover(true, |parser| parser.parse_statement_list_item(parameters))?);
+            }
+            mark_legacy_block_functions(&mut statements, self.is_strict());
+            cases.push(CaseClause { test, statements, range: self.range_from(case_start) });
+        }
+        Ok(Statement::SwitchStatement(SwitchStatement { discriminant, cases, range: self.range_from(start) }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-TryStatement>.
+    fn parse_try_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Try)?;
+        let block = self.parse_block(parameters)?;
+        let catch_start = self.range().start;
+        let handler = if self.eat(TokenKind::Catch) {
+            let parameter = if self.eat(TokenKind::OpeningParenthesis) {
+                let parameter = self.parse_binding(parameters)?;
+                self.expect(TokenKind::ClosingParenthesis)?;
+                Some(parameter)
+            } else {
+                None
+            };
+            let body = self.parse_block(parameters)?;
+            Some(CatchClause { parameter, body, range: self.range_from(catch_start) })
+        } else {
+            None
+        };
+        let finalizer = if handler.is_none() || self.at(TokenKind::Finally) {
+            self.expect(TokenKind::Finally)?;
+            Some(self.parse_block(parameters)?)
+        } else {
+            None
+        };
+        Ok(Statement::TryStatement(TryStatement { block, handler, finalizer, range: self.range_from(start) }))
+    }
+}
+
+/************************************************
+ *
+ * 14.3 Declarations and the Variable Statement
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Whether a `LexicalDeclaration` starts at the current token.
+    ///
+    /// `let` is an identifier unless it is followed by a binding:
+    /// `let = 1` assigns to a variable named `let` in non-strict code.
+    pub(super) fn at_lexical_declaration(&self) -> bool {
+        if self.at(TokenKind::Const) {
+            return true;
+        }
+        self.at_contextual(ContextualKeyword::Let) && matches!(
+            self.peek_next().0,
+            Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await | TokenKind::OpeningBracket | TokenKind::OpeningBrace)
+        )
+    }
+
+    /// Whether the current token starts `UsingDeclaration` or
+    /// `AwaitUsingDeclaration` of the explicit resource management proposal.
+    ///
+    /// ```plain
+    /// UsingDeclaration[In, Yield, Await] :
+    ///     using [no LineTerminator here] BindingList[?In, ?Yield, ?Await, ~Pattern] ;
+    ///
+    /// AwaitUsingDeclaration[In, Yield] :
+    ///     CoverAwaitExpressionAndAwaitUsingDeclarationHead[?Yield] [no LineTerminator here] BindingList[?In, ?Yield, +Await, ~Pattern] ;
+    /// ```
+    ///
+    /// `for (using of` starts a `for`-`of` head with an expression, so it is
+    /// not a declaration in a `for` head.
+    fn at_using_declaration(&self, parameters: Parameters, is_for_head: bool) -> bool {
+        let is_candidate = self.at_contextual(ContextualKeyword::Using) || parameters.allow_await && self.at(TokenKind::Await);
+        if !self.proposals().explicit_resource_management || !is_candidate {
+            return false;
+        }
+        let mut next = self.clone();
+        if next.eat(TokenKind::Await) && next.newline_before() {
+            return false;
+        }
+        if !next.at_contextual(ContextualKeyword::Using) {
+            return false;
+        }
+        next.bump();
+        matches!(next.kind(), Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await))
+            && !next.newline_before()
+            && !(is_for_head && next.at_contextual(ContextualKeyword::Of))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-LexicalDeclaration>,
+    /// `UsingDeclaration` or `AwaitUsingDeclaration` without a trailing `;`.
+    fn parse_lexical_declaration(&mut self, parameters: Parameters) -> Result<VariableDeclarationList, SourceCodeError> {
+        let start = self.range().start;
+        let kind = if self.eat(TokenKind::Const) {
+            VariableKind::Const
+        } else if self.eat(TokenKind::Await) {
+            self.bump();
+            VariableKind::AwaitUsing
+        } else if self.at_contextual(ContextualKeyword::Using) {
+            self.bump();
+            VariableKind::Using
+        } else {
+            self.bump();
+            VariableKind::Let
+        };
+        self.parse_variable_declaration_list(start, kind, parameters)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-VariableDeclarationList>
+    /// or <https://262.ecma-international.org/14.0/#prod-BindingList> after
+    /// its keyword starting at a given offset.
+    ///
+    /// Patterns and `const` bindings must be initialized unless they are
+    /// a single binding of a `for`-`in` or `for`-`of` head, where `in` or
+    /// `of` follows instead.
+    fn parse_variable_declaration_list(
+        &mut self,
+        start: usize,
+        kind: VariableKind,
+        parameters: Parameters
+    ) -> Result<VariableDeclarationList, SourceCodeError> {
+        let mut declarations = Vec::new();
+        loop {
+            let declaration_start = self.range().start;
+            let target = self.parse_binding(parameters)?;
+            let initializer = if self.eat(TokenKind::Assignment) {
+                Some(self.parse_assignment_expression(parameters)?)
+            } else {
+                let is_for_in_of_head = declarations.is_empty() && !parameters.allow_in && self.at_for_in_of();
+                let is_initialization_required = matches!(kind, VariableKind::Const | VariableKind::Using | VariableKind::AwaitUsing)
+                    || !matches!(target, Binding::BindingIdentifier(_));
+                if is_initialization_required && !is_for_in_of_head {
+                    return Err(self.expected("`=`"));
+                }
+                None
+            };
+            let range = self.range_from(declaration_start);
+            declarations.push(VariableDeclaration { target, initializer, range });
+            if !self.eat(TokenKind::Comma) {
+                return Ok(VariableDeclarationList { kind, declarations, range: self.range_from(start) });
+            }
+        }
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-BindingIdentifier>.
+    pub(super) fn parse_binding_identifier(&mut self, parameters: Parameters) -> Result<BindingIdentifier, SourceCodeError> {
+        let IdentifierReference { name, range } = self.parse_identifier_reference(parameters)?;
+        let identifier = BindingIdentifier { name, range };
+        if self.is_strict() {
+            self.check_strict_binding_identifier(&identifier)?;
+        }
+        Ok(identifier)
+    }
+
+    /// Report a `BindingIdentifier` that strict mode code does not allow.
+    ///
+    /// > It is a Syntax Error if the code matched by this production is
+    /// > contained in strict mode code and the StringValue of Identifier is
+    /// > "arguments" or "eval".
+    ///
+    /// Identifiers reserved in strict mode code are checked too, for names
+    /// parsed before a `"use strict"` directive made them strict.
+    pub(super) fn check_strict_binding_identifier(&self, identifier: &BindingIdentifier) -> Result<(), SourceCodeError> {
+        let name = identifier.name.as_str();
+        let message = if matches!(name, "eval" | "arguments") {
+            format!("`{name}` cannot be bound in strict mode code")
+        } else if is_reserved_in_strict_mode(name) {
+            format!("`{name}` cannot be an identifier in strict mode code")
+        } else {
+            return Ok(());
+        };
+        Err(self.error(identifier.range.clone(), ErrorCode::EarlyError, message))
+    }
+
+    /// Parse `BindingIdentifier` or `BindingPattern`.
+    pub(super) fn parse_binding(&mut self, parameters: Parameters) -> Result<Binding, SourceCodeError> {
+        match self.kind() {
+            Some(TokenKind::OpeningBrace) => {
+                Ok(Binding::ObjectBindingPattern(self.nested(|parser| parser.parse_object_binding_pattern(parameters))?))
+            },
+            Some(TokenKind::OpeningBracket) => {
+                Ok(Binding::ArrayBindingPattern(self.nested(|parser| parser.parse_array_binding_pattern(parameters))?))
+            },
+            _ => Ok(Binding::BindingIdentifier(self.parse_binding_identifier(parameters)?)),
+        }
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-BindingElement>.
+    pub(super) fn parse_binding_element(&mut self, parameters: Parameters) -> Result<BindingElement, SourceCodeError> {
+        let start = self.range().start;
+        let target = self.parse_binding(parameters)?;
+        let initializer = self.parse_optional_initializer(parameters)?;
+        Ok(BindingElement { target, initializer, range: self.range_from(start) })
+    }
+
+    fn parse_optional_initializer(&mut self, parameters: Parameters) -> Result<Option<Expression>, SourceCodeError> {
+        if !self.eat(TokenKind::Assignment) {
+            return Ok(None);
+        }
+        Ok(Some(self.parse_assignment_expression(parameters.with_in(true))?))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ObjectBindingPattern>.
+    fn parse_object_binding_pattern(&mut self, parameters: Parameters) -> Result<ObjectBindingPattern, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningBrace)?;
+        let mut properties = Vec::new();
+        let mut rest = None;
+        while !self.eat(TokenKind::RightBracePunctuator) {
+            if self.eat(TokenKind::Ellipsis) {
+                rest = Some(self.parse_binding_identifier(parameters)?);
+                self.expect(TokenKind::RightBracePunctuator)?;
+                break;
+            }
+            let is_shorthand = matches!(self.kind(), Some(TokenKind::IdentifierName | TokenKind::Yield | TokenKind::Await))
+                && self.peek_next().0 != Some(TokenKind::Colon);
+            let property = if is_shorthand {
+                let name = self.parse_binding_identifier(parameters)?;
+                let initializer = self.parse_optional_initializer(parameters)?;
+                BindingProperty::SingleNameBinding { name, initializer }
+            } else {
+                let name = self.parse_property_name(parameters)?;
+                self.expect(TokenKind::Colon)?;
+                let value = self.parse_binding_element(parameters)?;
+                BindingProperty::Property { name, value }
+            };
+            properties.push(property);
+            if !self.at(TokenKind::RightBracePunctuator) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(ObjectBindingPattern { properties, rest, range: self.range_from(start) })
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ArrayBindingPattern>.
+    fn parse_array_binding_pattern(&mut self, parameters: Parameters) -> Result<ArrayBindingPattern, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::OpeningBracket)?;
+        let mut elements = Vec::new();
+        let mut rest = None;
+        while !self.eat(TokenKind::ClosingBracket) {
+            if self.eat(TokenKind::Comma) {
+                elements.push(None);
+                continue;
+            }
+            if self.eat(TokenKind::Ellipsis) {
+                rest = Some(Box::new(self.parse_binding(parameters)?));
+                self.expect(TokenKind::ClosingBracket)?;
+                break;
+            }
+            elements.push(Some(self.parse_binding_element(parameters)?));
+            if !self.at(TokenKind::ClosingBracket) {
+                self.expect(TokenKind::Comma)?;
+            }
+        }
+        Ok(ArrayBindingPattern { elements, rest, range: self.range_from(start) })
+    }
+}
+
+/************************************************
+ *
+ * 14.7 Iteration Statements
+ *
+ ************************************************/
+
+impl Parser<'_> {
+    /// Parse <https://262.ecma-international.org/14.0/#prod-DoWhileStatement>.
+    fn parse_do_while_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::Do)?;
+        let body = Box::new(self.parse_substatement(parameters)?);
+        self.expect(TokenKind::While)?;
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let condition = self.parse_expression_with(parameters.with_in(true))?;
+        self.expect(TokenKind::ClosingParenthesis)?;
+        // A semicolon after `do`-`while` is inserted even if the next token
+        // follows on the same line.
+        self.eat(TokenKind::Semicolon);
+        Ok(Statement::DoWhileStatement(DoWhileStatement { body, condition, range: self.range_from(start) }))
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-WhileStatement>.
+    fn parse_while_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::While)?;
+        let (condition, body) = self.parse_parenthesized_head_and_body(parameters)?;
+        Ok(Statement::WhileStatement(WhileStatement { condition, body, range: self.range_from(start) }))
+    }
+
+    /// Whether `in` or an unescaped `of` follows the head of a `for`
+    /// statement.
+    fn at_for_in_of(&self) -> bool {
+        self.at(TokenKind::In) || self.at_contextual(ContextualKeyword::Of)
+    }
+
+    /// Parse <https://262.ecma-international.org/14.0/#prod-ForStatement>
+    /// and <https://262.ecma-international.org/14.0/#prod-ForInOfStatement>.
+    ///
+    /// All forms share the beginning up to `;`, `in`, or `of`, so the head is
+    /// parsed first with `[~In]` and then the kind of the loop is selected.
+    fn parse_for_statement(&mut self, parameters: Parameters) -> Result<Statement, SourceCodeError> {
+        let start = self.range().start;
+        self.expect(TokenKind::For)?;
+        let await_range = self.range();
+        let is_await = parameters.allow_await && self.eat(TokenKind::Await);
+        self.expect(TokenKind::OpeningParenthesis)?;
+        let head_parameters = parameters.with_in(false);
+        let init_start = self.range().start;
+        let init = if self.at(TokenKind::Semicolon) {
+            None
+        } else if self.eat(TokenKind::Var) {
+            let declarations = self.parse_variable_declaration_list(init_start, VariableKind::Var, head_parameters)?;
+            Some(ForInit::VariableDeclarationList(declarations))
+        } else if self.at_lexical_declaration() || self.at_using_declaration(parameters, true) {
+            Some(ForInit::VariableDeclarationList(self.parse_lexical_declaration(head_parameters)?))
+        } else {
+            let is_let = self.at_contextual(ContextualKeyword::Let);
+            let expression = self.parse_expression_with(head_parameters)?;
+            // `for (let of a)` is ambiguous, so it is excluded from
+            // ForInOfStatement.
+            let is_target = expression.is_left_hand_side_expression() && (!is_let || self.at(TokenKind::In));
+            if self.at_for_in_of() && !is_target {
+                let message = "left side of a `for`-`in` or `for`-`of` head must be a left-hand-side expression";
+                return Err(self.error(expression.range(), ErrorCode::UnexpectedInput, message.to_owned()));
+            }
+            Some(ForInit::Expression(expression))
+        };
+
+        if self.at_for_in_of() {
+            let target = match init {
+                Some(ForInit::Expression(expression)) => ForTarget::Expression(expression),
+                Some(ForInit::VariableDeclarationList(VariableDeclarationList { kind, mut declarations, range }))
+                    if declarations.len() == 1 && declarations[0].initializer.is_none() =>
+                {
+                    ForTarget::ForDeclaration { kind, binding: declarations.swap_remove(0).target, range }
+                },
+                _ => return Err(self.expected("`;`")),
+            };
+            let is_of = !self.eat(TokenKind::In);
+            if let ForTarget::ForDeclaration { kind: VariableKind::Using | VariableKind::AwaitUsing, range, .. } = &target {
+                if !is_of {
+                    let message = "`using` declarations require `of` rather than `in`".to_owned();
+                    return Err(self.error(range.clone(), ErrorCode::UnexpectedInput, message));
+                }
+            }
+            if is_await && !is_of {
+                let message = "`for await` requires `of` rather than `in`".to_owned();
+                return Err(self.error(await_range, ErrorCode::UnexpectedInput, message));
+            }
+            let source = if is_of {
+                self.bump();
+                self.parse_assignment_expression(parameters.with_in(true))?
+            } else {
+                self.parse_expression_with(parameters.with_in(true))?
+            };
+            self.expect(TokenKind::ClosingParenthesis)?;
+            let body = Box::new(self.parse_substatement(parameters)?);
+            let statement = Box::new(ForInOfStatement { is_await, target, source, body, range: self.range_from(start) });
+            return Ok(if is_of { Statement::ForOfStatement(statement) } else { Statement::ForInStatement(statement) });
+        }
+
+        if is_await {
+            return Err(self.expected("`of`"));
+        }
+        self.expect(TokenKind::Semicolon)?;
+        let condition = if self.at(TokenKind::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expression_with(parameters.with_in(true))?)
+        };
+        self.expect(TokenKind::Semicolon)?;
+        let update = if self.at(TokenKind::ClosingParenthesis) {
+            None
+        } else {
+            Some(self.parse_expression_with(parameters.with_in(true))?)
+        };
+        self.expect(TokenKind::ClosingParenthesis)?;
+        let body = Box::new(self.parse_substatement(parameters)?);
+        Ok(Statement::ForStatement(Box::new(ForStatement { init, condition, update, body, range: self.range_from(start) })))
+    }
+}
+
@@ -0,0 +1,199 @@
+//! Evaluation of scripts, statements and expressions, the dynamic semantics
+//! of ECMAScript.
+//!
+//! [`Engine::evaluate_script`](crate::engine::Engine::evaluate_script) runs
+//! <https://262.ecma-international.org/14.0/#sec-runtime-semantics-scriptevaluation>
+//! over a parsed [`Script`](crate::grammar::Script),
+//! [`Engine::evaluate_module`](crate::engine::Engine::evaluate_module) links
+//! and evaluates a graph of [`Module`](crate::grammar::Module)s, and
+//! [`Interpreter`] evaluates a single [`Expression`] in the global
+//! environment of its own realm. All of them walk the syntax tree directly,
+//! keeping bindings in [`Environment`]s of the running [`ExecutionContext`].
+//! A call in tail position of strict function code is made once the
+//! function returns, in place of it, so that it does not nest.
+//!
+//! Generator and async function bodies are lowered into resumable code
+//! that a frame runs between `yield`s and `await`s, going back to the tree
+//! walking evaluator for parts without them. An `await` goes on from
+//! a promise job once the awaited value settles.
+//!
+//! Async generators, `for await` outside async functions, regular
+//! expression literals, tagged templates, decorators and `import()` are
+//! not supported yet; code using them fails with
+//! [`ErrorKind::Unsupported`](crate::runtime::ErrorKind::Unsupported).
+
+mod async_functions;
+mod expressions;
+mod functions;
+mod generators;
+mod modules;
+mod patterns;
+mod resumable;
+mod scripts;
+mod statements;
+
+pub(crate) use functions::{CallCompletion, TailCall};
+pub use functions::FunctionData;
+pub use generators::Generator;
+pub(crate) use modules::ModuleRecord;
+pub(crate) use scripts::evaluate_script;
+
+use crate::engine::Engine;
+use crate::grammar::{Expression, Function};
+use crate::runtime::{charge, Context, Environment, ExecutionContext, JsError, PrivateEnvironment, PropertyKey, Realm, Value};
+use functions::FunctionCode;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// An evaluator of expressions over variables provided by a host.
+///
+/// Variables are properties of the global object of a realm the
+/// interpreter creates, so assigning to an unknown name creates it.
+///
+/// ```
+/// use embedded_ecmascript::grammar::Parser;
+/// use embedded_ecmascript::interpreter::Interpreter;
+/// use embedded_ecmascript::runtime::Value;
+///
+/// let mut interpreter = Interpreter::new();
+/// interpreter.set_variable("x", Value::Number(3.0));
+/// let expression = Parser::new("1 + 2 * x").parse_expression().unwrap();
+/// assert_eq!(interpreter.evaluate(&expression), Ok(Value::Number(7.0)));
+/// ```
+#[derive(Debug, Default)]
+pub struct Interpreter {
+    engine: Engine,
+}
+
+impl Interpreter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create or overwrite a variable visible to evaluated expressions.
+    ///
+    /// Non-writable properties of the global object, like `undefined`,
+    /// keep their values.
+    pub fn set_variable(&mut self, name: impl Into<String>, value: Value) {
+        self.engine.global_object().create_data_property(name.into(), value);
+    }
+
+    /// A variable set by the host or assigned by an evaluated expression.
+    #[must_use]
+    pub fn variable(&self, name: &str) -> Option<Value> {
+        self.engine.global_object().get_own_property(&PropertyKey::from(name))?.value
+    }
+
+    /// Evaluate an expression and get its value.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`JsError`] if the evaluation throws an
+    /// exception or needs an unsupported feature. The error points to the
+    /// innermost expression that threw.
+    pub fn evaluate(&mut self, expression: &Expression) -> Result<Value, JsError> {
+        let realm = self.engine.realm().clone();
+        scripts::evaluate_expression(self.engine.context(), &realm, expression)
+    }
+}
+
+/// Caches of a script or a function body shared by all its evaluations.
+#[derive(Default)]
+struct Code {
+    /// Code of functions the body defines by node addresses, so closures
+    /// created by the same node share it.
+    functions: RefCell<HashMap<*const (), Rc<FunctionCode>>>,
+    /// Function declarations in blocks also bound as `var` by
+    /// <https://262.ecma-international.org/14.0/#sec-block-level-function-declarations-web-legacy-compatibility-semantics>,
+    /// by source ranges, which copies of the body resumable code keeps
+    /// share.
+    hoisted_functions: RefCell<HashSet<Range<usize>>>,
+    /// Source text of a tree with deferred function bodies, to parse them
+    /// on the first call.
+    source: Option<Rc<str>>,
+}
+
+impl Code {
+    fn with_source(source: Option<Rc<str>>) -> Self {
+        Self { source, ..Self::default() }
+    }
+
+    /// Code of a function defined by `node`, created by `create` on the
+    /// first use.
+    fn function<T: ?Sized>(&self, node: &T, create: impl FnOnce() -> FunctionCode) -> Rc<FunctionCode> {
+        let key = std::ptr::from_ref(node).cast::<()>();
+        let mut functions = self.functions.borrow_mut();
+        functions
+            .entry(key)
+            .or_insert_with(|| {
+                charge(std::mem::size_of::<FunctionCode>());
+                Rc::new(create())
+            })
+            .clone()
+    }
+
+    fn is_hoisted(&self, function: &Function) -> bool {
+        self.hoisted_functions.borrow().contains(&function.range)
+    }
+}
+
+/// The state of evaluating a script or a function body.
+struct Evaluator<'a> {
+    context: &'a mut Context,
+    code: &'a Code,
+    is_strict: bool,
+    /// Whether `return` leaves a call in tail position to the caller of
+    /// the function, so that it runs in place of the function.
+    tail_calls: bool,
+}
+
+impl Evaluator<'_> {
+    fn running_execution_context(&self) -> &ExecutionContext {
+        self.context.running_execution_context().expect("ECMAScript code runs in an execution context")
+    }
+
+    fn realm(&self) -> Realm {
+        self.running_execution_context().realm.clone()
+    }
+
+    fn lexical_environment(&self) -> Environment {
+        let environment = self.running_execution_context().lexical_environment.clone();
+        environment.expect("ECMAScript code has a LexicalEnvironment")
+    }
+
+    fn variable_environment(&self) -> Environment {
+        let environment = self.running_execution_context().variable_environment.clone();
+        environment.expect("ECMAScript code has a VariableEnvironment")
+    }
+
+    /// Replace the `LexicalEnvironment` of the running execution context,
+    /// returning the previous one.
+    fn set_lexical_environment(&mut self, environment: Environment) -> Environment {
+        let context = self.context.running_execution_context_mut().expect("ECMAScript code runs in an execution context");
+        let previous = context.lexical_environment.replace(environment);
+        previous.expect("ECMAScript code has a LexicalEnvironment")
+    }
+
+    /// Run `evaluate` with `environment` as the `LexicalEnvironment`,
+    /// restoring the previous one afterwards whatever the result.
+    fn with_lexical_environment<T>(&mut self, environment: Environment, evaluate: impl FnOnce(&mut Self) -> T) -> T {
+        let previous = self.set_lexical_environment(environment);
+        let result = evaluate(self);
+        self.set_lexical_environment(previous);
+        result
+    }
+
+    /// Run `evaluate` with `environment` as the `PrivateEnvironment`,
+    /// restoring the previous one afterwards whatever the result.
+    fn with_private_environment<T>(&mut self, environment: PrivateEnvironment, evaluate: impl FnOnce(&mut Self) -> T) -> T {
+        let context = self.context.running_execution_context_mut().expect("ECMAScript code runs in an execution context");
+        let previous = context.private_environment.replace(environment);
+        let result = evaluate(self);
+        let context = self.context.running_execution_context_mut().expect("ECMAScript code runs in an execution context");
+        context.private_environment = previous;
+        result
+    }
+}
@@ -0,0 +1,70 @@
+//! <https://262.ecma-international.org/14.0/#sec-async-function-objects>
+
+use super::resumable::{Frame, Resumption, Suspension};
+use crate::runtime::{perform_promise_then, promise_resolve_with, Context, JsError, Object, PromiseCapability, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The suspended evaluation of an async function body with the promise
+/// it settles.
+struct AsyncFunction {
+    frame: RefCell<Option<Frame>>,
+    /// `%Promise%` of the realm of the function.
+    constructor: Object,
+    capability: PromiseCapability,
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-asyncblockstart>:
+/// run the body of `frame` up to its first `await`, settling the promise
+/// of `capability` once it completes.
+///
+/// Only errors that scripts cannot catch are returned; the rest reject
+/// the promise.
+pub(super) fn start(context: &mut Context, frame: Frame, constructor: Object, capability: PromiseCapability) -> Result<(), JsError> {
+    let function = AsyncFunction { frame: RefCell::new(Some(frame)), constructor, capability };
+    resume(context, &Rc::new(function), Resumption::Start)
+}
+
+/// Run the body from where it stopped until it awaits or completes.
+fn resume(context: &mut Context, function: &Rc<AsyncFunction>, mut resumption: Resumption) -> Result<(), JsError> {
+    let mut frame = function.frame.take().expect("an async function is resumed once per await");
+    loop {
+        match frame.resume(context, resumption) {
+            // <https://262.ecma-international.org/14.0/#await>
+            Ok(Suspension::Await(value)) => match promise_resolve_with(context, &function.constructor, value) {
+                Ok(promise) => {
+                    function.frame.replace(Some(frame));
+                    let on_fulfilled = continuation(context, function, Resumption::Next);
+                    let on_rejected = continuation(context, function, Resumption::Throw);
+                    perform_promise_then(context, &promise, on_fulfilled, on_rejected);
+                    return Ok(());
+                },
+                Err(error) if error.kind.is_catchable() => resumption = Resumption::Throw(error.into_value(context)),
+                Err(error) => return Err(error),
+            },
+            Ok(Suspension::Return(value)) => {
+                function.capability.resolve.call(context, &Value::Undefined, &[value])?;
+                return Ok(());
+            },
+            Ok(Suspension::Yield(_) | Suspension::YieldResult(_)) => unreachable!("async functions do not yield"),
+            Err(error) if error.kind.is_catchable() => {
+                let reason = error.into_value(context);
+                function.capability.reject.call(context, &Value::Undefined, &[reason])?;
+                return Ok(());
+            },
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// A function resuming the body with the value a promise it awaits
+/// settles with.
+fn continuation(context: &Context, function: &Rc<AsyncFunction>, resumption: fn(Value) -> Resumption) -> Object {
+    let prototype = context.current_realm().map(|realm| realm.intrinsics().function_prototype.clone());
+    let function = Rc::clone(function);
+    Object::builtin_function(prototype, "", 1, move |context, _, arguments| {
+        let value = arguments.first().cloned().unwrap_or(Value::Undefined);
+        resume(context, &function, resumption(value))?;
+        Ok(Value::Undefined)
+    })
+}
@@ -0,0 +1,1043 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-expressions>
+
+use super::functions::{get_super_constructor, super_call, TailCall};
+use super::statements::Completion;
+use super::Evaluator;
+use crate::grammar::{
+    Argument,
+    ArrayElement,
+    ArrayLiteral,
+    AssignmentExpression,
+    AssignmentOperator,
+    BinaryExpression,
+    BinaryOperator,
+    CallExpression,
+    Expression,
+    Literal,
+    MemberExpression,
+    MemberProperty,
+    MetaProperty,
+    NewExpression,
+    ObjectLiteral,
+    PropertyDefinition,
+    PropertyName,
+    SuperCall,
+    SuperProperty,
+    TemplateLiteral,
+    UnaryExpression,
+    UnaryOperator,
+    UpdateExpression,
+    UpdateOperator,
+};
+use crate::lexical_grammar::{NumericValue, PrivateIdentifier};
+use crate::runtime::{
+    string_to_bigint,
+    string_to_number,
+    BigIntOps,
+    Context,
+    Environment,
+    ErrorKind,
+    IteratorRecord,
+    JsError,
+    JsString,
+    Number,
+    Object,
+    PreferredType,
+    PrivateName,
+    PropertyKey,
+    Symbol,
+    Value,
+    WellKnownSymbol,
+};
+use num_bigint::BigInt;
+use num_traits::One;
+use std::cmp::Ordering;
+
+/// <https://262.ecma-international.org/14.0/#sec-reference-record-specification-type>
+pub(super) enum Reference {
+    /// A binding of an environment, which is `None` if the reference is
+    /// unresolvable.
+    Binding { environment: Option<Environment>, name: String },
+    /// A property of a value, which is `this` of a call through the
+    /// reference.
+    Property { base: Value, key: PropertyKey },
+    /// A Super Reference to a property of the prototype of a home object,
+    /// accessed and called with `this` of the method.
+    Super { base: Value, key: PropertyKey, this: Value },
+    /// A private member of a value, which is `this` of a call through the
+    /// reference.
+    Private { base: Value, name: PrivateName },
+}
+
+impl Reference {
+    /// `thisValue` of
+    /// <https://262.ecma-international.org/14.0/#sec-function-calls-runtime-semantics-evaluation>
+    /// calling a function through the reference.
+    pub(super) fn this_value(&self) -> Value {
+        match self {
+            Self::Property { base, .. } | Self::Private { base, .. } => base.clone(),
+            Self::Super { this, .. } => this.clone(),
+            Self::Binding { environment, .. } => {
+                let base = environment.as_ref().and_then(Environment::with_base_object);
+                base.map_or(Value::Undefined, Value::Object)
+            },
+        }
+    }
+}
+
+impl Evaluator<'_> {
+    /// Evaluate an expression and get its value.
+    ///
+    /// The error, if any, points to the innermost expression that threw.
+    pub(super) fn evaluate(&mut self, expression: &Expression) -> Result<Value, JsError> {
+        crate::grow_stack(|| self.evaluate_unattributed(expression)).map_err(|error| error.at(&expression.range()))
+    }
+
+    fn evaluate_unattributed(&mut self, expression: &Expression) -> Result<Value, JsError> {
+        match expression {
+            Expression::This(_) => self.get_this_environment().get_this_binding(),
+            Expression::IdentifierReference(reference) => {
+                let reference = self.resolve_binding(&reference.name)?;
+                self.get_value(&reference)
+            },
+            Expression::Literal(literal, _) => Ok(literal_value(literal)),
+            Expression::ArrayLiteral(literal) => self.evaluate_array_literal(literal),
+            Expression::ObjectLiteral(literal) => self.evaluate_object_literal(literal),
+            Expression::FunctionExpression(function) => {
+                Ok(Value::Object(self.instantiate_function_expression(function, None)))
+            },
+            Expression::ArrowFunction(function) => Ok(Value::Object(self.instantiate_arrow_function(function, None))),
+            Expression::ClassExpression(class) => {
+                let name = class.name.as_ref().map_or("", |name| name.name.as_str());
+                Ok(Value::Object(self.evaluate_class(class, &PropertyKey::from(name))?))
+            },
+            Expression::SuperProperty(property) => {
+                let reference = self.evaluate_super_property(property)?;
+                self.get_value(&reference)
+            },
+            Expression::SuperCall(call) => self.evaluate_super_call(call),
+            Expression::RegularExpressionLiteral(..) => Err(JsError::unsupported("regular expressions")),
+            Expression::TemplateLiteral(template) => self.evaluate_template(template),
+            Expression::TaggedTemplate(_) => Err(JsError::unsupported("tagged templates")),
+            Expression::ParenthesizedExpression(expression) => self.evaluate(&expression.expression),
+            Expression::MemberExpression(_) | Expression::CallExpression(_) => {
+                // Only links of an optional chain short-circuit.
+                Ok(self.evaluate_chain(expression)?.unwrap_or(Value::Undefined))
+            },
+            Expression::OptionalExpression(expression) => {
+                // <https://262.ecma-international.org/14.0/#sec-optional-chaining-evaluation>
+                Ok(self.evaluate_chain(&expression.expression)?.unwrap_or(Value::Undefined))
+            },
+            Expression::MetaProperty(MetaProperty::NewTarget, _) => {
+                Ok(self.get_this_environment().new_target().cloned().unwrap_or(Value::Undefined))
+            },
+            Expression::MetaProperty(MetaProperty::ImportMeta, _) | Expression::ImportCall(_) => {
+                Err(JsError::unsupported("imports"))
+            },
+            Expression::NewExpression(expression) => self.evaluate_new(expression),
+            Expression::UpdateExpression(expression) => self.evaluate_update(expression),
+            Expression::UnaryExpression(expression) => self.evaluate_unary(expression),
+            Expression::BinaryExpression(expression) => self.evaluate_binary(expression),
+            Expression::PrivateInExpression(expression) => {
+                let object = self.evaluate(&expression.object)?;
+                self.private_in(&expression.name, &object)
+            },
+            Expression::ConditionalExpression(expression) => {
+                // <https://262.ecma-international.org/14.0/#sec-conditional-operator-runtime-semantics-evaluation>
+                if self.evaluate(&expression.condition)?.to_boolean() {
+                    self.evaluate(&expression.consequent)
+                } else {
+                    self.evaluate(&expression.alternative)
+                }
+            },
+            Expression::AssignmentExpression(expression) => self.evaluate_assignment(expression),
+            Expression::CommaExpression(expression) => {
+                let mut value = Value::Undefined;
+                for item in &expression.expressions {
+                    value = self.evaluate(item)?;
+                }
+                Ok(value)
+            },
+            Expression::AwaitExpression(_) | Expression::YieldExpression(_) => {
+                Err(JsError::unsupported("suspending expressions in this position"))
+            },
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-namedevaluation>:
+    /// evaluate an expression naming an anonymous function it defines.
+    pub(super) fn evaluate_named(&mut self, expression: &Expression, name: &PropertyKey) -> Result<Value, JsError> {
+        match expression {
+            Expression::FunctionExpression(function) if function.name.is_none() => {
+                Ok(Value::Object(self.instantiate_function_expression(function, Some(name))))
+            },
+            Expression::ArrowFunction(function) => Ok(Value::Object(self.instantiate_arrow_function(function, Some(name)))),
+            Expression::ClassExpression(class) if class.name.is_none() => {
+                Ok(Value::Object(self.evaluate_class(class, name).map_err(|error| error.at(&expression.range()))?))
+            },
+            Expression::ParenthesizedExpression(parenthesized) => {
+                self.evaluate_named(&parenthesized.expression, name).map_err(|error| error.at(&expression.range()))
+            },
+            _ => self.evaluate(expression),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-resolvebinding> in the
+    /// running execution context.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a `with` object throws.
+    pub(super) fn resolve_binding(&mut self, name: &str) -> Result<Reference, JsError> {
+        let environment = self.lexical_environment().resolve(self.context, name)?;
+        Ok(Reference::Binding { environment, name: name.to_owned() })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getthisenvironment>
+    pub(super) fn get_this_environment(&self) -> Environment {
+        let mut environment = self.lexical_environment();
+        while !environment.has_this_binding() {
+            let outer = environment.outer().expect("the global environment has a this binding");
+            environment = outer.clone();
+        }
+        environment
+    }
+
+    /// Evaluate an expression to a [`Reference`] to assign to.
+    pub(super) fn evaluate_reference(&mut self, expression: &Expression) -> Result<Reference, JsError> {
+        let reference = match expression {
+            Expression::IdentifierReference(reference) => self.resolve_binding(&reference.name),
+            Expression::ParenthesizedExpression(parenthesized) => self.evaluate_reference(&parenthesized.expression),
+            Expression::MemberExpression(member) => {
+                self.evaluate_member(member).map(|reference| reference.expect("only optional chains short-circuit"))
+            },
+            Expression::SuperProperty(property) => self.evaluate_super_property(property),
+            _ => self
+                .evaluate(expression)
+                .and_then(|_| Err(JsError::new(ErrorKind::ReferenceError, "invalid assignment target"))),
+        };
+        reference.map_err(|error| error.at(&expression.range()))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getvalue>
+    pub(super) fn get_value(&mut self, reference: &Reference) -> Result<Value, JsError> {
+        match reference {
+            Reference::Binding { environment: None, name } => Err(not_defined(name)),
+            Reference::Binding { environment: Some(environment), name } => {
+                environment.get_binding_value(self.context, name, self.is_strict)
+            },
+            Reference::Property { base, key } | Reference::Super { base, key, .. } => {
+                let object = self.property_base(base, key, "read")?;
+                object.get(self.context, key, &reference.this_value())
+            },
+            Reference::Private { base: Value::Object(object), name } => object.private_get(self.context, name),
+            Reference::Private { name, .. } => Err(JsError::type_error(format!("cannot read private member {name} from a primitive value"))),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-putvalue>
+    pub(super) fn put_value(&mut self, reference: &Reference, value: Value) -> Result<(), JsError> {
+        match reference {
+            Reference::Binding { environment: None, name } => {
+                if self.is_strict {
+                    return Err(not_defined(name));
+                }
+                let global_object = self.realm().global_object().clone();
+                let receiver = Value::Object(global_object.clone());
+                global_object.set(self.context, PropertyKey::from(name.as_str()), value, &receiver)?;
+                Ok(())
+            },
+            Reference::Binding { environment: Some(environment), name } => {
+                environment.set_mutable_binding(self.context, name, value, self.is_strict)
+            },
+            Reference::Property { base, key } | Reference::Super { base, key, .. } => {
+                let object = self.property_base(base, key, "set")?;
+                if !object.set(self.context, key.clone(), value, &reference.this_value())? && self.is_strict {
+                    return Err(JsError::type_error(format!("cannot assign to read-only property {key}")));
+                }
+                Ok(())
+            },
+            Reference::Private { base: Value::Object(object), name } => object.private_set(self.context, name, value),
+            Reference::Private { name, .. } => Err(JsError::type_error(format!("cannot write private member {name} to a primitive value"))),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-toobject> of a base
+    /// value of a property reference, explaining what failed to `action`.
+    pub(super) fn property_base(&self, base: &Value, key: &PropertyKey, action: &str) -> Result<Object, JsError> {
+        if base.is_nullish() {
+            let base = if base.is_undefined() { "undefined" } else { "null" };
+            return Err(JsError::type_error(format!("cannot {action} property {key} of {base}")));
+        }
+        base.to_object(self.context)
+    }
+
+    /// Evaluate a link of an optional chain, `None` if the chain
+    /// short-circuits on a nullish value.
+    fn evaluate_chain(&mut self, expression: &Expression) -> Result<Option<Value>, JsError> {
+        let value = match expression {
+            Expression::MemberExpression(member) => match self.evaluate_member(member) {
+                Ok(Some(reference)) => self.get_value(&reference).map(Some),
+                result => result.map(|_| None),
+            },
+            Expression::CallExpression(call) => self.evaluate_call(call),
+            _ => return self.evaluate(expression).map(Some),
+        };
+        value.map_err(|error| error.at(&expression.range()))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-property-accessors-runtime-semantics-evaluation>
+    fn evaluate_member(&mut self, member: &MemberExpression) -> Result<Option<Reference>, JsError> {
+        let Some(base) = self.evaluate_chain(&member.object)? else {
+            return Ok(None);
+        };
+        if member.optional && base.is_nullish() {
+            return Ok(None);
+        }
+        let key = match &member.property {
+            MemberProperty::IdentifierName(name, _) => PropertyKey::from(name.as_str()),
+            MemberProperty::Expression(expression) => self.evaluate(expression)?.to_property_key(self.context)?,
+            MemberProperty::PrivateIdentifier(identifier, _) => {
+                return Ok(Some(Reference::Private { base, name: self.resolve_private_identifier(identifier) }));
+            },
+        };
+        Ok(Some(Reference::Property { base, key }))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-super-keyword-runtime-semantics-evaluation>
+    /// of `super.a` and `super[a]`.
+    fn evaluate_super_property(&mut self, property: &SuperProperty) -> Result<Reference, JsError> {
+        let this = self.get_this_environment().get_this_binding()?;
+        let key = match &property.property {
+            MemberProperty::IdentifierName(name, _) => PropertyKey::from(name.as_str()),
+            MemberProperty::Expression(expression) => self.evaluate(expression)?.to_property_key(self.context)?,
+            MemberProperty::PrivateIdentifier(..) => unreachable!("`super.#a` is a syntax error"),
+        };
+        self.super_reference(this, key)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-makesuperpropertyreference>
+    /// with `this` of the method.
+    pub(super) fn super_reference(&mut self, this: Value, key: PropertyKey) -> Result<Reference, JsError> {
+        let base = self.get_this_environment().get_super_base(self.context)?;
+        Ok(Reference::Super { base, key, this })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-super-keyword-runtime-semantics-evaluation>
+    /// of `super(...)` in a derived constructor.
+    fn evaluate_super_call(&mut self, call: &SuperCall) -> Result<Value, JsError> {
+        let environment = self.get_this_environment();
+        let parent = get_super_constructor(self.context, &environment)?;
+        let arguments = self.evaluate_arguments(&call.arguments)?;
+        Ok(Value::Object(super_call(self.context, &environment, &parent, &arguments)?))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-function-calls-runtime-semantics-evaluation>
+    fn evaluate_call(&mut self, call: &CallExpression) -> Result<Option<Value>, JsError> {
+        let Some(TailCall { function, this, arguments }) = self.evaluate_call_operands(call)? else {
+            return Ok(None);
+        };
+        function.call(self.context, &this, &arguments).map(Some)
+    }
+
+    /// The function, `this` and arguments of a call, `None` if the call
+    /// short-circuits.
+    fn evaluate_call_operands(&mut self, call: &CallExpression) -> Result<Option<TailCall>, JsError> {
+        let Some((function, this)) = self.evaluate_callee(&call.callee)? else {
+            return Ok(None);
+        };
+        if call.optional && function.is_nullish() {
+            return Ok(None);
+        }
+        let arguments = self.evaluate_arguments(&call.arguments)?;
+        match function {
+            Value::Object(function) if function.is_callable() => Ok(Some(TailCall { function, this, arguments })),
+            _ => Err(JsError::type_error(format!("{} is not a function", callee_name(&call.callee)))),
+        }
+    }
+
+    /// Evaluate the operand of `return`, leaving a call in tail position
+    /// to the caller of strict function code, like
+    /// <https://262.ecma-international.org/14.0/#sec-preparefortailcall>
+    /// does, so that the call does not nest.
+    ///
+    /// A call is in tail position if it is the operand, a branch of `?:`,
+    /// the right operand of `&&`, `||` or `??`, or the last item of
+    /// a comma expression in tail position.
+    pub(super) fn evaluate_returned(&mut self, expression: &Expression) -> Result<Completion, JsError> {
+        if !self.tail_calls {
+            return self.evaluate(expression).map(Completion::Return);
+        }
+        let completion = crate::grow_stack(|| match expression {
+            Expression::ParenthesizedExpression(parenthesized) => self.evaluate_returned(&parenthesized.expression),
+            Expression::ConditionalExpression(conditional) => {
+                if self.evaluate(&conditional.condition)?.to_boolean() {
+                    self.evaluate_returned(&conditional.consequent)
+                } else {
+                    self.evaluate_returned(&conditional.alternative)
+                }
+            },
+            Expression::BinaryExpression(binary)
+                if matches!(binary.operator, BinaryOperator::And | BinaryOperator::Or | BinaryOperator::NullishCoalescence) =>
+            {
+                let left = self.evaluate(&binary.left)?;
+                if short_circuits(binary.operator, &left) == Some(true) {
+                    Ok(Completion::Return(left))
+                } else {
+                    self.evaluate_returned(&binary.right)
+                }
+            },
+            Expression::CommaExpression(comma) => {
+                let (last, items) = comma.expressions.split_last().expect("a comma expression has items");
+                for item in items {
+                    self.evaluate(item)?;
+                }
+                self.evaluate_returned(last)
+            },
+            Expression::CallExpression(call) => Ok(match self.evaluate_call_operands(call)? {
+                Some(call) => Completion::TailCall(call),
+                None => Completion::Return(Value::Undefined),
+            }),
+            _ => self.evaluate(expression).map(Completion::Return),
+        });
+        completion.map_err(|error| error.at(&expression.range()))
+    }
+
+    /// A function to call and `this` to call it with, taken from
+    /// a reference if the callee is one.
+    pub(super) fn evaluate_callee(&mut self, callee: &Expression) -> Result<Option<(Value, Value)>, JsError> {
+        let reference = match callee {
+            Expression::MemberExpression(member) => self.evaluate_member(member),
+            Expression::IdentifierReference(reference) => self.resolve_binding(&reference.name).map(Some),
+            Expression::SuperProperty(property) => self.evaluate_super_property(property).map(Some),
+            Expression::ParenthesizedExpression(parenthesized)
+                if is_reference(&parenthesized.expression) || matches!(*parenthesized.expression, Expression::OptionalExpression(_)) =>
+            {
+                return self.evaluate_callee(&parenthesized.expression).map_err(|error| error.at(&callee.range()));
+            },
+            // `(a?.b)()` calls the property with `a` as `this`, or calls
+            // `undefined` once the chain short-circuits.
+            Expression::OptionalExpression(optional) if matches!(*optional.expression, Expression::MemberExpression(_)) => {
+                let function = self.evaluate_callee(&optional.expression)?;
+                return Ok(Some(function.unwrap_or((Value::Undefined, Value::Undefined))));
+            },
+            _ => return Ok(self.evaluate_chain(callee)?.map(|function| (function, Value::Undefined))),
+        };
+        let function = match reference {
+            Ok(Some(reference)) => self.get_value(&reference).map(|function| Some((function, reference.this_value()))),
+            result => result.map(|_| None),
+        };
+        function.map_err(|error| error.at(&callee.range()))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-argumentlistevaluation>
+    pub(super) fn evaluate_arguments(&mut self, arguments: &[Argument]) -> Result<Vec<Value>, JsError> {
+        let mut values = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            match argument {
+                Argument::AssignmentExpression(expression) => values.push(self.evaluate(expression)?),
+                Argument::SpreadElement(expression, range) => {
+                    let spread = self.evaluate(expression)?;
+                    self.iterate(&spread, |value| values.push(value)).map_err(|error| error.at(range))?;
+                },
+            }
+        }
+        Ok(values)
+    }
+
+    /// Get all values of an iterable, like spreading it does.
+    pub(super) fn iterate(&mut self, iterable: &Value, mut consume: impl FnMut(Value)) -> Result<(), JsError> {
+        let mut iterator = IteratorRecord::from_iterable(self.context, iterable)?;
+        while let Some(value) = iterator.step_value(self.context)? {
+            consume(value);
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-new-operator-runtime-semantics-evaluation>
+    fn evaluate_new(&mut self, expression: &NewExpression) -> Result<Value, JsError> {
+        let constructor = self.evaluate(&expression.callee)?;
+        let arguments = match &expression.arguments {
+            Some(arguments) => self.evaluate_arguments(arguments)?,
+            None => Vec::new(),
+        };
+        match constructor.as_object() {
+            Some(constructor) if constructor.is_constructor() => {
+                Ok(Value::Object(constructor.construct(self.context, &arguments, None)?))
+            },
+            _ => Err(JsError::type_error(format!("{} is not a constructor", callee_name(&expression.callee)))),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-arrayaccumulation>
+    fn evaluate_array_literal(&mut self, literal: &ArrayLiteral) -> Result<Value, JsError> {
+        let array = Object::new_array(Some(self.realm().intrinsics().array_prototype.clone()), Vec::new());
+        let mut length = 0_u64;
+        for element in &literal.elements {
+            match element {
+                ArrayElement::Elision(_) => length += 1,
+                ArrayElement::AssignmentExpression(expression) => {
+                    array.create_data_property(length.to_string(), self.evaluate(expression)?);
+                    length += 1;
+                },
+                ArrayElement::SpreadElement(expression, range) => {
+                    let spread = self.evaluate(expression)?;
+                    let append = |value| {
+                        array.create_data_property(length.to_string(), value);
+                        length += 1;
+                    };
+                    self.iterate(&spread, append).map_err(|error| error.at(range))?;
+                },
+            }
+        }
+        // Trailing holes are not created by elements.
+        #[allow(clippy::cast_precision_loss)] // lengths are less than 2^53
+        let length = Value::Number(length as f64);
+        array.set(self.context, PropertyKey::from("length"), length, &Value::Object(array.clone()))?;
+        Ok(Value::Object(array))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-object-initializer-runtime-semantics-evaluation>
+    fn evaluate_object_literal(&mut self, literal: &ObjectLiteral) -> Result<Value, JsError> {
+        let object = Object::new(Some(self.realm().intrinsics().object_prototype.clone()));
+        for property in &literal.properties {
+            self.evaluate_property_definition(&object, property)?;
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-propertydefinitionevaluation>
+    /// of a property of an object literal.
+    pub(super) fn evaluate_property_definition(&mut self, object: &Object, property: &PropertyDefinition) -> Result<(), JsError> {
+        match property {
+            PropertyDefinition::IdentifierReference(reference) => {
+                let binding = self.resolve_binding(&reference.name)?;
+                let value = self.get_value(&binding).map_err(|error| error.at(&reference.range))?;
+                object.create_data_property(reference.name.as_str(), value);
+            },
+            PropertyDefinition::Property { name, value } if is_proto_setter(name) => {
+                match self.evaluate(value)? {
+                    Value::Object(prototype) => object.set_prototype_of(Some(prototype)),
+                    Value::Null => object.set_prototype_of(None),
+                    _ => true,
+                };
+            },
+            PropertyDefinition::Property { name, value } => {
+                let key = self.evaluate_property_name(name)?;
+                let value = self.evaluate_named(value, &key)?;
+                object.create_data_property(key, value);
+            },
+            PropertyDefinition::MethodDefinition(method) => self.define_method(object, method, true)?,
+            PropertyDefinition::SpreadProperty(expression, _) => {
+                let source = self.evaluate(expression)?;
+                self.copy_data_properties(object, &source, &[])?;
+            },
+            PropertyDefinition::CoverInitializedName { name, .. } => {
+                let message = "an initializer is only allowed in a destructuring pattern";
+                return Err(JsError::new(ErrorKind::SyntaxError, message).at(&name.range));
+            },
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-object-initializer-runtime-semantics-evaluation>
+    /// of `PropertyName`.
+    pub(super) fn evaluate_property_name(&mut self, name: &PropertyName) -> Result<PropertyKey, JsError> {
+        Ok(match name {
+            PropertyName::IdentifierName(name, _) => PropertyKey::from(name.as_str()),
+            PropertyName::StringLiteral(literal, _) => PropertyKey::String(JsString::from_code_units(&literal.code_units())),
+            PropertyName::NumericLiteral(literal, _) => {
+                literal_value(&Literal::NumericLiteral(literal.clone())).to_property_key(self.context)?
+            },
+            PropertyName::ComputedPropertyName(expression, _) => self.evaluate(expression)?.to_property_key(self.context)?,
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-copydataproperties>
+    pub(super) fn copy_data_properties(
+        &mut self,
+        target: &Object,
+        source: &Value,
+        excluded: &[PropertyKey],
+    ) -> Result<(), JsError> {
+        if source.is_nullish() {
+            return Ok(());
+        }
+        let from = source.to_object(self.context)?;
+        for key in from.try_own_property_keys(self.context)? {
+            if excluded.contains(&key) {
+                continue;
+            }
+            if from.try_get_own_property(self.context, &key)?.is_some_and(|property| property.enumerable == Some(true)) {
+                let value = from.get(self.context, &key, &Value::Object(from.clone()))?;
+                target.create_data_property(key, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_template(&mut self, template: &TemplateLiteral) -> Result<Value, JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-template-literals-runtime-semantics-evaluation>
+        let mut text = JsString::new();
+        for (index, string) in template.strings.iter().enumerate() {
+            text = text.concat(&JsString::from_code_units(string.cooked.as_deref().unwrap_or_default()));
+            if let Some(substitution) = template.substitutions.get(index) {
+                text = text.concat(&self.evaluate(substitution)?.to_string(self.context)?);
+            }
+        }
+        Ok(Value::String(text))
+    }
+
+    fn evaluate_update(&mut self, expression: &UpdateExpression) -> Result<Value, JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-postfix-increment-operator-runtime-semantics-evaluation>
+        let reference = self.evaluate_reference(&expression.operand)?;
+        let old_value = self.get_value(&reference).map_err(|error| error.at(&expression.operand.range()))?;
+        let (old_value, new_value) = apply_update_operator(self.context, expression.operator, &old_value)?;
+        self.put_value(&reference, new_value.clone())?;
+        Ok(if expression.prefix { new_value } else { old_value })
+    }
+
+    fn evaluate_unary(&mut self, expression: &UnaryExpression) -> Result<Value, JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-unary-operators>
+        match expression.operator {
+            UnaryOperator::Delete => return self.evaluate_delete(&expression.operand).map(Value::Boolean),
+            UnaryOperator::Typeof => {
+                // An unresolvable reference is `undefined` for `typeof`.
+                if let Some(name) = reference_name(&expression.operand) {
+                    let reference = self.resolve_binding(name)?;
+                    if let Reference::Binding { environment: None, .. } = reference {
+                        return Ok(Value::from("undefined"));
+                    }
+                    let value = self.get_value(&reference).map_err(|error| error.at(&expression.operand.range()))?;
+                    return Ok(Value::from(type_of(&value)));
+                }
+            },
+            _ => {},
+        }
+        let value = self.evaluate(&expression.operand)?;
+        apply_unary_operator(self.context, expression.operator, &value)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-delete-operator-runtime-semantics-evaluation>
+    fn evaluate_delete(&mut self, operand: &Expression) -> Result<bool, JsError> {
+        let reference = match operand {
+            Expression::ParenthesizedExpression(parenthesized) => return self.evaluate_delete(&parenthesized.expression),
+            Expression::IdentifierReference(reference) => Some(self.resolve_binding(&reference.name)?),
+            Expression::MemberExpression(member) => self.evaluate_member(member)?,
+            Expression::SuperProperty(property) => Some(self.evaluate_super_property(property)?),
+            Expression::OptionalExpression(optional) => {
+                if let Expression::MemberExpression(member) = &*optional.expression {
+                    // A short-circuiting chain deletes nothing.
+                    self.evaluate_member(member)?
+                } else {
+                    self.evaluate(operand)?;
+                    None
+                }
+            },
+            _ => {
+                self.evaluate(operand)?;
+                None
+            },
+        };
+        self.delete_reference(reference)
+    }
+
+    /// Delete what a reference refers to, succeeding without one.
+    pub(super) fn delete_reference(&mut self, reference: Option<Reference>) -> Result<bool, JsError> {
+        Ok(match reference {
+            None | Some(Reference::Binding { environment: None, .. }) => true,
+            Some(Reference::Binding { environment: Some(environment), name }) => environment.delete_binding(self.context, &name)?,
+            Some(Reference::Property { base, key }) => {
+                let object = self.property_base(&base, &key, "delete")?;
+                let is_deleted = object.try_delete(self.context, &key)?;
+                if !is_deleted && self.is_strict {
+                    return Err(JsError::type_error(format!("cannot delete property {key}")));
+                }
+                is_deleted
+            },
+            Some(Reference::Super { .. }) => {
+                return Err(JsError::new(ErrorKind::ReferenceError, "cannot delete a `super` property"));
+            },
+            Some(Reference::Private { .. }) => unreachable!("`delete` of a private member is an early error"),
+        })
+    }
+
+    fn evaluate_binary(&mut self, expression: &BinaryExpression) -> Result<Value, JsError> {
+        let left = self.evaluate(&expression.left)?;
+        match short_circuits(expression.operator, &left) {
+            Some(true) => Ok(left),
+            Some(false) => self.evaluate(&expression.right),
+            None => {
+                let right = self.evaluate(&expression.right)?;
+                apply_binary_operator(self.context, expression.operator, &left, &right)
+            },
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-relational-operators-runtime-semantics-evaluation>
+    /// of `PrivateIdentifier in ShiftExpression` once the object is
+    /// evaluated.
+    pub(super) fn private_in(&self, identifier: &PrivateIdentifier, object: &Value) -> Result<Value, JsError> {
+        let Value::Object(object) = object else {
+            return Err(JsError::type_error("right-hand side of `in` is not an object"));
+        };
+        let name = self.resolve_private_identifier(identifier);
+        Ok(Value::Boolean(object.private_element_find(&name).is_some()))
+    }
+
+    fn evaluate_assignment(&mut self, expression: &AssignmentExpression) -> Result<Value, JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-assignment-operators-runtime-semantics-evaluation>
+        if expression.operator == AssignmentOperator::Assignment && is_pattern(&expression.target) {
+            let value = self.evaluate(&expression.value)?;
+            self.destructuring_assignment(&expression.target, &value)?;
+            return Ok(value);
+        }
+        let reference = self.evaluate_reference(&expression.target)?;
+        let Some(operator) = compound_operator(expression.operator) else {
+            let value = self.evaluate_assigned_value(&expression.target, &expression.value)?;
+            self.put_value(&reference, value.clone())?;
+            return Ok(value);
+        };
+        let left = self.get_value(&reference).map_err(|error| error.at(&expression.target.range()))?;
+        let value = match short_circuits(operator, &left) {
+            Some(true) => return Ok(left),
+            Some(false) => self.evaluate_assigned_value(&expression.target, &expression.value)?,
+            None => {
+                let right = self.evaluate(&expression.value)?;
+                apply_binary_operator(self.context, operator, &left, &right)?
+            },
+        };
+        self.put_value(&reference, value.clone())?;
+        Ok(value)
+    }
+
+    /// Evaluate a value assigned to a target, naming an anonymous function
+    /// after an identifier it is assigned to.
+    pub(super) fn evaluate_assigned_value(&mut self, target: &Expression, value: &Expression) -> Result<Value, JsError> {
+        match target {
+            Expression::IdentifierReference(reference) => self.evaluate_named(value, &PropertyKey::from(reference.name.as_str())),
+            _ => self.evaluate(value),
+        }
+    }
+}
+
+fn not_defined(name: &str) -> JsError {
+    JsError::new(ErrorKind::ReferenceError, format!("{name} is not defined"))
+}
+
+/// Whether the expression is an object or an array literal being
+/// a destructuring assignment pattern in place of an assignment target.
+pub(super) const fn is_pattern(expression: &Expression) -> bool {
+    matches!(expression, Expression::ObjectLiteral(_) | Expression::ArrayLiteral(_))
+}
+
+/// Whether a possibly parenthesized expression evaluates to a reference.
+pub(super) fn is_reference(expression: &Expression) -> bool {
+    match expression {
+        Expression::IdentifierReference(_) | Expression::MemberExpression(_) => true,
+        Expression::ParenthesizedExpression(parenthesized) => is_reference(&parenthesized.expression),
+        _ => false,
+    }
+}
+
+/// `__proto__: value` of an object literal, which sets its prototype.
+pub(super) fn is_proto_setter(name: &PropertyName) -> bool {
+    match name {
+        PropertyName::IdentifierName(name, _) => name == "__proto__",
+        PropertyName::StringLiteral(literal, _) => literal.code_units() == "__proto__".encode_utf16().collect::<Vec<_>>(),
+        _ => false,
+    }
+}
+
+/// A name of an identifier reference, possibly parenthesized.
+fn reference_name(expression: &Expression) -> Option<&str> {
+    match expression {
+        Expression::IdentifierReference(reference) => Some(&reference.name),
+        Expression::ParenthesizedExpression(expression) => reference_name(&expression.expression),
+        _ => None,
+    }
+}
+
+/// A name of what a call or `new` fails on for messages.
+pub(super) fn callee_name(callee: &Expression) -> &str {
+    match callee {
+        Expression::IdentifierReference(reference) => &reference.name,
+        Expression::MemberExpression(MemberExpression { property: MemberProperty::IdentifierName(name, _), .. }) => name,
+        Expression::ParenthesizedExpression(expression) => callee_name(&expression.expression),
+        _ => "expression",
+    }
+}
+
+/// The binary operator a compound assignment applies, `None` for `=`.
+pub(super) const fn compound_operator(operator: AssignmentOperator) -> Option<BinaryOperator> {
+    Some(match operator {
+        AssignmentOperator::Assignment => return None,
+        AssignmentOperator::AndAssignment => BinaryOperator::And,
+        AssignmentOperator::OrAssignment => BinaryOperator::Or,
+        AssignmentOperator::NullishCoalescenceAssignment => BinaryOperator::NullishCoalescence,
+        AssignmentOperator::MultiplicationAssignment => BinaryOperator::Multiplication,
+        AssignmentOperator::DivisionAssignment => BinaryOperator::Division,
+        AssignmentOperator::ModuloAssignment => BinaryOperator::Modulo,
+        AssignmentOperator::AdditionAssignment => BinaryOperator::Addition,
+        AssignmentOperator::SubtractionAssignment => BinaryOperator::Subtraction,
+        AssignmentOperator::LeftShiftAssignment => BinaryOperator::LeftShift,
+        AssignmentOperator::RightShiftAssignment => BinaryOperator::RightShift,
+        AssignmentOperator::UnsignedRightShiftAssignment => BinaryOperator::UnsignedRightShift,
+        AssignmentOperator::BitAndAssignment => BinaryOperator::BitAnd,
+        AssignmentOperator::BitXorAssignment => BinaryOperator::BitXor,
+        AssignmentOperator::BitOrAssignment => BinaryOperator::BitOr,
+        AssignmentOperator::ExponentiationAssignment => BinaryOperator::Exponentiation,
+    })
+}
+
+/// Whether a logical operator
+/// <https://262.ecma-international.org/14.0/#sec-binary-logical-operators-runtime-semantics-evaluation>
+/// gives its left operand without evaluating the right one, `None` for
+/// other operators.
+pub(super) fn short_circuits(operator: BinaryOperator, left: &Value) -> Option<bool> {
+    match operator {
+        BinaryOperator::And => Some(!left.to_boolean()),
+        BinaryOperator::Or => Some(left.to_boolean()),
+        BinaryOperator::NullishCoalescence => Some(!left.is_nullish()),
+        _ => None,
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-postfix-increment-operator-runtime-semantics-evaluation>
+/// and its siblings applied to the old value of an operand, giving it as
+/// a numeric value and the new value.
+pub(super) fn apply_update_operator(context: &mut Context, operator: UpdateOperator, value: &Value) -> Result<(Value, Value), JsError> {
+    let old_value = value.to_numeric(context)?;
+    let new_value = match (&old_value, operator) {
+        (Value::BigInt(value), UpdateOperator::Increment) => Value::BigInt(BigIntOps::add(value, &BigInt::one())?),
+        (Value::BigInt(value), UpdateOperator::Decrement) => Value::BigInt(BigIntOps::subtract(value, &BigInt::one())?),
+        (Value::Number(value), UpdateOperator::Increment) => Value::Number(Number::add(*value, 1.0)),
+        (Value::Number(value), UpdateOperator::Decrement) => Value::Number(Number::subtract(*value, 1.0)),
+        _ => unreachable!("ToNumeric gives a Number or a BigInt"),
+    };
+    Ok((old_value, new_value))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-unary-operators> applied
+/// to the value of an operand.
+pub(super) fn apply_unary_operator(context: &mut Context, operator: UnaryOperator, value: &Value) -> Result<Value, JsError> {
+    Ok(match operator {
+        // Deleting a value rather than a reference succeeds.
+        UnaryOperator::Delete => Value::Boolean(true),
+        UnaryOperator::Void => Value::Undefined,
+        UnaryOperator::Typeof => Value::from(type_of(value)),
+        UnaryOperator::Plus => Value::Number(value.to_number(context)?),
+        UnaryOperator::Minus => match value.to_numeric(context)? {
+            Value::BigInt(value) => Value::BigInt(BigIntOps::unary_minus(&value)),
+            value => Value::Number(Number::unary_minus(value.to_number(context)?)),
+        },
+        UnaryOperator::BitNot => match value.to_numeric(context)? {
+            Value::BigInt(value) => Value::BigInt(BigIntOps::bitwise_not(&value)),
+            value => Value::Number(Number::bitwise_not(value.to_number(context)?)),
+        },
+        UnaryOperator::Not => Value::Boolean(!value.to_boolean()),
+    })
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-applystringornumericbinaryoperator>
+/// and evaluation of relational and equality operators.
+pub(super) fn apply_binary_operator(context: &mut Context, operator: BinaryOperator, left: &Value, right: &Value) -> Result<Value, JsError> {
+    let value = match operator {
+        BinaryOperator::Less => Value::Boolean(is_less_than(context, left, right, true)? == Some(true)),
+        BinaryOperator::More => Value::Boolean(is_less_than(context, right, left, false)? == Some(true)),
+        BinaryOperator::LessOrEqual => Value::Boolean(is_less_than(context, right, left, false)? == Some(false)),
+        BinaryOperator::MoreOrEqual => Value::Boolean(is_less_than(context, left, right, true)? == Some(false)),
+        BinaryOperator::LooseEquality => Value::Boolean(is_loosely_equal(context, left, right)?),
+        BinaryOperator::LooseInequality => Value::Boolean(!is_loosely_equal(context, left, right)?),
+        BinaryOperator::StrictEquality => Value::Boolean(left.is_strictly_equal(right)),
+        BinaryOperator::StrictInequality => Value::Boolean(!left.is_strictly_equal(right)),
+        BinaryOperator::In => {
+            let Value::Object(object) = right else {
+                return Err(JsError::type_error("right-hand side of `in` is not an object"));
+            };
+            let key = left.to_property_key(context)?;
+            Value::Boolean(object.try_has_property(context, &key)?)
+        },
+        BinaryOperator::Instanceof => Value::Boolean(instance_of(context, left, right)?),
+        _ => {
+            let (left, right) = if operator == BinaryOperator::Addition {
+                let left = left.to_primitive(context, None)?;
+                let right = right.to_primitive(context, None)?;
+                if matches!(left, Value::String(_)) || matches!(right, Value::String(_)) {
+                    return Ok(Value::String(left.to_string(context)?.concat(&right.to_string(context)?)));
+                }
+                (left, right)
+            } else {
+                (left.clone(), right.clone())
+            };
+            let (left, right) = (left.to_numeric(context)?, right.to_numeric(context)?);
+            apply_numeric_operator(operator, left, right)?
+        },
+    };
+    Ok(value)
+}
+
+/// Steps 3 to 8 of
+/// <https://262.ecma-international.org/14.0/#sec-applystringornumericbinaryoperator>
+/// over operands [`Value::to_numeric`] gives.
+fn apply_numeric_operator(operator: BinaryOperator, left: Value, right: Value) -> Result<Value, JsError> {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => Ok(Value::Number(match operator {
+            BinaryOperator::Exponentiation => Number::exponentiate(left, right),
+            BinaryOperator::Multiplication => Number::multiply(left, right),
+            BinaryOperator::Division => Number::divide(left, right),
+            BinaryOperator::Modulo => Number::remainder(left, right),
+            BinaryOperator::Addition => Number::add(left, right),
+            BinaryOperator::Subtraction => Number::subtract(left, right),
+            BinaryOperator::LeftShift => Number::left_shift(left, right),
+            BinaryOperator::RightShift => Number::signed_right_shift(left, right),
+            BinaryOperator::UnsignedRightShift => Number::unsigned_right_shift(left, right),
+            BinaryOperator::BitAnd => Number::bitwise_and(left, right),
+            BinaryOperator::BitXor => Number::bitwise_xor(left, right),
+            BinaryOperator::BitOr => Number::bitwise_or(left, right),
+            _ => unreachable!("{operator:?} is not numeric"),
+        })),
+        (Value::BigInt(left), Value::BigInt(right)) => Ok(Value::BigInt(match operator {
+            BinaryOperator::Exponentiation => BigIntOps::exponentiate(&left, &right)?,
+            BinaryOperator::Multiplication => BigIntOps::multiply(&left, &right)?,
+            BinaryOperator::Division => BigIntOps::divide(&left, &right)?,
+            BinaryOperator::Modulo => BigIntOps::remainder(&left, &right)?,
+            BinaryOperator::Addition => BigIntOps::add(&left, &right)?,
+            BinaryOperator::Subtraction => BigIntOps::subtract(&left, &right)?,
+            BinaryOperator::LeftShift => BigIntOps::left_shift(&left, &right)?,
+            BinaryOperator::RightShift => BigIntOps::signed_right_shift(&left, &right)?,
+            BinaryOperator::UnsignedRightShift => BigIntOps::unsigned_right_shift(&left, &right)?,
+            BinaryOperator::BitAnd => BigIntOps::bitwise_and(&left, &right),
+            BinaryOperator::BitXor => BigIntOps::bitwise_xor(&left, &right),
+            BinaryOperator::BitOr => BigIntOps::bitwise_or(&left, &right),
+            _ => unreachable!("{operator:?} is not numeric"),
+        })),
+        _ => Err(JsError::type_error("cannot mix BigInt and other types, use explicit conversions")),
+    }
+}
+
+fn literal_value(literal: &Literal) -> Value {
+    // <https://262.ecma-international.org/14.0/#sec-literals-runtime-semantics-evaluation>
+    match literal {
+        Literal::NullLiteral => Value::Null,
+        Literal::BooleanLiteral(value) => Value::Boolean(*value),
+        Literal::NumericLiteral(literal) => match literal.numeric_value() {
+            NumericValue::Number(value) => Value::Number(value),
+            NumericValue::BigInt(value) => Value::BigInt(value),
+        },
+        Literal::StringLiteral(literal) => Value::String(JsString::from_code_units(&literal.code_units())),
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-islessthan>, `None` standing
+/// for **undefined** if either operand is NaN.
+fn is_less_than(context: &mut Context, left: &Value, right: &Value, left_first: bool) -> Result<Option<bool>, JsError> {
+    let (left, right) = if left_first {
+        let left = left.to_primitive(context, Some(PreferredType::Number))?;
+        (left, right.to_primitive(context, Some(PreferredType::Number))?)
+    } else {
+        let right = right.to_primitive(context, Some(PreferredType::Number))?;
+        (left.to_primitive(context, Some(PreferredType::Number))?, right)
+    };
+    match (&left, &right) {
+        // Strings are compared by their code units.
+        (Value::String(left), Value::String(right)) => return Ok(Some(left < right)),
+        (Value::BigInt(left), Value::String(right)) => {
+            return Ok(string_to_bigint(right).map(|right| BigIntOps::less_than(left, &right)));
+        },
+        (Value::String(left), Value::BigInt(right)) => {
+            return Ok(string_to_bigint(left).map(|left| BigIntOps::less_than(&left, right)));
+        },
+        _ => {},
+    }
+    Ok(match (left.to_numeric(context)?, right.to_numeric(context)?) {
+        (Value::Number(left), Value::Number(right)) => Number::less_than(left, right),
+        (Value::BigInt(left), Value::BigInt(right)) => Some(BigIntOps::less_than(&left, &right)),
+        (Value::BigInt(left), Value::Number(right)) => BigIntOps::compare_to_number(&left, right).map(Ordering::is_lt),
+        (Value::Number(left), Value::BigInt(right)) => BigIntOps::compare_to_number(&right, left).map(Ordering::is_gt),
+        _ => unreachable!("ToNumeric gives a Number or a BigInt"),
+    })
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-islooselyequal>
+fn is_loosely_equal(context: &mut Context, left: &Value, right: &Value) -> Result<bool, JsError> {
+    Ok(match (left, right) {
+        _ if left.r#type() == right.r#type() => left.is_strictly_equal(right),
+        (Value::Undefined | Value::Null, Value::Undefined | Value::Null) => true,
+        (Value::Number(number), Value::String(string)) | (Value::String(string), Value::Number(number)) => {
+            Number::equal(*number, string_to_number(string))
+        },
+        (Value::BigInt(bigint), Value::String(string)) | (Value::String(string), Value::BigInt(bigint)) => {
+            string_to_bigint(string).is_some_and(|value| BigIntOps::equal(bigint, &value))
+        },
+        (Value::BigInt(bigint), Value::Number(number)) | (Value::Number(number), Value::BigInt(bigint)) => {
+            BigIntOps::compare_to_number(bigint, *number) == Some(Ordering::Equal)
+        },
+        (Value::Boolean(_), _) => {
+            let left = Value::Number(left.to_number(context)?);
+            is_loosely_equal(context, &left, right)?
+        },
+        (_, Value::Boolean(_)) => {
+            let right = Value::Number(right.to_number(context)?);
+            is_loosely_equal(context, left, &right)?
+        },
+        (Value::Object(_), Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_)) => {
+            let left = left.to_primitive(context, None)?;
+            is_loosely_equal(context, &left, right)?
+        },
+        (Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_), Value::Object(_)) => {
+            let right = right.to_primitive(context, None)?;
+            is_loosely_equal(context, left, &right)?
+        },
+        _ => false,
+    })
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-instanceofoperator>
+fn instance_of(context: &mut Context, value: &Value, target: &Value) -> Result<bool, JsError> {
+    let Value::Object(target) = target else {
+        return Err(JsError::type_error("right-hand side of `instanceof` is not an object"));
+    };
+    let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::HasInstance));
+    if let Some(handler) = target.get_method(context, &key)? {
+        return Ok(handler.call(context, &Value::Object(target.clone()), std::slice::from_ref(value))?.to_boolean());
+    }
+    if !target.is_callable() {
+        return Err(JsError::type_error("right-hand side of `instanceof` is not callable"));
+    }
+    ordinary_has_instance(context, target, value)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-ordinaryhasinstance> of
+/// a callable object.
+fn ordinary_has_instance(context: &mut Context, constructor: &Object, value: &Value) -> Result<bool, JsError> {
+    let Value::Object(object) = value else {
+        return Ok(false);
+    };
+    let this = Value::Object(constructor.clone());
+    let Value::Object(prototype) = constructor.get(context, &PropertyKey::from("prototype"), &this)? else {
+        return Err(JsError::type_error("prototype of the right-hand side of `instanceof` is not an object"));
+    };
+    let mut ancestor = object.try_get_prototype_of(context)?;
+    while let Some(object) = ancestor {
+        if object == prototype {
+            return Ok(true);
+        }
+        ancestor = object.try_get_prototype_of(context)?;
+    }
+    Ok(false)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-typeof-operator>
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Undefined => "undefined",
+        Value::Object(object) if object.is_callable() => "function",
+        Value::Null | Value::Object(_) => "object",
+        Value::Boolean(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Symbol(_) => "symbol",
+        Value::BigInt(_) => "bigint",
+    }
+}
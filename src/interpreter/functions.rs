@@ -0,0 +1,1455 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-function-objects>
+//! and <https://262.ecma-international.org/14.0/#sec-ecmascript-language-functions-and-classes>.
+
+use super::async_functions;
+use super::generators::Generator;
+use super::resumable::{Frame, ResumableCode};
+use super::statements::Completion;
+use super::{Code, Evaluator};
+use crate::grammar::{
+    ArrowFunction,
+    Binding,
+    BindingProperty,
+    Block,
+    Class,
+    ClassElement,
+    ClassElementName,
+    ConciseBody,
+    Declaration,
+    Expression,
+    ForInit,
+    ForTarget,
+    FormalParameters,
+    Function,
+    LabelledItem,
+    MethodDefinition,
+    MethodKind,
+    Parser,
+    PropertyName,
+    Statement,
+    StatementListItem,
+    VariableKind,
+};
+use crate::lexical_grammar::PrivateIdentifier;
+use crate::runtime::{
+    charge_code,
+    Context,
+    Environment,
+    ErrorKind,
+    ExecutionContext,
+    Intrinsics,
+    JsError,
+    Object,
+    ObjectKind,
+    ParameterMap,
+    PrivateElement,
+    PrivateElementKind,
+    PrivateEnvironment,
+    PrivateName,
+    PromiseCapability,
+    PropertyDescriptor,
+    PropertyKey,
+    Realm,
+    Symbol,
+    Tracer,
+    Value,
+    WellKnownSymbol,
+};
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::vec;
+
+/// A syntax tree node defining a function.
+///
+/// Nodes are shared with the tree, so code of nested functions does not
+/// copy their bodies again.
+enum FunctionNode {
+    Function(Rc<Function>),
+    Arrow(Rc<ArrowFunction>),
+    Method(Rc<MethodDefinition>),
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-classdefinitionevaluation>
+    /// of a class without a `constructor` method, with an empty body.
+    DefaultConstructor,
+    /// An initializer of a class field, evaluated as a method returning
+    /// its value.
+    Initializer(Rc<Expression>),
+    /// `ClassStaticBlockStatementList`, evaluated as a method body.
+    StaticBlock(Rc<[StatementListItem]>),
+}
+
+/// A call in tail position of strict function code, which
+/// [`Object::call`] makes in place of the function once it returns.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TailCall {
+    pub(crate) function: Object,
+    pub(crate) this: Value,
+    pub(crate) arguments: Vec<Value>,
+}
+
+/// How `[[Call]]` of a function completes normally.
+pub(crate) enum CallCompletion {
+    Return(Value),
+    /// The function returns what the call returns.
+    TailCall(TailCall),
+}
+
+/// Parameters of functions without them in the source.
+const NO_PARAMETERS: &FormalParameters = &FormalParameters { parameters: Vec::new(), rest: None, range: 0..0 };
+
+/// `FunctionBody` or `ExpressionBody` of a function.
+enum Body<'a> {
+    Statements(&'a [StatementListItem]),
+    Expression(&'a Expression),
+}
+
+/// Code of a function shared by all function objects created from the same
+/// node, sharing the node with the tree.
+pub(super) struct FunctionCode {
+    node: FunctionNode,
+    /// The body skipped by the parser, parsed on the first call.
+    deferred_body: OnceCell<Vec<StatementListItem>>,
+    /// Caches of functions nested into the body.
+    code: Code,
+    /// The body lowered for generators on the first call.
+    resumable: OnceCell<ResumableCode>,
+}
+
+impl FunctionCode {
+    /// Code of a function defined in `outer`, whose source text nested
+    /// deferred bodies are parsed from.
+    fn new(node: FunctionNode, outer: &Code) -> Self {
+        let code = Code::with_source(outer.source.clone());
+        Self { node, deferred_body: OnceCell::new(), code, resumable: OnceCell::new() }
+    }
+
+    /// An evaluator of the body in the running execution context.
+    pub(super) fn evaluator<'a>(&'a self, context: &'a mut Context) -> Evaluator<'a> {
+        Evaluator { context, code: &self.code, is_strict: self.is_strict(), tail_calls: false }
+    }
+
+    /// The body lowered into code a [`Frame`] runs.
+    ///
+    /// Lowering copies parts of the body, which are charged like the tree
+    /// of the body is.
+    pub(super) fn resumable(&self) -> &ResumableCode {
+        self.resumable.get_or_init(|| {
+            charge_code(self.source_len());
+            match self.body() {
+                Body::Statements(statements) => ResumableCode::new(statements),
+                Body::Expression(expression) => ResumableCode::from_expression(expression),
+            }
+        })
+    }
+
+    fn parameters(&self) -> &FormalParameters {
+        match &self.node {
+            FunctionNode::Function(function) => &function.parameters,
+            FunctionNode::Arrow(function) => &function.parameters,
+            FunctionNode::Method(method) => &method.parameters,
+            FunctionNode::DefaultConstructor | FunctionNode::Initializer(_) | FunctionNode::StaticBlock(_) => NO_PARAMETERS,
+        }
+    }
+
+    /// Length of source text of the node.
+    fn source_len(&self) -> usize {
+        match &self.node {
+            FunctionNode::Function(function) => function.range.len(),
+            FunctionNode::Arrow(function) => function.range.len(),
+            FunctionNode::Method(method) => method.range.len(),
+            FunctionNode::DefaultConstructor => 0,
+            FunctionNode::Initializer(expression) => expression.range().len(),
+            FunctionNode::StaticBlock(statements) => statements.iter().map(|statement| statement.range().len()).sum(),
+        }
+    }
+
+    fn body(&self) -> Body<'_> {
+        match &self.node {
+            FunctionNode::Function(function) => Body::Statements(self.statements(&function.body)),
+            FunctionNode::Arrow(function) => match &function.body {
+                ConciseBody::Expression(expression) => Body::Expression(expression),
+                ConciseBody::FunctionBody(statements, _) => Body::Statements(self.statements(statements)),
+            },
+            FunctionNode::Method(method) => Body::Statements(self.statements(&method.body)),
+            FunctionNode::DefaultConstructor => Body::Statements(&[]),
+            FunctionNode::Initializer(expression) => Body::Expression(expression),
+            FunctionNode::StaticBlock(statements) => Body::Statements(statements),
+        }
+    }
+
+    /// Statements of a body, parsed on the first call if it is deferred.
+    fn statements<'a>(&'a self, body: &'a [StatementListItem]) -> &'a [StatementListItem] {
+        self.deferred_body.get().map_or(body, Vec::as_slice)
+    }
+
+    fn is_strict(&self) -> bool {
+        match &self.node {
+            FunctionNode::Function(function) => function.is_strict,
+            FunctionNode::Arrow(function) => function.is_strict,
+            FunctionNode::Method(method) => method.is_strict,
+            // All parts of a class are strict mode code.
+            FunctionNode::DefaultConstructor | FunctionNode::Initializer(_) | FunctionNode::StaticBlock(_) => true,
+        }
+    }
+
+    const fn is_arrow(&self) -> bool {
+        matches!(self.node, FunctionNode::Arrow(_))
+    }
+
+    /// Whether `new` applies to the function: plain functions are
+    /// constructors, while arrow functions, methods, generators and async
+    /// functions are not; class constructors are made ones on creation.
+    fn is_constructor(&self) -> bool {
+        matches!(&self.node, FunctionNode::Function(function) if !function.is_generator && !function.is_async)
+    }
+
+    /// Whether the function is a generator, returning a generator object
+    /// running its body.
+    fn is_generator(&self) -> bool {
+        match &self.node {
+            FunctionNode::Function(function) => function.is_generator && !function.is_async,
+            FunctionNode::Method(method) => matches!(method.kind, MethodKind::Generator),
+            _ => false,
+        }
+    }
+
+    /// Whether the function is an async function, returning a promise of
+    /// the completion of its body.
+    fn is_async(&self) -> bool {
+        match &self.node {
+            FunctionNode::Function(function) => function.is_async && !function.is_generator,
+            FunctionNode::Arrow(function) => function.is_async,
+            FunctionNode::Method(method) => matches!(method.kind, MethodKind::Async),
+            FunctionNode::DefaultConstructor | FunctionNode::Initializer(_) | FunctionNode::StaticBlock(_) => false,
+        }
+    }
+
+    /// Fail for functions whose evaluation is not supported yet.
+    fn check_supported(&self) -> Result<(), JsError> {
+        let is_async_generator = match &self.node {
+            FunctionNode::Function(function) => function.is_generator && function.is_async,
+            FunctionNode::Method(method) => matches!(method.kind, MethodKind::AsyncGenerator),
+            _ => false,
+        };
+        if is_async_generator {
+            return Err(JsError::unsupported("async generators"));
+        }
+        Ok(())
+    }
+
+    /// Parse a body skipped by the parser unless it is parsed already.
+    ///
+    /// A body is checked for early errors only when it is parsed, which
+    /// also compares its lexical declarations with the parameters.
+    fn parse_deferred_body(&self) -> Result<(), JsError> {
+        let deferred = match &self.node {
+            FunctionNode::Function(function) => function.deferred_body.as_ref(),
+            FunctionNode::Arrow(function) => function.deferred_body.as_ref(),
+            FunctionNode::Method(method) => method.deferred_body.as_ref(),
+            FunctionNode::DefaultConstructor | FunctionNode::Initializer(_) | FunctionNode::StaticBlock(_) => None,
+        };
+        let Some(deferred) = deferred.filter(|_| self.deferred_body.get().is_none()) else {
+            return Ok(());
+        };
+        let Some(source) = &self.code.source else {
+            return Err(JsError::unsupported("deferred function bodies without source text"));
+        };
+        let body = Parser::new(source)
+            .with_deferred_function_bodies()
+            .parse_deferred_body(deferred)
+            .map_err(|error| JsError::new(ErrorKind::SyntaxError, error.message).at(&error.range))?;
+        let parameter_names = self.parameters().bound_names();
+        for name in body.iter().flat_map(StatementListItem::top_level_lexically_declared_names) {
+            if parameter_names.iter().any(|parameter| parameter.name == name.name) {
+                let message = format!("identifier {} has already been declared", name.name);
+                return Err(JsError::new(ErrorKind::SyntaxError, message).at(&name.range));
+            }
+        }
+        // Nothing else sets the cell, so it is still empty.
+        let _ = self.deferred_body.set(body);
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-function-definitions-static-semantics-expectedargumentcount>
+    fn expected_argument_count(&self) -> usize {
+        let parameters = &self.parameters().parameters;
+        parameters.iter().take_while(|parameter| parameter.initializer.is_none()).count()
+    }
+}
+
+/// `[[ThisMode]]` of a function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ThisMode {
+    /// `this` of the enclosing code, as for arrow functions.
+    Lexical,
+    /// `this` as passed by a caller.
+    Strict,
+    /// `this` converted to an object, the global one for **undefined**
+    /// and **null**.
+    Global,
+}
+
+/// Internal slots of an
+/// <https://262.ecma-international.org/14.0/#sec-ecmascript-function-objects>,
+/// a function defined by ECMAScript code.
+pub struct FunctionData {
+    code: Rc<FunctionCode>,
+    /// `[[Environment]]` the function closes over.
+    environment: Environment,
+    /// `[[PrivateEnvironment]]` resolving private names in the body.
+    private_environment: Option<PrivateEnvironment>,
+    realm: Realm,
+    this_mode: ThisMode,
+    is_constructor: bool,
+    method: MethodSlots,
+}
+
+/// Internal slots of methods, field initializers and class constructors
+/// that plain functions leave empty.
+#[derive(Default)]
+struct MethodSlots {
+    /// `[[HomeObject]]`, the prototype of which `super` properties refer
+    /// to.
+    home_object: Option<Object>,
+    /// `[[ClassFieldInitializerName]]`, naming an anonymous function the
+    /// initializer defines.
+    class_field_initializer_name: Option<PropertyKey>,
+    /// `[[ConstructorKind]]` and `[[Fields]]` of a class constructor.
+    class: Option<ClassConstructor>,
+}
+
+/// Internal slots of a class constructor.
+struct ClassConstructor {
+    /// Whether `[[ConstructorKind]]` is derived: the class has `extends`,
+    /// so `this` is created by `super()`.
+    is_derived: bool,
+    /// `[[Fields]]`, set once the class body is evaluated.
+    fields: RefCell<Vec<ClassField>>,
+    /// `[[PrivateMethods]]`, the private methods and accessors of
+    /// instances, set with `fields`.
+    private_methods: RefCell<Vec<PrivateElement>>,
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-classfielddefinition-record-specification-type>
+struct ClassField {
+    name: ClassElementKey,
+    /// A method returning the initial value, **undefined** if `None`.
+    initializer: Option<Object>,
+}
+
+/// A property key or a private name a class element defines.
+enum ClassElementKey {
+    Property(PropertyKey),
+    Private(PrivateName),
+}
+
+impl FunctionData {
+    /// Report `[[Environment]]`, `[[HomeObject]]` and `[[Fields]]` to the
+    /// heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        tracer.environment(&self.environment);
+        if let Some(home_object) = &self.method.home_object {
+            tracer.object(home_object);
+        }
+        if let Some(class) = &self.method.class {
+            for initializer in class.fields.borrow().iter().filter_map(|field| field.initializer.as_ref()) {
+                tracer.object(initializer);
+            }
+            for method in class.private_methods.borrow().iter() {
+                method.trace(tracer);
+            }
+        }
+    }
+
+    /// Whether the function has a `[[Construct]]` internal method.
+    #[must_use]
+    pub const fn is_constructor(&self) -> bool {
+        self.is_constructor
+    }
+
+    /// `[[HomeObject]]` of a method, `None` for other functions.
+    #[must_use]
+    pub const fn home_object(&self) -> Option<&Object> {
+        self.method.home_object.as_ref()
+    }
+
+    /// `[[Call]]` of <https://262.ecma-international.org/14.0/#sec-ecmascript-function-objects-call-thisargument-argumentslist>.
+    pub(crate) fn call(
+        &self,
+        context: &mut Context,
+        function: &Object,
+        this: &Value,
+        arguments: &[Value],
+    ) -> Result<CallCompletion, JsError> {
+        if self.method.class.is_some() {
+            return Err(JsError::type_error("a class constructor cannot be invoked without `new`"));
+        }
+        self.code.check_supported()?;
+        self.code.parse_deferred_body()?;
+        if self.code.is_generator() {
+            return self.start_generator(context, function, this, arguments).map(CallCompletion::Return);
+        }
+        if self.code.is_async() {
+            return self.start_async_function(context, function, this, arguments).map(CallCompletion::Return);
+        }
+        let environment = self.prepare_for_ordinary_call(context, function, Value::Undefined);
+        let result = self
+            .bind_this(context, &environment, this)
+            .and_then(|()| self.evaluate_body(context, arguments));
+        context.pop_execution_context();
+        match result? {
+            Completion::Return(value) => Ok(CallCompletion::Return(value)),
+            Completion::TailCall(call) => Ok(CallCompletion::TailCall(call)),
+            _ => Ok(CallCompletion::Return(Value::Undefined)),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-evaluateasyncfunctionbody>
+    /// and <https://262.ecma-international.org/14.0/#sec-runtime-semantics-evaluateasyncconcisebody>:
+    /// instantiate declarations and run the body up to its first `await`,
+    /// returning a promise of its completion.
+    fn start_async_function(&self, context: &mut Context, function: &Object, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let constructor = self.realm.intrinsics().promise.clone();
+        let capability = PromiseCapability::new(context, &Value::Object(constructor.clone()))?;
+        let environment = self.prepare_for_ordinary_call(context, function, Value::Undefined);
+        let result = self
+            .bind_this(context, &environment, this)
+            .and_then(|()| self.code.evaluator(context).function_declaration_instantiation(&self.code, arguments));
+        let execution_context = context.pop_execution_context().expect("the call pushed an execution context");
+        let promise = capability.promise.clone();
+        match result {
+            Ok(()) => async_functions::start(context, Frame::new(self.code.clone(), execution_context), constructor, capability)?,
+            Err(error) if error.kind.is_catchable() => {
+                let reason = error.into_value(context);
+                capability.reject.call(context, &Value::Undefined, &[reason])?;
+            },
+            Err(error) => return Err(error),
+        }
+        Ok(Value::Object(promise))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-evaluategeneratorbody>:
+    /// instantiate declarations and create a generator object suspended
+    /// at the start of the body.
+    fn start_generator(&self, context: &mut Context, function: &Object, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let environment = self.prepare_for_ordinary_call(context, function, Value::Undefined);
+        let result = self.bind_this(context, &environment, this).and_then(|()| {
+            self.code.evaluator(context).function_declaration_instantiation(&self.code, arguments)?;
+            get_prototype_from_constructor(context, function, |intrinsics| &intrinsics.generator_prototype)
+        });
+        let execution_context = context.pop_execution_context().expect("the call pushed an execution context");
+        let frame = Frame::new(self.code.clone(), execution_context);
+        Ok(Value::Object(Generator::create(result?, frame)))
+    }
+
+    /// `[[Construct]]` of <https://262.ecma-international.org/14.0/#sec-ecmascript-function-objects-construct-argumentslist-newtarget>.
+    ///
+    /// A derived class constructor gets `this` from `super()`; without
+    /// a `constructor` method, it passes its arguments to the parent one.
+    pub(crate) fn construct(
+        &self,
+        context: &mut Context,
+        function: &Object,
+        arguments: &[Value],
+        new_target: &Object,
+    ) -> Result<Object, JsError> {
+        self.code.check_supported()?;
+        self.code.parse_deferred_body()?;
+        let is_derived = self.method.class.as_ref().is_some_and(|class| class.is_derived);
+        let this = if is_derived {
+            None
+        } else {
+            Some(Object::new(Some(get_prototype_from_constructor(context, new_target, |intrinsics| &intrinsics.object_prototype)?)))
+        };
+        let environment = self.prepare_for_ordinary_call(context, function, Value::Object(new_target.clone()));
+        let result = match &this {
+            Some(this) => self
+                .bind_this(context, &environment, &Value::Object(this.clone()))
+                .and_then(|()| initialize_instance_elements(context, this, function))
+                .and_then(|()| self.evaluate_body(context, arguments)),
+            None if matches!(self.code.node, FunctionNode::DefaultConstructor) => get_super_constructor(context, &environment)
+                .and_then(|parent| super_call(context, &environment, &parent, arguments))
+                .map(|_| Completion::Normal(None)),
+            None => self.evaluate_body(context, arguments),
+        };
+        context.pop_execution_context();
+        let completion = match result? {
+            Completion::TailCall(TailCall { function, this, arguments }) => Completion::Return(function.call(context, &this, &arguments)?),
+            completion => completion,
+        };
+        match completion {
+            Completion::Return(Value::Object(object)) => return Ok(object),
+            Completion::Return(value) if this.is_none() && !value.is_undefined() => {
+                return Err(JsError::type_error("a derived constructor may only return an object or undefined"));
+            },
+            _ => {},
+        }
+        if let Some(this) = this {
+            return Ok(this);
+        }
+        match environment.get_this_binding()? {
+            Value::Object(this) => Ok(this),
+            _ => unreachable!("`super()` binds an object"),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-prepareforordinarycall>:
+    /// push an execution context of the function, returning its function
+    /// Environment Record.
+    fn prepare_for_ordinary_call(&self, context: &mut Context, function: &Object, new_target: Value) -> Environment {
+        let is_lexical = self.this_mode == ThisMode::Lexical;
+        let environment = Environment::new_function(function.clone(), new_target, is_lexical, self.environment.clone());
+        let mut execution_context =
+            ExecutionContext::with_environment(Some(function.clone()), self.realm.clone(), environment.clone());
+        execution_context.private_environment.clone_from(&self.private_environment);
+        context.push_execution_context(execution_context);
+        environment
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarycallbindthis>
+    fn bind_this(&self, context: &Context, environment: &Environment, this: &Value) -> Result<(), JsError> {
+        let this = match self.this_mode {
+            ThisMode::Lexical => return Ok(()),
+            ThisMode::Strict => this.clone(),
+            ThisMode::Global if this.is_nullish() => self.realm.global_environment().get_this_binding()?,
+            ThisMode::Global => Value::Object(this.to_object(context)?),
+        };
+        environment.bind_this_value(this)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarycallevaluatebody>
+    ///
+    /// A call in tail position of strict code is left to the caller.
+    fn evaluate_body(&self, context: &mut Context, arguments: &[Value]) -> Result<Completion, JsError> {
+        let mut evaluator = self.code.evaluator(context);
+        evaluator.function_declaration_instantiation(&self.code, arguments)?;
+        evaluator.tail_calls = self.code.is_strict();
+        match (self.code.body(), &self.method.class_field_initializer_name) {
+            (Body::Statements(statements), _) => evaluator.evaluate_statements(statements),
+            (Body::Expression(expression), Some(name)) => evaluator.evaluate_named(expression, name).map(Completion::Return),
+            (Body::Expression(expression), None) => evaluator.evaluate_returned(expression),
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-getprototypefromconstructor>
+/// with an intrinsic of the realm of the constructor as the default.
+fn get_prototype_from_constructor(
+    context: &mut Context,
+    constructor: &Object,
+    default: fn(&Intrinsics) -> &Object,
+) -> Result<Object, JsError> {
+    let key = PropertyKey::from("prototype");
+    if let Value::Object(prototype) = constructor.get(context, &key, &Value::Object(constructor.clone()))? {
+        return Ok(prototype);
+    }
+    // GetFunctionRealm
+    let realm = match constructor.kind() {
+        ObjectKind::Function(function) => function.realm.clone(),
+        _ => context.current_realm().cloned().unwrap_or_default(),
+    };
+    Ok(default(realm.intrinsics()).clone())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-getsuperconstructor>
+/// of a derived constructor with the function `environment`.
+pub(super) fn get_super_constructor(context: &mut Context, environment: &Environment) -> Result<Value, JsError> {
+    let function = environment.function_object().expect("`super()` is only allowed in constructors");
+    Ok(function.try_get_prototype_of(context)?.map_or(Value::Null, Value::Object))
+}
+
+/// Steps of <https://262.ecma-international.org/14.0/#sec-super-keyword-runtime-semantics-evaluation>
+/// of `super(...)` after evaluation of arguments: construct `this` with
+/// the parent constructor and initialize fields of the derived class.
+pub(super) fn super_call(
+    context: &mut Context,
+    environment: &Environment,
+    parent: &Value,
+    arguments: &[Value],
+) -> Result<Object, JsError> {
+    let Some(parent) = parent.as_object().filter(|parent| parent.is_constructor()) else {
+        return Err(JsError::type_error("the parent class is not a constructor"));
+    };
+    let Some(Value::Object(new_target)) = environment.new_target().cloned() else {
+        return Err(JsError::type_error("`super()` is only allowed in constructors called with `new`"));
+    };
+    let this = parent.construct(context, arguments, Some(&new_target))?;
+    environment.bind_this_value(Value::Object(this.clone()))?;
+    let function = environment.function_object().expect("`super()` is only allowed in constructors");
+    initialize_instance_elements(context, &this, function)?;
+    Ok(this)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-initializeinstanceelements>
+/// of a class constructor.
+fn initialize_instance_elements(context: &mut Context, object: &Object, constructor: &Object) -> Result<(), JsError> {
+    let ObjectKind::Function(function) = constructor.kind() else {
+        return Ok(());
+    };
+    let Some(class) = &function.method.class else {
+        return Ok(());
+    };
+    for method in class.private_methods.borrow().iter() {
+        object.private_element_add(method.clone())?;
+    }
+    for field in class.fields.borrow().iter() {
+        define_field(context, object, field)?;
+    }
+    Ok(())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-definefield>
+fn define_field(context: &mut Context, receiver: &Object, field: &ClassField) -> Result<(), JsError> {
+    let value = match &field.initializer {
+        Some(initializer) => initializer.call(context, &Value::Object(receiver.clone()), &[])?,
+        None => Value::Undefined,
+    };
+    match &field.name {
+        ClassElementKey::Private(name) => {
+            receiver.private_element_add(PrivateElement { key: name.clone(), kind: PrivateElementKind::Field(value) })
+        },
+        ClassElementKey::Property(key) => {
+            let descriptor = PropertyDescriptor::data(value, true, true, true);
+            if !receiver.try_define_own_property(context, key.clone(), descriptor)? {
+                return Err(JsError::type_error(format!("cannot define field {key}")));
+            }
+            Ok(())
+        },
+    }
+}
+
+impl Evaluator<'_> {
+    /// <https://262.ecma-international.org/14.0/#sec-functiondeclarationinstantiation>
+    ///
+    /// An unmapped arguments object, for strict functions and ones with
+    /// parameters other than plain identifiers, has no `callee`.
+    fn function_declaration_instantiation(
+        &mut self,
+        code: &FunctionCode,
+        arguments: &[Value],
+    ) -> Result<(), JsError> {
+        let body = match code.body() {
+            Body::Statements(statements) => statements,
+            Body::Expression(_) => &[],
+        };
+        let formals = code.parameters();
+        let parameter_names: Vec<&str> = formals.bound_names().iter().map(|name| name.name.as_str()).collect();
+        let has_duplicates = parameter_names.iter().enumerate().any(|(index, name)| parameter_names[..index].contains(name));
+        let has_parameter_expressions = contains_expression(formals);
+        let var_names: Vec<&str> = body
+            .iter()
+            .flat_map(StatementListItem::top_level_var_declared_names)
+            .map(|name| name.name.as_str())
+            .collect();
+        let lexical_names: Vec<&str> = body
+            .iter()
+            .flat_map(StatementListItem::top_level_lexically_declared_names)
+            .map(|name| name.name.as_str())
+            .collect();
+        let functions_to_initialize = top_level_functions(body);
+        let function_names: HashSet<&str> = functions_to_initialize.iter().filter_map(|f| function_name(f)).collect();
+
+        let arguments_object_needed = !code.is_arrow()
+            && !parameter_names.contains(&"arguments")
+            && (has_parameter_expressions
+                || !(function_names.contains("arguments") || lexical_names.contains(&"arguments")));
+
+        let environment = if self.is_strict || !has_parameter_expressions {
+            self.lexical_environment()
+        } else {
+            let environment = Environment::new_declarative(Some(self.lexical_environment()));
+            self.set_lexical_environment(environment.clone());
+            environment
+        };
+        for name in &parameter_names {
+            if !environment.has_binding(self.context, name)? {
+                environment.create_mutable_binding(self.context, name, false)?;
+                if has_duplicates {
+                    environment.initialize_binding(self.context, name, Value::Undefined)?;
+                }
+            }
+        }
+        let mut parameter_bindings: HashSet<&str> = parameter_names.iter().copied().collect();
+        if arguments_object_needed {
+            let arguments_object = if self.is_strict || !formals.is_simple() {
+                self.create_unmapped_arguments_object(arguments)
+            } else {
+                self.create_mapped_arguments_object(&parameter_names, arguments, &environment)
+            };
+            if self.is_strict {
+                environment.create_immutable_binding("arguments", false);
+            } else {
+                environment.create_mutable_binding(self.context, "arguments", false)?;
+            }
+            environment.initialize_binding(self.context, "arguments", Value::Object(arguments_object))?;
+            parameter_bindings.insert("arguments");
+        }
+
+        // IteratorBindingInitialization of the parameters over the
+        // arguments list, which is not observable.
+        let parameter_environment = (!has_duplicates).then_some(&environment);
+        let mut remaining = arguments.iter().cloned();
+        for parameter in &formals.parameters {
+            let mut value = remaining.next().unwrap_or(Value::Undefined);
+            if let (Value::Undefined, Some(initializer)) = (&value, &parameter.initializer) {
+                value = match &parameter.target {
+                    Binding::BindingIdentifier(name) => {
+                        self.evaluate_named(initializer, &PropertyKey::from(name.name.as_str()))?
+                    },
+                    _ => self.evaluate(initializer)?,
+                };
+            }
+            self.binding_initialization(&parameter.target, value, parameter_environment)?;
+        }
+        if let Some(rest) = &formals.rest {
+            let prototype = self.realm().intrinsics().array_prototype.clone();
+            let array = Object::new_array(Some(prototype), remaining.collect());
+            self.binding_initialization(rest, Value::Object(array), parameter_environment)?;
+        }
+
+        let mut instantiated_var_names = parameter_bindings.clone();
+        let var_environment = if has_parameter_expressions {
+            // Parameter initializers must not see `var` declarations.
+            let var_environment = Environment::new_declarative(Some(environment.clone()));
+            let context = self.context.running_execution_context_mut().expect("ECMAScript code runs in an execution context");
+            context.variable_environment = Some(var_environment.clone());
+            for name in &var_names {
+                if instantiated_var_names.insert(name) {
+                    var_environment.create_mutable_binding(self.context, name, false)?;
+                    let value = if !parameter_bindings.contains(name) || function_names.contains(name) {
+                        Value::Undefined
+                    } else {
+                        environment.get_binding_value(self.context, name, false)?
+                    };
+                    var_environment.initialize_binding(self.context, name, value)?;
+                }
+            }
+            var_environment
+        } else {
+            for name in &var_names {
+                if instantiated_var_names.insert(name) {
+                    environment.create_mutable_binding(self.context, name, false)?;
+                    environment.initialize_binding(self.context, name, Value::Undefined)?;
+                }
+            }
+            environment
+        };
+
+        // <https://262.ecma-international.org/14.0/#sec-web-compat-functiondeclarationinstantiation>
+        if !self.is_strict {
+            let top_level_lexical_names: HashSet<&str> = lexical_names.iter().copied().collect();
+            let parameter_names: HashSet<&str> = parameter_names.iter().copied().collect();
+            for function in legacy_block_functions(body, &top_level_lexical_names) {
+                let Some(name) = function_name(function) else { continue };
+                if parameter_names.contains(name) {
+                    continue;
+                }
+                if name != "arguments" && instantiated_var_names.insert(name) {
+                    var_environment.create_mutable_binding(self.context, name, false)?;
+                    var_environment.initialize_binding(self.context, name, Value::Undefined)?;
+                }
+                self.code.hoisted_functions.borrow_mut().insert(function.range.clone());
+            }
+        }
+
+        let lexical_environment = if self.is_strict {
+            var_environment.clone()
+        } else {
+            // Direct `eval` calls add `var` bindings between the two.
+            Environment::new_declarative(Some(var_environment.clone()))
+        };
+        self.set_lexical_environment(lexical_environment.clone());
+        for item in body {
+            if let StatementListItem::Declaration(declaration @ (Declaration::LexicalDeclaration(_) | Declaration::ClassDeclaration(_))) = item {
+                let is_constant = matches!(declaration, Declaration::LexicalDeclaration(list) if list.kind == VariableKind::Const);
+                for name in declaration.bound_names() {
+                    if is_constant {
+                        lexical_environment.create_immutable_binding(&name.name, true);
+                    } else {
+                        lexical_environment.create_mutable_binding(self.context, &name.name, false)?;
+                    }
+                }
+            }
+        }
+        for function in functions_to_initialize {
+            let name = function_name(function).expect("declarations are named");
+            let object = self.instantiate_function_object(function, &lexical_environment);
+            var_environment.set_mutable_binding(self.context, name, Value::Object(object), false)?;
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createunmappedargumentsobject>
+    /// without `callee`.
+    fn create_unmapped_arguments_object(&self, arguments: &[Value]) -> Object {
+        let object = Object::new(Some(self.realm().intrinsics().object_prototype.clone()));
+        self.define_arguments(&object, arguments);
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createmappedargumentsobject>:
+    /// an arguments object with each index up to the number of parameters
+    /// kept in sync with the last parameter of its position's name.
+    fn create_mapped_arguments_object(&self, parameter_names: &[&str], arguments: &[Value], environment: &Environment) -> Object {
+        let names: Vec<_> = (0..arguments.len())
+            .map(|index| {
+                let name = parameter_names.get(index)?;
+                (!parameter_names[index + 1..].contains(name)).then(|| (*name).to_owned())
+            })
+            .collect();
+        let prototype = self.realm().intrinsics().object_prototype.clone();
+        // Without mapped arguments, the object is an ordinary one that does
+        // not tie the environment to itself.
+        let object = if names.iter().any(Option::is_some) {
+            let map = ParameterMap::new(environment.clone(), names);
+            Object::with_kind(Some(prototype), ObjectKind::Arguments(Rc::new(map)))
+        } else {
+            Object::new(Some(prototype))
+        };
+        self.define_arguments(&object, arguments);
+        if let Some(callee) = environment.function_object() {
+            object.create_non_enumerable_data_property(PropertyKey::from("callee"), Value::Object(callee.clone()));
+        }
+        object
+    }
+
+    /// Define the argument values, `length` and `@@iterator` of an
+    /// arguments object.
+    fn define_arguments(&self, object: &Object, arguments: &[Value]) {
+        let realm = self.realm();
+        #[allow(clippy::cast_precision_loss)] // argument counts are less than 2^53
+        let length = PropertyDescriptor::data(Value::Number(arguments.len() as f64), true, false, true);
+        assert!(object.define_own_property(PropertyKey::from("length"), length), "a fresh object is extensible");
+        for (index, argument) in arguments.iter().enumerate() {
+            object.create_data_property(index.to_string(), argument.clone());
+        }
+        let values = Value::Object(realm.intrinsics().array_prototype_values.clone());
+        let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::Iterator));
+        let values = PropertyDescriptor::data(values, true, false, true);
+        assert!(object.define_own_property(key, values), "a fresh object is extensible");
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-instantiatefunctionobject>
+    /// of a function declaration.
+    pub(super) fn instantiate_function_object(&self, function: &Rc<Function>, environment: &Environment) -> Object {
+        let code = self.code.function(&**function, || FunctionCode::new(FunctionNode::Function(function.clone()), self.code));
+        let object = self.ordinary_function_create(code, environment.clone(), MethodSlots::default());
+        let name = function.name.as_ref().map_or("default", |name| name.name.as_str());
+        set_function_name(&object, &PropertyKey::from(name), None);
+        if !function.is_generator && !function.is_async {
+            self.make_constructor(&object);
+        }
+        self.define_generator_prototype(&object);
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-instantiateordinaryfunctionexpression>,
+    /// naming an anonymous function `name`.
+    pub(super) fn instantiate_function_expression(&mut self, function: &Rc<Function>, name: Option<&PropertyKey>) -> Object {
+        let code = self.code.function(&**function, || FunctionCode::new(FunctionNode::Function(function.clone()), self.code));
+        let is_constructor = !function.is_generator && !function.is_async;
+        let Some(own_name) = &function.name else {
+            let object = self.ordinary_function_create(code, self.lexical_environment(), MethodSlots::default());
+            set_function_name(&object, &name.cloned().unwrap_or_else(|| PropertyKey::from("")), None);
+            if is_constructor {
+                self.make_constructor(&object);
+            }
+            self.define_generator_prototype(&object);
+            return object;
+        };
+        // The name is visible to the body only.
+        let environment = Environment::new_declarative(Some(self.lexical_environment()));
+        environment.create_immutable_binding(&own_name.name, false);
+        let object = self.ordinary_function_create(code, environment.clone(), MethodSlots::default());
+        set_function_name(&object, &PropertyKey::from(own_name.name.as_str()), None);
+        if is_constructor {
+            self.make_constructor(&object);
+        }
+        self.define_generator_prototype(&object);
+        environment
+            .initialize_binding(self.context, &own_name.name, Value::Object(object.clone()))
+            .expect("a fresh binding is not initialized");
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-instantiatearrowfunctionexpression>
+    pub(super) fn instantiate_arrow_function(&self, function: &Rc<ArrowFunction>, name: Option<&PropertyKey>) -> Object {
+        let code = self.code.function(&**function, || FunctionCode::new(FunctionNode::Arrow(function.clone()), self.code));
+        let object = self.ordinary_function_create(code, self.lexical_environment(), MethodSlots::default());
+        set_function_name(&object, &name.cloned().unwrap_or_else(|| PropertyKey::from("")), None);
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-methoddefinitionevaluation>
+    /// defining a method of an object literal or a class on `object`, its
+    /// `[[HomeObject]]`; only object literal methods are enumerable.
+    pub(super) fn define_method(&mut self, object: &Object, method: &Rc<MethodDefinition>, enumerable: bool) -> Result<(), JsError> {
+        let key = self.evaluate_class_element_name(&method.name)?;
+        self.define_class_method(object, method, key, enumerable).map(|_| ())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-methoddefinitionevaluation>
+    /// defining an object literal method with a key already evaluated.
+    pub(super) fn define_method_with_key(&self, object: &Object, method: &Rc<MethodDefinition>, key: PropertyKey) -> Result<(), JsError> {
+        self.define_class_method(object, method, ClassElementKey::Property(key), true).map(|_| ())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-methoddefinitionevaluation>
+    /// defining a method with a name already evaluated, returning a private
+    /// one as a `PrivateElement` instead.
+    fn define_class_method(
+        &self,
+        object: &Object,
+        method: &Rc<MethodDefinition>,
+        key: ClassElementKey,
+        enumerable: bool,
+    ) -> Result<Option<PrivateElement>, JsError> {
+        let code = self.code.function(&**method, || FunctionCode::new(FunctionNode::Method(method.clone()), self.code));
+        let slots = MethodSlots { home_object: Some(object.clone()), ..MethodSlots::default() };
+        let closure = self.ordinary_function_create(code, self.lexical_environment(), slots);
+        let prefix = match method.kind {
+            MethodKind::Getter => Some("get"),
+            MethodKind::Setter => Some("set"),
+            _ => None,
+        };
+        let name = match &key {
+            ClassElementKey::Property(key) => key.clone(),
+            ClassElementKey::Private(name) => PropertyKey::from(name.description()),
+        };
+        set_function_name(&closure, &name, prefix);
+        self.define_generator_prototype(&closure);
+        let closure = Value::Object(closure);
+        let key = match key {
+            ClassElementKey::Property(key) => key,
+            ClassElementKey::Private(key) => {
+                let kind = match method.kind {
+                    MethodKind::Getter => PrivateElementKind::Accessor { get: Some(closure), set: None },
+                    MethodKind::Setter => PrivateElementKind::Accessor { get: None, set: Some(closure) },
+                    _ => PrivateElementKind::Method(closure),
+                };
+                return Ok(Some(PrivateElement { key, kind }));
+            },
+        };
+        let configurable = Some(true);
+        let descriptor = match method.kind {
+            MethodKind::Getter => {
+                PropertyDescriptor { get: Some(closure), enumerable: Some(enumerable), configurable, ..PropertyDescriptor::default() }
+            },
+            MethodKind::Setter => {
+                PropertyDescriptor { set: Some(closure), enumerable: Some(enumerable), configurable, ..PropertyDescriptor::default() }
+            },
+            _ => PropertyDescriptor::data(closure, true, enumerable, true),
+        };
+        // A computed name of a static method may be a non-configurable
+        // `prototype`.
+        if !object.define_own_property(key.clone(), descriptor) {
+            return Err(JsError::type_error(format!("cannot redefine property {key}")));
+        }
+        Ok(None)
+    }
+
+    /// A property key of a class element or an object literal method, or
+    /// a private name resolved in the running `PrivateEnvironment`.
+    fn evaluate_class_element_name(&mut self, name: &ClassElementName) -> Result<ClassElementKey, JsError> {
+        match name {
+            ClassElementName::PropertyName(name) => self.evaluate_property_name(name).map(ClassElementKey::Property),
+            ClassElementName::PrivateIdentifier(identifier, _) => Ok(ClassElementKey::Private(self.resolve_private_identifier(identifier))),
+        }
+    }
+
+    /// A key of a class element, taking a computed one from keys evaluated
+    /// beforehand if there are any.
+    fn class_element_key(
+        &mut self,
+        name: &ClassElementName,
+        computed_keys: Option<&mut vec::IntoIter<PropertyKey>>,
+    ) -> Result<ClassElementKey, JsError> {
+        match (name, computed_keys) {
+            (ClassElementName::PropertyName(PropertyName::ComputedPropertyName(..)), Some(keys)) => {
+                Ok(ClassElementKey::Property(keys.next().expect("computed keys are evaluated for each computed name")))
+            },
+            (name, _) => self.evaluate_class_element_name(name),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-resolve-private-identifier>
+    /// in the running execution context.
+    pub(super) fn resolve_private_identifier(&self, identifier: &PrivateIdentifier) -> PrivateName {
+        let environment = self.running_execution_context().private_environment.as_ref();
+        environment
+            .and_then(|environment| environment.resolve(&identifier.string_value()))
+            .expect("early errors check that private names are declared")
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-classdefinitionevaluation>
+    /// of a class named `name`, binding its own name, if any, in the class
+    /// scope.
+    ///
+    /// Decorators are not supported.
+    pub(super) fn evaluate_class(&mut self, class: &Class, name: &PropertyKey) -> Result<Object, JsError> {
+        if !class.decorators.is_empty() {
+            return Err(JsError::unsupported("decorators"));
+        }
+        let class_environment = self.new_class_environment(class);
+        self.with_lexical_environment(class_environment, |evaluator| {
+            let heritage = class.heritage.as_ref().map(|heritage| evaluator.evaluate(heritage)).transpose()?;
+            let parents = evaluator.class_parents(heritage)?;
+            let private_environment = evaluator.new_class_private_environment(class);
+            evaluator.with_private_environment(private_environment, |evaluator| evaluator.evaluate_class_body(class, name, parents, None))
+        })
+    }
+
+    /// A scope of a class with its own name, if any, not initialized yet.
+    pub(super) fn new_class_environment(&self, class: &Class) -> Environment {
+        let class_environment = Environment::new_declarative(Some(self.lexical_environment()));
+        if let Some(binding) = &class.name {
+            class_environment.create_immutable_binding(&binding.name, true);
+        }
+        class_environment
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newprivateenvironment>
+    /// of a class body in the running one; the heritage resolves private
+    /// names of enclosing classes only, so it is evaluated before.
+    pub(super) fn new_class_private_environment(&self, class: &Class) -> PrivateEnvironment {
+        let outer_private_environment = self.running_execution_context().private_environment.clone();
+        PrivateEnvironment::new(outer_private_environment, class.elements.iter().filter_map(private_bound_identifier))
+    }
+
+    /// Steps of
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-classdefinitionevaluation>
+    /// giving parents of the prototype and the constructor of a class with
+    /// the value of its heritage, if any.
+    pub(super) fn class_parents(&mut self, heritage: Option<Value>) -> Result<(Option<Object>, Object), JsError> {
+        let realm = self.realm();
+        Ok(match heritage {
+            None => (Some(realm.intrinsics().object_prototype.clone()), realm.intrinsics().function_prototype.clone()),
+            Some(Value::Null) => (None, realm.intrinsics().function_prototype.clone()),
+            Some(Value::Object(superclass)) if superclass.is_constructor() => {
+                let prototype = superclass.get(self.context, &PropertyKey::from("prototype"), &Value::Object(superclass.clone()))?;
+                match prototype {
+                    Value::Object(prototype) => (Some(prototype), superclass),
+                    Value::Null => (None, superclass),
+                    _ => return Err(JsError::type_error("prototype of the parent class is neither an object nor null")),
+                }
+            },
+            Some(_) => return Err(JsError::type_error("the parent class is not a constructor")),
+        })
+    }
+
+    /// Steps of
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-classdefinitionevaluation>
+    /// after evaluation of the heritage, running in the class scope and
+    /// the `PrivateEnvironment` of the class body with the parents the
+    /// heritage gave; computed keys of elements are taken in order from
+    /// `computed_keys` if they are already evaluated.
+    pub(super) fn evaluate_class_body(
+        &mut self,
+        class: &Class,
+        name: &PropertyKey,
+        (prototype_parent, constructor_parent): (Option<Object>, Object),
+        computed_keys: Option<Vec<PropertyKey>>,
+    ) -> Result<Object, JsError> {
+        let class_environment = self.lexical_environment();
+        let prototype = Object::new(prototype_parent);
+        let constructor = class.elements.iter().find_map(|element| match element {
+            ClassElement::MethodDefinition { is_static: false, method, .. } if is_constructor_name(&method.name) => Some(method),
+            _ => None,
+        });
+        let code = match constructor {
+            Some(method) => self.code.function(&**method, || FunctionCode::new(FunctionNode::Method(method.clone()), self.code)),
+            None => self.code.function(class, || FunctionCode::new(FunctionNode::DefaultConstructor, self.code)),
+        };
+        let slots = MethodSlots {
+            home_object: Some(prototype.clone()),
+            class: Some(ClassConstructor {
+                is_derived: class.heritage.is_some(),
+                fields: RefCell::default(),
+                private_methods: RefCell::default(),
+            }),
+            ..MethodSlots::default()
+        };
+        let function = self.ordinary_function_create(code, class_environment.clone(), slots);
+        assert!(function.set_prototype_of(Some(constructor_parent)), "a fresh object is extensible");
+        set_function_name(&function, name, None);
+        let descriptor = PropertyDescriptor::data(Value::Object(prototype.clone()), false, false, false);
+        assert!(function.define_own_property(PropertyKey::from("prototype"), descriptor), "a fresh object is extensible");
+        let descriptor = PropertyDescriptor::data(Value::Object(function.clone()), true, false, true);
+        assert!(prototype.define_own_property(PropertyKey::from("constructor"), descriptor), "a fresh object is extensible");
+
+        let elements = self.evaluate_class_elements(class, constructor, &prototype, &function, computed_keys)?;
+        if let Some(binding) = &class.name {
+            class_environment.initialize_binding(self.context, &binding.name, Value::Object(function.clone()))?;
+        }
+        let ObjectKind::Function(data) = function.kind() else {
+            unreachable!("a class constructor is a function");
+        };
+        let class_slots = data.method.class.as_ref().expect("a class constructor has class slots");
+        class_slots.fields.replace(elements.instance_fields);
+        class_slots.private_methods.replace(elements.private_methods);
+        for method in elements.static_private_methods {
+            function.private_element_add(method)?;
+        }
+        for element in elements.static_elements {
+            match element {
+                StaticElement::Field(field) => define_field(self.context, &function, &field)?,
+                StaticElement::Block(block) => {
+                    block.call(self.context, &Value::Object(function.clone()), &[])?;
+                },
+            }
+        }
+        Ok(function)
+    }
+
+    /// Define methods of a class body and create initializers of its
+    /// fields and static blocks, skipping the `constructor` method.
+    fn evaluate_class_elements(
+        &mut self,
+        class: &Class,
+        constructor: Option<&Rc<MethodDefinition>>,
+        prototype: &Object,
+        function: &Object,
+        computed_keys: Option<Vec<PropertyKey>>,
+    ) -> Result<ClassElements, JsError> {
+        let mut computed_keys = computed_keys.map(Vec::into_iter);
+        let mut elements = ClassElements {
+            instance_fields: Vec::new(),
+            private_methods: Vec::new(),
+            static_private_methods: Vec::new(),
+            static_elements: Vec::new(),
+        };
+        for element in &class.elements {
+            match element {
+                ClassElement::MethodDefinition { method, .. } if constructor.is_some_and(|constructor| Rc::ptr_eq(constructor, method)) => {},
+                ClassElement::MethodDefinition { is_static, method, .. } => {
+                    let key = self.class_element_key(&method.name, computed_keys.as_mut())?;
+                    if let Some(method) = self.define_class_method(if *is_static { function } else { prototype }, method, key, false)? {
+                        let container = if *is_static { &mut elements.static_private_methods } else { &mut elements.private_methods };
+                        add_private_method(container, method);
+                    }
+                },
+                ClassElement::FieldDefinition { is_static, name, initializer, .. } => {
+                    let home_object = if *is_static { function } else { prototype };
+                    let name = self.class_element_key(name, computed_keys.as_mut())?;
+                    let function_name = match &name {
+                        ClassElementKey::Property(key) => key.clone(),
+                        ClassElementKey::Private(name) => PropertyKey::from(name.description()),
+                    };
+                    let initializer = initializer.as_ref().map(|initializer| {
+                        let code = self.code.function(&**initializer, || FunctionCode::new(FunctionNode::Initializer(initializer.clone()), self.code));
+                        let slots = MethodSlots {
+                            home_object: Some(home_object.clone()),
+                            class_field_initializer_name: Some(function_name),
+                            class: None,
+                        };
+                        self.ordinary_function_create(code, self.lexical_environment(), slots)
+                    });
+                    let field = ClassField { name, initializer };
+                    if *is_static {
+                        elements.static_elements.push(StaticElement::Field(field));
+                    } else {
+                        elements.instance_fields.push(field);
+                    }
+                },
+                ClassElement::ClassStaticBlock(statements, _) => {
+                    let code = self.code.function(&**statements, || FunctionCode::new(FunctionNode::StaticBlock(statements.clone()), self.code));
+                    let slots = MethodSlots { home_object: Some(function.clone()), ..MethodSlots::default() };
+                    let block = self.ordinary_function_create(code, self.lexical_environment(), slots);
+                    elements.static_elements.push(StaticElement::Block(block));
+                },
+            }
+        }
+        Ok(elements)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryfunctioncreate>
+    /// with `%Function.prototype%` as the prototype.
+    fn ordinary_function_create(&self, code: Rc<FunctionCode>, environment: Environment, method: MethodSlots) -> Object {
+        let realm = self.realm();
+        let this_mode = if code.is_arrow() {
+            ThisMode::Lexical
+        } else if code.is_strict() {
+            ThisMode::Strict
+        } else {
+            ThisMode::Global
+        };
+        #[allow(clippy::cast_precision_loss)] // parameter counts are less than 2^53
+        let length = Value::Number(code.expected_argument_count() as f64);
+        let is_constructor = method.class.is_some() || code.is_constructor();
+        let prototype = if code.is_generator() {
+            realm.intrinsics().generator_function_prototype.clone()
+        } else {
+            realm.intrinsics().function_prototype.clone()
+        };
+        let private_environment = self.running_execution_context().private_environment.clone();
+        let data = FunctionData { code, environment, private_environment, realm, this_mode, is_constructor, method };
+        let function = Object::with_kind(Some(prototype), ObjectKind::Function(Rc::new(data)));
+        let length = PropertyDescriptor::data(length, false, false, true);
+        assert!(function.define_own_property(PropertyKey::from("length"), length), "a fresh object is extensible");
+        function
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-makeconstructor>
+    /// with a fresh `prototype` object.
+    fn make_constructor(&self, function: &Object) {
+        let prototype = Object::new(Some(self.realm().intrinsics().object_prototype.clone()));
+        let constructor = PropertyDescriptor::data(Value::Object(function.clone()), true, false, true);
+        assert!(prototype.define_own_property(PropertyKey::from("constructor"), constructor), "a fresh object is extensible");
+        let prototype = PropertyDescriptor::data(Value::Object(prototype), true, false, false);
+        assert!(function.define_own_property(PropertyKey::from("prototype"), prototype), "a fresh object is extensible");
+    }
+
+    /// Give a generator function a fresh `prototype` object inheriting
+    /// `%GeneratorFunction.prototype.prototype%`, leaving other functions
+    /// alone.
+    fn define_generator_prototype(&self, function: &Object) {
+        let ObjectKind::Function(data) = function.kind() else {
+            unreachable!("ordinary functions have function data");
+        };
+        if !data.code.is_generator() {
+            return;
+        }
+        let prototype = Object::new(Some(self.realm().intrinsics().generator_prototype.clone()));
+        let prototype = PropertyDescriptor::data(Value::Object(prototype), true, false, false);
+        assert!(function.define_own_property(PropertyKey::from("prototype"), prototype), "a fresh object is extensible");
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-setfunctionname>
+fn set_function_name(function: &Object, name: &PropertyKey, prefix: Option<&str>) {
+    let name = match name {
+        PropertyKey::String(name) => name.to_string(),
+        PropertyKey::Symbol(symbol) => symbol.description().map_or_else(String::new, |description| format!("[{description}]")),
+    };
+    let name = prefix.map_or_else(|| name.clone(), |prefix| format!("{prefix} {name}"));
+    let descriptor = PropertyDescriptor::data(Value::from(name), false, false, true);
+    assert!(function.define_own_property(PropertyKey::from("name"), descriptor), "a fresh function has no name");
+}
+
+/// Instance fields and static elements of a class body in source order,
+/// with private methods apart.
+struct ClassElements {
+    instance_fields: Vec<ClassField>,
+    private_methods: Vec<PrivateElement>,
+    static_private_methods: Vec<PrivateElement>,
+    static_elements: Vec<StaticElement>,
+}
+
+/// Add a private method or accessor to `container`, joining a getter and
+/// a setter of the same name into one accessor.
+fn add_private_method(container: &mut Vec<PrivateElement>, method: PrivateElement) {
+    let Some(existing) = container.iter_mut().find(|existing| existing.key == method.key) else {
+        container.push(method);
+        return;
+    };
+    if let (
+        PrivateElementKind::Accessor { get, set },
+        PrivateElementKind::Accessor { get: other_get, set: other_set },
+    ) = (&mut existing.kind, method.kind)
+    {
+        *get = get.take().or(other_get);
+        *set = set.take().or(other_set);
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-static-semantics-privateboundidentifiers>
+/// of a class element.
+fn private_bound_identifier(element: &ClassElement) -> Option<String> {
+    match element {
+        ClassElement::MethodDefinition { method, .. } => match &method.name {
+            ClassElementName::PrivateIdentifier(name, _) => Some(name.string_value()),
+            _ => None,
+        },
+        ClassElement::FieldDefinition { name: ClassElementName::PrivateIdentifier(name, _), .. } => Some(name.string_value()),
+        _ => None,
+    }
+}
+
+/// A static field or a static block, evaluated once the class binding is
+/// initialized.
+enum StaticElement {
+    Field(ClassField),
+    Block(Object),
+}
+
+/// Whether a `ClassElementName` is `constructor`.
+fn is_constructor_name(name: &ClassElementName) -> bool {
+    match name {
+        ClassElementName::PropertyName(PropertyName::IdentifierName(name, _)) => name == "constructor",
+        ClassElementName::PropertyName(PropertyName::StringLiteral(literal, _)) => literal.string_value() == "constructor",
+        _ => false,
+    }
+}
+
+fn function_name(function: &Function) -> Option<&str> {
+    function.name.as_ref().map(|name| name.name.as_str())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-static-semantics-containsexpression>
+/// of formal parameters.
+fn contains_expression(parameters: &FormalParameters) -> bool {
+    fn binding_contains_expression(binding: &Binding) -> bool {
+        match binding {
+            Binding::BindingIdentifier(_) => false,
+            Binding::ObjectBindingPattern(pattern) => pattern.properties.iter().any(|property| match property {
+                BindingProperty::SingleNameBinding { initializer, .. } => initializer.is_some(),
+                BindingProperty::Property { name, value } => {
+                    matches!(name, PropertyName::ComputedPropertyName(..))
+                        || value.initializer.is_some()
+                        || binding_contains_expression(&value.target)
+                },
+            }),
+            Binding::ArrayBindingPattern(pattern) => {
+                pattern.elements.iter().flatten().any(|element| {
+                    element.initializer.is_some() || binding_contains_expression(&element.target)
+                }) || pattern.rest.as_deref().is_some_and(binding_contains_expression)
+            },
+        }
+    }
+    parameters.parameters.iter().any(|parameter| {
+        parameter.initializer.is_some() || binding_contains_expression(&parameter.target)
+    }) || parameters.rest.as_ref().is_some_and(binding_contains_expression)
+}
+
+/// Function declarations directly in a script or a function body, including
+/// labelled ones, that its instantiation binds: the last one of each name,
+/// in source order.
+pub(super) fn top_level_functions(body: &[StatementListItem]) -> Vec<&Rc<Function>> {
+    let mut functions = Vec::new();
+    for item in body {
+        let function = match item {
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => function,
+            StatementListItem::Statement(Statement::LabelledStatement(statement)) => {
+                let mut item = &*statement.body;
+                while let LabelledItem::Statement(Statement::LabelledStatement(statement)) = item {
+                    item = &statement.body;
+                }
+                let LabelledItem::FunctionDeclaration(function) = item else { continue };
+                function
+            },
+            StatementListItem::Statement(_) | StatementListItem::Declaration(_) => continue,
+        };
+        functions.push(function);
+    }
+    let mut seen = HashSet::new();
+    let mut unique: Vec<&Rc<Function>> = functions.into_iter().rev().filter(|function| seen.insert(function_name(function))).collect();
+    unique.reverse();
+    unique
+}
+
+/// Function declarations in blocks and `switch` cases of a script or
+/// a function body that
+/// <https://262.ecma-international.org/14.0/#sec-block-level-function-declarations-web-legacy-compatibility-semantics>
+/// also binds as `var`: ones whose replacement by `var` would not conflict
+/// with lexical declarations around them or `lexical_names` of the body.
+pub(super) fn legacy_block_functions<'a>(body: &'a [StatementListItem], lexical_names: &HashSet<&'a str>) -> Vec<&'a Function> {
+    let mut walker = LegacyFunctionWalker { scopes: vec![lexical_names.iter().copied().collect()], functions: Vec::new() };
+    walker.statement_list(body, false);
+    walker.functions
+}
+
+struct LegacyFunctionWalker<'a> {
+    /// Lexically declared names of scopes enclosing the current one.
+    scopes: Vec<HashSet<&'a str>>,
+    functions: Vec<&'a Function>,
+}
+
+impl<'a> LegacyFunctionWalker<'a> {
+    fn with_scope(&mut self, names: HashSet<&'a str>, walk: impl FnOnce(&mut Self)) {
+        self.scopes.push(names);
+        walk(self);
+        self.scopes.pop();
+    }
+
+    /// Walk statements, collecting legacy functions directly in them if
+    /// they are a block or a case block.
+    fn statement_list(&mut self, statements: &'a [StatementListItem], is_block: bool) {
+        for item in statements {
+            match item {
+                StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) if is_block => {
+                    // The block itself declares the name, so only enclosing
+                    // scopes may conflict.
+                    let is_conflicting = function_name(function).is_some_and(|name| {
+                        self.scopes[..self.scopes.len() - 1].iter().any(|scope| scope.contains(name))
+                    });
+                    if function.is_legacy_block_function && !is_conflicting {
+                        self.functions.push(function);
+                    }
+                },
+                StatementListItem::Statement(statement) => self.statement(statement),
+                StatementListItem::Declaration(_) => {},
+            }
+        }
+    }
+
+    fn block(&mut self, block: &'a Block) {
+        let names = block.lexically_declared_names().into_iter().map(|name| name.name.as_str()).collect();
+        self.with_scope(names, |walker| walker.statement_list(&block.statements, true));
+    }
+
+    fn statement(&mut self, statement: &'a Statement) {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::BlockStatement(block) => self.block(block),
+                Statement::IfStatement(statement) => {
+                    self.statement(&statement.consequent);
+                    if let Some(alternative) = &statement.alternative {
+                        self.statement(alternative);
+                    }
+                },
+                Statement::DoWhileStatement(statement) => self.statement(&statement.body),
+                Statement::WhileStatement(statement) => self.statement(&statement.body),
+                Statement::WithStatement(statement) => self.statement(&statement.body),
+                Statement::ForStatement(statement) => {
+                    let names = match &statement.init {
+                        Some(ForInit::VariableDeclarationList(list)) if list.kind != VariableKind::Var => {
+                            list.bound_names().into_iter().map(|name| name.name.as_str()).collect()
+                        },
+                        _ => HashSet::new(),
+                    };
+                    self.with_scope(names, |walker| walker.statement(&statement.body));
+                },
+                Statement::ForInStatement(statement) | Statement::ForOfStatement(statement) => {
+                    let names = match &statement.target {
+                        ForTarget::ForDeclaration { kind, binding, .. } if *kind != VariableKind::Var => {
+                            binding.bound_names().into_iter().map(|name| name.name.as_str()).collect()
+                        },
+                        _ => HashSet::new(),
+                    };
+                    self.with_scope(names, |walker| walker.statement(&statement.body));
+                },
+                Statement::SwitchStatement(statement) => {
+                    let names = statement.lexically_declared_names().into_iter().map(|name| name.name.as_str()).collect();
+                    let statements = &statement.cases;
+                    self.with_scope(names, |walker| {
+                        for case in statements {
+                            walker.statement_list(&case.statements, true);
+                        }
+                    });
+                },
+                Statement::LabelledStatement(statement) => {
+                    if let LabelledItem::Statement(statement) = &*statement.body {
+                        self.statement(statement);
+                    }
+                },
+                Statement::TryStatement(statement) => {
+                    self.block(&statement.block);
+                    if let Some(handler) = &statement.handler {
+                        // A `var` may redeclare a simple catch parameter.
+                        let names = match &handler.parameter {
+                            Some(parameter @ (Binding::ObjectBindingPattern(_) | Binding::ArrayBindingPattern(_))) => {
+                                parameter.bound_names().into_iter().map(|name| name.name.as_str()).collect()
+                            },
+                            _ => HashSet::new(),
+                        };
+                        self.with_scope(names, |walker| walker.block(&handler.body));
+                    }
+                    if let Some(finalizer) = &statement.finalizer {
+                        self.block(finalizer);
+                    }
+                },
+                _ => {},
+            }
+        });
+    }
+}
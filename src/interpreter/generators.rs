@@ -0,0 +1,99 @@
+//! <https://262.ecma-international.org/14.0/#sec-generator-objects>
+
+use super::resumable::{Frame, Resumption, Suspension};
+use crate::runtime::{create_iter_result_object, Context, JsError, Object, ObjectKind, Tracer, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// `[[GeneratorState]]` with the suspended evaluation of the body.
+enum GeneratorState {
+    SuspendedStart(Frame),
+    SuspendedYield(Frame),
+    Executing,
+    Completed,
+}
+
+/// Internal slots of a generator object.
+pub struct Generator {
+    state: RefCell<GeneratorState>,
+}
+
+impl Generator {
+    /// A generator object suspended at the start of the body of `frame`.
+    pub(super) fn create(prototype: Object, frame: Frame) -> Object {
+        let generator = Self { state: RefCell::new(GeneratorState::SuspendedStart(frame)) };
+        Object::with_kind(Some(prototype), ObjectKind::Generator(Rc::new(generator)))
+    }
+
+    /// Report the suspended evaluation to the heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        if let GeneratorState::SuspendedStart(frame) | GeneratorState::SuspendedYield(frame) = &*self.state.borrow() {
+            frame.trace(tracer);
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-generator.prototype.next>
+    pub(crate) fn next(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let value = arguments.first().cloned().unwrap_or(Value::Undefined);
+        resume(context, this, Resumption::Next(value))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-generator.prototype.return>
+    pub(crate) fn r#return(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let value = arguments.first().cloned().unwrap_or(Value::Undefined);
+        resume(context, this, Resumption::Return(value))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-generator.prototype.throw>
+    pub(crate) fn throw(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let value = arguments.first().cloned().unwrap_or(Value::Undefined);
+        resume(context, this, Resumption::Throw(value))
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-generatorresume> and
+/// <https://262.ecma-international.org/14.0/#sec-generatorresumeabrupt>
+/// after <https://262.ecma-international.org/14.0/#sec-generatorvalidate>.
+fn resume(context: &mut Context, this: &Value, resumption: Resumption) -> Result<Value, JsError> {
+    let Some(ObjectKind::Generator(generator)) = this.as_object().map(Object::kind) else {
+        return Err(JsError::type_error("not a generator"));
+    };
+    let state = generator.state.replace(GeneratorState::Executing);
+    let (mut frame, resumption) = match (state, resumption) {
+        (GeneratorState::Executing, _) => return Err(JsError::type_error("generator is already running")),
+        // A generator that has not started completes without running.
+        (GeneratorState::SuspendedStart(_) | GeneratorState::Completed, Resumption::Return(value)) => {
+            generator.state.replace(GeneratorState::Completed);
+            return Ok(create_iter_result_object(context, value, true));
+        },
+        (GeneratorState::SuspendedStart(_) | GeneratorState::Completed, Resumption::Throw(value)) => {
+            generator.state.replace(GeneratorState::Completed);
+            return Err(JsError::thrown(value));
+        },
+        (GeneratorState::Completed, _) => {
+            generator.state.replace(GeneratorState::Completed);
+            return Ok(create_iter_result_object(context, Value::Undefined, true));
+        },
+        (GeneratorState::SuspendedStart(frame), _) => (frame, Resumption::Start),
+        (GeneratorState::SuspendedYield(frame), resumption) => (frame, resumption),
+    };
+    match frame.resume(context, resumption) {
+        Ok(Suspension::Yield(value)) => {
+            generator.state.replace(GeneratorState::SuspendedYield(frame));
+            Ok(create_iter_result_object(context, value, false))
+        },
+        Ok(Suspension::YieldResult(result)) => {
+            generator.state.replace(GeneratorState::SuspendedYield(frame));
+            Ok(result)
+        },
+        Ok(Suspension::Await(_)) => unreachable!("generators do not await"),
+        Ok(Suspension::Return(value)) => {
+            generator.state.replace(GeneratorState::Completed);
+            Ok(create_iter_result_object(context, value, true))
+        },
+        Err(error) => {
+            generator.state.replace(GeneratorState::Completed);
+            Err(error)
+        },
+    }
+}
@@ -0,0 +1,526 @@
+//! <https://262.ecma-international.org/14.0/#sec-source-text-module-records>
+//! linking and evaluation.
+//!
+//! Modules are loaded up front by the host, so the graph is complete before
+//! linking starts, and evaluation is synchronous since top-level `await` is
+//! not supported yet.
+
+use super::{Code, Evaluator};
+use crate::grammar::{
+    Declaration,
+    ExportDeclaration,
+    ExportEntry,
+    ImportEntry,
+    ImportName,
+    Module,
+    ModuleItem,
+    StatementListItem,
+    VariableKind,
+    DEFAULT_EXPORT_NAME,
+};
+use crate::runtime::{
+    Context,
+    Environment,
+    ErrorKind,
+    ExecutionContext,
+    JsError,
+    ModuleExport,
+    ModuleNamespace,
+    Object,
+    PropertyKey,
+    Realm,
+    Value,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+
+/// `[[Status]]` of a Cyclic Module Record, without `new` since modules are
+/// loaded when their records are created.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Status {
+    Unlinked,
+    Linking,
+    Linked,
+    Evaluating,
+    Evaluated,
+}
+
+/// What <https://262.ecma-international.org/14.0/#sec-resolveexport>
+/// finds for a name.
+enum Resolution {
+    /// A binding in the environment of a module.
+    Binding(ModuleRecord, String),
+    /// The namespace object of a module.
+    Namespace(ModuleRecord),
+    /// No export of the name, or a circular import of it.
+    NotFound,
+    /// Different bindings exported by `export *` of different modules.
+    Ambiguous,
+}
+
+impl Resolution {
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Binding(module, name), Self::Binding(other_module, other_name)) => module == other_module && name == other_name,
+            (Self::Namespace(module), Self::Namespace(other_module)) => module == other_module,
+            _ => false,
+        }
+    }
+}
+
+struct ModuleData {
+    /// A specifier the host loaded the module by.
+    specifier: String,
+    realm: Realm,
+    module: Module,
+    /// `[[Environment]]`, created with the record so other modules can
+    /// refer to it before the module is linked.
+    environment: Environment,
+    /// `[[Namespace]]`
+    namespace: RefCell<Option<Object>>,
+    status: Cell<Status>,
+    /// `[[DFSIndex]]`
+    dfs_index: Cell<usize>,
+    /// `[[DFSAncestorIndex]]`
+    dfs_ancestor_index: Cell<usize>,
+    /// `[[LoadedModules]]` by specifier.
+    loaded_modules: RefCell<HashMap<String, ModuleRecord>>,
+    /// `[[EvaluationError]]`
+    evaluation_error: RefCell<Option<JsError>>,
+    /// `[[ImportEntries]]`
+    import_entries: Vec<ImportEntry>,
+    /// `[[LocalExportEntries]]`
+    local_export_entries: Vec<ExportEntry>,
+    /// `[[IndirectExportEntries]]`
+    indirect_export_entries: Vec<ExportEntry>,
+    /// `[[StarExportEntries]]`
+    star_export_entries: Vec<ExportEntry>,
+    code: Code,
+}
+
+/// A Source Text Module Record; clones refer to the same record.
+#[derive(Clone)]
+pub struct ModuleRecord(Rc<ModuleData>);
+
+impl ModuleRecord {
+    /// Create a record of a parsed module as
+    /// <https://262.ecma-international.org/14.0/#sec-parsemodule> does.
+    ///
+    /// Deferred function bodies of the module are parsed from `source` on
+    /// the first call.
+    #[must_use]
+    pub fn new(specifier: &str, realm: &Realm, module: Module, source: Option<Rc<str>>) -> Self {
+        let import_entries = module.import_entries();
+        let mut local_export_entries = Vec::new();
+        let mut indirect_export_entries = Vec::new();
+        let mut star_export_entries = Vec::new();
+        for entry in module.export_entries() {
+            if entry.module_request.is_none() {
+                let local_name = entry.local_name.as_deref();
+                let import = import_entries.iter().find(|import| Some(import.local_name.as_str()) == local_name);
+                match import {
+                    // A re-exported namespace import is a local binding.
+                    None | Some(ImportEntry { import_name: ImportName::NamespaceObject, .. }) => local_export_entries.push(entry),
+                    Some(import) => indirect_export_entries.push(ExportEntry {
+                        export_name: entry.export_name,
+                        module_request: Some(import.module_request.clone()),
+                        import_name: Some(import.import_name.clone()),
+                        local_name: None,
+                    }),
+                }
+            } else if entry.import_name == Some(ImportName::AllButDefault) {
+                star_export_entries.push(entry);
+            } else {
+                indirect_export_entries.push(entry);
+            }
+        }
+        Self(Rc::new(ModuleData {
+            specifier: specifier.to_owned(),
+            realm: realm.clone(),
+            environment: Environment::new_module(realm.global_environment().clone()),
+            module,
+            namespace: RefCell::new(None),
+            status: Cell::new(Status::Unlinked),
+            dfs_index: Cell::new(0),
+            dfs_ancestor_index: Cell::new(0),
+            loaded_modules: RefCell::default(),
+            evaluation_error: RefCell::new(None),
+            import_entries,
+            local_export_entries,
+            indirect_export_entries,
+            star_export_entries,
+            code: Code::with_source(source),
+        }))
+    }
+
+    #[must_use]
+    pub fn specifier(&self) -> &str {
+        &self.0.specifier
+    }
+
+    /// `[[RequestedModules]]`
+    #[must_use]
+    pub fn requested_modules(&self) -> Vec<&str> {
+        self.0.module.module_requests()
+    }
+
+    /// A module loaded for a specifier the module requests.
+    #[must_use]
+    pub fn loaded_module(&self, specifier: &str) -> Option<Self> {
+        self.0.loaded_modules.borrow().get(specifier).cloned()
+    }
+
+    /// Record a module loaded for a specifier the module requests.
+    pub fn set_loaded_module(&self, specifier: &str, module: Self) {
+        self.0.loaded_modules.borrow_mut().insert(specifier.to_owned(), module);
+    }
+
+    fn required_module(&self, specifier: &str) -> Self {
+        self.loaded_module(specifier).expect("requested modules are loaded before linking")
+    }
+
+    /// `[[EvaluationError]]`: an exception the module or one of its
+    /// dependencies threw when it was evaluated.
+    #[must_use]
+    pub fn evaluation_error(&self) -> Option<JsError> {
+        self.0.evaluation_error.borrow().clone()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-moduledeclarationlinking>
+    /// of the module and the modules it depends on.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `SyntaxError` for an import that no module
+    /// exports or that is ambiguous. Modules linked by the call go back
+    /// to being unlinked then.
+    pub fn link(&self, context: &mut Context) -> Result<(), JsError> {
+        let mut stack = Vec::new();
+        if let Err(error) = self.inner_link(context, &mut stack, 0) {
+            for module in stack {
+                module.0.status.set(Status::Unlinked);
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-InnerModuleLinking>
+    fn inner_link(&self, context: &mut Context, stack: &mut Vec<Self>, index: usize) -> Result<usize, JsError> {
+        if self.0.status.get() != Status::Unlinked {
+            return Ok(index);
+        }
+        self.0.status.set(Status::Linking);
+        self.0.dfs_index.set(index);
+        self.0.dfs_ancestor_index.set(index);
+        let mut index = index + 1;
+        stack.push(self.clone());
+        for specifier in self.requested_modules() {
+            let required = self.required_module(specifier);
+            index = required.inner_link(context, stack, index)?;
+            if required.0.status.get() == Status::Linking {
+                let ancestor_index = self.0.dfs_ancestor_index.get().min(required.0.dfs_ancestor_index.get());
+                self.0.dfs_ancestor_index.set(ancestor_index);
+            }
+        }
+        self.initialize_environment(context)?;
+        if self.0.dfs_ancestor_index.get() == self.0.dfs_index.get() {
+            while let Some(module) = stack.pop() {
+                module.0.status.set(Status::Linked);
+                if module == *self {
+                    break;
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-moduleevaluation>
+    /// of the module after the modules it depends on, each once.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the module or one of its
+    /// dependencies throws; evaluating them again gives the same error.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the module is not linked.
+    pub fn evaluate(&self, context: &mut Context) -> Result<(), JsError> {
+        let mut stack = Vec::new();
+        if let Err(error) = self.inner_evaluate(context, &mut stack, 0) {
+            for module in stack {
+                module.0.status.set(Status::Evaluated);
+                module.0.evaluation_error.replace(Some(error.clone()));
+            }
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-innermoduleevaluation>
+    fn inner_evaluate(&self, context: &mut Context, stack: &mut Vec<Self>, index: usize) -> Result<usize, JsError> {
+        match self.0.status.get() {
+            Status::Evaluated => return self.0.evaluation_error.borrow().clone().map_or(Ok(index), Err),
+            Status::Evaluating => return Ok(index),
+            Status::Linked => {},
+            Status::Unlinked | Status::Linking => panic!("module {} is evaluated before it is linked", self.0.specifier),
+        }
+        self.0.status.set(Status::Evaluating);
+        self.0.dfs_index.set(index);
+        self.0.dfs_ancestor_index.set(index);
+        let mut index = index + 1;
+        stack.push(self.clone());
+        for specifier in self.requested_modules() {
+            let required = self.required_module(specifier);
+            index = required.inner_evaluate(context, stack, index)?;
+            if required.0.status.get() == Status::Evaluating {
+                let ancestor_index = self.0.dfs_ancestor_index.get().min(required.0.dfs_ancestor_index.get());
+                self.0.dfs_ancestor_index.set(ancestor_index);
+            }
+        }
+        self.execute(context)?;
+        if self.0.dfs_ancestor_index.get() == self.0.dfs_index.get() {
+            while let Some(module) = stack.pop() {
+                module.0.status.set(Status::Evaluated);
+                if module == *self {
+                    break;
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getexportednames>
+    fn exported_names(&self, export_star_set: &mut Vec<Self>) -> Vec<String> {
+        if export_star_set.contains(self) {
+            return Vec::new();
+        }
+        export_star_set.push(self.clone());
+        let mut names: Vec<String> = self
+            .0
+            .local_export_entries
+            .iter()
+            .chain(&self.0.indirect_export_entries)
+            .filter_map(|entry| entry.export_name.clone())
+            .collect();
+        for entry in &self.0.star_export_entries {
+            let requested = self.required_module(entry.module_request.as_deref().expect("star exports have a module request"));
+            for name in requested.exported_names(export_star_set) {
+                if name != "default" && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-resolveexport>
+    fn resolve_export(&self, export_name: &str, resolve_set: &mut Vec<(Self, String)>) -> Resolution {
+        if resolve_set.iter().any(|(module, name)| module == self && name == export_name) {
+            return Resolution::NotFound;
+        }
+        resolve_set.push((self.clone(), export_name.to_owned()));
+        let is_exported = |entry: &&ExportEntry| entry.export_name.as_deref() == Some(export_name);
+        if let Some(entry) = self.0.local_export_entries.iter().find(is_exported) {
+            let local_name = entry.local_name.clone().expect("local exports have a local name");
+            return Resolution::Binding(self.clone(), local_name);
+        }
+        if let Some(entry) = self.0.indirect_export_entries.iter().find(is_exported) {
+            let imported = self.required_module(entry.module_request.as_deref().expect("indirect exports have a module request"));
+            return match &entry.import_name {
+                Some(ImportName::All) => Resolution::Namespace(imported),
+                Some(ImportName::Name(name)) => imported.resolve_export(name, resolve_set),
+                _ => unreachable!("indirect exports name a binding or all of a module"),
+            };
+        }
+        // A default export is not provided by `export *`.
+        if export_name == "default" {
+            return Resolution::NotFound;
+        }
+        let mut star_resolution = Resolution::NotFound;
+        for entry in &self.0.star_export_entries {
+            let imported = self.required_module(entry.module_request.as_deref().expect("star exports have a module request"));
+            let resolution = imported.resolve_export(export_name, resolve_set);
+            match resolution {
+                Resolution::Ambiguous => return Resolution::Ambiguous,
+                Resolution::NotFound => {},
+                _ if matches!(star_resolution, Resolution::NotFound) => star_resolution = resolution,
+                _ if !resolution.is_same(&star_resolution) => return Resolution::Ambiguous,
+                _ => {},
+            }
+        }
+        star_resolution
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getmodulenamespace>:
+    /// a module namespace object created on the first use.
+    ///
+    /// The object is cached before its exports are resolved, so namespaces
+    /// of modules exporting each other's ones refer to each other.
+    #[must_use]
+    pub fn namespace(&self) -> Object {
+        if let Some(namespace) = &*self.0.namespace.borrow() {
+            return namespace.clone();
+        }
+        let state = Rc::new(ModuleNamespace::default());
+        let namespace = Object::new_module_namespace(state.clone());
+        self.0.namespace.replace(Some(namespace.clone()));
+        let mut exports = Vec::new();
+        for name in self.exported_names(&mut Vec::new()) {
+            let export = match self.resolve_export(&name, &mut Vec::new()) {
+                Resolution::Binding(module, binding_name) => ModuleExport::Binding(module.0.environment.clone(), binding_name),
+                Resolution::Namespace(module) => ModuleExport::Namespace(module.namespace()),
+                Resolution::NotFound | Resolution::Ambiguous => continue,
+            };
+            exports.push((name, export));
+        }
+        state.set_exports(exports);
+        namespace
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-source-text-module-record-initialize-environment>
+    fn initialize_environment(&self, context: &mut Context) -> Result<(), JsError> {
+        for entry in &self.0.indirect_export_entries {
+            let name = entry.export_name.as_deref().expect("indirect exports are named");
+            if let Resolution::NotFound | Resolution::Ambiguous = self.resolve_export(name, &mut Vec::new()) {
+                let request = entry.module_request.as_deref().unwrap_or_default();
+                return Err(unresolvable(request, name));
+            }
+        }
+        let environment = &self.0.environment;
+        for entry in &self.0.import_entries {
+            let imported = self.required_module(&entry.module_request);
+            let namespace = match &entry.import_name {
+                ImportName::Name(name) => match imported.resolve_export(name, &mut Vec::new()) {
+                    Resolution::Binding(module, binding_name) => {
+                        environment.create_import_binding(&entry.local_name, module.0.environment.clone(), &binding_name);
+                        continue;
+                    },
+                    Resolution::Namespace(module) => module.namespace(),
+                    Resolution::NotFound | Resolution::Ambiguous => return Err(unresolvable(&entry.module_request, name)),
+                },
+                _ => imported.namespace(),
+            };
+            environment.create_immutable_binding(&entry.local_name, true);
+            environment.initialize_binding(context, &entry.local_name, Value::Object(namespace))?;
+        }
+        context.push_execution_context(ExecutionContext::with_environment(None, self.0.realm.clone(), environment.clone()));
+        let mut evaluator = Evaluator { context, code: &self.0.code, is_strict: true, tail_calls: false };
+        let result = evaluator.module_declaration_instantiation(&self.0.module, environment);
+        context.pop_execution_context();
+        result
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-source-text-module-record-execute-module>
+    fn execute(&self, context: &mut Context) -> Result<(), JsError> {
+        let environment = self.0.environment.clone();
+        context.push_execution_context(ExecutionContext::with_environment(None, self.0.realm.clone(), environment));
+        let mut evaluator = Evaluator { context, code: &self.0.code, is_strict: true, tail_calls: false };
+        let result = evaluator.evaluate_module_body(&self.0.module);
+        context.pop_execution_context();
+        result
+    }
+}
+
+impl Evaluator<'_> {
+    /// Bind declarations of a module body, the part of
+    /// <https://262.ecma-international.org/14.0/#sec-source-text-module-record-initialize-environment>
+    /// after imports are bound.
+    fn module_declaration_instantiation(&mut self, module: &Module, environment: &Environment) -> Result<(), JsError> {
+        let mut declared_var_names = HashSet::new();
+        for name in module.var_declared_names() {
+            if declared_var_names.insert(name.name.as_str()) {
+                environment.create_mutable_binding(self.context, &name.name, false)?;
+                environment.initialize_binding(self.context, &name.name, Value::Undefined)?;
+            }
+        }
+        for item in &module.body {
+            match item {
+                ModuleItem::StatementListItem(StatementListItem::Declaration(declaration))
+                | ModuleItem::ExportDeclaration(
+                    ExportDeclaration::Declaration(StatementListItem::Declaration(declaration), _)
+                    | ExportDeclaration::DefaultDeclaration(declaration, _),
+                ) => self.instantiate_module_declaration(declaration, environment)?,
+                ModuleItem::ExportDeclaration(ExportDeclaration::DefaultExpression(..)) => {
+                    environment.create_mutable_binding(self.context, DEFAULT_EXPORT_NAME, false)?;
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
+    /// Bind a declaration at the top level of a module, initializing
+    /// a function one; an anonymous default export is bound as
+    /// `*default*`.
+    fn instantiate_module_declaration(&mut self, declaration: &Declaration, environment: &Environment) -> Result<(), JsError> {
+        match declaration {
+            Declaration::FunctionDeclaration(function) => {
+                let name = function.name.as_ref().map_or(DEFAULT_EXPORT_NAME, |name| name.name.as_str());
+                environment.create_mutable_binding(self.context, name, false)?;
+                let object = self.instantiate_function_object(function, environment);
+                environment.initialize_binding(self.context, name, Value::Object(object))
+            },
+            Declaration::ClassDeclaration(class) => {
+                let name = class.name.as_ref().map_or(DEFAULT_EXPORT_NAME, |name| name.name.as_str());
+                environment.create_mutable_binding(self.context, name, false)
+            },
+            Declaration::LexicalDeclaration(list) => {
+                for name in declaration.bound_names() {
+                    if list.kind == VariableKind::Const {
+                        environment.create_immutable_binding(&name.name, true);
+                    } else {
+                        environment.create_mutable_binding(self.context, &name.name, false)?;
+                    }
+                }
+                Ok(())
+            },
+        }
+    }
+
+    /// Evaluation of `ModuleBody`, whose completion value is not used.
+    fn evaluate_module_body(&mut self, module: &Module) -> Result<(), JsError> {
+        for item in &module.body {
+            match item {
+                ModuleItem::StatementListItem(item) | ModuleItem::ExportDeclaration(ExportDeclaration::Declaration(item, _)) => {
+                    self.evaluate_statement_list_item(item)?;
+                },
+                ModuleItem::ExportDeclaration(ExportDeclaration::DefaultDeclaration(Declaration::ClassDeclaration(class), _)) => {
+                    let name = class.name.as_ref().map_or("default", |name| name.name.as_str());
+                    let function = self.evaluate_class(class, &PropertyKey::from(name)).map_err(|error| error.at(&class.range))?;
+                    let binding = class.name.as_ref().map_or(DEFAULT_EXPORT_NAME, |name| name.name.as_str());
+                    self.lexical_environment().initialize_binding(self.context, binding, Value::Object(function))?;
+                },
+                ModuleItem::ExportDeclaration(ExportDeclaration::DefaultExpression(expression, _)) => {
+                    let value = self.evaluate_named(expression, &PropertyKey::from("default"))?;
+                    self.lexical_environment().initialize_binding(self.context, DEFAULT_EXPORT_NAME, value)?;
+                },
+                // Imports are bound and functions are instantiated by
+                // linking.
+                ModuleItem::ImportDeclaration(_) | ModuleItem::ExportDeclaration(_) => {},
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unresolvable(module_request: &str, name: &str) -> JsError {
+    JsError::new(ErrorKind::SyntaxError, format!("module {module_request} does not provide a single export named {name}"))
+}
+
+impl PartialEq for ModuleRecord {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ModuleRecord {}
+
+impl fmt::Debug for ModuleRecord {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "ModuleRecord({:?})", self.0.specifier)
+    }
+}
@@ -0,0 +1,319 @@
+//! <https://262.ecma-international.org/14.0/#sec-destructuring-binding-patterns>
+//! and <https://262.ecma-international.org/14.0/#sec-destructuring-assignment>.
+
+use super::expressions::{is_pattern, Reference};
+use super::Evaluator;
+use crate::grammar::{
+    ArrayBindingPattern,
+    ArrayElement,
+    ArrayLiteral,
+    AssignmentExpression,
+    AssignmentOperator,
+    Binding,
+    BindingElement,
+    BindingProperty,
+    Expression,
+    ObjectBindingPattern,
+    ObjectLiteral,
+    PropertyDefinition,
+};
+use crate::runtime::{Environment, IteratorRecord, JsError, Object, PropertyKey, Value};
+
+impl Evaluator<'_> {
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-bindinginitialization>:
+    /// initialize bindings of `environment`, or assign to resolved ones if
+    /// it is `None`.
+    pub(super) fn binding_initialization(
+        &mut self,
+        binding: &Binding,
+        value: Value,
+        environment: Option<&Environment>,
+    ) -> Result<(), JsError> {
+        let result = match binding {
+            Binding::BindingIdentifier(identifier) => self.initialize_bound_name(&identifier.name, value, environment),
+            Binding::ObjectBindingPattern(pattern) => {
+                require_object_coercible(&value)?;
+                self.object_binding_initialization(pattern, &value, environment)
+            },
+            Binding::ArrayBindingPattern(pattern) => {
+                let mut iterator = IteratorRecord::from_iterable(self.context, &value)?;
+                let result = self.array_binding_initialization(pattern, &mut iterator, environment);
+                if iterator.done {
+                    result
+                } else {
+                    iterator.close(self.context, result)
+                }
+            },
+        };
+        result.map_err(|error| error.at(&binding.range()))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-initializeboundname>
+    fn initialize_bound_name(&mut self, name: &str, value: Value, environment: Option<&Environment>) -> Result<(), JsError> {
+        if let Some(environment) = environment {
+            environment.initialize_binding(self.context, name, value)
+        } else {
+            let reference = self.resolve_binding(name)?;
+            self.put_value(&reference, value)
+        }
+    }
+
+    fn object_binding_initialization(
+        &mut self,
+        pattern: &ObjectBindingPattern,
+        value: &Value,
+        environment: Option<&Environment>,
+    ) -> Result<(), JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-destructuring-binding-patterns-runtime-semantics-propertybindinginitialization>
+        let mut bound_keys = Vec::new();
+        for property in &pattern.properties {
+            match property {
+                BindingProperty::SingleNameBinding { name, initializer } => {
+                    let key = PropertyKey::from(name.name.as_str());
+                    let reference = environment.is_none().then(|| self.resolve_binding(&name.name)).transpose()?;
+                    let mut property_value = self.get_property(value, &key)?;
+                    if let (Value::Undefined, Some(initializer)) = (&property_value, initializer) {
+                        property_value = self.evaluate_named(initializer, &key)?;
+                    }
+                    match reference {
+                        Some(reference) => self.put_value(&reference, property_value)?,
+                        None => self.initialize_bound_name(&name.name, property_value, environment)?,
+                    }
+                    bound_keys.push(key);
+                },
+                BindingProperty::Property { name, value: element } => {
+                    let key = self.evaluate_property_name(name)?;
+                    let property_value = self.get_property(value, &key)?;
+                    self.binding_element_initialization(element, property_value, environment)?;
+                    bound_keys.push(key);
+                },
+            }
+        }
+        if let Some(rest) = &pattern.rest {
+            // <https://262.ecma-international.org/14.0/#sec-destructuring-binding-patterns-runtime-semantics-restbindinginitialization>
+            let reference = environment.is_none().then(|| self.resolve_binding(&rest.name)).transpose()?;
+            let rest_object = Object::new(Some(self.realm().intrinsics().object_prototype.clone()));
+            self.copy_data_properties(&rest_object, value, &bound_keys)?;
+            match reference {
+                Some(reference) => self.put_value(&reference, Value::Object(rest_object))?,
+                None => self.initialize_bound_name(&rest.name, Value::Object(rest_object), environment)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-keyedbindinginitialization>
+    /// of an element whose value is already taken.
+    fn binding_element_initialization(
+        &mut self,
+        element: &BindingElement,
+        mut value: Value,
+        environment: Option<&Environment>,
+    ) -> Result<(), JsError> {
+        if let Binding::BindingIdentifier(identifier) = &element.target {
+            // The reference is resolved before the initializer runs.
+            let reference = environment.is_none().then(|| self.resolve_binding(&identifier.name)).transpose()?;
+            if let (Value::Undefined, Some(initializer)) = (&value, &element.initializer) {
+                value = self.evaluate_named(initializer, &PropertyKey::from(identifier.name.as_str()))?;
+            }
+            return match reference {
+                Some(reference) => self.put_value(&reference, value),
+                None => self.initialize_bound_name(&identifier.name, value, environment),
+            };
+        }
+        if let (Value::Undefined, Some(initializer)) = (&value, &element.initializer) {
+            value = self.evaluate(initializer)?;
+        }
+        self.binding_initialization(&element.target, value, environment)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-iteratorbindinginitialization>
+    fn array_binding_initialization(
+        &mut self,
+        pattern: &ArrayBindingPattern,
+        iterator: &mut IteratorRecord,
+        environment: Option<&Environment>,
+    ) -> Result<(), JsError> {
+        for element in &pattern.elements {
+            let value = if iterator.done { None } else { iterator.step_value(self.context)? };
+            if let Some(element) = element {
+                self.binding_element_initialization(element, value.unwrap_or(Value::Undefined), environment)?;
+            }
+        }
+        if let Some(rest) = &pattern.rest {
+            let reference = match &**rest {
+                Binding::BindingIdentifier(identifier) if environment.is_none() => {
+                    Some(self.resolve_binding(&identifier.name)?)
+                },
+                _ => None,
+            };
+            let mut values = Vec::new();
+            while !iterator.done {
+                if let Some(value) = iterator.step_value(self.context)? {
+                    values.push(value);
+                }
+            }
+            let array = Value::Object(self.create_array(values));
+            match reference {
+                Some(reference) => self.put_value(&reference, array)?,
+                None => self.binding_initialization(rest, array, environment)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getv>
+    pub(super) fn get_property(&mut self, value: &Value, key: &PropertyKey) -> Result<Value, JsError> {
+        let object = value.to_object(self.context)?;
+        object.get(self.context, key, value)
+    }
+
+    fn create_array(&self, values: Vec<Value>) -> Object {
+        Object::new_array(Some(self.realm().intrinsics().array_prototype.clone()), values)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-destructuringassignmentevaluation>
+    /// of an object or an array literal reinterpreted as a pattern.
+    pub(super) fn destructuring_assignment(&mut self, pattern: &Expression, value: &Value) -> Result<(), JsError> {
+        let result = match pattern {
+            Expression::ObjectLiteral(literal) => {
+                require_object_coercible(value)?;
+                self.object_assignment(literal, value)
+            },
+            Expression::ArrayLiteral(literal) => {
+                let mut iterator = IteratorRecord::from_iterable(self.context, value)?;
+                let result = self.array_assignment(literal, &mut iterator);
+                if iterator.done {
+                    result
+                } else {
+                    iterator.close(self.context, result)
+                }
+            },
+            _ => unreachable!("only object and array literals are assignment patterns"),
+        };
+        result.map_err(|error| error.at(&pattern.range()))
+    }
+
+    fn object_assignment(&mut self, literal: &ObjectLiteral, value: &Value) -> Result<(), JsError> {
+        let mut assigned_keys = Vec::new();
+        for property in &literal.properties {
+            match property {
+                PropertyDefinition::IdentifierReference(identifier) => {
+                    let key = PropertyKey::from(identifier.name.as_str());
+                    let reference = self.resolve_binding(&identifier.name)?;
+                    let property_value = self.get_property(value, &key)?;
+                    self.put_value(&reference, property_value)?;
+                    assigned_keys.push(key);
+                },
+                PropertyDefinition::CoverInitializedName { name, initializer } => {
+                    let key = PropertyKey::from(name.name.as_str());
+                    let reference = self.resolve_binding(&name.name)?;
+                    let mut property_value = self.get_property(value, &key)?;
+                    if property_value.is_undefined() {
+                        property_value = self.evaluate_named(initializer, &key)?;
+                    }
+                    self.put_value(&reference, property_value)?;
+                    assigned_keys.push(key);
+                },
+                PropertyDefinition::Property { name, value: target } => {
+                    let key = self.evaluate_property_name(name)?;
+                    self.keyed_assignment(target, value, &key)?;
+                    assigned_keys.push(key);
+                },
+                PropertyDefinition::SpreadProperty(target, _) => {
+                    // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-restdestructuringassignmentevaluation>
+                    let reference = self.evaluate_reference(target)?;
+                    let rest_object = Object::new(Some(self.realm().intrinsics().object_prototype.clone()));
+                    self.copy_data_properties(&rest_object, value, &assigned_keys)?;
+                    self.put_value(&reference, Value::Object(rest_object))?;
+                },
+                PropertyDefinition::MethodDefinition(method) => {
+                    unreachable!("early errors reject methods in patterns, got {method:?}")
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-keyeddestructuringassignmentevaluation>
+    fn keyed_assignment(&mut self, element: &Expression, value: &Value, key: &PropertyKey) -> Result<(), JsError> {
+        let (target, initializer) = split_default(element);
+        let reference = (!is_pattern(target)).then(|| self.evaluate_reference(target)).transpose()?;
+        let property_value = self.get_property(value, key)?;
+        self.assign_element(target, initializer, reference, property_value)
+    }
+
+    fn array_assignment(&mut self, literal: &ArrayLiteral, iterator: &mut IteratorRecord) -> Result<(), JsError> {
+        // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-iteratordestructuringassignmentevaluation>
+        for element in &literal.elements {
+            match element {
+                ArrayElement::Elision(_) => {
+                    if !iterator.done {
+                        iterator.step_value(self.context)?;
+                    }
+                },
+                ArrayElement::AssignmentExpression(element) => {
+                    let (target, initializer) = split_default(element);
+                    let reference = (!is_pattern(target)).then(|| self.evaluate_reference(target)).transpose()?;
+                    let value = if iterator.done { None } else { iterator.step_value(self.context)? };
+                    self.assign_element(target, initializer, reference, value.unwrap_or(Value::Undefined))?;
+                },
+                ArrayElement::SpreadElement(target, _) => {
+                    let reference = (!is_pattern(target)).then(|| self.evaluate_reference(target)).transpose()?;
+                    let mut values = Vec::new();
+                    while !iterator.done {
+                        if let Some(value) = iterator.step_value(self.context)? {
+                            values.push(value);
+                        }
+                    }
+                    let array = Value::Object(self.create_array(values));
+                    match reference {
+                        Some(reference) => self.put_value(&reference, array)?,
+                        None => self.destructuring_assignment(target, &array)?,
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Assign a value taken by a pattern to its target, evaluating
+    /// a default if the value is **undefined**.
+    fn assign_element(
+        &mut self,
+        target: &Expression,
+        initializer: Option<&Expression>,
+        reference: Option<Reference>,
+        mut value: Value,
+    ) -> Result<(), JsError> {
+        if let (Value::Undefined, Some(initializer)) = (&value, initializer) {
+            value = self.evaluate_assigned_value(target, initializer)?;
+        }
+        match reference {
+            Some(reference) => self.put_value(&reference, value),
+            None => self.destructuring_assignment(target, &value),
+        }
+    }
+}
+
+/// Split `target = default` of an assignment pattern element.
+pub(super) fn split_default(element: &Expression) -> (&Expression, Option<&Expression>) {
+    match element {
+        Expression::AssignmentExpression(AssignmentExpression {
+            operator: AssignmentOperator::Assignment,
+            target,
+            value,
+            ..
+        }) => (target, Some(value)),
+        _ => (element, None),
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-requireobjectcoercible>
+pub(super) fn require_object_coercible(value: &Value) -> Result<(), JsError> {
+    if value.is_nullish() {
+        let value = if value.is_undefined() { "undefined" } else { "null" };
+        return Err(JsError::type_error(format!("cannot destructure {value}")));
+    }
+    Ok(())
+}
@@ -0,0 +1,2500 @@
+//! Evaluation of generator and async function bodies that stops at `yield`
+//! or `await` and goes on from there later.
+//!
+//! [`ResumableCode`] lowers a body into instructions a [`Frame`] runs,
+//! keeping its position, operands, scopes and handlers between runs.
+//! Statements and expressions without suspension points are single
+//! instructions evaluated by the tree walking [`Evaluator`], so only the
+//! path to each `yield` and `await` is lowered.
+//!
+//! `yield` and `await` in tagged templates, decorators and `import()` are
+//! not supported yet, failing with [`ErrorKind::Unsupported`](crate::runtime::ErrorKind::Unsupported)
+//! once evaluation reaches them.
+
+use super::expressions::{
+    apply_binary_operator,
+    apply_unary_operator,
+    apply_update_operator,
+    callee_name,
+    compound_operator,
+    is_pattern,
+    is_proto_setter,
+    is_reference,
+    short_circuits,
+    Reference,
+};
+use super::functions::{get_super_constructor, super_call, FunctionCode};
+use super::patterns::{require_object_coercible, split_default};
+use super::statements::{lexically_scoped_declarations, Completion, PropertyEnumeration};
+use crate::grammar::{
+    Argument,
+    ArrayElement,
+    AssignmentExpression,
+    AssignmentOperator,
+    BinaryOperator,
+    Binding,
+    BindingElement,
+    BindingIdentifier,
+    BindingProperty,
+    Block,
+    Class,
+    ClassElement,
+    ClassElementName,
+    Declaration,
+    Decorator,
+    DoWhileStatement,
+    Expression,
+    ForInOfStatement,
+    ForInit,
+    ForStatement,
+    ForTarget,
+    LabelledItem,
+    MemberExpression,
+    MemberProperty,
+    MethodDefinition,
+    PropertyDefinition,
+    PropertyName,
+    Statement,
+    StatementListItem,
+    SwitchStatement,
+    TryStatement,
+    UnaryOperator,
+    UpdateOperator,
+    VariableDeclarationList,
+    VariableKind,
+    WhileStatement,
+};
+use crate::lexical_grammar::PrivateIdentifier;
+use crate::runtime::{
+    Context,
+    Environment,
+    ExecutionContext,
+    IteratorRecord,
+    JsError,
+    JsString,
+    Object,
+    PrivateEnvironment,
+    PropertyKey,
+    Tracer,
+    Value,
+};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A position in instructions, resolved once the whole body is lowered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Label(usize);
+
+/// A place `break` or `continue` goes to, with how many scopes and
+/// handlers are active there.
+#[derive(Clone, Copy, Debug)]
+struct Jump {
+    label: Label,
+    scopes: usize,
+    handlers: usize,
+}
+
+/// A statement `break` or `continue` may end.
+#[derive(Clone, Debug)]
+struct BreakTarget {
+    kind: TargetKind,
+    labels: Vec<String>,
+    break_to: Jump,
+    /// `None` for statements other than loops.
+    continue_to: Option<Jump>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TargetKind {
+    Iteration,
+    Switch,
+    /// A labelled statement other than a loop or a `switch`, which only
+    /// a labelled `break` ends.
+    Labelled,
+}
+
+/// Where a `break` or a `continue` completion of a statement goes.
+fn find_jump(targets: &[BreakTarget], completion: &Completion) -> Option<Jump> {
+    let mut targets = targets.iter().rev();
+    match completion {
+        Completion::Break(None, _) => targets.find(|target| target.kind != TargetKind::Labelled).map(|target| target.break_to),
+        Completion::Break(Some(label), _) => targets.find(|target| target.labels.contains(label)).map(|target| target.break_to),
+        Completion::Continue(label, _) => targets
+            .filter(|target| target.kind == TargetKind::Iteration)
+            .find(|target| label.as_ref().is_none_or(|label| target.labels.contains(label)))
+            .and_then(|target| target.continue_to),
+        Completion::Normal(_) | Completion::Return(_) | Completion::TailCall(_) => None,
+    }
+}
+
+/// An instruction of [`ResumableCode`] working on the operand stack of
+/// a [`Frame`], which is empty between statements.
+enum Instruction {
+    /// Evaluate a statement list item without suspension points, going to
+    /// one of the targets on `break` or `continue`.
+    Statement(Rc<StatementListItem>, Rc<[BreakTarget]>),
+    /// Push the value of an expression without suspension points.
+    Expression(Rc<Expression>),
+    Push(Value),
+    Pop,
+    Goto(Label),
+    /// Pop a value, going to the label if it is falsy.
+    GotoIfFalse(Label),
+    /// Go to the label keeping the value on top if a logical operator
+    /// short-circuits on it, pop it otherwise.
+    ShortCircuit(BinaryOperator, Label),
+    CheckLimits,
+    /// Pop two operands, pushing the result.
+    Binary(BinaryOperator),
+    /// Pop an object, pushing whether it has a private member.
+    PrivateIn(PrivateIdentifier),
+    Unary(UnaryOperator),
+    /// Pop a case selector; if it is strictly equal to the discriminant
+    /// below, pop that too and go to the label.
+    Case(Label),
+    /// Push a reference to an expression without suspension points to the
+    /// reference stack.
+    Reference(Rc<Expression>),
+    /// Push a reference to a resolved binding.
+    ResolveBinding(String),
+    /// Pop a property key and a base value, pushing a reference to the
+    /// property.
+    PropertyReference,
+    /// Pop a base value, pushing a reference to its private member.
+    PrivateReference(PrivateIdentifier),
+    /// Push `this` of a `super` property, which goes before its key.
+    SuperThis,
+    /// Pop a property key and `this`, pushing a Super Reference to the
+    /// property.
+    SuperReference,
+    /// Push the value of the innermost reference.
+    GetReferenceValue,
+    /// Pop the innermost reference, assigning the value on top to it.
+    PutReferenceValue,
+    DropReference,
+    /// Pop a value, assigning it to a target without suspension points.
+    Assign(Rc<Expression>),
+    /// Destructure the value on top with a pattern.
+    DestructuringAssignment(Rc<Expression>),
+    /// Pop a value, initializing a binding in the `LexicalEnvironment` if
+    /// the flag is set, or assigning it to resolved bindings otherwise.
+    InitializeBinding(Rc<Binding>, bool),
+    /// Check that the value on top may be destructured by an object
+    /// pattern.
+    RequireObjectCoercible(Range<usize>),
+    /// Pop a property key, pushing the property of the value below;
+    /// the key is appended to the innermost list if the flag is set, for
+    /// a rest element to leave it out.
+    PatternProperty(bool),
+    /// Pop the innermost list of property keys, pushing an object with the
+    /// other own enumerable properties of the value on top.
+    RestObject,
+    /// Start iterating over the value on top for an array pattern.
+    PatternIterate(Range<usize>),
+    /// Push the next value of the innermost iterator, or **undefined** once
+    /// it is done.
+    PatternStep,
+    /// Push an array of the values the innermost iterator has left.
+    PatternRest,
+    /// End the innermost iterator, closing it unless it is done.
+    PatternClose,
+    /// Go to the label if the value on top is not **undefined**, pop it
+    /// otherwise for a default to replace it.
+    GotoIfDefined(Label),
+    /// Push the value of an expression without suspension points, naming
+    /// an anonymous function after the key.
+    NamedExpression(Rc<Expression>, PropertyKey),
+    /// Pop the innermost reference, adding one to its value or subtracting
+    /// one from it; the new value is pushed if the flag is set, the old one
+    /// otherwise.
+    Update(UpdateOperator, bool),
+    /// Pop the innermost reference, deleting what it refers to, and push
+    /// whether it is deleted.
+    Delete,
+    /// Push a function and `this` of a callee without suspension points.
+    Callee(Rc<Expression>),
+    /// Pop a property key and a base value, pushing the property value and
+    /// the base as `this`.
+    MethodCallee,
+    /// Pop the innermost reference, pushing its value and `this` of a call
+    /// through it.
+    ReferenceCallee,
+    /// Pop the value on top and go to the label if it is nullish, ending an
+    /// optional chain.
+    OptionalChain(Label),
+    /// Pop a function and `this` on top and go to the label if the function
+    /// is nullish, ending an optional chain.
+    OptionalCall(Label),
+    /// Push the parent constructor of a derived constructor.
+    SuperConstructor,
+    /// Start an argument list.
+    NewList,
+    /// Pop a value, appending it to the innermost list.
+    Append,
+    /// Pop an iterable, appending its values to the innermost list.
+    Spread(Range<usize>),
+    /// Pop the innermost list, `this` and a function, pushing the result of
+    /// the call; the callee is named in messages.
+    Call(String, Range<usize>),
+    /// Pop the innermost list and a constructor, pushing the constructed
+    /// object.
+    Construct(String, Range<usize>),
+    /// Pop the innermost list and the parent constructor, binding `this`
+    /// to the object it constructs.
+    SuperCall,
+    /// Pop a property key and a base value, pushing the property value.
+    GetProperty,
+    /// Convert the value on top to a property key.
+    ToPropertyKey,
+    /// Push a key of a property name without suspension points.
+    PropertyName(Rc<PropertyName>),
+    NewArray,
+    /// Pop a value, appending it to the array below.
+    ArrayAppend,
+    /// Make the array on top one element longer.
+    ArrayHole,
+    /// Pop an iterable, appending its values to the array below.
+    ArraySpread(Range<usize>),
+    NewObject,
+    /// Define a property without suspension points on the object on top.
+    DefineProperty(Rc<PropertyDefinition>),
+    /// Pop a value and a property key, defining the property on the object
+    /// below.
+    DefineDataProperty,
+    /// Pop a property key, defining the property with a value of an
+    /// expression without suspension points on the object below, naming an
+    /// anonymous function after the key.
+    DefineNamedProperty(Rc<Expression>),
+    /// Pop a property key, defining a method with it on the object on top.
+    DefineMethod(Rc<MethodDefinition>),
+    /// Pop a value, copying its own enumerable properties to the object
+    /// below.
+    CopyDataProperties,
+    /// Enter the scope of a class, where its own name is not initialized
+    /// yet.
+    EnterClass(Rc<Class>),
+    /// Pop the heritage, if the class has one, pushing parents of the
+    /// prototype and the constructor, and enter the `PrivateEnvironment` of
+    /// the class body.
+    EnterClassBody(Rc<Class>),
+    /// Pop computed keys of class elements, if the class has them, and the
+    /// parents, pushing the class named after the key, and leave the
+    /// `PrivateEnvironment` of its body.
+    DefineClass(Rc<Class>, PropertyKey),
+    /// Convert the value on top to a String.
+    ToString,
+    /// Pop one String less than there are strings, pushing the template
+    /// string interleaving them.
+    Template(Rc<[JsString]>),
+    /// Enter a block scope, instantiating its declarations.
+    EnterBlock(Rc<[StatementListItem]>),
+    /// Enter a scope with uninitialized bindings, immutable if the flag is
+    /// set.
+    EnterDeclarations(Rc<[String]>, bool),
+    /// Pop a value, entering an object environment of it.
+    EnterWith(Range<usize>),
+    ExitScope,
+    /// <https://262.ecma-international.org/14.0/#sec-createperiterationenvironment>
+    CopyScope(Rc<[String]>),
+    /// Catch errors, and run `finally` on abrupt completions, of the
+    /// instructions up to the matching [`Instruction::ExitTry`].
+    EnterTry { catch: Option<Label>, finally: Option<Label> },
+    ExitTry,
+    /// Start a `finally` block after a normal completion.
+    EnterFinally,
+    /// End a `finally` block, going on with the completion it delayed.
+    ExitFinally,
+    /// Pop a value, starting to iterate over it with `for`-`of`, or to
+    /// enumerate its keys with `for`-`in` if the flag is set; `for`-`in`
+    /// over a nullish value goes to the label.
+    Iterate(bool, Label, Range<usize>),
+    /// Push the next value of the innermost iteration, or end it and go to
+    /// the label when it is done.
+    Next(Label),
+    /// Pop a value, starting to iterate over it with `for await`; an
+    /// abrupt completion leaving the loop awaits `return` of the iterator
+    /// at the label.
+    AsyncIterate(Label, Range<usize>),
+    /// Push what to await for the next value of the innermost `for await`:
+    /// a result of `next` of an async iterator, or the next value of a sync
+    /// one; a sync iterator going done ends the loop, going to the label.
+    AsyncNext(Label),
+    /// Take the awaited result of `next` of an async iterator on top,
+    /// replacing it with its value, or end the loop and go to the label
+    /// if it is done; an awaited value of a sync iterator stays as is.
+    AsyncIteratorValue(Label),
+    /// Pop the awaited result of `return` of an async iterator, checking
+    /// that it is an object.
+    AsyncIteratorClosed,
+    /// Pop a value, completing the body with it.
+    Return,
+    /// Pop a value, throwing it.
+    Throw(Range<usize>),
+    /// Pop a value, suspending with it; the value the generator is resumed
+    /// with is pushed back.
+    Yield,
+    /// Pop a value, suspending until it settles; the value it fulfills with
+    /// is pushed back and the reason it rejects with is thrown.
+    Await,
+    /// Pop an iterable, pushing its iterator and `next` method for
+    /// [`Instruction::YieldDelegate`].
+    GetIterator(Range<usize>),
+    /// <https://262.ecma-international.org/14.0/#sec-generator-function-definitions-runtime-semantics-evaluation>
+    /// of `yield*` over the iterator and `next` method on top, replacing
+    /// them with the value the iterator completes with.
+    YieldDelegate,
+}
+
+/// A function body lowered into instructions a [`Frame`] runs.
+pub(super) struct ResumableCode {
+    instructions: Vec<Instruction>,
+    /// Positions of labels.
+    labels: Vec<usize>,
+}
+
+impl ResumableCode {
+    /// Lower a `FunctionBody`.
+    pub(super) fn new(body: &[StatementListItem]) -> Self {
+        let mut lowering = Lowering::default();
+        lowering.statements(body);
+        lowering.emit(Instruction::Push(Value::Undefined));
+        lowering.emit(Instruction::Return);
+        lowering.finish()
+    }
+
+    /// Lower an `ExpressionBody` of an arrow function.
+    pub(super) fn from_expression(body: &Expression) -> Self {
+        let mut lowering = Lowering::default();
+        lowering.expression(body);
+        lowering.emit(Instruction::Return);
+        lowering.finish()
+    }
+
+    fn position(&self, label: Label) -> usize {
+        self.labels[label.0]
+    }
+}
+
+#[derive(Default)]
+struct Lowering {
+    instructions: Vec<Instruction>,
+    labels: Vec<usize>,
+    targets: Vec<BreakTarget>,
+    /// A copy of `targets` leaf statements share until they change.
+    shared_targets: Option<Rc<[BreakTarget]>>,
+    /// How many scopes and handlers the current instruction runs in.
+    scopes: usize,
+    handlers: usize,
+}
+
+impl Lowering {
+    fn finish(self) -> ResumableCode {
+        ResumableCode { instructions: self.instructions, labels: self.labels }
+    }
+
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+
+    fn label(&mut self) -> Label {
+        self.labels.push(usize::MAX);
+        Label(self.labels.len() - 1)
+    }
+
+    /// Make the label point to the next instruction.
+    fn place(&mut self, label: Label) {
+        self.labels[label.0] = self.instructions.len();
+    }
+
+    /// A jump to the label from the current scopes and handlers.
+    const fn jump_to(&self, label: Label) -> Jump {
+        Jump { label, scopes: self.scopes, handlers: self.handlers }
+    }
+
+    fn with_target(&mut self, target: BreakTarget, lower: impl FnOnce(&mut Self)) {
+        self.targets.push(target);
+        self.shared_targets = None;
+        lower(self);
+        self.targets.pop();
+        self.shared_targets = None;
+    }
+
+    fn leaf(&mut self, item: StatementListItem) {
+        let targets = match &self.shared_targets {
+            Some(targets) => targets.clone(),
+            None => self.shared_targets.insert(self.targets.clone().into()).clone(),
+        };
+        self.emit(Instruction::Statement(Rc::new(item), targets));
+    }
+
+    fn statements(&mut self, statements: &[StatementListItem]) {
+        for item in statements {
+            match item {
+                StatementListItem::Statement(statement) if statement_suspends(statement) => self.statement(statement, Vec::new()),
+                StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) if list_suspends(list) => self.declarations(list),
+                StatementListItem::Declaration(Declaration::ClassDeclaration(class)) if class_suspends(class) && class.decorators.is_empty() => {
+                    // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-bindingclassdeclarationevaluation>
+                    let name = class.name.as_ref().expect("only an exported default class is anonymous");
+                    self.class(class, PropertyKey::from(name.name.as_str()));
+                    self.emit(Instruction::InitializeBinding(Rc::new(Binding::BindingIdentifier(name.clone())), true));
+                },
+                item => self.leaf(item.clone()),
+            }
+        }
+    }
+
+    /// Lower a statement that is the body of another one.
+    fn substatement(&mut self, statement: &Statement) {
+        if statement_suspends(statement) {
+            self.statement(statement, Vec::new());
+        } else {
+            self.leaf(StatementListItem::Statement(statement.clone()));
+        }
+    }
+
+    /// Lower a statement with suspension points and `labels`.
+    fn statement(&mut self, statement: &Statement, labels: Vec<String>) {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::LabelledStatement(labelled) => {
+                    let mut labels = labels;
+                    labels.push(labelled.label.clone());
+                    match &*labelled.body {
+                        LabelledItem::Statement(body) => self.statement(body, labels),
+                        LabelledItem::FunctionDeclaration(_) => self.leaf(StatementListItem::Statement(statement.clone())),
+                    }
+                },
+                Statement::DoWhileStatement(statement) => self.do_while(statement, labels),
+                Statement::WhileStatement(statement) => self.while_loop(statement, labels),
+                Statement::ForStatement(statement) => self.for_loop(statement, labels),
+                Statement::ForInStatement(statement) => self.for_in_of(statement, true, labels),
+                Statement::ForOfStatement(statement) => self.for_in_of(statement, false, labels),
+                Statement::SwitchStatement(statement) => self.switch(statement, labels),
+                _ if !labels.is_empty() => {
+                    let end = self.label();
+                    let target = BreakTarget { kind: TargetKind::Labelled, labels, break_to: self.jump_to(end), continue_to: None };
+                    self.with_target(target, |lowering| lowering.statement(statement, Vec::new()));
+                    self.place(end);
+                },
+                Statement::BlockStatement(block) => self.block(block),
+                Statement::VariableStatement(list) => self.declarations(list),
+                Statement::ExpressionStatement(expression, _) => {
+                    self.expression(expression);
+                    self.emit(Instruction::Pop);
+                },
+                Statement::IfStatement(statement) => {
+                    let alternative = self.label();
+                    let end = self.label();
+                    self.expression(&statement.condition);
+                    self.emit(Instruction::GotoIfFalse(alternative));
+                    self.substatement(&statement.consequent);
+                    self.emit(Instruction::Goto(end));
+                    self.place(alternative);
+                    if let Some(statement) = &statement.alternative {
+                        self.substatement(statement);
+                    }
+                    self.place(end);
+                },
+                Statement::ReturnStatement(statement) => {
+                    match &statement.argument {
+                        Some(argument) => self.expression(argument),
+                        None => self.emit(Instruction::Push(Value::Undefined)),
+                    }
+                    self.emit(Instruction::Return);
+                },
+                Statement::WithStatement(statement) => {
+                    self.expression(&statement.object);
+                    self.emit(Instruction::EnterWith(statement.object.range()));
+                    self.scopes += 1;
+                    self.substatement(&statement.body);
+                    self.scopes -= 1;
+                    self.emit(Instruction::ExitScope);
+                },
+                Statement::ThrowStatement(statement) => {
+                    self.expression(&statement.argument);
+                    self.emit(Instruction::Throw(statement.range.clone()));
+                },
+                Statement::TryStatement(statement) => self.try_statement(statement),
+                Statement::EmptyStatement(_)
+                | Statement::ContinueStatement(_)
+                | Statement::BreakStatement(_)
+                | Statement::DebuggerStatement(_) => self.leaf(StatementListItem::Statement(statement.clone())),
+            }
+        });
+    }
+
+    fn block(&mut self, block: &Block) {
+        if !block.statements.iter().any(item_suspends) {
+            self.leaf(StatementListItem::Statement(Statement::BlockStatement(block.clone())));
+            return;
+        }
+        let is_scoped = block.statements.iter().any(|item| lexically_scoped_declarations(item).next().is_some());
+        if is_scoped {
+            self.emit(Instruction::EnterBlock(block.statements.clone().into()));
+            self.scopes += 1;
+        }
+        self.statements(&block.statements);
+        if is_scoped {
+            self.scopes -= 1;
+            self.emit(Instruction::ExitScope);
+        }
+    }
+
+    /// Lower `var`, `let` and `const` declarations one by one, the ones
+    /// without suspension points as leaves.
+    fn declarations(&mut self, list: &VariableDeclarationList) {
+        if matches!(list.kind, VariableKind::Using | VariableKind::AwaitUsing) {
+            self.leaf(StatementListItem::Declaration(Declaration::LexicalDeclaration(list.clone())));
+            return;
+        }
+        for declaration in &list.declarations {
+            let suspends = binding_suspends(&declaration.target) || declaration.initializer.as_ref().is_some_and(expression_suspends);
+            let Some(initializer) = declaration.initializer.as_ref().filter(|_| suspends) else {
+                let list = VariableDeclarationList {
+                    kind: list.kind,
+                    declarations: vec![declaration.clone()],
+                    range: declaration.range.clone(),
+                };
+                self.leaf(match list.kind {
+                    VariableKind::Var => StatementListItem::Statement(Statement::VariableStatement(list)),
+                    _ => StatementListItem::Declaration(Declaration::LexicalDeclaration(list)),
+                });
+                continue;
+            };
+            match (&declaration.target, list.kind) {
+                (Binding::BindingIdentifier(name), VariableKind::Var) => {
+                    self.emit(Instruction::ResolveBinding(name.name.clone()));
+                    self.named(initializer, &name.name);
+                    self.emit(Instruction::PutReferenceValue);
+                    self.emit(Instruction::Pop);
+                },
+                (target @ Binding::BindingIdentifier(name), _) => {
+                    self.named(initializer, &name.name);
+                    self.emit(Instruction::InitializeBinding(Rc::new(target.clone()), true));
+                },
+                (target, kind) => {
+                    self.expression(initializer);
+                    self.initialize_binding(target, kind != VariableKind::Var);
+                },
+            }
+        }
+    }
+
+    /// Lower the body of a loop that `break` ends and `continue` goes on
+    /// with.
+    fn loop_body(&mut self, body: &Statement, labels: Vec<String>, break_to: Jump, continue_to: Jump) {
+        let target = BreakTarget { kind: TargetKind::Iteration, labels, break_to, continue_to: Some(continue_to) };
+        self.with_target(target, |lowering| lowering.substatement(body));
+    }
+
+    fn do_while(&mut self, statement: &DoWhileStatement, labels: Vec<String>) {
+        let head = self.label();
+        let test = self.label();
+        let end = self.label();
+        self.place(head);
+        self.emit(Instruction::CheckLimits);
+        self.loop_body(&statement.body, labels, self.jump_to(end), self.jump_to(test));
+        self.place(test);
+        self.expression(&statement.condition);
+        self.emit(Instruction::GotoIfFalse(end));
+        self.emit(Instruction::Goto(head));
+        self.place(end);
+    }
+
+    fn while_loop(&mut self, statement: &WhileStatement, labels: Vec<String>) {
+        let head = self.label();
+        let end = self.label();
+        self.place(head);
+        self.emit(Instruction::CheckLimits);
+        self.expression(&statement.condition);
+        self.emit(Instruction::GotoIfFalse(end));
+        self.loop_body(&statement.body, labels, self.jump_to(end), self.jump_to(head));
+        self.emit(Instruction::Goto(head));
+        self.place(end);
+    }
+
+    fn for_loop(&mut self, statement: &ForStatement, labels: Vec<String>) {
+        let mut per_iteration: Rc<[String]> = Rc::new([]);
+        let mut is_scoped = false;
+        match &statement.init {
+            None => {},
+            Some(ForInit::Expression(expression)) if expression_suspends(expression) => {
+                self.expression(expression);
+                self.emit(Instruction::Pop);
+            },
+            Some(ForInit::Expression(expression)) => {
+                let statement = Statement::ExpressionStatement(expression.clone(), expression.range());
+                self.leaf(StatementListItem::Statement(statement));
+            },
+            Some(ForInit::VariableDeclarationList(list)) if list.kind == VariableKind::Var => self.declarations(list),
+            Some(ForInit::VariableDeclarationList(list)) => {
+                let names: Rc<[String]> = list.bound_names().into_iter().map(|name| name.name.clone()).collect();
+                let is_constant = list.kind == VariableKind::Const;
+                self.emit(Instruction::EnterDeclarations(names.clone(), is_constant));
+                self.scopes += 1;
+                is_scoped = true;
+                self.declarations(list);
+                if !is_constant {
+                    per_iteration = names;
+                }
+            },
+        }
+        if !per_iteration.is_empty() {
+            self.emit(Instruction::CopyScope(per_iteration.clone()));
+        }
+        let head = self.label();
+        let update = self.label();
+        let end = self.label();
+        self.place(head);
+        self.emit(Instruction::CheckLimits);
+        if let Some(condition) = &statement.condition {
+            self.expression(condition);
+            self.emit(Instruction::GotoIfFalse(end));
+        }
+        self.loop_body(&statement.body, labels, self.jump_to(end), self.jump_to(update));
+        self.place(update);
+        if !per_iteration.is_empty() {
+            self.emit(Instruction::CopyScope(per_iteration));
+        }
+        if let Some(update) = &statement.update {
+            self.expression(update);
+            self.emit(Instruction::Pop);
+        }
+        self.emit(Instruction::Goto(head));
+        self.place(end);
+        if is_scoped {
+            self.scopes -= 1;
+            self.emit(Instruction::ExitScope);
+        }
+    }
+
+    fn for_in_of(&mut self, statement: &ForInOfStatement, is_enumeration: bool, labels: Vec<String>) {
+        let declaration = match &statement.target {
+            ForTarget::ForDeclaration { kind: VariableKind::Using | VariableKind::AwaitUsing, .. } => None,
+            ForTarget::ForDeclaration { kind: kind @ (VariableKind::Let | VariableKind::Const), binding, .. } => {
+                let names: Rc<[String]> = binding.bound_names().into_iter().map(|name| name.name.clone()).collect();
+                Some(Some((names, *kind == VariableKind::Const)))
+            },
+            _ => Some(None),
+        };
+        // Unsupported forms fail as leaves.
+        let Some(declaration) = declaration else {
+            let statement = Box::new(statement.clone());
+            let statement = if is_enumeration { Statement::ForInStatement(statement) } else { Statement::ForOfStatement(statement) };
+            self.leaf(StatementListItem::Statement(statement));
+            return;
+        };
+        if let Some((names, _)) = &declaration {
+            // The source sees the declared names in the temporal dead zone.
+            self.emit(Instruction::EnterDeclarations(names.clone(), false));
+            self.scopes += 1;
+            self.expression(&statement.source);
+            self.scopes -= 1;
+            self.emit(Instruction::ExitScope);
+        } else {
+            self.expression(&statement.source);
+        }
+        let head = self.label();
+        let end = self.label();
+        let close = statement.is_await.then(|| self.label());
+        match close {
+            Some(close) => self.emit(Instruction::AsyncIterate(close, statement.source.range())),
+            None => self.emit(Instruction::Iterate(is_enumeration, end, statement.source.range())),
+        }
+        let break_to = self.jump_to(end);
+        self.handlers += 1;
+        let continue_to = self.jump_to(head);
+        self.place(head);
+        self.emit(Instruction::CheckLimits);
+        if close.is_some() {
+            self.emit(Instruction::AsyncNext(end));
+            self.emit(Instruction::Await);
+            self.emit(Instruction::AsyncIteratorValue(end));
+        } else {
+            self.emit(Instruction::Next(end));
+        }
+        match (&statement.target, &declaration) {
+            (ForTarget::Expression(target), _) if is_pattern(target) => {
+                self.destructuring_assignment(target);
+                self.emit(Instruction::Pop);
+            },
+            (ForTarget::Expression(target), _) if expression_suspends(target) => {
+                self.reference(target);
+                self.emit(Instruction::PutReferenceValue);
+                self.emit(Instruction::Pop);
+            },
+            (ForTarget::Expression(target), _) => self.emit(Instruction::Assign(Rc::new(target.clone()))),
+            (ForTarget::ForDeclaration { binding, .. }, None) => self.initialize_binding(binding, false),
+            (ForTarget::ForDeclaration { binding, .. }, Some((names, is_constant))) => {
+                self.emit(Instruction::EnterDeclarations(names.clone(), *is_constant));
+                self.scopes += 1;
+                self.initialize_binding(binding, true);
+            },
+        }
+        self.loop_body(&statement.body, labels, break_to, continue_to);
+        if declaration.is_some() {
+            self.scopes -= 1;
+            self.emit(Instruction::ExitScope);
+        }
+        self.emit(Instruction::Goto(head));
+        self.handlers -= 1;
+        self.place(end);
+        if let Some(close) = close {
+            // <https://262.ecma-international.org/14.0/#sec-asynciteratorclose>
+            // runs like a `finally` block delaying the completion.
+            let after = self.label();
+            self.emit(Instruction::Goto(after));
+            self.place(close);
+            self.emit(Instruction::Await);
+            self.emit(Instruction::AsyncIteratorClosed);
+            self.emit(Instruction::ExitFinally);
+            self.place(after);
+        }
+    }
+
+    fn switch(&mut self, statement: &SwitchStatement, labels: Vec<String>) {
+        self.expression(&statement.discriminant);
+        let statements: Rc<[StatementListItem]> = statement.cases.iter().flat_map(|case| case.statements.clone()).collect();
+        let is_scoped = statements.iter().any(|item| lexically_scoped_declarations(item).next().is_some());
+        if is_scoped {
+            self.emit(Instruction::EnterBlock(statements));
+            self.scopes += 1;
+        }
+        let bodies: Vec<Label> = statement.cases.iter().map(|_| self.label()).collect();
+        let end = self.label();
+        for (case, body) in statement.cases.iter().zip(&bodies) {
+            if let Some(test) = &case.test {
+                self.expression(test);
+                self.emit(Instruction::Case(*body));
+            }
+        }
+        self.emit(Instruction::Pop);
+        let default = statement.cases.iter().zip(&bodies).find(|(case, _)| case.test.is_none());
+        self.emit(Instruction::Goto(default.map_or(end, |(_, body)| *body)));
+        let target = BreakTarget { kind: TargetKind::Switch, labels, break_to: self.jump_to(end), continue_to: None };
+        self.with_target(target, |lowering| {
+            for (case, body) in statement.cases.iter().zip(&bodies) {
+                lowering.place(*body);
+                lowering.statements(&case.statements);
+            }
+        });
+        self.place(end);
+        if is_scoped {
+            self.scopes -= 1;
+            self.emit(Instruction::ExitScope);
+        }
+    }
+
+    fn try_statement(&mut self, statement: &TryStatement) {
+        let catch = statement.handler.as_ref().map(|_| self.label());
+        let finally = statement.finalizer.as_ref().map(|_| self.label());
+        self.emit(Instruction::EnterTry { catch, finally });
+        self.handlers += 1;
+        self.block(&statement.block);
+        self.handlers -= 1;
+        self.emit(Instruction::ExitTry);
+        if let (Some(handler), Some(catch)) = (&statement.handler, catch) {
+            let end = self.label();
+            self.emit(Instruction::Goto(end));
+            self.place(catch);
+            // The `finally` block still runs after the `catch` one.
+            if finally.is_some() {
+                self.handlers += 1;
+            }
+            if let Some(parameter) = &handler.parameter {
+                let names = parameter.bound_names().into_iter().map(|name| name.name.clone()).collect();
+                self.emit(Instruction::EnterDeclarations(names, false));
+                self.scopes += 1;
+                self.initialize_binding(parameter, true);
+                self.block(&handler.body);
+                self.scopes -= 1;
+                self.emit(Instruction::ExitScope);
+            } else {
+                self.emit(Instruction::Pop);
+                self.block(&handler.body);
+            }
+            if finally.is_some() {
+                self.handlers -= 1;
+                self.emit(Instruction::ExitTry);
+            }
+            self.place(end);
+        }
+        if let (Some(finalizer), Some(finally)) = (&statement.finalizer, finally) {
+            self.emit(Instruction::EnterFinally);
+            self.place(finally);
+            self.handlers += 1;
+            self.block(finalizer);
+            self.handlers -= 1;
+            self.emit(Instruction::ExitFinally);
+        }
+    }
+
+    /// Lower an expression pushing its value.
+    fn expression(&mut self, expression: &Expression) {
+        crate::grow_stack(|| {
+            if !expression_suspends(expression) {
+                self.emit(Instruction::Expression(Rc::new(expression.clone())));
+                return;
+            }
+            match expression {
+                Expression::AwaitExpression(expression) => {
+                    self.expression(&expression.argument);
+                    self.emit(Instruction::Await);
+                },
+                Expression::YieldExpression(expression) => {
+                    match &expression.argument {
+                        Some(argument) => self.expression(argument),
+                        None => self.emit(Instruction::Push(Value::Undefined)),
+                    }
+                    if expression.is_delegate {
+                        self.emit(Instruction::GetIterator(expression.range.clone()));
+                        self.emit(Instruction::YieldDelegate);
+                    } else {
+                        self.emit(Instruction::Yield);
+                    }
+                },
+                Expression::ParenthesizedExpression(expression) => self.expression(&expression.expression),
+                Expression::BinaryExpression(expression) => {
+                    self.expression(&expression.left);
+                    if matches!(expression.operator, BinaryOperator::And | BinaryOperator::Or | BinaryOperator::NullishCoalescence) {
+                        let end = self.label();
+                        self.emit(Instruction::ShortCircuit(expression.operator, end));
+                        self.expression(&expression.right);
+                        self.place(end);
+                    } else {
+                        self.expression(&expression.right);
+                        self.emit(Instruction::Binary(expression.operator));
+                    }
+                },
+                Expression::PrivateInExpression(expression) => {
+                    self.expression(&expression.object);
+                    self.emit(Instruction::PrivateIn(expression.name.clone()));
+                },
+                Expression::ConditionalExpression(expression) => {
+                    let alternative = self.label();
+                    let end = self.label();
+                    self.expression(&expression.condition);
+                    self.emit(Instruction::GotoIfFalse(alternative));
+                    self.expression(&expression.consequent);
+                    self.emit(Instruction::Goto(end));
+                    self.place(alternative);
+                    self.expression(&expression.alternative);
+                    self.place(end);
+                },
+                Expression::CommaExpression(expression) => {
+                    for (index, item) in expression.expressions.iter().enumerate() {
+                        if index > 0 {
+                            self.emit(Instruction::Pop);
+                        }
+                        self.expression(item);
+                    }
+                },
+                Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::Delete => self.delete(&unary.operand),
+                Expression::UnaryExpression(unary) => {
+                    self.expression(&unary.operand);
+                    self.emit(Instruction::Unary(unary.operator));
+                },
+                Expression::UpdateExpression(update) => {
+                    self.reference(&update.operand);
+                    self.emit(Instruction::Update(update.operator, update.prefix));
+                },
+                Expression::AssignmentExpression(assignment) => self.assignment(assignment),
+                Expression::MemberExpression(_) | Expression::CallExpression(_) => self.chain(expression, None),
+                Expression::OptionalExpression(optional) => {
+                    let short = self.label();
+                    let end = self.label();
+                    self.chain(&optional.expression, Some(short));
+                    self.emit(Instruction::Goto(end));
+                    self.place(short);
+                    self.emit(Instruction::Push(Value::Undefined));
+                    self.place(end);
+                },
+                Expression::SuperProperty(property) => {
+                    self.super_reference(&property.property);
+                    self.emit(Instruction::GetReferenceValue);
+                    self.emit(Instruction::DropReference);
+                },
+                Expression::SuperCall(call) => {
+                    self.emit(Instruction::SuperConstructor);
+                    self.arguments(&call.arguments);
+                    self.emit(Instruction::SuperCall);
+                },
+                Expression::NewExpression(new) => {
+                    self.expression(&new.callee);
+                    self.arguments(new.arguments.as_deref().unwrap_or_default());
+                    self.emit(Instruction::Construct(callee_name(&new.callee).to_owned(), new.range.clone()));
+                },
+                Expression::ClassExpression(class) if class.decorators.is_empty() => {
+                    let name = class.name.as_ref().map_or("", |name| name.name.as_str());
+                    self.class(class, PropertyKey::from(name));
+                },
+                Expression::ArrayLiteral(literal) => {
+                    self.emit(Instruction::NewArray);
+                    for element in &literal.elements {
+                        match element {
+                            ArrayElement::Elision(_) => self.emit(Instruction::ArrayHole),
+                            ArrayElement::AssignmentExpression(expression) => {
+                                self.expression(expression);
+                                self.emit(Instruction::ArrayAppend);
+                            },
+                            ArrayElement::SpreadElement(expression, range) => {
+                                self.expression(expression);
+                                self.emit(Instruction::ArraySpread(range.clone()));
+                            },
+                        }
+                    }
+                },
+                Expression::ObjectLiteral(literal) => {
+                    self.emit(Instruction::NewObject);
+                    for property in &literal.properties {
+                        self.property_definition(property);
+                    }
+                },
+                Expression::TemplateLiteral(template) => {
+                    for substitution in &template.substitutions {
+                        self.expression(substitution);
+                        self.emit(Instruction::ToString);
+                    }
+                    let strings = template.strings.iter().map(|string| {
+                        JsString::from_code_units(string.cooked.as_deref().unwrap_or_default())
+                    });
+                    self.emit(Instruction::Template(strings.collect()));
+                },
+                // Decorators, tagged templates and `import()` fail once
+                // evaluation reaches them, whether they suspend or not.
+                _ => self.emit(Instruction::Expression(Rc::new(expression.clone()))),
+            }
+        });
+    }
+
+    /// Lower a key of a public member.
+    fn property_key(&mut self, property: &MemberProperty) {
+        match property {
+            MemberProperty::IdentifierName(name, _) => self.emit(Instruction::Push(Value::from(name.as_str()))),
+            MemberProperty::Expression(expression) => {
+                self.expression(expression);
+                self.emit(Instruction::ToPropertyKey);
+            },
+            MemberProperty::PrivateIdentifier(..) => unreachable!("private members have no keys"),
+        }
+    }
+
+    /// Lower a key of a property name.
+    fn property_name(&mut self, name: &PropertyName) {
+        match name {
+            PropertyName::ComputedPropertyName(name, _) if expression_suspends(name) => {
+                self.expression(name);
+                self.emit(Instruction::ToPropertyKey);
+            },
+            name => self.emit(Instruction::PropertyName(Rc::new(name.clone()))),
+        }
+    }
+
+    /// Lower an expression pushing its value, naming an anonymous function
+    /// or class after `name`.
+    fn named(&mut self, expression: &Expression, name: &str) {
+        match expression {
+            expression if !expression_suspends(expression) => {
+                self.emit(Instruction::NamedExpression(Rc::new(expression.clone()), PropertyKey::from(name)));
+            },
+            Expression::ClassExpression(class) if class.name.is_none() && class.decorators.is_empty() => {
+                self.class(class, PropertyKey::from(name));
+            },
+            expression => self.expression(expression),
+        }
+    }
+
+    /// Lower an assignment target pushing a reference to it.
+    fn reference(&mut self, target: &Expression) {
+        match target {
+            target if !expression_suspends(target) => self.emit(Instruction::Reference(Rc::new(target.clone()))),
+            Expression::ParenthesizedExpression(parenthesized) => self.reference(&parenthesized.expression),
+            Expression::MemberExpression(member) => {
+                self.expression(&member.object);
+                match &member.property {
+                    MemberProperty::PrivateIdentifier(identifier, _) => self.emit(Instruction::PrivateReference(identifier.clone())),
+                    property => {
+                        self.property_key(property);
+                        self.emit(Instruction::PropertyReference);
+                    },
+                }
+            },
+            Expression::SuperProperty(property) => self.super_reference(&property.property),
+            _ => unreachable!("early errors reject other assignment targets"),
+        }
+    }
+
+    /// Lower a property of `super` pushing a reference to it.
+    fn super_reference(&mut self, property: &MemberProperty) {
+        self.emit(Instruction::SuperThis);
+        self.property_key(property);
+        self.emit(Instruction::SuperReference);
+    }
+
+    /// Lower an operand of `delete` pushing whether it is deleted.
+    fn delete(&mut self, operand: &Expression) {
+        match operand {
+            Expression::ParenthesizedExpression(parenthesized) => self.delete(&parenthesized.expression),
+            Expression::MemberExpression(_) | Expression::SuperProperty(_) => {
+                self.reference(operand);
+                self.emit(Instruction::Delete);
+            },
+            Expression::OptionalExpression(optional) if matches!(*optional.expression, Expression::MemberExpression(_)) => {
+                let Expression::MemberExpression(member) = &*optional.expression else {
+                    unreachable!("matched above");
+                };
+                // A short-circuiting chain deletes nothing.
+                let short = self.label();
+                let end = self.label();
+                self.member_base(member, Some(short));
+                self.property_key(&member.property);
+                self.emit(Instruction::PropertyReference);
+                self.emit(Instruction::Delete);
+                self.emit(Instruction::Goto(end));
+                self.place(short);
+                self.emit(Instruction::Push(Value::Boolean(true)));
+                self.place(end);
+            },
+            operand => {
+                self.expression(operand);
+                self.emit(Instruction::Unary(UnaryOperator::Delete));
+            },
+        }
+    }
+
+    /// Lower a member or a call pushing its value; a link of an optional
+    /// chain short-circuiting on a nullish value goes to `short`, which is
+    /// `None` outside optional chains.
+    fn chain(&mut self, expression: &Expression, short: Option<Label>) {
+        match expression {
+            Expression::MemberExpression(member) if short.is_some() || expression_suspends(expression) => {
+                self.member_base(member, short);
+                match &member.property {
+                    MemberProperty::PrivateIdentifier(identifier, _) => {
+                        self.emit(Instruction::PrivateReference(identifier.clone()));
+                        self.emit(Instruction::GetReferenceValue);
+                        self.emit(Instruction::DropReference);
+                    },
+                    property => {
+                        self.property_key(property);
+                        self.emit(Instruction::GetProperty);
+                    },
+                }
+            },
+            Expression::CallExpression(call) if short.is_some() || expression_suspends(expression) => {
+                self.callee(&call.callee, short);
+                if call.optional {
+                    self.emit(Instruction::OptionalCall(short.expect("optional calls are in optional chains")));
+                }
+                self.arguments(&call.arguments);
+                self.emit(Instruction::Call(callee_name(&call.callee).to_owned(), call.range.clone()));
+            },
+            expression => self.expression(expression),
+        }
+    }
+
+    /// Lower the object of a member pushing its value, ending an optional
+    /// chain at `?.` if it is nullish.
+    fn member_base(&mut self, member: &MemberExpression, short: Option<Label>) {
+        self.chain(&member.object, short);
+        if member.optional {
+            self.emit(Instruction::OptionalChain(short.expect("optional members are in optional chains")));
+        }
+    }
+
+    fn assignment(&mut self, assignment: &AssignmentExpression) {
+        if assignment.operator == AssignmentOperator::Assignment && is_pattern(&assignment.target) {
+            self.expression(&assignment.value);
+            self.destructuring_assignment(&assignment.target);
+            return;
+        }
+        self.reference(&assignment.target);
+        match compound_operator(assignment.operator) {
+            None => {
+                self.assigned_value(&assignment.target, &assignment.value);
+                self.emit(Instruction::PutReferenceValue);
+            },
+            Some(operator @ (BinaryOperator::And | BinaryOperator::Or | BinaryOperator::NullishCoalescence)) => {
+                let short_circuit = self.label();
+                let end = self.label();
+                self.emit(Instruction::GetReferenceValue);
+                self.emit(Instruction::ShortCircuit(operator, short_circuit));
+                self.assigned_value(&assignment.target, &assignment.value);
+                self.emit(Instruction::PutReferenceValue);
+                self.emit(Instruction::Goto(end));
+                self.place(short_circuit);
+                self.emit(Instruction::DropReference);
+                self.place(end);
+            },
+            Some(operator) => {
+                self.emit(Instruction::GetReferenceValue);
+                self.expression(&assignment.value);
+                self.emit(Instruction::Binary(operator));
+                self.emit(Instruction::PutReferenceValue);
+            },
+        }
+    }
+
+    /// Lower a value assigned to a target, naming an anonymous function or
+    /// class after an identifier it is assigned to.
+    fn assigned_value(&mut self, target: &Expression, value: &Expression) {
+        match target {
+            Expression::IdentifierReference(reference) => self.named(value, &reference.name),
+            _ => self.expression(value),
+        }
+    }
+
+    /// Lower a callee pushing a function and `this` to call it with.
+    fn callee(&mut self, callee: &Expression, short: Option<Label>) {
+        let is_link = matches!(callee, Expression::MemberExpression(_) | Expression::CallExpression(_));
+        match callee {
+            callee if !expression_suspends(callee) && (short.is_none() || !is_link) => {
+                self.emit(Instruction::Callee(Rc::new(callee.clone())));
+            },
+            Expression::MemberExpression(member) => {
+                self.member_base(member, short);
+                match &member.property {
+                    MemberProperty::PrivateIdentifier(identifier, _) => {
+                        self.emit(Instruction::PrivateReference(identifier.clone()));
+                        self.emit(Instruction::ReferenceCallee);
+                    },
+                    property => {
+                        self.property_key(property);
+                        self.emit(Instruction::MethodCallee);
+                    },
+                }
+            },
+            Expression::SuperProperty(property) => {
+                self.super_reference(&property.property);
+                self.emit(Instruction::ReferenceCallee);
+            },
+            Expression::ParenthesizedExpression(parenthesized)
+                if is_reference(&parenthesized.expression) || matches!(*parenthesized.expression, Expression::OptionalExpression(_)) =>
+            {
+                self.callee(&parenthesized.expression, None);
+            },
+            // `(a?.b)()` calls the property with `a` as `this`, or calls
+            // `undefined` once the chain short-circuits.
+            Expression::OptionalExpression(optional) if matches!(*optional.expression, Expression::MemberExpression(_)) => {
+                let short = self.label();
+                let end = self.label();
+                self.callee(&optional.expression, Some(short));
+                self.emit(Instruction::Goto(end));
+                self.place(short);
+                self.emit(Instruction::Push(Value::Undefined));
+                self.emit(Instruction::Push(Value::Undefined));
+                self.place(end);
+            },
+            callee => {
+                self.chain(callee, short);
+                self.emit(Instruction::Push(Value::Undefined));
+            },
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Argument]) {
+        self.emit(Instruction::NewList);
+        for argument in arguments {
+            match argument {
+                Argument::AssignmentExpression(expression) => {
+                    self.expression(expression);
+                    self.emit(Instruction::Append);
+                },
+                Argument::SpreadElement(expression, range) => {
+                    self.expression(expression);
+                    self.emit(Instruction::Spread(range.clone()));
+                },
+            }
+        }
+    }
+
+    fn property_definition(&mut self, property: &PropertyDefinition) {
+        match property {
+            PropertyDefinition::Property { name, value } if property_suspends(property) && !is_proto_setter(name) => {
+                self.property_name(name);
+                if expression_suspends(value) {
+                    self.expression(value);
+                    self.emit(Instruction::DefineDataProperty);
+                } else {
+                    self.emit(Instruction::DefineNamedProperty(Rc::new(value.clone())));
+                }
+            },
+            PropertyDefinition::MethodDefinition(method) if property_suspends(property) => {
+                let ClassElementName::PropertyName(name) = &method.name else {
+                    unreachable!("methods of object literals have no private names");
+                };
+                self.property_name(name);
+                self.emit(Instruction::DefineMethod(method.clone()));
+            },
+            PropertyDefinition::SpreadProperty(expression, _) if expression_suspends(expression) => {
+                self.expression(expression);
+                self.emit(Instruction::CopyDataProperties);
+            },
+            property => self.emit(Instruction::DefineProperty(Rc::new(property.clone()))),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-classdefinitionevaluation>
+    /// of a class without decorators pushing it: the heritage and computed
+    /// keys are evaluated first, the rest of the class at once.
+    fn class(&mut self, class: &Class, name: PropertyKey) {
+        let class = Rc::new(class.clone());
+        self.emit(Instruction::EnterClass(class.clone()));
+        self.scopes += 1;
+        if let Some(heritage) = &class.heritage {
+            self.expression(heritage);
+        }
+        self.emit(Instruction::EnterClassBody(class.clone()));
+        self.handlers += 1;
+        for name in computed_names(&class) {
+            self.property_name(name);
+        }
+        self.emit(Instruction::DefineClass(class, name));
+        self.handlers -= 1;
+        self.scopes -= 1;
+        self.emit(Instruction::ExitScope);
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-bindinginitialization>
+    /// of the value on top, popping it; bindings of the `LexicalEnvironment`
+    /// are initialized if `is_lexical` is set, resolved bindings are
+    /// assigned to otherwise.
+    fn initialize_binding(&mut self, binding: &Binding, is_lexical: bool) {
+        match binding {
+            binding if !binding_suspends(binding) => self.emit(Instruction::InitializeBinding(Rc::new(binding.clone()), is_lexical)),
+            Binding::ObjectBindingPattern(pattern) => {
+                self.emit(Instruction::RequireObjectCoercible(binding.range()));
+                let has_rest = pattern.rest.is_some();
+                if has_rest {
+                    self.emit(Instruction::NewList);
+                }
+                for property in &pattern.properties {
+                    match property {
+                        BindingProperty::SingleNameBinding { name, initializer } => {
+                            self.emit(Instruction::Push(Value::from(name.name.as_str())));
+                            self.single_name_binding(name, initializer.as_ref(), is_lexical, Instruction::PatternProperty(has_rest));
+                        },
+                        BindingProperty::Property { name, value } => {
+                            self.property_name(name);
+                            self.binding_element(value, is_lexical, Instruction::PatternProperty(has_rest));
+                        },
+                    }
+                }
+                if let Some(rest) = &pattern.rest {
+                    if !is_lexical {
+                        self.emit(Instruction::ResolveBinding(rest.name.clone()));
+                    }
+                    self.emit(Instruction::RestObject);
+                    self.bind_name(rest, is_lexical);
+                }
+                self.emit(Instruction::Pop);
+            },
+            Binding::ArrayBindingPattern(pattern) => {
+                self.emit(Instruction::PatternIterate(binding.range()));
+                self.handlers += 1;
+                for element in &pattern.elements {
+                    if let Some(element) = element {
+                        self.binding_element(element, is_lexical, Instruction::PatternStep);
+                    } else {
+                        self.emit(Instruction::PatternStep);
+                        self.emit(Instruction::Pop);
+                    }
+                }
+                match pattern.rest.as_deref() {
+                    Some(Binding::BindingIdentifier(rest)) => self.single_name_binding(rest, None, is_lexical, Instruction::PatternRest),
+                    Some(rest) => {
+                        self.emit(Instruction::PatternRest);
+                        self.initialize_binding(rest, is_lexical);
+                    },
+                    None => {},
+                }
+                self.handlers -= 1;
+                self.emit(Instruction::PatternClose);
+                self.emit(Instruction::Pop);
+            },
+            Binding::BindingIdentifier(_) => unreachable!("identifiers do not suspend"),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-keyedbindinginitialization>
+    /// of an element whose value `read` pushes.
+    fn binding_element(&mut self, element: &BindingElement, is_lexical: bool, read: Instruction) {
+        match &element.target {
+            Binding::BindingIdentifier(identifier) => {
+                self.single_name_binding(identifier, element.initializer.as_ref(), is_lexical, read);
+            },
+            target => {
+                self.emit(read);
+                if let Some(initializer) = &element.initializer {
+                    self.default_value(initializer, None);
+                }
+                self.initialize_binding(target, is_lexical);
+            },
+        }
+    }
+
+    /// A `SingleNameBinding` whose value `read` pushes, resolving the name
+    /// first unless it is initialized in the `LexicalEnvironment`.
+    fn single_name_binding(&mut self, identifier: &BindingIdentifier, initializer: Option<&Expression>, is_lexical: bool, read: Instruction) {
+        if !is_lexical {
+            self.emit(Instruction::ResolveBinding(identifier.name.clone()));
+        }
+        self.emit(read);
+        if let Some(initializer) = initializer {
+            self.default_value(initializer, Some(&identifier.name));
+        }
+        self.bind_name(identifier, is_lexical);
+    }
+
+    /// Pop a value, initializing a binding of the `LexicalEnvironment` with
+    /// it if `is_lexical` is set, or assigning it to the innermost reference
+    /// otherwise.
+    fn bind_name(&mut self, identifier: &BindingIdentifier, is_lexical: bool) {
+        if is_lexical {
+            self.emit(Instruction::InitializeBinding(Rc::new(Binding::BindingIdentifier(identifier.clone())), true));
+        } else {
+            self.emit(Instruction::PutReferenceValue);
+            self.emit(Instruction::Pop);
+        }
+    }
+
+    /// Replace **undefined** on top with a value of a default, naming an
+    /// anonymous function or class after the target if it is named.
+    fn default_value(&mut self, initializer: &Expression, name: Option<&str>) {
+        let end = self.label();
+        self.emit(Instruction::GotoIfDefined(end));
+        match name {
+            Some(name) => self.named(initializer, name),
+            None => self.expression(initializer),
+        }
+        self.place(end);
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-destructuringassignmentevaluation>
+    /// of the value on top, keeping it.
+    fn destructuring_assignment(&mut self, pattern: &Expression) {
+        match pattern {
+            pattern if !expression_suspends(pattern) => self.emit(Instruction::DestructuringAssignment(Rc::new(pattern.clone()))),
+            Expression::ObjectLiteral(literal) => {
+                self.emit(Instruction::RequireObjectCoercible(pattern.range()));
+                let has_rest = literal.properties.iter().any(|property| matches!(property, PropertyDefinition::SpreadProperty(..)));
+                if has_rest {
+                    self.emit(Instruction::NewList);
+                }
+                for property in &literal.properties {
+                    match property {
+                        PropertyDefinition::IdentifierReference(identifier) => {
+                            self.emit(Instruction::ResolveBinding(identifier.name.clone()));
+                            self.emit(Instruction::Push(Value::from(identifier.name.as_str())));
+                            self.emit(Instruction::PatternProperty(has_rest));
+                            self.emit(Instruction::PutReferenceValue);
+                            self.emit(Instruction::Pop);
+                        },
+                        PropertyDefinition::CoverInitializedName { name, initializer } => {
+                            self.emit(Instruction::ResolveBinding(name.name.clone()));
+                            self.emit(Instruction::Push(Value::from(name.name.as_str())));
+                            self.emit(Instruction::PatternProperty(has_rest));
+                            self.default_value(initializer, Some(&name.name));
+                            self.emit(Instruction::PutReferenceValue);
+                            self.emit(Instruction::Pop);
+                        },
+                        PropertyDefinition::Property { name, value } => {
+                            self.property_name(name);
+                            self.assignment_element(value, Instruction::PatternProperty(has_rest));
+                        },
+                        PropertyDefinition::SpreadProperty(target, _) => {
+                            self.reference(target);
+                            self.emit(Instruction::RestObject);
+                            self.emit(Instruction::PutReferenceValue);
+                            self.emit(Instruction::Pop);
+                        },
+                        PropertyDefinition::MethodDefinition(_) => unreachable!("early errors reject methods in patterns"),
+                    }
+                }
+            },
+            Expression::ArrayLiteral(literal) => {
+                self.emit(Instruction::PatternIterate(pattern.range()));
+                self.handlers += 1;
+                for element in &literal.elements {
+                    match element {
+                        ArrayElement::Elision(_) => {
+                            self.emit(Instruction::PatternStep);
+                            self.emit(Instruction::Pop);
+                        },
+                        ArrayElement::AssignmentExpression(element) => self.assignment_element(element, Instruction::PatternStep),
+                        ArrayElement::SpreadElement(target, _) => self.assignment_element(target, Instruction::PatternRest),
+                    }
+                }
+                self.handlers -= 1;
+                self.emit(Instruction::PatternClose);
+            },
+            _ => unreachable!("only object and array literals are assignment patterns"),
+        }
+    }
+
+    /// An element of an assignment pattern whose value `read` pushes; the
+    /// target is evaluated first unless it is a nested pattern.
+    fn assignment_element(&mut self, element: &Expression, read: Instruction) {
+        let (target, initializer) = split_default(element);
+        if is_pattern(target) {
+            self.emit(read);
+            if let Some(initializer) = initializer {
+                self.default_value(initializer, None);
+            }
+            self.destructuring_assignment(target);
+            self.emit(Instruction::Pop);
+            return;
+        }
+        self.reference(target);
+        self.emit(read);
+        if let Some(initializer) = initializer {
+            let name = match target {
+                Expression::IdentifierReference(reference) => Some(reference.name.as_str()),
+                _ => None,
+            };
+            self.default_value(initializer, name);
+        }
+        self.emit(Instruction::PutReferenceValue);
+        self.emit(Instruction::Pop);
+    }
+}
+
+/// Computed names of class elements in the order they are evaluated.
+fn computed_names(class: &Class) -> impl Iterator<Item = &PropertyName> {
+    class.elements.iter().filter_map(|element| {
+        let name = match element {
+            ClassElement::MethodDefinition { method, .. } => &method.name,
+            ClassElement::FieldDefinition { name, .. } => name,
+            ClassElement::ClassStaticBlock(..) => return None,
+        };
+        match name {
+            ClassElementName::PropertyName(name @ PropertyName::ComputedPropertyName(..)) => Some(name),
+            _ => None,
+        }
+    })
+}
+
+fn item_suspends(item: &StatementListItem) -> bool {
+    match item {
+        StatementListItem::Statement(statement) => statement_suspends(statement),
+        StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) => list_suspends(list),
+        StatementListItem::Declaration(Declaration::ClassDeclaration(class)) => class_suspends(class),
+        StatementListItem::Declaration(Declaration::FunctionDeclaration(_)) => false,
+    }
+}
+
+/// The value of an awaited result of `next` of an async iterator, `None`
+/// if it is done.
+fn async_iterator_value(context: &mut Context, result: &Value) -> Result<Option<Value>, JsError> {
+    let Value::Object(object) = result else {
+        return Err(JsError::type_error("iterator result is not an object"));
+    };
+    if object.get(context, &PropertyKey::from("done"), result)?.to_boolean() {
+        return Ok(None);
+    }
+    object.get(context, &PropertyKey::from("value"), result).map(Some)
+}
+
+/// Whether a statement contains `yield` or `await` outside nested
+/// functions.
+fn statement_suspends(statement: &Statement) -> bool {
+    match statement {
+        Statement::BlockStatement(block) => block.statements.iter().any(item_suspends),
+        Statement::VariableStatement(list) => list_suspends(list),
+        Statement::EmptyStatement(_)
+        | Statement::ContinueStatement(_)
+        | Statement::BreakStatement(_)
+        | Statement::DebuggerStatement(_) => false,
+        Statement::ExpressionStatement(expression, _) => expression_suspends(expression),
+        Statement::IfStatement(statement) => {
+            expression_suspends(&statement.condition)
+                || statement_suspends(&statement.consequent)
+                || statement.alternative.as_deref().is_some_and(statement_suspends)
+        },
+        Statement::DoWhileStatement(statement) => statement_suspends(&statement.body) || expression_suspends(&statement.condition),
+        Statement::WhileStatement(statement) => expression_suspends(&statement.condition) || statement_suspends(&statement.body),
+        Statement::ForStatement(statement) => {
+            let init_suspends = match &statement.init {
+                Some(ForInit::Expression(expression)) => expression_suspends(expression),
+                Some(ForInit::VariableDeclarationList(list)) => list_suspends(list),
+                None => false,
+            };
+            init_suspends
+                || statement.condition.as_ref().is_some_and(expression_suspends)
+                || statement.update.as_ref().is_some_and(expression_suspends)
+                || statement_suspends(&statement.body)
+        },
+        Statement::ForInStatement(statement) | Statement::ForOfStatement(statement) => {
+            let target_suspends = match &statement.target {
+                ForTarget::Expression(expression) => expression_suspends(expression),
+                ForTarget::ForDeclaration { binding, .. } => binding_suspends(binding),
+            };
+            statement.is_await || target_suspends || expression_suspends(&statement.source) || statement_suspends(&statement.body)
+        },
+        Statement::ReturnStatement(statement) => statement.argument.as_ref().is_some_and(expression_suspends),
+        Statement::WithStatement(statement) => expression_suspends(&statement.object) || statement_suspends(&statement.body),
+        Statement::SwitchStatement(statement) => {
+            expression_suspends(&statement.discriminant)
+                || statement.cases.iter().any(|case| {
+                    case.test.as_ref().is_some_and(expression_suspends) || case.statements.iter().any(item_suspends)
+                })
+        },
+        Statement::LabelledStatement(statement) => match &*statement.body {
+            LabelledItem::Statement(statement) => statement_suspends(statement),
+            LabelledItem::FunctionDeclaration(_) => false,
+        },
+        Statement::ThrowStatement(statement) => expression_suspends(&statement.argument),
+        Statement::TryStatement(statement) => {
+            statement.block.statements.iter().any(item_suspends)
+                || statement.handler.as_ref().is_some_and(|handler| {
+                    handler.parameter.as_ref().is_some_and(binding_suspends) || handler.body.statements.iter().any(item_suspends)
+                })
+                || statement.finalizer.as_ref().is_some_and(|finalizer| finalizer.statements.iter().any(item_suspends))
+        },
+    }
+}
+
+fn list_suspends(list: &VariableDeclarationList) -> bool {
+    list.declarations.iter().any(|declaration| {
+        binding_suspends(&declaration.target) || declaration.initializer.as_ref().is_some_and(expression_suspends)
+    })
+}
+
+fn binding_suspends(binding: &Binding) -> bool {
+    match binding {
+        Binding::BindingIdentifier(_) => false,
+        Binding::ObjectBindingPattern(pattern) => pattern.properties.iter().any(|property| match property {
+            BindingProperty::SingleNameBinding { initializer, .. } => initializer.as_ref().is_some_and(expression_suspends),
+            BindingProperty::Property { name, value } => {
+                property_name_suspends(name)
+                    || binding_suspends(&value.target)
+                    || value.initializer.as_ref().is_some_and(expression_suspends)
+            },
+        }),
+        Binding::ArrayBindingPattern(pattern) => {
+            pattern.elements.iter().flatten().any(|element| {
+                binding_suspends(&element.target) || element.initializer.as_ref().is_some_and(expression_suspends)
+            }) || pattern.rest.as_deref().is_some_and(binding_suspends)
+        },
+    }
+}
+
+fn property_name_suspends(name: &PropertyName) -> bool {
+    matches!(name, PropertyName::ComputedPropertyName(expression, _) if expression_suspends(expression))
+}
+
+fn property_suspends(property: &PropertyDefinition) -> bool {
+    match property {
+        PropertyDefinition::IdentifierReference(_) => false,
+        PropertyDefinition::Property { name, value } => property_name_suspends(name) || expression_suspends(value),
+        PropertyDefinition::MethodDefinition(method) => {
+            matches!(&method.name, ClassElementName::PropertyName(name) if property_name_suspends(name))
+        },
+        PropertyDefinition::SpreadProperty(expression, _) => expression_suspends(expression),
+        PropertyDefinition::CoverInitializedName { initializer, .. } => expression_suspends(initializer),
+    }
+}
+
+fn class_suspends(class: &Class) -> bool {
+    let name_suspends = |name: &ClassElementName| matches!(name, ClassElementName::PropertyName(name) if property_name_suspends(name));
+    let decorators_suspend = |decorators: &[Decorator]| decorators.iter().any(|decorator| expression_suspends(&decorator.expression));
+    decorators_suspend(&class.decorators)
+        || class.heritage.as_deref().is_some_and(expression_suspends)
+        || class.elements.iter().any(|element| match element {
+            ClassElement::MethodDefinition { decorators, method, .. } => decorators_suspend(decorators) || name_suspends(&method.name),
+            ClassElement::FieldDefinition { decorators, name, .. } => decorators_suspend(decorators) || name_suspends(name),
+            ClassElement::ClassStaticBlock(..) => false,
+        })
+}
+
+fn arguments_suspend(arguments: &[Argument]) -> bool {
+    arguments.iter().any(|argument| match argument {
+        Argument::AssignmentExpression(expression) | Argument::SpreadElement(expression, _) => expression_suspends(expression),
+    })
+}
+
+fn member_property_suspends(property: &MemberProperty) -> bool {
+    matches!(property, MemberProperty::Expression(expression) if expression_suspends(expression))
+}
+
+/// Whether an expression contains `yield` or `await` outside nested
+/// functions.
+fn expression_suspends(expression: &Expression) -> bool {
+    match expression {
+        Expression::YieldExpression(_) | Expression::AwaitExpression(_) => true,
+        Expression::This(_)
+        | Expression::IdentifierReference(_)
+        | Expression::Literal(..)
+        | Expression::RegularExpressionLiteral(..)
+        | Expression::MetaProperty(..)
+        | Expression::FunctionExpression(_)
+        | Expression::ArrowFunction(_) => false,
+        Expression::ArrayLiteral(literal) => literal.elements.iter().any(|element| match element {
+            ArrayElement::Elision(_) => false,
+            ArrayElement::AssignmentExpression(expression) | ArrayElement::SpreadElement(expression, _) => {
+                expression_suspends(expression)
+            },
+        }),
+        Expression::ObjectLiteral(literal) => literal.properties.iter().any(property_suspends),
+        Expression::ClassExpression(class) => class_suspends(class),
+        Expression::TemplateLiteral(template) => template.substitutions.iter().any(expression_suspends),
+        Expression::ParenthesizedExpression(expression) => expression_suspends(&expression.expression),
+        Expression::MemberExpression(member) => expression_suspends(&member.object) || member_property_suspends(&member.property),
+        Expression::SuperProperty(property) => member_property_suspends(&property.property),
+        Expression::TaggedTemplate(template) => {
+            expression_suspends(&template.tag) || template.template.substitutions.iter().any(expression_suspends)
+        },
+        Expression::CallExpression(call) => expression_suspends(&call.callee) || arguments_suspend(&call.arguments),
+        Expression::SuperCall(call) => arguments_suspend(&call.arguments),
+        Expression::ImportCall(call) => {
+            expression_suspends(&call.specifier) || call.options.as_deref().is_some_and(expression_suspends)
+        },
+        Expression::OptionalExpression(expression) => expression_suspends(&expression.expression),
+        Expression::NewExpression(new) => {
+            expression_suspends(&new.callee) || new.arguments.as_deref().is_some_and(arguments_suspend)
+        },
+        Expression::UpdateExpression(update) => expression_suspends(&update.operand),
+        Expression::UnaryExpression(unary) => expression_suspends(&unary.operand),
+        Expression::BinaryExpression(binary) => expression_suspends(&binary.left) || expression_suspends(&binary.right),
+        Expression::PrivateInExpression(expression) => expression_suspends(&expression.object),
+        Expression::ConditionalExpression(conditional) => {
+            expression_suspends(&conditional.condition)
+                || expression_suspends(&conditional.consequent)
+                || expression_suspends(&conditional.alternative)
+        },
+        Expression::AssignmentExpression(assignment) => {
+            expression_suspends(&assignment.target) || expression_suspends(&assignment.value)
+        },
+        Expression::CommaExpression(expression) => expression.expressions.iter().any(expression_suspends),
+    }
+}
+
+/// How a suspended [`Frame`] goes on.
+pub(super) enum Resumption {
+    /// Start running the body.
+    Start,
+    /// Go on with a value of the expression it stopped at.
+    Next(Value),
+    /// Throw at the expression it stopped at.
+    Throw(Value),
+    /// Return from the body at the expression it stopped at, running
+    /// `finally` blocks.
+    Return(Value),
+}
+
+/// Why a [`Frame`] stopped running.
+pub(super) enum Suspension {
+    /// `yield` of a value.
+    Yield(Value),
+    /// `yield*` passing on an iterator result object of the inner iterator.
+    YieldResult(Value),
+    /// `await` of a value.
+    Await(Value),
+    /// The end of the body with the value it returns.
+    Return(Value),
+}
+
+/// A completion that leaves instructions in the middle.
+enum Abrupt {
+    Jump(Jump),
+    Return(Value),
+    Throw(JsError),
+}
+
+/// What follows an instruction.
+enum Step {
+    Continue,
+    Suspend(Suspension),
+    Abrupt(Abrupt),
+}
+
+/// A construct that handles abrupt completions going through it.
+enum Handler {
+    /// A `try` statement whose `catch` and `finally` blocks are not entered
+    /// yet, with the number of scopes around it.
+    Try { catch: Option<Label>, finally: Option<Label>, scopes: usize },
+    /// A `finally` block with the completion it delays, `None` if it runs
+    /// after a normal one.
+    Finally(Option<Abrupt>),
+    /// A `for`-`of` loop or an array pattern, closing its iterator unless
+    /// it is done.
+    Iterator(IteratorRecord),
+    /// A `for await` loop over an async iterator, or a sync one if
+    /// `is_sync` is set, closing it at `close` with `scopes` around the
+    /// loop; `is_stepping` is set while the next value is awaited, when
+    /// the iterator is not closed.
+    AsyncIterator { record: IteratorRecord, is_sync: bool, is_stepping: bool, close: Label, scopes: usize },
+    /// A `for`-`in` loop.
+    Enumeration(PropertyEnumeration),
+    /// A class body, restoring the `PrivateEnvironment` around it.
+    ClassBody(Option<PrivateEnvironment>),
+}
+
+/// A running or suspended evaluation of [`ResumableCode`] of a function.
+pub(super) struct Frame {
+    code: Rc<FunctionCode>,
+    /// The execution context of the body, `None` while it runs on the
+    /// execution context stack.
+    execution_context: Option<ExecutionContext>,
+    pc: usize,
+    stack: Vec<Value>,
+    references: Vec<Reference>,
+    lists: Vec<Vec<Value>>,
+    /// `LexicalEnvironment`s around entered scopes, the innermost last.
+    scopes: Vec<Environment>,
+    handlers: Vec<Handler>,
+    /// Whether `yield*` has suspended, passing resumptions to the inner
+    /// iterator.
+    is_delegating: bool,
+}
+
+impl Frame {
+    /// A frame at the start of a body, whose declarations are already
+    /// instantiated in the execution context.
+    pub(super) const fn new(code: Rc<FunctionCode>, execution_context: ExecutionContext) -> Self {
+        Self {
+            code,
+            execution_context: Some(execution_context),
+            pc: 0,
+            stack: Vec::new(),
+            references: Vec::new(),
+            lists: Vec::new(),
+            scopes: Vec::new(),
+            handlers: Vec::new(),
+            is_delegating: false,
+        }
+    }
+
+    /// Report the execution context and values the frame holds to the
+    /// heap.
+    pub(super) fn trace(&self, tracer: &mut Tracer) {
+        if let Some(execution_context) = &self.execution_context {
+            if let Some(function) = &execution_context.function {
+                tracer.object(function);
+            }
+            for environment in [&execution_context.lexical_environment, &execution_context.variable_environment].into_iter().flatten() {
+                tracer.environment(environment);
+            }
+        }
+        for value in self.stack.iter().chain(self.lists.iter().flatten()) {
+            tracer.value(value);
+        }
+        for environment in &self.scopes {
+            tracer.environment(environment);
+        }
+    }
+
+    /// Run the body from where it stopped until it suspends or completes.
+    pub(super) fn resume(&mut self, context: &mut Context, resumption: Resumption) -> Result<Suspension, JsError> {
+        let execution_context = self.execution_context.take().expect("a running frame is not resumed");
+        context.push_execution_context(execution_context);
+        let result = self.run(context, resumption);
+        self.execution_context = context.pop_execution_context();
+        result
+    }
+
+    fn run(&mut self, context: &mut Context, resumption: Resumption) -> Result<Suspension, JsError> {
+        let code = Rc::clone(&self.code);
+        let program = code.resumable();
+        let mut step = if std::mem::take(&mut self.is_delegating) {
+            self.delegate(context, &resumption)
+        } else {
+            match resumption {
+                Resumption::Start => Ok(Step::Continue),
+                Resumption::Next(value) => {
+                    self.stack.push(value);
+                    Ok(Step::Continue)
+                },
+                Resumption::Throw(value) => Err(JsError::thrown(value)),
+                Resumption::Return(value) => Ok(Step::Abrupt(Abrupt::Return(value))),
+            }
+        };
+        loop {
+            let abrupt = match step {
+                Ok(Step::Continue) => {
+                    let instruction = &program.instructions[self.pc];
+                    self.pc += 1;
+                    step = self.execute(context, program, instruction);
+                    continue;
+                },
+                Ok(Step::Suspend(suspension)) => return Ok(suspension),
+                Ok(Step::Abrupt(abrupt)) => abrupt,
+                Err(error) => Abrupt::Throw(error),
+            };
+            if let Some(value) = self.unwind(context, program, abrupt)? {
+                return Ok(Suspension::Return(value));
+            }
+            step = Ok(Step::Continue);
+        }
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("lowering balances the operand stack")
+    }
+
+    fn top(&self) -> &Value {
+        self.stack.last().expect("lowering balances the operand stack")
+    }
+
+    /// The object on top, which lowering puts there.
+    fn top_object(&self) -> Object {
+        match self.top() {
+            Value::Object(object) => object.clone(),
+            _ => unreachable!("literals are objects"),
+        }
+    }
+
+    fn pop_list(&mut self) -> Vec<Value> {
+        self.lists.pop().expect("lowering balances argument lists")
+    }
+
+    fn pop_key(&mut self, context: &mut Context) -> Result<PropertyKey, JsError> {
+        self.pop().to_property_key(context)
+    }
+
+    #[allow(clippy::too_many_lines)] // one arm per instruction
+    fn execute(&mut self, context: &mut Context, program: &ResumableCode, instruction: &Instruction) -> Result<Step, JsError> {
+        let code = Rc::clone(&self.code);
+        let mut evaluator = code.evaluator(context);
+        match instruction {
+            Instruction::Statement(item, targets) => match evaluator.evaluate_statement_list_item(item)? {
+                Completion::Normal(_) => {},
+                Completion::Return(value) => return Ok(Step::Abrupt(Abrupt::Return(value))),
+                completion => {
+                    let jump = find_jump(targets, &completion).expect("early errors reject unknown targets");
+                    return Ok(Step::Abrupt(Abrupt::Jump(jump)));
+                },
+            },
+            Instruction::Expression(expression) => {
+                let value = evaluator.evaluate(expression)?;
+                self.stack.push(value);
+            },
+            Instruction::Push(value) => self.stack.push(value.clone()),
+            Instruction::Pop => {
+                self.pop();
+            },
+            Instruction::Goto(label) => self.pc = program.position(*label),
+            Instruction::GotoIfFalse(label) => {
+                if !self.pop().to_boolean() {
+                    self.pc = program.position(*label);
+                }
+            },
+            Instruction::ShortCircuit(operator, label) => {
+                if short_circuits(*operator, self.top()) == Some(true) {
+                    self.pc = program.position(*label);
+                } else {
+                    self.pop();
+                }
+            },
+            Instruction::CheckLimits => evaluator.context.check_limits()?,
+            Instruction::Binary(operator) => {
+                let right = self.pop();
+                let left = self.pop();
+                let value = apply_binary_operator(evaluator.context, *operator, &left, &right)?;
+                self.stack.push(value);
+            },
+            Instruction::PrivateIn(identifier) => {
+                let object = self.pop();
+                let value = evaluator.private_in(identifier, &object)?;
+                self.stack.push(value);
+            },
+            Instruction::Unary(operator) => {
+                let value = self.pop();
+                let value = apply_unary_operator(evaluator.context, *operator, &value)?;
+                self.stack.push(value);
+            },
+            Instruction::Case(label) => {
+                let selector = self.pop();
+                if selector.is_strictly_equal(self.top()) {
+                    self.pop();
+                    self.pc = program.position(*label);
+                }
+            },
+            Instruction::Reference(expression) => {
+                let reference = evaluator.evaluate_reference(expression)?;
+                self.references.push(reference);
+            },
+            Instruction::ResolveBinding(name) => {
+                let reference = evaluator.resolve_binding(name)?;
+                self.references.push(reference);
+            },
+            Instruction::PropertyReference => {
+                let key = self.pop_key(evaluator.context)?;
+                let base = self.pop();
+                self.references.push(Reference::Property { base, key });
+            },
+            Instruction::PrivateReference(identifier) => {
+                let base = self.pop();
+                let name = evaluator.resolve_private_identifier(identifier);
+                self.references.push(Reference::Private { base, name });
+            },
+            Instruction::SuperThis => {
+                let this = evaluator.get_this_environment().get_this_binding()?;
+                self.stack.push(this);
+            },
+            Instruction::SuperReference => {
+                let key = self.pop_key(evaluator.context)?;
+                let this = self.pop();
+                let reference = evaluator.super_reference(this, key)?;
+                self.references.push(reference);
+            },
+            Instruction::GetReferenceValue => {
+                let reference = self.references.last().expect("lowering balances references");
+                let value = evaluator.get_value(reference)?;
+                self.stack.push(value);
+            },
+            Instruction::PutReferenceValue => {
+                let reference = self.references.pop().expect("lowering balances references");
+                evaluator.put_value(&reference, self.top().clone())?;
+            },
+            Instruction::DropReference => {
+                self.references.pop();
+            },
+            Instruction::Assign(target) => {
+                let value = self.pop();
+                let reference = evaluator.evaluate_reference(target)?;
+                evaluator.put_value(&reference, value).map_err(|error| error.at(&target.range()))?;
+            },
+            Instruction::DestructuringAssignment(pattern) => evaluator.destructuring_assignment(pattern, self.top())?,
+            Instruction::InitializeBinding(binding, is_lexical) => {
+                let value = self.pop();
+                let environment = is_lexical.then(|| evaluator.lexical_environment());
+                evaluator.binding_initialization(binding, value, environment.as_ref())?;
+            },
+            Instruction::RequireObjectCoercible(range) => require_object_coercible(self.top()).map_err(|error| error.at(range))?,
+            Instruction::PatternProperty(is_excluded) => {
+                let key = self.pop_key(evaluator.context)?;
+                let value = evaluator.get_property(self.top(), &key)?;
+                if *is_excluded {
+                    self.lists.last_mut().expect("lowering balances argument lists").push(Value::from(key));
+                }
+                self.stack.push(value);
+            },
+            Instruction::RestObject => {
+                let excluded = self.pop_list().into_iter().map(|key| key.to_property_key(evaluator.context));
+                let excluded = excluded.collect::<Result<Vec<_>, _>>()?;
+                let object = Object::new(Some(evaluator.realm().intrinsics().object_prototype.clone()));
+                evaluator.copy_data_properties(&object, self.top(), &excluded)?;
+                self.stack.push(Value::Object(object));
+            },
+            Instruction::PatternIterate(range) => {
+                let record = IteratorRecord::from_iterable(evaluator.context, self.top()).map_err(|error| error.at(range))?;
+                self.handlers.push(Handler::Iterator(record));
+            },
+            Instruction::PatternStep => {
+                let Some(Handler::Iterator(record)) = self.handlers.last_mut() else {
+                    unreachable!("lowering balances handlers");
+                };
+                let value = if record.done { None } else { record.step_value(evaluator.context)? };
+                self.stack.push(value.unwrap_or(Value::Undefined));
+            },
+            Instruction::PatternRest => {
+                let Some(Handler::Iterator(record)) = self.handlers.last_mut() else {
+                    unreachable!("lowering balances handlers");
+                };
+                let mut values = Vec::new();
+                while !record.done {
+                    values.extend(record.step_value(evaluator.context)?);
+                }
+                let prototype = evaluator.realm().intrinsics().array_prototype.clone();
+                self.stack.push(Value::Object(Object::new_array(Some(prototype), values)));
+            },
+            Instruction::PatternClose => {
+                let Some(Handler::Iterator(record)) = self.handlers.pop() else {
+                    unreachable!("lowering balances handlers");
+                };
+                if !record.done {
+                    record.close(evaluator.context, Ok(()))?;
+                }
+            },
+            Instruction::GotoIfDefined(label) => {
+                if self.top().is_undefined() {
+                    self.pop();
+                } else {
+                    self.pc = program.position(*label);
+                }
+            },
+            Instruction::NamedExpression(expression, name) => {
+                let value = evaluator.evaluate_named(expression, name)?;
+                self.stack.push(value);
+            },
+            Instruction::Update(operator, is_prefix) => {
+                let reference = self.references.pop().expect("lowering balances references");
+                let old_value = evaluator.get_value(&reference)?;
+                let (old_value, new_value) = apply_update_operator(evaluator.context, *operator, &old_value)?;
+                evaluator.put_value(&reference, new_value.clone())?;
+                self.stack.push(if *is_prefix { new_value } else { old_value });
+            },
+            Instruction::Delete => {
+                let reference = self.references.pop().expect("lowering balances references");
+                let is_deleted = evaluator.delete_reference(Some(reference))?;
+                self.stack.push(Value::Boolean(is_deleted));
+            },
+            Instruction::Callee(callee) => {
+                let (function, this) = evaluator.evaluate_callee(callee)?.unwrap_or((Value::Undefined, Value::Undefined));
+                self.stack.push(function);
+                self.stack.push(this);
+            },
+            Instruction::MethodCallee => {
+                let key = self.pop_key(evaluator.context)?;
+                let base = self.pop();
+                let object = evaluator.property_base(&base, &key, "read")?;
+                let function = object.get(evaluator.context, &key, &base)?;
+                self.stack.extend([function, base]);
+            },
+            Instruction::ReferenceCallee => {
+                let reference = self.references.pop().expect("lowering balances references");
+                let function = evaluator.get_value(&reference)?;
+                self.stack.extend([function, reference.this_value()]);
+            },
+            Instruction::OptionalChain(label) => {
+                if self.top().is_nullish() {
+                    self.pop();
+                    self.pc = program.position(*label);
+                }
+            },
+            Instruction::OptionalCall(label) => {
+                if self.stack[self.stack.len() - 2].is_nullish() {
+                    self.stack.truncate(self.stack.len() - 2);
+                    self.pc = program.position(*label);
+                }
+            },
+            Instruction::SuperConstructor => {
+                let environment = evaluator.get_this_environment();
+                let parent = get_super_constructor(evaluator.context, &environment)?;
+                self.stack.push(parent);
+            },
+            Instruction::NewList => self.lists.push(Vec::new()),
+            Instruction::Append => {
+                let value = self.pop();
+                self.lists.last_mut().expect("lowering balances argument lists").push(value);
+            },
+            Instruction::Spread(range) => {
+                let iterable = self.pop();
+                let list = self.lists.last_mut().expect("lowering balances argument lists");
+                evaluator.iterate(&iterable, |value| list.push(value)).map_err(|error| error.at(range))?;
+            },
+            Instruction::Call(name, range) => {
+                let arguments = self.pop_list();
+                let this = self.pop();
+                let value = match self.pop().as_object() {
+                    Some(function) if function.is_callable() => function.call(evaluator.context, &this, &arguments),
+                    _ => Err(JsError::type_error(format!("{name} is not a function"))),
+                };
+                self.stack.push(value.map_err(|error| error.at(range))?);
+            },
+            Instruction::Construct(name, range) => {
+                let arguments = self.pop_list();
+                let object = match self.pop().as_object() {
+                    Some(constructor) if constructor.is_constructor() => constructor.construct(evaluator.context, &arguments, None),
+                    _ => Err(JsError::type_error(format!("{name} is not a constructor"))),
+                };
+                self.stack.push(Value::Object(object.map_err(|error| error.at(range))?));
+            },
+            Instruction::SuperCall => {
+                let arguments = self.pop_list();
+                let parent = self.pop();
+                let environment = evaluator.get_this_environment();
+                let this = super_call(evaluator.context, &environment, &parent, &arguments)?;
+                self.stack.push(Value::Object(this));
+            },
+            Instruction::GetProperty => {
+                let key = self.pop_key(evaluator.context)?;
+                let base = self.pop();
+                let object = evaluator.property_base(&base, &key, "read")?;
+                let value = object.get(evaluator.context, &key, &base)?;
+                self.stack.push(value);
+            },
+            Instruction::ToPropertyKey => {
+                let key = self.pop_key(evaluator.context)?;
+                self.stack.push(Value::from(key));
+            },
+            Instruction::PropertyName(name) => {
+                let key = evaluator.evaluate_property_name(name)?;
+                self.stack.push(Value::from(key));
+            },
+            Instruction::NewArray => {
+                let prototype = evaluator.realm().intrinsics().array_prototype.clone();
+                self.stack.push(Value::Object(Object::new_array(Some(prototype), Vec::new())));
+            },
+            Instruction::ArrayAppend => {
+                let value = self.pop();
+                let array = self.top_object();
+                let length = array.length_of_array_like(evaluator.context)?;
+                array.create_data_property(length.to_string(), value);
+            },
+            Instruction::ArrayHole => {
+                let array = self.top_object();
+                let length = array.length_of_array_like(evaluator.context)?;
+                #[allow(clippy::cast_precision_loss)] // lengths are less than 2^53
+                let length = Value::Number((length + 1) as f64);
+                array.set(evaluator.context, PropertyKey::from("length"), length, &Value::Object(array.clone()))?;
+            },
+            Instruction::ArraySpread(range) => {
+                let iterable = self.pop();
+                let array = self.top_object();
+                let mut values = Vec::new();
+                evaluator.iterate(&iterable, |value| values.push(value)).map_err(|error| error.at(range))?;
+                let length = array.length_of_array_like(evaluator.context)?;
+                for (index, value) in (length..).zip(values) {
+                    array.create_data_property(index.to_string(), value);
+                }
+            },
+            Instruction::NewObject => {
+                let prototype = evaluator.realm().intrinsics().object_prototype.clone();
+                self.stack.push(Value::Object(Object::new(Some(prototype))));
+            },
+            Instruction::DefineProperty(property) => evaluator.evaluate_property_definition(&self.top_object(), property)?,
+            Instruction::DefineDataProperty => {
+                let value = self.pop();
+                let key = self.pop_key(evaluator.context)?;
+                self.top_object().create_data_property(key, value);
+            },
+            Instruction::DefineNamedProperty(value) => {
+                let key = self.pop_key(evaluator.context)?;
+                let value = evaluator.evaluate_named(value, &key)?;
+                self.top_object().create_data_property(key, value);
+            },
+            Instruction::DefineMethod(method) => {
+                let key = self.pop_key(evaluator.context)?;
+                evaluator.define_method_with_key(&self.top_object(), method, key)?;
+            },
+            Instruction::CopyDataProperties => {
+                let source = self.pop();
+                evaluator.copy_data_properties(&self.top_object(), &source, &[])?;
+            },
+            Instruction::EnterClass(class) => {
+                let environment = evaluator.new_class_environment(class);
+                self.scopes.push(evaluator.set_lexical_environment(environment));
+            },
+            Instruction::EnterClassBody(class) => {
+                let heritage = class.heritage.is_some().then(|| self.pop());
+                let (prototype_parent, constructor_parent) = evaluator.class_parents(heritage)?;
+                self.stack.extend([prototype_parent.map_or(Value::Null, Value::Object), Value::Object(constructor_parent)]);
+                let private_environment = evaluator.new_class_private_environment(class);
+                let execution_context = evaluator.context.running_execution_context_mut().expect("a frame runs in its execution context");
+                let outer = execution_context.private_environment.replace(private_environment);
+                self.handlers.push(Handler::ClassBody(outer));
+            },
+            Instruction::DefineClass(class, name) => {
+                let keys = self.stack.split_off(self.stack.len() - computed_names(class).count());
+                let keys = keys.into_iter().map(|key| key.to_property_key(evaluator.context)).collect::<Result<Vec<_>, _>>()?;
+                let (Value::Object(constructor_parent), prototype_parent) = (self.pop(), self.pop()) else {
+                    unreachable!("the constructor parent is an object");
+                };
+                let prototype_parent = match prototype_parent {
+                    Value::Object(object) => Some(object),
+                    _ => None,
+                };
+                let function = evaluator.evaluate_class_body(class, name, (prototype_parent, constructor_parent), Some(keys));
+                let Some(Handler::ClassBody(outer)) = self.handlers.pop() else {
+                    unreachable!("lowering balances handlers");
+                };
+                let execution_context = evaluator.context.running_execution_context_mut().expect("a frame runs in its execution context");
+                execution_context.private_environment = outer;
+                self.stack.push(Value::Object(function.map_err(|error| error.at(&class.range))?));
+            },
+            Instruction::ToString => {
+                let value = self.pop().to_string(evaluator.context)?;
+                self.stack.push(Value::String(value));
+            },
+            Instruction::Template(strings) => {
+                let substitutions = self.stack.split_off(self.stack.len() - (strings.len() - 1));
+                let mut text = strings[0].clone();
+                for (substitution, string) in substitutions.iter().zip(&strings[1..]) {
+                    let Value::String(substitution) = substitution else {
+                        unreachable!("substitutions are converted to strings");
+                    };
+                    text = text.concat(substitution).concat(string);
+                }
+                self.stack.push(Value::String(text));
+            },
+            Instruction::EnterBlock(statements) => {
+                let environment = Environment::new_declarative(Some(evaluator.lexical_environment()));
+                evaluator.block_declaration_instantiation(statements.iter(), &environment)?;
+                self.scopes.push(evaluator.set_lexical_environment(environment));
+            },
+            Instruction::EnterDeclarations(names, is_constant) => {
+                let environment = Environment::new_declarative(Some(evaluator.lexical_environment()));
+                for name in names.iter() {
+                    if *is_constant {
+                        environment.create_immutable_binding(name, true);
+                    } else {
+                        environment.create_mutable_binding(evaluator.context, name, false)?;
+                    }
+                }
+                self.scopes.push(evaluator.set_lexical_environment(environment));
+            },
+            Instruction::EnterWith(range) => {
+                let object = self.pop().to_object(evaluator.context).map_err(|error| error.at(range))?;
+                let environment = Environment::new_object(object, true, Some(evaluator.lexical_environment()));
+                self.scopes.push(evaluator.set_lexical_environment(environment));
+            },
+            Instruction::ExitScope => {
+                let outer = self.scopes.pop().expect("lowering balances scopes");
+                evaluator.set_lexical_environment(outer);
+            },
+            Instruction::CopyScope(names) => {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                evaluator.create_per_iteration_environment(&names)?;
+            },
+            Instruction::EnterTry { catch, finally } => {
+                self.handlers.push(Handler::Try { catch: *catch, finally: *finally, scopes: self.scopes.len() });
+            },
+            Instruction::ExitTry => {
+                self.handlers.pop();
+            },
+            Instruction::EnterFinally => self.handlers.push(Handler::Finally(None)),
+            Instruction::ExitFinally => {
+                if let Some(Handler::Finally(Some(abrupt))) = self.handlers.pop() {
+                    return Ok(Step::Abrupt(abrupt));
+                }
+            },
+            Instruction::Iterate(is_enumeration, label, range) => {
+                let source = self.pop();
+                let handler = if !*is_enumeration {
+                    Handler::Iterator(IteratorRecord::from_iterable(evaluator.context, &source).map_err(|error| error.at(range))?)
+                } else if source.is_nullish() {
+                    self.pc = program.position(*label);
+                    return Ok(Step::Continue);
+                } else {
+                    Handler::Enumeration(PropertyEnumeration::new(source.to_object(evaluator.context).map_err(|error| error.at(range))?))
+                };
+                self.handlers.push(handler);
+            },
+            Instruction::Next(label) => {
+                let next = match self.handlers.last_mut() {
+                    Some(Handler::Iterator(record)) => record.step_value(evaluator.context),
+                    Some(Handler::Enumeration(enumeration)) => enumeration.next(evaluator.context),
+                    _ => unreachable!("lowering balances handlers"),
+                };
+                match next {
+                    Ok(Some(value)) => self.stack.push(value),
+                    result => {
+                        self.handlers.pop();
+                        result?;
+                        self.pc = program.position(*label);
+                    },
+                }
+            },
+            Instruction::AsyncIterate(close, range) => {
+                let source = self.pop();
+                let (record, is_sync) = IteratorRecord::from_async_iterable(evaluator.context, &source).map_err(|error| error.at(range))?;
+                let scopes = self.scopes.len();
+                self.handlers.push(Handler::AsyncIterator { record, is_sync, is_stepping: false, close: *close, scopes });
+            },
+            Instruction::AsyncNext(label) => {
+                let Some(Handler::AsyncIterator { record, is_sync, is_stepping, .. }) = self.handlers.last_mut() else {
+                    unreachable!("lowering balances handlers");
+                };
+                *is_stepping = true;
+                let next = if *is_sync {
+                    record.step_value(evaluator.context)
+                } else {
+                    match &record.next_method {
+                        Value::Object(next) => next.call(evaluator.context, &Value::Object(record.iterator.clone()), &[]).map(Some),
+                        _ => Err(JsError::type_error("iterator has no next method")),
+                    }
+                };
+                match next {
+                    Ok(Some(value)) => self.stack.push(value),
+                    result => {
+                        self.handlers.pop();
+                        result?;
+                        self.pc = program.position(*label);
+                    },
+                }
+            },
+            Instruction::AsyncIteratorValue(label) => {
+                let Some(Handler::AsyncIterator { is_sync, is_stepping, .. }) = self.handlers.last_mut() else {
+                    unreachable!("lowering balances handlers");
+                };
+                *is_stepping = false;
+                if !*is_sync {
+                    let result = self.pop();
+                    match async_iterator_value(evaluator.context, &result) {
+                        Ok(Some(value)) => self.stack.push(value),
+                        result => {
+                            self.handlers.pop();
+                            result?;
+                            self.pc = program.position(*label);
+                        },
+                    }
+                }
+            },
+            Instruction::AsyncIteratorClosed => {
+                if !self.pop().is_object() {
+                    return Err(JsError::type_error("iterator return method returned a non-object"));
+                }
+            },
+            Instruction::Return => return Ok(Step::Abrupt(Abrupt::Return(self.pop()))),
+            Instruction::Throw(range) => return Err(JsError::thrown(self.pop()).at(range)),
+            Instruction::Yield => return Ok(Step::Suspend(Suspension::Yield(self.pop()))),
+            Instruction::Await => return Ok(Step::Suspend(Suspension::Await(self.pop()))),
+            Instruction::GetIterator(range) => {
+                let iterable = self.pop();
+                let record = IteratorRecord::from_iterable(evaluator.context, &iterable).map_err(|error| error.at(range))?;
+                self.stack.extend([Value::Object(record.iterator), record.next_method]);
+            },
+            Instruction::YieldDelegate => return self.delegate(evaluator.context, &Resumption::Next(Value::Undefined)),
+        }
+        Ok(Step::Continue)
+    }
+
+    /// Pass a resumption to the inner iterator of `yield*`, suspending with
+    /// its result until it is done.
+    fn delegate(&mut self, context: &mut Context, received: &Resumption) -> Result<Step, JsError> {
+        let next_method = self.pop();
+        let iterator = self.pop();
+        let Value::Object(iterator_object) = &iterator else {
+            unreachable!("GetIterator gives an object");
+        };
+        let result = match received {
+            Resumption::Start => unreachable!("a started frame delegates"),
+            Resumption::Next(value) => match &next_method {
+                Value::Object(next) => next.call(context, &iterator, std::slice::from_ref(value))?,
+                _ => return Err(JsError::type_error("iterator has no next method")),
+            },
+            Resumption::Throw(value) => {
+                let Some(throw) = iterator_object.get_method(context, &PropertyKey::from("throw"))? else {
+                    // The inner iterator gets a chance to clean up, while the
+                    // protocol is violated.
+                    let record = IteratorRecord { iterator: iterator_object.clone(), next_method, done: false };
+                    record.close(context, Ok(()))?;
+                    return Err(JsError::type_error("the iterator `yield*` delegates to has no throw method"));
+                };
+                throw.call(context, &iterator, std::slice::from_ref(value))?
+            },
+            Resumption::Return(value) => match iterator_object.get_method(context, &PropertyKey::from("return"))? {
+                Some(r#return) => r#return.call(context, &iterator, std::slice::from_ref(value))?,
+                None => return Ok(Step::Abrupt(Abrupt::Return(value.clone()))),
+            },
+        };
+        let Value::Object(result_object) = &result else {
+            return Err(JsError::type_error("iterator result is not an object"));
+        };
+        if !result_object.get(context, &PropertyKey::from("done"), &result)?.to_boolean() {
+            self.stack.extend([iterator, next_method]);
+            self.is_delegating = true;
+            return Ok(Step::Suspend(Suspension::YieldResult(result)));
+        }
+        let value = result_object.get(context, &PropertyKey::from("value"), &result)?;
+        if matches!(received, Resumption::Return(_)) {
+            return Ok(Step::Abrupt(Abrupt::Return(value)));
+        }
+        self.stack.push(value);
+        Ok(Step::Continue)
+    }
+
+    /// Go through handlers from the innermost one until one takes over an
+    /// abrupt completion, giving the value the body returns if none does.
+    fn unwind(&mut self, context: &mut Context, program: &ResumableCode, mut abrupt: Abrupt) -> Result<Option<Value>, JsError> {
+        loop {
+            match &abrupt {
+                Abrupt::Jump(jump) if jump.handlers == self.handlers.len() => {
+                    self.exit_scopes(context, jump.scopes);
+                    self.pc = program.position(jump.label);
+                    return Ok(None);
+                },
+                // Errors of unsupported features or running out of fuel
+                // skip `catch` and `finally`.
+                Abrupt::Throw(error) if !error.kind.is_catchable() => {
+                    let Abrupt::Throw(error) = abrupt else { unreachable!("matched above") };
+                    return Err(error);
+                },
+                _ => {},
+            }
+            let Some(handler) = self.handlers.pop() else {
+                return match abrupt {
+                    Abrupt::Return(value) => Ok(Some(value)),
+                    Abrupt::Throw(error) => Err(error),
+                    Abrupt::Jump(_) => unreachable!("jumps stay in the body"),
+                };
+            };
+            abrupt = match handler {
+                Handler::Try { catch, finally, scopes } => {
+                    self.exit_scopes(context, scopes);
+                    self.stack.clear();
+                    self.references.clear();
+                    self.lists.clear();
+                    match (catch, abrupt) {
+                        (Some(catch), Abrupt::Throw(error)) => {
+                            if finally.is_some() {
+                                self.handlers.push(Handler::Try { catch: None, finally, scopes });
+                            }
+                            self.stack.push(error.into_value(context));
+                            self.pc = program.position(catch);
+                            return Ok(None);
+                        },
+                        (_, abrupt) => match finally {
+                            Some(finally) => {
+                                self.handlers.push(Handler::Finally(Some(abrupt)));
+                                self.pc = program.position(finally);
+                                return Ok(None);
+                            },
+                            None => abrupt,
+                        },
+                    }
+                },
+                // A completion leaving a `finally` block replaces the one
+                // it delays, and an iterator whose next value is awaited is
+                // done.
+                Handler::Finally(_)
+                | Handler::Enumeration(_)
+                | Handler::Iterator(IteratorRecord { done: true, .. })
+                | Handler::AsyncIterator { is_stepping: true, .. } => abrupt,
+                Handler::ClassBody(outer) => {
+                    let execution_context = context.running_execution_context_mut().expect("a frame runs in its execution context");
+                    execution_context.private_environment = outer;
+                    abrupt
+                },
+                Handler::Iterator(record) | Handler::AsyncIterator { record, is_sync: true, is_stepping: false, .. } => match abrupt {
+                    Abrupt::Throw(error) => Abrupt::Throw(record.close(context, Err::<(), _>(error)).expect_err("the error stays")),
+                    abrupt => match record.close(context, Ok(())) {
+                        Ok(()) => abrupt,
+                        Err(error) => Abrupt::Throw(error),
+                    },
+                },
+                Handler::AsyncIterator { record, close, scopes, .. } => {
+                    let iterator = Value::Object(record.iterator.clone());
+                    let r#return = record.iterator.get_method(context, &PropertyKey::from("return"));
+                    match (abrupt, r#return) {
+                        // The error wins whatever `return` does, so its
+                        // result is not awaited.
+                        (Abrupt::Throw(error), r#return) => {
+                            if let Ok(Some(r#return)) = r#return {
+                                let _ = r#return.call(context, &iterator, &[]);
+                            }
+                            Abrupt::Throw(error)
+                        },
+                        (abrupt, Ok(None)) => abrupt,
+                        (_, Err(error)) => Abrupt::Throw(error),
+                        (abrupt, Ok(Some(r#return))) => match r#return.call(context, &iterator, &[]) {
+                            Ok(result) => {
+                                self.exit_scopes(context, scopes);
+                                self.stack.clear();
+                                self.references.clear();
+                                self.lists.clear();
+                                self.handlers.push(Handler::Finally(Some(abrupt)));
+                                self.stack.push(result);
+                                self.pc = program.position(close);
+                                return Ok(None);
+                            },
+                            Err(error) => Abrupt::Throw(error),
+                        },
+                    }
+                },
+            };
+        }
+    }
+
+    /// Leave scopes entered after the first `count` ones.
+    fn exit_scopes(&mut self, context: &mut Context, count: usize) {
+        if self.scopes.len() <= count {
+            return;
+        }
+        let outer = self.scopes.drain(count..).next().expect("a scope is left");
+        let execution_context = context.running_execution_context_mut().expect("a frame runs in its execution context");
+        execution_context.lexical_environment = Some(outer);
+    }
+}
@@ -0,0 +1,126 @@
+//! <https://262.ecma-international.org/14.0/#sec-scripts> evaluation.
+
+use super::functions::{legacy_block_functions, top_level_functions};
+use super::statements::Completion;
+use super::{Code, Evaluator};
+use crate::grammar::{Declaration, Expression, Script, StatementListItem, VariableKind};
+use crate::runtime::{Context, Environment, ErrorKind, ExecutionContext, JsError, Realm, Value};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-scriptevaluation>
+/// of a parsed script in a realm.
+///
+/// Deferred function bodies of the script are parsed from `source` on the
+/// first call.
+pub fn evaluate_script(context: &mut Context, realm: &Realm, script: &Script, source: Option<Rc<str>>) -> Result<Value, JsError> {
+    let environment = realm.global_environment().clone();
+    context.push_execution_context(ExecutionContext::with_environment(None, realm.clone(), environment.clone()));
+    let code = Code::with_source(source);
+    let mut evaluator = Evaluator { context, code: &code, is_strict: script.is_strict, tail_calls: false };
+    let result = evaluator
+        .global_declaration_instantiation(script, &environment)
+        .and_then(|()| evaluator.evaluate_statements(&script.body));
+    context.pop_execution_context();
+    match result? {
+        Completion::Normal(value) => Ok(value.unwrap_or(Value::Undefined)),
+        _ => unreachable!("early errors keep `return`, `break` and `continue` inside the script"),
+    }
+}
+
+/// Evaluate a standalone non-strict expression in the global environment of
+/// a realm.
+pub(super) fn evaluate_expression(context: &mut Context, realm: &Realm, expression: &Expression) -> Result<Value, JsError> {
+    let environment = realm.global_environment().clone();
+    context.push_execution_context(ExecutionContext::with_environment(None, realm.clone(), environment));
+    let code = Code::default();
+    let result = Evaluator { context, code: &code, is_strict: false, tail_calls: false }.evaluate(expression);
+    context.pop_execution_context();
+    result
+}
+
+impl Evaluator<'_> {
+    /// <https://262.ecma-international.org/14.0/#sec-globaldeclarationinstantiation>
+    /// with <https://262.ecma-international.org/14.0/#sec-web-compat-globaldeclarationinstantiation>
+    /// for non-strict scripts.
+    fn global_declaration_instantiation(&mut self, script: &Script, environment: &Environment) -> Result<(), JsError> {
+        let lexical_names = script.lexically_declared_names();
+        let var_names = script.var_declared_names();
+        for name in &lexical_names {
+            if environment.has_var_declaration(&name.name)
+                || environment.has_lexical_declaration(&name.name)
+                || environment.has_restricted_global_property(self.context, &name.name)?
+            {
+                return Err(redeclaration(&name.name).at(&name.range));
+            }
+        }
+        for name in &var_names {
+            if environment.has_lexical_declaration(&name.name) {
+                return Err(redeclaration(&name.name).at(&name.range));
+            }
+        }
+
+        let functions_to_initialize = top_level_functions(&script.body);
+        let mut declared_function_names = HashSet::new();
+        for function in &functions_to_initialize {
+            let name = function.name.as_ref().expect("declarations are named");
+            if !environment.can_declare_global_function(self.context, &name.name)? {
+                return Err(JsError::type_error(format!("cannot declare global function {}", name.name)).at(&name.range));
+            }
+            declared_function_names.insert(name.name.as_str());
+        }
+        let mut declared_var_names = Vec::new();
+        for name in &var_names {
+            if declared_function_names.contains(name.name.as_str()) || declared_var_names.contains(&name.name.as_str()) {
+                continue;
+            }
+            if !environment.can_declare_global_var(self.context, &name.name)? {
+                return Err(JsError::type_error(format!("cannot declare global variable {}", name.name)).at(&name.range));
+            }
+            declared_var_names.push(name.name.as_str());
+        }
+
+        if !script.is_strict {
+            let top_level_lexical_names = lexical_names.iter().map(|name| name.name.as_str()).collect();
+            let mut declared_function_or_var_names: HashSet<&str> =
+                declared_function_names.iter().chain(&declared_var_names).copied().collect();
+            for function in legacy_block_functions(&script.body, &top_level_lexical_names) {
+                let Some(name) = &function.name else { continue };
+                if environment.has_lexical_declaration(&name.name) || !environment.can_declare_global_var(self.context, &name.name)? {
+                    continue;
+                }
+                if declared_function_or_var_names.insert(name.name.as_str()) {
+                    environment.create_global_var_binding(self.context, &name.name, false)?;
+                }
+                self.code.hoisted_functions.borrow_mut().insert(function.range.clone());
+            }
+        }
+
+        for item in &script.body {
+            let StatementListItem::Declaration(declaration @ (Declaration::LexicalDeclaration(_) | Declaration::ClassDeclaration(_))) = item else {
+                continue;
+            };
+            let is_constant = matches!(declaration, Declaration::LexicalDeclaration(list) if list.kind == VariableKind::Const);
+            for name in declaration.bound_names() {
+                if is_constant {
+                    environment.create_immutable_binding(&name.name, true);
+                } else {
+                    environment.create_mutable_binding(self.context, &name.name, false)?;
+                }
+            }
+        }
+        for function in functions_to_initialize {
+            let name = function.name.as_ref().expect("declarations are named");
+            let object = self.instantiate_function_object(function, environment);
+            environment.create_global_function_binding(self.context, &name.name, Value::Object(object), false)?;
+        }
+        for name in declared_var_names {
+            environment.create_global_var_binding(self.context, name, false)?;
+        }
+        Ok(())
+    }
+}
+
+fn redeclaration(name: &str) -> JsError {
+    JsError::new(ErrorKind::SyntaxError, format!("identifier {name} has already been declared"))
+}
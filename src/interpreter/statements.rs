@@ -0,0 +1,722 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-statements-and-declarations>
+
+use super::expressions::is_pattern;
+use super::functions::TailCall;
+use super::Evaluator;
+use crate::grammar::{
+    Binding,
+    Block,
+    CatchClause,
+    Declaration,
+    DoWhileStatement,
+    ForInOfStatement,
+    ForInit,
+    ForStatement,
+    ForTarget,
+    Function,
+    LabelledItem,
+    Statement,
+    StatementListItem,
+    SwitchStatement,
+    TryStatement,
+    VariableDeclarationList,
+    VariableKind,
+    WhileStatement,
+};
+use crate::runtime::{
+    Context,
+    Environment,
+    IteratorRecord,
+    JsError,
+    Object,
+    PropertyKey,
+    Value,
+};
+use std::collections::{HashSet, VecDeque};
+use std::mem;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#sec-completion-record-specification-type>
+/// other than a throw completion, which is an `Err`; `None` values stand
+/// for **empty**.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Completion {
+    Normal(Option<Value>),
+    Return(Value),
+    /// A return of what a call in tail position returns, left to the
+    /// caller of the function.
+    TailCall(TailCall),
+    /// A `break` with an optional label.
+    Break(Option<String>, Option<Value>),
+    /// A `continue` with an optional label.
+    Continue(Option<String>, Option<Value>),
+}
+
+impl Completion {
+    /// <https://262.ecma-international.org/14.0/#sec-updateempty>
+    fn update_empty(self, value: Option<Value>) -> Self {
+        match self {
+            Self::Normal(None) => Self::Normal(value),
+            Self::Break(label, None) => Self::Break(label, value),
+            Self::Continue(label, None) => Self::Continue(label, value),
+            completion => completion,
+        }
+    }
+
+    const fn value(&self) -> Option<&Value> {
+        match self {
+            Self::Normal(value) | Self::Break(_, value) | Self::Continue(_, value) => value.as_ref(),
+            Self::Return(value) => Some(value),
+            Self::TailCall(_) => None,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-loopcontinues>
+    fn loop_continues(&self, labels: &[&str]) -> bool {
+        match self {
+            Self::Normal(_) | Self::Continue(None, _) => true,
+            Self::Continue(Some(label), _) => labels.contains(&label.as_str()),
+            _ => false,
+        }
+    }
+}
+
+/// Which of `for`-`in` or `for`-`of` a loop is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum IterationKind {
+    Enumerate,
+    Iterate,
+}
+
+/// Values a `for`-`in` or a `for`-`of` loop goes through.
+enum ForInOfIterator {
+    Enumerate(PropertyEnumeration),
+    Iterate(IteratorRecord),
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-enumerate-object-properties>:
+/// enumerable String keys of an object and its prototypes, each once,
+/// skipping ones deleted before they are reached.
+pub(super) struct PropertyEnumeration {
+    /// The object whose keys are being visited, `None` after the end of the
+    /// prototype chain.
+    object: Option<Object>,
+    /// Keys of `object` not visited yet, `None` until they are taken.
+    keys: Option<VecDeque<PropertyKey>>,
+    visited: HashSet<PropertyKey>,
+}
+
+impl PropertyEnumeration {
+    pub(super) fn new(object: Object) -> Self {
+        Self { object: Some(object), keys: None, visited: HashSet::new() }
+    }
+
+    /// The next key, `None` after the last one; proxies along the chain
+    /// run their `ownKeys`, `getOwnPropertyDescriptor` and
+    /// `getPrototypeOf` traps.
+    pub(super) fn next(&mut self, context: &mut Context) -> Result<Option<Value>, JsError> {
+        loop {
+            let Some(object) = self.object.clone() else {
+                return Ok(None);
+            };
+            if self.keys.is_none() {
+                let keys = object.try_own_property_keys(context)?.into_iter();
+                self.keys = Some(keys.filter(|key| matches!(key, PropertyKey::String(_))).collect());
+            }
+            let keys = self.keys.as_mut().expect("keys are taken above");
+            while let Some(key) = keys.pop_front() {
+                let Some(property) = object.try_get_own_property(context, &key)? else { continue };
+                // A shadowing property hides the one of a prototype even if
+                // it is not enumerable itself.
+                if self.visited.insert(key.clone()) && property.enumerable == Some(true) {
+                    return Ok(Some(Value::from(key)));
+                }
+            }
+            self.object = object.try_get_prototype_of(context)?;
+            self.keys = None;
+        }
+    }
+}
+
+impl Evaluator<'_> {
+    /// Evaluate a `StatementList` of a block, a `switch` case or a body.
+    pub(super) fn evaluate_statements(&mut self, statements: &[StatementListItem]) -> Result<Completion, JsError> {
+        let mut value = None;
+        for item in statements {
+            match self.evaluate_statement_list_item(item)? {
+                Completion::Normal(None) => {},
+                Completion::Normal(item_value) => value = item_value,
+                completion => return Ok(completion.update_empty(value)),
+            }
+        }
+        Ok(Completion::Normal(value))
+    }
+
+    pub(super) fn evaluate_statement_list_item(&mut self, item: &StatementListItem) -> Result<Completion, JsError> {
+        match item {
+            StatementListItem::Statement(statement) => self.evaluate_statement(statement),
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => {
+                self.evaluate_function_declaration(function)?;
+                Ok(Completion::Normal(None))
+            },
+            StatementListItem::Declaration(Declaration::ClassDeclaration(class)) => {
+                // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-bindingclassdeclarationevaluation>
+                let name = class.name.as_ref().expect("only an exported default class is anonymous");
+                let key = PropertyKey::from(name.name.as_str());
+                let function = crate::grow_stack(|| self.evaluate_class(class, &key)).map_err(|error| error.at(&class.range))?;
+                self.lexical_environment().initialize_binding(self.context, &name.name, Value::Object(function))?;
+                Ok(Completion::Normal(None))
+            },
+            StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) => {
+                self.evaluate_lexical_declaration(list)?;
+                Ok(Completion::Normal(None))
+            },
+        }
+    }
+
+    /// Evaluation of a function declaration, which is instantiated
+    /// beforehand; a hoisted one in a block gets its `var` binding
+    /// assigned as
+    /// <https://262.ecma-international.org/14.0/#sec-block-level-function-declarations-web-legacy-compatibility-semantics>
+    /// describes.
+    fn evaluate_function_declaration(&mut self, function: &Function) -> Result<(), JsError> {
+        let Some(name) = &function.name else {
+            return Ok(());
+        };
+        if self.code.is_hoisted(function) {
+            let value = self.lexical_environment().get_binding_value(self.context, &name.name, false)?;
+            self.variable_environment().set_mutable_binding(self.context, &name.name, value, false)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate_statement(&mut self, statement: &Statement) -> Result<Completion, JsError> {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::BlockStatement(block) => self.evaluate_block(block),
+                Statement::VariableStatement(list) => {
+                    self.evaluate_variable_declarations(list)?;
+                    Ok(Completion::Normal(None))
+                },
+                Statement::EmptyStatement(_) | Statement::DebuggerStatement(_) => Ok(Completion::Normal(None)),
+                Statement::ExpressionStatement(expression, _) => Ok(Completion::Normal(Some(self.evaluate(expression)?))),
+                Statement::IfStatement(statement) => {
+                    // <https://262.ecma-international.org/14.0/#sec-if-statement-runtime-semantics-evaluation>
+                    let completion = if self.evaluate(&statement.condition)?.to_boolean() {
+                        self.evaluate_statement(&statement.consequent)?
+                    } else if let Some(alternative) = &statement.alternative {
+                        self.evaluate_statement(alternative)?
+                    } else {
+                        Completion::Normal(None)
+                    };
+                    Ok(completion.update_empty(Some(Value::Undefined)))
+                },
+                Statement::DoWhileStatement(_)
+                | Statement::WhileStatement(_)
+                | Statement::ForStatement(_)
+                | Statement::ForInStatement(_)
+                | Statement::ForOfStatement(_)
+                | Statement::SwitchStatement(_)
+                | Statement::LabelledStatement(_) => self.labelled_evaluation(statement, &[]),
+                Statement::ContinueStatement(statement) => Ok(Completion::Continue(statement.label.clone(), None)),
+                Statement::BreakStatement(statement) => Ok(Completion::Break(statement.label.clone(), None)),
+                Statement::ReturnStatement(statement) => match &statement.argument {
+                    Some(argument) => self.evaluate_returned(argument),
+                    None => Ok(Completion::Return(Value::Undefined)),
+                },
+                Statement::WithStatement(statement) => {
+                    // <https://262.ecma-international.org/14.0/#sec-with-statement-runtime-semantics-evaluation>
+                    let object = self.evaluate(&statement.object)?;
+                    let object = object.to_object(self.context).map_err(|error| error.at(&statement.object.range()))?;
+                    let environment = Environment::new_object(object, true, Some(self.lexical_environment()));
+                    let completion = self.with_lexical_environment(environment, |evaluator| {
+                        evaluator.evaluate_statement(&statement.body)
+                    })?;
+                    Ok(completion.update_empty(Some(Value::Undefined)))
+                },
+                Statement::ThrowStatement(statement) => {
+                    let value = self.evaluate(&statement.argument)?;
+                    Err(JsError::thrown(value).at(&statement.range))
+                },
+                Statement::TryStatement(statement) => self.evaluate_try(statement),
+            }
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-block-runtime-semantics-evaluation>
+    fn evaluate_block(&mut self, block: &Block) -> Result<Completion, JsError> {
+        if block.statements.iter().all(|item| lexically_scoped_declarations(item).next().is_none()) {
+            return self.evaluate_statements(&block.statements);
+        }
+        let environment = Environment::new_declarative(Some(self.lexical_environment()));
+        self.block_declaration_instantiation(&block.statements, &environment)?;
+        self.with_lexical_environment(environment, |evaluator| evaluator.evaluate_statements(&block.statements))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-blockdeclarationinstantiation>
+    /// with <https://262.ecma-international.org/14.0/#sec-web-compat-blockdeclarationinstantiation>
+    /// for repeated function declarations of non-strict code.
+    pub(super) fn block_declaration_instantiation<'a>(
+        &mut self,
+        statements: impl IntoIterator<Item = &'a StatementListItem>,
+        environment: &Environment,
+    ) -> Result<(), JsError> {
+        let mut declared = HashSet::new();
+        let mut initialized = HashSet::new();
+        for item in statements {
+            for declaration in lexically_scoped_declarations(item) {
+                let is_constant = matches!(
+                    declaration,
+                    ScopedDeclaration::Declaration(Declaration::LexicalDeclaration(list)) if list.kind == VariableKind::Const
+                );
+                for name in declaration.bound_names() {
+                    if is_constant {
+                        environment.create_immutable_binding(name, true);
+                    } else if declared.insert(name) {
+                        environment.create_mutable_binding(self.context, name, false)?;
+                    }
+                }
+                if let ScopedDeclaration::Function(function) = declaration {
+                    let Some(name) = &function.name else { continue };
+                    let object = Value::Object(self.instantiate_function_object(function, environment));
+                    if initialized.insert(name.name.as_str()) {
+                        environment.initialize_binding(self.context, &name.name, object)?;
+                    } else {
+                        environment.set_mutable_binding(self.context, &name.name, object, false)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-variable-statement-runtime-semantics-evaluation>
+    fn evaluate_variable_declarations(&mut self, list: &VariableDeclarationList) -> Result<(), JsError> {
+        for declaration in &list.declarations {
+            let Some(initializer) = &declaration.initializer else { continue };
+            match &declaration.target {
+                Binding::BindingIdentifier(name) => {
+                    let reference = self.resolve_binding(&name.name)?;
+                    let value = self.evaluate_named(initializer, &PropertyKey::from(name.name.as_str()))?;
+                    self.put_value(&reference, value).map_err(|error| error.at(&declaration.range))?;
+                },
+                target => {
+                    let value = self.evaluate(initializer)?;
+                    self.binding_initialization(target, value, None)?;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-let-and-const-declarations-runtime-semantics-evaluation>
+    fn evaluate_lexical_declaration(&mut self, list: &VariableDeclarationList) -> Result<(), JsError> {
+        if matches!(list.kind, VariableKind::Using | VariableKind::AwaitUsing) {
+            return Err(JsError::unsupported("`using` declarations").at(&list.range));
+        }
+        let environment = self.lexical_environment();
+        for declaration in &list.declarations {
+            let value = match (&declaration.initializer, &declaration.target) {
+                (None, _) => Value::Undefined,
+                (Some(initializer), Binding::BindingIdentifier(name)) => {
+                    self.evaluate_named(initializer, &PropertyKey::from(name.name.as_str()))?
+                },
+                (Some(initializer), _) => self.evaluate(initializer)?,
+            };
+            self.binding_initialization(&declaration.target, value, Some(&environment))?;
+        }
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-statement-semantics-runtime-semantics-labelledevaluation>
+    fn labelled_evaluation(&mut self, statement: &Statement, labels: &[&str]) -> Result<Completion, JsError> {
+        let completion = match statement {
+            Statement::LabelledStatement(statement) => {
+                let mut labels = labels.to_vec();
+                labels.push(&statement.label);
+                let completion = match &*statement.body {
+                    LabelledItem::Statement(body) => self.labelled_evaluation(body, &labels)?,
+                    LabelledItem::FunctionDeclaration(_) => Completion::Normal(None),
+                };
+                return Ok(match completion {
+                    Completion::Break(Some(label), value) if label == statement.label => {
+                        Completion::Normal(Some(value.unwrap_or(Value::Undefined)))
+                    },
+                    completion => completion,
+                });
+            },
+            Statement::DoWhileStatement(statement) => self.evaluate_do_while(statement, labels)?,
+            Statement::WhileStatement(statement) => self.evaluate_while(statement, labels)?,
+            Statement::ForStatement(statement) => self.evaluate_for(statement, labels)?,
+            Statement::ForInStatement(statement) => self.evaluate_for_in_of(statement, IterationKind::Enumerate, labels)?,
+            Statement::ForOfStatement(statement) => self.evaluate_for_in_of(statement, IterationKind::Iterate, labels)?,
+            Statement::SwitchStatement(statement) => self.evaluate_switch(statement)?,
+            _ => return self.evaluate_statement(statement),
+        };
+        // A `break` without a label ends the innermost loop or `switch`.
+        Ok(match completion {
+            Completion::Break(None, value) => Completion::Normal(Some(value.unwrap_or(Value::Undefined))),
+            completion => completion,
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-dowhileloopevaluation>
+    fn evaluate_do_while(&mut self, statement: &DoWhileStatement, labels: &[&str]) -> Result<Completion, JsError> {
+        let mut value = Value::Undefined;
+        loop {
+            self.context.check_limits()?;
+            let completion = self.evaluate_statement(&statement.body)?;
+            if !completion.loop_continues(labels) {
+                return Ok(completion.update_empty(Some(value)));
+            }
+            if let Some(completion_value) = completion.value() {
+                value = completion_value.clone();
+            }
+            if !self.evaluate(&statement.condition)?.to_boolean() {
+                return Ok(Completion::Normal(Some(value)));
+            }
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-whileloopevaluation>
+    fn evaluate_while(&mut self, statement: &WhileStatement, labels: &[&str]) -> Result<Completion, JsError> {
+        let mut value = Value::Undefined;
+        loop {
+            self.context.check_limits()?;
+            if !self.evaluate(&statement.condition)?.to_boolean() {
+                return Ok(Completion::Normal(Some(value)));
+            }
+            let completion = self.evaluate_statement(&statement.body)?;
+            if !completion.loop_continues(labels) {
+                return Ok(completion.update_empty(Some(value)));
+            }
+            if let Some(completion_value) = completion.value() {
+                value = completion_value.clone();
+            }
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-forloopevaluation>
+    fn evaluate_for(&mut self, statement: &ForStatement, labels: &[&str]) -> Result<Completion, JsError> {
+        let list = match &statement.init {
+            None => return self.for_body_evaluation(statement, &[], labels),
+            Some(ForInit::Expression(expression)) => {
+                self.evaluate(expression)?;
+                return self.for_body_evaluation(statement, &[], labels);
+            },
+            Some(ForInit::VariableDeclarationList(list)) if list.kind == VariableKind::Var => {
+                self.evaluate_variable_declarations(list)?;
+                return self.for_body_evaluation(statement, &[], labels);
+            },
+            Some(ForInit::VariableDeclarationList(list)) => list,
+        };
+        if matches!(list.kind, VariableKind::Using | VariableKind::AwaitUsing) {
+            return Err(JsError::unsupported("`using` declarations").at(&list.range));
+        }
+        let is_constant = list.kind == VariableKind::Const;
+        let names: Vec<&str> = list.bound_names().into_iter().map(|name| name.name.as_str()).collect();
+        let environment = Environment::new_declarative(Some(self.lexical_environment()));
+        for name in &names {
+            if is_constant {
+                environment.create_immutable_binding(name, true);
+            } else {
+                environment.create_mutable_binding(self.context, name, false)?;
+            }
+        }
+        self.with_lexical_environment(environment, |evaluator| {
+            evaluator.evaluate_lexical_declaration(list)?;
+            let per_iteration_bindings = if is_constant { &[][..] } else { &names };
+            evaluator.for_body_evaluation(statement, per_iteration_bindings, labels)
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-forbodyevaluation>
+    fn for_body_evaluation(
+        &mut self,
+        statement: &ForStatement,
+        per_iteration_bindings: &[&str],
+        labels: &[&str],
+    ) -> Result<Completion, JsError> {
+        let mut value = Value::Undefined;
+        self.create_per_iteration_environment(per_iteration_bindings)?;
+        loop {
+            self.context.check_limits()?;
+            if let Some(condition) = &statement.condition {
+                if !self.evaluate(condition)?.to_boolean() {
+                    return Ok(Completion::Normal(Some(value)));
+                }
+            }
+            let completion = self.evaluate_statement(&statement.body)?;
+            if !completion.loop_continues(labels) {
+                return Ok(completion.update_empty(Some(value)));
+            }
+            if let Some(completion_value) = completion.value() {
+                value = completion_value.clone();
+            }
+            self.create_per_iteration_environment(per_iteration_bindings)?;
+            if let Some(update) = &statement.update {
+                self.evaluate(update)?;
+            }
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createperiterationenvironment>,
+    /// so closures of each iteration see their own `let` bindings.
+    pub(super) fn create_per_iteration_environment(&mut self, bindings: &[&str]) -> Result<(), JsError> {
+        if bindings.is_empty() {
+            return Ok(());
+        }
+        let last_iteration = self.lexical_environment();
+        let this_iteration = Environment::new_declarative(last_iteration.outer().cloned());
+        for name in bindings {
+            this_iteration.create_mutable_binding(self.context, name, false)?;
+            let value = last_iteration.get_binding_value(self.context, name, true)?;
+            this_iteration.initialize_binding(self.context, name, value)?;
+        }
+        self.set_lexical_environment(this_iteration);
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-forinofloopevaluation>
+    fn evaluate_for_in_of(
+        &mut self,
+        statement: &ForInOfStatement,
+        kind: IterationKind,
+        labels: &[&str],
+    ) -> Result<Completion, JsError> {
+        if statement.is_await {
+            return Err(JsError::unsupported("`for await` outside async functions").at(&statement.range));
+        }
+        if let ForTarget::ForDeclaration { kind: VariableKind::Using | VariableKind::AwaitUsing, range, .. } = &statement.target {
+            return Err(JsError::unsupported("`using` declarations").at(range));
+        }
+        // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-forinofheadevaluation>
+        let source = match &statement.target {
+            ForTarget::ForDeclaration { kind: VariableKind::Let | VariableKind::Const, binding, .. } => {
+                // The source sees the declared names in the temporal dead
+                // zone.
+                let environment = Environment::new_declarative(Some(self.lexical_environment()));
+                for name in binding.bound_names() {
+                    environment.create_mutable_binding(self.context, &name.name, false)?;
+                }
+                self.with_lexical_environment(environment, |evaluator| evaluator.evaluate(&statement.source))?
+            },
+            _ => self.evaluate(&statement.source)?,
+        };
+        let mut iterator = match kind {
+            IterationKind::Enumerate if source.is_nullish() => return Ok(Completion::Break(None, None)),
+            IterationKind::Enumerate => {
+                let object = source.to_object(self.context).map_err(|error| error.at(&statement.source.range()))?;
+                ForInOfIterator::Enumerate(PropertyEnumeration::new(object))
+            },
+            IterationKind::Iterate => {
+                let record = IteratorRecord::from_iterable(self.context, &source);
+                ForInOfIterator::Iterate(record.map_err(|error| error.at(&statement.source.range()))?)
+            },
+        };
+
+        // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-forin-div-ofbodyevaluation-lhs-stmt-iterator-lhskind-labelset>
+        let mut value = Value::Undefined;
+        loop {
+            if let Err(error) = self.context.check_limits() {
+                return close_iterator(self, &iterator, Err(error));
+            }
+            let next = match &mut iterator {
+                ForInOfIterator::Enumerate(enumeration) => enumeration.next(self.context)?,
+                ForInOfIterator::Iterate(record) => record.step_value(self.context)?,
+            };
+            let Some(next) = next else {
+                return Ok(Completion::Normal(Some(value)));
+            };
+            // The iterator is closed after a returned call is made.
+            let iteration = match kind {
+                IterationKind::Enumerate => self.evaluate_for_in_of_iteration(statement, next),
+                IterationKind::Iterate => self.without_tail_calls(|evaluator| evaluator.evaluate_for_in_of_iteration(statement, next)),
+            };
+            let completion = match iteration {
+                Ok(completion) => completion,
+                Err(error) => return close_iterator(self, &iterator, Err(error)),
+            };
+            if !completion.loop_continues(labels) {
+                return close_iterator(self, &iterator, Ok(completion.update_empty(Some(value))));
+            }
+            if let Some(completion_value) = completion.value() {
+                value = completion_value.clone();
+            }
+        }
+    }
+
+    /// Bind a value of an iteration of `for`-`in` or `for`-`of` to its
+    /// target and evaluate the body.
+    fn evaluate_for_in_of_iteration(&mut self, statement: &ForInOfStatement, next: Value) -> Result<Completion, JsError> {
+        match &statement.target {
+            ForTarget::Expression(target) if is_pattern(target) => self.destructuring_assignment(target, &next)?,
+            ForTarget::Expression(target) => {
+                let reference = self.evaluate_reference(target)?;
+                self.put_value(&reference, next).map_err(|error| error.at(&target.range()))?;
+            },
+            ForTarget::ForDeclaration { kind: VariableKind::Var, binding, .. } => {
+                self.binding_initialization(binding, next, None)?;
+            },
+            ForTarget::ForDeclaration { kind, binding, .. } => {
+                // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-fordeclarationbindinginstantiation>
+                let environment = Environment::new_declarative(Some(self.lexical_environment()));
+                for name in binding.bound_names() {
+                    if *kind == VariableKind::Const {
+                        environment.create_immutable_binding(&name.name, true);
+                    } else {
+                        environment.create_mutable_binding(self.context, &name.name, false)?;
+                    }
+                }
+                return self.with_lexical_environment(environment.clone(), |evaluator| {
+                    evaluator.binding_initialization(binding, next, Some(&environment))?;
+                    evaluator.evaluate_statement(&statement.body)
+                });
+            },
+        }
+        self.evaluate_statement(&statement.body)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-switch-statement-runtime-semantics-evaluation>
+    fn evaluate_switch(&mut self, statement: &SwitchStatement) -> Result<Completion, JsError> {
+        let input = self.evaluate(&statement.discriminant)?;
+        let statements = statement.cases.iter().flat_map(|case| &case.statements);
+        let environment = Environment::new_declarative(Some(self.lexical_environment()));
+        self.block_declaration_instantiation(statements, &environment)?;
+        self.with_lexical_environment(environment, |evaluator| {
+            // <https://262.ecma-international.org/14.0/#sec-runtime-semantics-caseblockevaluation>
+            let mut start = None;
+            for (index, case) in statement.cases.iter().enumerate() {
+                let Some(test) = &case.test else { continue };
+                if evaluator.evaluate(test)?.is_strictly_equal(&input) {
+                    start = Some(index);
+                    break;
+                }
+            }
+            let start = start.or_else(|| statement.cases.iter().position(|case| case.test.is_none()));
+            let mut value = Value::Undefined;
+            for case in start.map_or(&[][..], |start| &statement.cases[start..]) {
+                let completion = evaluator.evaluate_statements(&case.statements)?;
+                if let Some(completion_value) = completion.value() {
+                    value = completion_value.clone();
+                }
+                if !matches!(completion, Completion::Normal(_)) {
+                    return Ok(completion.update_empty(Some(value)));
+                }
+            }
+            Ok(Completion::Normal(Some(value)))
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-try-statement-runtime-semantics-evaluation>
+    ///
+    /// Errors of unsupported features or running out of fuel are not caught
+    /// and skip `finally`, since the code cannot go on as written.
+    fn evaluate_try(&mut self, statement: &TryStatement) -> Result<Completion, JsError> {
+        // A call returned from the block may throw to `catch` and has to be
+        // made before `finally`, like one returned from `catch` before it.
+        let mut completion = self.without_tail_calls(|evaluator| evaluator.evaluate_block(&statement.block));
+        if let (Err(error), Some(handler)) = (&completion, &statement.handler) {
+            if error.kind.is_catchable() {
+                let thrown = self.caught_value(error.clone());
+                completion = match &statement.finalizer {
+                    Some(_) => self.without_tail_calls(|evaluator| evaluator.evaluate_catch(handler, thrown)),
+                    None => self.evaluate_catch(handler, thrown),
+                };
+            }
+        }
+        if matches!(&completion, Err(error) if !error.kind.is_catchable()) {
+            return completion;
+        }
+        if let Some(finalizer) = &statement.finalizer {
+            let finalizer_completion = self.evaluate_block(finalizer)?;
+            if !matches!(finalizer_completion, Completion::Normal(_)) {
+                return Ok(finalizer_completion.update_empty(Some(Value::Undefined)));
+            }
+        }
+        Ok(completion?.update_empty(Some(Value::Undefined)))
+    }
+
+    /// Evaluate code that has to run after calls it returns, which it
+    /// makes in place then.
+    fn without_tail_calls<T>(&mut self, evaluate: impl FnOnce(&mut Self) -> T) -> T {
+        let tail_calls = mem::replace(&mut self.tail_calls, false);
+        let result = evaluate(self);
+        self.tail_calls = tail_calls;
+        result
+    }
+
+    /// The value a `catch` clause gets for an error: the value thrown by
+    /// ECMAScript code or an object describing an error of the runtime.
+    fn caught_value(&self, error: JsError) -> Value {
+        error.into_value(self.context)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-runtime-semantics-catchclauseevaluation>
+    fn evaluate_catch(&mut self, handler: &CatchClause, thrown: Value) -> Result<Completion, JsError> {
+        let Some(parameter) = &handler.parameter else {
+            return self.evaluate_block(&handler.body);
+        };
+        let environment = Environment::new_declarative(Some(self.lexical_environment()));
+        for name in parameter.bound_names() {
+            environment.create_mutable_binding(self.context, &name.name, false)?;
+        }
+        self.with_lexical_environment(environment.clone(), |evaluator| {
+            evaluator.binding_initialization(parameter, thrown, Some(&environment))?;
+            evaluator.evaluate_block(&handler.body)
+        })
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-iteratorclose> of
+/// a `for`-`of` iterator after an abrupt completion; `for`-`in` has nothing
+/// to close.
+fn close_iterator(
+    evaluator: &mut Evaluator<'_>,
+    iterator: &ForInOfIterator,
+    completion: Result<Completion, JsError>,
+) -> Result<Completion, JsError> {
+    match iterator {
+        ForInOfIterator::Enumerate(_) => completion,
+        ForInOfIterator::Iterate(record) => record.close(evaluator.context, completion),
+    }
+}
+
+/// A declaration <https://262.ecma-international.org/14.0/#sec-static-semantics-lexicallyscopeddeclarations>
+/// lists.
+pub(super) enum ScopedDeclaration<'a> {
+    Declaration(&'a Declaration),
+    /// A labelled function declaration.
+    Function(&'a Rc<Function>),
+}
+
+impl<'a> ScopedDeclaration<'a> {
+    fn bound_names(&self) -> Vec<&'a str> {
+        match self {
+            Self::Declaration(declaration) => declaration.bound_names().into_iter().map(|name| name.name.as_str()).collect(),
+            Self::Function(function) => function.name.iter().map(|name| name.name.as_str()).collect(),
+        }
+    }
+}
+
+/// `LexicallyScopedDeclarations` of an item of a block or a `switch` case.
+pub(super) fn lexically_scoped_declarations(item: &StatementListItem) -> impl Iterator<Item = ScopedDeclaration<'_>> {
+    let declaration = match item {
+        StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => {
+            Some(ScopedDeclaration::Function(function))
+        },
+        StatementListItem::Declaration(declaration) => Some(ScopedDeclaration::Declaration(declaration)),
+        StatementListItem::Statement(Statement::LabelledStatement(statement)) => {
+            let mut item = &*statement.body;
+            while let LabelledItem::Statement(Statement::LabelledStatement(statement)) = item {
+                item = &statement.body;
+            }
+            match item {
+                LabelledItem::FunctionDeclaration(function) => Some(ScopedDeclaration::Function(function)),
+                LabelledItem::Statement(_) => None,
+            }
+        },
+        StatementListItem::Statement(_) => None,
+    };
+    declaration.into_iter()
+}
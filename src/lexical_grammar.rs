@@ -80,11 +80,17 @@
  *
  ************************************************/
 
-use pest::Span;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use pest::Span as PestSpan;
 use pest_ast::FromPest;
 use pest_derive::Parser;
 
-fn span_into_str(span: Span) -> &str {
+mod pairs;
+mod scanner;
+mod unicode;
+
+fn span_into_str(span: PestSpan<'_>) -> &str {
     span.as_str()
 }
 
@@ -92,15 +98,18 @@ fn span_into_str(span: Span) -> &str {
 #[grammar = "lexical_grammar.pest"]
 struct Ecma262Parser;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::WhiteSpace))]
 pub struct WhiteSpace;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LineTerminator))]
 pub struct LineTerminator;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::PrivateIdentifier))]
 pub struct PrivateIdentifier {
     identifier_name: IdentifierName
@@ -116,12 +125,66 @@ impl PrivateIdentifier {
     }
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::IdentifierName))]
 pub struct IdentifierName {
     // Escape sequence decoding do not allow to use `&str`
-    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
-    decoded: String
+    #[pest_ast(outer(with(span_into_str), with(decode_identifier_name)))]
+    decoded: String,
+    #[pest_ast(outer(with(span_into_str), with(contains_escape)))]
+    escaped: bool,
+}
+
+fn contains_escape(text: &str) -> bool {
+    text.contains('\\')
+}
+
+/// An `IdentifierName` that has a special meaning in some syntactic contexts
+/// while remaining a valid identifier elsewhere.
+///
+/// Such words are not a part of `ReservedWord`, so the tokenizer returns them
+/// as plain `IdentifierName`; a parser uses [`IdentifierName::keyword_kind`]
+/// to recognize them where the grammar expects a keyword.
+///
+/// See <https://262.ecma-international.org/14.0/#sec-keywords-and-reserved-words>.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ContextualKeyword {
+    As,
+    Async,
+    From,
+    Get,
+    Let,
+    Meta,
+    Of,
+    Set,
+    Static,
+    Target,
+    Using,
+}
+
+/// Replace `\u` escapes of a matched `IdentifierName` with code points they
+/// stand for.
+///
+/// Escapes of code points that cannot be a part of an identifier are
+/// replaced with U+FFFD; the tokenizer rejects such names anyway, see
+/// [`find_invalid_identifier_escape`].
+fn decode_identifier_name(text: &str) -> String {
+    if !contains_escape(text) {
+        return text.to_owned();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut codepoints = text.chars().peekable();
+    while let Some(codepoint) = codepoints.next() {
+        if codepoint == '\\' && codepoints.next_if_eq(&'u').is_some() {
+            let value = decode_unicode_escape_tail(&mut codepoints);
+            decoded.push(char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER));
+        } else {
+            decoded.push(codepoint);
+        }
+    }
+    decoded
 }
 
 impl IdentifierName {
@@ -129,225 +192,344 @@ impl IdentifierName {
     pub fn string_value(&self) -> String {
         self.decoded.clone()
     }
+
+    /// A contextual keyword spelled by this name, if any.
+    ///
+    /// <https://262.ecma-international.org/14.0/#sec-keywords-and-reserved-words>:
+    ///
+    /// > Those that are contextually disallowed as identifiers, in strict mode
+    /// > code: `let`, `static`, `implements`, `interface`, `package`,
+    /// > `private`, `protected`, and `public`;
+    /// >
+    /// > Those that are always allowed as identifiers, but also appear as
+    /// > keywords within certain syntactic productions, at places where
+    /// > Identifier is not allowed: `as`, `async`, `from`, `get`, `meta`,
+    /// > `of`, `set`, and `target`.
+    ///
+    /// A keyword written with `\u` escapes is not a keyword, so such names
+    /// return `None`:
+    ///
+    /// > the syntactic grammar [...] uses terminal symbols that cannot be
+    /// > matched by an IdentifierName containing escapes.
+    #[must_use]
+    pub fn keyword_kind(&self) -> Option<ContextualKeyword> {
+        if self.escaped {
+            return None;
+        }
+        match self.decoded.as_str() {
+            "as" => Some(ContextualKeyword::As),
+            "async" => Some(ContextualKeyword::Async),
+            "from" => Some(ContextualKeyword::From),
+            "get" => Some(ContextualKeyword::Get),
+            "let" => Some(ContextualKeyword::Let),
+            "meta" => Some(ContextualKeyword::Meta),
+            "of" => Some(ContextualKeyword::Of),
+            "set" => Some(ContextualKeyword::Set),
+            "static" => Some(ContextualKeyword::Static),
+            "target" => Some(ContextualKeyword::Target),
+            "using" => Some(ContextualKeyword::Using),
+            _ => None,
+        }
+    }
+
+    /// Whether the name cannot be an identifier in strict mode code.
+    ///
+    /// <https://262.ecma-international.org/14.0/#sec-identifiers-static-semantics-early-errors>:
+    ///
+    /// > It is a Syntax Error if this phrase is contained in strict mode code
+    /// > and the StringValue of IdentifierName is one of "implements",
+    /// > "interface", "let", "package", "private", "protected", "public",
+    /// > "static", or "yield".
+    ///
+    /// The rule applies to identifiers only, not to property names like
+    /// `a.public`, so the tokenizer leaves the check to a parser.
+    #[must_use]
+    pub fn is_reserved_in_strict_mode(&self) -> bool {
+        is_reserved_in_strict_mode(&self.decoded)
+    }
+}
+
+/// Whether a `StringValue` of an identifier is reserved in strict mode code;
+/// see [`IdentifierName::is_reserved_in_strict_mode`].
+pub(crate) fn is_reserved_in_strict_mode(name: &str) -> bool {
+    matches!(
+        name,
+        "implements" | "interface" | "let" | "package" | "private" | "protected" | "public" | "static" | "yield"
+    )
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OptionalChainingPunctuator))]
 pub struct OptionalChainingPunctuator;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Addition))]
 pub struct Addition;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::AdditionAssignment))]
 pub struct AdditionAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::And))]
 pub struct And;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::AndAssignment))]
 pub struct AndAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Assignment))]
 pub struct Assignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitAnd))]
 pub struct BitAnd;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitAndAssignment))]
 pub struct BitAndAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitNot))]
 pub struct BitNot;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitOr))]
 pub struct BitOr;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitOrAssignment))]
 pub struct BitOrAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitXor))]
 pub struct BitXor;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::BitXorAssignment))]
 pub struct BitXorAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::ClosingBracket))]
 pub struct ClosingBracket;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::ClosingParenthesis))]
 pub struct ClosingParenthesis;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Colon))]
 pub struct Colon;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Comma))]
 pub struct Comma;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Decrement))]
 pub struct Decrement;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Dot))]
 pub struct Dot;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Ellipsis))]
 pub struct Ellipsis;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Exponentiation))]
 pub struct Exponentiation;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::ExponentiationAssignment))]
 pub struct ExponentiationAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::FunctionArrow))]
 pub struct FunctionArrow;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Increment))]
 pub struct Increment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LeftShift))]
 pub struct LeftShift;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LeftShiftAssignment))]
 pub struct LeftShiftAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Less))]
 pub struct Less;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LessOrEqual))]
 pub struct LessOrEqual;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LooseEquality))]
 pub struct LooseEquality;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::LooseInequality))]
 pub struct LooseInequality;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Modulo))]
 pub struct Modulo;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::ModuloAssignment))]
 pub struct ModuloAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::More))]
 pub struct More;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::MoreOrEqual))]
 pub struct MoreOrEqual;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Multiplication))]
 pub struct Multiplication;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::MultiplicationAssignment))]
 pub struct MultiplicationAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Not))]
 pub struct Not;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::NullishCoalescence))]
 pub struct NullishCoalescence;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::NullishCoalescenceAssignment))]
 pub struct NullishCoalescenceAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OpeningBrace))]
 pub struct OpeningBrace;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OpeningBracket))]
 pub struct OpeningBracket;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OpeningParenthesis))]
 pub struct OpeningParenthesis;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Or))]
 pub struct Or;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OrAssignment))]
 pub struct OrAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::QuestionMark))]
 pub struct QuestionMark;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::RightShift))]
 pub struct RightShift;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::RightShiftAssignment))]
 pub struct RightShiftAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Semicolon))]
 pub struct Semicolon;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::StrictEquality))]
 pub struct StrictEquality;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::StrictInequality))]
 pub struct StrictInequality;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Subtraction))]
 pub struct Subtraction;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::SubtractionAssignment))]
 pub struct SubtractionAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::UnsignedRightShift))]
 pub struct UnsignedRightShift;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::UnsignedRightShiftAssignment))]
 pub struct UnsignedRightShiftAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::OtherPunctuator))]
 pub enum OtherPunctuator {
     Addition(Addition),
@@ -405,174 +587,856 @@ pub enum OtherPunctuator {
     UnsignedRightShiftAssignment(UnsignedRightShiftAssignment),
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Punctuator))]
 pub enum Punctuator {
     OptionalChainingPunctuator(OptionalChainingPunctuator),
     OtherPunctuator(OtherPunctuator),
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::DecimalIntegerLiteral))]
+pub struct DecimalIntegerLiteral {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    digits: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::DecimalDigits))]
+pub struct DecimalDigits {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    digits: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::SignedInteger))]
+pub struct SignedInteger {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    text: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::ExponentPart))]
+pub struct ExponentPart {
+    signed_integer: SignedInteger
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::DecimalLiteral))]
+pub struct DecimalLiteral {
+    integer_part: Option<DecimalIntegerLiteral>,
+    fraction: Option<DecimalDigits>,
+    exponent: Option<ExponentPart>,
+    // Tells `5.` from `5` for reserialization.
+    #[pest_ast(outer(with(span_into_str), with(contains_decimal_point)))]
+    decimal_point: bool,
+}
+
+fn contains_decimal_point(text: &str) -> bool {
+    text.contains('.')
+}
+
+impl DecimalLiteral {
+    /// Digits before the decimal point, if any (`1` in `1.5e-3`).
+    ///
+    /// Digit strings are returned as written, with numeric separators.
+    #[must_use]
+    pub fn integer_part(&self) -> Option<&str> {
+        self.integer_part.as_ref().map(|part| part.digits.as_str())
+    }
+
+    /// Digits after the decimal point, if any (`5` in `1.5e-3`).
+    #[must_use]
+    pub fn fraction(&self) -> Option<&str> {
+        self.fraction.as_ref().map(|part| part.digits.as_str())
+    }
+
+    /// An optionally signed power of ten, if any (`-3` in `1.5e-3`).
+    #[must_use]
+    pub fn exponent(&self) -> Option<&str> {
+        self.exponent.as_ref().map(|part| part.signed_integer.text.as_str())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numericvalue>
+    ///
+    /// The MV is rounded to the nearest Number value.
+    #[must_use]
+    pub fn numeric_value(&self) -> f64 {
+        let normalized = format!(
+            "{}.{}e{}",
+            self.integer_part().unwrap_or("0"),
+            self.fraction().unwrap_or("0"),
+            self.exponent().unwrap_or("0")
+        ).replace('_', "");
+        // The grammar leaves nothing Rust would not parse.
+        normalized.parse().unwrap_or(f64::NAN)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::BinaryDigits))]
+pub struct BinaryDigits {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    digits: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::BinaryIntegerLiteral))]
+pub struct BinaryIntegerLiteral {
+    digits: BinaryDigits
+}
+
+impl BinaryIntegerLiteral {
+    /// Digits after the `0b` prefix.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.digits.digits
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::OctalDigits))]
+pub struct OctalDigits {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    digits: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::OctalIntegerLiteral))]
+pub struct OctalIntegerLiteral {
+    digits: OctalDigits
+}
+
+impl OctalIntegerLiteral {
+    /// Digits after the `0o` prefix.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.digits.digits
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::HexDigits))]
+pub struct HexDigits {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    digits: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::HexIntegerLiteral))]
+pub struct HexIntegerLiteral {
+    digits: HexDigits
+}
+
+impl HexIntegerLiteral {
+    /// Digits after the `0x` prefix.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.digits.digits
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::NonDecimalIntegerLiteral))]
+pub enum NonDecimalIntegerLiteral {
+    BinaryIntegerLiteral(BinaryIntegerLiteral),
+    OctalIntegerLiteral(OctalIntegerLiteral),
+    HexIntegerLiteral(HexIntegerLiteral),
+}
+
+impl NonDecimalIntegerLiteral {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-mv>
+    #[must_use]
+    pub fn mathematical_value(&self) -> BigInt {
+        let (digits, radix) = match self {
+            Self::BinaryIntegerLiteral(literal) => (literal.digits(), 2),
+            Self::OctalIntegerLiteral(literal) => (literal.digits(), 8),
+            Self::HexIntegerLiteral(literal) => (literal.digits(), 16),
+        };
+        parse_integer(digits, radix)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numericvalue>
+    ///
+    /// The MV is rounded to the nearest Number value.
+    #[must_use]
+    pub fn numeric_value(&self) -> f64 {
+        self.mathematical_value().to_f64().unwrap_or(f64::INFINITY)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::LegacyOctalIntegerLiteral))]
+pub struct LegacyOctalIntegerLiteral {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    text: String
+}
+
+impl LegacyOctalIntegerLiteral {
+    /// Digits after the leading `0`.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.text[1..]
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-mv>
+    #[must_use]
+    pub fn mathematical_value(&self) -> BigInt {
+        parse_integer(self.digits(), 8)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numericvalue>
+    ///
+    /// The MV is rounded to the nearest Number value.
+    #[must_use]
+    pub fn numeric_value(&self) -> f64 {
+        self.mathematical_value().to_f64().unwrap_or(f64::INFINITY)
+    }
+}
+
+/// Compute MV of digits matched by the grammar, skipping numeric separators.
+fn parse_integer(digits: &str, radix: u32) -> BigInt {
+    let digits = digits.replace('_', "");
+    BigInt::parse_bytes(digits.as_bytes(), radix).unwrap_or_default()
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::DecimalBigIntegerLiteral))]
+pub struct DecimalBigIntegerLiteral {
+    integer_part: DecimalIntegerLiteral
+}
+
+impl DecimalBigIntegerLiteral {
+    /// Digits before the `n` suffix.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.integer_part.digits
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-mv>
+    #[must_use]
+    pub fn mathematical_value(&self) -> BigInt {
+        parse_integer(self.digits(), 10)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::NonDecimalBigIntegerLiteral))]
+pub struct NonDecimalBigIntegerLiteral {
+    integer: NonDecimalIntegerLiteral
+}
+
+impl NonDecimalBigIntegerLiteral {
+    /// The literal without the `n` suffix.
+    #[must_use]
+    pub const fn integer(&self) -> &NonDecimalIntegerLiteral {
+        &self.integer
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-mv>
+    #[must_use]
+    pub fn mathematical_value(&self) -> BigInt {
+        self.integer.mathematical_value()
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::NumericLiteral))]
+pub enum NumericLiteral {
+    DecimalLiteral(DecimalLiteral),
+    DecimalBigIntegerLiteral(DecimalBigIntegerLiteral),
+    NonDecimalIntegerLiteral(NonDecimalIntegerLiteral),
+    NonDecimalBigIntegerLiteral(NonDecimalBigIntegerLiteral),
+    LegacyOctalIntegerLiteral(LegacyOctalIntegerLiteral),
+}
+
+/// A value of a numeric literal: either a Number or a `BigInt`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NumericValue {
+    Number(f64),
+    BigInt(BigInt),
+}
+
+impl NumericLiteral {
+    /// <https://262.ecma-international.org/14.0/#sec-numericvalue>
+    #[must_use]
+    pub fn numeric_value(&self) -> NumericValue {
+        match self {
+            Self::DecimalLiteral(literal) => NumericValue::Number(literal.numeric_value()),
+            Self::NonDecimalIntegerLiteral(literal) => NumericValue::Number(literal.numeric_value()),
+            Self::DecimalBigIntegerLiteral(literal) => NumericValue::BigInt(literal.mathematical_value()),
+            Self::NonDecimalBigIntegerLiteral(literal) => NumericValue::BigInt(literal.mathematical_value()),
+            Self::LegacyOctalIntegerLiteral(literal) => NumericValue::Number(literal.numeric_value()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::StringLiteral))]
+pub struct StringLiteral {
+    // Quotes included; escape sequences are decoded on demand.
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    text: String
+}
+
+impl StringLiteral {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-sv>
+    ///
+    /// The specification defines the value as a sequence of UTF-16 code
+    /// units that may contain unpaired surrogates (`"\uD800"`), so it is
+    /// returned as is.
+    #[must_use]
+    pub fn code_units(&self) -> Vec<u16> {
+        let body = &self.text[1..self.text.len() - 1];
+        let mut units = Vec::with_capacity(body.len());
+        let mut codepoints = body.chars().peekable();
+        while let Some(codepoint) = codepoints.next() {
+            if codepoint == '\\' {
+                decode_escape_sequence(&mut codepoints, &mut units);
+            } else {
+                units.extend_from_slice(codepoint.encode_utf16(&mut [0; 2]));
+            }
+        }
+        units
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-sv>
+    ///
+    /// Unpaired surrogates are replaced with U+FFFD REPLACEMENT CHARACTER;
+    /// use [`StringLiteral::code_units`] to get them.
+    #[must_use]
+    pub fn string_value(&self) -> String {
+        String::from_utf16_lossy(&self.code_units())
+    }
+}
+
+/// Append SV of a `LineContinuation` or an `EscapeSequence` which `\` is
+/// already taken from.
+///
+/// The input is expected to be matched by the grammar before.
+fn decode_escape_sequence(
+    codepoints: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    units: &mut Vec<u16>
+) {
+    let Some(escaped) = codepoints.next() else {
+        return;
+    };
+    let value = match escaped {
+        // The SV of LineContinuation :: `\` LineTerminatorSequence is
+        // the empty String.
+        '\r' => {
+            codepoints.next_if_eq(&'\n');
+            return;
+        },
+        '\n' | '\u{2028}' | '\u{2029}' => return,
+        // Table 66: String Single Character Escape Sequences
+        'b' => 0x0008,
+        't' => 0x0009,
+        'n' => 0x000A,
+        'v' => 0x000B,
+        'f' => 0x000C,
+        'r' => 0x000D,
+        'x' => read_hex_digits(codepoints, 2),
+        'u' => decode_unicode_escape_tail(codepoints),
+        // The SV of `0` [lookahead ∉ DecimalDigit] and of
+        // LegacyOctalEscapeSequence is the MV of its octal digits.
+        '0'..='7' => {
+            let max_digits = if escaped <= '3' { 3 } else { 2 };
+            let mut value = escaped.to_digit(8).unwrap_or_default();
+            for _ in 1..max_digits {
+                let Some(digit) = codepoints.next_if(|next| next.is_digit(8)) else {
+                    break;
+                };
+                value = value * 8 + digit.to_digit(8).unwrap_or_default();
+            }
+            value
+        },
+        // `'`, `"`, `\`, NonOctalDecimalEscapeSequence and
+        // NonEscapeCharacter stand for themselves.
+        other => u32::from(other),
+    };
+    push_code_point(units, value);
+}
+
+/// Decode an `UnicodeEscapeSequence` which `\u` is already taken from.
+fn decode_unicode_escape_tail(codepoints: &mut std::iter::Peekable<std::str::Chars<'_>>) -> u32 {
+    if codepoints.next_if_eq(&'{').is_none() {
+        return read_hex_digits(codepoints, 4);
+    }
+    let value = read_hex_digits(codepoints, usize::MAX);
+    codepoints.next_if_eq(&'}');
+    value
+}
+
+fn read_hex_digits(codepoints: &mut std::iter::Peekable<std::str::Chars<'_>>, limit: usize) -> u32 {
+    let mut value: u32 = 0;
+    for _ in 0..limit {
+        let Some(digit) = codepoints.peek().and_then(|next| next.to_digit(16)) else {
+            break;
+        };
+        codepoints.next();
+        value = value.saturating_mul(16).saturating_add(digit);
+    }
+    value
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-utf16encodecodepoint>
+///
+/// Unlike `char::encode_utf16`, accepts surrogate code points.
+fn push_code_point(units: &mut Vec<u16>, value: u32) {
+    match char::from_u32(value) {
+        Some(codepoint) => units.extend_from_slice(codepoint.encode_utf16(&mut [0; 2])),
+        // Only surrogates get here: the grammar limits escapes to 0x10FFFF.
+        None => units.push(u16::try_from(value).unwrap_or(0xFFFD)),
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::RegularExpressionBody))]
+pub struct RegularExpressionBody {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    text: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::RegularExpressionFlags))]
+pub struct RegularExpressionFlags {
+    #[pest_ast(outer(with(span_into_str), with(str::to_string)))]
+    text: String
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::RegularExpressionLiteral))]
+pub struct RegularExpressionLiteral {
+    body: RegularExpressionBody,
+    flags: RegularExpressionFlags
+}
+
+impl RegularExpressionLiteral {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-bodytext>
+    #[must_use]
+    pub fn body_text(&self) -> &str {
+        &self.body.text
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-flagtext>
+    #[must_use]
+    pub fn flag_text(&self) -> &str {
+        &self.flags.text
+    }
+}
+
+/// Find a flag that makes a regular expression literal invalid.
+///
+/// Implements the flag part of
+/// <https://262.ecma-international.org/14.0/#sec-isvalidregularexpressionliteral>:
+///
+/// > 2. If flags contains any code points other than `d`, `g`, `i`, `m`, `s`,
+/// >    `u`, `v`, or `y`, or if flags contains any code point more than once,
+/// >    return false.
+/// > 3. If flags contains `u`, let u be true; else let u be false.
+/// > 4. If flags contains `v`, let v be true; else let v be false.
+/// > [...]
+/// > 7. If u is true and v is true, return false.
+///
+/// Returns a byte range of the flag within `flags`.
+fn find_invalid_regular_expression_flag(flags: &str) -> Option<Range<usize>> {
+    let mut seen = String::new();
+    for (position, flag) in flags.char_indices() {
+        let is_known = matches!(flag, 'd' | 'g' | 'i' | 'm' | 's' | 'u' | 'v' | 'y');
+        let is_conflicting = (flag == 'u' && seen.contains('v')) || (flag == 'v' && seen.contains('u'));
+        if !is_known || seen.contains(flag) || is_conflicting {
+            return Some(position..position + flag.len_utf8());
+        }
+        seen.push(flag);
+    }
+    None
+}
+
+/// Cut `TemplateCharacters` out of a matched template token by dropping its
+/// opening `` ` `` or `}` and its closing `` ` `` or `${`.
+fn template_characters(text: &str) -> String {
+    let closing = if text.ends_with("${") { 2 } else { 1 };
+    text.get(1..text.len() - closing).unwrap_or_default().to_owned()
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>
+///
+/// Returns `None` for *undefined*, that is if the characters contain
+/// a `NotEscapeSequence`.
+fn template_value(characters: &str) -> Option<Vec<u16>> {
+    let mut units = Vec::with_capacity(characters.len());
+    let mut rest = characters;
+    while let Some(codepoint) = rest.chars().next() {
+        let length = match codepoint {
+            '\\' => {
+                let escaped = &rest[1..];
+                let matched = Ecma262Parser::parse(Rule::TemplateEscapeSequence, escaped).ok()
+                    .or_else(|| Ecma262Parser::parse(Rule::LineTerminatorSequence, escaped).ok())?;
+                let length = 1 + matched.as_str().len();
+                decode_escape_sequence(&mut rest[1..length].chars().peekable(), &mut units);
+                length
+            },
+            // The TV of LineTerminatorSequence is its TRV.
+            '\r' => {
+                units.push(u16::from(b'\n'));
+                if rest[1..].starts_with('\n') { 2 } else { 1 }
+            },
+            _ => {
+                units.extend_from_slice(codepoint.encode_utf16(&mut [0; 2]));
+                codepoint.len_utf8()
+            },
+        };
+        rest = &rest[length..];
+    }
+    Some(units)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+///
+/// Characters are taken as is except <CR><LF> and <CR> line terminators
+/// normalized to <LF>.
+fn template_raw_value(characters: &str) -> String {
+    characters.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::NoSubstitutionTemplate))]
+pub struct NoSubstitutionTemplate {
+    #[pest_ast(outer(with(span_into_str), with(template_characters)))]
+    characters: String
+}
+
+impl NoSubstitutionTemplate {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>
+    ///
+    /// `None` stands for *undefined* allowed in tagged templates only.
+    #[must_use]
+    pub fn cooked_value(&self) -> Option<Vec<u16>> {
+        template_value(&self.characters)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+    #[must_use]
+    pub fn raw_value(&self) -> String {
+        template_raw_value(&self.characters)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::TemplateHead))]
+pub struct TemplateHead {
+    #[pest_ast(outer(with(span_into_str), with(template_characters)))]
+    characters: String
+}
+
+impl TemplateHead {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>
+    ///
+    /// `None` stands for *undefined* allowed in tagged templates only.
+    #[must_use]
+    pub fn cooked_value(&self) -> Option<Vec<u16>> {
+        template_value(&self.characters)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+    #[must_use]
+    pub fn raw_value(&self) -> String {
+        template_raw_value(&self.characters)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::TemplateMiddle))]
+pub struct TemplateMiddle {
+    #[pest_ast(outer(with(span_into_str), with(template_characters)))]
+    characters: String
+}
+
+impl TemplateMiddle {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>
+    ///
+    /// `None` stands for *undefined* allowed in tagged templates only.
+    #[must_use]
+    pub fn cooked_value(&self) -> Option<Vec<u16>> {
+        template_value(&self.characters)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+    #[must_use]
+    pub fn raw_value(&self) -> String {
+        template_raw_value(&self.characters)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::TemplateTail))]
+pub struct TemplateTail {
+    #[pest_ast(outer(with(span_into_str), with(template_characters)))]
+    characters: String
+}
+
+impl TemplateTail {
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-tv>
+    ///
+    /// `None` stands for *undefined* allowed in tagged templates only.
+    #[must_use]
+    pub fn cooked_value(&self) -> Option<Vec<u16>> {
+        template_value(&self.characters)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-static-semantics-trv>
+    #[must_use]
+    pub fn raw_value(&self) -> String {
+        template_raw_value(&self.characters)
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::Template))]
+pub enum Template {
+    NoSubstitutionTemplate(NoSubstitutionTemplate),
+    TemplateHead(TemplateHead),
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[pest_ast(rule(Rule::TemplateSubstitutionTail))]
+pub enum TemplateSubstitutionTail {
+    TemplateMiddle(TemplateMiddle),
+    TemplateTail(TemplateTail),
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::CommonToken))]
 pub enum CommonToken {
     IdentifierName(IdentifierName),
     PrivateIdentifier(PrivateIdentifier),
+    NumericLiteral(NumericLiteral),
+    StringLiteral(StringLiteral),
+    Template(Template),
     Punctuator(Punctuator),
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Await))]
 pub struct Await;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Break))]
 pub struct Break;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Case))]
 pub struct Case;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Catch))]
 pub struct Catch;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Class))]
 pub struct Class;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Const))]
 pub struct Const;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Continue))]
 pub struct Continue;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Debugger))]
 pub struct Debugger;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Default))]
 pub struct Default;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Delete))]
 pub struct Delete;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Do))]
 pub struct Do;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Else))]
 pub struct Else;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Enum))]
 pub struct Enum;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Export))]
 pub struct Export;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Extends))]
 pub struct Extends;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::False))]
 pub struct False;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Finally))]
 pub struct Finally;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::For))]
 pub struct For;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Function))]
 pub struct Function;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::If))]
 pub struct If;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Import))]
 pub struct Import;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::In))]
 pub struct In;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Instanceof))]
 pub struct Instanceof;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::New))]
 pub struct New;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Null))]
 pub struct Null;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Return))]
 pub struct Return;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Super))]
 pub struct Super;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Switch))]
 pub struct Switch;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::This))]
 pub struct This;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Throw))]
 pub struct Throw;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::True))]
 pub struct True;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Try))]
 pub struct Try;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Typeof))]
 pub struct Typeof;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Var))]
 pub struct Var;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Void))]
 pub struct Void;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::While))]
 pub struct While;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::With))]
 pub struct With;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Yield))]
 pub struct Yield;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::ReservedWord))]
 pub enum ReservedWord {
     Await(Await),
@@ -615,41 +1479,117 @@ pub enum ReservedWord {
     Yield(Yield),
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::RightBracePunctuator))]
 pub struct RightBracePunctuator;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::DivisionAssignment))]
 pub struct DivisionAssignment;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Division))]
 pub struct Division;
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::DivPunctuator))]
 pub enum DivPunctuator {
     DivisionAssignment(DivisionAssignment),
     Division(Division),
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::Comment))]
-pub enum Comment {
-    MultiLineComment(MultiLineComment),
-    SingleLineComment(SingleLineComment),
+pub enum Comment<'src> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    MultiLineComment(MultiLineComment<'src>),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    SingleLineComment(SingleLineComment<'src>),
+}
+
+impl<'src> Comment<'src> {
+    /// Vendor-specific static semantic declaration and definition
+    #[must_use]
+    pub const fn string_value(&self) -> &'src str {
+        match self {
+            Self::MultiLineComment(comment) => comment.string_value(),
+            Self::SingleLineComment(comment) => comment.string_value(),
+        }
+    }
+
+    /// Whether the comment counts as a `LineTerminator` for the syntactic
+    /// grammar; see [`MultiLineComment::contains_line_terminator`].
+    #[must_use]
+    pub fn contains_line_terminator(&self) -> bool {
+        match self {
+            Self::MultiLineComment(comment) => comment.contains_line_terminator(),
+            Self::SingleLineComment(_) => false,
+        }
+    }
 }
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::MultiLineComment))]
-pub struct MultiLineComment;
+pub struct MultiLineComment<'src> {
+    #[pest_ast(outer(with(span_into_str), with(strip_multi_line_comment_delimiters)))]
+    content: &'src str,
+}
+
+fn strip_multi_line_comment_delimiters(text: &str) -> &str {
+    text.get(2..text.len().saturating_sub(2)).unwrap_or_default()
+}
+
+impl<'src> MultiLineComment<'src> {
+    /// Vendor-specific static semantic declaration and definition
+    ///
+    /// Returns a text between `/*` and `*/`.
+    #[must_use]
+    pub const fn string_value(&self) -> &'src str {
+        self.content
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-comments>:
+    ///
+    /// > Comments behave like white space and are discarded except that, if
+    /// > a MultiLineComment contains a line terminator code point, then the
+    /// > entire comment is considered to be a LineTerminator for purposes of
+    /// > parsing by the syntactic grammar.
+    #[must_use]
+    pub fn contains_line_terminator(&self) -> bool {
+        self.content.contains(['\n', '\r', '\u{2028}', '\u{2029}'])
+    }
+}
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::SingleLineComment))]
-pub struct SingleLineComment;
+pub struct SingleLineComment<'src> {
+    #[pest_ast(outer(with(span_into_str), with(strip_single_line_comment_delimiter)))]
+    content: &'src str,
+}
 
-#[derive(Debug, Eq, FromPest, PartialEq)]
+fn strip_single_line_comment_delimiter(text: &str) -> &str {
+    text.get(2..).unwrap_or_default()
+}
+
+impl<'src> SingleLineComment<'src> {
+    /// Vendor-specific static semantic declaration and definition
+    ///
+    /// Returns a text after `//` up to but not including a line terminator.
+    #[must_use]
+    pub const fn string_value(&self) -> &'src str {
+        self.content
+    }
+}
+
+#[derive(Clone, Debug, Eq, FromPest, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::HashbangComment))]
 pub struct HashbangComment<'src> {
      #[pest_ast(outer(with(span_into_str)))]
@@ -665,11 +1605,13 @@ impl HashbangComment<'_> {
 }
 
 #[derive(Debug, FromPest)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::InputElementDiv))]
-pub enum InputElementDiv {
+pub enum InputElementDiv<'src> {
     WhiteSpace(WhiteSpace),
     LineTerminator(LineTerminator),
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
     CommonToken(CommonToken),
     DivPunctuator(DivPunctuator),
     ReservedWord(ReservedWord),
@@ -677,45 +1619,59 @@ pub enum InputElementDiv {
 }
 
 #[derive(Debug, FromPest)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::InputElementRegExp))]
-pub enum InputElementRegExp {
+pub enum InputElementRegExp<'src> {
     WhiteSpace(WhiteSpace),
     LineTerminator(LineTerminator),
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
+    RegularExpressionLiteral(RegularExpressionLiteral),
     CommonToken(CommonToken),
     ReservedWord(ReservedWord),
     RightBracePunctuator(RightBracePunctuator),
 }
 
 #[derive(Debug, FromPest)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::InputElementRegExpOrTemplateTail))]
-pub enum InputElementRegExpOrTemplateTail {
+pub enum InputElementRegExpOrTemplateTail<'src> {
     WhiteSpace(WhiteSpace),
     LineTerminator(LineTerminator),
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
+    RegularExpressionLiteral(RegularExpressionLiteral),
     CommonToken(CommonToken),
     DivPunctuator(DivPunctuator),
     ReservedWord(ReservedWord),
+    TemplateSubstitutionTail(TemplateSubstitutionTail),
 }
 
 #[derive(Debug, FromPest)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::InputElementTemplateTail))]
-pub enum InputElementTemplateTail {
+pub enum InputElementTemplateTail<'src> {
     WhiteSpace(WhiteSpace),
     LineTerminator(LineTerminator),
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
     CommonToken(CommonToken),
     DivPunctuator(DivPunctuator),
     ReservedWord(ReservedWord),
+    TemplateSubstitutionTail(TemplateSubstitutionTail),
 }
 
 #[derive(Debug, FromPest)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[pest_ast(rule(Rule::InputElementHashbangOrRegExp))]
 pub enum InputElementHashbangOrRegExp<'src> {
     WhiteSpace(WhiteSpace),
     LineTerminator(LineTerminator),
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
+    RegularExpressionLiteral(RegularExpressionLiteral),
     CommonToken(CommonToken),
+    #[cfg_attr(feature = "serde", serde(borrow))]
     HashbangComment(HashbangComment<'src>),
     ReservedWord(ReservedWord),
 }
@@ -726,19 +1682,29 @@ pub enum InputElementHashbangOrRegExp<'src> {
  *
  ************************************************/
 
-use from_pest::FromPest;
-use pest::{iterators::Pairs, Parser};
+use crate::source_text::Utf16Source;
+use crate::{Diagnostics, ErrorCode, Label, LineIndex, Severity, SourceCodeError};
+use pest::{error::{ErrorVariant, InputLocation}, iterators::Pairs, Parser};
+use std::ops::Range;
 
 /// An output of the tokenization step
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Token<'src> {
-    Comment(Comment),
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Comment(Comment<'src>),
     CommonToken(CommonToken),
     DivPunctuator(DivPunctuator),
+    #[cfg_attr(feature = "serde", serde(borrow))]
     HashbangComment(HashbangComment<'src>),
     LineTerminator(LineTerminator),
+    RegularExpressionLiteral(RegularExpressionLiteral),
     ReservedWord(ReservedWord),
     RightBracePunctuator(RightBracePunctuator),
+    TemplateSubstitutionTail(TemplateSubstitutionTail),
+    /// A code point that does not start any token, produced instead of an
+    /// error when [`LexerOptions::recover_errors`] is set.
+    Unknown(char),
     WhiteSpace(WhiteSpace),
 }
 
@@ -776,21 +1742,66 @@ pub enum GoalSymbols {
     InputElementDiv
 }
 
-enum PackedToken<'src> {
-    Div(InputElementDiv),
-    HashbangOrRegExp(InputElementHashbangOrRegExp<'src>),
-    RegExp(InputElementRegExp),
-    RegExpOrTemplateTail(InputElementRegExpOrTemplateTail),
-    TemplateTail(InputElementTemplateTail),
+impl GoalSymbols {
+    /// Guess a goal symbol for a next token from a previous one.
+    ///
+    /// The specification selects the goal symbol from the syntactic grammar
+    /// context. Without a parser at hand, the context is approximated the same
+    /// way as other engines do: a `/` is a division after something that ends
+    /// an operand (a name, a literal, `)`, `]`, `this` and other literal-like
+    /// reserved words) and a regular expression start otherwise.
+    ///
+    /// `previous` is the last token that is not white space, a line terminator
+    /// or a comment; `None` means the start of a *Script* or *Module*.
+    /// `in_template_substitution` tells that `previous` is directly inside
+    /// a template substitution (`${ ... }`) with no unclosed `{`, so a next
+    /// `}` continues the template instead of closing a block.
+    #[must_use]
+    pub const fn infer(previous: Option<&Token>, in_template_substitution: bool) -> Self {
+        let Some(previous) = previous else {
+            return Self::InputElementHashbangOrRegExp;
+        };
+        let ends_operand = matches!(
+            previous,
+            Token::CommonToken(
+                CommonToken::IdentifierName(_)
+                | CommonToken::PrivateIdentifier(_)
+                | CommonToken::NumericLiteral(_)
+                | CommonToken::StringLiteral(_)
+                | CommonToken::Template(Template::NoSubstitutionTemplate(_))
+                | CommonToken::Punctuator(Punctuator::OtherPunctuator(
+                    OtherPunctuator::ClosingParenthesis(_)
+                    | OtherPunctuator::ClosingBracket(_)
+                    | OtherPunctuator::Increment(_)
+                    | OtherPunctuator::Decrement(_)
+                ))
+            )
+            | Token::ReservedWord(
+                ReservedWord::False(_)
+                | ReservedWord::Null(_)
+                | ReservedWord::Super(_)
+                | ReservedWord::This(_)
+                | ReservedWord::True(_)
+            )
+            | Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateTail(_))
+            | Token::RegularExpressionLiteral(_)
+        );
+        match (ends_operand, in_template_substitution) {
+            (true, false) => Self::InputElementDiv,
+            (false, false) => Self::InputElementRegExp,
+            (true, true) => Self::InputElementTemplateTail,
+            (false, true) => Self::InputElementRegExpOrTemplateTail,
+        }
+    }
 }
 
 fn get_unprocessed_tail<'src>(
     recognized_source_start: &Pairs<Rule>,
     whole_source: &'src str
-) -> &'src str {
+) -> Option<&'src str> {
     let mut tokens = recognized_source_start.clone();
-    let processed_substring = tokens.next().unwrap().as_span();
-    &whole_source[processed_substring.end()..]
+    let processed_substring = tokens.next()?.as_span();
+    whole_source.get(processed_substring.end()..)
 }
 
 /// Extract a first token from a `.js`/`.mjs` text.
@@ -802,14 +1813,211 @@ fn get_unprocessed_tail<'src>(
 ///
 /// # Errors
 ///
-/// Will return `Err` with rustc-style formatted error message string, if input
-/// start does not form a correct  ECMAScript 2023 token.
+/// Will return `Err` with [`SourceCodeError`] positioned relative to `input`,
+/// if input start does not form a correct ECMAScript 2023 token.
+///
+/// The function never panics. If the library itself is broken (like a goal
+/// symbol of the grammar does not match its typed tree), the error has
+/// [`ErrorCode::InternalError`] code.
+pub fn get_next_token(input: &str, mode: GoalSymbols) -> Result<(Token<'_>, &str), SourceCodeError> {
+    get_next_token_at(input, 0, mode)
+}
+
+/// Tokenization settings not covered by goal symbols.
+// Each setting is independently enabled, so they are flags rather than
+// a state machine.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LexerOptions {
+    /// Return a run of consecutive white space code points as a single
+    /// `Token::WhiteSpace` instead of a token per code point.
+    pub coalesce_white_space: bool,
+    /// Return a code point that does not start a correct token as
+    /// `Token::Unknown` and continue instead of failing, so consumers like
+    /// editors get the rest of a token stream.
+    pub recover_errors: bool,
+    /// Tokenize strict mode code, reporting legacy octal and non-octal
+    /// decimal integer literals (`010`, `08`) and escape sequences (`'\\01'`,
+    /// `'\\8'`) as [`ErrorCode::EarlyError`].
+    ///
+    /// Such errors are not recovered from since the tokens are recognized.
+    pub strict: bool,
+    /// Recognize HTML-like comments of
+    /// <https://262.ecma-international.org/14.0/#sec-html-like-comments>,
+    /// `<!--` and `-->` first on a line up to the line end, as
+    /// `Comment::SingleLineComment`s, as web browsers do for scripts but
+    /// not for modules.
+    pub html_like_comments: bool,
+}
+
+impl std::default::Default for LexerOptions {
+    fn default() -> Self {
+        Self { coalesce_white_space: true, recover_errors: false, strict: false, html_like_comments: false }
+    }
+}
+
+impl LexerOptions {
+    /// Options producing a token per each ECMA-262 input element, as
+    /// [`get_next_token`] does.
+    #[must_use]
+    pub const fn per_input_element() -> Self {
+        Self { coalesce_white_space: false, recover_errors: false, strict: false, html_like_comments: false }
+    }
+}
+
+/// Extract a token starting at a given byte offset of a `.js`/`.mjs` text.
+///
+/// Returns a tuple of the token and its byte offsets in `source`. Unlike
+/// [`get_next_token`] returning an unprocessed tail slice, the offsets stay
+/// valid for any storage of the same text and can be passed where slices
+/// cannot, like across FFI. A next token starts at the range end.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned relative to `source`,
+/// if the text at `offset` does not form a correct ECMAScript 2023 token.
+///
+/// # Panics
+///
+/// Will panic if `offset` lies outside of `source` or does not fall on
+/// a UTF-8 character boundary.
+pub fn get_next_token_with_range(
+    source: &str,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<(Token<'_>, Range<usize>), SourceCodeError> {
+    get_next_token_with_options(source, offset, mode, &LexerOptions::per_input_element())
+}
+
+/// Extract a token starting at a given byte offset of a `.js`/`.mjs` text
+/// the way [`get_next_token_with_range`] does, with extra settings applied.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned relative to `source`,
+/// if the text at `offset` does not form a correct ECMAScript 2023 token.
 ///
 /// # Panics
 ///
-/// Will panic if the root grammar errorneously defines an empty goal symbol.
-/// This means a broken grammar file used by developers to build the parser.
-pub fn get_next_token(input: &str, mode: GoalSymbols) -> Result<(Token, &str), String> {
+/// Will panic if `offset` lies outside of `source` or does not fall on
+/// a UTF-8 character boundary.
+pub fn get_next_token_with_options<'src>(
+    source: &'src str,
+    offset: usize,
+    mode: GoalSymbols,
+    options: &LexerOptions
+) -> Result<(Token<'src>, Range<usize>), SourceCodeError> {
+    if options.html_like_comments {
+        if let Some(comment) = get_html_like_comment(source, offset) {
+            return Ok(comment);
+        }
+    }
+    let (token, tail) = match get_next_token_at(source, offset, mode) {
+        Ok(recognized) => recognized,
+        Err(error) if options.recover_errors && error.code != ErrorCode::InternalError => {
+            return get_unknown_token(source, offset).ok_or(error);
+        },
+        Err(error) => return Err(error),
+    };
+    let mut end = source.len() - tail.len();
+    if options.strict {
+        check_strict_mode_token(&token, source, offset..end)?;
+    }
+    if options.coalesce_white_space && token == Token::WhiteSpace(WhiteSpace) {
+        end += scanner::white_space_run_length(tail);
+    }
+    Ok((token, offset..end))
+}
+
+/// Extract a token starting at a given code unit offset of a UTF-16
+/// `.js`/`.mjs` text the way [`get_next_token_with_range`] does.
+///
+/// Returns a tuple of the token and its code unit offsets in `source`.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned in code units,
+/// if the text at `offset` does not form a correct ECMAScript 2023 token.
+///
+/// # Panics
+///
+/// Will panic if `offset` lies outside of `source` or falls between two
+/// code units of a surrogate pair.
+pub fn get_next_token_utf16(
+    source: &Utf16Source,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<(Token<'_>, Range<usize>), SourceCodeError> {
+    let Some(byte_offset) = source.utf8_offset(offset) else {
+        panic!("code unit offset {offset} does not fall on a code point boundary");
+    };
+    get_next_token_with_range(&source.text, byte_offset, mode)
+        .map(|(token, range)| (token, source.utf16_range(range)))
+        .map_err(|error| source.utf16_error(error))
+}
+
+/// Take `SingleLineHTMLOpenComment` or `SingleLineHTMLCloseComment` of
+/// <https://262.ecma-international.org/14.0/#sec-html-like-comments> at
+/// a given offset as a single-line comment of the text after `<!--` or
+/// `-->`.
+///
+/// A close comment must follow only white space on its line.
+fn get_html_like_comment(source: &str, offset: usize) -> Option<(Token<'_>, Range<usize>)> {
+    let tail = &source[offset..];
+    let delimiter_length = if tail.starts_with("<!--") {
+        4
+    } else if tail.starts_with("-->") && is_first_on_line(source, offset) {
+        3
+    } else {
+        return None;
+    };
+    let end = offset + delimiter_length + scanner::line_length(&tail[delimiter_length..]);
+    let comment = SingleLineComment { content: &source[offset + delimiter_length..end] };
+    Some((Token::Comment(Comment::SingleLineComment(comment)), offset..end))
+}
+
+/// Take a code point at a given offset as a token.
+fn get_unknown_token(source: &str, offset: usize) -> Option<(Token<'_>, Range<usize>)> {
+    let codepoint = source.get(offset..)?.chars().next()?;
+    Some((Token::Unknown(codepoint), offset..offset + codepoint.len_utf8()))
+}
+
+/// Extract a first token from a `.js`/`.mjs` text the way [`get_next_token`]
+/// does, matching `lexical_grammar.pest` with `pest` directly.
+///
+/// It is several times slower than the hand-written scanner behind other
+/// functions and is kept as a reference to test the scanner against.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned relative to `input`,
+/// if input start does not form a correct ECMAScript 2023 token.
+pub fn get_next_token_with_grammar(input: &str, mode: GoalSymbols) -> Result<(Token<'_>, &str), SourceCodeError> {
+    get_next_token_from_grammar(input, 0, mode)
+}
+
+fn get_next_token_at(
+    source: &str,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<(Token<'_>, &str), SourceCodeError> {
+    let input = &source[offset..];
+    let Some((token, length)) = scanner::scan(input, mode) else {
+        // Let the grammar describe what is wrong.
+        let result = get_next_token_from_grammar(source, offset, mode);
+        debug_assert!(result.is_err(), "the scanner rejected a token matched by the grammar");
+        return result;
+    };
+    check_token(&token, source, offset..offset + length)?;
+    Ok((token, &input[length..]))
+}
+
+fn get_next_token_from_grammar(
+    source: &str,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<(Token<'_>, &str), SourceCodeError> {
+    let input = &source[offset..];
     let goal = match mode {
         GoalSymbols::InputElementHashbangOrRegExp => Rule::InputElementHashbangOrRegExp,
         GoalSymbols::InputElementRegExpOrTemplateTail => Rule::InputElementRegExpOrTemplateTail,
@@ -817,88 +2025,1560 @@ pub fn get_next_token(input: &str, mode: GoalSymbols) -> Result<(Token, &str), S
         GoalSymbols::InputElementTemplateTail => Rule::InputElementTemplateTail,
         GoalSymbols::InputElementDiv => Rule::InputElementDiv
     };
-    Ecma262Parser::parse(goal, input)
-        .map(|mut tree| -> (Token, &str) {
-            let tail = get_unprocessed_tail(&tree, input);
-            let typed_packed: PackedToken = match mode {
-                GoalSymbols::InputElementHashbangOrRegExp => {
-                    let typed = InputElementHashbangOrRegExp::from_pest(&mut tree);
-                    PackedToken::HashbangOrRegExp(typed.unwrap())
-                },
-                GoalSymbols::InputElementRegExpOrTemplateTail => {
-                    let typed = InputElementRegExpOrTemplateTail::from_pest(&mut tree);
-                    PackedToken::RegExpOrTemplateTail(typed.unwrap())
-                },
-                GoalSymbols::InputElementRegExp => {
-                    let typed = InputElementRegExp::from_pest(&mut tree);
-                    PackedToken::RegExp(typed.unwrap())
-                },
-                GoalSymbols::InputElementTemplateTail => {
-                    let typed = InputElementTemplateTail::from_pest(&mut tree);
-                    PackedToken::TemplateTail(typed.unwrap())
-                },
-                GoalSymbols::InputElementDiv => {
-                    let typed = InputElementDiv::from_pest(&mut tree);
-                    PackedToken::Div(typed.unwrap())
-                },
-            };
-            (unpack_token(typed_packed), tail)
+    let mut tree = Ecma262Parser::parse(goal, input).map_err(|error| {
+        describe_unterminated_token(source, offset, mode)
+            .unwrap_or_else(|| into_source_code_error(&error, source, offset))
+    })?;
+    let Some(tail) = get_unprocessed_tail(&tree, input) else {
+        let message = format!("{goal:?} produced no parse tree");
+        return Err(internal_error(source, offset..offset, message));
+    };
+    let Some(token) = tree.next().and_then(pairs::token) else {
+        let message = format!("cannot convert {goal:?} parse tree");
+        return Err(internal_error(source, offset..source.len() - tail.len(), message));
+    };
+    check_token(&token, source, offset..source.len() - tail.len())?;
+    Ok((token, tail))
+}
+
+/// Report legacy and deprecated constructs of correct source text.
+///
+/// These are tokens [`check_strict_mode_token`] rejects in strict mode code
+/// and HTML-like comments of
+/// <https://262.ecma-international.org/14.0/#sec-html-like-comments>.
+/// Unless [`LexerOptions::html_like_comments`] are recognized, `<!--` and
+/// `-->` are tokenized as punctuators unlike in web browsers, which is
+/// reported too.
+///
+/// Deprecated statements like `with` are left to a parser since a token
+/// alone does not tell a statement from a property name.
+pub(crate) fn check_legacy_token(token: &Token<'_>, source: &str, range: Range<usize>) -> Option<SourceCodeError> {
+    let mut warning = if let Err(error) = check_strict_mode_token(token, source, range.clone()) {
+        error
+    } else {
+        let (range, message) = match token.kind() {
+            TokenKind::SingleLineComment if source[range.start..].starts_with(['<', '-']) => {
+                (range, "HTML-like comments are deprecated")
+            },
+            TokenKind::Less if source[range.start..].starts_with("<!--") => {
+                (range.start..range.start + 4, "`<!--` is tokenized as `<`, `!` and `--`, not as an HTML-like comment")
+            },
+            TokenKind::Decrement if source[range.end..].starts_with('>') && is_first_on_line(source, range.start) => {
+                (range.start..range.end + 1, "`-->` is tokenized as `--` and `>`, not as an HTML-like comment")
+            },
+            _ => return None,
+        };
+        SourceCodeError::new(source, range, ErrorCode::DeprecatedSyntax, message.to_owned())
+    };
+    warning.code = ErrorCode::DeprecatedSyntax;
+    warning.severity = Severity::Warning;
+    Some(warning)
+}
+
+/// Check that only white space separates an offset from a preceding line
+/// terminator or the start of the text.
+fn is_first_on_line(source: &str, offset: usize) -> bool {
+    let line_start = source[..offset].rfind(['\n', '\r', '\u{2028}', '\u{2029}']).unwrap_or(0);
+    source[line_start..offset].trim().is_empty()
+}
+
+/// Report tokens forbidden in strict mode code.
+///
+/// See <https://262.ecma-international.org/14.0/#sec-numeric-literals-early-errors>
+/// and <https://262.ecma-international.org/14.0/#sec-string-literals-early-errors>.
+fn check_strict_mode_token(token: &Token<'_>, source: &str, range: Range<usize>) -> Result<(), SourceCodeError> {
+    let error = match token {
+        Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::LegacyOctalIntegerLiteral(_))) => {
+            Some((range, "legacy octal literals are not allowed in strict mode code"))
+        },
+        Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::DecimalLiteral(literal))) => {
+            literal.integer_part()
+                .filter(|digits| digits.len() > 1 && digits.starts_with('0'))
+                .map(|digits| {
+                    let digits_range = range.start..range.start + digits.len();
+                    (digits_range, "decimals with a leading zero are not allowed in strict mode code")
+                })
+        },
+        Token::CommonToken(CommonToken::StringLiteral(_)) => {
+            find_legacy_escape(&source[range.clone()]).map(|escape| {
+                let escape_range = range.start + escape.start..range.start + escape.end;
+                (escape_range, "octal escape sequences are not allowed in strict mode code")
+            })
+        },
+        _ => None,
+    };
+    match error {
+        Some((range, message)) => {
+            Err(SourceCodeError::new(source, range, ErrorCode::EarlyError, message.to_owned()))
+        },
+        None => Ok(()),
+    }
+}
+
+/// Find a first `LegacyOctalEscapeSequence` or
+/// `NonOctalDecimalEscapeSequence` of a matched string literal.
+fn find_legacy_escape(text: &str) -> Option<Range<usize>> {
+    let is_octal = |position: usize| text.as_bytes().get(position).is_some_and(|byte| matches!(byte, b'0'..=b'7'));
+    let mut position = 0;
+    while let Some(found) = text[position..].find('\\') {
+        let escape = position + found + 1;
+        let first = text.as_bytes().get(escape).copied().unwrap_or_default();
+        let length = match first {
+            b'0' if !text.as_bytes().get(escape + 1).is_some_and(u8::is_ascii_digit) => 0,
+            b'0'..=b'7' if !is_octal(escape + 1) => 1,
+            b'4'..=b'7' => 2,
+            b'0'..=b'3' => if is_octal(escape + 2) { 3 } else { 2 },
+            b'8' | b'9' => 1,
+            _ => 0,
+        };
+        if length > 0 {
+            return Some(escape - 1..escape + length);
+        }
+        position = escape + text[escape..].chars().next().map_or(0, char::len_utf8);
+    }
+    None
+}
+
+/// Report errors that the grammar cannot express.
+fn check_token(token: &Token<'_>, source: &str, range: Range<usize>) -> Result<(), SourceCodeError> {
+    let name_start = match token {
+        Token::CommonToken(CommonToken::IdentifierName(_)) => Some(range.start),
+        Token::CommonToken(CommonToken::PrivateIdentifier(_)) => Some(range.start + 1),
+        _ => None,
+    };
+    let error = if let Token::RegularExpressionLiteral(literal) = token {
+        let flags_start = range.end - literal.flag_text().len();
+        find_invalid_regular_expression_flag(literal.flag_text()).map(|flag| {
+            (flags_start + flag.start..flags_start + flag.end, "invalid regular expression flag")
         })
-        .map_err(|error| error.to_string())
-}
-
-fn unpack_token(input: PackedToken<'_>) -> Token<'_> {
-    match input {
-        PackedToken::Div(root) => {
-            match root {
-                InputElementDiv::WhiteSpace(item) => Token::WhiteSpace(item),
-                InputElementDiv::LineTerminator(item) => Token::LineTerminator(item),
-                InputElementDiv::Comment(item) => Token::Comment(item),
-                InputElementDiv::CommonToken(item) => Token::CommonToken(item),
-                InputElementDiv::DivPunctuator(item) => Token::DivPunctuator(item),
-                InputElementDiv::ReservedWord(item) => Token::ReservedWord(item),
-                InputElementDiv::RightBracePunctuator(item) => Token::RightBracePunctuator(item),
-            }
+    } else if let Token::CommonToken(CommonToken::NumericLiteral(_)) = token {
+        let tail = &source[range.end..];
+        find_misplaced_separator(tail).map_or_else(
+            || {
+                find_numeric_literal_continuation(tail).map(|length| {
+                    (range.end..range.end + length, "numeric literal must not be followed by a name or a digit")
+                })
+            },
+            |position| {
+                let start = range.end + position;
+                Some((start..start + 1, "numeric separator `_` is allowed only between digits"))
+            },
+        )
+    } else if let Some(start) = name_start {
+        find_invalid_identifier_escape(&source[start..range.end]).map(|escape| {
+            (start + escape.start..start + escape.end, "escape sequence does not form an identifier character")
+        })
+    } else {
+        None
+    };
+    match error {
+        Some((range, message)) => {
+            Err(SourceCodeError::new(source, range, ErrorCode::UnexpectedInput, message.to_owned()))
         },
-        PackedToken::HashbangOrRegExp(root) => {
-            match root {
-                InputElementHashbangOrRegExp::WhiteSpace(item) => Token::WhiteSpace(item),
-                InputElementHashbangOrRegExp::LineTerminator(item) => Token::LineTerminator(item),
-                InputElementHashbangOrRegExp::Comment(item) => Token::Comment(item),
-                InputElementHashbangOrRegExp::CommonToken(item) => Token::CommonToken(item),
-                InputElementHashbangOrRegExp::HashbangComment(item) => Token::HashbangComment(item),
-                InputElementHashbangOrRegExp::ReservedWord(item) => Token::ReservedWord(item),
+        None => Ok(()),
+    }
+}
+
+/// Find a `\u` escape that does not stand for a code point allowed at its
+/// place in an `IdentifierName`.
+///
+/// From <https://262.ecma-international.org/14.0/#sec-identifier-names-static-semantics-early-errors>:
+///
+/// > IdentifierStart :: `\` UnicodeEscapeSequence
+/// >
+/// > - It is a Syntax Error if IdentifierCodePoint of UnicodeEscapeSequence
+/// >   is not some Unicode code point matched by the IdentifierStartChar
+/// >   lexical grammar production.
+/// >
+/// > IdentifierPart :: `\` UnicodeEscapeSequence
+/// >
+/// > - It is a Syntax Error if IdentifierCodePoint of UnicodeEscapeSequence
+/// >   is not some Unicode code point matched by the IdentifierPartChar
+/// >   lexical grammar production.
+fn find_invalid_identifier_escape(name: &str) -> Option<Range<usize>> {
+    let mut rule = Rule::IdentifierStartChar;
+    let mut position = 0;
+    while let Some(tail) = name.get(position..).filter(|tail| !tail.is_empty()) {
+        let length = match tail.strip_prefix("\\u{") {
+            Some(rest) => rest.find('}').map_or(tail.len(), |end| end + 4),
+            None if tail.starts_with('\\') => tail.len().min(6),
+            None => tail.chars().next().map_or(1, char::len_utf8),
+        };
+        let escape = position..position + length;
+        if tail.starts_with('\\') {
+            let decoded = decode_identifier_name(&name[escape.clone()]);
+            let is_allowed = Ecma262Parser::parse(rule, &decoded)
+                .is_ok_and(|pairs| pairs.as_str() == decoded);
+            if !is_allowed {
+                return Some(escape);
             }
+        }
+        rule = Rule::IdentifierPartChar;
+        position = escape.end;
+    }
+    None
+}
+
+/// Find a `_` that was left out of a numeric literal because it does not
+/// stand between two digits.
+///
+/// From <https://262.ecma-international.org/14.0/#sec-literals-numeric-literals>:
+///
+/// > The SourceCharacter immediately following a NumericLiteral must not be
+/// > an IdentifierStart or DecimalDigit.
+///
+/// so the tail of a numeric literal starting with `_` (`1_`, `1__0`),
+/// optionally after a prefix (`0x_1`) or an exponent indicator (`1e_5`,
+/// `1e+_5`), is a syntax error anyway; the function just points at it.
+fn find_misplaced_separator(tail: &str) -> Option<usize> {
+    let bytes = tail.as_bytes();
+    let position = match bytes {
+        [b'e' | b'E', b'+' | b'-', ..] => 2,
+        [b'x' | b'X' | b'o' | b'O' | b'b' | b'B' | b'e' | b'E', ..] => 1,
+        _ => 0,
+    };
+    (bytes.get(position) == Some(&b'_')).then_some(position)
+}
+
+/// Find a code point that immediately follows a numeric literal but cannot,
+/// returning its length, like `i` in `3in` (see the restriction quoted for
+/// [`find_misplaced_separator`]). A `\` is taken for a start of an escape in
+/// `IdentifierStart`.
+fn find_numeric_literal_continuation(tail: &str) -> Option<usize> {
+    let next = tail.chars().next()?;
+    let is_forbidden = match next {
+        '0'..='9' | 'a'..='z' | 'A'..='Z' | '$' | '_' | '\\' => true,
+        _ if next.is_ascii() => false,
+        _ => Ecma262Parser::parse(Rule::IdentifierStartChar, &tail[..next.len_utf8()]).is_ok(),
+    };
+    is_forbidden.then_some(next.len_utf8())
+}
+
+/// Report a disagreement between the grammar and its typed tree.
+///
+/// It is a bug of the library, not of the tokenized text, so it is reported
+/// instead of panicking to let the embedder survive it.
+fn internal_error(source: &str, range: Range<usize>, message: String) -> SourceCodeError {
+    SourceCodeError::new(source, range, ErrorCode::InternalError, message)
+}
+
+fn into_source_code_error(
+    error: &pest::error::Error<Rule>,
+    source: &str,
+    offset: usize
+) -> SourceCodeError {
+    let range = match error.location {
+        InputLocation::Pos(position) => {
+            // Blame a whole code point the tokenizer stumbled on, if any.
+            let start = offset + position;
+            let width = source[start..].chars().next().map_or(0, char::len_utf8);
+            start..start + width
         },
-        PackedToken::RegExp(root) => {
-            match root {
-                InputElementRegExp::WhiteSpace(item) => Token::WhiteSpace(item),
-                InputElementRegExp::LineTerminator(item) => Token::LineTerminator(item),
-                InputElementRegExp::Comment(item) => Token::Comment(item),
-                InputElementRegExp::CommonToken(item) => Token::CommonToken(item),
-                InputElementRegExp::ReservedWord(item) => Token::ReservedWord(item),
-                InputElementRegExp::RightBracePunctuator(item) => Token::RightBracePunctuator(item),
-            }
+        InputLocation::Span((start, end)) => offset + start..offset + end,
+    };
+    let is_goal_symbol = |rule: &Rule| {
+        matches!(
+            rule,
+            Rule::InputElementHashbangOrRegExp
+                | Rule::InputElementRegExpOrTemplateTail
+                | Rule::InputElementRegExp
+                | Rule::InputElementTemplateTail
+                | Rule::InputElementDiv
+        )
+    };
+    match &error.variant {
+        // A goal symbol is a name internal to the tokenizer, so such an
+        // error only tells that no token starts at the place.
+        ErrorVariant::ParsingError { positives, .. } if positives.iter().all(is_goal_symbol) => {
+            // Spell out code points that would not show in a message.
+            let found = match source[range.clone()].chars().next() {
+                None => "end of input".to_owned(),
+                Some(codepoint) if codepoint.is_ascii_graphic() || codepoint.is_alphanumeric() => format!("`{codepoint}`"),
+                Some(codepoint) => format!("U+{:04X}", u32::from(codepoint)),
+            };
+            let message = format!("expected a token, found {found}");
+            let mut converted = SourceCodeError::new(source, range, ErrorCode::UnexpectedInput, message);
+            converted.expected.push("a token".to_owned());
+            converted
         },
-        PackedToken::RegExpOrTemplateTail(root) => {
-            match root {
-                InputElementRegExpOrTemplateTail::WhiteSpace(item) => Token::WhiteSpace(item),
-                InputElementRegExpOrTemplateTail::LineTerminator(item) => Token::LineTerminator(item),
-                InputElementRegExpOrTemplateTail::Comment(item) => Token::Comment(item),
-                InputElementRegExpOrTemplateTail::CommonToken(item) => Token::CommonToken(item),
-                InputElementRegExpOrTemplateTail::DivPunctuator(item) => Token::DivPunctuator(item),
-                InputElementRegExpOrTemplateTail::ReservedWord(item) => Token::ReservedWord(item),
-            }
+        ErrorVariant::ParsingError { positives, .. } => {
+            let message = error.variant.message().into_owned();
+            let mut converted = SourceCodeError::new(source, range, ErrorCode::UnexpectedInput, message);
+            converted.expected = positives.iter().map(|rule| format!("{rule:?}")).collect();
+            converted
+        },
+        ErrorVariant::CustomError { message } => {
+            SourceCodeError::new(source, range, ErrorCode::UnexpectedInput, message.clone())
+        },
+    }
+}
+
+/// Report a string, template, comment or regular expression literal that
+/// lacks its closing delimiter.
+///
+/// Token rules are atomic, so the grammar can only tell that no token starts
+/// at `offset`; this blames the opening delimiter and labels the place the
+/// closing one is missing at.
+fn describe_unterminated_token(source: &str, offset: usize, mode: GoalSymbols) -> Option<SourceCodeError> {
+    let input = &source[offset..];
+    let allows_regular_expression = matches!(
+        mode,
+        GoalSymbols::InputElementHashbangOrRegExp
+            | GoalSymbols::InputElementRegExp
+            | GoalSymbols::InputElementRegExpOrTemplateTail
+    );
+    let allows_template_tail = matches!(
+        mode,
+        GoalSymbols::InputElementRegExpOrTemplateTail | GoalSymbols::InputElementTemplateTail
+    );
+    let (kind, opening, end, expected): (_, _, _, &[&str]) = match input.as_bytes() {
+        [quote @ (b'"' | b'\''), ..] => {
+            let end = find_string_literal_end(input, *quote)?;
+            let expected: &[&str] = if *quote == b'"' { &["`\"`"] } else { &["`'`"] };
+            ("string literal", 1, end, expected)
         },
-        PackedToken::TemplateTail(root) => {
-            match root {
-                InputElementTemplateTail::WhiteSpace(item) => Token::WhiteSpace(item),
-                InputElementTemplateTail::LineTerminator(item) => Token::LineTerminator(item),
-                InputElementTemplateTail::Comment(item) => Token::Comment(item),
-                InputElementTemplateTail::CommonToken(item) => Token::CommonToken(item),
-                InputElementTemplateTail::DivPunctuator(item) => Token::DivPunctuator(item),
-                InputElementTemplateTail::ReservedWord(item) => Token::ReservedWord(item),
+        [b'`', ..] => ("template literal", 1, find_template_end(input)?, &["`` ` ``", "`${`"]),
+        [b'}', ..] if allows_template_tail => ("template literal", 1, find_template_end(input)?, &["`` ` ``", "`${`"]),
+        [b'/', b'*', ..] => {
+            if input.contains("*/") {
+                return None;
             }
+            ("comment", 2, input.len(), &["`*/`"])
         },
+        [b'/', ..] if allows_regular_expression => {
+            ("regular expression literal", 1, find_regular_expression_end(input)?, &["`/`"])
+        },
+        _ => return None,
+    };
+    let expected: Vec<String> = expected.iter().map(ToString::to_string).collect();
+    let message = format!("unterminated {kind}");
+    let mut error = SourceCodeError::new(source, offset..offset + opening, ErrorCode::UnexpectedInput, message);
+    error.labels.push(Label {
+        range: offset + end..offset + end,
+        message: format!("expected {} here", expected.join(" or ")),
+    });
+    error.expected = expected;
+    Some(error)
+}
+
+/// Find where a string literal lacking its closing quote stops: at a line
+/// terminator other than `<LS>` and `<PS>` or at the end of input.
+fn find_string_literal_end(input: &str, quote: u8) -> Option<usize> {
+    let mut codepoints = input.char_indices().skip(1);
+    while let Some((position, codepoint)) = codepoints.next() {
+        match codepoint {
+            '\\' => {
+                codepoints.next();
+            },
+            '\n' | '\r' => return Some(position),
+            _ if codepoint == char::from(quote) => return None,
+            _ => {},
+        }
+    }
+    Some(input.len())
+}
+
+/// Find the end of input if a template literal continuation is not closed
+/// by `` ` `` or `${`.
+fn find_template_end(input: &str) -> Option<usize> {
+    let mut codepoints = input.char_indices().skip(1).peekable();
+    while let Some((_, codepoint)) = codepoints.next() {
+        match codepoint {
+            '\\' => {
+                codepoints.next();
+            },
+            '`' => return None,
+            '$' if codepoints.next_if(|(_, next)| *next == '{').is_some() => return None,
+            _ => {},
+        }
+    }
+    Some(input.len())
+}
+
+/// Find where a regular expression literal lacking its closing `/` stops:
+/// at a line terminator or at the end of input.
+fn find_regular_expression_end(input: &str) -> Option<usize> {
+    let mut is_in_class = false;
+    let mut codepoints = input.char_indices().skip(1);
+    while let Some((position, codepoint)) = codepoints.next() {
+        match codepoint {
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => return Some(position),
+            '\\' => match codepoints.next() {
+                Some((position, '\n' | '\r' | '\u{2028}' | '\u{2029}')) => return Some(position),
+                Some(_) => {},
+                None => break,
+            },
+            '[' => is_in_class = true,
+            ']' => is_in_class = false,
+            '/' if !is_in_class => return None,
+            _ => {},
+        }
+    }
+    Some(input.len())
+}
+
+/************************************************
+ *
+ * Compact token representation
+ *
+ ************************************************/
+
+/// A byte range of a token inside a source text.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+impl Span {
+    #[must_use]
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Length of the range in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Slice the text covered by the span out of the source it was taken from.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the span lies outside of `source` or does not fall on
+    /// UTF-8 character boundaries, that is, when `source` is not the text
+    /// the span was produced for.
+    #[must_use]
+    pub fn source_text<'src>(&self, source: &'src str) -> &'src str {
+        &source[self.start..self.end]
+    }
+}
+
+/// A flat identifier of a terminal production recognized by the tokenizer.
+///
+/// Unlike [`Token`], which mirrors nesting of the grammar
+/// (`Token::CommonToken(CommonToken::Punctuator(...))`), the kind is a plain
+/// 16-bit number that is cheap to store, compare and dispatch on.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[repr(u16)]
+pub enum TokenKind {
+    WhiteSpace,
+    LineTerminator,
+    MultiLineComment,
+    SingleLineComment,
+    HashbangComment,
+    IdentifierName,
+    PrivateIdentifier,
+    NumericLiteral,
+    StringLiteral,
+    NoSubstitutionTemplate,
+    TemplateHead,
+    TemplateMiddle,
+    TemplateTail,
+    RegularExpressionLiteral,
+    OptionalChainingPunctuator,
+    Addition,
+    AdditionAssignment,
+    And,
+    AndAssignment,
+    Assignment,
+    BitAnd,
+    BitAndAssignment,
+    BitNot,
+    BitOr,
+    BitOrAssignment,
+    BitXor,
+    BitXorAssignment,
+    ClosingBracket,
+    ClosingParenthesis,
+    Colon,
+    Comma,
+    Decrement,
+    Dot,
+    Ellipsis,
+    Exponentiation,
+    ExponentiationAssignment,
+    FunctionArrow,
+    Increment,
+    LeftShift,
+    LeftShiftAssignment,
+    Less,
+    LessOrEqual,
+    LooseEquality,
+    LooseInequality,
+    Modulo,
+    ModuloAssignment,
+    More,
+    MoreOrEqual,
+    Multiplication,
+    MultiplicationAssignment,
+    Not,
+    NullishCoalescence,
+    NullishCoalescenceAssignment,
+    OpeningBrace,
+    OpeningBracket,
+    OpeningParenthesis,
+    Or,
+    OrAssignment,
+    QuestionMark,
+    RightShift,
+    RightShiftAssignment,
+    Semicolon,
+    StrictEquality,
+    StrictInequality,
+    Subtraction,
+    SubtractionAssignment,
+    UnsignedRightShift,
+    UnsignedRightShiftAssignment,
+    Division,
+    DivisionAssignment,
+    RightBracePunctuator,
+    Await,
+    Break,
+    Case,
+    Catch,
+    Class,
+    Const,
+    Continue,
+    Debugger,
+    Default,
+    Delete,
+    Do,
+    Else,
+    Enum,
+    Export,
+    Extends,
+    False,
+    Finally,
+    For,
+    Function,
+    If,
+    Import,
+    In,
+    Instanceof,
+    New,
+    Null,
+    Return,
+    Super,
+    Switch,
+    This,
+    Throw,
+    True,
+    Try,
+    Typeof,
+    Var,
+    Void,
+    While,
+    With,
+    Yield,
+    Unknown,
+}
+
+impl TokenKind {
+    /// Every kind in the order of their numeric values.
+    pub const ALL: &'static [Self] = &[
+        Self::WhiteSpace,
+        Self::LineTerminator,
+        Self::MultiLineComment,
+        Self::SingleLineComment,
+        Self::HashbangComment,
+        Self::IdentifierName,
+        Self::PrivateIdentifier,
+        Self::NumericLiteral,
+        Self::StringLiteral,
+        Self::NoSubstitutionTemplate,
+        Self::TemplateHead,
+        Self::TemplateMiddle,
+        Self::TemplateTail,
+        Self::RegularExpressionLiteral,
+        Self::OptionalChainingPunctuator,
+        Self::Addition,
+        Self::AdditionAssignment,
+        Self::And,
+        Self::AndAssignment,
+        Self::Assignment,
+        Self::BitAnd,
+        Self::BitAndAssignment,
+        Self::BitNot,
+        Self::BitOr,
+        Self::BitOrAssignment,
+        Self::BitXor,
+        Self::BitXorAssignment,
+        Self::ClosingBracket,
+        Self::ClosingParenthesis,
+        Self::Colon,
+        Self::Comma,
+        Self::Decrement,
+        Self::Dot,
+        Self::Ellipsis,
+        Self::Exponentiation,
+        Self::ExponentiationAssignment,
+        Self::FunctionArrow,
+        Self::Increment,
+        Self::LeftShift,
+        Self::LeftShiftAssignment,
+        Self::Less,
+        Self::LessOrEqual,
+        Self::LooseEquality,
+        Self::LooseInequality,
+        Self::Modulo,
+        Self::ModuloAssignment,
+        Self::More,
+        Self::MoreOrEqual,
+        Self::Multiplication,
+        Self::MultiplicationAssignment,
+        Self::Not,
+        Self::NullishCoalescence,
+        Self::NullishCoalescenceAssignment,
+        Self::OpeningBrace,
+        Self::OpeningBracket,
+        Self::OpeningParenthesis,
+        Self::Or,
+        Self::OrAssignment,
+        Self::QuestionMark,
+        Self::RightShift,
+        Self::RightShiftAssignment,
+        Self::Semicolon,
+        Self::StrictEquality,
+        Self::StrictInequality,
+        Self::Subtraction,
+        Self::SubtractionAssignment,
+        Self::UnsignedRightShift,
+        Self::UnsignedRightShiftAssignment,
+        Self::Division,
+        Self::DivisionAssignment,
+        Self::RightBracePunctuator,
+        Self::Await,
+        Self::Break,
+        Self::Case,
+        Self::Catch,
+        Self::Class,
+        Self::Const,
+        Self::Continue,
+        Self::Debugger,
+        Self::Default,
+        Self::Delete,
+        Self::Do,
+        Self::Else,
+        Self::Enum,
+        Self::Export,
+        Self::Extends,
+        Self::False,
+        Self::Finally,
+        Self::For,
+        Self::Function,
+        Self::If,
+        Self::Import,
+        Self::In,
+        Self::Instanceof,
+        Self::New,
+        Self::Null,
+        Self::Return,
+        Self::Super,
+        Self::Switch,
+        Self::This,
+        Self::Throw,
+        Self::True,
+        Self::Try,
+        Self::Typeof,
+        Self::Var,
+        Self::Void,
+        Self::While,
+        Self::With,
+        Self::Yield,
+        Self::Unknown,
+    ];
+}
+
+impl TokenKind {
+    /// Whether the kind is `WhiteSpace`, `LineTerminator`, `Comment` or
+    /// `HashbangComment`, that is, it separates tokens meaningful to the
+    /// syntactic grammar.
+    #[must_use]
+    pub const fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            Self::WhiteSpace
+                | Self::LineTerminator
+                | Self::MultiLineComment
+                | Self::SingleLineComment
+                | Self::HashbangComment
+        )
+    }
+
+    /// Whether the kind is `Punctuator`, `DivPunctuator` or
+    /// `RightBracePunctuator`.
+    #[must_use]
+    pub const fn is_punctuator(self) -> bool {
+        self.punctuator_text().is_some()
+    }
+
+    /// Whether the kind is `ReservedWord`.
+    #[must_use]
+    pub const fn is_keyword(self) -> bool {
+        self.keyword_text().is_some()
+    }
+
+    /// The only source text a punctuator kind can be recognized from.
+    #[must_use]
+    pub const fn punctuator_text(self) -> Option<&'static str> {
+        let text = match self {
+            Self::OptionalChainingPunctuator => "?.",
+            Self::Addition => "+",
+            Self::AdditionAssignment => "+=",
+            Self::And => "&&",
+            Self::AndAssignment => "&&=",
+            Self::Assignment => "=",
+            Self::BitAnd => "&",
+            Self::BitAndAssignment => "&=",
+            Self::BitNot => "~",
+            Self::BitOr => "|",
+            Self::BitOrAssignment => "|=",
+            Self::BitXor => "^",
+            Self::BitXorAssignment => "^=",
+            Self::ClosingBracket => "]",
+            Self::ClosingParenthesis => ")",
+            Self::Colon => ":",
+            Self::Comma => ",",
+            Self::Decrement => "--",
+            Self::Dot => ".",
+            Self::Ellipsis => "...",
+            Self::Exponentiation => "**",
+            Self::ExponentiationAssignment => "**=",
+            Self::FunctionArrow => "=>",
+            Self::Increment => "++",
+            Self::LeftShift => "<<",
+            Self::LeftShiftAssignment => "<<=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::LooseEquality => "==",
+            Self::LooseInequality => "!=",
+            Self::Modulo => "%",
+            Self::ModuloAssignment => "%=",
+            Self::More => ">",
+            Self::MoreOrEqual => ">=",
+            Self::Multiplication => "*",
+            Self::MultiplicationAssignment => "*=",
+            Self::Not => "!",
+            Self::NullishCoalescence => "??",
+            Self::NullishCoalescenceAssignment => "??=",
+            Self::OpeningBrace => "{",
+            Self::OpeningBracket => "[",
+            Self::OpeningParenthesis => "(",
+            Self::Or => "||",
+            Self::OrAssignment => "||=",
+            Self::QuestionMark => "?",
+            Self::RightShift => ">>",
+            Self::RightShiftAssignment => ">>=",
+            Self::Semicolon => ";",
+            Self::StrictEquality => "===",
+            Self::StrictInequality => "!==",
+            Self::Subtraction => "-",
+            Self::SubtractionAssignment => "-=",
+            Self::UnsignedRightShift => ">>>",
+            Self::UnsignedRightShiftAssignment => ">>>=",
+            Self::Division => "/",
+            Self::DivisionAssignment => "/=",
+            Self::RightBracePunctuator => "}",
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// The only source text a reserved word kind can be recognized from.
+    #[must_use]
+    pub const fn keyword_text(self) -> Option<&'static str> {
+        let text = match self {
+            Self::Await => "await",
+            Self::Break => "break",
+            Self::Case => "case",
+            Self::Catch => "catch",
+            Self::Class => "class",
+            Self::Const => "const",
+            Self::Continue => "continue",
+            Self::Debugger => "debugger",
+            Self::Default => "default",
+            Self::Delete => "delete",
+            Self::Do => "do",
+            Self::Else => "else",
+            Self::Enum => "enum",
+            Self::Export => "export",
+            Self::Extends => "extends",
+            Self::False => "false",
+            Self::Finally => "finally",
+            Self::For => "for",
+            Self::Function => "function",
+            Self::If => "if",
+            Self::Import => "import",
+            Self::In => "in",
+            Self::Instanceof => "instanceof",
+            Self::New => "new",
+            Self::Null => "null",
+            Self::Return => "return",
+            Self::Super => "super",
+            Self::Switch => "switch",
+            Self::This => "this",
+            Self::Throw => "throw",
+            Self::True => "true",
+            Self::Try => "try",
+            Self::Typeof => "typeof",
+            Self::Var => "var",
+            Self::Void => "void",
+            Self::While => "while",
+            Self::With => "with",
+            Self::Yield => "yield",
+            _ => return None,
+        };
+        Some(text)
+    }
+}
+
+impl TryFrom<u16> for TokenKind {
+    type Error = u16;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::ALL.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
+impl From<TokenKind> for u16 {
+    fn from(kind: TokenKind) -> Self {
+        kind as Self
+    }
+}
+
+impl Token<'_> {
+    /// Get a flat identifier of the token production.
+    #[must_use]
+    pub const fn kind(&self) -> TokenKind {
+        match self {
+            Token::WhiteSpace(_) => TokenKind::WhiteSpace,
+            Token::LineTerminator(_) => TokenKind::LineTerminator,
+            Token::Comment(Comment::MultiLineComment(_)) => TokenKind::MultiLineComment,
+            Token::Comment(Comment::SingleLineComment(_)) => TokenKind::SingleLineComment,
+            Token::HashbangComment(_) => TokenKind::HashbangComment,
+            Token::Unknown(_) => TokenKind::Unknown,
+            Token::CommonToken(CommonToken::IdentifierName(_)) => TokenKind::IdentifierName,
+            Token::CommonToken(CommonToken::PrivateIdentifier(_)) => TokenKind::PrivateIdentifier,
+            Token::CommonToken(CommonToken::NumericLiteral(_)) => TokenKind::NumericLiteral,
+            Token::CommonToken(CommonToken::StringLiteral(_)) => TokenKind::StringLiteral,
+            Token::CommonToken(CommonToken::Template(Template::NoSubstitutionTemplate(_))) => {
+                TokenKind::NoSubstitutionTemplate
+            },
+            Token::CommonToken(CommonToken::Template(Template::TemplateHead(_))) => TokenKind::TemplateHead,
+            Token::CommonToken(CommonToken::Punctuator(punctuator)) => match punctuator {
+                Punctuator::OptionalChainingPunctuator(_) => TokenKind::OptionalChainingPunctuator,
+                Punctuator::OtherPunctuator(other) => match other {
+                    OtherPunctuator::Addition(_) => TokenKind::Addition,
+                    OtherPunctuator::AdditionAssignment(_) => TokenKind::AdditionAssignment,
+                    OtherPunctuator::And(_) => TokenKind::And,
+                    OtherPunctuator::AndAssignment(_) => TokenKind::AndAssignment,
+                    OtherPunctuator::Assignment(_) => TokenKind::Assignment,
+                    OtherPunctuator::BitAnd(_) => TokenKind::BitAnd,
+                    OtherPunctuator::BitAndAssignment(_) => TokenKind::BitAndAssignment,
+                    OtherPunctuator::BitNot(_) => TokenKind::BitNot,
+                    OtherPunctuator::BitOr(_) => TokenKind::BitOr,
+                    OtherPunctuator::BitOrAssignment(_) => TokenKind::BitOrAssignment,
+                    OtherPunctuator::BitXor(_) => TokenKind::BitXor,
+                    OtherPunctuator::BitXorAssignment(_) => TokenKind::BitXorAssignment,
+                    OtherPunctuator::ClosingBracket(_) => TokenKind::ClosingBracket,
+                    OtherPunctuator::ClosingParenthesis(_) => TokenKind::ClosingParenthesis,
+                    OtherPunctuator::Colon(_) => TokenKind::Colon,
+                    OtherPunctuator::Comma(_) => TokenKind::Comma,
+                    OtherPunctuator::Decrement(_) => TokenKind::Decrement,
+                    OtherPunctuator::Dot(_) => TokenKind::Dot,
+                    OtherPunctuator::Ellipsis(_) => TokenKind::Ellipsis,
+                    OtherPunctuator::Exponentiation(_) => TokenKind::Exponentiation,
+                    OtherPunctuator::ExponentiationAssignment(_) => TokenKind::ExponentiationAssignment,
+                    OtherPunctuator::FunctionArrow(_) => TokenKind::FunctionArrow,
+                    OtherPunctuator::Increment(_) => TokenKind::Increment,
+                    OtherPunctuator::LeftShift(_) => TokenKind::LeftShift,
+                    OtherPunctuator::LeftShiftAssignment(_) => TokenKind::LeftShiftAssignment,
+                    OtherPunctuator::Less(_) => TokenKind::Less,
+                    OtherPunctuator::LessOrEqual(_) => TokenKind::LessOrEqual,
+                    OtherPunctuator::LooseEquality(_) => TokenKind::LooseEquality,
+                    OtherPunctuator::LooseInequality(_) => TokenKind::LooseInequality,
+                    OtherPunctuator::Modulo(_) => TokenKind::Modulo,
+                    OtherPunctuator::ModuloAssignment(_) => TokenKind::ModuloAssignment,
+                    OtherPunctuator::More(_) => TokenKind::More,
+                    OtherPunctuator::MoreOrEqual(_) => TokenKind::MoreOrEqual,
+                    OtherPunctuator::Multiplication(_) => TokenKind::Multiplication,
+                    OtherPunctuator::MultiplicationAssignment(_) => TokenKind::MultiplicationAssignment,
+                    OtherPunctuator::Not(_) => TokenKind::Not,
+                    OtherPunctuator::NullishCoalescence(_) => TokenKind::NullishCoalescence,
+                    OtherPunctuator::NullishCoalescenceAssignment(_) => TokenKind::NullishCoalescenceAssignment,
+                    OtherPunctuator::OpeningBrace(_) => TokenKind::OpeningBrace,
+                    OtherPunctuator::OpeningBracket(_) => TokenKind::OpeningBracket,
+                    OtherPunctuator::OpeningParenthesis(_) => TokenKind::OpeningParenthesis,
+                    OtherPunctuator::Or(_) => TokenKind::Or,
+                    OtherPunctuator::OrAssignment(_) => TokenKind::OrAssignment,
+                    OtherPunctuator::QuestionMark(_) => TokenKind::QuestionMark,
+                    OtherPunctuator::RightShift(_) => TokenKind::RightShift,
+                    OtherPunctuator::RightShiftAssignment(_) => TokenKind::RightShiftAssignment,
+                    OtherPunctuator::Semicolon(_) => TokenKind::Semicolon,
+                    OtherPunctuator::StrictEquality(_) => TokenKind::StrictEquality,
+                    OtherPunctuator::StrictInequality(_) => TokenKind::StrictInequality,
+                    OtherPunctuator::Subtraction(_) => TokenKind::Subtraction,
+                    OtherPunctuator::SubtractionAssignment(_) => TokenKind::SubtractionAssignment,
+                    OtherPunctuator::UnsignedRightShift(_) => TokenKind::UnsignedRightShift,
+                    OtherPunctuator::UnsignedRightShiftAssignment(_) => TokenKind::UnsignedRightShiftAssignment,
+                },
+            },
+            Token::DivPunctuator(DivPunctuator::Division(_)) => TokenKind::Division,
+            Token::DivPunctuator(DivPunctuator::DivisionAssignment(_)) => TokenKind::DivisionAssignment,
+            Token::RightBracePunctuator(_) => TokenKind::RightBracePunctuator,
+            Token::RegularExpressionLiteral(_) => TokenKind::RegularExpressionLiteral,
+            Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateMiddle(_)) => TokenKind::TemplateMiddle,
+            Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateTail(_)) => TokenKind::TemplateTail,
+            Token::ReservedWord(word) => match word {
+                ReservedWord::Await(_) => TokenKind::Await,
+                ReservedWord::Break(_) => TokenKind::Break,
+                ReservedWord::Case(_) => TokenKind::Case,
+                ReservedWord::Catch(_) => TokenKind::Catch,
+                ReservedWord::Class(_) => TokenKind::Class,
+                ReservedWord::Const(_) => TokenKind::Const,
+                ReservedWord::Continue(_) => TokenKind::Continue,
+                ReservedWord::Debugger(_) => TokenKind::Debugger,
+                ReservedWord::Default(_) => TokenKind::Default,
+                ReservedWord::Delete(_) => TokenKind::Delete,
+                ReservedWord::Do(_) => TokenKind::Do,
+                ReservedWord::Else(_) => TokenKind::Else,
+                ReservedWord::Enum(_) => TokenKind::Enum,
+                ReservedWord::Export(_) => TokenKind::Export,
+                ReservedWord::Extends(_) => TokenKind::Extends,
+                ReservedWord::False(_) => TokenKind::False,
+                ReservedWord::Finally(_) => TokenKind::Finally,
+                ReservedWord::For(_) => TokenKind::For,
+                ReservedWord::Function(_) => TokenKind::Function,
+                ReservedWord::If(_) => TokenKind::If,
+                ReservedWord::Import(_) => TokenKind::Import,
+                ReservedWord::In(_) => TokenKind::In,
+                ReservedWord::Instanceof(_) => TokenKind::Instanceof,
+                ReservedWord::New(_) => TokenKind::New,
+                ReservedWord::Null(_) => TokenKind::Null,
+                ReservedWord::Return(_) => TokenKind::Return,
+                ReservedWord::Super(_) => TokenKind::Super,
+                ReservedWord::Switch(_) => TokenKind::Switch,
+                ReservedWord::This(_) => TokenKind::This,
+                ReservedWord::Throw(_) => TokenKind::Throw,
+                ReservedWord::True(_) => TokenKind::True,
+                ReservedWord::Try(_) => TokenKind::Try,
+                ReservedWord::Typeof(_) => TokenKind::Typeof,
+                ReservedWord::Var(_) => TokenKind::Var,
+                ReservedWord::Void(_) => TokenKind::Void,
+                ReservedWord::While(_) => TokenKind::While,
+                ReservedWord::With(_) => TokenKind::With,
+                ReservedWord::Yield(_) => TokenKind::Yield,
+            },
+        }
+    }
+
+    /// See [`TokenKind::is_trivia`].
+    #[must_use]
+    pub const fn is_trivia(&self) -> bool {
+        self.kind().is_trivia()
+    }
+
+    /// See [`TokenKind::is_punctuator`].
+    #[must_use]
+    pub const fn is_punctuator(&self) -> bool {
+        self.kind().is_punctuator()
+    }
+
+    /// See [`TokenKind::is_keyword`].
+    #[must_use]
+    pub const fn is_keyword(&self) -> bool {
+        self.kind().is_keyword()
+    }
+
+    /// See [`TokenKind::punctuator_text`].
+    #[must_use]
+    pub const fn punctuator_text(&self) -> Option<&'static str> {
+        self.kind().punctuator_text()
+    }
+
+    /// See [`TokenKind::keyword_text`].
+    #[must_use]
+    pub const fn keyword_text(&self) -> Option<&'static str> {
+        self.kind().keyword_text()
+    }
+}
+
+/// A token stored as its kind and location only.
+///
+/// Use it for bulk processing of large token streams; the typed token tree
+/// can be recovered on demand with [`CompactToken::typed`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CompactToken {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+impl CompactToken {
+    /// Tokenize the source text covered by the span again and return
+    /// the typed token tree.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`], if `source` is not the text
+    /// the compact token was taken from, including when the span does not
+    /// fit into it or splits a code point.
+    pub fn typed<'src>(&self, source: &'src str) -> Result<Token<'src>, SourceCodeError> {
+        let Span { start, end } = self.span;
+        if start > end || source.get(start..end).is_none() {
+            let message = format!("span {start}..{end} does not fall on code point boundaries of the source");
+            return Err(SourceCodeError::new(source, start..end, ErrorCode::UnexpectedInput, message));
+        }
+        // An unknown code point comes from error recovery, no goal symbol
+        // recognizes it.
+        if self.kind == TokenKind::Unknown {
+            let unknown = get_unknown_token(source, self.span.start)
+                .filter(|(_, range)| range.end == self.span.end);
+            if let Some((token, _)) = unknown {
+                return Ok(token);
+            }
+        }
+        // Each kind is recognized by InputElementDiv except a hashbang
+        // comment allowed at the start of a script only, template
+        // continuations recognized instead of `}` inside substitutions, and
+        // regular expressions recognized instead of `/`.
+        let mode = match self.kind {
+            TokenKind::HashbangComment => GoalSymbols::InputElementHashbangOrRegExp,
+            TokenKind::RegularExpressionLiteral => GoalSymbols::InputElementRegExp,
+            TokenKind::TemplateMiddle | TokenKind::TemplateTail => GoalSymbols::InputElementTemplateTail,
+            _ => GoalSymbols::InputElementDiv,
+        };
+        let (token, tail) = get_next_token_at(source, self.span.start, mode)?;
+        let mut token_end = source.len() - tail.len();
+        // The token may be a run of white space coalesced by LexerOptions.
+        if token == Token::WhiteSpace(WhiteSpace) && token_end < self.span.end {
+            token_end += scanner::white_space_run_length(tail);
+        }
+        if token.kind() == self.kind && token_end == self.span.end {
+            Ok(token)
+        } else {
+            let message = format!("expected {:?}, found {:?}", self.kind, token.kind());
+            Err(SourceCodeError::new(source, self.span.start..self.span.end, ErrorCode::UnexpectedInput, message))
+        }
+    }
+}
+
+/// Extract a token starting at a given byte offset of a `.js`/`.mjs` text
+/// in its compact form.
+///
+/// The span of the returned token is relative to the start of `source`.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned relative to `source`,
+/// if the text at `offset` does not form a correct ECMAScript 2023 token.
+///
+/// # Panics
+///
+/// Will panic if `offset` lies outside of `source` or does not fall on
+/// a UTF-8 character boundary.
+pub fn get_next_compact_token(
+    source: &str,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<CompactToken, SourceCodeError> {
+    let (token, range) = get_next_token_with_range(source, offset, mode)?;
+    Ok(CompactToken { kind: token.kind(), span: range.into() })
+}
+
+/************************************************
+ *
+ * Source text reconstruction
+ *
+ ************************************************/
+
+use std::fmt;
+
+impl fmt::Display for IdentifierName {
+    /// Write the name with `\u` escapes decoded.
+    ///
+    /// A name written with escapes gets its first code point escaped back,
+    /// so `\u0069f` stays an identifier rather than the `if` keyword.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut codepoints = self.decoded.chars();
+        if self.escaped {
+            if let Some(first) = codepoints.next() {
+                write!(formatter, "\\u{{{:X}}}", u32::from(first))?;
+            }
+        }
+        formatter.write_str(codepoints.as_str())
+    }
+}
+
+impl fmt::Display for PrivateIdentifier {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "#{}", self.identifier_name)
+    }
+}
+
+impl fmt::Display for DecimalLiteral {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.integer_part().unwrap_or_default())?;
+        if self.decimal_point {
+            formatter.write_str(".")?;
+        }
+        formatter.write_str(self.fraction().unwrap_or_default())?;
+        self.exponent().map_or(Ok(()), |exponent| write!(formatter, "e{exponent}"))
+    }
+}
+
+impl fmt::Display for NonDecimalIntegerLiteral {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BinaryIntegerLiteral(literal) => write!(formatter, "0b{}", literal.digits()),
+            Self::OctalIntegerLiteral(literal) => write!(formatter, "0o{}", literal.digits()),
+            Self::HexIntegerLiteral(literal) => write!(formatter, "0x{}", literal.digits()),
+        }
+    }
+}
+
+impl fmt::Display for NumericLiteral {
+    /// Write the literal with prefixes and exponent indicators in lowercase;
+    /// digits and numeric separators are kept as is.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DecimalLiteral(literal) => write!(formatter, "{literal}"),
+            Self::DecimalBigIntegerLiteral(literal) => write!(formatter, "{}n", literal.digits()),
+            Self::NonDecimalIntegerLiteral(literal) => write!(formatter, "{literal}"),
+            Self::NonDecimalBigIntegerLiteral(literal) => write!(formatter, "{}n", literal.integer()),
+            Self::LegacyOctalIntegerLiteral(literal) => formatter.write_str(&literal.text),
+        }
+    }
+}
+
+impl fmt::Display for StringLiteral {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.text)
+    }
+}
+
+impl fmt::Display for RegularExpressionLiteral {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "/{}/{}", self.body_text(), self.flag_text())
+    }
+}
+
+impl fmt::Display for NoSubstitutionTemplate {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "`{}`", self.characters)
+    }
+}
+
+impl fmt::Display for TemplateHead {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "`{}${{", self.characters)
+    }
+}
+
+impl fmt::Display for TemplateMiddle {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "}}{}${{", self.characters)
+    }
+}
+
+impl fmt::Display for TemplateTail {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "}}{}`", self.characters)
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSubstitutionTemplate(template) => write!(formatter, "{template}"),
+            Self::TemplateHead(template) => write!(formatter, "{template}"),
+        }
+    }
+}
+
+impl fmt::Display for TemplateSubstitutionTail {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TemplateMiddle(template) => write!(formatter, "{template}"),
+            Self::TemplateTail(template) => write!(formatter, "{template}"),
+        }
+    }
+}
+
+impl fmt::Display for MultiLineComment<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "/*{}*/", self.content)
+    }
+}
+
+impl fmt::Display for SingleLineComment<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "//{}", self.content)
+    }
+}
+
+impl fmt::Display for Comment<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MultiLineComment(comment) => write!(formatter, "{comment}"),
+            Self::SingleLineComment(comment) => write!(formatter, "{comment}"),
+        }
+    }
+}
+
+impl fmt::Display for HashbangComment<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.content)
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    /// Write source text that tokenizes back into an equal token under the
+    /// same goal symbol.
+    ///
+    /// The typed tree does not keep everything the original text had, so
+    /// the result may differ from it:
+    ///
+    /// - any `WhiteSpace` is written as a space and any `LineTerminator` as
+    ///   `<LF>`;
+    /// - `\u` escapes of identifier names are decoded except the first one;
+    /// - numeric literal prefixes and exponent indicators are lowercased.
+    ///
+    /// Tokens are written without anything around them, so a transformation
+    /// that drops white space or comments must keep neighbours like `a` and
+    /// `in` or `+` and `+` apart itself.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Comment(comment) => write!(formatter, "{comment}"),
+            Self::CommonToken(CommonToken::IdentifierName(name)) => write!(formatter, "{name}"),
+            Self::CommonToken(CommonToken::PrivateIdentifier(name)) => write!(formatter, "{name}"),
+            Self::CommonToken(CommonToken::NumericLiteral(literal)) => write!(formatter, "{literal}"),
+            Self::CommonToken(CommonToken::StringLiteral(literal)) => write!(formatter, "{literal}"),
+            Self::CommonToken(CommonToken::Template(template)) => write!(formatter, "{template}"),
+            Self::HashbangComment(comment) => write!(formatter, "{comment}"),
+            Self::LineTerminator(_) => formatter.write_str("\n"),
+            Self::RegularExpressionLiteral(literal) => write!(formatter, "{literal}"),
+            Self::TemplateSubstitutionTail(template) => write!(formatter, "{template}"),
+            Self::Unknown(codepoint) => write!(formatter, "{codepoint}"),
+            Self::WhiteSpace(_) => formatter.write_str(" "),
+            Self::CommonToken(CommonToken::Punctuator(_))
+            | Self::DivPunctuator(_)
+            | Self::ReservedWord(_)
+            | Self::RightBracePunctuator(_) => {
+                let text = self.punctuator_text().or_else(|| self.keyword_text());
+                formatter.write_str(text.unwrap_or_default())
+            },
+        }
+    }
+}
+
+/************************************************
+ *
+ * Positioned tokens
+ *
+ ************************************************/
+
+/// A value annotated with a location inside a source text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Positioned<T> {
+    pub value: T,
+    /// Byte offsets of the value in the source text.
+    pub span: Span,
+    /// 1-based line of the span start.
+    pub line: usize,
+    /// 1-based column of the span start, counted in code points.
+    pub column: usize,
+}
+
+/// Extract a token starting at a given byte offset of a `.js`/`.mjs` text
+/// together with its byte range, line and column.
+///
+/// Positions are taken from `lines` built once for the whole source text, so
+/// scanning a text token by token does not recount line breaks each time.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] positioned relative to the text
+/// of `lines`, if the text at `offset` does not form a correct
+/// ECMAScript 2023 token.
+///
+/// # Panics
+///
+/// Will panic if `offset` lies outside of the source text or does not fall on
+/// a UTF-8 character boundary.
+pub fn get_next_positioned_token<'src>(
+    lines: &LineIndex<'src>,
+    offset: usize,
+    mode: GoalSymbols
+) -> Result<Positioned<Token<'src>>, SourceCodeError> {
+    let (value, range) = get_next_token_with_range(lines.source(), offset, mode)?;
+    let (line, column) = lines.line_column(range.start);
+    Ok(Positioned { value, span: range.into(), line, column })
+}
+
+/************************************************
+ *
+ * Lexer
+ *
+ ************************************************/
+
+/// A stateful tokenizer selecting a goal symbol for each next token itself.
+///
+/// The goal symbol is picked from the previously recognized token with
+/// [`GoalSymbols::infer`], tracking braces of template substitutions so
+/// a `}` closing a nested block is not taken for a template continuation.
+/// This is the heuristic real engines use instead of the syntactic grammar
+/// context the specification relies on.
+///
+/// Tokens recognized elsewhere, like with an explicit goal symbol, can be
+/// passed to [`Tokenizer::observe`] to keep the state in sync.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tokenizer {
+    mode: GoalSymbols,
+    /// A number of unclosed `{` in each enclosing template substitution.
+    substitution_braces: Vec<usize>,
+}
+
+impl std::default::Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
     }
 }
+
+impl Tokenizer {
+    /// Start at the beginning of a *Script* or *Module*.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_goal(GoalSymbols::InputElementHashbangOrRegExp)
+    }
+
+    /// Start with a given goal symbol, like for a fragment of a text.
+    #[must_use]
+    pub const fn with_goal(goal: GoalSymbols) -> Self {
+        Self { mode: goal, substitution_braces: Vec::new() }
+    }
+
+    /// A goal symbol for a next token.
+    #[must_use]
+    pub const fn goal(&self) -> GoalSymbols {
+        self.mode
+    }
+
+    /// Extract a token starting at a given byte offset the way
+    /// [`get_next_token_with_range`] does, with the goal symbol selected
+    /// automatically.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with [`SourceCodeError`] positioned relative to
+    /// `source`, if the text at `offset` does not form a correct
+    /// ECMAScript 2023 token. The state is left unchanged then.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `offset` lies outside of `source` or does not fall on
+    /// a UTF-8 character boundary.
+    pub fn next_token<'src>(
+        &mut self,
+        source: &'src str,
+        offset: usize
+    ) -> Result<(Token<'src>, Range<usize>), SourceCodeError> {
+        let (token, range) = get_next_token_with_range(source, offset, self.mode)?;
+        self.observe(&token);
+        Ok((token, range))
+    }
+
+    /// Update the state after recognizing a token.
+    pub fn observe(&mut self, token: &Token<'_>) {
+        match token {
+            Token::WhiteSpace(_) | Token::LineTerminator(_) | Token::Comment(_) | Token::HashbangComment(_) => {
+                // A hashbang is allowed at the very start only.
+                if self.mode == GoalSymbols::InputElementHashbangOrRegExp {
+                    self.mode = GoalSymbols::InputElementRegExp;
+                }
+                return;
+            },
+            Token::CommonToken(CommonToken::Template(Template::TemplateHead(_))) => {
+                self.substitution_braces.push(0);
+            },
+            Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateTail(_)) => {
+                self.substitution_braces.pop();
+            },
+            Token::CommonToken(CommonToken::Punctuator(Punctuator::OtherPunctuator(OtherPunctuator::OpeningBrace(_)))) => {
+                if let Some(braces) = self.substitution_braces.last_mut() {
+                    *braces += 1;
+                }
+            },
+            Token::RightBracePunctuator(_) => {
+                if let Some(braces) = self.substitution_braces.last_mut() {
+                    *braces = braces.saturating_sub(1);
+                }
+            },
+            _ => {},
+        }
+        let in_template_substitution = self.substitution_braces.last() == Some(&0);
+        self.mode = GoalSymbols::infer(Some(token), in_template_substitution);
+    }
+}
+
+/// An iterator over tokens of a `.js`/`.mjs` text.
+///
+/// The lexer selects a goal symbol for each next token itself the way
+/// [`Tokenizer`] does.
+///
+/// After an error the iterator stops.
+#[derive(Debug)]
+pub struct Lexer<'src> {
+    source: &'src str,
+    offset: usize,
+    tokenizer: Tokenizer,
+    options: LexerOptions,
+    failed: bool,
+}
+
+impl<'src> Lexer<'src> {
+    /// Tokenize a *Script* or *Module* with [`LexerOptions::default`].
+    #[must_use]
+    pub fn new(source: &'src str) -> Self {
+        Self::with_options(source, LexerOptions::default())
+    }
+
+    #[must_use]
+    pub const fn with_options(source: &'src str, options: LexerOptions) -> Self {
+        Self { source, offset: 0, tokenizer: Tokenizer::new(), options, failed: false }
+    }
+
+    /// Byte offset of a next token.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'src> Iterator for Lexer<'src> {
+    type Item = Result<(Token<'src>, Span), SourceCodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.source.len() {
+            return None;
+        }
+        match get_next_token_with_options(self.source, self.offset, self.tokenizer.goal(), &self.options) {
+            Ok((token, range)) => {
+                self.tokenizer.observe(&token);
+                self.offset = range.end;
+                Some(Ok((token, range.into())))
+            },
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            },
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Lexer<'_> {}
+
+/// Split a whole `.js`/`.mjs` text into tokens, starting with a given goal
+/// symbol and selecting the next ones the way [`Tokenizer`] does.
+///
+/// Pass [`GoalSymbols::InputElementHashbangOrRegExp`] for a whole *Script*
+/// or *Module*. White space is coalesced as with [`LexerOptions::default`].
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] for the first place that does
+/// not form a correct ECMAScript 2023 token.
+pub fn tokenize(source: &str, goal: GoalSymbols) -> Result<Vec<(Token<'_>, Span)>, SourceCodeError> {
+    let mut lexer = Lexer::new(source);
+    lexer.tokenizer = Tokenizer::with_goal(goal);
+    lexer.collect()
+}
+
+/// Split a whole `.js`/`.mjs` text into tokens like [`tokenize`] does,
+/// reporting each malformed token into `diagnostics` and continuing.
+///
+/// A code point a malformed token starts with is returned as
+/// [`Token::Unknown`], as with [`LexerOptions::recover_errors`], and
+/// tokenization resumes right after it.
+///
+/// Correct but legacy constructs like legacy octal literals and escape
+/// sequences are reported too, as [`Severity::Warning`].
+pub fn tokenize_with_diagnostics<'src>(
+    source: &'src str,
+    goal: GoalSymbols,
+    diagnostics: &mut Diagnostics
+) -> Vec<(Token<'src>, Span)> {
+    let options = LexerOptions::default();
+    let mut tokenizer = Tokenizer::with_goal(goal);
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while offset < source.len() {
+        let (token, range) = match get_next_token_with_options(source, offset, tokenizer.goal(), &options) {
+            Ok((token, range)) => {
+                if let Some(warning) = check_legacy_token(&token, source, range.clone()) {
+                    diagnostics.report(warning);
+                }
+                (token, range)
+            },
+            Err(error) => {
+                diagnostics.report(error);
+                let Some(unknown) = get_unknown_token(source, offset) else {
+                    break;
+                };
+                unknown
+            },
+        };
+        tokenizer.observe(&token);
+        offset = range.end;
+        tokens.push((token, range.into()));
+    }
+    tokens
+}
+
+/// A token together with the exact source text it was recognized from.
+#[derive(Debug, Eq, PartialEq)]
+pub struct LosslessToken<'src> {
+    pub token: Token<'src>,
+    pub span: Span,
+    pub text: &'src str,
+}
+
+/// Split a whole `.js`/`.mjs` text into tokens keeping white space, line
+/// terminators and comments along with their source text.
+///
+/// Concatenation of texts of the returned tokens is the input itself, so
+/// formatters and minifiers can reproduce any part of it byte for byte.
+///
+/// # Errors
+///
+/// Will return `Err` with [`SourceCodeError`] for the first place that does
+/// not form a correct ECMAScript 2023 token.
+pub fn tokenize_lossless(source: &str) -> Result<Vec<LosslessToken<'_>>, SourceCodeError> {
+    Lexer::new(source)
+        .map(|token| {
+            let (token, span) = token?;
+            Ok(LosslessToken { token, span, text: span.source_text(source) })
+        })
+        .collect()
+}
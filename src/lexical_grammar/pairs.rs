@@ -0,0 +1,287 @@
+//! Repacking of a `pest` parse tree of a goal symbol into a typed token.
+//!
+//! It walks the pairs rule by rule and so depends only on the grammar, which
+//! keeps [`super::get_next_token_with_grammar`] an independent reference for
+//! the hand-written scanner.
+
+use super::{
+    contains_escape,
+    decode_identifier_name,
+    strip_multi_line_comment_delimiters,
+    strip_single_line_comment_delimiter,
+    template_characters,
+    BinaryDigits,
+    BinaryIntegerLiteral,
+    Comment,
+    CommonToken,
+    DecimalBigIntegerLiteral,
+    DecimalDigits,
+    DecimalIntegerLiteral,
+    DecimalLiteral,
+    DivPunctuator,
+    ExponentPart,
+    HashbangComment,
+    HexDigits,
+    HexIntegerLiteral,
+    IdentifierName,
+    LegacyOctalIntegerLiteral,
+    LineTerminator,
+    MultiLineComment,
+    NoSubstitutionTemplate,
+    NonDecimalBigIntegerLiteral,
+    NonDecimalIntegerLiteral,
+    NumericLiteral,
+    OctalDigits,
+    OctalIntegerLiteral,
+    OptionalChainingPunctuator,
+    OtherPunctuator,
+    PrivateIdentifier,
+    Punctuator,
+    RegularExpressionBody,
+    RegularExpressionFlags,
+    RegularExpressionLiteral,
+    ReservedWord,
+    RightBracePunctuator,
+    Rule,
+    SignedInteger,
+    SingleLineComment,
+    StringLiteral,
+    Template,
+    TemplateHead,
+    TemplateMiddle,
+    TemplateSubstitutionTail,
+    TemplateTail,
+    Token,
+    WhiteSpace,
+};
+use pest::iterators::Pair;
+
+/// Map a pair of a rule matching a fixed text to a variant of the same name.
+macro_rules! unit_variant {
+    ($pair:expr, $enum:ident, [$($name:ident),* $(,)?]) => {
+        match $pair.as_rule() {
+            $(Rule::$name => $enum::$name(super::$name),)*
+            _ => return None,
+        }
+    };
+}
+
+/// Convert a parse tree of an `InputElement*` goal symbol.
+///
+/// Returns `None` if the tree does not have a shape the grammar gives it.
+pub(super) fn token(goal: Pair<'_, Rule>) -> Option<Token<'_>> {
+    let element = goal.into_inner().next()?;
+    let token = match element.as_rule() {
+        Rule::WhiteSpace => Token::WhiteSpace(WhiteSpace),
+        Rule::LineTerminator => Token::LineTerminator(LineTerminator),
+        Rule::Comment => Token::Comment(comment(first_inner(element)?)?),
+        Rule::HashbangComment => Token::HashbangComment(HashbangComment { content: element.as_str() }),
+        Rule::ReservedWord => Token::ReservedWord(reserved_word(first_inner(element)?)?),
+        Rule::CommonToken => Token::CommonToken(common_token(first_inner(element)?)?),
+        Rule::DivPunctuator => {
+            Token::DivPunctuator(unit_variant!(first_inner(element)?, DivPunctuator, [DivisionAssignment, Division]))
+        },
+        Rule::RightBracePunctuator => Token::RightBracePunctuator(RightBracePunctuator),
+        Rule::RegularExpressionLiteral => Token::RegularExpressionLiteral(regular_expression_literal(element)?),
+        Rule::TemplateSubstitutionTail => {
+            Token::TemplateSubstitutionTail(template_substitution_tail(first_inner(element)?)?)
+        },
+        _ => return None,
+    };
+    Some(token)
+}
+
+fn first_inner(pair: Pair<'_, Rule>) -> Option<Pair<'_, Rule>> {
+    pair.into_inner().next()
+}
+
+fn comment(pair: Pair<'_, Rule>) -> Option<Comment<'_>> {
+    let text = pair.as_str();
+    match pair.as_rule() {
+        Rule::MultiLineComment => Some(Comment::MultiLineComment(MultiLineComment {
+            content: strip_multi_line_comment_delimiters(text)
+        })),
+        Rule::SingleLineComment => Some(Comment::SingleLineComment(SingleLineComment {
+            content: strip_single_line_comment_delimiter(text)
+        })),
+        _ => None,
+    }
+}
+
+/************************************************
+ *
+ * 12.7 Names and Keywords
+ *
+ ************************************************/
+
+fn identifier_name(pair: &Pair<'_, Rule>) -> IdentifierName {
+    let text = pair.as_str();
+    IdentifierName { decoded: decode_identifier_name(text), escaped: contains_escape(text) }
+}
+
+fn reserved_word(pair: Pair<'_, Rule>) -> Option<ReservedWord> {
+    Some(unit_variant!(pair, ReservedWord, [
+        Await, Break, Case, Catch, Class, Const, Continue, Debugger, Default,
+        Delete, Do, Else, Enum, Export, Extends, False, Finally, For, Function,
+        If, Import, In, Instanceof, New, Null, Return, Super, Switch, This,
+        Throw, True, Try, Typeof, Var, Void, While, With, Yield,
+    ]))
+}
+
+/************************************************
+ *
+ * 12.6 Tokens
+ *
+ ************************************************/
+
+fn common_token(pair: Pair<'_, Rule>) -> Option<CommonToken> {
+    let token = match pair.as_rule() {
+        Rule::IdentifierName => CommonToken::IdentifierName(identifier_name(&pair)),
+        Rule::PrivateIdentifier => CommonToken::PrivateIdentifier(PrivateIdentifier {
+            identifier_name: identifier_name(&first_inner(pair)?)
+        }),
+        Rule::NumericLiteral => CommonToken::NumericLiteral(numeric_literal(first_inner(pair)?)?),
+        Rule::StringLiteral => CommonToken::StringLiteral(StringLiteral { text: pair.as_str().to_owned() }),
+        Rule::Template => CommonToken::Template(template(first_inner(pair)?)?),
+        Rule::Punctuator => CommonToken::Punctuator(punctuator(first_inner(pair)?)?),
+        _ => return None,
+    };
+    Some(token)
+}
+
+/************************************************
+ *
+ * 12.8 Punctuators
+ *
+ ************************************************/
+
+fn punctuator(pair: Pair<'_, Rule>) -> Option<Punctuator> {
+    match pair.as_rule() {
+        Rule::OptionalChainingPunctuator => {
+            Some(Punctuator::OptionalChainingPunctuator(OptionalChainingPunctuator))
+        },
+        Rule::OtherPunctuator => {
+            Some(Punctuator::OtherPunctuator(unit_variant!(first_inner(pair)?, OtherPunctuator, [
+                OpeningBrace, OpeningParenthesis, ClosingParenthesis,
+                OpeningBracket, ClosingBracket, Ellipsis, Dot, Semicolon, Comma,
+                StrictEquality, FunctionArrow, LooseEquality, Assignment,
+                StrictInequality, LooseInequality, Not, Increment,
+                AdditionAssignment, Addition, Decrement, SubtractionAssignment,
+                Subtraction, ModuloAssignment, Modulo, ExponentiationAssignment,
+                Exponentiation, MultiplicationAssignment, Multiplication,
+                UnsignedRightShiftAssignment, UnsignedRightShift,
+                RightShiftAssignment, RightShift, MoreOrEqual, More,
+                LeftShiftAssignment, LeftShift, LessOrEqual, Less,
+                AndAssignment, And, BitAndAssignment, BitAnd, OrAssignment, Or,
+                BitOrAssignment, BitOr, BitXorAssignment, BitXor, BitNot,
+                NullishCoalescenceAssignment, NullishCoalescence, QuestionMark,
+                Colon,
+            ])))
+        },
+        _ => None,
+    }
+}
+
+/************************************************
+ *
+ * 12.9.3 Numeric Literals
+ *
+ ************************************************/
+
+fn numeric_literal(pair: Pair<'_, Rule>) -> Option<NumericLiteral> {
+    let literal = match pair.as_rule() {
+        Rule::NonDecimalBigIntegerLiteral => NumericLiteral::NonDecimalBigIntegerLiteral(NonDecimalBigIntegerLiteral {
+            integer: non_decimal_integer_literal(first_inner(pair)?)?
+        }),
+        Rule::NonDecimalIntegerLiteral => {
+            NumericLiteral::NonDecimalIntegerLiteral(non_decimal_integer_literal(pair)?)
+        },
+        Rule::DecimalBigIntegerLiteral => NumericLiteral::DecimalBigIntegerLiteral(DecimalBigIntegerLiteral {
+            integer_part: DecimalIntegerLiteral { digits: first_inner(pair)?.as_str().to_owned() }
+        }),
+        Rule::LegacyOctalIntegerLiteral => NumericLiteral::LegacyOctalIntegerLiteral(LegacyOctalIntegerLiteral {
+            text: pair.as_str().to_owned()
+        }),
+        Rule::DecimalLiteral => NumericLiteral::DecimalLiteral(decimal_literal(pair)?),
+        _ => return None,
+    };
+    Some(literal)
+}
+
+fn decimal_literal(pair: Pair<'_, Rule>) -> Option<DecimalLiteral> {
+    let decimal_point = pair.as_str().contains('.');
+    let mut literal = DecimalLiteral { integer_part: None, fraction: None, exponent: None, decimal_point };
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::DecimalIntegerLiteral => {
+                literal.integer_part = Some(DecimalIntegerLiteral { digits: part.as_str().to_owned() });
+            },
+            Rule::DecimalDigits => literal.fraction = Some(DecimalDigits { digits: part.as_str().to_owned() }),
+            Rule::ExponentPart => {
+                let text = first_inner(part)?.as_str().to_owned();
+                literal.exponent = Some(ExponentPart { signed_integer: SignedInteger { text } });
+            },
+            _ => return None,
+        }
+    }
+    Some(literal)
+}
+
+fn non_decimal_integer_literal(pair: Pair<'_, Rule>) -> Option<NonDecimalIntegerLiteral> {
+    let literal = first_inner(pair)?;
+    let rule = literal.as_rule();
+    let digits = first_inner(literal)?.as_str().to_owned();
+    let literal = match rule {
+        Rule::BinaryIntegerLiteral => NonDecimalIntegerLiteral::BinaryIntegerLiteral(BinaryIntegerLiteral {
+            digits: BinaryDigits { digits }
+        }),
+        Rule::OctalIntegerLiteral => NonDecimalIntegerLiteral::OctalIntegerLiteral(OctalIntegerLiteral {
+            digits: OctalDigits { digits }
+        }),
+        Rule::HexIntegerLiteral => NonDecimalIntegerLiteral::HexIntegerLiteral(HexIntegerLiteral {
+            digits: HexDigits { digits }
+        }),
+        _ => return None,
+    };
+    Some(literal)
+}
+
+/************************************************
+ *
+ * 12.9.5 Regular Expression Literals
+ *
+ ************************************************/
+
+fn regular_expression_literal(pair: Pair<'_, Rule>) -> Option<RegularExpressionLiteral> {
+    let mut parts = pair.into_inner();
+    let body = parts.next().filter(|part| part.as_rule() == Rule::RegularExpressionBody)?;
+    let flags = parts.next().filter(|part| part.as_rule() == Rule::RegularExpressionFlags)?;
+    Some(RegularExpressionLiteral {
+        body: RegularExpressionBody { text: body.as_str().to_owned() },
+        flags: RegularExpressionFlags { text: flags.as_str().to_owned() },
+    })
+}
+
+/************************************************
+ *
+ * 12.9.6 Template Literal Lexical Components
+ *
+ ************************************************/
+
+fn template(pair: Pair<'_, Rule>) -> Option<Template> {
+    let characters = template_characters(pair.as_str());
+    match pair.as_rule() {
+        Rule::NoSubstitutionTemplate => Some(Template::NoSubstitutionTemplate(NoSubstitutionTemplate { characters })),
+        Rule::TemplateHead => Some(Template::TemplateHead(TemplateHead { characters })),
+        _ => None,
+    }
+}
+
+fn template_substitution_tail(pair: Pair<'_, Rule>) -> Option<TemplateSubstitutionTail> {
+    let characters = template_characters(pair.as_str());
+    match pair.as_rule() {
+        Rule::TemplateMiddle => Some(TemplateSubstitutionTail::TemplateMiddle(TemplateMiddle { characters })),
+        Rule::TemplateTail => Some(TemplateSubstitutionTail::TemplateTail(TemplateTail { characters })),
+        _ => None,
+    }
+}
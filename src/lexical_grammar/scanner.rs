@@ -0,0 +1,913 @@
+//! A hand-written recognizer of the lexical grammar.
+//!
+//! It follows `lexical_grammar.pest` rule by rule, including the ordered
+//! choice and the non-backtracking repetition of PEG, and builds the typed
+//! token tree directly instead of going through `pest` pairs.
+//! Input that does not start any token is left for the grammar to report, so
+//! error messages stay the same.
+
+use super::{
+    contains_escape,
+    decode_identifier_name,
+    template_characters,
+    Addition,
+    AdditionAssignment,
+    And,
+    AndAssignment,
+    Assignment,
+    Await,
+    BinaryDigits,
+    BinaryIntegerLiteral,
+    BitAnd,
+    BitAndAssignment,
+    BitNot,
+    BitOr,
+    BitOrAssignment,
+    BitXor,
+    BitXorAssignment,
+    Break,
+    Case,
+    Catch,
+    Class,
+    ClosingBracket,
+    ClosingParenthesis,
+    Colon,
+    Comma,
+    Comment,
+    CommonToken,
+    Const,
+    Continue,
+    Debugger,
+    DecimalBigIntegerLiteral,
+    DecimalDigits,
+    DecimalIntegerLiteral,
+    DecimalLiteral,
+    Decrement,
+    Delete,
+    DivPunctuator,
+    Division,
+    DivisionAssignment,
+    Do,
+    Dot,
+    Ellipsis,
+    Else,
+    Enum,
+    Exponentiation,
+    ExponentiationAssignment,
+    ExponentPart,
+    Export,
+    Extends,
+    False,
+    Finally,
+    For,
+    Function,
+    FunctionArrow,
+    GoalSymbols,
+    HashbangComment,
+    HexDigits,
+    HexIntegerLiteral,
+    IdentifierName,
+    If,
+    Import,
+    In,
+    Increment,
+    Instanceof,
+    LeftShift,
+    LeftShiftAssignment,
+    LegacyOctalIntegerLiteral,
+    Less,
+    LessOrEqual,
+    LineTerminator,
+    LooseEquality,
+    LooseInequality,
+    Modulo,
+    ModuloAssignment,
+    More,
+    MoreOrEqual,
+    MultiLineComment,
+    Multiplication,
+    MultiplicationAssignment,
+    New,
+    NoSubstitutionTemplate,
+    NonDecimalBigIntegerLiteral,
+    NonDecimalIntegerLiteral,
+    Not,
+    Null,
+    NullishCoalescence,
+    NullishCoalescenceAssignment,
+    NumericLiteral,
+    OctalDigits,
+    OctalIntegerLiteral,
+    OpeningBrace,
+    OpeningBracket,
+    OpeningParenthesis,
+    OptionalChainingPunctuator,
+    Or,
+    OrAssignment,
+    OtherPunctuator,
+    PrivateIdentifier,
+    Punctuator,
+    QuestionMark,
+    RegularExpressionBody,
+    RegularExpressionFlags,
+    RegularExpressionLiteral,
+    ReservedWord,
+    Return,
+    RightBracePunctuator,
+    RightShift,
+    RightShiftAssignment,
+    Semicolon,
+    SignedInteger,
+    SingleLineComment,
+    StrictEquality,
+    StrictInequality,
+    StringLiteral,
+    Subtraction,
+    SubtractionAssignment,
+    Super,
+    Switch,
+    Template,
+    TemplateHead,
+    TemplateMiddle,
+    TemplateSubstitutionTail,
+    TemplateTail,
+    This,
+    Throw,
+    Token,
+    True,
+    Try,
+    Typeof,
+    UnsignedRightShift,
+    UnsignedRightShiftAssignment,
+    Var,
+    Void,
+    While,
+    WhiteSpace,
+    With,
+    Yield,
+};
+use super::unicode::{ID_CONTINUE, ID_START, SPACE_SEPARATOR};
+
+/// Recognize a token at the start of `input` the way a goal symbol of the
+/// grammar does and return it with its byte length.
+pub(super) fn scan(input: &str, mode: GoalSymbols) -> Option<(Token<'_>, usize)> {
+    // Most of a typical text is ASCII indentation and line breaks, so leave
+    // Unicode classification for the rest.
+    match byte_at(input, 0)? {
+        byte if is_ascii_white_space(byte) => return Some((Token::WhiteSpace(WhiteSpace), 1)),
+        b'\n' | b'\r' => return Some((Token::LineTerminator(LineTerminator), 1)),
+        byte if byte.is_ascii() => {},
+        _ => {
+            let first = input.chars().next()?;
+            if is_white_space(first) {
+                return Some((Token::WhiteSpace(WhiteSpace), first.len_utf8()));
+            }
+            if is_line_terminator(first) {
+                return Some((Token::LineTerminator(LineTerminator), first.len_utf8()));
+            }
+        },
+    }
+    if let Some((comment, length)) = scan_comment(input) {
+        return Some((Token::Comment(comment), length));
+    }
+    let allows_regular_expression = matches!(
+        mode,
+        GoalSymbols::InputElementHashbangOrRegExp
+            | GoalSymbols::InputElementRegExpOrTemplateTail
+            | GoalSymbols::InputElementRegExp
+    );
+    if allows_regular_expression {
+        if let Some((literal, length)) = scan_regular_expression_literal(input) {
+            return Some((Token::RegularExpressionLiteral(literal), length));
+        }
+    }
+    if let Some((word, length)) = scan_reserved_word(input) {
+        return Some((Token::ReservedWord(word), length));
+    }
+    if let Some((token, length)) = scan_common_token(input) {
+        return Some((Token::CommonToken(token), length));
+    }
+    match mode {
+        GoalSymbols::InputElementDiv => scan_div_punctuator(input).or_else(|| scan_right_brace_punctuator(input)),
+        GoalSymbols::InputElementRegExp => scan_right_brace_punctuator(input),
+        GoalSymbols::InputElementRegExpOrTemplateTail => scan_template_substitution_tail(input),
+        GoalSymbols::InputElementTemplateTail => {
+            scan_div_punctuator(input).or_else(|| scan_template_substitution_tail(input))
+        },
+        GoalSymbols::InputElementHashbangOrRegExp => scan_hashbang_comment(input),
+    }
+}
+
+/// Get a byte length of white space code points at the input start.
+pub(super) fn white_space_run_length(input: &str) -> usize {
+    let mut end = 0;
+    loop {
+        end += count_bytes(input, end, is_ascii_white_space);
+        match char_at(input, end) {
+            Some(codepoint) if !codepoint.is_ascii() && is_white_space(codepoint) => end += codepoint.len_utf8(),
+            _ => return end,
+        }
+    }
+}
+
+fn byte_at(input: &str, position: usize) -> Option<u8> {
+    input.as_bytes().get(position).copied()
+}
+
+fn char_at(input: &str, position: usize) -> Option<char> {
+    input.get(position..)?.chars().next()
+}
+
+fn is_byte_at(input: &str, position: usize, predicate: fn(u8) -> bool) -> bool {
+    byte_at(input, position).is_some_and(predicate)
+}
+
+fn count_bytes(input: &str, position: usize, predicate: fn(u8) -> bool) -> usize {
+    input.as_bytes()
+        .get(position..)
+        .map_or(0, |tail| tail.iter().take_while(|byte| predicate(**byte)).count())
+}
+
+const fn is_decimal_digit(byte: u8) -> bool {
+    byte.is_ascii_digit()
+}
+
+const fn is_hex_digit(byte: u8) -> bool {
+    byte.is_ascii_hexdigit()
+}
+
+const fn is_octal_digit(byte: u8) -> bool {
+    matches!(byte, b'0'..=b'7')
+}
+
+const fn is_binary_digit(byte: u8) -> bool {
+    matches!(byte, b'0' | b'1')
+}
+
+/// ASCII part of `WhiteSpace`.
+const fn is_ascii_white_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | 0x0B | 0x0C)
+}
+
+/// `WhiteSpace` of 12.2 White Space.
+fn is_white_space(codepoint: char) -> bool {
+    matches!(codepoint, '\u{9}' | '\u{B}' | '\u{C}' | '\u{FEFF}') || SPACE_SEPARATOR.contains(codepoint)
+}
+
+/// `LineTerminator` of 12.3 Line Terminators.
+const fn is_line_terminator(codepoint: char) -> bool {
+    matches!(codepoint, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Skip a `LineTerminatorSequence` known to start at `position`.
+fn skip_line_terminator_sequence(input: &str, position: usize) -> usize {
+    if input[position..].starts_with("\r\n") {
+        position + 2
+    } else {
+        position + char_at(input, position).map_or(1, char::len_utf8)
+    }
+}
+
+/// `SingleLineCommentChars`: everything up to a line terminator.
+pub(super) fn line_length(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    let mut position = 0;
+    // <LS> and <PS> are encoded as E2 80 A8 and E2 80 A9.
+    while let Some(found) = memchr::memchr3(b'\n', b'\r', 0xE2, &bytes[position..]) {
+        let candidate = position + found;
+        if bytes[candidate] != 0xE2 || matches!(bytes.get(candidate + 1..candidate + 3), Some([0x80, 0xA8 | 0xA9])) {
+            return candidate;
+        }
+        position = candidate + 1;
+    }
+    input.len()
+}
+
+fn scan_comment(input: &str) -> Option<(Comment<'_>, usize)> {
+    if let Some(body) = input.strip_prefix("/*") {
+        let length = 2 + memchr::memmem::find(body.as_bytes(), b"*/")? + 2;
+        let content = &input[2..length - 2];
+        return Some((Comment::MultiLineComment(MultiLineComment { content }), length));
+    }
+    let body = input.strip_prefix("//")?;
+    let length = 2 + line_length(body);
+    Some((Comment::SingleLineComment(SingleLineComment { content: &input[2..length] }), length))
+}
+
+fn scan_hashbang_comment(input: &str) -> Option<(Token<'_>, usize)> {
+    let body = input.strip_prefix("#!")?;
+    let length = 2 + line_length(body);
+    Some((Token::HashbangComment(HashbangComment { content: &input[..length] }), length))
+}
+
+/************************************************
+ *
+ * 12.7 Names and Keywords
+ *
+ ************************************************/
+
+fn is_identifier_start_char(codepoint: char) -> bool {
+    if codepoint.is_ascii() {
+        codepoint.is_ascii_alphabetic() || codepoint == '$' || codepoint == '_'
+    } else {
+        ID_START.contains(codepoint)
+    }
+}
+
+fn is_identifier_part_char(codepoint: char) -> bool {
+    if codepoint.is_ascii() {
+        codepoint.is_ascii_alphanumeric() || codepoint == '$' || codepoint == '_'
+    } else {
+        ID_CONTINUE.contains(codepoint) || codepoint == '\u{200C}' || codepoint == '\u{200D}'
+    }
+}
+
+/// `IdentifierStart` or `IdentifierPart` depending on `is_allowed`.
+fn scan_identifier_code_point(input: &str, position: usize, is_allowed: fn(char) -> bool) -> Option<usize> {
+    let codepoint = char_at(input, position)?;
+    if is_allowed(codepoint) {
+        Some(position + codepoint.len_utf8())
+    } else if codepoint == '\\' {
+        scan_unicode_escape_sequence(input, position + 1)
+    } else {
+        None
+    }
+}
+
+fn scan_identifier_name(input: &str, position: usize) -> Option<usize> {
+    let mut end = scan_identifier_code_point(input, position, is_identifier_start_char)?;
+    loop {
+        end += count_bytes(input, end, |byte| byte.is_ascii_alphanumeric() || byte == b'$' || byte == b'_');
+        match scan_identifier_code_point(input, end, is_identifier_part_char) {
+            Some(next) => end = next,
+            None => return Some(end),
+        }
+    }
+}
+
+fn identifier_name(text: &str) -> IdentifierName {
+    IdentifierName { decoded: decode_identifier_name(text), escaped: contains_escape(text) }
+}
+
+/// `ReservedWord ~ !IdentifierPart`.
+///
+/// A reserved word followed by a lowercase letter is a prefix of a longer
+/// name, so it is enough to look at a whole run of lowercase letters.
+fn scan_reserved_word(input: &str) -> Option<(ReservedWord, usize)> {
+    let length = count_bytes(input, 0, |byte| byte.is_ascii_lowercase());
+    let word = match &input[..length] {
+        "await" => ReservedWord::Await(Await),
+        "break" => ReservedWord::Break(Break),
+        "case" => ReservedWord::Case(Case),
+        "catch" => ReservedWord::Catch(Catch),
+        "class" => ReservedWord::Class(Class),
+        "const" => ReservedWord::Const(Const),
+        "continue" => ReservedWord::Continue(Continue),
+        "debugger" => ReservedWord::Debugger(Debugger),
+        "default" => ReservedWord::Default(super::Default),
+        "delete" => ReservedWord::Delete(Delete),
+        "do" => ReservedWord::Do(Do),
+        "else" => ReservedWord::Else(Else),
+        "enum" => ReservedWord::Enum(Enum),
+        "export" => ReservedWord::Export(Export),
+        "extends" => ReservedWord::Extends(Extends),
+        "false" => ReservedWord::False(False),
+        "finally" => ReservedWord::Finally(Finally),
+        "for" => ReservedWord::For(For),
+        "function" => ReservedWord::Function(Function),
+        "if" => ReservedWord::If(If),
+        "import" => ReservedWord::Import(Import),
+        "in" => ReservedWord::In(In),
+        "instanceof" => ReservedWord::Instanceof(Instanceof),
+        "new" => ReservedWord::New(New),
+        "null" => ReservedWord::Null(Null),
+        "return" => ReservedWord::Return(Return),
+        "super" => ReservedWord::Super(Super),
+        "switch" => ReservedWord::Switch(Switch),
+        "this" => ReservedWord::This(This),
+        "throw" => ReservedWord::Throw(Throw),
+        "true" => ReservedWord::True(True),
+        "try" => ReservedWord::Try(Try),
+        "typeof" => ReservedWord::Typeof(Typeof),
+        "var" => ReservedWord::Var(Var),
+        "void" => ReservedWord::Void(Void),
+        "while" => ReservedWord::While(While),
+        "with" => ReservedWord::With(With),
+        "yield" => ReservedWord::Yield(Yield),
+        _ => return None,
+    };
+    if scan_identifier_code_point(input, length, is_identifier_part_char).is_some() {
+        return None;
+    }
+    Some((word, length))
+}
+
+/************************************************
+ *
+ * 12.6 Tokens
+ *
+ ************************************************/
+
+fn scan_common_token(input: &str) -> Option<(CommonToken, usize)> {
+    if let Some(length) = scan_identifier_name(input, 0) {
+        return Some((CommonToken::IdentifierName(identifier_name(&input[..length])), length));
+    }
+    if byte_at(input, 0) == Some(b'#') {
+        if let Some(length) = scan_identifier_name(input, 1) {
+            let identifier_name = identifier_name(&input[1..length]);
+            return Some((CommonToken::PrivateIdentifier(PrivateIdentifier { identifier_name }), length));
+        }
+    }
+    if let Some((literal, length)) = scan_numeric_literal(input) {
+        return Some((CommonToken::NumericLiteral(literal), length));
+    }
+    if let Some(length) = scan_string_literal(input) {
+        let literal = StringLiteral { text: input[..length].to_owned() };
+        return Some((CommonToken::StringLiteral(literal), length));
+    }
+    if let Some((template, length)) = scan_template(input) {
+        return Some((CommonToken::Template(template), length));
+    }
+    scan_punctuator(input).map(|(punctuator, length)| (CommonToken::Punctuator(punctuator), length))
+}
+
+/************************************************
+ *
+ * 12.8 Punctuators
+ *
+ ************************************************/
+
+fn scan_punctuator(input: &str) -> Option<(Punctuator, usize)> {
+    if input.starts_with("?.") && !is_byte_at(input, 2, is_decimal_digit) {
+        return Some((Punctuator::OptionalChainingPunctuator(OptionalChainingPunctuator), 2));
+    }
+    let (punctuator, length) = match input.as_bytes() {
+        [b'{', ..] => (OtherPunctuator::OpeningBrace(OpeningBrace), 1),
+        [b'(', ..] => (OtherPunctuator::OpeningParenthesis(OpeningParenthesis), 1),
+        [b')', ..] => (OtherPunctuator::ClosingParenthesis(ClosingParenthesis), 1),
+        [b'[', ..] => (OtherPunctuator::OpeningBracket(OpeningBracket), 1),
+        [b']', ..] => (OtherPunctuator::ClosingBracket(ClosingBracket), 1),
+        [b'.', b'.', b'.', ..] => (OtherPunctuator::Ellipsis(Ellipsis), 3),
+        [b'.', ..] => (OtherPunctuator::Dot(Dot), 1),
+        [b';', ..] => (OtherPunctuator::Semicolon(Semicolon), 1),
+        [b',', ..] => (OtherPunctuator::Comma(Comma), 1),
+        [b'=', b'=', b'=', ..] => (OtherPunctuator::StrictEquality(StrictEquality), 3),
+        [b'=', b'>', ..] => (OtherPunctuator::FunctionArrow(FunctionArrow), 2),
+        [b'=', b'=', ..] => (OtherPunctuator::LooseEquality(LooseEquality), 2),
+        [b'=', ..] => (OtherPunctuator::Assignment(Assignment), 1),
+        [b'!', b'=', b'=', ..] => (OtherPunctuator::StrictInequality(StrictInequality), 3),
+        [b'!', b'=', ..] => (OtherPunctuator::LooseInequality(LooseInequality), 2),
+        [b'!', ..] => (OtherPunctuator::Not(Not), 1),
+        [b'+', b'+', ..] => (OtherPunctuator::Increment(Increment), 2),
+        [b'+', b'=', ..] => (OtherPunctuator::AdditionAssignment(AdditionAssignment), 2),
+        [b'+', ..] => (OtherPunctuator::Addition(Addition), 1),
+        [b'-', b'-', ..] => (OtherPunctuator::Decrement(Decrement), 2),
+        [b'-', b'=', ..] => (OtherPunctuator::SubtractionAssignment(SubtractionAssignment), 2),
+        [b'-', ..] => (OtherPunctuator::Subtraction(Subtraction), 1),
+        [b'%', b'=', ..] => (OtherPunctuator::ModuloAssignment(ModuloAssignment), 2),
+        [b'%', ..] => (OtherPunctuator::Modulo(Modulo), 1),
+        [b'*', b'*', b'=', ..] => (OtherPunctuator::ExponentiationAssignment(ExponentiationAssignment), 3),
+        [b'*', b'*', ..] => (OtherPunctuator::Exponentiation(Exponentiation), 2),
+        [b'*', b'=', ..] => (OtherPunctuator::MultiplicationAssignment(MultiplicationAssignment), 2),
+        [b'*', ..] => (OtherPunctuator::Multiplication(Multiplication), 1),
+        [b'>', b'>', b'>', b'=', ..] => {
+            (OtherPunctuator::UnsignedRightShiftAssignment(UnsignedRightShiftAssignment), 4)
+        },
+        [b'>', b'>', b'>', ..] => (OtherPunctuator::UnsignedRightShift(UnsignedRightShift), 3),
+        [b'>', b'>', b'=', ..] => (OtherPunctuator::RightShiftAssignment(RightShiftAssignment), 3),
+        [b'>', b'>', ..] => (OtherPunctuator::RightShift(RightShift), 2),
+        [b'>', b'=', ..] => (OtherPunctuator::MoreOrEqual(MoreOrEqual), 2),
+        [b'>', ..] => (OtherPunctuator::More(More), 1),
+        [b'<', b'<', b'=', ..] => (OtherPunctuator::LeftShiftAssignment(LeftShiftAssignment), 3),
+        [b'<', b'<', ..] => (OtherPunctuator::LeftShift(LeftShift), 2),
+        [b'<', b'=', ..] => (OtherPunctuator::LessOrEqual(LessOrEqual), 2),
+        [b'<', ..] => (OtherPunctuator::Less(Less), 1),
+        [b'&', b'&', b'=', ..] => (OtherPunctuator::AndAssignment(AndAssignment), 3),
+        [b'&', b'&', ..] => (OtherPunctuator::And(And), 2),
+        [b'&', b'=', ..] => (OtherPunctuator::BitAndAssignment(BitAndAssignment), 2),
+        [b'&', ..] => (OtherPunctuator::BitAnd(BitAnd), 1),
+        [b'|', b'|', b'=', ..] => (OtherPunctuator::OrAssignment(OrAssignment), 3),
+        [b'|', b'|', ..] => (OtherPunctuator::Or(Or), 2),
+        [b'|', b'=', ..] => (OtherPunctuator::BitOrAssignment(BitOrAssignment), 2),
+        [b'|', ..] => (OtherPunctuator::BitOr(BitOr), 1),
+        [b'^', b'=', ..] => (OtherPunctuator::BitXorAssignment(BitXorAssignment), 2),
+        [b'^', ..] => (OtherPunctuator::BitXor(BitXor), 1),
+        [b'~', ..] => (OtherPunctuator::BitNot(BitNot), 1),
+        [b'?', b'?', b'=', ..] => {
+            (OtherPunctuator::NullishCoalescenceAssignment(NullishCoalescenceAssignment), 3)
+        },
+        [b'?', b'?', ..] => (OtherPunctuator::NullishCoalescence(NullishCoalescence), 2),
+        [b'?', ..] => (OtherPunctuator::QuestionMark(QuestionMark), 1),
+        [b':', ..] => (OtherPunctuator::Colon(Colon), 1),
+        _ => return None,
+    };
+    Some((Punctuator::OtherPunctuator(punctuator), length))
+}
+
+fn scan_div_punctuator(input: &str) -> Option<(Token<'_>, usize)> {
+    let (punctuator, length) = match input.as_bytes() {
+        [b'/', b'=', ..] => (DivPunctuator::DivisionAssignment(DivisionAssignment), 2),
+        [b'/', ..] => (DivPunctuator::Division(Division), 1),
+        _ => return None,
+    };
+    Some((Token::DivPunctuator(punctuator), length))
+}
+
+fn scan_right_brace_punctuator(input: &str) -> Option<(Token<'_>, usize)> {
+    input.starts_with('}').then_some((Token::RightBracePunctuator(RightBracePunctuator), 1))
+}
+
+/************************************************
+ *
+ * 12.9.3 Numeric Literals
+ *
+ ************************************************/
+
+fn scan_numeric_literal(input: &str) -> Option<(NumericLiteral, usize)> {
+    if let Some((integer, end)) = scan_non_decimal_integer_literal(input) {
+        if byte_at(input, end) == Some(b'n') {
+            let literal = NonDecimalBigIntegerLiteral { integer };
+            return Some((NumericLiteral::NonDecimalBigIntegerLiteral(literal), end + 1));
+        }
+        return Some((NumericLiteral::NonDecimalIntegerLiteral(integer), end));
+    }
+    let integer_end = scan_decimal_integer_literal(input, 0);
+    let has_leading_zero = byte_at(input, 0) == Some(b'0') && is_byte_at(input, 1, is_decimal_digit);
+    if let Some(end) = integer_end.filter(|end| !has_leading_zero && byte_at(input, *end) == Some(b'n')) {
+        let integer_part = DecimalIntegerLiteral { digits: input[..end].to_owned() };
+        let literal = DecimalBigIntegerLiteral { integer_part };
+        return Some((NumericLiteral::DecimalBigIntegerLiteral(literal), end + 1));
+    }
+    if let Some(end) = scan_legacy_octal_integer_literal(input) {
+        let literal = LegacyOctalIntegerLiteral { text: input[..end].to_owned() };
+        return Some((NumericLiteral::LegacyOctalIntegerLiteral(literal), end));
+    }
+    let integer_part = integer_end.map(|end| DecimalIntegerLiteral { digits: input[..end].to_owned() });
+    let fraction_start = integer_end.unwrap_or_default();
+    let decimal_point = byte_at(input, fraction_start) == Some(b'.');
+    let (fraction, exponent_start) = if decimal_point {
+        match scan_digits(input, fraction_start + 1, is_decimal_digit) {
+            Some(end) => (Some(DecimalDigits { digits: input[fraction_start + 1..end].to_owned() }), end),
+            // `.` without digits after an integer part.
+            None if integer_part.is_some() => (None, fraction_start + 1),
+            None => return None,
+        }
+    } else if integer_part.is_some() {
+        (None, fraction_start)
+    } else {
+        return None;
+    };
+    let exponent_end = scan_exponent_part(input, exponent_start);
+    let exponent = exponent_end.map(|end| ExponentPart {
+        signed_integer: SignedInteger { text: input[exponent_start + 1..end].to_owned() }
+    });
+    let end = exponent_end.unwrap_or(exponent_start);
+    let literal = DecimalLiteral { integer_part, fraction, exponent, decimal_point };
+    Some((NumericLiteral::DecimalLiteral(literal), end))
+}
+
+fn scan_non_decimal_integer_literal(input: &str) -> Option<(NonDecimalIntegerLiteral, usize)> {
+    let is_digit = match input.as_bytes() {
+        [b'0', b'b' | b'B', ..] => is_binary_digit,
+        [b'0', b'o' | b'O', ..] => is_octal_digit,
+        [b'0', b'x' | b'X', ..] => is_hex_digit,
+        _ => return None,
+    };
+    let end = scan_digits(input, 2, is_digit)?;
+    let digits = input[2..end].to_owned();
+    let literal = match input.as_bytes()[1] {
+        b'b' | b'B' => NonDecimalIntegerLiteral::BinaryIntegerLiteral(BinaryIntegerLiteral {
+            digits: BinaryDigits { digits }
+        }),
+        b'o' | b'O' => NonDecimalIntegerLiteral::OctalIntegerLiteral(OctalIntegerLiteral {
+            digits: OctalDigits { digits }
+        }),
+        _ => NonDecimalIntegerLiteral::HexIntegerLiteral(HexIntegerLiteral { digits: HexDigits { digits } }),
+    };
+    Some((literal, end))
+}
+
+/// `Digit ~ (NumericLiteralSeparator? ~ Digit)*`.
+fn scan_digits(input: &str, position: usize, is_digit: fn(u8) -> bool) -> Option<usize> {
+    if !is_byte_at(input, position, is_digit) {
+        return None;
+    }
+    let mut end = position + 1;
+    loop {
+        if is_byte_at(input, end, is_digit) {
+            end += 1;
+        } else if byte_at(input, end) == Some(b'_') && is_byte_at(input, end + 1, is_digit) {
+            end += 2;
+        } else {
+            return Some(end);
+        }
+    }
+}
+
+/// `"0" ~ OctalDigit+ ~ !NonOctalDigit`.
+fn scan_legacy_octal_integer_literal(input: &str) -> Option<usize> {
+    if byte_at(input, 0) != Some(b'0') {
+        return None;
+    }
+    let end = 1 + count_bytes(input, 1, is_octal_digit);
+    (end > 1 && !matches!(byte_at(input, end), Some(b'8' | b'9'))).then_some(end)
+}
+
+/// `NonOctalDecimalIntegerLiteral | "0" | NonZeroDigit ~
+/// (NumericLiteralSeparator? ~ DecimalDigits)?`.
+fn scan_decimal_integer_literal(input: &str, position: usize) -> Option<usize> {
+    match byte_at(input, position)? {
+        b'0' => {
+            let octal_end = position + 1 + count_bytes(input, position + 1, is_octal_digit);
+            if matches!(byte_at(input, octal_end), Some(b'8' | b'9')) {
+                Some(octal_end + count_bytes(input, octal_end, is_decimal_digit))
+            } else {
+                Some(position + 1)
+            }
+        },
+        b'1'..=b'9' => Some(scan_digits(input, position, is_decimal_digit).unwrap_or(position + 1)),
+        _ => None,
+    }
+}
+
+/// `ExponentIndicator ~ SignedInteger`.
+fn scan_exponent_part(input: &str, position: usize) -> Option<usize> {
+    if !matches!(byte_at(input, position)?, b'e' | b'E') {
+        return None;
+    }
+    let sign = usize::from(matches!(byte_at(input, position + 1), Some(b'+' | b'-')));
+    scan_digits(input, position + 1 + sign, is_decimal_digit)
+}
+
+/************************************************
+ *
+ * 12.9.4 String Literals
+ *
+ ************************************************/
+
+fn scan_string_literal(input: &str) -> Option<usize> {
+    let quote = char::from(byte_at(input, 0).filter(|byte| matches!(byte, b'"' | b'\''))?);
+    let mut position = 1;
+    loop {
+        // Only ASCII code points are special, so skip the rest bytewise.
+        position += count_bytes(input, position, |byte| !matches!(byte, b'"' | b'\'' | b'\\' | b'\n' | b'\r'));
+        let codepoint = char_at(input, position)?;
+        position = match codepoint {
+            _ if codepoint == quote => return Some(position + 1),
+            '\\' => {
+                let escaped = char_at(input, position + 1)?;
+                if is_line_terminator(escaped) {
+                    skip_line_terminator_sequence(input, position + 1)
+                } else {
+                    scan_escape_sequence(input, position + 1)?
+                }
+            },
+            '\n' | '\r' => return None,
+            _ => position + codepoint.len_utf8(),
+        };
+    }
+}
+
+/// `EscapeSequence` starting right after `\`.
+fn scan_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    let codepoint = char_at(input, position)?;
+    match codepoint {
+        '0'..='9' => scan_decimal_escape_sequence(input, position),
+        'x' => scan_hex_escape_sequence(input, position),
+        'u' => scan_unicode_escape_sequence(input, position),
+        _ => scan_character_escape_sequence(input, position),
+    }
+}
+
+/// `CharacterEscapeSequence`: any code point but a digit, `x`, `u` or
+/// a line terminator.
+fn scan_character_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    let codepoint = char_at(input, position)?;
+    let is_escape_character = codepoint.is_ascii_digit() || codepoint == 'x' || codepoint == 'u';
+    (!is_escape_character && !is_line_terminator(codepoint)).then(|| position + codepoint.len_utf8())
+}
+
+/// `"0" ~ !DecimalDigit`, `LegacyOctalEscapeSequence` and
+/// `NonOctalDecimalEscapeSequence`.
+fn scan_decimal_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    let second = byte_at(input, position + 1).unwrap_or_default();
+    let third = byte_at(input, position + 2).unwrap_or_default();
+    let length = match byte_at(input, position)? {
+        b'0' if !is_decimal_digit(second) => 1,
+        b'0' if matches!(second, b'8' | b'9') => 1,
+        b'1'..=b'7' if !is_octal_digit(second) => 1,
+        b'0'..=b'3' if !is_octal_digit(third) => 2,
+        b'4'..=b'7' => 2,
+        b'0'..=b'3' => 3,
+        b'8' | b'9' => 1,
+        _ => return None,
+    };
+    Some(position + length)
+}
+
+/// `"x" ~ HexDigit ~ HexDigit`.
+fn scan_hex_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    (byte_at(input, position) == Some(b'x') && count_bytes(input, position + 1, is_hex_digit) >= 2)
+        .then_some(position + 3)
+}
+
+/// `"u" ~ Hex4Digits | "u{" ~ CodePoint ~ "}"`.
+fn scan_unicode_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    if byte_at(input, position) != Some(b'u') {
+        return None;
+    }
+    if count_bytes(input, position + 1, is_hex_digit) >= 4 {
+        return Some(position + 5);
+    }
+    if byte_at(input, position + 1) != Some(b'{') {
+        return None;
+    }
+    let end = scan_code_point(input, position + 2)?;
+    (byte_at(input, end) == Some(b'}')).then_some(end + 1)
+}
+
+/// `&HexDigit ~ "0"* ~ ("10" ~ HexDigit{4} | HexDigit{1, 5})? ~ !HexDigit`.
+fn scan_code_point(input: &str, position: usize) -> Option<usize> {
+    if !is_byte_at(input, position, is_hex_digit) {
+        return None;
+    }
+    let start = position + count_bytes(input, position, |byte| byte == b'0');
+    let count = count_bytes(input, start, is_hex_digit);
+    let is_astral = input[start..].starts_with("10") && count >= 6;
+    match (is_astral, count) {
+        (true, 6) | (false, 0..=5) => Some(start + count),
+        _ => None,
+    }
+}
+
+/************************************************
+ *
+ * 12.9.5 Regular Expression Literals
+ *
+ ************************************************/
+
+fn scan_regular_expression_literal(input: &str) -> Option<(RegularExpressionLiteral, usize)> {
+    if byte_at(input, 0) != Some(b'/') || matches!(byte_at(input, 1), Some(b'*' | b'/')) {
+        return None;
+    }
+    let mut body_end = scan_regular_expression_char(input, 1)?;
+    while let Some(next) = scan_regular_expression_char(input, body_end) {
+        body_end = next;
+    }
+    if byte_at(input, body_end) != Some(b'/') {
+        return None;
+    }
+    let flags_start = body_end + 1;
+    let flags_length = input[flags_start..]
+        .find(|codepoint| !is_identifier_part_char(codepoint))
+        .unwrap_or(input.len() - flags_start);
+    let end = flags_start + flags_length;
+    let literal = RegularExpressionLiteral {
+        body: RegularExpressionBody { text: input[1..body_end].to_owned() },
+        flags: RegularExpressionFlags { text: input[flags_start..end].to_owned() },
+    };
+    Some((literal, end))
+}
+
+/// `RegularExpressionChar`.
+fn scan_regular_expression_char(input: &str, position: usize) -> Option<usize> {
+    let codepoint = char_at(input, position)?;
+    match codepoint {
+        '/' => None,
+        '\\' => scan_regular_expression_backslash_sequence(input, position),
+        '[' => scan_regular_expression_class(input, position),
+        _ if is_line_terminator(codepoint) => None,
+        _ => Some(position + codepoint.len_utf8()),
+    }
+}
+
+/// `"\\" ~ RegularExpressionNonTerminator`.
+fn scan_regular_expression_backslash_sequence(input: &str, position: usize) -> Option<usize> {
+    let escaped = char_at(input, position + 1)?;
+    (!is_line_terminator(escaped)).then(|| position + 1 + escaped.len_utf8())
+}
+
+/// `"[" ~ RegularExpressionClassChars ~ "]"`.
+fn scan_regular_expression_class(input: &str, position: usize) -> Option<usize> {
+    let mut position = position + 1;
+    loop {
+        let codepoint = char_at(input, position)?;
+        position = match codepoint {
+            ']' => return Some(position + 1),
+            '\\' => scan_regular_expression_backslash_sequence(input, position)?,
+            _ if is_line_terminator(codepoint) => return None,
+            _ => position + codepoint.len_utf8(),
+        };
+    }
+}
+
+/************************************************
+ *
+ * 12.9.6 Template Literal Lexical Components
+ *
+ ************************************************/
+
+fn scan_template(input: &str) -> Option<(Template, usize)> {
+    if byte_at(input, 0) != Some(b'`') {
+        return None;
+    }
+    let end = scan_template_characters(input, 1);
+    if byte_at(input, end) == Some(b'`') {
+        let text = &input[..=end];
+        let template = NoSubstitutionTemplate { characters: template_characters(text) };
+        return Some((Template::NoSubstitutionTemplate(template), end + 1));
+    }
+    input[end..].starts_with("${").then(|| {
+        let characters = template_characters(&input[..end + 2]);
+        (Template::TemplateHead(TemplateHead { characters }), end + 2)
+    })
+}
+
+fn scan_template_substitution_tail(input: &str) -> Option<(Token<'_>, usize)> {
+    if byte_at(input, 0) != Some(b'}') {
+        return None;
+    }
+    let end = scan_template_characters(input, 1);
+    let (tail, length) = if input[end..].starts_with("${") {
+        let characters = template_characters(&input[..end + 2]);
+        (TemplateSubstitutionTail::TemplateMiddle(TemplateMiddle { characters }), end + 2)
+    } else if byte_at(input, end) == Some(b'`') {
+        let characters = template_characters(&input[..=end]);
+        (TemplateSubstitutionTail::TemplateTail(TemplateTail { characters }), end + 1)
+    } else {
+        return None;
+    };
+    Some((Token::TemplateSubstitutionTail(tail), length))
+}
+
+/// `TemplateCharacter*`.
+fn scan_template_characters(input: &str, position: usize) -> usize {
+    let mut position = position;
+    while let Some(codepoint) = char_at(input, position) {
+        position = match codepoint {
+            '$' if byte_at(input, position + 1) == Some(b'{') => break,
+            '`' => break,
+            '\\' => {
+                let escape = scan_template_escape_sequence(input, position + 1)
+                    .or_else(|| scan_not_escape_sequence(input, position + 1));
+                match (escape, char_at(input, position + 1)) {
+                    (Some(end), _) => end,
+                    (None, Some(escaped)) if is_line_terminator(escaped) => {
+                        skip_line_terminator_sequence(input, position + 1)
+                    },
+                    (None, _) => break,
+                }
+            },
+            _ if is_line_terminator(codepoint) => skip_line_terminator_sequence(input, position),
+            _ => position + codepoint.len_utf8(),
+        };
+    }
+    position
+}
+
+/// `TemplateEscapeSequence` starting right after `\`.
+fn scan_template_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    match byte_at(input, position)? {
+        b'0' if !is_byte_at(input, position + 1, is_decimal_digit) => Some(position + 1),
+        b'0'..=b'9' => None,
+        b'x' => scan_hex_escape_sequence(input, position),
+        b'u' => scan_unicode_escape_sequence(input, position),
+        _ => scan_character_escape_sequence(input, position),
+    }
+}
+
+/// `NotEscapeSequence` starting right after `\`.
+fn scan_not_escape_sequence(input: &str, position: usize) -> Option<usize> {
+    let hex_digits = count_bytes(input, position + 1, is_hex_digit);
+    match byte_at(input, position)? {
+        b'0' if is_byte_at(input, position + 1, is_decimal_digit) => Some(position + 2),
+        b'1'..=b'9' => Some(position + 1),
+        b'x' if hex_digits < 2 => Some(position + 1 + hex_digits),
+        b'u' if byte_at(input, position + 1) != Some(b'{') => {
+            (hex_digits < 4).then_some(position + 1 + hex_digits)
+        },
+        b'u' => {
+            let start = position + 2;
+            if !is_byte_at(input, start, is_hex_digit) {
+                return Some(start);
+            }
+            if let Some(end) = scan_not_code_point(input, start) {
+                return Some(end);
+            }
+            scan_code_point(input, start)
+                .filter(|end| !is_byte_at(input, *end, is_hex_digit) && byte_at(input, *end) != Some(b'}'))
+        },
+        _ => None,
+    }
+}
+
+/// `"0"* ~ (HexDigit{7,} | "1" ~ !"0" ~ HexDigit{5} | !("0" | "1") ~ HexDigit{6}) ~ !HexDigit`.
+fn scan_not_code_point(input: &str, position: usize) -> Option<usize> {
+    let start = position + count_bytes(input, position, |byte| byte == b'0');
+    let count = count_bytes(input, start, is_hex_digit);
+    match count {
+        7.. => Some(start + count),
+        6 if !input[start..].starts_with("10") => Some(start + 6),
+        _ => None,
+    }
+}
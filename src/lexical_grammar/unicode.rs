@@ -0,0 +1,23 @@
+//! Unicode properties of code points, looked up in tables `build.rs`
+//! generates from `unicode/properties.txt`.
+//!
+//! The file pins the Unicode version of the scanner, independently of the
+//! `pest` builtin properties the grammar uses.
+
+/// A set of code points as an index of a chunk for each 512 of them and
+/// the distinct chunks as bitmaps.
+pub(super) struct Table {
+    index: &'static [u8; 0x11_0000 / 512],
+    chunks: &'static [[u64; 8]],
+}
+
+impl Table {
+    pub(super) fn contains(&self, codepoint: char) -> bool {
+        let codepoint = codepoint as usize;
+        let chunk = &self.chunks[usize::from(self.index[codepoint / 512])];
+        let bit = codepoint % 512;
+        chunk[bit / 64] & (1 << (bit % 64)) != 0
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/unicode_tables.rs"));
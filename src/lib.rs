@@ -7,5 +7,354 @@
 //! Each grammar rule looks like `Production :: ProductionDefinition`. Each
 //! production has an algorithm for each static and dynamic semantics.
 
+pub mod codegen;
+pub mod early_errors;
+pub mod engine;
 pub mod grammar;
+pub mod interpreter;
 pub mod lexical_grammar;
+pub mod runtime;
+pub mod scopes;
+pub mod source_text;
+pub mod token_cache;
+pub mod token_stream;
+
+use std::fmt;
+use std::ops::Range;
+
+/// Native stack a recursive step may take before [`grow_stack`] moves
+/// deeper steps to a new segment; unoptimized builds take tens of
+/// kilobytes per nesting level of source text.
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// Size of each native stack segment [`grow_stack`] allocates.
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// Run a step of a recursion driven by source text, like parsing a nested
+/// expression, on a new native stack segment if the current one runs low,
+/// so the depth of the recursion is bounded by limits of the library
+/// rather than by the stack of a host thread.
+pub(crate) fn grow_stack<T>(step: impl FnOnce() -> T) -> T {
+    stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, step)
+}
+
+/// Mapping from byte offsets of a source text to line and column numbers.
+///
+/// Lines are split as `LineTerminatorSequence` does, so `<CR><LF>` is a single
+/// line break and `<LS>`/`<PS>` break lines too.
+#[derive(Debug)]
+pub struct LineIndex<'src> {
+    source: &'src str,
+    line_starts: Vec<usize>,
+}
+
+impl<'src> LineIndex<'src> {
+    #[must_use]
+    pub fn new(source: &'src str) -> Self {
+        let mut line_starts = vec![0];
+        let mut codepoints = source.char_indices().peekable();
+        while let Some((offset, codepoint)) = codepoints.next() {
+            let is_break = match codepoint {
+                '\r' => codepoints.peek().is_none_or(|(_, next)| *next != '\n'),
+                '\n' | '\u{2028}' | '\u{2029}' => true,
+                _ => false,
+            };
+            if is_break {
+                line_starts.push(offset + codepoint.len_utf8());
+            }
+        }
+        Self { source, line_starts }
+    }
+
+    /// The text the index was built for.
+    #[must_use]
+    pub const fn source(&self) -> &'src str {
+        self.source
+    }
+
+    /// Get a 1-based line and a 1-based column (counted in code points) of
+    /// a byte offset.
+    ///
+    /// Offsets past the end of the source point to its last line.
+    #[must_use]
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.source
+            .get(line_start..offset)
+            .map_or(offset - line_start, |prefix| prefix.chars().count());
+        (line, column + 1)
+    }
+
+    /// Get byte offsets of a 1-based line without its line terminator.
+    ///
+    /// Lines past the end of the source yield its last line.
+    #[must_use]
+    pub fn line_range(&self, line: usize) -> Range<usize> {
+        let line = line.clamp(1, self.line_starts.len());
+        let start = self.line_starts[line - 1];
+        let end = self.line_starts.get(line).map_or(self.source.len(), |next| *next);
+        let text = &self.source[start..end];
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let text = text.strip_suffix(['\r', '\u{2028}', '\u{2029}']).unwrap_or(text);
+        start..start + text.len()
+    }
+}
+
+/// How bad a reported problem is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// The source text does not conform to ECMA-262.
+    Error,
+    /// The source text conforms to ECMA-262 but likely contains a mistake.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// A machine-readable category of a reported problem.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    /// Source text does not match any production expected at the place.
+    UnexpectedInput,
+    /// Source text ends before a production expected at the place is
+    /// complete.
+    IncompleteInput,
+    /// Encoded input does not represent a sequence of Unicode code points.
+    IllFormedInput,
+    /// Source text matches the grammar but breaks one of its early error
+    /// rules, like restrictions of strict mode code.
+    EarlyError,
+    /// Source text is correct but uses a legacy or deprecated feature; such
+    /// problems are reported with [`Severity::Warning`].
+    DeprecatedSyntax,
+    /// Source text nests expressions, statements or patterns deeper than
+    /// the parser is set to allow.
+    NestingTooDeep,
+    /// The library is broken, for example, its grammar and typed tree
+    /// disagree. Please report it as a bug.
+    InternalError,
+}
+
+/// A secondary location attached to a reported problem.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Label {
+    /// Byte offsets of the labeled source text.
+    pub range: Range<usize>,
+    pub message: String,
+}
+
+/// A problem found in a `.js`/`.mjs` text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceCodeError {
+    /// Byte offsets of the offending source text.
+    pub range: Range<usize>,
+    /// 1-based line of the range start.
+    pub line: usize,
+    /// 1-based column of the range start, counted in code points.
+    pub column: usize,
+    pub message: String,
+    /// Other places related to the problem, if any.
+    pub labels: Vec<Label>,
+    /// Descriptions of input that would be accepted instead, like `` `*/` ``
+    /// for an unterminated comment; empty if unknown.
+    pub expected: Vec<String>,
+    pub severity: Severity,
+    pub code: ErrorCode,
+}
+
+impl SourceCodeError {
+    /// Create an error report with a position computed from the source text
+    /// the range refers to.
+    #[must_use]
+    pub fn new(source: &str, range: Range<usize>, code: ErrorCode, message: String) -> Self {
+        let (line, column) = LineIndex::new(source).line_column(range.start);
+        Self {
+            range,
+            line,
+            column,
+            message,
+            labels: Vec::new(),
+            expected: Vec::new(),
+            severity: Severity::Error,
+            code,
+        }
+    }
+
+    /// Format the error the way rustc does, quoting source lines the error
+    /// and its labels point to:
+    ///
+    /// ```text
+    /// error: unterminated string literal
+    ///  --> 1:9
+    ///   |
+    /// 1 | let a = 'abc
+    ///   |         ^
+    ///   |             - expected `'` here
+    /// ```
+    ///
+    /// `source` is expected to be the text the error was reported for.
+    #[must_use]
+    pub fn render(&self, source: &str) -> String {
+        let index = LineIndex::new(source);
+        let primary = Label { range: self.range.clone(), message: String::new() };
+        let mut marks: Vec<_> = std::iter::once((&primary, '^'))
+            .chain(self.labels.iter().map(|label| (label, '-')))
+            .map(|(label, marker)| (index.line_column(label.range.start).0, label, marker))
+            .collect();
+        // The sort is stable, so the primary mark goes first on its line.
+        marks.sort_by_key(|(line, ..)| *line);
+        let gutter = marks.last().map_or(1, |(line, ..)| line.to_string().len());
+        let mut rendered = vec![
+            format!("{}: {}", self.severity, self.message),
+            format!("{:gutter$}--> {}:{}", "", self.line, self.column),
+            format!("{:gutter$} |", ""),
+        ];
+        let mut previous_line = None;
+        for (line, label, marker) in marks {
+            let line_range = index.line_range(line);
+            let text = &source[line_range.clone()];
+            if previous_line != Some(line) {
+                rendered.push(format!("{line:>gutter$} | {text}"));
+                previous_line = Some(line);
+            }
+            // Keep tabs so the markers line up with the quoted text.
+            let start = label.range.start.clamp(line_range.start, line_range.end) - line_range.start;
+            let end = label.range.end.clamp(line_range.start, line_range.end) - line_range.start;
+            let indent: String = text.get(..start).unwrap_or_default()
+                .chars()
+                .map(|codepoint| if codepoint == '\t' { '\t' } else { ' ' })
+                .collect();
+            let width = text.get(start..end).map_or(0, |marked| marked.chars().count()).max(1);
+            let markers = marker.to_string().repeat(width);
+            let underline = format!("{:gutter$} | {indent}{markers} {}", "", label.message);
+            rendered.push(underline.trim_end().to_owned());
+        }
+        rendered.join("\n")
+    }
+}
+
+impl fmt::Display for SourceCodeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{}: {}", self.severity, self.message)?;
+        write!(formatter, " --> {}:{}", self.line, self.column)
+    }
+}
+
+impl std::error::Error for SourceCodeError {}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for SourceCodeError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(format!("embedded_ecmascript::{:?}", self.code)))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.severity {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        if self.expected.is_empty() {
+            return None;
+        }
+        Some(Box::new(format!("expected {}", self.expected.join(" or "))))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let primary = miette::LabeledSpan::new_primary_with_span(None, self.range.clone());
+        let secondary = self.labels
+            .iter()
+            .map(|label| miette::LabeledSpan::new_with_span(Some(label.message.clone()), label.range.clone()));
+        Some(Box::new(std::iter::once(primary).chain(secondary)))
+    }
+}
+
+/// A sink collecting all problems found in a source text instead of stopping
+/// at the first one.
+///
+/// Functions taking it recover from each problem and continue, so editors and
+/// linters get a full list per file. Problems are kept in the order they are
+/// reported in.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diagnostics {
+    reports: Vec<SourceCodeError>,
+}
+
+impl Diagnostics {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { reports: Vec::new() }
+    }
+
+    pub fn report(&mut self, error: SourceCodeError) {
+        self.reports.push(error);
+    }
+
+    /// Whether any of reported problems has [`Severity::Error`].
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.reports.iter().any(|report| report.severity == Severity::Error)
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, SourceCodeError> {
+        self.reports.iter()
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<SourceCodeError> {
+        self.reports
+    }
+
+    /// Format each problem with [`SourceCodeError::render`], separated by
+    /// empty lines.
+    #[must_use]
+    pub fn render(&self, source: &str) -> String {
+        let rendered: Vec<_> = self.reports.iter().map(|report| report.render(source)).collect();
+        rendered.join("\n\n")
+    }
+}
+
+impl Extend<SourceCodeError> for Diagnostics {
+    fn extend<T: IntoIterator<Item = SourceCodeError>>(&mut self, reports: T) {
+        self.reports.extend(reports);
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = SourceCodeError;
+    type IntoIter = std::vec::IntoIter<SourceCodeError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reports.into_iter()
+    }
+}
+
+impl<'diagnostics> IntoIterator for &'diagnostics Diagnostics {
+    type Item = &'diagnostics SourceCodeError;
+    type IntoIter = std::slice::Iter<'diagnostics, SourceCodeError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reports.iter()
+    }
+}
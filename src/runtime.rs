@@ -0,0 +1,52 @@
+//! Values and objects ECMAScript code operates on at run time.
+//!
+//! Implements the language types of
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-data-types-and-values>
+//! and the abstract operations the specification defines over them. The
+//! [`crate::interpreter`] evaluates code in terms of these types.
+
+mod arguments;
+mod bigint;
+mod bindings;
+mod context;
+mod conversions;
+mod environment;
+mod error;
+mod error_objects;
+mod heap;
+mod iteration;
+mod namespace;
+mod number;
+mod object;
+mod private_names;
+mod promise;
+mod proxy;
+mod realm;
+mod reflect;
+#[cfg(feature = "serde")]
+mod serialization;
+mod string;
+mod value;
+
+pub use arguments::ParameterMap;
+pub use bigint::*;
+pub use bindings::*;
+pub use context::*;
+pub use conversions::*;
+pub use environment::*;
+pub use error::*;
+pub use heap::{Gc, HandleScope, Heap, HeapScope, HeapStatistics};
+pub(crate) use heap::{charge, charge_code, Tracer};
+pub use iteration::*;
+pub use namespace::*;
+pub use number::*;
+pub use object::*;
+pub use private_names::*;
+pub use promise::{Promise, PromiseCapability, PromiseState};
+pub(crate) use promise::{perform_promise_then, promise_resolve_with};
+pub use proxy::Proxy;
+pub use realm::*;
+#[cfg(feature = "serde")]
+pub use serialization::*;
+pub use string::*;
+pub use value::*;
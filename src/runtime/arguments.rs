@@ -0,0 +1,62 @@
+//! <https://262.ecma-international.org/14.0/#sec-arguments-exotic-objects>
+
+use super::heap::Tracer;
+use super::{Environment, PropertyKey, Value};
+use std::cell::RefCell;
+
+/// `[[ParameterMap]]` of a mapped arguments object, being one of kind
+/// [`ObjectKind::Arguments`](super::ObjectKind::Arguments).
+///
+/// Each argument index is mapped to the parameter binding it is kept in
+/// sync with, the way accessors made by `MakeArgGetter` and
+/// `MakeArgSetter` do, until the index is unmapped.
+#[derive(Debug)]
+pub struct ParameterMap {
+    environment: Environment,
+    names: RefCell<Vec<Option<String>>>,
+}
+
+impl ParameterMap {
+    /// Map each argument index to a parameter of `environment`, `None` for
+    /// indices without one.
+    #[must_use]
+    pub const fn new(environment: Environment, names: Vec<Option<String>>) -> Self {
+        Self { environment, names: RefCell::new(names) }
+    }
+
+    fn name(&self, key: &PropertyKey) -> Option<String> {
+        let index = usize::try_from(key.as_array_index()?).ok()?;
+        self.names.borrow().get(index).cloned().flatten()
+    }
+
+    /// Whether the property is mapped to a parameter.
+    pub(super) fn has(&self, key: &PropertyKey) -> bool {
+        self.name(key).is_some()
+    }
+
+    /// The value of the parameter a property is mapped to.
+    pub(super) fn get(&self, key: &PropertyKey) -> Option<Value> {
+        self.environment.binding_value(&self.name(key)?)
+    }
+
+    /// Assign to the parameter a property is mapped to.
+    pub(super) fn set(&self, key: &PropertyKey, value: Value) {
+        if let Some(name) = self.name(key) {
+            self.environment.set_binding_value(&name, value);
+        }
+    }
+
+    /// Stop keeping a property in sync with its parameter.
+    pub(super) fn delete(&self, key: &PropertyKey) {
+        let Some(index) = key.as_array_index().and_then(|index| usize::try_from(index).ok()) else {
+            return;
+        };
+        if let Some(name) = self.names.borrow_mut().get_mut(index) {
+            *name = None;
+        }
+    }
+
+    pub(super) fn trace(&self, tracer: &mut Tracer) {
+        tracer.environment(&self.environment);
+    }
+}
@@ -0,0 +1,240 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types-bigint-type>
+
+use super::{ErrorKind, JsError};
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+/// The largest `BigInt` an operation may produce, in bits, like in V8.
+///
+/// The specification allows any size, but a single `2n ** 2n ** 40n`
+/// would exhaust the memory of a host.
+const MAX_BITS: u64 = 1 << 30;
+
+/// Operations of
+/// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint>
+/// over arbitrary-precision integers of `num-bigint`, named as in the
+/// specification.
+///
+/// Operations that may throw return a `RangeError`, or a `TypeError` for
+/// `>>>`, which `BigInt`s do not have.
+///
+/// ```
+/// use embedded_ecmascript::runtime::BigIntOps;
+/// use num_bigint::BigInt;
+///
+/// let x = BigInt::from(-7);
+/// assert_eq!(BigIntOps::divide(&x, &BigInt::from(2)), Ok(BigInt::from(-3)));
+/// assert_eq!(BigIntOps::signed_right_shift(&x, &BigInt::from(1)), Ok(BigInt::from(-4)));
+/// assert_eq!(BigIntOps::to_string(&BigInt::from(255), 16), "ff");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BigIntOps;
+
+impl BigIntOps {
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-unaryMinus>
+    #[must_use]
+    pub fn unary_minus(x: &BigInt) -> BigInt {
+        -x
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-bitwiseNOT>
+    #[must_use]
+    pub fn bitwise_not(x: &BigInt) -> BigInt {
+        -x - 1
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-exponentiate>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the exponent is negative or
+    /// the result is too large.
+    pub fn exponentiate(base: &BigInt, exponent: &BigInt) -> Result<BigInt, JsError> {
+        if exponent.is_negative() {
+            return Err(JsError::new(ErrorKind::RangeError, "exponent must not be negative"));
+        }
+        if exponent.is_zero() {
+            return Ok(BigInt::one());
+        }
+        // Powers of 0, 1 and -1 never grow.
+        if base.is_zero() || base.magnitude().is_one() {
+            let is_even = !exponent.bit(0);
+            return Ok(if base.is_negative() && is_even { BigInt::one() } else { base.clone() });
+        }
+        let exponent = exponent.to_u32().filter(|&exponent| u64::from(exponent) * base.bits() <= MAX_BITS);
+        exponent.map(|exponent| base.pow(exponent)).ok_or_else(too_large)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-multiply>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the result is too large.
+    pub fn multiply(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        // The product has at least one bit less than the operands together.
+        if x.bits() + y.bits() > MAX_BITS + 1 {
+            return Err(too_large());
+        }
+        checked(x * y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-divide>,
+    /// rounding towards zero.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` for division by zero.
+    pub fn divide(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        if y.is_zero() {
+            return Err(division_by_zero());
+        }
+        Ok(x / y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-remainder>,
+    /// which has the sign of `n`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` for division by zero.
+    pub fn remainder(n: &BigInt, d: &BigInt) -> Result<BigInt, JsError> {
+        if d.is_zero() {
+            return Err(division_by_zero());
+        }
+        Ok(n % d)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-add>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the result is too large.
+    pub fn add(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        checked(x + y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-subtract>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the result is too large.
+    pub fn subtract(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        checked(x - y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-leftShift>;
+    /// a negative shift is a right one, rounding towards negative infinity.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the result is too large.
+    pub fn left_shift(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        if x.is_zero() {
+            return Ok(BigInt::zero());
+        }
+        if y.is_negative() {
+            // Shifting by more bits than there are leaves only the sign.
+            return Ok(match y.magnitude().to_u64().filter(|&shift| shift < x.bits()) {
+                Some(shift) => x >> shift,
+                None if x.is_negative() => -BigInt::one(),
+                None => BigInt::zero(),
+            });
+        }
+        let shift = y.to_u64().filter(|&shift| x.bits().saturating_add(shift) <= MAX_BITS);
+        shift.map(|shift| x << shift).ok_or_else(too_large)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-signedRightShift>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if the result is too large.
+    pub fn signed_right_shift(x: &BigInt, y: &BigInt) -> Result<BigInt, JsError> {
+        Self::left_shift(x, &-y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-unsignedRightShift>
+    ///
+    /// # Errors
+    ///
+    /// Will always return `Err` with a `TypeError` since `BigInt`s have no
+    /// unsigned representation.
+    pub fn unsigned_right_shift(_x: &BigInt, _y: &BigInt) -> Result<BigInt, JsError> {
+        Err(JsError::type_error("BigInts have no unsigned right shift, use >> instead"))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-lessThan>
+    #[must_use]
+    pub fn less_than(x: &BigInt, y: &BigInt) -> bool {
+        x < y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-equal>
+    #[must_use]
+    pub fn equal(x: &BigInt, y: &BigInt) -> bool {
+        x == y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-bitwiseAND>
+    #[must_use]
+    pub fn bitwise_and(x: &BigInt, y: &BigInt) -> BigInt {
+        x & y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-bitwiseXOR>
+    #[must_use]
+    pub fn bitwise_xor(x: &BigInt, y: &BigInt) -> BigInt {
+        x ^ y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-bitwiseOR>
+    #[must_use]
+    pub fn bitwise_or(x: &BigInt, y: &BigInt) -> BigInt {
+        x | y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-bigint-tostring>
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `radix` is not in the 2..=36 range.
+    #[must_use]
+    pub fn to_string(x: &BigInt, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix {radix} is out of the 2..=36 range");
+        x.to_str_radix(radix)
+    }
+
+    /// Compare a `BigInt` with a Number exactly, as
+    /// <https://262.ecma-international.org/14.0/#sec-islessthan> and
+    /// <https://262.ecma-international.org/14.0/#sec-islooselyequal> do;
+    /// `None` if the Number is NaN.
+    #[must_use]
+    pub fn compare_to_number(x: &BigInt, y: f64) -> Option<Ordering> {
+        if y.is_nan() {
+            return None;
+        }
+        if y.is_infinite() {
+            return Some(if y > 0.0 { Ordering::Less } else { Ordering::Greater });
+        }
+        // A fraction only matters if the integral parts are equal.
+        let integral = BigInt::from_f64(y.trunc())?;
+        let fraction = 0.0.partial_cmp(&(y - y.trunc()))?;
+        Some(x.cmp(&integral).then(fraction))
+    }
+}
+
+fn checked(result: BigInt) -> Result<BigInt, JsError> {
+    if result.bits() > MAX_BITS {
+        return Err(too_large());
+    }
+    Ok(result)
+}
+
+fn too_large() -> JsError {
+    JsError::new(ErrorKind::RangeError, "maximum BigInt size exceeded")
+}
+
+fn division_by_zero() -> JsError {
+    JsError::new(ErrorKind::RangeError, "division by zero")
+}
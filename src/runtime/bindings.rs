@@ -0,0 +1,244 @@
+//! Conversions between [`Value`] and Rust types for host functions.
+//!
+//! [`TryFromJs`] converts values coming from scripts the way built-in
+//! functions do, applying <https://262.ecma-international.org/14.0/#sec-type-conversion>
+//! operations; [`IntoJs`] converts results back. [`FromArguments`] takes
+//! a tuple of them from an argument list, with missing arguments being
+//! **undefined**.
+
+use super::{Context, JsError, JsString, Object, PropertyKey, Realm, Value};
+
+/// A Rust type a [`Value`] converts to.
+pub trait TryFromJs: Sized {
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the value has no
+    /// representation as `Self`, or with an exception a conversion throws.
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError>;
+}
+
+/// A Rust type that converts to a [`Value`].
+///
+/// Objects are created with prototypes of the current realm.
+pub trait IntoJs {
+    fn into_js(self, context: &Context) -> Value;
+}
+
+/// A tuple of [`TryFromJs`] types an argument list converts to.
+pub trait FromArguments: Sized {
+    /// # Errors
+    ///
+    /// Will return `Err` with an error of the first argument failing to
+    /// convert.
+    fn from_arguments(arguments: &[Value], context: &mut Context) -> Result<Self, JsError>;
+}
+
+impl TryFromJs for Value {
+    fn try_from_js(value: &Value, _: &mut Context) -> Result<Self, JsError> {
+        Ok(value.clone())
+    }
+}
+
+impl TryFromJs for bool {
+    /// <https://262.ecma-international.org/14.0/#sec-toboolean>
+    fn try_from_js(value: &Value, _: &mut Context) -> Result<Self, JsError> {
+        Ok(value.to_boolean())
+    }
+}
+
+impl TryFromJs for f64 {
+    /// <https://262.ecma-international.org/14.0/#sec-tonumber>
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        value.to_number(context)
+    }
+}
+
+impl TryFromJs for i32 {
+    /// <https://262.ecma-international.org/14.0/#sec-toint32>
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        value.to_int32(context)
+    }
+}
+
+impl TryFromJs for u32 {
+    /// <https://262.ecma-international.org/14.0/#sec-touint32>
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        value.to_uint32(context)
+    }
+}
+
+impl TryFromJs for JsString {
+    /// <https://262.ecma-international.org/14.0/#sec-tostring>
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        value.to_string(context)
+    }
+}
+
+impl TryFromJs for String {
+    /// <https://262.ecma-international.org/14.0/#sec-tostring>, failing for
+    /// strings with unpaired surrogates.
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        value
+            .to_string(context)?
+            .to_std_string()
+            .ok_or_else(|| JsError::type_error("string has unpaired surrogates"))
+    }
+}
+
+impl TryFromJs for Object {
+    fn try_from_js(value: &Value, _: &mut Context) -> Result<Self, JsError> {
+        match value {
+            Value::Object(object) => Ok(object.clone()),
+            _ => Err(JsError::type_error("expected an object")),
+        }
+    }
+}
+
+impl<T: TryFromJs> TryFromJs for Option<T> {
+    /// `None` for **undefined** and **null**.
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        match value {
+            Value::Undefined | Value::Null => Ok(None),
+            _ => T::try_from_js(value, context).map(Some),
+        }
+    }
+}
+
+impl<T: TryFromJs> TryFromJs for Vec<T> {
+    /// Elements of an array-like object, up to its `length`.
+    fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+        let object = Object::try_from_js(value, context)?;
+        let length = object.length_of_array_like(context)?;
+        (0..length).map(|index| T::try_from_js(&element(context, &object, index)?, context)).collect()
+    }
+}
+
+impl IntoJs for Value {
+    fn into_js(self, _: &Context) -> Value {
+        self
+    }
+}
+
+impl IntoJs for () {
+    /// **undefined**, for functions returning nothing.
+    fn into_js(self, _: &Context) -> Value {
+        Value::Undefined
+    }
+}
+
+impl IntoJs for Object {
+    fn into_js(self, _: &Context) -> Value {
+        Value::Object(self)
+    }
+}
+
+impl IntoJs for bool {
+    fn into_js(self, _: &Context) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+impl IntoJs for f64 {
+    fn into_js(self, _: &Context) -> Value {
+        Value::Number(self)
+    }
+}
+
+impl IntoJs for i32 {
+    fn into_js(self, _: &Context) -> Value {
+        Value::from(self)
+    }
+}
+
+impl IntoJs for u32 {
+    fn into_js(self, _: &Context) -> Value {
+        Value::from(self)
+    }
+}
+
+impl IntoJs for JsString {
+    fn into_js(self, _: &Context) -> Value {
+        Value::String(self)
+    }
+}
+
+impl IntoJs for String {
+    fn into_js(self, _: &Context) -> Value {
+        Value::from(self)
+    }
+}
+
+impl IntoJs for &str {
+    fn into_js(self, _: &Context) -> Value {
+        Value::from(self)
+    }
+}
+
+impl<T: IntoJs> IntoJs for Option<T> {
+    /// **undefined** for `None`.
+    fn into_js(self, context: &Context) -> Value {
+        self.map_or(Value::Undefined, |value| value.into_js(context))
+    }
+}
+
+impl<T: IntoJs> IntoJs for Vec<T> {
+    /// <https://262.ecma-international.org/14.0/#sec-createarrayfromlist>
+    fn into_js(self, context: &Context) -> Value {
+        let elements = self.into_iter().map(|element| element.into_js(context)).collect();
+        Value::Object(new_array(context, elements))
+    }
+}
+
+/// The element of an array-like object at an index.
+fn element(context: &mut Context, object: &Object, index: u64) -> Result<Value, JsError> {
+    object.get(context, &PropertyKey::from(index.to_string()), &Value::Object(object.clone()))
+}
+
+/// An array with the prototype of the current realm.
+fn new_array(context: &Context, elements: Vec<Value>) -> Object {
+    let prototype = context.current_realm().map(Realm::intrinsics).map(|intrinsics| intrinsics.array_prototype.clone());
+    Object::new_array(prototype, elements)
+}
+
+/// Implement the conversions for a tuple, which is an array of a fixed
+/// length to scripts.
+macro_rules! tuple_conversions {
+    ($($name:ident $index:tt),*) => {
+        impl<$($name: TryFromJs),*> TryFromJs for ($($name,)*) {
+            /// The first elements of an array-like object, **undefined**
+            /// if missing.
+            #[allow(unused_variables)]
+            fn try_from_js(value: &Value, context: &mut Context) -> Result<Self, JsError> {
+                let object = Object::try_from_js(value, context)?;
+                Ok(($($name::try_from_js(&element(context, &object, $index)?, context)?,)*))
+            }
+        }
+
+        impl<$($name: IntoJs),*> IntoJs for ($($name,)*) {
+            #[allow(unused_variables)]
+            fn into_js(self, context: &Context) -> Value {
+                Value::Object(new_array(context, vec![$(self.$index.into_js(context)),*]))
+            }
+        }
+
+        impl<$($name: TryFromJs),*> FromArguments for ($($name,)*) {
+            #[allow(unused_variables)]
+            fn from_arguments(arguments: &[Value], context: &mut Context) -> Result<Self, JsError> {
+                Ok(($($name::try_from_js(arguments.get($index).unwrap_or(&Value::Undefined), context)?,)*))
+            }
+        }
+    };
+}
+
+tuple_conversions!(A 0);
+tuple_conversions!(A 0, B 1);
+tuple_conversions!(A 0, B 1, C 2);
+tuple_conversions!(A 0, B 1, C 2, D 3);
+tuple_conversions!(A 0, B 1, C 2, D 3, E 4);
+tuple_conversions!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+impl FromArguments for () {
+    fn from_arguments(_: &[Value], _: &mut Context) -> Result<Self, JsError> {
+        Ok(())
+    }
+}
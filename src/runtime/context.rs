@@ -0,0 +1,289 @@
+//! State shared by abstract operations and functions while code runs.
+
+use super::{ErrorKind, ExecutionContext, Heap, JsError, Object, Realm};
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::ControlFlow;
+
+/// How deep calls may nest before [`Context`] throws a `RangeError`.
+/// Calls grow the native stack onto the heap as they go, so the limit
+/// bounds memory rather than the stack of the host thread. A call in tail
+/// position of strict code takes the depth of the call it ends.
+const MAX_CALL_DEPTH: usize = 256;
+
+/// Bounds on memory code running in a [`Context`] may take, counted in
+/// objects and environments, and in bytes of strings, properties and code.
+///
+/// They are checked as calls and loop iterations start, so straight-line
+/// code may go over them by what its source text creates, and a loop by
+/// what its last iteration creates.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryLimits {
+    /// How many objects and environments of the heap of the context may
+    /// be alive, including intrinsics of its realm; unreachable ones are
+    /// collected before the limit is enforced.
+    ///
+    /// A collection scans the whole heap, so once one finds the heap near
+    /// the limit, the next waits until a quarter of the limit more nodes
+    /// are created, which the heap may go over the limit by meanwhile.
+    pub max_object_count: Option<usize>,
+    /// How many objects and environments a single evaluation may create.
+    pub allocation_budget: Option<usize>,
+    /// How many bytes of strings, properties and code a single evaluation
+    /// may create, as [`Heap::allocated_bytes`] counts them.
+    ///
+    /// Source text is charged before it is parsed, so a script or a module
+    /// too large for the budget fails without building its tree.
+    pub byte_budget: Option<usize>,
+}
+
+/// A host hook polled as calls and loop iterations start, stopping the
+/// running code with an `Interrupted` error when it breaks.
+///
+/// A watchdog or a cancellation token of another thread usually sets an
+/// atomic flag that the hook reads.
+pub type InterruptHandler = Box<dyn Fn() -> ControlFlow<()>>;
+
+/// <https://262.ecma-international.org/14.0/#job>: an operation run once
+/// no other code runs.
+pub type Job = Box<dyn FnOnce(&mut Context) -> Result<(), JsError>>;
+
+/// A job waiting in a queue, with the realm to run it in.
+struct PendingJob {
+    job: Job,
+    realm: Option<Realm>,
+}
+
+/// The state of an agent that abstract operations run in.
+///
+/// Functions get it to call other functions; it limits how deep the calls
+/// nest, keeps
+/// <https://262.ecma-international.org/14.0/#execution-context-stack>
+/// and owns the [`Heap`] of objects the code creates.
+#[derive(Default)]
+pub struct Context {
+    call_depth: usize,
+    execution_contexts: Vec<ExecutionContext>,
+    heap: Heap,
+    memory_limits: MemoryLimits,
+    /// [`Heap::allocations`] when the allocation budget was last reset.
+    allocations_at_reset: usize,
+    /// [`Heap::allocated_bytes`] when the byte budget was last reset.
+    bytes_at_reset: usize,
+    /// [`Heap::allocations`] when garbage was last collected to count
+    /// live nodes.
+    allocations_at_collection: usize,
+    /// How many more calls and loop iterations code may start, unbounded
+    /// if `None`.
+    fuel: Option<u64>,
+    interrupt_handler: Option<InterruptHandler>,
+    /// Promise jobs waiting to run, in the order they are enqueued.
+    jobs: VecDeque<PendingJob>,
+}
+
+impl Context {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `step` as a nested call: a function call or an internal method
+    /// of a proxy going on to its target.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `RangeError` if calls nest too deep, or
+    /// with an exception `step` throws.
+    pub(crate) fn nested_call<T>(&mut self, step: impl FnOnce(&mut Self) -> Result<T, JsError>) -> Result<T, JsError> {
+        if self.call_depth == MAX_CALL_DEPTH {
+            return Err(JsError::new(ErrorKind::RangeError, "maximum call stack size exceeded"));
+        }
+        self.check_limits()?;
+        self.call_depth += 1;
+        // Calls nest as deep as the limit allows whatever stack the host
+        // thread has.
+        let result = crate::grow_stack(|| step(self));
+        self.call_depth -= 1;
+        result
+    }
+
+    /// The heap objects and environments created by code running in the
+    /// context belong to.
+    #[must_use]
+    pub const fn heap(&self) -> &Heap {
+        &self.heap
+    }
+
+    #[must_use]
+    pub const fn memory_limits(&self) -> MemoryLimits {
+        self.memory_limits
+    }
+
+    /// Bound memory code may take from now on, resetting the allocation
+    /// budget.
+    pub fn set_memory_limits(&mut self, memory_limits: MemoryLimits) {
+        self.memory_limits = memory_limits;
+        self.reset_allocation_budget();
+    }
+
+    /// Give the code that runs next the whole allocation and byte budgets.
+    pub fn reset_allocation_budget(&mut self) {
+        self.allocations_at_reset = self.heap.allocations();
+        self.bytes_at_reset = self.heap.allocated_bytes();
+    }
+
+    /// How many more calls and loop iterations code may start.
+    #[must_use]
+    pub const fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    /// Bound how many calls and loop iterations code may start before it
+    /// stops with an `OutOfFuel` error, so that a script running forever
+    /// does not hang the host.
+    pub const fn set_fuel(&mut self, fuel: Option<u64>) {
+        self.fuel = fuel;
+    }
+
+    /// Poll a hook as code runs, stopping it once the hook breaks; `None`
+    /// removes the hook.
+    pub fn set_interrupt_handler(&mut self, interrupt_handler: Option<InterruptHandler>) {
+        self.interrupt_handler = interrupt_handler;
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-hostenqueuepromisejob>:
+    /// schedule a job of a promise reaction or an `await` continuation to
+    /// run in a realm, after jobs enqueued earlier.
+    ///
+    /// Jobs run only when the host calls [`Context::run_jobs`].
+    pub fn enqueue_promise_job(&mut self, job: Job, realm: Option<Realm>) {
+        self.jobs.push_back(PendingJob { job, realm });
+    }
+
+    #[must_use]
+    pub fn has_pending_jobs(&self) -> bool {
+        !self.jobs.is_empty()
+    }
+
+    /// Run enqueued jobs, including ones they enqueue, until none is left.
+    ///
+    /// A job with a realm runs in a new execution context of it, like
+    /// <https://262.ecma-international.org/14.0/#sec-hostmakejobcallback>
+    /// callbacks do.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the error of the first job that fails,
+    /// leaving later jobs queued.
+    pub fn run_jobs(&mut self) -> Result<(), JsError> {
+        while let Some(PendingJob { job, realm }) = self.jobs.pop_front() {
+            let Some(realm) = realm else {
+                job(self)?;
+                continue;
+            };
+            self.push_execution_context(ExecutionContext::new(None, realm));
+            let result = job(self);
+            self.pop_execution_context();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Check that running code stays within limits of the context, at
+    /// a point it may go on indefinitely from, like a loop iteration, and
+    /// burn a unit of fuel.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an `Interrupted` error if the interrupt
+    /// handler breaks, with an `OutOfFuel` one if the fuel is burnt out,
+    /// or with a `MemoryLimit` one if the code has taken more memory than
+    /// [`MemoryLimits`] allow.
+    pub(crate) fn check_limits(&mut self) -> Result<(), JsError> {
+        if self.interrupt_handler.as_ref().is_some_and(|handler| handler().is_break()) {
+            return Err(JsError::new(ErrorKind::Interrupted, "execution was interrupted"));
+        }
+        if let Some(fuel) = &mut self.fuel {
+            if *fuel == 0 {
+                return Err(JsError::new(ErrorKind::OutOfFuel, "execution ran out of fuel"));
+            }
+            *fuel -= 1;
+        }
+        let MemoryLimits { max_object_count, allocation_budget, .. } = self.memory_limits;
+        let allocations = self.heap.allocations();
+        if allocation_budget.is_some_and(|budget| allocations - self.allocations_at_reset > budget) {
+            return Err(JsError::new(ErrorKind::MemoryLimit, "allocation budget exceeded"));
+        }
+        self.check_byte_budget()?;
+        // Counting live nodes takes a collection, so it happens only once
+        // nodes created since the last one may be over the limit, and not
+        // again until enough more are created to pay for the scan.
+        if let Some(count) = max_object_count {
+            if self.heap.registered_size() > count && allocations - self.allocations_at_collection > count / 4 {
+                self.allocations_at_collection = allocations;
+                if self.heap.collect_garbage().live > count {
+                    return Err(JsError::new(ErrorKind::MemoryLimit, "maximum object count exceeded"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fail with a `MemoryLimit` error if the current evaluation has taken
+    /// more bytes than [`MemoryLimits::byte_budget`] allows.
+    pub(crate) fn check_byte_budget(&self) -> Result<(), JsError> {
+        let bytes = self.heap.allocated_bytes() - self.bytes_at_reset;
+        if self.memory_limits.byte_budget.is_some_and(|budget| bytes > budget) {
+            return Err(JsError::new(ErrorKind::MemoryLimit, "byte budget exceeded"));
+        }
+        Ok(())
+    }
+
+    /// Make an execution context the running one, suspending the current
+    /// one until [`Context::pop_execution_context`].
+    pub fn push_execution_context(&mut self, execution_context: ExecutionContext) {
+        self.execution_contexts.push(execution_context);
+    }
+
+    /// Remove the running execution context, resuming the one below it.
+    pub fn pop_execution_context(&mut self) -> Option<ExecutionContext> {
+        self.execution_contexts.pop()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#running-execution-context>,
+    /// `None` if no code runs.
+    #[must_use]
+    pub fn running_execution_context(&self) -> Option<&ExecutionContext> {
+        self.execution_contexts.last()
+    }
+
+    pub fn running_execution_context_mut(&mut self) -> Option<&mut ExecutionContext> {
+        self.execution_contexts.last_mut()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#current-realm>
+    #[must_use]
+    pub fn current_realm(&self) -> Option<&Realm> {
+        self.running_execution_context().map(|execution_context| &execution_context.realm)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#active-function-object>,
+    /// `None` outside of functions.
+    #[must_use]
+    pub fn active_function_object(&self) -> Option<&Object> {
+        self.running_execution_context().and_then(|execution_context| execution_context.function.as_ref())
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Context")
+            .field("call_depth", &self.call_depth)
+            .field("execution_contexts", &self.execution_contexts)
+            .field("memory_limits", &self.memory_limits)
+            .field("fuel", &self.fuel)
+            .field("pending_jobs", &self.jobs.len())
+            .finish_non_exhaustive()
+    }
+}
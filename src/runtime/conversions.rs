@@ -0,0 +1,314 @@
+//! <https://262.ecma-international.org/14.0/#sec-type-conversion>
+
+use super::{
+    BigIntOps,
+    Context,
+    ErrorKind,
+    Intrinsics,
+    JsError,
+    JsString,
+    Number,
+    Object,
+    ObjectKind,
+    PropertyKey,
+    Realm,
+    Symbol,
+    Value,
+    WellKnownSymbol,
+};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+/// A type [`Value::to_primitive`] prefers to convert an object to; an
+/// absent one is `default`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PreferredType {
+    String,
+    Number,
+}
+
+impl Value {
+    /// <https://262.ecma-international.org/14.0/#sec-toprimitive>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object cannot be
+    /// converted, or with an exception its conversion method throws.
+    pub fn to_primitive(&self, context: &mut Context, preferred_type: Option<PreferredType>) -> Result<Self, JsError> {
+        let Self::Object(object) = self else {
+            return Ok(self.clone());
+        };
+        let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::ToPrimitive));
+        if let Some(exotic_to_primitive) = object.get_method(context, &key)? {
+            let hint = match preferred_type {
+                None => "default",
+                Some(PreferredType::String) => "string",
+                Some(PreferredType::Number) => "number",
+            };
+            let result = exotic_to_primitive.call(context, self, &[Self::from(hint)])?;
+            if result.is_object() {
+                return Err(JsError::type_error("Symbol.toPrimitive method returned an object"));
+            }
+            return Ok(result);
+        }
+        ordinary_to_primitive(context, object, preferred_type.unwrap_or(PreferredType::Number))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-toboolean>
+    #[must_use]
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            Self::Undefined | Self::Null => false,
+            Self::Boolean(value) => *value,
+            Self::Number(value) => !(*value == 0.0 || value.is_nan()),
+            Self::String(value) => !value.is_empty(),
+            Self::BigInt(value) => *value != BigInt::ZERO,
+            Self::Symbol(_) | Self::Object(_) => true,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-tonumeric>, giving
+    /// either a Number or a `BigInt`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception [`Self::to_primitive`] throws,
+    /// or with a `TypeError` for a Symbol.
+    pub fn to_numeric(&self, context: &mut Context) -> Result<Self, JsError> {
+        let primitive = self.to_primitive(context, Some(PreferredType::Number))?;
+        if let Self::BigInt(_) = primitive {
+            return Ok(primitive);
+        }
+        primitive.to_number(context).map(Self::Number)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-tonumber>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` for a Symbol and a `BigInt`, or
+    /// with an exception [`Self::to_primitive`] throws.
+    pub fn to_number(&self, context: &mut Context) -> Result<f64, JsError> {
+        Ok(match self {
+            Self::Undefined => f64::NAN,
+            Self::Null => 0.0,
+            Self::Boolean(value) => f64::from(u8::from(*value)),
+            Self::Number(value) => *value,
+            Self::String(value) => string_to_number(value),
+            Self::Symbol(_) => return Err(JsError::type_error("cannot convert a Symbol to a number")),
+            Self::BigInt(_) => return Err(JsError::type_error("cannot convert a BigInt to a number")),
+            Self::Object(_) => return self.to_primitive(context, Some(PreferredType::Number))?.to_number(context),
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-tobigint>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` for values other than Booleans,
+    /// `BigInt`s and Strings, with a `SyntaxError` for a String that is not
+    /// an integer, or with an exception [`Self::to_primitive`] throws.
+    pub fn to_bigint(&self, context: &mut Context) -> Result<BigInt, JsError> {
+        match self.to_primitive(context, Some(PreferredType::Number))? {
+            Self::Boolean(value) => Ok(BigInt::from(u8::from(value))),
+            Self::BigInt(value) => Ok(value),
+            Self::String(value) => string_to_bigint(&value).ok_or_else(|| {
+                JsError::new(ErrorKind::SyntaxError, format!("cannot convert {value:?} to a BigInt"))
+            }),
+            Self::Number(_) => Err(JsError::type_error("cannot convert a Number to a BigInt")),
+            Self::Symbol(_) => Err(JsError::type_error("cannot convert a Symbol to a BigInt")),
+            _ => Err(JsError::type_error("cannot convert undefined or null to a BigInt")),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-toint32>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` like [`Self::to_number`].
+    pub fn to_int32(&self, context: &mut Context) -> Result<i32, JsError> {
+        self.to_number(context).map(Number::to_int32)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-touint32>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` like [`Self::to_number`].
+    pub fn to_uint32(&self, context: &mut Context) -> Result<u32, JsError> {
+        self.to_number(context).map(Number::to_uint32)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-tostring>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` for a Symbol, or with an
+    /// exception [`Self::to_primitive`] throws.
+    pub fn to_string(&self, context: &mut Context) -> Result<JsString, JsError> {
+        Ok(match self {
+            Self::Undefined => "undefined".into(),
+            Self::Null => "null".into(),
+            Self::Boolean(value) => value.to_string().into(),
+            Self::Number(value) => Number::to_string(*value, 10).into(),
+            Self::String(value) => value.clone(),
+            Self::Symbol(_) => return Err(JsError::type_error("cannot convert a Symbol to a string")),
+            Self::BigInt(value) => BigIntOps::to_string(value, 10).into(),
+            Self::Object(_) => return self.to_primitive(context, Some(PreferredType::String))?.to_string(context),
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-toobject>, wrapping
+    /// a primitive into an object with a prototype of the current realm.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` for **undefined** and **null**.
+    pub fn to_object(&self, context: &Context) -> Result<Object, JsError> {
+        let intrinsics = context.current_realm().map(Realm::intrinsics);
+        let wrapper = |prototype: fn(&Intrinsics) -> &Object, kind| {
+            Object::with_kind(intrinsics.map(|intrinsics| prototype(intrinsics).clone()), kind)
+        };
+        Ok(match self {
+            Self::Undefined | Self::Null => {
+                return Err(JsError::type_error(format!("cannot convert {} to an object", type_name(self))));
+            },
+            Self::Boolean(value) => wrapper(|intrinsics| &intrinsics.boolean_prototype, ObjectKind::Boolean(*value)),
+            Self::Number(value) => wrapper(|intrinsics| &intrinsics.number_prototype, ObjectKind::Number(*value)),
+            Self::String(value) => {
+                let prototype = intrinsics.map(|intrinsics| intrinsics.string_prototype.clone());
+                Object::new_string(prototype, value.clone())
+            },
+            Self::Symbol(value) => wrapper(|intrinsics| &intrinsics.symbol_prototype, ObjectKind::Symbol(value.clone())),
+            Self::BigInt(value) => wrapper(|intrinsics| &intrinsics.bigint_prototype, ObjectKind::BigInt(value.clone())),
+            Self::Object(object) => object.clone(),
+        })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-topropertykey>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception [`Self::to_primitive`] throws.
+    pub fn to_property_key(&self, context: &mut Context) -> Result<PropertyKey, JsError> {
+        match self.to_primitive(context, Some(PreferredType::String))? {
+            Self::Symbol(symbol) => Ok(PropertyKey::Symbol(symbol)),
+            key => key.to_string(context).map(PropertyKey::String),
+        }
+    }
+}
+
+/// `undefined` or `null` for an error message.
+const fn type_name(value: &Value) -> &'static str {
+    if matches!(value, Value::Undefined) { "undefined" } else { "null" }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-ordinarytoprimitive>
+fn ordinary_to_primitive(context: &mut Context, object: &Object, hint: PreferredType) -> Result<Value, JsError> {
+    let method_names = match hint {
+        PreferredType::String => ["toString", "valueOf"],
+        PreferredType::Number => ["valueOf", "toString"],
+    };
+    let this = Value::Object(object.clone());
+    for name in method_names {
+        let method = object.get(context, &PropertyKey::from(name), &this)?;
+        if let Value::Object(method) = method {
+            if method.is_callable() {
+                let result = method.call(context, &this, &[])?;
+                if !result.is_object() {
+                    return Ok(result);
+                }
+            }
+        }
+    }
+    Err(JsError::type_error("cannot convert an object to a primitive value"))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-stringtonumber>
+///
+/// Follows <https://262.ecma-international.org/14.0/#prod-StringNumericLiteral>:
+/// white space around a number is ignored, an empty string is 0, and
+/// unlike numeric literals, a string allows neither numeric separators nor
+/// a sign before `0x`, `0o` and `0b` literals.
+#[must_use]
+pub fn string_to_number(text: &JsString) -> f64 {
+    // Other code units, including unpaired surrogates, make the string
+    // invalid anyway.
+    let Some(text) = text.trim(is_white_space_or_line_terminator).to_std_string() else {
+        return f64::NAN;
+    };
+    if text.is_empty() {
+        return 0.0;
+    }
+    let radix = match text.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => return string_to_decimal(&text).unwrap_or(f64::NAN),
+    };
+    let digits = &text[2..];
+    if digits.is_empty() || !digits.chars().all(|digit| digit.is_digit(radix)) {
+        return f64::NAN;
+    }
+    // The MV is rounded to the nearest Number value.
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+        .and_then(|value| value.to_f64())
+        .unwrap_or(f64::NAN)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-stringtobigint>, `None`
+/// standing for **undefined**.
+///
+/// Follows <https://262.ecma-international.org/14.0/#prod-StringIntegerLiteral>:
+/// like [`string_to_number`] but without fractions, exponents and
+/// `Infinity`.
+#[must_use]
+pub fn string_to_bigint(text: &JsString) -> Option<BigInt> {
+    let text = text.trim(is_white_space_or_line_terminator).to_std_string()?;
+    let (digits, radix) = match text.get(..2) {
+        Some("0x" | "0X") => (&text[2..], 16),
+        Some("0o" | "0O") => (&text[2..], 8),
+        Some("0b" | "0B") => (&text[2..], 2),
+        _ if text.is_empty() => return Some(BigInt::ZERO),
+        _ => {
+            let unsigned = text.strip_prefix(['+', '-']).unwrap_or(&text);
+            if unsigned.is_empty() || !unsigned.bytes().all(|digit| digit.is_ascii_digit()) {
+                return None;
+            }
+            return BigInt::parse_bytes(text.as_bytes(), 10);
+        },
+    };
+    if digits.is_empty() || !digits.chars().all(|digit| digit.is_digit(radix)) {
+        return None;
+    }
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+}
+
+/// Parse <https://262.ecma-international.org/14.0/#prod-StrDecimalLiteral>.
+fn string_to_decimal(text: &str) -> Option<f64> {
+    let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+    if unsigned == "Infinity" {
+        return Some(if text.starts_with('-') { f64::NEG_INFINITY } else { f64::INFINITY });
+    }
+    let (mantissa, exponent) = unsigned.split_once(['e', 'E']).unwrap_or((unsigned, "0"));
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    let is_digits = |part: &str| part.bytes().all(|digit| digit.is_ascii_digit());
+    let is_valid = (!integer.is_empty() || !fraction.is_empty())
+        && !exponent.is_empty()
+        && is_digits(integer)
+        && is_digits(fraction)
+        && is_digits(exponent);
+    // What is left matches Rust syntax, which rounds to the nearest value.
+    is_valid.then(|| text.parse().ok()).flatten()
+}
+
+/// `WhiteSpace` and `LineTerminator` code points, which `StringToNumber`
+/// trims.
+const fn is_white_space_or_line_terminator(codepoint: char) -> bool {
+    matches!(
+        codepoint,
+        '\t' | '\u{b}' | '\u{c}' | ' ' | '\u{a0}' | '\u{feff}' | '\u{1680}' | '\u{2000}'..='\u{200a}'
+            | '\u{202f}' | '\u{205f}' | '\u{3000}' | '\n' | '\r' | '\u{2028}' | '\u{2029}'
+    )
+}
@@ -0,0 +1,788 @@
+//! <https://262.ecma-international.org/14.0/#sec-environment-records>
+
+use super::heap::{self, Trace, Tracer};
+use super::{Context, ErrorKind, JsError, Object, ObjectKind, PropertyDescriptor, PropertyKey, Symbol, Value, WellKnownSymbol};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::rc::Rc;
+
+/// A binding of a declarative Environment Record.
+#[derive(Clone, Debug)]
+struct Binding {
+    /// `None` until the binding is initialized, which makes accessing it
+    /// a `ReferenceError`, like for `let` before its declaration.
+    value: Option<Value>,
+    is_mutable: bool,
+    /// Whether the binding can be removed by
+    /// [`Environment::delete_binding`], like `var` created by `eval`.
+    is_deletable: bool,
+    /// Whether assigning to the immutable binding always throws rather than
+    /// only in strict mode code.
+    is_strict: bool,
+}
+
+type Bindings = RefCell<HashMap<String, Binding>>;
+
+/// `[[ThisBindingStatus]]` and `[[ThisValue]]` of a function Environment
+/// Record.
+#[derive(Clone, Debug)]
+enum ThisBinding {
+    /// An arrow function uses `this` of the enclosing code.
+    Lexical,
+    /// A derived constructor before `super()` returns.
+    Uninitialized,
+    Initialized(Value),
+}
+
+enum EnvironmentKind {
+    /// <https://262.ecma-international.org/14.0/#sec-declarative-environment-records>
+    Declarative(Bindings),
+    /// <https://262.ecma-international.org/14.0/#sec-function-environment-records>
+    Function {
+        bindings: Bindings,
+        this: RefCell<ThisBinding>,
+        function_object: Object,
+        /// **undefined** for a `[[Call]]`.
+        new_target: Value,
+    },
+    /// <https://262.ecma-international.org/14.0/#sec-object-environment-records>
+    Object { binding_object: Object, is_with_environment: bool },
+    /// <https://262.ecma-international.org/14.0/#sec-global-environment-records>
+    Global {
+        /// `[[ObjectRecord]]` bindings, being properties of the global
+        /// object.
+        object_record: Object,
+        global_this: Object,
+        /// `[[DeclarativeRecord]]` bindings created by top-level `let`,
+        /// `const` and `class` declarations.
+        declarative_record: Bindings,
+        /// `[[VarNames]]`
+        var_names: RefCell<HashSet<String>>,
+    },
+    /// <https://262.ecma-international.org/14.0/#sec-module-environment-records>
+    Module {
+        bindings: Bindings,
+        /// Indirect bindings created by `CreateImportBinding`, each being
+        /// a name in another module environment.
+        imports: RefCell<HashMap<String, (Environment, String)>>,
+    },
+}
+
+struct EnvironmentRecord {
+    kind: EnvironmentKind,
+    /// `[[OuterEnv]]`, `None` for a global one.
+    outer: Option<Environment>,
+}
+
+/// An Environment Record binding identifiers of a scope to values, with
+/// a reference to the enclosing one; clones refer to the same record.
+///
+/// Names are looked up from the innermost environment outwards:
+///
+/// ```
+/// use embedded_ecmascript::runtime::{Context, Environment, Value};
+///
+/// let mut context = Context::new();
+/// let outer = Environment::new_declarative(None);
+/// outer.create_mutable_binding(&mut context, "a", false).unwrap();
+/// outer.initialize_binding(&mut context, "a", Value::from(1)).unwrap();
+/// let inner = Environment::new_declarative(Some(outer.clone()));
+/// inner.create_immutable_binding("b", true);
+///
+/// assert_eq!(inner.resolve(&mut context, "a"), Ok(Some(outer)));
+/// let uninitialized = inner.get_binding_value(&mut context, "b", true);
+/// assert!(uninitialized.is_err());
+/// ```
+#[derive(Clone)]
+pub struct Environment(Rc<EnvironmentRecord>);
+
+impl Environment {
+    fn new(kind: EnvironmentKind, outer: Option<Self>) -> Self {
+        let record = Rc::new(EnvironmentRecord { kind, outer });
+        heap::register(Rc::downgrade(&record) as _);
+        Self(record)
+    }
+
+    /// Identity of the record in the heap.
+    pub(crate) fn address(&self) -> usize {
+        Rc::as_ptr(&self.0).cast::<()>() as usize
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newdeclarativeenvironment>
+    #[must_use]
+    pub fn new_declarative(outer: Option<Self>) -> Self {
+        Self::new(EnvironmentKind::Declarative(Bindings::default()), outer)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newfunctionenvironment>
+    ///
+    /// `this` is lexical, taken from the enclosing code, if `lexical_this`,
+    /// as for arrow functions.
+    #[must_use]
+    pub fn new_function(function_object: Object, new_target: Value, lexical_this: bool, outer: Self) -> Self {
+        let this = if lexical_this { ThisBinding::Lexical } else { ThisBinding::Uninitialized };
+        let kind = EnvironmentKind::Function {
+            bindings: Bindings::default(),
+            this: RefCell::new(this),
+            function_object,
+            new_target,
+        };
+        Self::new(kind, Some(outer))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newobjectenvironment>
+    #[must_use]
+    pub fn new_object(binding_object: Object, is_with_environment: bool, outer: Option<Self>) -> Self {
+        Self::new(EnvironmentKind::Object { binding_object, is_with_environment }, outer)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newglobalenvironment>
+    #[must_use]
+    pub fn new_global(global_object: Object, this_value: Object) -> Self {
+        let kind = EnvironmentKind::Global {
+            object_record: global_object,
+            global_this: this_value,
+            declarative_record: Bindings::default(),
+            var_names: RefCell::default(),
+        };
+        Self::new(kind, None)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-newmoduleenvironment>
+    #[must_use]
+    pub fn new_module(outer: Self) -> Self {
+        let kind = EnvironmentKind::Module { bindings: Bindings::default(), imports: RefCell::default() };
+        Self::new(kind, Some(outer))
+    }
+
+    /// `[[OuterEnv]]`
+    #[must_use]
+    pub fn outer(&self) -> Option<&Self> {
+        self.0.outer.as_ref()
+    }
+
+    /// Bindings of a declarative record or of a declarative part of
+    /// a function or a global one.
+    fn bindings(&self) -> Option<&Bindings> {
+        match &self.0.kind {
+            EnvironmentKind::Declarative(bindings)
+            | EnvironmentKind::Function { bindings, .. }
+            | EnvironmentKind::Module { bindings, .. } => Some(bindings),
+            EnvironmentKind::Global { declarative_record, .. } => Some(declarative_record),
+            EnvironmentKind::Object { .. } => None,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getidentifierreference>:
+    /// the innermost environment from this one outwards that has
+    /// a binding of `name`, `None` if the reference is unresolvable.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a `with` object throws while its
+    /// properties are looked up.
+    pub fn resolve(&self, context: &mut Context, name: &str) -> Result<Option<Self>, JsError> {
+        let mut environment = Some(self);
+        while let Some(current) = environment {
+            if current.has_binding(context, name)? {
+                return Ok(Some(current.clone()));
+            }
+            environment = current.outer();
+        }
+        Ok(None)
+    }
+
+    /// `HasBinding`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the binding object or a getter
+    /// of `Symbol.unscopables` of a `with` object throws.
+    pub fn has_binding(&self, context: &mut Context, name: &str) -> Result<bool, JsError> {
+        match &self.0.kind {
+            EnvironmentKind::Object { binding_object, is_with_environment } => {
+                let key = PropertyKey::from(name);
+                if !binding_object.try_has_property(context, &key)? {
+                    return Ok(false);
+                }
+                if !is_with_environment {
+                    return Ok(true);
+                }
+                let this = Value::Object(binding_object.clone());
+                let unscopables_key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::Unscopables));
+                if let Value::Object(unscopables) = binding_object.get(context, &unscopables_key, &this)? {
+                    let blocked = unscopables.get(context, &key, &Value::Object(unscopables.clone()))?;
+                    return Ok(!blocked.to_boolean());
+                }
+                Ok(true)
+            },
+            EnvironmentKind::Global { object_record, declarative_record, .. } => {
+                if declarative_record.borrow().contains_key(name) {
+                    return Ok(true);
+                }
+                object_record.try_has_property(context, &PropertyKey::from(name))
+            },
+            EnvironmentKind::Module { bindings, imports } => {
+                Ok(bindings.borrow().contains_key(name) || imports.borrow().contains_key(name))
+            },
+            _ => Ok(self.bindings().is_some_and(|bindings| bindings.borrow().contains_key(name))),
+        }
+    }
+
+    /// `CreateMutableBinding` of an uninitialized binding, or of a property
+    /// of a binding object, which is configurable if `is_deletable`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the binding object does not
+    /// accept the property, or with an exception it throws.
+    pub fn create_mutable_binding(&self, context: &mut Context, name: &str, is_deletable: bool) -> Result<(), JsError> {
+        if let EnvironmentKind::Object { binding_object, .. } = &self.0.kind {
+            let descriptor = PropertyDescriptor::data(Value::Undefined, true, true, is_deletable);
+            if !binding_object.try_define_own_property(context, PropertyKey::from(name), descriptor)? {
+                return Err(JsError::type_error(format!("cannot define {name}")));
+            }
+            return Ok(());
+        }
+        let binding = Binding { value: None, is_mutable: true, is_deletable, is_strict: false };
+        self.declare(name, binding);
+        Ok(())
+    }
+
+    /// `CreateImmutableBinding` of an uninitialized binding, which throws on
+    /// assignment regardless of strict mode if `is_strict`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic for an object Environment Record, which has no immutable
+    /// bindings.
+    pub fn create_immutable_binding(&self, name: &str, is_strict: bool) {
+        self.declare(name, Binding { value: None, is_mutable: false, is_deletable: false, is_strict });
+    }
+
+    fn declare(&self, name: &str, binding: Binding) {
+        let bindings = self.bindings().expect("an object Environment Record has no declarative bindings");
+        bindings.borrow_mut().insert(name.to_owned(), binding);
+    }
+
+    /// `InitializeBinding`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a setter of the binding object
+    /// throws.
+    pub fn initialize_binding(&self, context: &mut Context, name: &str, value: Value) -> Result<(), JsError> {
+        if let Some(bindings) = self.bindings() {
+            if let Some(binding) = bindings.borrow_mut().get_mut(name) {
+                binding.value = Some(value);
+                return Ok(());
+            }
+        }
+        match &self.0.kind {
+            EnvironmentKind::Object { .. } => self.set_mutable_binding(context, name, value, false),
+            EnvironmentKind::Global { object_record, .. } => {
+                Self::set_property(context, object_record, name, value, false)
+            },
+            _ => unreachable!("{name} is initialized before it is created"),
+        }
+    }
+
+    /// `SetMutableBinding`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `ReferenceError` for an uninitialized
+    /// binding, with a `TypeError` for an immutable binding in strict mode
+    /// code, or with an exception a setter of the binding object throws.
+    pub fn set_mutable_binding(&self, context: &mut Context, name: &str, value: Value, is_strict: bool) -> Result<(), JsError> {
+        if let EnvironmentKind::Module { imports, .. } = &self.0.kind {
+            if imports.borrow().contains_key(name) {
+                return Err(JsError::type_error(format!("assignment to imported binding {name}")));
+            }
+        }
+        if let Some(bindings) = self.bindings() {
+            let mut bindings = bindings.borrow_mut();
+            if let Some(binding) = bindings.get_mut(name) {
+                let is_strict = is_strict || binding.is_strict;
+                if binding.value.is_none() {
+                    return Err(uninitialized(name));
+                }
+                if binding.is_mutable {
+                    binding.value = Some(value);
+                } else if is_strict {
+                    return Err(JsError::type_error(format!("assignment to constant variable {name}")));
+                }
+                return Ok(());
+            }
+            if let EnvironmentKind::Declarative(_) | EnvironmentKind::Function { .. } = &self.0.kind {
+                // An environment created for `eval` can lose the binding,
+                // which recreates it.
+                drop(bindings);
+                if is_strict {
+                    return Err(not_defined(name));
+                }
+                self.create_mutable_binding(context, name, true)?;
+                return self.initialize_binding(context, name, value);
+            }
+        }
+        let (EnvironmentKind::Object { binding_object: object, .. } | EnvironmentKind::Global { object_record: object, .. }) =
+            &self.0.kind
+        else {
+            unreachable!("declarative bindings are handled above");
+        };
+        if is_strict && !object.try_has_property(context, &PropertyKey::from(name))? {
+            return Err(not_defined(name));
+        }
+        Self::set_property(context, object, name, value, is_strict)
+    }
+
+    /// `Set(bindingObject, N, V, S)`, throwing on failure if `is_strict`.
+    fn set_property(context: &mut Context, object: &Object, name: &str, value: Value, is_strict: bool) -> Result<(), JsError> {
+        let receiver = Value::Object(object.clone());
+        if !object.set(context, PropertyKey::from(name), value, &receiver)? && is_strict {
+            return Err(JsError::type_error(format!("cannot assign to read-only property {name}")));
+        }
+        Ok(())
+    }
+
+    /// `GetBindingValue`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `ReferenceError` for an uninitialized
+    /// binding or a property of the binding object gone in strict mode
+    /// code, or with an exception a getter of the binding object throws.
+    pub fn get_binding_value(&self, context: &mut Context, name: &str, is_strict: bool) -> Result<Value, JsError> {
+        if let EnvironmentKind::Module { .. } = &self.0.kind {
+            return self.module_binding_value(name);
+        }
+        if let Some(bindings) = self.bindings() {
+            if let Some(binding) = bindings.borrow().get(name) {
+                return binding.value.clone().ok_or_else(|| uninitialized(name));
+            }
+        }
+        let object = match &self.0.kind {
+            EnvironmentKind::Object { binding_object, .. } => binding_object,
+            EnvironmentKind::Global { object_record, .. } => object_record,
+            _ => return Err(not_defined(name)),
+        };
+        let key = PropertyKey::from(name);
+        if !object.try_has_property(context, &key)? {
+            return if is_strict { Err(not_defined(name)) } else { Ok(Value::Undefined) };
+        }
+        object.get(context, &key, &Value::Object(object.clone()))
+    }
+
+    /// `GetBindingValue` of a module Environment Record, which follows
+    /// import bindings to the environments of other modules and never runs
+    /// code.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `ReferenceError` for an uninitialized or
+    /// a missing binding.
+    pub fn module_binding_value(&self, name: &str) -> Result<Value, JsError> {
+        if let EnvironmentKind::Module { imports, .. } = &self.0.kind {
+            let target = imports.borrow().get(name).cloned();
+            if let Some((environment, target_name)) = target {
+                return environment.module_binding_value(&target_name);
+            }
+        }
+        let bindings = self.bindings().and_then(|bindings| bindings.borrow().get(name).map(|binding| binding.value.clone()));
+        match bindings {
+            Some(Some(value)) => Ok(value),
+            Some(None) => Err(uninitialized(name)),
+            None => Err(not_defined(name)),
+        }
+    }
+
+    /// The value of an initialized binding of a declarative Environment
+    /// Record, which never runs code, like a getter made by
+    /// <https://262.ecma-international.org/14.0/#sec-makearggetter> reads it.
+    #[must_use]
+    pub fn binding_value(&self, name: &str) -> Option<Value> {
+        self.bindings()?.borrow().get(name)?.value.clone()
+    }
+
+    /// Assign to an initialized mutable binding of a declarative
+    /// Environment Record, like a setter made by
+    /// <https://262.ecma-international.org/14.0/#sec-makeargsetter> does.
+    pub fn set_binding_value(&self, name: &str, value: Value) {
+        let Some(bindings) = self.bindings() else {
+            return;
+        };
+        if let Some(binding) = bindings.borrow_mut().get_mut(name) {
+            if binding.is_mutable && binding.value.is_some() {
+                binding.value = Some(value);
+            }
+        }
+    }
+
+    /// `CreateImportBinding` of a module Environment Record: bind `name` to
+    /// `target_name` in the environment of another module, so the binding
+    /// always has the current value of the target one.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the record is not a module one.
+    pub fn create_import_binding(&self, name: &str, target: Self, target_name: &str) {
+        let EnvironmentKind::Module { imports, .. } = &self.0.kind else {
+            panic!("not a module Environment Record");
+        };
+        imports.borrow_mut().insert(name.to_owned(), (target, target_name.to_owned()));
+    }
+
+    /// `DeleteBinding`, returning whether the binding is gone.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the binding object throws.
+    pub fn delete_binding(&self, context: &mut Context, name: &str) -> Result<bool, JsError> {
+        if let Some(bindings) = self.bindings() {
+            let mut bindings = bindings.borrow_mut();
+            if let Some(binding) = bindings.get(name) {
+                if !binding.is_deletable {
+                    return Ok(false);
+                }
+                bindings.remove(name);
+                return Ok(true);
+            }
+        }
+        match &self.0.kind {
+            EnvironmentKind::Object { binding_object, .. } => binding_object.try_delete(context, &PropertyKey::from(name)),
+            EnvironmentKind::Global { object_record, var_names, .. } => {
+                let key = PropertyKey::from(name);
+                if object_record.try_get_own_property(context, &key)?.is_none() {
+                    return Ok(true);
+                }
+                let is_deleted = object_record.try_delete(context, &key)?;
+                if is_deleted {
+                    var_names.borrow_mut().remove(name);
+                }
+                Ok(is_deleted)
+            },
+            _ => Ok(true),
+        }
+    }
+
+    /// `HasThisBinding`
+    #[must_use]
+    pub fn has_this_binding(&self) -> bool {
+        match &self.0.kind {
+            EnvironmentKind::Function { this, .. } => !matches!(*this.borrow(), ThisBinding::Lexical),
+            EnvironmentKind::Global { .. } | EnvironmentKind::Module { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// `HasSuperBinding`
+    #[must_use]
+    pub fn has_super_binding(&self) -> bool {
+        match &self.0.kind {
+            EnvironmentKind::Function { this, function_object, .. } => {
+                !matches!(*this.borrow(), ThisBinding::Lexical)
+                    && matches!(function_object.kind(), ObjectKind::Function(function) if function.home_object().is_some())
+            },
+            _ => false,
+        }
+    }
+
+    /// `GetSuperBase` of an environment that [`Self::has_super_binding`]:
+    /// the prototype of the home object of its function, **undefined**
+    /// without one.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a proxy home object throws.
+    pub fn get_super_base(&self, context: &mut Context) -> Result<Value, JsError> {
+        let home_object = match &self.0.kind {
+            EnvironmentKind::Function { function_object, .. } => match function_object.kind() {
+                ObjectKind::Function(function) => function.home_object().cloned(),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(home_object) = home_object else {
+            return Ok(Value::Undefined);
+        };
+        Ok(home_object.try_get_prototype_of(context)?.map_or(Value::Null, Value::Object))
+    }
+
+    /// `WithBaseObject`: the binding object of a `with` statement, which
+    /// becomes `this` of functions called by name.
+    #[must_use]
+    pub fn with_base_object(&self) -> Option<Object> {
+        match &self.0.kind {
+            EnvironmentKind::Object { binding_object, is_with_environment: true } => Some(binding_object.clone()),
+            _ => None,
+        }
+    }
+
+    /// `BindThisValue` of a function Environment Record.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `ReferenceError` if `this` is already
+    /// initialized.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the record is not a function one with its own `this`.
+    pub fn bind_this_value(&self, value: Value) -> Result<(), JsError> {
+        let EnvironmentKind::Function { this, .. } = &self.0.kind else {
+            panic!("only function Environment Records bind this");
+        };
+        let mut this = this.borrow_mut();
+        match *this {
+            ThisBinding::Lexical => panic!("an arrow function has no own this"),
+            ThisBinding::Initialized(_) => Err(JsError::new(ErrorKind::ReferenceError, "this is already initialized")),
+            ThisBinding::Uninitialized => {
+                *this = ThisBinding::Initialized(value);
+                Ok(())
+            },
+        }
+    }
+
+    /// `GetThisBinding` of an environment that [`Self::has_this_binding`],
+    /// which is **undefined** for a module one.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `ReferenceError` if `this` is not
+    /// initialized yet.
+    pub fn get_this_binding(&self) -> Result<Value, JsError> {
+        match &self.0.kind {
+            EnvironmentKind::Function { this, .. } => match &*this.borrow() {
+                ThisBinding::Initialized(value) => Ok(value.clone()),
+                _ => Err(JsError::new(ErrorKind::ReferenceError, "this is not initialized")),
+            },
+            EnvironmentKind::Global { global_this, .. } => Ok(Value::Object(global_this.clone())),
+            _ => Ok(Value::Undefined),
+        }
+    }
+
+    /// `[[FunctionObject]]` of a function Environment Record.
+    #[must_use]
+    pub fn function_object(&self) -> Option<&Object> {
+        match &self.0.kind {
+            EnvironmentKind::Function { function_object, .. } => Some(function_object),
+            _ => None,
+        }
+    }
+
+    /// `[[NewTarget]]` of a function Environment Record.
+    #[must_use]
+    pub fn new_target(&self) -> Option<&Value> {
+        match &self.0.kind {
+            EnvironmentKind::Function { new_target, .. } => Some(new_target),
+            _ => None,
+        }
+    }
+
+    /// `[[ObjectRecord]]` of a global Environment Record.
+    fn global_object(&self) -> &Object {
+        let EnvironmentKind::Global { object_record, .. } = &self.0.kind else {
+            panic!("not a global Environment Record");
+        };
+        object_record
+    }
+
+    /// `HasVarDeclaration` of a global Environment Record.
+    #[must_use]
+    pub fn has_var_declaration(&self, name: &str) -> bool {
+        match &self.0.kind {
+            EnvironmentKind::Global { var_names, .. } => var_names.borrow().contains(name),
+            _ => false,
+        }
+    }
+
+    /// `HasLexicalDeclaration` of a global Environment Record.
+    #[must_use]
+    pub fn has_lexical_declaration(&self, name: &str) -> bool {
+        match &self.0.kind {
+            EnvironmentKind::Global { declarative_record, .. } => declarative_record.borrow().contains_key(name),
+            _ => false,
+        }
+    }
+
+    /// `HasRestrictedGlobalProperty`: whether the global object has
+    /// a non-configurable property, like `undefined`, that a lexical
+    /// declaration cannot shadow.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the global object throws.
+    pub fn has_restricted_global_property(&self, context: &mut Context, name: &str) -> Result<bool, JsError> {
+        let property = self.global_object().try_get_own_property(context, &PropertyKey::from(name))?;
+        Ok(property.is_some_and(|property| property.configurable == Some(false)))
+    }
+
+    /// `CanDeclareGlobalVar`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the global object throws.
+    pub fn can_declare_global_var(&self, context: &mut Context, name: &str) -> Result<bool, JsError> {
+        let global_object = self.global_object();
+        if global_object.try_get_own_property(context, &PropertyKey::from(name))?.is_some() {
+            return Ok(true);
+        }
+        global_object.try_is_extensible(context)
+    }
+
+    /// `CanDeclareGlobalFunction`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception the global object throws.
+    pub fn can_declare_global_function(&self, context: &mut Context, name: &str) -> Result<bool, JsError> {
+        let global_object = self.global_object();
+        Ok(match global_object.try_get_own_property(context, &PropertyKey::from(name))? {
+            None => global_object.try_is_extensible(context)?,
+            Some(property) if property.configurable == Some(true) => true,
+            Some(property) => {
+                property.is_data_descriptor() && property.writable == Some(true) && property.enumerable == Some(true)
+            },
+        })
+    }
+
+    /// `CreateGlobalVarBinding`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the global object does not
+    /// accept the property, or with an exception it throws.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the record is not a global one.
+    pub fn create_global_var_binding(&self, context: &mut Context, name: &str, is_deletable: bool) -> Result<(), JsError> {
+        let EnvironmentKind::Global { object_record, var_names, .. } = &self.0.kind else {
+            panic!("not a global Environment Record");
+        };
+        let key = PropertyKey::from(name);
+        if object_record.try_get_own_property(context, &key)?.is_none() && object_record.try_is_extensible(context)? {
+            let object_environment = Self::new_object(object_record.clone(), false, None);
+            object_environment.create_mutable_binding(context, name, is_deletable)?;
+        }
+        var_names.borrow_mut().insert(name.to_owned());
+        Ok(())
+    }
+
+    /// `CreateGlobalFunctionBinding`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the global object does not
+    /// accept the property, or with an exception it throws.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the record is not a global one.
+    pub fn create_global_function_binding(
+        &self,
+        context: &mut Context,
+        name: &str,
+        value: Value,
+        is_deletable: bool,
+    ) -> Result<(), JsError> {
+        let EnvironmentKind::Global { object_record, var_names, .. } = &self.0.kind else {
+            panic!("not a global Environment Record");
+        };
+        let key = PropertyKey::from(name);
+        let is_replaced = object_record
+            .try_get_own_property(context, &key)?
+            .is_none_or(|property| property.configurable == Some(true));
+        let descriptor = if is_replaced {
+            PropertyDescriptor::data(value.clone(), true, true, is_deletable)
+        } else {
+            PropertyDescriptor { value: Some(value.clone()), ..PropertyDescriptor::default() }
+        };
+        if !object_record.try_define_own_property(context, key, descriptor)? {
+            return Err(JsError::type_error(format!("cannot define {name}")));
+        }
+        Self::set_property(context, object_record, name, value, false)?;
+        var_names.borrow_mut().insert(name.to_owned());
+        Ok(())
+    }
+}
+
+fn uninitialized(name: &str) -> JsError {
+    JsError::new(ErrorKind::ReferenceError, format!("cannot access {name} before initialization"))
+}
+
+fn not_defined(name: &str) -> JsError {
+    JsError::new(ErrorKind::ReferenceError, format!("{name} is not defined"))
+}
+
+impl Trace for EnvironmentRecord {
+    fn trace(&self, tracer: &mut Tracer) {
+        if let Some(outer) = &self.outer {
+            tracer.environment(outer);
+        }
+        let trace_bindings = |tracer: &mut Tracer, bindings: &Bindings| {
+            for binding in bindings.borrow().values() {
+                if let Some(value) = &binding.value {
+                    tracer.value(value);
+                }
+            }
+        };
+        match &self.kind {
+            EnvironmentKind::Declarative(bindings) => trace_bindings(tracer, bindings),
+            EnvironmentKind::Function { bindings, this, function_object, new_target } => {
+                trace_bindings(tracer, bindings);
+                if let ThisBinding::Initialized(this) = &*this.borrow() {
+                    tracer.value(this);
+                }
+                tracer.object(function_object);
+                tracer.value(new_target);
+            },
+            EnvironmentKind::Object { binding_object, .. } => tracer.object(binding_object),
+            EnvironmentKind::Global { object_record, global_this, declarative_record, .. } => {
+                tracer.object(object_record);
+                tracer.object(global_this);
+                trace_bindings(tracer, declarative_record);
+            },
+            EnvironmentKind::Module { bindings, imports } => {
+                trace_bindings(tracer, bindings);
+                for (environment, _) in imports.borrow().values() {
+                    tracer.environment(environment);
+                }
+            },
+        }
+    }
+
+    /// Clear bindings; references to objects outside of them are dropped
+    /// once the objects are cleared too.
+    fn clear(&self) {
+        match &self.kind {
+            EnvironmentKind::Declarative(bindings) | EnvironmentKind::Global { declarative_record: bindings, .. } => {
+                bindings.borrow_mut().clear();
+            },
+            EnvironmentKind::Function { bindings, this, .. } => {
+                bindings.borrow_mut().clear();
+                this.replace(ThisBinding::Uninitialized);
+            },
+            EnvironmentKind::Object { .. } => {},
+            EnvironmentKind::Module { bindings, imports } => {
+                bindings.borrow_mut().clear();
+                imports.borrow_mut().clear();
+            },
+        }
+    }
+}
+
+impl PartialEq for Environment {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Environment {}
+
+impl fmt::Debug for Environment {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Environment({:p})", Rc::as_ptr(&self.0))
+    }
+}
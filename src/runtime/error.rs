@@ -0,0 +1,154 @@
+//! <https://262.ecma-international.org/14.0/#sec-completion-record-specification-type>
+//! of throw completions.
+
+use super::{BigIntOps, Context, Number, Object, ObjectKind, PropertyKey, Value};
+use std::fmt;
+use std::ops::Range;
+
+/// A kind of a [`JsError`], named after native error types where one fits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// An unresolvable reference is read, like an undefined variable.
+    ReferenceError,
+    /// A value is outside of an allowed range, like a call stack depth.
+    RangeError,
+    /// Code takes more memory than
+    /// [`MemoryLimits`](super::MemoryLimits) allow; scripts catch it as
+    /// a `RangeError`.
+    MemoryLimit,
+    /// Text parsed at run time is malformed, like `BigInt("1.5")`.
+    SyntaxError,
+    /// An operation is applied to a value of a wrong type, like `a in 1`.
+    TypeError,
+    /// A value thrown by a `throw` statement.
+    Thrown,
+    /// The code uses a feature the runtime does not implement yet.
+    Unsupported,
+    /// The code runs longer than its fuel allows, as set by
+    /// [`Context::set_fuel`](super::Context::set_fuel).
+    OutOfFuel,
+    /// The interrupt handler of the host stopped the code, as set by
+    /// [`Context::set_interrupt_handler`](super::Context::set_interrupt_handler).
+    Interrupted,
+}
+
+impl ErrorKind {
+    /// Whether `try` statements catch errors of the kind; others stop the
+    /// code as written, skipping `finally` blocks as well.
+    #[must_use]
+    pub const fn is_catchable(self) -> bool {
+        !matches!(self, Self::Unsupported | Self::OutOfFuel | Self::Interrupted)
+    }
+}
+
+/// An exception thrown by ECMAScript code or an abstract operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Byte offsets of the innermost expression that threw, if the error
+    /// comes from evaluated code.
+    pub range: Option<Range<usize>>,
+    /// The value ECMAScript code threw, `None` for an error of the runtime
+    /// that code has not caught yet.
+    pub value: Option<Value>,
+    /// Names of functions the error propagated out of, innermost first.
+    pub stack: Vec<String>,
+}
+
+impl JsError {
+    #[must_use]
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into(), range: None, value: None, stack: Vec::new() }
+    }
+
+    /// A throw completion of a value thrown by ECMAScript code, described
+    /// without running any code.
+    #[must_use]
+    pub fn thrown(value: Value) -> Self {
+        let message = match &value {
+            Value::Undefined => "undefined".to_owned(),
+            Value::Null => "null".to_owned(),
+            Value::Boolean(value) => value.to_string(),
+            Value::Number(value) => Number::to_string(*value, 10),
+            Value::String(value) => value.to_string(),
+            Value::Symbol(value) => value.descriptive_string(),
+            Value::BigInt(value) => BigIntOps::to_string(value, 10),
+            Value::Object(object) => describe_error(object).unwrap_or_else(|| "object".to_owned()),
+        };
+        Self { kind: ErrorKind::Thrown, message, range: None, value: Some(value), stack: Vec::new() }
+    }
+
+    #[must_use]
+    pub fn type_error(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::TypeError, message)
+    }
+
+    pub(crate) fn unsupported(what: &str) -> Self {
+        Self::new(ErrorKind::Unsupported, format!("{what} are not supported yet"))
+    }
+
+    /// The value ECMAScript code catches for the error: the value thrown
+    /// by ECMAScript code, or an Error object of the current realm
+    /// describing an error of the runtime.
+    #[must_use]
+    pub fn into_value(self, context: &Context) -> Value {
+        if let Some(value) = self.value {
+            return value;
+        }
+        let prototype = context.current_realm().map(|realm| realm.intrinsics().error_prototype_of(self.kind).clone());
+        Value::Object(Object::new_error(prototype, Some(self.message.into())))
+    }
+
+    /// Attribute the error to a place in source text unless it already has
+    /// a more precise one.
+    #[must_use]
+    pub fn at(mut self, range: &Range<usize>) -> Self {
+        self.range.get_or_insert_with(|| range.clone());
+        self
+    }
+
+    /// Record that the error propagated out of a function.
+    #[must_use]
+    pub fn through(mut self, function_name: String) -> Self {
+        self.stack.push(function_name);
+        self
+    }
+}
+
+/// `name: message` of an Error object like
+/// <https://262.ecma-international.org/14.0/#sec-error.prototype.tostring>
+/// does, reading only data properties so that no code runs.
+fn describe_error(object: &Object) -> Option<String> {
+    if !matches!(object.kind(), ObjectKind::Error) {
+        return None;
+    }
+    let property = |name: &str| {
+        let key = PropertyKey::from(name);
+        let mut current = Some(object.clone());
+        while let Some(object) = current {
+            if let Some(property) = object.get_own_property(&key) {
+                return match property.value {
+                    Some(Value::String(value)) => value.to_string(),
+                    _ => String::new(),
+                };
+            }
+            current = object.get_prototype_of();
+        }
+        String::new()
+    };
+    let (name, message) = (property("name"), property("message"));
+    Some(match (name.is_empty(), message.is_empty()) {
+        (true, _) => message,
+        (false, true) => name,
+        (false, false) => format!("{name}: {message}"),
+    })
+}
+
+impl fmt::Display for JsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for JsError {}
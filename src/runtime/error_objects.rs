@@ -0,0 +1,83 @@
+//! <https://262.ecma-international.org/14.0/#sec-error-objects>
+
+use super::{Context, JsError, JsString, Object, PropertyDescriptor, PropertyKey, Value};
+
+/// Create `%Error%` or a constructor of
+/// <https://262.ecma-international.org/14.0/#sec-nativeerror-object-structure>,
+/// linking it with its prototype.
+///
+/// `prototype` gets `constructor`, `name` and an empty `message`.
+pub(super) fn create_error_constructor(constructor_prototype: Object, prototype: &Object, name: &str) -> Object {
+    let fallback = prototype.clone();
+    let constructor = Object::native_constructor(Some(constructor_prototype), name, 1, move |context, arguments, new_target| {
+        construct_error(context, arguments, new_target, &fallback)
+    });
+    let define = |object: &Object, key: &str, value: Value, writable| {
+        let descriptor = PropertyDescriptor::data(value, writable, false, writable);
+        assert!(object.define_own_property(PropertyKey::from(key), descriptor), "a fresh object is extensible");
+    };
+    define(&constructor, "prototype", Value::Object(prototype.clone()), false);
+    define(prototype, "constructor", Value::Object(constructor.clone()), true);
+    define(prototype, "name", Value::from(name), true);
+    define(prototype, "message", Value::from(""), true);
+    constructor
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-error-message> and
+/// <https://262.ecma-international.org/14.0/#sec-nativeerror>.
+fn construct_error(
+    context: &mut Context,
+    arguments: &[Value],
+    new_target: Option<&Object>,
+    fallback: &Object,
+) -> Result<Object, JsError> {
+    // Called as a function, the constructor is `NewTarget`.
+    let new_target = new_target.cloned().or_else(|| context.active_function_object().cloned());
+    let prototype = match &new_target {
+        Some(new_target) => match new_target.get(context, &PropertyKey::from("prototype"), &Value::Object(new_target.clone()))? {
+            Value::Object(prototype) => prototype,
+            _ => fallback.clone(),
+        },
+        None => fallback.clone(),
+    };
+    let message = match arguments.first() {
+        None | Some(Value::Undefined) => None,
+        Some(message) => Some(message.to_string(context)?),
+    };
+    let error = Object::new_error(Some(prototype), message);
+    install_error_cause(context, &error, arguments.get(1).unwrap_or(&Value::Undefined))?;
+    Ok(error)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-installerrorcause>
+fn install_error_cause(context: &mut Context, error: &Object, options: &Value) -> Result<(), JsError> {
+    let Value::Object(options) = options else {
+        return Ok(());
+    };
+    let key = PropertyKey::from("cause");
+    if options.try_has_property(context, &key)? {
+        let cause = options.get(context, &key, &Value::Object(options.clone()))?;
+        error.create_non_enumerable_data_property(key, cause);
+    }
+    Ok(())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-error.prototype.tostring>
+pub(super) fn error_prototype_to_string(context: &mut Context, this: &Value, _: &[Value]) -> Result<Value, JsError> {
+    let Value::Object(object) = this else {
+        return Err(JsError::type_error("Error.prototype.toString called on a non-object"));
+    };
+    let mut get = |name: &str, default: &str| match object.get(context, &PropertyKey::from(name), this)? {
+        Value::Undefined => Ok(JsString::from(default)),
+        value => value.to_string(context),
+    };
+    let name = get("name", "Error")?;
+    let message = get("message", "")?;
+    Ok(Value::String(if name.is_empty() {
+        message
+    } else if message.is_empty() {
+        name
+    } else {
+        name.concat(&JsString::from(": ")).concat(&message)
+    }))
+}
@@ -0,0 +1,376 @@
+//! Reclamation of objects and environments that refer to each other.
+//!
+//! Objects and Environment Records are reference counted, so a cycle of
+//! them, like a function stored in a property of its own prototype or
+//! a closure kept in a variable of the environment it closes over, is
+//! never freed by counting alone. Each [`Context`](super::Context) owns
+//! a [`Heap`] that [`Heap::collect_garbage`] finds such cycles in with
+//! a mark-and-sweep pass and breaks them.
+//!
+//! A node belongs to the heap entered with [`Heap::enter`] when it is
+//! created, which is the heap of the engine running code; nodes created
+//! while no heap is entered are left to reference counting alone.
+//!
+//! Marking starts from roots: values the host keeps in [`Gc`] handles and
+//! [`HandleScope`]s, and nodes referred to more times than other nodes of
+//! the heap and handles account for. The latter are held by Rust code the
+//! heap does not trace, like a realm, an execution context, a variable of
+//! running code, a native function or a node of another heap.
+//!
+//! Nodes are separate reference counted allocations rather than slots of
+//! a per-realm arena addressed by indices. [`Object`] and [`Environment`]
+//! are handles that the host, native functions and the interpreter keep
+//! and pass around freely, and their methods reach the node with no heap
+//! at hand, like [`Object::get_own_property`] does; with index handles,
+//! every such access would go through the heap owning the slot, and
+//! a handle outliving its node would silently refer to a reused slot.
+//! Counting also frees most nodes as soon as they become unreachable,
+//! without pauses, and leaves only cycles to collections. What an arena
+//! would add, nodes allocated close to each other and a heap copied as
+//! a whole for a snapshot, nothing needs yet.
+
+use super::{Environment, Object, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+
+/// A node of the heap reporting references it holds to other nodes.
+pub trait Trace {
+    /// Report each node the node refers to, once per reference it holds.
+    fn trace(&self, tracer: &mut Tracer);
+
+    /// Drop all references to other nodes the node holds, as it is
+    /// unreachable.
+    fn clear(&self);
+}
+
+/// References to nodes reported by [`Trace::trace`], by node addresses.
+#[derive(Default)]
+pub struct Tracer {
+    edges: Vec<usize>,
+}
+
+impl Tracer {
+    pub fn object(&mut self, object: &Object) {
+        self.edges.push(object.address());
+    }
+
+    pub fn environment(&mut self, environment: &Environment) {
+        self.edges.push(environment.address());
+    }
+
+    pub fn value(&mut self, value: &Value) {
+        if let Value::Object(object) = value {
+            self.object(object);
+        }
+    }
+}
+
+/// Statistics of a heap.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct HeapStatistics {
+    /// Objects and environments alive after the collection.
+    pub live: usize,
+    /// Objects and environments the collection freed.
+    pub collected: usize,
+}
+
+/// Nodes of a heap that may still be alive.
+#[derive(Default)]
+struct HeapData {
+    nodes: Vec<Weak<dyn Trace>>,
+    /// How many nodes to register before forgetting dead ones.
+    prune_threshold: usize,
+    /// How many nodes the heap has got, including dead ones.
+    allocations: usize,
+    /// How many bytes strings and properties created while the heap was
+    /// entered have taken, including freed ones.
+    allocated_bytes: usize,
+    /// Addresses of nodes kept by handles, by handle identifiers.
+    roots: HashMap<usize, usize>,
+    next_root: usize,
+}
+
+/// Forget dead nodes once the heap has this many more registered.
+const MIN_PRUNE_THRESHOLD: usize = 1024;
+
+thread_local! {
+    /// The heap nodes created on the thread belong to.
+    static CURRENT: RefCell<Option<Heap>> = const { RefCell::new(None) };
+}
+
+/// Objects and environments created by code of an engine.
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+///
+/// let mut engine = Engine::new();
+/// let mut other = Engine::new();
+/// engine.evaluate_script("var a = {}; a.self = a; a = undefined;").unwrap();
+/// assert_eq!(other.collect_garbage().collected, 0);
+/// assert!(engine.collect_garbage().collected > 0);
+/// ```
+#[derive(Clone, Default)]
+pub struct Heap(Rc<RefCell<HeapData>>);
+
+impl Heap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make nodes created on the thread belong to the heap until the scope
+    /// ends, restoring the heap entered before.
+    #[must_use]
+    pub fn enter(&self) -> HeapScope {
+        HeapScope { previous: CURRENT.replace(Some(self.clone())) }
+    }
+
+    fn register(&self, node: Weak<dyn Trace>) {
+        let heap = &mut *self.0.borrow_mut();
+        heap.nodes.push(node);
+        heap.allocations += 1;
+        if heap.nodes.len() >= heap.prune_threshold {
+            heap.nodes.retain(|node| node.strong_count() > 0);
+            heap.prune_threshold = (heap.nodes.len() * 2).max(MIN_PRUNE_THRESHOLD);
+        }
+    }
+
+    /// How many objects and environments of the heap are alive, including
+    /// unreachable ones the next collection frees.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.0.borrow().nodes.iter().filter(|node| node.strong_count() > 0).count()
+    }
+
+    /// How many objects and environments the heap has got so far.
+    #[must_use]
+    pub fn allocations(&self) -> usize {
+        self.0.borrow().allocations
+    }
+
+    /// How many bytes strings, properties and code created while the heap
+    /// was entered have taken so far, including freed ones.
+    ///
+    /// It counts storage of code units and of property entries, without
+    /// what the allocator adds to them, and an estimate of syntax trees by
+    /// the length of their source text.
+    #[must_use]
+    pub fn allocated_bytes(&self) -> usize {
+        self.0.borrow().allocated_bytes
+    }
+
+    /// An upper bound of [`Heap::size`] that is cheap to get, counting
+    /// nodes that died since they were last forgotten.
+    #[must_use]
+    pub(crate) fn registered_size(&self) -> usize {
+        self.0.borrow().nodes.len()
+    }
+
+    /// Keep a value alive across collections until the handle is dropped.
+    ///
+    /// ```
+    /// use embedded_ecmascript::engine::Engine;
+    /// use embedded_ecmascript::runtime::Value;
+    ///
+    /// let mut engine = Engine::new();
+    /// let value = engine.evaluate_script("var o = { n: 1 }; o.self = o; o").unwrap();
+    /// let handle = engine.heap().root(value);
+    /// engine.evaluate_script("o = undefined;").unwrap();
+    /// assert_eq!(engine.collect_garbage().collected, 0);
+    /// assert!(handle.is_object());
+    /// ```
+    #[must_use]
+    pub fn root<T: Clone + Into<Value>>(&self, value: T) -> Gc<T> {
+        let root = match value.clone().into() {
+            Value::Object(object) => {
+                let heap = &mut *self.0.borrow_mut();
+                heap.next_root += 1;
+                heap.roots.insert(heap.next_root, object.address());
+                Some(heap.next_root)
+            },
+            _ => None,
+        };
+        Gc { value, heap: Rc::downgrade(&self.0), root }
+    }
+
+    /// Start a scope keeping values alive together until it ends.
+    #[must_use]
+    pub fn handle_scope(&self) -> HandleScope {
+        HandleScope { heap: self.clone(), handles: RefCell::default() }
+    }
+
+    /// Free objects and environments of the heap that are kept alive only
+    /// by references from each other.
+    ///
+    /// Objects referred to only by closures of native functions are not
+    /// traced through, so a cycle passing through such a closure stays.
+    #[allow(clippy::must_use_candidate)] // collecting is the point of the call
+    pub fn collect_garbage(&self) -> HeapStatistics {
+        let (nodes, roots): (Vec<Rc<dyn Trace>>, Vec<usize>) = {
+            let heap = &mut *self.0.borrow_mut();
+            heap.nodes.retain(|node| node.strong_count() > 0);
+            (heap.nodes.iter().filter_map(Weak::upgrade).collect(), heap.roots.values().copied().collect())
+        };
+        let index: HashMap<usize, usize> =
+            nodes.iter().enumerate().map(|(position, node)| (address(node), position)).collect();
+
+        // Count references each node gets from other nodes and handles.
+        let mut internal = vec![0_usize; nodes.len()];
+        let mut edges = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let mut tracer = Tracer::default();
+            node.trace(&mut tracer);
+            let targets: Vec<usize> = tracer.edges.iter().filter_map(|edge| index.get(edge).copied()).collect();
+            for &target in &targets {
+                internal[target] += 1;
+            }
+            edges.push(targets);
+        }
+        let mut pending: Vec<usize> = roots.iter().filter_map(|root| index.get(root).copied()).collect();
+        for &root in &pending {
+            internal[root] += 1;
+        }
+
+        // Mark from handles and nodes referred to from outside; `nodes`
+        // holds one reference to each itself.
+        let mut marked = vec![false; nodes.len()];
+        pending.extend((0..nodes.len()).filter(|&position| Rc::strong_count(&nodes[position]) - 1 > internal[position]));
+        while let Some(position) = pending.pop() {
+            if !std::mem::replace(&mut marked[position], true) {
+                pending.extend(edges[position].iter().copied().filter(|&target| !marked[target]));
+            }
+        }
+
+        // Sweep: unreachable nodes drop references to each other and are
+        // freed as `nodes` goes away.
+        let mut statistics = HeapStatistics::default();
+        for (node, marked) in nodes.iter().zip(marked) {
+            if marked {
+                statistics.live += 1;
+            } else {
+                node.clear();
+                statistics.collected += 1;
+            }
+        }
+        statistics
+    }
+}
+
+/// Add a node created on the thread to the heap entered, if any.
+pub fn register(node: Weak<dyn Trace>) {
+    let heap = CURRENT.with_borrow(Clone::clone);
+    if let Some(heap) = heap {
+        heap.register(node);
+    }
+}
+
+/// Count bytes a string or a property created on the thread takes against
+/// the heap entered, if any.
+pub fn charge(bytes: usize) {
+    let heap = CURRENT.with_borrow(Clone::clone);
+    if let Some(heap) = heap {
+        let heap = &mut *heap.0.borrow_mut();
+        heap.allocated_bytes = heap.allocated_bytes.saturating_add(bytes);
+    }
+}
+
+/// Count bytes a syntax tree parsed from `source_len` bytes of source text
+/// takes against the heap entered, if any.
+///
+/// Trees are not measured node by node: ones of typical code take from
+/// 40 to 110 bytes per byte of their text, so each byte is charged about
+/// the middle of that.
+pub fn charge_code(source_len: usize) {
+    charge(source_len.saturating_mul(TREE_BYTES_PER_SOURCE_BYTE));
+}
+
+/// Bytes [`charge_code`] counts for each byte of source text.
+const TREE_BYTES_PER_SOURCE_BYTE: usize = 64;
+
+/// The heap entered by [`Heap::enter`] until the scope is dropped.
+pub struct HeapScope {
+    previous: Option<Heap>,
+}
+
+impl Drop for HeapScope {
+    fn drop(&mut self) {
+        CURRENT.set(self.previous.take());
+    }
+}
+
+/// A handle to a value that keeps it, and whatever it refers to, alive
+/// across collections of a heap until it is dropped.
+///
+/// It dereferences to the value.
+pub struct Gc<T: Clone + Into<Value>> {
+    value: T,
+    heap: Weak<RefCell<HeapData>>,
+    /// The identifier of the root in the heap, if the value is an object.
+    root: Option<usize>,
+}
+
+impl<T: Clone + Into<Value>> Deref for Gc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Clone + Into<Value>> Clone for Gc<T> {
+    fn clone(&self) -> Self {
+        let Some(heap) = self.heap.upgrade() else {
+            return Self { value: self.value.clone(), heap: Weak::new(), root: None };
+        };
+        Heap(heap).root(self.value.clone())
+    }
+}
+
+impl<T: Clone + Into<Value>> Drop for Gc<T> {
+    fn drop(&mut self) {
+        if let (Some(heap), Some(root)) = (self.heap.upgrade(), self.root) {
+            heap.borrow_mut().roots.remove(&root);
+        }
+    }
+}
+
+impl<T: Clone + Into<Value> + fmt::Debug> fmt::Debug for Gc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Gc").field(&self.value).finish()
+    }
+}
+
+/// Values kept alive together until the scope is dropped, for a host
+/// working with many of them at once.
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+///
+/// let mut engine = Engine::new();
+/// let scope = engine.heap().handle_scope();
+/// let first = scope.root(engine.evaluate_script("var a = {}; a.self = a; a").unwrap());
+/// let second = scope.root(engine.evaluate_script("var b = [a]; b[1] = b; b").unwrap());
+/// engine.evaluate_script("a = b = undefined;").unwrap();
+/// assert_eq!(engine.collect_garbage().collected, 0);
+/// drop((scope, first, second));
+/// assert!(engine.collect_garbage().collected >= 2);
+/// ```
+pub struct HandleScope {
+    heap: Heap,
+    handles: RefCell<Vec<Gc<Value>>>,
+}
+
+impl HandleScope {
+    /// Keep a value alive until the scope ends, giving it back.
+    pub fn root<T: Clone + Into<Value>>(&self, value: T) -> T {
+        self.handles.borrow_mut().push(self.heap.root(value.clone().into()));
+        value
+    }
+}
+
+fn address(node: &Rc<dyn Trace>) -> usize {
+    Rc::as_ptr(node).cast::<()>() as usize
+}
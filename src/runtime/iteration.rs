@@ -0,0 +1,186 @@
+//! <https://262.ecma-international.org/14.0/#sec-operations-on-iterator-objects>
+//! and iterators of built-in collections.
+
+use super::{Context, JsError, Object, ObjectKind, PropertyKey, Symbol, Tracer, Value, WellKnownSymbol};
+use std::cell::{Cell, RefCell};
+
+/// <https://262.ecma-international.org/14.0/#sec-iterator-records>
+#[derive(Clone, Debug)]
+pub struct IteratorRecord {
+    pub iterator: Object,
+    pub next_method: Value,
+    pub done: bool,
+}
+
+impl IteratorRecord {
+    /// <https://262.ecma-international.org/14.0/#sec-getiterator> of a sync
+    /// iterator.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the value is not iterable,
+    /// or with an exception its `Symbol.iterator` method throws.
+    pub fn from_iterable(context: &mut Context, iterable: &Value) -> Result<Self, JsError> {
+        let Some(method) = iterator_method(context, iterable, WellKnownSymbol::Iterator)? else {
+            return Err(JsError::type_error("value is not iterable"));
+        };
+        Self::from_method(context, iterable, &method)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getiterator> of an
+    /// async iterator, flagged `true` if the value has only a sync one.
+    ///
+    /// The caller awaits values of a sync iterator, as
+    /// <https://262.ecma-international.org/14.0/#sec-createasyncfromsynciterator>
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the value is not iterable,
+    /// or with an exception its `Symbol.asyncIterator` or `Symbol.iterator`
+    /// method throws.
+    pub fn from_async_iterable(context: &mut Context, iterable: &Value) -> Result<(Self, bool), JsError> {
+        match iterator_method(context, iterable, WellKnownSymbol::AsyncIterator)? {
+            Some(method) => Ok((Self::from_method(context, iterable, &method)?, false)),
+            None => Ok((Self::from_iterable(context, iterable)?, true)),
+        }
+    }
+
+    fn from_method(context: &mut Context, iterable: &Value, method: &Object) -> Result<Self, JsError> {
+        let Value::Object(iterator) = method.call(context, iterable, &[])? else {
+            return Err(JsError::type_error("iterator method returned a non-object"));
+        };
+        let next_method = iterator.get(context, &PropertyKey::from("next"), &Value::Object(iterator.clone()))?;
+        Ok(Self { iterator, next_method, done: false })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-iteratorstepvalue>:
+    /// the next value, `None` once the iterator is done.
+    ///
+    /// The record is marked done if `next` or accessing its result throws,
+    /// so the iterator is not closed after that.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if `next` returns a non-object,
+    /// or with an exception it throws.
+    pub fn step_value(&mut self, context: &mut Context) -> Result<Option<Value>, JsError> {
+        let result = self.step(context);
+        if !matches!(result, Ok(Some(_))) {
+            self.done = true;
+        }
+        result
+    }
+
+    fn step(&self, context: &mut Context) -> Result<Option<Value>, JsError> {
+        let Value::Object(next_method) = &self.next_method else {
+            return Err(JsError::type_error("iterator has no next method"));
+        };
+        let Value::Object(result) = next_method.call(context, &Value::Object(self.iterator.clone()), &[])? else {
+            return Err(JsError::type_error("iterator result is not an object"));
+        };
+        let this = Value::Object(result.clone());
+        if result.get(context, &PropertyKey::from("done"), &this)?.to_boolean() {
+            return Ok(None);
+        }
+        result.get(context, &PropertyKey::from("value"), &this).map(Some)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-iteratorclose>
+    /// after an abrupt completion `completion`, or a normal one if it is
+    /// `Ok`; an error of the completion takes precedence over errors of
+    /// `return`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with the error of `completion`, or with
+    /// a `TypeError` if `return` returns a non-object, or with an exception
+    /// it throws.
+    pub fn close<T>(&self, context: &mut Context, completion: Result<T, JsError>) -> Result<T, JsError> {
+        let iterator = Value::Object(self.iterator.clone());
+        let inner = self.iterator.get_method(context, &PropertyKey::from("return"));
+        let inner = match inner {
+            Ok(Some(method)) => method.call(context, &iterator, &[]),
+            Ok(None) => return completion,
+            Err(error) => Err(error),
+        };
+        let value = completion?;
+        if !inner?.is_object() {
+            return Err(JsError::type_error("iterator return method returned a non-object"));
+        }
+        Ok(value)
+    }
+}
+
+/// A method of a value under a well-known symbol, like `Symbol.iterator`.
+fn iterator_method(context: &mut Context, value: &Value, symbol: WellKnownSymbol) -> Result<Option<Object>, JsError> {
+    let key = PropertyKey::Symbol(Symbol::well_known(symbol));
+    match value {
+        Value::Object(object) => object.get_method(context, &key),
+        Value::Undefined | Value::Null => Ok(None),
+        primitive => primitive.to_object(context)?.get_method(context, &key),
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-createiterresultobject>
+#[must_use]
+pub fn create_iter_result_object(context: &Context, value: Value, done: bool) -> Value {
+    let prototype = context.current_realm().map(|realm| realm.intrinsics().object_prototype.clone());
+    let result = Object::new(prototype);
+    result.create_data_property("value", value);
+    result.create_data_property("done", done);
+    Value::Object(result)
+}
+
+/// Internal slots of
+/// <https://262.ecma-international.org/14.0/#sec-array-iterator-objects>
+/// iterating over values.
+#[derive(Debug)]
+pub struct ArrayIterator {
+    /// `[[IteratedArrayLike]]`, `None` once the iteration is over.
+    iterated: RefCell<Option<Object>>,
+    /// `[[ArrayLikeNextIndex]]`
+    next_index: Cell<u64>,
+}
+
+impl ArrayIterator {
+    /// Report the iterated object to the heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        if let Some(iterated) = &*self.iterated.borrow() {
+            tracer.object(iterated);
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createarrayiterator>
+    /// of values of an array-like object.
+    #[must_use]
+    pub fn create(context: &Context, array: Object) -> Object {
+        let prototype = context.current_realm().map(|realm| realm.intrinsics().array_iterator_prototype.clone());
+        let state = Self { iterated: RefCell::new(Some(array)), next_index: Cell::new(0) };
+        Object::with_kind(prototype, ObjectKind::ArrayIterator(state.into()))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-%arrayiteratorprototype%.next>
+    pub(crate) fn next(context: &mut Context, this: &Value, _: &[Value]) -> Result<Value, JsError> {
+        let Some(ObjectKind::ArrayIterator(state)) = this.as_object().map(Object::kind) else {
+            return Err(JsError::type_error("not an Array Iterator"));
+        };
+        let Some(array) = state.iterated.borrow().clone() else {
+            return Ok(create_iter_result_object(context, Value::Undefined, true));
+        };
+        let index = state.next_index.get();
+        if index >= array.length_of_array_like(context)? {
+            state.iterated.replace(None);
+            return Ok(create_iter_result_object(context, Value::Undefined, true));
+        }
+        state.next_index.set(index + 1);
+        let value = array.get(context, &PropertyKey::from(index.to_string()), &Value::Object(array.clone()))?;
+        Ok(create_iter_result_object(context, value, false))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-array.prototype.values>
+    pub(crate) fn values(context: &mut Context, this: &Value, _: &[Value]) -> Result<Value, JsError> {
+        let array = this.to_object(context)?;
+        Ok(Value::Object(Self::create(context, array)))
+    }
+}
@@ -0,0 +1,56 @@
+//! <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects>
+
+use super::{Environment, JsError, JsString, Object, Value};
+use std::cell::RefCell;
+
+/// What a name exported by a module resolves to.
+#[derive(Clone, Debug)]
+pub enum ModuleExport {
+    /// A binding in the environment of the module declaring it.
+    Binding(Environment, String),
+    /// A namespace object of another module, exported by `export * as`.
+    Namespace(Object),
+}
+
+/// Internal slots of a module namespace exotic object, which
+/// [`Object::new_module_namespace`] creates.
+///
+/// `[[Exports]]` is filled after the object is created, so modules
+/// importing each other's namespaces can refer to objects being built.
+#[derive(Debug, Default)]
+pub struct ModuleNamespace {
+    /// `[[Exports]]` sorted by code units, each with its resolution.
+    exports: RefCell<Vec<(JsString, ModuleExport)>>,
+}
+
+impl ModuleNamespace {
+    /// Set `[[Exports]]` once names exported by the module are resolved.
+    pub fn set_exports(&self, exports: impl IntoIterator<Item = (String, ModuleExport)>) {
+        let mut exports: Vec<_> = exports.into_iter().map(|(name, export)| (JsString::from(name), export)).collect();
+        exports.sort_by(|(left, _), (right, _)| left.cmp(right));
+        *self.exports.borrow_mut() = exports;
+    }
+
+    /// `[[Exports]]` in code unit order.
+    #[must_use]
+    pub fn names(&self) -> Vec<JsString> {
+        self.exports.borrow().iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// The current value of an export, `None` if the module does not
+    /// export the name.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Some(Err)` with a `ReferenceError` if the binding is not
+    /// initialized yet.
+    #[must_use]
+    pub fn get(&self, name: &JsString) -> Option<Result<Value, JsError>> {
+        let exports = self.exports.borrow();
+        let (_, export) = exports.iter().find(|(export_name, _)| export_name == name)?;
+        Some(match export {
+            ModuleExport::Binding(environment, binding_name) => environment.module_binding_value(binding_name),
+            ModuleExport::Namespace(namespace) => Ok(Value::Object(namespace.clone())),
+        })
+    }
+}
@@ -0,0 +1,280 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types-number-type>
+
+/// Operations of
+/// <https://262.ecma-international.org/14.0/#sec-numeric-types-number>
+/// over IEEE 754 binary64 values, named as in the specification.
+///
+/// Rust float operators already round as the specification requires;
+/// these functions cover cases where ECMAScript and IEEE 754 libraries
+/// disagree, like `1 ** NaN` or `-0` formatting.
+///
+/// ```
+/// use embedded_ecmascript::runtime::Number;
+///
+/// assert!(Number::exponentiate(1.0, f64::NAN).is_nan());
+/// assert_eq!(Number::to_string(1e21, 10), "1e+21");
+/// assert_eq!(Number::to_string(0.000_001, 10), "0.000001");
+/// assert_eq!(Number::to_string(-255.5, 16), "-ff.8");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Number;
+
+/// Digits of radices from 2 to 36.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+impl Number {
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-unaryMinus>
+    #[must_use]
+    pub fn unary_minus(x: f64) -> f64 {
+        -x
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-bitwiseNOT>
+    #[must_use]
+    pub fn bitwise_not(x: f64) -> f64 {
+        f64::from(!Self::to_int32(x))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-exponentiate>
+    #[must_use]
+    pub fn exponentiate(base: f64, exponent: f64) -> f64 {
+        // Unlike C `pow`, 1 to a NaN or an infinite power is NaN.
+        if exponent.is_nan() || (Self::equal(base.abs(), 1.0) && exponent.is_infinite()) {
+            return f64::NAN;
+        }
+        base.powf(exponent)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-multiply>
+    #[must_use]
+    pub fn multiply(x: f64, y: f64) -> f64 {
+        x * y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-divide>
+    #[must_use]
+    pub fn divide(x: f64, y: f64) -> f64 {
+        x / y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-remainder>
+    ///
+    /// Truncating like C `fmod`, so the result has the sign of `n`.
+    #[must_use]
+    pub fn remainder(n: f64, d: f64) -> f64 {
+        n % d
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-add>
+    #[must_use]
+    pub fn add(x: f64, y: f64) -> f64 {
+        x + y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-subtract>
+    #[must_use]
+    pub fn subtract(x: f64, y: f64) -> f64 {
+        x - y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-leftShift>
+    #[must_use]
+    pub fn left_shift(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_int32(x).wrapping_shl(Self::to_uint32(y)))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-signedRightShift>
+    #[must_use]
+    pub fn signed_right_shift(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_int32(x).wrapping_shr(Self::to_uint32(y)))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-unsignedRightShift>
+    #[must_use]
+    pub fn unsigned_right_shift(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_uint32(x).wrapping_shr(Self::to_uint32(y)))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-lessThan>,
+    /// `None` standing for **undefined** if either operand is NaN.
+    #[must_use]
+    pub fn less_than(x: f64, y: f64) -> Option<bool> {
+        x.partial_cmp(&y).map(std::cmp::Ordering::is_lt)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-equal>
+    #[must_use]
+    #[allow(clippy::float_cmp)] // Number::equal is exact IEEE 754 comparison
+    pub fn equal(x: f64, y: f64) -> bool {
+        x == y
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-sameValue>
+    #[must_use]
+    pub const fn same_value(x: f64, y: f64) -> bool {
+        (x.is_nan() && y.is_nan()) || x.to_bits() == y.to_bits()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-sameValueZero>
+    #[must_use]
+    pub fn same_value_zero(x: f64, y: f64) -> bool {
+        (x.is_nan() && y.is_nan()) || Self::equal(x, y)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-bitwiseAND>
+    #[must_use]
+    pub fn bitwise_and(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_int32(x) & Self::to_int32(y))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-bitwiseXOR>
+    #[must_use]
+    pub fn bitwise_xor(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_int32(x) ^ Self::to_int32(y))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-bitwiseOR>
+    #[must_use]
+    pub fn bitwise_or(x: f64, y: f64) -> f64 {
+        f64::from(Self::to_int32(x) | Self::to_int32(y))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-numeric-types-number-tostring>
+    ///
+    /// Radix 10 gives the shortest digit string that round-trips. Other
+    /// radices, which the specification leaves implementation-approximated,
+    /// produce the digits other engines do: fraction digits stop once they
+    /// identify the value uniquely.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `radix` is not in the 2..=36 range.
+    #[must_use]
+    pub fn to_string(x: f64, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix {radix} is out of the 2..=36 range");
+        if x.is_nan() {
+            return "NaN".to_owned();
+        }
+        if x == 0.0 {
+            return "0".to_owned();
+        }
+        let sign = if x < 0.0 { "-" } else { "" };
+        if x.is_infinite() {
+            return format!("{sign}Infinity");
+        }
+        let magnitude = if radix == 10 { decimal_to_string(x.abs()) } else { radix_to_string(x.abs(), radix) };
+        format!("{sign}{magnitude}")
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-touint32> of a Number.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_uint32(x: f64) -> u32 {
+        if !x.is_finite() {
+            return 0;
+        }
+        x.trunc().rem_euclid(4_294_967_296.0) as u32
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-toint32> of a Number.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_int32(x: f64) -> i32 {
+        Self::to_uint32(x) as i32
+    }
+}
+
+/// Steps 5 to 12 of `Number::toString` for a finite positive number.
+fn decimal_to_string(x: f64) -> String {
+    // Rust picks the shortest digits that round-trip, which is what the
+    // specification asks for with k as small as possible; `{:e}` formats
+    // them as `d.ddde-7`.
+    let scientific = format!("{x:e}");
+    let (mantissa, exponent) = scientific.split_once('e').expect("scientific notation has an exponent");
+    let digits = mantissa.replace('.', "");
+    let k = digits.len();
+    let n = exponent.parse::<isize>().expect("an exponent is an integer") + 1;
+    #[allow(clippy::cast_possible_wrap)] // up to 17 digits
+    let k_signed = k as isize;
+    match n {
+        1..=21 if k_signed <= n => digits + &"0".repeat(n.unsigned_abs() - k),
+        1..=21 => format!("{}.{}", &digits[..n.unsigned_abs()], &digits[n.unsigned_abs()..]),
+        -5..=0 => format!("0.{}{digits}", "0".repeat(n.unsigned_abs())),
+        _ => {
+            let exponent_sign = if n > 0 { '+' } else { '-' };
+            let exponent = (n - 1).abs();
+            if k == 1 {
+                format!("{digits}e{exponent_sign}{exponent}")
+            } else {
+                format!("{}.{}e{exponent_sign}{exponent}", &digits[..1], &digits[1..])
+            }
+        },
+    }
+}
+
+/// `Number::toString` of a finite positive number in a radix other than 10.
+///
+/// Follows the algorithm of V8 and `SpiderMonkey`: fraction digits are
+/// generated until the rest is within half a unit in the last place, and
+/// integer digits beyond the precision of a double are zeros.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::while_float)]
+fn radix_to_string(x: f64, radix: u32) -> String {
+    let radix_float = f64::from(radix);
+    let mut integer = x.floor();
+    let mut fraction = x - integer;
+    // Half of the distance to the next representable number.
+    let next = f64::from_bits(x.to_bits() + 1);
+    let mut delta = (0.5 * (next - x)).max(f64::from_bits(1));
+    let mut fraction_digits = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= radix_float;
+            delta *= radix_float;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= f64::from(digit);
+            if (fraction > 0.5 || (Number::equal(fraction, 0.5) && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // Round the digits up, propagating the carry.
+                loop {
+                    match fraction_digits.pop() {
+                        None => {
+                            integer += 1.0;
+                            break;
+                        },
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        },
+                        Some(_) => {},
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    let mut integer_digits = Vec::new();
+    // Digits below the precision of a double are unknown.
+    while integer / radix_float >= 9_007_199_254_740_992.0 {
+        integer /= radix_float;
+        integer_digits.push(0);
+    }
+    loop {
+        let remainder = integer % radix_float;
+        integer_digits.push(remainder as u32);
+        integer = (integer - remainder) / radix_float;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+
+    let mut text: String = integer_digits.iter().rev().map(|&digit| char::from(DIGITS[digit as usize])).collect();
+    if !fraction_digits.is_empty() {
+        text.push('.');
+        text.extend(fraction_digits.iter().map(|&digit| char::from(DIGITS[digit as usize])));
+    }
+    text
+}
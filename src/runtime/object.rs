@@ -0,0 +1,1292 @@
+//! <https://262.ecma-international.org/14.0/#sec-object-type> and
+//! <https://262.ecma-international.org/14.0/#sec-ordinary-object-internal-methods-and-internal-slots>.
+
+use super::heap::{self, Trace, Tracer};
+use super::{
+    ArrayIterator,
+    Context,
+    ErrorKind,
+    ExecutionContext,
+    JsError,
+    JsString,
+    ModuleNamespace,
+    Number,
+    ParameterMap,
+    PrivateElement,
+    PrivateElementKind,
+    PrivateName,
+    Promise,
+    PropertyKey,
+    Proxy,
+    Symbol,
+    Value,
+    WellKnownSymbol,
+};
+use crate::interpreter::{CallCompletion, FunctionData, Generator, TailCall};
+use num_bigint::BigInt;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#sec-property-descriptor-specification-type>
+///
+/// Absent fields are `None`. A descriptor of an existing property,
+/// returned by [`Object::get_own_property`], has all fields of its kind.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyDescriptor {
+    pub value: Option<Value>,
+    pub writable: Option<bool>,
+    /// A function object or **undefined**.
+    pub get: Option<Value>,
+    /// A function object or **undefined**.
+    pub set: Option<Value>,
+    pub enumerable: Option<bool>,
+    pub configurable: Option<bool>,
+}
+
+impl PropertyDescriptor {
+    /// A complete data property descriptor.
+    #[must_use]
+    pub const fn data(value: Value, writable: bool, enumerable: bool, configurable: bool) -> Self {
+        Self {
+            value: Some(value),
+            writable: Some(writable),
+            get: None,
+            set: None,
+            enumerable: Some(enumerable),
+            configurable: Some(configurable),
+        }
+    }
+
+    /// A complete accessor property descriptor.
+    #[must_use]
+    pub const fn accessor(get: Value, set: Value, enumerable: bool, configurable: bool) -> Self {
+        Self {
+            value: None,
+            writable: None,
+            get: Some(get),
+            set: Some(set),
+            enumerable: Some(enumerable),
+            configurable: Some(configurable),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isaccessordescriptor>
+    #[must_use]
+    pub const fn is_accessor_descriptor(&self) -> bool {
+        self.get.is_some() || self.set.is_some()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isdatadescriptor>
+    #[must_use]
+    pub const fn is_data_descriptor(&self) -> bool {
+        self.value.is_some() || self.writable.is_some()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isgenericdescriptor>
+    #[must_use]
+    pub const fn is_generic_descriptor(&self) -> bool {
+        !self.is_accessor_descriptor() && !self.is_data_descriptor()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-topropertydescriptor>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if `value` is not an object,
+    /// has a getter or a setter that is not callable or has both accessor
+    /// and data fields, or with an exception a getter throws.
+    pub fn from_value(context: &mut Context, value: &Value) -> Result<Self, JsError> {
+        let Value::Object(object) = value else {
+            return Err(JsError::type_error("property descriptor is not an object"));
+        };
+        let mut field = |name: &str| -> Result<Option<Value>, JsError> {
+            let key = PropertyKey::from(name);
+            if !object.try_has_property(context, &key)? {
+                return Ok(None);
+            }
+            object.get(context, &key, value).map(Some)
+        };
+        let enumerable = field("enumerable")?.map(|value| value.to_boolean());
+        let configurable = field("configurable")?.map(|value| value.to_boolean());
+        let field_value = field("value")?;
+        let writable = field("writable")?.map(|value| value.to_boolean());
+        let get = field("get")?;
+        let set = field("set")?;
+        for accessor in [&get, &set].into_iter().flatten() {
+            if !matches!(accessor, Value::Undefined) && !accessor.as_object().is_some_and(Object::is_callable) {
+                return Err(JsError::type_error("property accessor is not a function"));
+            }
+        }
+        let descriptor = Self { value: field_value, writable, get, set, enumerable, configurable };
+        if descriptor.is_accessor_descriptor() && descriptor.is_data_descriptor() {
+            return Err(JsError::type_error("property descriptor has both accessor and data fields"));
+        }
+        Ok(descriptor)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-frompropertydescriptor>
+    /// of a present descriptor, inheriting from `%Object.prototype%` of the
+    /// current realm.
+    #[must_use]
+    pub fn to_object(&self, context: &Context) -> Object {
+        let object = Object::new(context.current_realm().map(|realm| realm.intrinsics().object_prototype.clone()));
+        let fields = [
+            ("value", self.value.clone()),
+            ("writable", self.writable.map(Value::Boolean)),
+            ("get", self.get.clone()),
+            ("set", self.set.clone()),
+            ("enumerable", self.enumerable.map(Value::Boolean)),
+            ("configurable", self.configurable.map(Value::Boolean)),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                object.create_data_property(name, value);
+            }
+        }
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-completepropertydescriptor>
+    pub(super) fn complete(mut self) -> Self {
+        if self.is_generic_descriptor() || self.is_data_descriptor() {
+            self.value.get_or_insert(Value::Undefined);
+            self.writable.get_or_insert(false);
+        } else {
+            self.get.get_or_insert(Value::Undefined);
+            self.set.get_or_insert(Value::Undefined);
+        }
+        self.enumerable.get_or_insert(false);
+        self.configurable.get_or_insert(false);
+        self
+    }
+}
+
+/// A function implemented in Rust, called with a `this` value and
+/// arguments.
+pub type NativeFunction = Rc<dyn Fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError>>;
+
+/// A constructor implemented in Rust, called with arguments and
+/// `NewTarget`, which is `None` for a call without `new`.
+pub type NativeConstructor = Rc<dyn Fn(&mut Context, &[Value], Option<&Object>) -> Result<Object, JsError>>;
+
+/// What kind of an object it is, with internal slots specific to the kind.
+#[derive(Clone, Default)]
+pub enum ObjectKind {
+    #[default]
+    Ordinary,
+    /// <https://262.ecma-international.org/14.0/#sec-built-in-function-objects>
+    NativeFunction(NativeFunction),
+    /// <https://262.ecma-international.org/14.0/#sec-built-in-function-objects>
+    /// that is a constructor.
+    NativeConstructor(NativeConstructor),
+    /// <https://262.ecma-international.org/14.0/#sec-ecmascript-function-objects>
+    Function(Rc<FunctionData>),
+    /// <https://262.ecma-international.org/14.0/#sec-array-exotic-objects>
+    Array,
+    /// <https://262.ecma-international.org/14.0/#sec-arguments-exotic-objects>
+    /// with `[[ParameterMap]]`.
+    Arguments(Rc<ParameterMap>),
+    /// A Boolean object with `[[BooleanData]]`.
+    Boolean(bool),
+    /// A Number object with `[[NumberData]]`.
+    Number(f64),
+    /// <https://262.ecma-international.org/14.0/#sec-string-exotic-objects>
+    /// with `[[StringData]]`.
+    String(JsString),
+    /// A Symbol object with `[[SymbolData]]`.
+    Symbol(Symbol),
+    /// A `BigInt` object with `[[BigIntData]]`.
+    BigInt(BigInt),
+    /// <https://262.ecma-international.org/14.0/#sec-properties-of-error-instances>
+    /// with `[[ErrorData]]`.
+    Error,
+    /// <https://262.ecma-international.org/14.0/#sec-array-iterator-objects>
+    ArrayIterator(Rc<ArrayIterator>),
+    /// <https://262.ecma-international.org/14.0/#sec-properties-of-generator-instances>
+    Generator(Rc<Generator>),
+    /// <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects>
+    ModuleNamespace(Rc<ModuleNamespace>),
+    /// <https://262.ecma-international.org/14.0/#sec-promise-objects>
+    Promise(Rc<Promise>),
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots>
+    Proxy(Rc<Proxy>),
+}
+
+/// Internal slots of an object.
+#[derive(Default)]
+struct ObjectData {
+    kind: ObjectKind,
+    prototype: Option<Object>,
+    extensible: bool,
+    properties: HashMap<PropertyKey, PropertyDescriptor>,
+    /// Keys of `properties` in the order of their creation.
+    order: Vec<PropertyKey>,
+    /// `[[PrivateElements]]`
+    private_elements: Vec<PrivateElement>,
+}
+
+/// A reference to an object; clones refer to the same object.
+#[derive(Clone)]
+pub struct Object(Rc<RefCell<ObjectData>>);
+
+impl Object {
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryobjectcreate>
+    #[must_use]
+    pub fn new(prototype: Option<Self>) -> Self {
+        Self::with_kind(prototype, ObjectKind::Ordinary)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-makebasicobject> with
+    /// internal slots of a kind.
+    #[must_use]
+    pub fn with_kind(prototype: Option<Self>, kind: ObjectKind) -> Self {
+        let data = Rc::new(RefCell::new(ObjectData { kind, prototype, extensible: true, ..ObjectData::default() }));
+        heap::register(Rc::downgrade(&data) as _);
+        Self(data)
+    }
+
+    /// Identity of the object in the heap.
+    pub(crate) fn address(&self) -> usize {
+        Rc::as_ptr(&self.0).cast::<()>() as usize
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createbuiltinfunction>
+    /// without `name` and `length` properties.
+    #[must_use]
+    pub fn native_function(
+        prototype: Option<Self>,
+        function: impl Fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError> + 'static,
+    ) -> Self {
+        Self::with_kind(prototype, ObjectKind::NativeFunction(Rc::new(function)))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createbuiltinfunction>
+    /// with `length` and `name` properties.
+    #[must_use]
+    pub fn builtin_function(
+        prototype: Option<Self>,
+        name: &str,
+        length: usize,
+        function: impl Fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError> + 'static,
+    ) -> Self {
+        let object = Self::native_function(prototype, function);
+        #[allow(clippy::cast_precision_loss)] // parameter counts are less than 2^53
+        let length = PropertyDescriptor::data(Value::Number(length as f64), false, false, true);
+        object.ordinary_define_own_property(PropertyKey::from("length"), length);
+        let name = PropertyDescriptor::data(Value::from(name), false, false, true);
+        object.ordinary_define_own_property(PropertyKey::from("name"), name);
+        object
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createbuiltinfunction>
+    /// of a constructor, with `length` and `name` properties.
+    #[must_use]
+    pub fn native_constructor(
+        prototype: Option<Self>,
+        name: &str,
+        length: usize,
+        constructor: impl Fn(&mut Context, &[Value], Option<&Self>) -> Result<Self, JsError> + 'static,
+    ) -> Self {
+        let object = Self::with_kind(prototype, ObjectKind::NativeConstructor(Rc::new(constructor)));
+        #[allow(clippy::cast_precision_loss)] // parameter counts are less than 2^53
+        let length = PropertyDescriptor::data(Value::Number(length as f64), false, false, true);
+        object.ordinary_define_own_property(PropertyKey::from("length"), length);
+        let name = PropertyDescriptor::data(Value::from(name), false, false, true);
+        object.ordinary_define_own_property(PropertyKey::from("name"), name);
+        object
+    }
+
+    /// An object with `[[ErrorData]]`, like
+    /// <https://262.ecma-international.org/14.0/#sec-error-message> creates,
+    /// with a `message` property unless it is `None`.
+    #[must_use]
+    pub fn new_error(prototype: Option<Self>, message: Option<JsString>) -> Self {
+        let error = Self::with_kind(prototype, ObjectKind::Error);
+        if let Some(message) = message {
+            error.create_non_enumerable_data_property(PropertyKey::from("message"), Value::String(message));
+        }
+        error
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createarrayfromlist>
+    #[must_use]
+    pub fn new_array(prototype: Option<Self>, elements: Vec<Value>) -> Self {
+        let array = Self::with_kind(prototype, ObjectKind::Array);
+        #[allow(clippy::cast_precision_loss)] // arrays are shorter than 2^32
+        let length = PropertyDescriptor::data(Value::Number(elements.len() as f64), true, false, false);
+        array.ordinary_define_own_property(PropertyKey::from("length"), length);
+        for (index, element) in elements.into_iter().enumerate() {
+            let element = PropertyDescriptor::data(element, true, true, true);
+            array.ordinary_define_own_property(PropertyKey::from(index.to_string()), element);
+        }
+        array
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-stringcreate>
+    #[must_use]
+    pub fn new_string(prototype: Option<Self>, value: JsString) -> Self {
+        #[allow(clippy::cast_precision_loss)] // strings are shorter than 2^53
+        let length = PropertyDescriptor::data(Value::Number(value.len() as f64), false, false, false);
+        let string = Self::with_kind(prototype, ObjectKind::String(value));
+        string.ordinary_define_own_property(PropertyKey::from("length"), length);
+        string
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-modulenamespacecreate>
+    /// of a namespace whose exports may be set later.
+    #[must_use]
+    pub fn new_module_namespace(namespace: Rc<ModuleNamespace>) -> Self {
+        let object = Self::with_kind(None, ObjectKind::ModuleNamespace(namespace));
+        let tag = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::ToStringTag));
+        object.ordinary_define_own_property(tag, PropertyDescriptor::data(Value::from("Module"), false, false, false));
+        object.0.borrow_mut().extensible = false;
+        object
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> ObjectKind {
+        self.0.borrow().kind.clone()
+    }
+
+    /// `[[ProxyTarget]]` and `[[ProxyHandler]]` of a proxy.
+    fn as_proxy(&self) -> Option<Rc<Proxy>> {
+        match &self.0.borrow().kind {
+            ObjectKind::Proxy(proxy) => Some(Rc::clone(proxy)),
+            _ => None,
+        }
+    }
+
+    /// The object internal methods without traps work on instead of
+    /// a proxy.
+    fn proxy_target(&self) -> Option<Self> {
+        let mut target = self.as_proxy()?.target()?;
+        // Proxies of proxies nest as deep as code makes them.
+        while let Some(inner) = target.as_proxy().and_then(|proxy| proxy.target()) {
+            target = inner;
+        }
+        Some(target)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isarray> of an object,
+    /// being `false` for a revoked proxy.
+    #[must_use]
+    pub fn is_array(&self) -> bool {
+        let object = self.proxy_target().unwrap_or_else(|| self.clone());
+        let is_array = matches!(object.0.borrow().kind, ObjectKind::Array);
+        is_array
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-iscallable>
+    #[must_use]
+    pub fn is_callable(&self) -> bool {
+        match &self.0.borrow().kind {
+            ObjectKind::NativeFunction(_) | ObjectKind::NativeConstructor(_) | ObjectKind::Function(_) => true,
+            ObjectKind::Proxy(proxy) => proxy.is_callable(),
+            _ => false,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isconstructor>
+    #[must_use]
+    pub fn is_constructor(&self) -> bool {
+        match &self.0.borrow().kind {
+            ObjectKind::NativeConstructor(_) => true,
+            ObjectKind::Function(function) => function.is_constructor(),
+            ObjectKind::Proxy(proxy) => proxy.is_constructor(),
+            _ => false,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-call>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object is not callable,
+    /// or with an exception the function throws.
+    pub fn call(&self, context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        if !self.is_callable() {
+            return Err(JsError::type_error("not a function"));
+        }
+        let _heap = context.heap().enter();
+        let result = context.nested_call(|context| {
+            let mut completion = self.call_in_place(context, this, arguments)?;
+            // A call in tail position runs at the depth of the call it ends,
+            // so strict code recursing in tail position does not run out of
+            // it.
+            loop {
+                match completion {
+                    CallCompletion::Return(value) => return Ok(value),
+                    CallCompletion::TailCall(TailCall { function, this, arguments }) => {
+                        context.check_limits()?;
+                        completion = function
+                            .call_in_place(context, &this, &arguments)
+                            .map_err(|error| error.through(function.function_name()))?;
+                    },
+                }
+            }
+        });
+        result.map_err(|error| error.through(self.function_name()))
+    }
+
+    /// `[[Call]]` of a callable object, which leaves a call in tail
+    /// position of a function to the caller.
+    fn call_in_place(&self, context: &mut Context, this: &Value, arguments: &[Value]) -> Result<CallCompletion, JsError> {
+        let value = match self.kind() {
+            ObjectKind::NativeFunction(function) => self.run_native(context, |context| function(context, this, arguments)),
+            ObjectKind::NativeConstructor(constructor) => {
+                self.run_native(context, |context| constructor(context, arguments, None).map(Value::Object))
+            },
+            ObjectKind::Function(function) => return function.call(context, self, this, arguments),
+            ObjectKind::Proxy(proxy) => proxy.call(context, this, arguments),
+            _ => Err(JsError::type_error("not a function")),
+        };
+        value.map(CallCompletion::Return)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-construct>, with
+    /// `new_target` defaulting to the constructor itself.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object is not
+    /// a constructor, or with an exception the constructor throws.
+    pub fn construct(&self, context: &mut Context, arguments: &[Value], new_target: Option<&Self>) -> Result<Self, JsError> {
+        if !self.is_constructor() {
+            return Err(JsError::type_error("not a constructor"));
+        }
+        let new_target = new_target.unwrap_or(self);
+        let _heap = context.heap().enter();
+        let result = context.nested_call(|context| match self.kind() {
+            ObjectKind::NativeConstructor(constructor) => {
+                self.run_native(context, |context| constructor(context, arguments, Some(new_target)))
+            },
+            ObjectKind::Function(function) => function.construct(context, self, arguments, new_target),
+            ObjectKind::Proxy(proxy) => proxy.construct(context, arguments, new_target),
+            _ => Err(JsError::type_error("not a constructor")),
+        });
+        result.map_err(|error| error.through(self.function_name()))
+    }
+
+    /// Run a built-in function in an execution context of its own.
+    fn run_native<T>(&self, context: &mut Context, run: impl FnOnce(&mut Context) -> Result<T, JsError>) -> Result<T, JsError> {
+        // Built-in functions do not record their realm yet, so they run in
+        // the realm of the caller.
+        let realm = context.current_realm().cloned();
+        if let Some(realm) = &realm {
+            context.push_execution_context(ExecutionContext::new(Some(self.clone()), realm.clone()));
+        }
+        let result = run(context);
+        if realm.is_some() {
+            context.pop_execution_context();
+        }
+        result
+    }
+
+    /// The own `name` data property of a function, for stack traces.
+    fn function_name(&self) -> String {
+        match self.get_own_property(&PropertyKey::from("name")).and_then(|property| property.value) {
+            Some(Value::String(name)) if !name.is_empty() => name.to_string(),
+            _ => "<anonymous>".to_owned(),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinary-object-internal-methods-and-internal-slots-getprototypeof>
+    #[must_use]
+    pub fn get_prototype_of(&self) -> Option<Self> {
+        if let Some(target) = self.proxy_target() {
+            return target.get_prototype_of();
+        }
+        self.0.borrow().prototype.clone()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarysetprototypeof>
+    ///
+    /// Returns `false` if the object is not extensible or the change would
+    /// make a prototype chain circular.
+    #[must_use]
+    pub fn set_prototype_of(&self, prototype: Option<Self>) -> bool {
+        if let Some(target) = self.proxy_target() {
+            return target.set_prototype_of(prototype);
+        }
+        if self.get_prototype_of() == prototype {
+            return true;
+        }
+        if !self.is_extensible() {
+            return false;
+        }
+        let mut ancestor = prototype.clone();
+        while let Some(object) = ancestor {
+            if object == *self {
+                return false;
+            }
+            ancestor = object.get_prototype_of();
+        }
+        self.0.borrow_mut().prototype = prototype;
+        true
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryisextensible>
+    #[must_use]
+    pub fn is_extensible(&self) -> bool {
+        if let Some(target) = self.proxy_target() {
+            return target.is_extensible();
+        }
+        let data = self.0.borrow();
+        !matches!(data.kind, ObjectKind::Proxy(_)) && data.extensible
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarypreventextensions>
+    #[must_use]
+    pub fn prevent_extensions(&self) -> bool {
+        if let Some(target) = self.proxy_target() {
+            return target.prevent_extensions();
+        }
+        self.0.borrow_mut().extensible = false;
+        true
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarygetownproperty>,
+    /// or its counterpart of an arguments object or
+    /// <https://262.ecma-international.org/14.0/#sec-string-exotic-objects-getownproperty-p>
+    /// for a String object.
+    ///
+    /// An export of a module namespace object that is not initialized yet
+    /// is shown as **undefined**, since looking it up here does not throw;
+    /// [`Object::get`] throws a `ReferenceError` for it.
+    #[must_use]
+    pub fn get_own_property(&self, key: &PropertyKey) -> Option<PropertyDescriptor> {
+        if let Some(target) = self.proxy_target() {
+            return target.get_own_property(key);
+        }
+        let data = self.0.borrow();
+        if let Some(property) = data.properties.get(key) {
+            let mut property = property.clone();
+            // <https://262.ecma-international.org/14.0/#sec-arguments-exotic-objects-getownproperty-p>
+            if let ObjectKind::Arguments(map) = &data.kind {
+                property.value = map.get(key).or(property.value);
+            }
+            return Some(property);
+        }
+        string_get_own_property(&data.kind, key).or_else(|| namespace_get_own_property(&data.kind, key))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarydefineownproperty>,
+    /// or its counterpart of an Array, an arguments, a String or a module
+    /// namespace object.
+    ///
+    /// Returns `false` if the property cannot be created or changed that
+    /// way, like when it is non-configurable. An array `length` must be
+    /// a Number holding an array length.
+    #[must_use]
+    pub fn define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        if let Some(target) = self.proxy_target() {
+            return target.define_own_property(key, descriptor);
+        }
+        match self.kind() {
+            ObjectKind::Array => self.array_define_own_property(key, descriptor),
+            ObjectKind::Arguments(map) => self.arguments_define_own_property(&map, key, descriptor),
+            ObjectKind::String(_) => match self.get_own_property(&key) {
+                Some(current) if !self.0.borrow().properties.contains_key(&key) => is_compatible(&descriptor, &current),
+                _ => self.ordinary_define_own_property(key, descriptor),
+            },
+            // <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects-defineownproperty-p-desc>
+            ObjectKind::ModuleNamespace(_) if matches!(key, PropertyKey::String(_)) => {
+                let Some(current) = self.get_own_property(&key) else {
+                    return false;
+                };
+                if descriptor.configurable == Some(true)
+                    || descriptor.enumerable == Some(false)
+                    || descriptor.is_accessor_descriptor()
+                    || descriptor.writable == Some(false)
+                {
+                    return false;
+                }
+                is_same_if_present(descriptor.value.as_ref(), current.value.as_ref())
+            },
+            _ => self.ordinary_define_own_property(key, descriptor),
+        }
+    }
+
+    fn ordinary_define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        let current = self.get_own_property(&key);
+        let extensible = self.is_extensible();
+        self.validate_and_apply_property_descriptor(key, extensible, descriptor, current)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-array-exotic-objects-defineownproperty-p-desc>
+    fn array_define_own_property(&self, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        if key == PropertyKey::from("length") {
+            return self.array_set_length(descriptor);
+        }
+        let Some(index) = key.as_array_index() else {
+            return self.ordinary_define_own_property(key, descriptor);
+        };
+        let (length, is_length_writable) = self.array_length();
+        if index >= length && !is_length_writable {
+            return false;
+        }
+        if !self.ordinary_define_own_property(key, descriptor) {
+            return false;
+        }
+        if index >= length {
+            self.set_array_length(index + 1, None);
+        }
+        true
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-arguments-exotic-objects-defineownproperty-p-desc>
+    fn arguments_define_own_property(&self, map: &ParameterMap, key: PropertyKey, descriptor: PropertyDescriptor) -> bool {
+        if !map.has(&key) {
+            return self.ordinary_define_own_property(key, descriptor);
+        }
+        let mut new_descriptor = descriptor.clone();
+        if descriptor.is_data_descriptor() && descriptor.value.is_none() && descriptor.writable == Some(false) {
+            new_descriptor.value = map.get(&key);
+        }
+        if !self.ordinary_define_own_property(key.clone(), new_descriptor) {
+            return false;
+        }
+        if descriptor.is_accessor_descriptor() {
+            map.delete(&key);
+        } else {
+            if let Some(value) = descriptor.value {
+                map.set(&key, value);
+            }
+            if descriptor.writable == Some(false) {
+                map.delete(&key);
+            }
+        }
+        true
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-arraysetlength>
+    fn array_set_length(&self, descriptor: PropertyDescriptor) -> bool {
+        let length_key = PropertyKey::from("length");
+        let Some(value) = &descriptor.value else {
+            return self.ordinary_define_own_property(length_key, descriptor);
+        };
+        let Value::Number(number) = value else {
+            return false;
+        };
+        let new_length = Number::to_uint32(*number);
+        if !Number::equal(f64::from(new_length), *number) {
+            return false;
+        }
+        let (old_length, is_length_writable) = self.array_length();
+        let mut new_length_descriptor = PropertyDescriptor { value: Some(Value::from(new_length)), ..descriptor };
+        if new_length >= old_length {
+            return self.ordinary_define_own_property(length_key, new_length_descriptor);
+        }
+        if !is_length_writable {
+            return false;
+        }
+        let new_writable = new_length_descriptor.writable != Some(false);
+        new_length_descriptor.writable = Some(true);
+        if !self.ordinary_define_own_property(length_key.clone(), new_length_descriptor) {
+            return false;
+        }
+        let mut indices: Vec<u32> = self
+            .0
+            .borrow()
+            .order
+            .iter()
+            .filter_map(PropertyKey::as_array_index)
+            .filter(|&index| index >= new_length)
+            .collect();
+        indices.sort_unstable_by(|left, right| right.cmp(left));
+        for index in indices {
+            if !self.delete(&PropertyKey::from(index.to_string())) {
+                self.set_array_length(index + 1, (!new_writable).then_some(false));
+                return false;
+            }
+        }
+        if !new_writable {
+            let frozen = PropertyDescriptor { writable: Some(false), ..PropertyDescriptor::default() };
+            return self.ordinary_define_own_property(length_key, frozen);
+        }
+        true
+    }
+
+    /// The value and the writability of `length` of an array.
+    fn array_length(&self) -> (u32, bool) {
+        let data = self.0.borrow();
+        let length = data.properties.get(&PropertyKey::from("length"));
+        match length {
+            Some(PropertyDescriptor { value: Some(Value::Number(length)), writable, .. }) => {
+                (Number::to_uint32(*length), *writable == Some(true))
+            },
+            _ => unreachable!("arrays have a numeric length"),
+        }
+    }
+
+    fn set_array_length(&self, length: u32, writable: Option<bool>) {
+        let mut data = self.0.borrow_mut();
+        let property = data.properties.get_mut(&PropertyKey::from("length")).expect("arrays have a length");
+        property.value = Some(Value::from(length));
+        property.writable = writable.or(property.writable);
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-validateandapplypropertydescriptor>
+    fn validate_and_apply_property_descriptor(
+        &self,
+        key: PropertyKey,
+        extensible: bool,
+        descriptor: PropertyDescriptor,
+        current: Option<PropertyDescriptor>,
+    ) -> bool {
+        let Some(current) = current else {
+            if !extensible {
+                return false;
+            }
+            heap::charge(mem::size_of::<(PropertyKey, PropertyDescriptor)>() + mem::size_of::<PropertyKey>());
+            let mut data = self.0.borrow_mut();
+            data.order.push(key.clone());
+            data.properties.insert(key, descriptor.complete());
+            return true;
+        };
+        if !is_compatible(&descriptor, &current) {
+            return false;
+        }
+        let mut updated = if descriptor.is_accessor_descriptor() && current.is_data_descriptor() {
+            PropertyDescriptor::accessor(
+                Value::Undefined,
+                Value::Undefined,
+                current.enumerable.unwrap_or_default(),
+                current.configurable.unwrap_or_default(),
+            )
+        } else if descriptor.is_data_descriptor() && current.is_accessor_descriptor() {
+            PropertyDescriptor::data(
+                Value::Undefined,
+                false,
+                current.enumerable.unwrap_or_default(),
+                current.configurable.unwrap_or_default(),
+            )
+        } else {
+            current
+        };
+        let PropertyDescriptor { value, writable, get, set, enumerable, configurable } = descriptor;
+        updated.value = value.or(updated.value);
+        updated.writable = writable.or(updated.writable);
+        updated.get = get.or(updated.get);
+        updated.set = set.or(updated.set);
+        updated.enumerable = enumerable.or(updated.enumerable);
+        updated.configurable = configurable.or(updated.configurable);
+        self.0.borrow_mut().properties.insert(key, updated);
+        true
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryhasproperty>
+    #[must_use]
+    pub fn has_property(&self, key: &PropertyKey) -> bool {
+        if self.get_own_property(key).is_some() {
+            return true;
+        }
+        self.get_prototype_of().is_some_and(|prototype| prototype.has_property(key))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryget>
+    ///
+    /// A getter is called with `receiver` as `this`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a getter or a proxy trap throws,
+    /// or with a `TypeError` if a proxy is revoked or its trap breaks an
+    /// invariant.
+    pub fn get(&self, context: &mut Context, key: &PropertyKey, receiver: &Value) -> Result<Value, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.get(context, key, receiver));
+        }
+        // <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects-get-p-receiver>
+        if let (ObjectKind::ModuleNamespace(namespace), PropertyKey::String(name)) = (&self.0.borrow().kind, key) {
+            return namespace.get(name).unwrap_or(Ok(Value::Undefined));
+        }
+        let Some(descriptor) = self.get_own_property(key) else {
+            return self
+                .get_prototype_of()
+                .map_or(Ok(Value::Undefined), |prototype| prototype.get(context, key, receiver));
+        };
+        if let Some(value) = descriptor.value {
+            return Ok(value);
+        }
+        match descriptor.get {
+            Some(Value::Object(getter)) => getter.call(context, receiver, &[]),
+            _ => Ok(Value::Undefined),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryset>
+    ///
+    /// A setter is called with `receiver` as `this`, and a data property is
+    /// created or changed on `receiver`. Returns `false` if the property is
+    /// read-only.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a setter or a proxy trap throws,
+    /// with a `RangeError` for an invalid array length, or with
+    /// a `TypeError` if a proxy is revoked or its trap breaks an invariant.
+    pub fn set(&self, context: &mut Context, key: PropertyKey, value: Value, receiver: &Value) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.set(context, key, value, receiver));
+        }
+        // <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects-set-p-v-receiver>
+        if let ObjectKind::ModuleNamespace(_) = self.0.borrow().kind {
+            return Ok(false);
+        }
+        let own = match self.get_own_property(&key) {
+            Some(own) => own,
+            None => match self.get_prototype_of() {
+                Some(parent) => return parent.set(context, key, value, receiver),
+                None => PropertyDescriptor::data(Value::Undefined, true, true, true),
+            },
+        };
+        if !own.is_data_descriptor() {
+            let Some(Value::Object(setter)) = own.set else {
+                return Ok(false);
+            };
+            setter.call(context, receiver, &[value])?;
+            return Ok(true);
+        }
+        if own.writable == Some(false) {
+            return Ok(false);
+        }
+        let Value::Object(receiver) = receiver else {
+            return Ok(false);
+        };
+        // <https://262.ecma-international.org/14.0/#sec-arraysetlength>
+        // converts the length before anything else.
+        let value = if receiver.is_array() && key == PropertyKey::from("length") {
+            let length = value.to_uint32(context)?;
+            if !Number::equal(f64::from(length), value.to_number(context)?) {
+                return Err(JsError::new(ErrorKind::RangeError, "invalid array length"));
+            }
+            Value::from(length)
+        } else {
+            value
+        };
+        let Some(existing) = receiver.try_get_own_property(context, &key)? else {
+            return receiver.try_define_own_property(context, key, PropertyDescriptor::data(value, true, true, true));
+        };
+        if existing.is_accessor_descriptor() || existing.writable == Some(false) {
+            return Ok(false);
+        }
+        receiver.try_define_own_property(context, key, PropertyDescriptor { value: Some(value), ..PropertyDescriptor::default() })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-getmethod> of an object.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the property is neither
+    /// a function nor nullish, or with an exception a getter throws.
+    pub fn get_method(&self, context: &mut Context, key: &PropertyKey) -> Result<Option<Self>, JsError> {
+        match self.get(context, key, &Value::Object(self.clone()))? {
+            Value::Undefined | Value::Null => Ok(None),
+            Value::Object(function) if function.is_callable() => Ok(Some(function)),
+            _ => Err(JsError::type_error("property is not a function")),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinarydelete>, also
+    /// unmapping a property of an arguments object.
+    ///
+    /// Returns `false` if the property exists and is non-configurable.
+    #[must_use]
+    pub fn delete(&self, key: &PropertyKey) -> bool {
+        if let Some(target) = self.proxy_target() {
+            return target.delete(key);
+        }
+        match self.get_own_property(key) {
+            None => true,
+            Some(property) if property.configurable == Some(true) => {
+                let mut data = self.0.borrow_mut();
+                data.properties.remove(key);
+                data.order.retain(|existing| existing != key);
+                if let ObjectKind::Arguments(map) = &data.kind {
+                    map.delete(key);
+                }
+                true
+            },
+            Some(_) => false,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-ordinaryownpropertykeys>
+    ///
+    /// Array indices go first in ascending order, then other Strings and
+    /// Symbols, each in the order of property creation. A module namespace
+    /// object lists its exports in code unit order instead of Strings.
+    #[must_use]
+    pub fn own_property_keys(&self) -> Vec<PropertyKey> {
+        if let Some(target) = self.proxy_target() {
+            return target.own_property_keys();
+        }
+        let data = self.0.borrow();
+        if let ObjectKind::ModuleNamespace(namespace) = &data.kind {
+            let symbols = data.order.iter().filter(|key| matches!(key, PropertyKey::Symbol(_))).cloned();
+            return namespace.names().into_iter().map(PropertyKey::String).chain(symbols).collect();
+        }
+        let mut indices: Vec<_> = data.order.iter().filter_map(|key| key.as_array_index().map(|index| (index, key.clone()))).collect();
+        if let ObjectKind::String(string) = &data.kind {
+            let length = u32::try_from(string.len()).unwrap_or(u32::MAX);
+            indices.extend((0..length).map(|index| (index, PropertyKey::from(index.to_string()))));
+        }
+        indices.sort_unstable_by_key(|(index, _)| *index);
+        let strings = data.order.iter().filter(|key| matches!(key, PropertyKey::String(_)) && key.as_array_index().is_none());
+        let symbols = data.order.iter().filter(|key| matches!(key, PropertyKey::Symbol(_)));
+        indices.into_iter().map(|(_, key)| key).chain(strings.chain(symbols).cloned()).collect()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createnonenumerabledatapropertyorthrow>
+    /// on an object known to accept the property.
+    pub(crate) fn create_non_enumerable_data_property(&self, key: PropertyKey, value: Value) {
+        self.ordinary_define_own_property(key, PropertyDescriptor::data(value, true, false, true));
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createdataproperty>
+    pub fn create_data_property(&self, key: impl Into<PropertyKey>, value: impl Into<Value>) -> bool {
+        self.define_own_property(key.into(), PropertyDescriptor::data(value.into(), true, true, true))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-lengthofarraylike>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a getter or a conversion of
+    /// `length` throws.
+    pub fn length_of_array_like(&self, context: &mut Context) -> Result<u64, JsError> {
+        let length = self.get(context, &PropertyKey::from("length"), &Value::Object(self.clone()))?;
+        let length = length.to_number(context)?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 2^53 - 1
+        Ok(if length.is_nan() || length <= 0.0 { 0 } else { length.min(9_007_199_254_740_991.0) as u64 })
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-createlistfromarraylike>
+    /// accepting elements of any type.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a getter or a conversion of
+    /// `length` throws.
+    pub fn create_list_from_array_like(&self, context: &mut Context) -> Result<Vec<Value>, JsError> {
+        let length = self.length_of_array_like(context)?;
+        let receiver = Value::Object(self.clone());
+        (0..length).map(|index| self.get(context, &PropertyKey::from(index.to_string()), &receiver)).collect()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-privateelementfind>
+    #[must_use]
+    pub fn private_element_find(&self, name: &PrivateName) -> Option<PrivateElement> {
+        self.0.borrow().private_elements.iter().find(|element| element.key == *name).cloned()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-privatefieldadd> and
+    /// <https://262.ecma-international.org/14.0/#sec-privatemethodoraccessoradd>,
+    /// which differ only in the kind of the element.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object already has the
+    /// element, like when a constructor returning it is called again.
+    pub fn private_element_add(&self, element: PrivateElement) -> Result<(), JsError> {
+        if self.private_element_find(&element.key).is_some() {
+            return Err(JsError::type_error(format!("cannot initialize {} twice on the same object", element.key)));
+        }
+        heap::charge(mem::size_of::<PrivateElement>());
+        self.0.borrow_mut().private_elements.push(element);
+        Ok(())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-privateget>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object has no such
+    /// element or it is an accessor without a getter, or with an exception
+    /// the getter throws.
+    pub fn private_get(&self, context: &mut Context, name: &PrivateName) -> Result<Value, JsError> {
+        let Some(element) = self.private_element_find(name) else {
+            return Err(JsError::type_error(format!("cannot read private member {name} from an object whose class did not declare it")));
+        };
+        match element.kind {
+            PrivateElementKind::Field(value) | PrivateElementKind::Method(value) => Ok(value),
+            PrivateElementKind::Accessor { get: Some(Value::Object(getter)), .. } => getter.call(context, &Value::Object(self.clone()), &[]),
+            PrivateElementKind::Accessor { .. } => Err(JsError::type_error(format!("private accessor {name} has no getter"))),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-privateset>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if the object has no such
+    /// element, it is a method or an accessor without a setter, or with an
+    /// exception the setter throws.
+    pub fn private_set(&self, context: &mut Context, name: &PrivateName, value: Value) -> Result<(), JsError> {
+        let Some(element) = self.private_element_find(name) else {
+            return Err(JsError::type_error(format!("cannot write private member {name} to an object whose class did not declare it")));
+        };
+        match element.kind {
+            PrivateElementKind::Field(_) => {
+                if let Some(element) = self.0.borrow_mut().private_elements.iter_mut().find(|element| element.key == *name) {
+                    element.kind = PrivateElementKind::Field(value);
+                }
+                Ok(())
+            },
+            PrivateElementKind::Method(_) => Err(JsError::type_error(format!("private method {name} is not writable"))),
+            PrivateElementKind::Accessor { set: Some(Value::Object(setter)), .. } => {
+                setter.call(context, &Value::Object(self.clone()), &[value]).map(|_| ())
+            },
+            PrivateElementKind::Accessor { .. } => Err(JsError::type_error(format!("private accessor {name} has no setter"))),
+        }
+    }
+
+    /// `[[GetPrototypeOf]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_get_prototype_of(&self, context: &mut Context) -> Result<Option<Self>, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.get_prototype_of(context));
+        }
+        Ok(self.get_prototype_of())
+    }
+
+    /// `[[SetPrototypeOf]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_set_prototype_of(&self, context: &mut Context, prototype: Option<Self>) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.set_prototype_of(context, prototype));
+        }
+        Ok(self.set_prototype_of(prototype))
+    }
+
+    /// `[[IsExtensible]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_is_extensible(&self, context: &mut Context) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.is_extensible(context));
+        }
+        Ok(self.is_extensible())
+    }
+
+    /// `[[PreventExtensions]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_prevent_extensions(&self, context: &mut Context) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.prevent_extensions(context));
+        }
+        Ok(self.prevent_extensions())
+    }
+
+    /// `[[GetOwnProperty]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_get_own_property(&self, context: &mut Context, key: &PropertyKey) -> Result<Option<PropertyDescriptor>, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.get_own_property(context, key));
+        }
+        Ok(self.get_own_property(key))
+    }
+
+    /// `[[DefineOwnProperty]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_define_own_property(
+        &self,
+        context: &mut Context,
+        key: PropertyKey,
+        descriptor: PropertyDescriptor,
+    ) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.define_own_property(context, key, descriptor));
+        }
+        Ok(self.define_own_property(key, descriptor))
+    }
+
+    /// `[[HasProperty]]`, calling traps of proxies on the prototype chain.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if a proxy is revoked or its trap breaks an invariant.
+    pub fn try_has_property(&self, context: &mut Context, key: &PropertyKey) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.has_property(context, key));
+        }
+        if self.get_own_property(key).is_some() {
+            return Ok(true);
+        }
+        self.get_prototype_of().map_or(Ok(false), |prototype| prototype.try_has_property(context, key))
+    }
+
+    /// `[[Delete]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_delete(&self, context: &mut Context, key: &PropertyKey) -> Result<bool, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.delete(context, key));
+        }
+        Ok(self.delete(key))
+    }
+
+    /// `[[OwnPropertyKeys]]`, calling a trap of a proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with an exception a trap throws, or with
+    /// a `TypeError` if the proxy is revoked or the trap breaks an invariant.
+    pub fn try_own_property_keys(&self, context: &mut Context) -> Result<Vec<PropertyKey>, JsError> {
+        if let Some(proxy) = self.as_proxy() {
+            return context.nested_call(|context| proxy.own_property_keys(context));
+        }
+        Ok(self.own_property_keys())
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-stringgetownproperty>
+fn string_get_own_property(kind: &ObjectKind, key: &PropertyKey) -> Option<PropertyDescriptor> {
+    let ObjectKind::String(string) = kind else {
+        return None;
+    };
+    let index = usize::try_from(key.as_array_index()?).ok()?;
+    let unit = string.code_unit_at(index)?;
+    let value = Value::String(JsString::from_code_units(&[unit]));
+    Some(PropertyDescriptor::data(value, false, true, false))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-module-namespace-exotic-objects-getownproperty-p>
+/// of a String key.
+fn namespace_get_own_property(kind: &ObjectKind, key: &PropertyKey) -> Option<PropertyDescriptor> {
+    let (ObjectKind::ModuleNamespace(namespace), PropertyKey::String(name)) = (kind, key) else {
+        return None;
+    };
+    let value = namespace.get(name)?.unwrap_or(Value::Undefined);
+    Some(PropertyDescriptor::data(value, true, true, false))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-iscompatiblepropertydescriptor>
+pub(super) fn is_compatible_property_descriptor(
+    extensible: bool,
+    descriptor: &PropertyDescriptor,
+    current: Option<&PropertyDescriptor>,
+) -> bool {
+    current.map_or(extensible, |current| is_compatible(descriptor, current))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-iscompatiblepropertydescriptor>
+/// of a descriptor with an existing property, whatever the extensibility.
+fn is_compatible(descriptor: &PropertyDescriptor, current: &PropertyDescriptor) -> bool {
+    if current.configurable != Some(false) {
+        return true;
+    }
+    if descriptor.configurable == Some(true) {
+        return false;
+    }
+    if descriptor.enumerable.is_some() && descriptor.enumerable != current.enumerable {
+        return false;
+    }
+    if !descriptor.is_generic_descriptor() && descriptor.is_accessor_descriptor() != current.is_accessor_descriptor() {
+        return false;
+    }
+    if current.is_accessor_descriptor() {
+        return is_same_if_present(descriptor.get.as_ref(), current.get.as_ref())
+            && is_same_if_present(descriptor.set.as_ref(), current.set.as_ref());
+    }
+    if current.writable == Some(false) {
+        return descriptor.writable != Some(true) && is_same_if_present(descriptor.value.as_ref(), current.value.as_ref());
+    }
+    true
+}
+
+/// Whether a descriptor field is either absent or holds the same value as
+/// the current property.
+fn is_same_if_present(field: Option<&Value>, current: Option<&Value>) -> bool {
+    field.is_none_or(|field| current.is_some_and(|current| field.same_value(current)))
+}
+
+impl PartialEq for Object {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Object {}
+
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.0).hash(state);
+    }
+}
+
+impl Trace for RefCell<ObjectData> {
+    fn trace(&self, tracer: &mut Tracer) {
+        let data = self.borrow();
+        if let Some(prototype) = &data.prototype {
+            tracer.object(prototype);
+        }
+        for property in data.properties.values() {
+            for value in [&property.value, &property.get, &property.set].into_iter().flatten() {
+                tracer.value(value);
+            }
+        }
+        for element in &data.private_elements {
+            element.trace(tracer);
+        }
+        // Internal slots shared with something else than the object are
+        // left alone, keeping what they refer to alive.
+        match &data.kind {
+            ObjectKind::Function(function) if Rc::strong_count(function) == 1 => function.trace(tracer),
+            ObjectKind::ArrayIterator(iterator) if Rc::strong_count(iterator) == 1 => iterator.trace(tracer),
+            ObjectKind::Arguments(map) if Rc::strong_count(map) == 1 => map.trace(tracer),
+            ObjectKind::Generator(generator) if Rc::strong_count(generator) == 1 => generator.trace(tracer),
+            ObjectKind::Promise(promise) if Rc::strong_count(promise) == 1 => promise.trace(tracer),
+            ObjectKind::Proxy(proxy) if Rc::strong_count(proxy) == 1 => proxy.trace(tracer),
+            _ => {},
+        }
+    }
+
+    fn clear(&self) {
+        let mut data = self.borrow_mut();
+        data.prototype = None;
+        data.properties.clear();
+        data.order.clear();
+        data.private_elements.clear();
+        data.kind = ObjectKind::Ordinary;
+    }
+}
+
+impl fmt::Debug for Object {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Properties may refer back to the object, so they are left out.
+        write!(formatter, "Object({:p})", Rc::as_ptr(&self.0))
+    }
+}
@@ -0,0 +1,144 @@
+//! <https://262.ecma-international.org/14.0/#sec-private-names> and
+//! <https://262.ecma-international.org/14.0/#sec-privateenvironment-records>.
+
+use super::heap::Tracer;
+use super::Value;
+use std::fmt;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#sec-private-names>, created
+/// anew by each evaluation of a class body; clones are the same name.
+#[derive(Clone)]
+pub struct PrivateName(Rc<str>);
+
+impl PrivateName {
+    /// A new name unequal to all others, `description` included.
+    #[must_use]
+    pub fn new(description: &str) -> Self {
+        Self(Rc::from(description))
+    }
+
+    /// `[[Description]]`, the `PrivateIdentifier` with its `#`.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for PrivateName {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PrivateName {}
+
+impl fmt::Debug for PrivateName {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "PrivateName({}, {:p})", self.0, Rc::as_ptr(&self.0))
+    }
+}
+
+impl fmt::Display for PrivateName {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+struct PrivateEnvironmentData {
+    outer: Option<PrivateEnvironment>,
+    names: Vec<PrivateName>,
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-privateenvironment-records>
+/// of a class body, holding the names it declares.
+#[derive(Clone)]
+pub struct PrivateEnvironment(Rc<PrivateEnvironmentData>);
+
+impl PrivateEnvironment {
+    /// <https://262.ecma-international.org/14.0/#sec-newprivateenvironment>
+    /// with fresh names for each of `descriptions`.
+    #[must_use]
+    pub fn new(outer: Option<Self>, descriptions: impl IntoIterator<Item = String>) -> Self {
+        let mut names: Vec<PrivateName> = Vec::new();
+        for description in descriptions {
+            // A getter and a setter share a name.
+            if !names.iter().any(|name| name.description() == description) {
+                names.push(PrivateName::new(&description));
+            }
+        }
+        Self(Rc::new(PrivateEnvironmentData { outer, names }))
+    }
+
+    /// `[[OuterPrivateEnvironment]]`
+    #[must_use]
+    pub fn outer(&self) -> Option<&Self> {
+        self.0.outer.as_ref()
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-resolve-private-identifier>
+    ///
+    /// `None` is impossible for code that passed early errors, which check
+    /// that every referenced name is declared by an enclosing class.
+    #[must_use]
+    pub fn resolve(&self, identifier: &str) -> Option<PrivateName> {
+        let mut environment = Some(self);
+        while let Some(current) = environment {
+            if let Some(name) = current.0.names.iter().find(|name| name.description() == identifier) {
+                return Some(name.clone());
+            }
+            environment = current.outer();
+        }
+        None
+    }
+}
+
+impl PartialEq for PrivateEnvironment {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PrivateEnvironment {}
+
+impl fmt::Debug for PrivateEnvironment {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "PrivateEnvironment({:p})", Rc::as_ptr(&self.0))
+    }
+}
+
+/// `[[Kind]]` of a
+/// <https://262.ecma-international.org/14.0/#sec-privateelement-specification-type>
+/// with the fields of the kind.
+#[derive(Clone, Debug)]
+pub enum PrivateElementKind {
+    /// A field with its `[[Value]]`.
+    Field(Value),
+    /// A method with its function in `[[Value]]`.
+    Method(Value),
+    /// An accessor with `[[Get]]` and `[[Set]]`, either of which may be
+    /// missing.
+    Accessor { get: Option<Value>, set: Option<Value> },
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-privateelement-specification-type>
+#[derive(Clone, Debug)]
+pub struct PrivateElement {
+    /// `[[Key]]`
+    pub key: PrivateName,
+    pub kind: PrivateElementKind,
+}
+
+impl PrivateElement {
+    /// Report values of the element to the heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        match &self.kind {
+            PrivateElementKind::Field(value) | PrivateElementKind::Method(value) => tracer.value(value),
+            PrivateElementKind::Accessor { get, set } => {
+                for value in [get, set].into_iter().flatten() {
+                    tracer.value(value);
+                }
+            },
+        }
+    }
+}
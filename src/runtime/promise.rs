@@ -0,0 +1,567 @@
+//! <https://262.ecma-international.org/14.0/#sec-promise-objects>
+//!
+//! Reactions run as jobs of [`Context::enqueue_promise_job`], so promises
+//! settle only as the host runs jobs.
+
+use super::{
+    Context,
+    IteratorRecord,
+    JsError,
+    Object,
+    ObjectKind,
+    PropertyDescriptor,
+    PropertyKey,
+    Symbol,
+    Tracer,
+    Value,
+    WellKnownSymbol,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// `[[PromiseState]]` of a promise with its `[[PromiseResult]]`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromiseState {
+    Pending,
+    Fulfilled(Value),
+    Rejected(Value),
+}
+
+/// `[[Type]]` of a `PromiseReaction` Record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ReactionType {
+    Fulfill,
+    Reject,
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promisereaction-records>
+#[derive(Clone, Debug)]
+struct PromiseReaction {
+    capability: Option<PromiseCapability>,
+    kind: ReactionType,
+    /// `None` for a reaction passing the value or the reason through.
+    handler: Option<Object>,
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promisecapability-records>
+#[derive(Clone, Debug)]
+pub struct PromiseCapability {
+    pub promise: Object,
+    pub resolve: Object,
+    pub reject: Object,
+}
+
+impl PromiseCapability {
+    /// <https://262.ecma-international.org/14.0/#sec-newpromisecapability>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if `constructor` is not
+    /// a constructor or does not pass callable functions to its executor,
+    /// or with an exception the constructor throws.
+    pub fn new(context: &mut Context, constructor: &Value) -> Result<Self, JsError> {
+        let Some(constructor) = constructor.as_object().filter(|constructor| constructor.is_constructor()) else {
+            return Err(JsError::type_error("promise capability of a non-constructor"));
+        };
+        let functions = Rc::new(RefCell::new((Value::Undefined, Value::Undefined)));
+        let slots = Rc::clone(&functions);
+        let executor = Object::builtin_function(function_prototype(context), "", 2, move |_, _, arguments| {
+            let mut slots = slots.borrow_mut();
+            if !matches!(*slots, (Value::Undefined, Value::Undefined)) {
+                return Err(JsError::type_error("promise executor is called twice"));
+            }
+            *slots = (argument(arguments, 0), argument(arguments, 1));
+            Ok(Value::Undefined)
+        });
+        let promise = constructor.construct(context, &[Value::Object(executor)], None)?;
+        let (resolve, reject) = functions.replace((Value::Undefined, Value::Undefined));
+        let (Some(resolve), Some(reject)) = (callable(resolve), callable(reject)) else {
+            return Err(JsError::type_error("promise resolving functions are not callable"));
+        };
+        Ok(Self { promise, resolve, reject })
+    }
+
+    /// Reject the promise with an error unless ECMAScript code cannot catch
+    /// it, like `IfAbruptRejectPromise` does, returning the promise.
+    fn reject_with(&self, context: &mut Context, error: JsError) -> Result<Value, JsError> {
+        let reason = catchable_value(context, error)?;
+        self.reject.call(context, &Value::Undefined, &[reason])?;
+        Ok(Value::Object(self.promise.clone()))
+    }
+}
+
+/// Internal slots of
+/// <https://262.ecma-international.org/14.0/#sec-properties-of-promise-instances>.
+#[derive(Debug)]
+pub struct Promise {
+    state: RefCell<PromiseState>,
+    /// `[[PromiseFulfillReactions]]` and `[[PromiseRejectReactions]]`
+    /// while pending.
+    reactions: RefCell<Vec<(PromiseReaction, PromiseReaction)>>,
+}
+
+impl Promise {
+    /// A pending promise object with a prototype.
+    #[must_use]
+    pub fn create(prototype: Option<Object>) -> Object {
+        let promise = Self { state: RefCell::new(PromiseState::Pending), reactions: RefCell::default() };
+        Object::with_kind(prototype, ObjectKind::Promise(Rc::new(promise)))
+    }
+
+    #[must_use]
+    pub fn state(&self) -> PromiseState {
+        self.state.borrow().clone()
+    }
+
+    /// Report the result and reactions to the heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        if let PromiseState::Fulfilled(value) | PromiseState::Rejected(value) = &*self.state.borrow() {
+            tracer.value(value);
+        }
+        for (fulfill, reject) in &*self.reactions.borrow() {
+            for reaction in [fulfill, reject] {
+                if let Some(PromiseCapability { promise, resolve, reject }) = &reaction.capability {
+                    tracer.object(promise);
+                    tracer.object(resolve);
+                    tracer.object(reject);
+                }
+                if let Some(handler) = &reaction.handler {
+                    tracer.object(handler);
+                }
+            }
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-fulfillpromise> and
+    /// <https://262.ecma-international.org/14.0/#sec-rejectpromise>.
+    fn settle(&self, context: &mut Context, state: PromiseState) {
+        let (kind, argument) = match &state {
+            PromiseState::Fulfilled(value) => (ReactionType::Fulfill, value.clone()),
+            PromiseState::Rejected(reason) => (ReactionType::Reject, reason.clone()),
+            PromiseState::Pending => unreachable!("a promise settles to a fulfilled or a rejected state"),
+        };
+        self.state.replace(state);
+        // <https://262.ecma-international.org/14.0/#sec-triggerpromisereactions>
+        for (fulfill, reject) in self.reactions.take() {
+            let reaction = if kind == ReactionType::Fulfill { fulfill } else { reject };
+            enqueue_reaction_job(context, reaction, argument.clone());
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-performpromisethen>
+    fn perform_then(
+        &self,
+        context: &mut Context,
+        on_fulfilled: &Value,
+        on_rejected: &Value,
+        capability: Option<PromiseCapability>,
+    ) {
+        let fulfill = PromiseReaction { capability: capability.clone(), kind: ReactionType::Fulfill, handler: callable(on_fulfilled.clone()) };
+        let reject = PromiseReaction { capability, kind: ReactionType::Reject, handler: callable(on_rejected.clone()) };
+        match self.state() {
+            PromiseState::Pending => self.reactions.borrow_mut().push((fulfill, reject)),
+            PromiseState::Fulfilled(value) => enqueue_reaction_job(context, fulfill, value),
+            PromiseState::Rejected(reason) => enqueue_reaction_job(context, reject, reason),
+        }
+    }
+}
+
+/// Create `%Promise%` with its static methods and `%Promise.prototype%`
+/// methods, linking the two.
+pub(super) fn create_promise_constructor(function_prototype: &Object, prototype: &Object) -> Object {
+    let fallback = prototype.clone();
+    let constructor = Object::native_constructor(Some(function_prototype.clone()), "Promise", 1, move |context, arguments, new_target| {
+        construct_promise(context, arguments, new_target, &fallback)
+    });
+    let method = |object: &Object, name: &str, length, function: fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError>| {
+        let function = Object::builtin_function(Some(function_prototype.clone()), name, length, function);
+        let descriptor = PropertyDescriptor::data(Value::Object(function), true, false, true);
+        assert!(object.define_own_property(PropertyKey::from(name), descriptor), "a fresh object is extensible");
+    };
+    method(&constructor, "all", 1, promise_all);
+    method(&constructor, "race", 1, promise_race);
+    method(&constructor, "reject", 1, promise_reject);
+    method(&constructor, "resolve", 1, promise_resolve);
+    let species = Object::builtin_function(Some(function_prototype.clone()), "get [Symbol.species]", 0, |_, this, _| Ok(this.clone()));
+    let species = PropertyDescriptor {
+        get: Some(Value::Object(species)),
+        set: Some(Value::Undefined),
+        enumerable: Some(false),
+        configurable: Some(true),
+        ..PropertyDescriptor::default()
+    };
+    assert!(constructor.define_own_property(Symbol::well_known(WellKnownSymbol::Species).into(), species), "a fresh object is extensible");
+
+    method(prototype, "catch", 1, promise_prototype_catch);
+    method(prototype, "finally", 1, promise_prototype_finally);
+    method(prototype, "then", 2, promise_prototype_then);
+    let define = |object: &Object, key: PropertyKey, value: Value, writable| {
+        let descriptor = PropertyDescriptor::data(value, writable, false, true);
+        assert!(object.define_own_property(key, descriptor), "a fresh object is extensible");
+    };
+    define(&constructor, PropertyKey::from("prototype"), Value::Object(prototype.clone()), false);
+    define(prototype, PropertyKey::from("constructor"), Value::Object(constructor.clone()), true);
+    define(prototype, Symbol::well_known(WellKnownSymbol::ToStringTag).into(), Value::from("Promise"), false);
+    constructor
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise-executor>
+fn construct_promise(
+    context: &mut Context,
+    arguments: &[Value],
+    new_target: Option<&Object>,
+    fallback: &Object,
+) -> Result<Object, JsError> {
+    let Some(new_target) = new_target else {
+        return Err(JsError::type_error("Promise constructor cannot be invoked without new"));
+    };
+    let Some(executor) = callable(argument(arguments, 0)) else {
+        return Err(JsError::type_error("Promise resolver is not a function"));
+    };
+    let prototype = match new_target.get(context, &PropertyKey::from("prototype"), &Value::Object(new_target.clone()))? {
+        Value::Object(prototype) => prototype,
+        _ => fallback.clone(),
+    };
+    let promise = Promise::create(Some(prototype));
+    let (resolve, reject) = create_resolving_functions(context, &promise);
+    if let Err(error) = executor.call(context, &Value::Undefined, &[Value::Object(resolve), Value::Object(reject.clone())]) {
+        let reason = catchable_value(context, error)?;
+        reject.call(context, &Value::Undefined, &[reason])?;
+    }
+    Ok(promise)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-createresolvingfunctions>
+fn create_resolving_functions(context: &Context, promise: &Object) -> (Object, Object) {
+    let already_resolved = Rc::new(Cell::new(false));
+    let (target, resolved) = (promise.clone(), Rc::clone(&already_resolved));
+    let resolve = Object::builtin_function(function_prototype(context), "", 1, move |context, _, arguments| {
+        if !resolved.replace(true) {
+            resolve_promise(context, &target, argument(arguments, 0))?;
+        }
+        Ok(Value::Undefined)
+    });
+    let (target, resolved) = (promise.clone(), already_resolved);
+    let reject = Object::builtin_function(function_prototype(context), "", 1, move |context, _, arguments| {
+        if !resolved.replace(true) {
+            settle(context, &target, PromiseState::Rejected(argument(arguments, 0)));
+        }
+        Ok(Value::Undefined)
+    });
+    (resolve, reject)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise-resolve-functions>
+/// once the promise is not resolved yet.
+fn resolve_promise(context: &mut Context, promise: &Object, resolution: Value) -> Result<(), JsError> {
+    let Value::Object(thenable) = &resolution else {
+        settle(context, promise, PromiseState::Fulfilled(resolution));
+        return Ok(());
+    };
+    if thenable == promise {
+        let reason = JsError::type_error("a promise cannot be resolved with itself").into_value(context);
+        settle(context, promise, PromiseState::Rejected(reason));
+        return Ok(());
+    }
+    let then = match thenable.get(context, &PropertyKey::from("then"), &resolution) {
+        Ok(then) => then,
+        Err(error) => {
+            let reason = catchable_value(context, error)?;
+            settle(context, promise, PromiseState::Rejected(reason));
+            return Ok(());
+        },
+    };
+    let Some(then) = callable(then) else {
+        settle(context, promise, PromiseState::Fulfilled(resolution));
+        return Ok(());
+    };
+    // <https://262.ecma-international.org/14.0/#sec-newpromiseresolvethenablejob>
+    let (promise, thenable) = (promise.clone(), thenable.clone());
+    let job = move |context: &mut Context| {
+        let (resolve, reject) = create_resolving_functions(context, &promise);
+        let arguments = [Value::Object(resolve), Value::Object(reject.clone())];
+        if let Err(error) = then.call(context, &Value::Object(thenable), &arguments) {
+            let reason = catchable_value(context, error)?;
+            reject.call(context, &Value::Undefined, &[reason])?;
+        }
+        Ok(())
+    };
+    let realm = context.current_realm().cloned();
+    context.enqueue_promise_job(Box::new(job), realm);
+    Ok(())
+}
+
+/// Fulfill or reject a promise object.
+fn settle(context: &mut Context, promise: &Object, state: PromiseState) {
+    if let ObjectKind::Promise(data) = promise.kind() {
+        data.settle(context, state);
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-newpromisereactionjob>
+fn enqueue_reaction_job(context: &mut Context, reaction: PromiseReaction, argument: Value) {
+    let job = move |context: &mut Context| {
+        let result = match (&reaction.handler, reaction.kind) {
+            (None, ReactionType::Fulfill) => Ok(argument),
+            (None, ReactionType::Reject) => Err(argument),
+            (Some(handler), _) => match handler.call(context, &Value::Undefined, &[argument]) {
+                Ok(value) => Ok(value),
+                Err(error) => Err(catchable_value(context, error)?),
+            },
+        };
+        let Some(capability) = reaction.capability else {
+            return Ok(());
+        };
+        match result {
+            Ok(value) => capability.resolve.call(context, &Value::Undefined, &[value])?,
+            Err(reason) => capability.reject.call(context, &Value::Undefined, &[reason])?,
+        };
+        Ok(())
+    };
+    let realm = context.current_realm().cloned();
+    context.enqueue_promise_job(Box::new(job), realm);
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise-resolve>
+pub fn promise_resolve_with(context: &mut Context, constructor: &Object, value: Value) -> Result<Value, JsError> {
+    if let Value::Object(object) = &value {
+        if matches!(object.kind(), ObjectKind::Promise(_)) {
+            let value_constructor = object.get(context, &PropertyKey::from("constructor"), &value)?;
+            if value_constructor.same_value(&Value::Object(constructor.clone())) {
+                return Ok(value);
+            }
+        }
+    }
+    let capability = PromiseCapability::new(context, &Value::Object(constructor.clone()))?;
+    capability.resolve.call(context, &Value::Undefined, &[value])?;
+    Ok(Value::Object(capability.promise))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-performpromisethen>
+/// without a result capability, doing nothing for other objects than
+/// promises.
+pub fn perform_promise_then(context: &mut Context, promise: &Value, on_fulfilled: Object, on_rejected: Object) {
+    if let Some(ObjectKind::Promise(data)) = promise.as_object().map(Object::kind) {
+        data.perform_then(context, &Value::Object(on_fulfilled), &Value::Object(on_rejected), None);
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.resolve>
+fn promise_resolve(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let Value::Object(constructor) = this else {
+        return Err(JsError::type_error("Promise.resolve called on a non-object"));
+    };
+    promise_resolve_with(context, constructor, argument(arguments, 0))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.reject>
+fn promise_reject(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let capability = PromiseCapability::new(context, this)?;
+    capability.reject.call(context, &Value::Undefined, &[argument(arguments, 0)])?;
+    Ok(Value::Object(capability.promise))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.all>
+fn promise_all(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    combine(context, this, &argument(arguments, 0), perform_promise_all)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.race>
+fn promise_race(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    combine(context, this, &argument(arguments, 0), |context, iterator, constructor, capability, resolve| {
+        // <https://262.ecma-international.org/14.0/#sec-performpromiserace>
+        while let Some(next) = iterator.step_value(context)? {
+            let next_promise = resolve.call(context, constructor, &[next])?;
+            let handlers = [Value::Object(capability.resolve.clone()), Value::Object(capability.reject.clone())];
+            invoke(context, &next_promise, "then", &handlers)?;
+        }
+        Ok(Value::Object(capability.promise.clone()))
+    })
+}
+
+/// Steps of `Promise.all` and `Promise.race` around going through
+/// promises of an iterable.
+fn combine(
+    context: &mut Context,
+    this: &Value,
+    iterable: &Value,
+    perform: impl FnOnce(&mut Context, &mut IteratorRecord, &Value, &PromiseCapability, &Object) -> Result<Value, JsError>,
+) -> Result<Value, JsError> {
+    let capability = PromiseCapability::new(context, this)?;
+    // <https://262.ecma-international.org/14.0/#sec-getpromiseresolve>
+    let resolve = match this.to_object(context)?.get(context, &PropertyKey::from("resolve"), this) {
+        Ok(resolve) => match callable(resolve) {
+            Some(resolve) => resolve,
+            None => return capability.reject_with(context, JsError::type_error("Promise resolve is not a function")),
+        },
+        Err(error) => return capability.reject_with(context, error),
+    };
+    let mut iterator = match IteratorRecord::from_iterable(context, iterable) {
+        Ok(iterator) => iterator,
+        Err(error) => return capability.reject_with(context, error),
+    };
+    match perform(context, &mut iterator, this, &capability, &resolve) {
+        Ok(promise) => Ok(promise),
+        Err(error) if iterator.done => capability.reject_with(context, error),
+        Err(error) => match iterator.close(context, Err::<Value, _>(error)) {
+            Ok(promise) => Ok(promise),
+            Err(error) => capability.reject_with(context, error),
+        },
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-performpromiseall>
+fn perform_promise_all(
+    context: &mut Context,
+    iterator: &mut IteratorRecord,
+    constructor: &Value,
+    capability: &PromiseCapability,
+    resolve: &Object,
+) -> Result<Value, JsError> {
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let remaining = Rc::new(Cell::new(1_usize));
+    while let Some(next) = iterator.step_value(context)? {
+        let index = values.borrow().len();
+        values.borrow_mut().push(Value::Undefined);
+        let next_promise = resolve.call(context, constructor, &[next])?;
+        // <https://262.ecma-international.org/14.0/#sec-promise.all-resolve-element-functions>
+        let already_called = Cell::new(false);
+        let (element_values, element_remaining, element_capability) = (Rc::clone(&values), Rc::clone(&remaining), capability.clone());
+        let on_fulfilled = Object::builtin_function(function_prototype(context), "", 1, move |context, _, arguments| {
+            if already_called.replace(true) {
+                return Ok(Value::Undefined);
+            }
+            element_values.borrow_mut()[index] = argument(arguments, 0);
+            element_remaining.set(element_remaining.get() - 1);
+            if element_remaining.get() == 0 {
+                resolve_with_array(context, &element_capability, &element_values)?;
+            }
+            Ok(Value::Undefined)
+        });
+        remaining.set(remaining.get() + 1);
+        let handlers = [Value::Object(on_fulfilled), Value::Object(capability.reject.clone())];
+        invoke(context, &next_promise, "then", &handlers)?;
+    }
+    remaining.set(remaining.get() - 1);
+    if remaining.get() == 0 {
+        resolve_with_array(context, capability, &values)?;
+    }
+    Ok(Value::Object(capability.promise.clone()))
+}
+
+/// Resolve the promise of `Promise.all` with an array of values of all
+/// promises.
+fn resolve_with_array(context: &mut Context, capability: &PromiseCapability, values: &RefCell<Vec<Value>>) -> Result<(), JsError> {
+    let prototype = context.current_realm().map(|realm| realm.intrinsics().array_prototype.clone());
+    let array = Object::new_array(prototype, values.borrow().clone());
+    capability.resolve.call(context, &Value::Undefined, &[Value::Object(array)])?;
+    Ok(())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.prototype.then>
+fn promise_prototype_then(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let Some((promise, data)) = this.as_object().and_then(|object| match object.kind() {
+        ObjectKind::Promise(data) => Some((object, data)),
+        _ => None,
+    }) else {
+        return Err(JsError::type_error("Promise.prototype.then called on a non-promise"));
+    };
+    let constructor = species_constructor(context, promise)?;
+    let capability = PromiseCapability::new(context, &constructor)?;
+    data.perform_then(context, &argument(arguments, 0), &argument(arguments, 1), Some(capability.clone()));
+    Ok(Value::Object(capability.promise))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.prototype.catch>
+fn promise_prototype_catch(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    invoke(context, this, "then", &[Value::Undefined, argument(arguments, 0)])
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-promise.prototype.finally>
+fn promise_prototype_finally(context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let Value::Object(promise) = this else {
+        return Err(JsError::type_error("Promise.prototype.finally called on a non-object"));
+    };
+    let Value::Object(constructor) = species_constructor(context, promise)? else {
+        unreachable!("species constructors are objects");
+    };
+    let on_finally = argument(arguments, 0);
+    let Some(on_finally) = callable(on_finally.clone()) else {
+        return invoke(context, this, "then", &[on_finally.clone(), on_finally]);
+    };
+    // Both handlers wait for the promise `onFinally` returns, then pass the
+    // original value or reason on.
+    let finally_handler = |settle_with: fn(Value) -> Result<Value, JsError>| {
+        let (on_finally, constructor) = (on_finally.clone(), constructor.clone());
+        Object::builtin_function(function_prototype(context), "", 1, move |context, _, arguments| {
+            let result = on_finally.call(context, &Value::Undefined, &[])?;
+            let promise = promise_resolve_with(context, &constructor, result)?;
+            let value = argument(arguments, 0);
+            let thunk = Object::builtin_function(function_prototype(context), "", 0, move |_, _, _| settle_with(value.clone()));
+            invoke(context, &promise, "then", &[Value::Object(thunk)])
+        })
+    };
+    let then_finally = finally_handler(Ok);
+    let catch_finally = finally_handler(|reason| Err(JsError::thrown(reason)));
+    invoke(context, this, "then", &[Value::Object(then_finally), Value::Object(catch_finally)])
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-speciesconstructor> with
+/// `%Promise%` of the current realm as the default.
+fn species_constructor(context: &mut Context, object: &Object) -> Result<Value, JsError> {
+    let default = context_promise(context)
+        .map(Value::Object)
+        .ok_or_else(|| JsError::type_error("no default constructor outside of a realm"));
+    let constructor = object.get(context, &PropertyKey::from("constructor"), &Value::Object(object.clone()))?;
+    let Value::Object(constructor) = constructor else {
+        return match constructor {
+            Value::Undefined => default,
+            _ => Err(JsError::type_error("constructor is not an object")),
+        };
+    };
+    let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::Species));
+    match constructor.get(context, &key, &Value::Object(constructor.clone()))? {
+        Value::Undefined | Value::Null => default,
+        Value::Object(species) if species.is_constructor() => Ok(Value::Object(species)),
+        _ => Err(JsError::type_error("Symbol.species is not a constructor")),
+    }
+}
+
+/// `%Promise%` of the current realm.
+fn context_promise(context: &Context) -> Option<Object> {
+    context.current_realm().map(|realm| realm.intrinsics().promise.clone())
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-invoke>
+fn invoke(context: &mut Context, value: &Value, name: &str, arguments: &[Value]) -> Result<Value, JsError> {
+    let function = value.to_object(context)?.get(context, &PropertyKey::from(name), value)?;
+    let Some(function) = callable(function) else {
+        return Err(JsError::type_error(format!("{name} is not a function")));
+    };
+    function.call(context, value, arguments)
+}
+
+/// The value ECMAScript code catches for an error, or the error itself if
+/// code cannot catch it.
+fn catchable_value(context: &Context, error: JsError) -> Result<Value, JsError> {
+    if error.kind.is_catchable() {
+        Ok(error.into_value(context))
+    } else {
+        Err(error)
+    }
+}
+
+fn callable(value: Value) -> Option<Object> {
+    match value {
+        Value::Object(object) if object.is_callable() => Some(object),
+        _ => None,
+    }
+}
+
+pub(super) fn argument(arguments: &[Value], index: usize) -> Value {
+    arguments.get(index).cloned().unwrap_or(Value::Undefined)
+}
+
+pub(super) fn function_prototype(context: &Context) -> Option<Object> {
+    context.current_realm().map(|realm| realm.intrinsics().function_prototype.clone())
+}
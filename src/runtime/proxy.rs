@@ -0,0 +1,426 @@
+//! <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots>
+//! and <https://262.ecma-international.org/14.0/#sec-proxy-objects>.
+//!
+//! Internal methods of a proxy call traps of its handler and check what
+//! they report against the target, so each takes a context and may fail.
+
+use super::object::is_compatible_property_descriptor;
+use super::promise::{argument, function_prototype};
+use super::{Context, JsError, Object, ObjectKind, PropertyDescriptor, PropertyKey, Tracer, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Internal slots of a proxy exotic object.
+#[derive(Debug)]
+pub struct Proxy {
+    /// `[[ProxyTarget]]` and `[[ProxyHandler]]`, or `None` once the proxy
+    /// is revoked.
+    slots: RefCell<Option<(Object, Object)>>,
+    /// Whether the proxy has `[[Call]]`, copied from the target on creation.
+    is_callable: bool,
+    /// Whether the proxy has `[[Construct]]`, copied from the target on
+    /// creation.
+    is_constructor: bool,
+}
+
+impl Proxy {
+    /// <https://262.ecma-international.org/14.0/#sec-proxycreate>
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` with a `TypeError` if `target` or `handler` is not
+    /// an object.
+    pub fn create(target: &Value, handler: &Value) -> Result<Object, JsError> {
+        let (Value::Object(target), Value::Object(handler)) = (target, handler) else {
+            return Err(JsError::type_error("proxy target and handler must be objects"));
+        };
+        let proxy = Self {
+            slots: RefCell::new(Some((target.clone(), handler.clone()))),
+            is_callable: target.is_callable(),
+            is_constructor: target.is_constructor(),
+        };
+        Ok(Object::with_kind(None, ObjectKind::Proxy(Rc::new(proxy))))
+    }
+
+    #[must_use]
+    pub const fn is_callable(&self) -> bool {
+        self.is_callable
+    }
+
+    #[must_use]
+    pub const fn is_constructor(&self) -> bool {
+        self.is_constructor
+    }
+
+    /// `[[ProxyTarget]]`, or `None` if the proxy is revoked.
+    #[must_use]
+    pub fn target(&self) -> Option<Object> {
+        self.slots.borrow().as_ref().map(|(target, _)| target.clone())
+    }
+
+    /// Make all further traps of the proxy throw a `TypeError`.
+    pub fn revoke(&self) {
+        self.slots.replace(None);
+    }
+
+    /// Report the target and the handler to the heap.
+    pub(crate) fn trace(&self, tracer: &mut Tracer) {
+        if let Some((target, handler)) = &*self.slots.borrow() {
+            tracer.object(target);
+            tracer.object(handler);
+        }
+    }
+
+    /// The target, the handler and its trap of a name if it has one.
+    fn trap(&self, context: &mut Context, name: &str) -> Result<(Object, Object, Option<Object>), JsError> {
+        let slots = self.slots.borrow().clone();
+        let Some((target, handler)) = slots else {
+            return Err(JsError::type_error(format!("{name} on a revoked proxy")));
+        };
+        let trap = handler.get_method(context, &PropertyKey::from(name))?;
+        Ok((target, handler, trap))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-getprototypeof>
+    pub(crate) fn get_prototype_of(&self, context: &mut Context) -> Result<Option<Object>, JsError> {
+        let (target, handler, trap) = self.trap(context, "getPrototypeOf")?;
+        let Some(trap) = trap else {
+            return target.try_get_prototype_of(context);
+        };
+        let prototype = match trap.call(context, &Value::Object(handler), &[Value::Object(target.clone())])? {
+            Value::Object(prototype) => Some(prototype),
+            Value::Null => None,
+            _ => return Err(JsError::type_error("getPrototypeOf trap returned neither an object nor null")),
+        };
+        if !target.try_is_extensible(context)? && prototype != target.try_get_prototype_of(context)? {
+            return Err(JsError::type_error("getPrototypeOf trap changed the prototype of a non-extensible target"));
+        }
+        Ok(prototype)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-setprototypeof-v>
+    pub(crate) fn set_prototype_of(&self, context: &mut Context, prototype: Option<Object>) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "setPrototypeOf")?;
+        let Some(trap) = trap else {
+            return target.try_set_prototype_of(context, prototype);
+        };
+        let value = prototype.clone().map_or(Value::Null, Value::Object);
+        if !trap.call(context, &Value::Object(handler), &[Value::Object(target.clone()), value])?.to_boolean() {
+            return Ok(false);
+        }
+        if !target.try_is_extensible(context)? && prototype != target.try_get_prototype_of(context)? {
+            return Err(JsError::type_error("setPrototypeOf trap changed the prototype of a non-extensible target"));
+        }
+        Ok(true)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-isextensible>
+    pub(crate) fn is_extensible(&self, context: &mut Context) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "isExtensible")?;
+        let Some(trap) = trap else {
+            return target.try_is_extensible(context);
+        };
+        let is_extensible = trap.call(context, &Value::Object(handler), &[Value::Object(target.clone())])?.to_boolean();
+        if is_extensible != target.try_is_extensible(context)? {
+            return Err(JsError::type_error("isExtensible trap disagrees with the target"));
+        }
+        Ok(is_extensible)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-preventextensions>
+    pub(crate) fn prevent_extensions(&self, context: &mut Context) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "preventExtensions")?;
+        let Some(trap) = trap else {
+            return target.try_prevent_extensions(context);
+        };
+        let is_prevented = trap.call(context, &Value::Object(handler), &[Value::Object(target.clone())])?.to_boolean();
+        if is_prevented && target.try_is_extensible(context)? {
+            return Err(JsError::type_error("preventExtensions trap left the target extensible"));
+        }
+        Ok(is_prevented)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-getownproperty-p>
+    pub(crate) fn get_own_property(&self, context: &mut Context, key: &PropertyKey) -> Result<Option<PropertyDescriptor>, JsError> {
+        let (target, handler, trap) = self.trap(context, "getOwnPropertyDescriptor")?;
+        let Some(trap) = trap else {
+            return target.try_get_own_property(context, key);
+        };
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone())];
+        let result = trap.call(context, &Value::Object(handler), &arguments)?;
+        if !matches!(result, Value::Object(_) | Value::Undefined) {
+            return Err(JsError::type_error("getOwnPropertyDescriptor trap returned neither an object nor undefined"));
+        }
+        let target_descriptor = target.try_get_own_property(context, key)?;
+        if matches!(result, Value::Undefined) {
+            let Some(target_descriptor) = target_descriptor else {
+                return Ok(None);
+            };
+            if target_descriptor.configurable == Some(false) {
+                return Err(JsError::type_error(format!("getOwnPropertyDescriptor trap hid non-configurable property {key}")));
+            }
+            if !target.try_is_extensible(context)? {
+                return Err(JsError::type_error(format!("getOwnPropertyDescriptor trap hid property {key} of a non-extensible target")));
+            }
+            return Ok(None);
+        }
+        let is_extensible = target.try_is_extensible(context)?;
+        let descriptor = PropertyDescriptor::from_value(context, &result)?.complete();
+        if !is_compatible_property_descriptor(is_extensible, &descriptor, target_descriptor.as_ref()) {
+            return Err(JsError::type_error(format!("getOwnPropertyDescriptor trap reported property {key} incompatible with the target")));
+        }
+        if descriptor.configurable == Some(false) {
+            let Some(target_descriptor) = target_descriptor.filter(|property| property.configurable == Some(false)) else {
+                return Err(JsError::type_error(format!("getOwnPropertyDescriptor trap reported property {key} as non-configurable")));
+            };
+            if descriptor.writable == Some(false) && target_descriptor.writable == Some(true) {
+                return Err(JsError::type_error(format!("getOwnPropertyDescriptor trap reported property {key} as non-writable")));
+            }
+        }
+        Ok(Some(descriptor))
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-defineownproperty-p-desc>
+    pub(crate) fn define_own_property(
+        &self,
+        context: &mut Context,
+        key: PropertyKey,
+        descriptor: PropertyDescriptor,
+    ) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "defineProperty")?;
+        let Some(trap) = trap else {
+            return target.try_define_own_property(context, key, descriptor);
+        };
+        let descriptor_object = Value::Object(descriptor.to_object(context));
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone()), descriptor_object];
+        if !trap.call(context, &Value::Object(handler), &arguments)?.to_boolean() {
+            return Ok(false);
+        }
+        let target_descriptor = target.try_get_own_property(context, &key)?;
+        let is_extensible = target.try_is_extensible(context)?;
+        let is_non_configurable = descriptor.configurable == Some(false);
+        let Some(target_descriptor) = target_descriptor else {
+            if !is_extensible {
+                return Err(JsError::type_error(format!("defineProperty trap added property {key} to a non-extensible target")));
+            }
+            if is_non_configurable {
+                return Err(JsError::type_error(format!("defineProperty trap defined missing property {key} as non-configurable")));
+            }
+            return Ok(true);
+        };
+        if !is_compatible_property_descriptor(is_extensible, &descriptor, Some(&target_descriptor)) {
+            return Err(JsError::type_error(format!("defineProperty trap accepted property {key} incompatible with the target")));
+        }
+        if is_non_configurable && target_descriptor.configurable == Some(true) {
+            return Err(JsError::type_error(format!("defineProperty trap defined configurable property {key} as non-configurable")));
+        }
+        if target_descriptor.is_data_descriptor()
+            && target_descriptor.configurable == Some(false)
+            && target_descriptor.writable == Some(true)
+            && descriptor.writable == Some(false)
+        {
+            return Err(JsError::type_error(format!("defineProperty trap defined writable property {key} as non-writable")));
+        }
+        Ok(true)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-hasproperty-p>
+    pub(crate) fn has_property(&self, context: &mut Context, key: &PropertyKey) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "has")?;
+        let Some(trap) = trap else {
+            return target.try_has_property(context, key);
+        };
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone())];
+        let has = trap.call(context, &Value::Object(handler), &arguments)?.to_boolean();
+        if !has {
+            if let Some(target_descriptor) = target.try_get_own_property(context, key)? {
+                if target_descriptor.configurable == Some(false) {
+                    return Err(JsError::type_error(format!("has trap hid non-configurable property {key}")));
+                }
+                if !target.try_is_extensible(context)? {
+                    return Err(JsError::type_error(format!("has trap hid property {key} of a non-extensible target")));
+                }
+            }
+        }
+        Ok(has)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-get-p-receiver>
+    pub(crate) fn get(&self, context: &mut Context, key: &PropertyKey, receiver: &Value) -> Result<Value, JsError> {
+        let (target, handler, trap) = self.trap(context, "get")?;
+        let Some(trap) = trap else {
+            return target.get(context, key, receiver);
+        };
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone()), receiver.clone()];
+        let value = trap.call(context, &Value::Object(handler), &arguments)?;
+        if let Some(target_descriptor) = fixed_property(context, &target, key)? {
+            if target_descriptor.writable == Some(false) && !target_descriptor.value.is_some_and(|fixed| fixed.same_value(&value)) {
+                return Err(JsError::type_error(format!("get trap changed non-writable, non-configurable property {key}")));
+            }
+            if matches!(target_descriptor.get, Some(Value::Undefined)) && !matches!(value, Value::Undefined) {
+                return Err(JsError::type_error(format!("get trap reported a value of non-configurable property {key} without a getter")));
+            }
+        }
+        Ok(value)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-set-p-v-receiver>
+    pub(crate) fn set(&self, context: &mut Context, key: PropertyKey, value: Value, receiver: &Value) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "set")?;
+        let Some(trap) = trap else {
+            return target.set(context, key, value, receiver);
+        };
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone()), value.clone(), receiver.clone()];
+        if !trap.call(context, &Value::Object(handler), &arguments)?.to_boolean() {
+            return Ok(false);
+        }
+        if let Some(target_descriptor) = fixed_property(context, &target, &key)? {
+            if target_descriptor.writable == Some(false) && !target_descriptor.value.is_some_and(|fixed| fixed.same_value(&value)) {
+                return Err(JsError::type_error(format!("set trap changed non-writable, non-configurable property {key}")));
+            }
+            if matches!(target_descriptor.set, Some(Value::Undefined)) {
+                return Err(JsError::type_error(format!("set trap set non-configurable property {key} without a setter")));
+            }
+        }
+        Ok(true)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-delete-p>
+    pub(crate) fn delete(&self, context: &mut Context, key: &PropertyKey) -> Result<bool, JsError> {
+        let (target, handler, trap) = self.trap(context, "deleteProperty")?;
+        let Some(trap) = trap else {
+            return target.try_delete(context, key);
+        };
+        let arguments = [Value::Object(target.clone()), Value::from(key.clone())];
+        if !trap.call(context, &Value::Object(handler), &arguments)?.to_boolean() {
+            return Ok(false);
+        }
+        let Some(target_descriptor) = target.try_get_own_property(context, key)? else {
+            return Ok(true);
+        };
+        if target_descriptor.configurable == Some(false) {
+            return Err(JsError::type_error(format!("deleteProperty trap deleted non-configurable property {key}")));
+        }
+        if !target.try_is_extensible(context)? {
+            return Err(JsError::type_error(format!("deleteProperty trap deleted property {key} of a non-extensible target")));
+        }
+        Ok(true)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-ownpropertykeys>
+    pub(crate) fn own_property_keys(&self, context: &mut Context) -> Result<Vec<PropertyKey>, JsError> {
+        let (target, handler, trap) = self.trap(context, "ownKeys")?;
+        let Some(trap) = trap else {
+            return target.try_own_property_keys(context);
+        };
+        let Value::Object(result) = trap.call(context, &Value::Object(handler), &[Value::Object(target.clone())])? else {
+            return Err(JsError::type_error("ownKeys trap returned a non-object"));
+        };
+        let mut keys = Vec::new();
+        for element in result.create_list_from_array_like(context)? {
+            let key = match element {
+                Value::String(name) => PropertyKey::String(name),
+                Value::Symbol(symbol) => PropertyKey::Symbol(symbol),
+                _ => return Err(JsError::type_error("ownKeys trap returned a key that is neither a String nor a Symbol")),
+            };
+            if keys.contains(&key) {
+                return Err(JsError::type_error(format!("ownKeys trap returned key {key} twice")));
+            }
+            keys.push(key);
+        }
+        let is_extensible = target.try_is_extensible(context)?;
+        let (mut configurable, mut non_configurable) = (Vec::new(), Vec::new());
+        for key in target.try_own_property_keys(context)? {
+            match target.try_get_own_property(context, &key)? {
+                Some(property) if property.configurable == Some(false) => non_configurable.push(key),
+                _ => configurable.push(key),
+            }
+        }
+        if is_extensible && non_configurable.is_empty() {
+            return Ok(keys);
+        }
+        let mut unchecked = keys.clone();
+        let mut check = |key: &PropertyKey| {
+            let position = unchecked.iter().position(|unchecked| unchecked == key);
+            position.map(|position| unchecked.swap_remove(position)).is_some()
+        };
+        if let Some(key) = non_configurable.iter().find(|&key| !check(key)) {
+            return Err(JsError::type_error(format!("ownKeys trap omitted non-configurable property {key}")));
+        }
+        if is_extensible {
+            return Ok(keys);
+        }
+        if let Some(key) = configurable.iter().find(|&key| !check(key)) {
+            return Err(JsError::type_error(format!("ownKeys trap omitted property {key} of a non-extensible target")));
+        }
+        if let Some(key) = unchecked.first() {
+            return Err(JsError::type_error(format!("ownKeys trap added property {key} to a non-extensible target")));
+        }
+        Ok(keys)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-call-thisargument-argumentslist>
+    pub(crate) fn call(&self, context: &mut Context, this: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+        let (target, handler, trap) = self.trap(context, "apply")?;
+        let Some(trap) = trap else {
+            return target.call(context, this, arguments);
+        };
+        let arguments = Object::new_array(array_prototype(context), arguments.to_vec());
+        trap.call(context, &Value::Object(handler), &[Value::Object(target), this.clone(), Value::Object(arguments)])
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-proxy-object-internal-methods-and-internal-slots-construct-argumentslist-newtarget>
+    pub(crate) fn construct(&self, context: &mut Context, arguments: &[Value], new_target: &Object) -> Result<Object, JsError> {
+        let (target, handler, trap) = self.trap(context, "construct")?;
+        let Some(trap) = trap else {
+            return target.construct(context, arguments, Some(new_target));
+        };
+        let arguments = Object::new_array(array_prototype(context), arguments.to_vec());
+        let arguments = [Value::Object(target), Value::Object(arguments), Value::Object(new_target.clone())];
+        match trap.call(context, &Value::Object(handler), &arguments)? {
+            Value::Object(object) => Ok(object),
+            _ => Err(JsError::type_error("construct trap returned a non-object")),
+        }
+    }
+}
+
+/// An own property of the target that is non-configurable, so `get` and
+/// `set` traps must agree with it.
+fn fixed_property(context: &mut Context, target: &Object, key: &PropertyKey) -> Result<Option<PropertyDescriptor>, JsError> {
+    Ok(target.try_get_own_property(context, key)?.filter(|property| property.configurable == Some(false)))
+}
+
+/// Create `%Proxy%` with `Proxy.revocable`.
+pub(super) fn create_proxy_constructor(function_prototype: &Object) -> Object {
+    let constructor = Object::native_constructor(Some(function_prototype.clone()), "Proxy", 2, |_, arguments, new_target| {
+        if new_target.is_none() {
+            return Err(JsError::type_error("Proxy constructor requires `new`"));
+        }
+        Proxy::create(&argument(arguments, 0), &argument(arguments, 1))
+    });
+    let revocable = Object::builtin_function(Some(function_prototype.clone()), "revocable", 2, proxy_revocable);
+    let descriptor = PropertyDescriptor::data(Value::Object(revocable), true, false, true);
+    assert!(constructor.define_own_property(PropertyKey::from("revocable"), descriptor), "a fresh object is extensible");
+    constructor
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-proxy.revocable>
+fn proxy_revocable(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let proxy = Proxy::create(&argument(arguments, 0), &argument(arguments, 1))?;
+    // `[[RevocableProxy]]`, cleared by the first call.
+    let revocable = RefCell::new(Some(proxy.clone()));
+    let revoke = Object::builtin_function(function_prototype(context), "", 0, move |_, _, _| {
+        if let Some(ObjectKind::Proxy(proxy)) = revocable.take().map(|proxy| proxy.kind()) {
+            proxy.revoke();
+        }
+        Ok(Value::Undefined)
+    });
+    let result = Object::new(context.current_realm().map(|realm| realm.intrinsics().object_prototype.clone()));
+    result.create_data_property("proxy", Value::Object(proxy));
+    result.create_data_property("revoke", Value::Object(revoke));
+    Ok(Value::Object(result))
+}
+
+fn array_prototype(context: &Context) -> Option<Object> {
+    context.current_realm().map(|realm| realm.intrinsics().array_prototype.clone())
+}
@@ -0,0 +1,348 @@
+//! <https://262.ecma-international.org/14.0/#sec-code-realms> and
+//! <https://262.ecma-international.org/14.0/#sec-execution-contexts>.
+
+use super::{
+    ArrayIterator,
+    Context,
+    Environment,
+    ErrorKind,
+    JsError,
+    JsString,
+    Object,
+    ObjectKind,
+    PrivateEnvironment,
+    PropertyDescriptor,
+    PropertyKey,
+    Symbol,
+    Value,
+    WellKnownSymbol,
+};
+use super::error_objects::{create_error_constructor, error_prototype_to_string};
+use super::promise::create_promise_constructor;
+use super::proxy::create_proxy_constructor;
+use super::reflect::create_reflect_object;
+use crate::interpreter::Generator;
+use std::fmt;
+use std::rc::Rc;
+
+/// <https://262.ecma-international.org/14.0/#sec-well-known-intrinsic-objects>
+/// of a realm, named after them without `%`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Intrinsics {
+    /// `%Object.prototype%`, the root of ordinary prototype chains.
+    pub object_prototype: Object,
+    /// `%Function.prototype%`, a function returning **undefined**.
+    pub function_prototype: Object,
+    /// `%Array.prototype%`, an empty array.
+    pub array_prototype: Object,
+    /// `%Array.prototype.values%`, also being `Symbol.iterator` of arrays.
+    pub array_prototype_values: Object,
+    /// `%IteratorPrototype%`, whose `Symbol.iterator` returns `this`.
+    pub iterator_prototype: Object,
+    /// `%ArrayIteratorPrototype%`
+    pub array_iterator_prototype: Object,
+    /// `%GeneratorFunction.prototype%`, the prototype of generator
+    /// functions.
+    pub generator_function_prototype: Object,
+    /// `%GeneratorFunction.prototype.prototype%`, the prototype of
+    /// `prototype` objects of generator functions.
+    pub generator_prototype: Object,
+    /// `%Boolean.prototype%`
+    pub boolean_prototype: Object,
+    /// `%Number.prototype%`
+    pub number_prototype: Object,
+    /// `%String.prototype%`, an empty String object.
+    pub string_prototype: Object,
+    /// `%Symbol.prototype%`
+    pub symbol_prototype: Object,
+    /// `%BigInt.prototype%`
+    pub bigint_prototype: Object,
+    /// `%Error%`
+    pub error: Object,
+    /// `%Error.prototype%`, with `toString`.
+    pub error_prototype: Object,
+    /// `%RangeError%`
+    pub range_error: Object,
+    /// `%RangeError.prototype%`
+    pub range_error_prototype: Object,
+    /// `%ReferenceError%`
+    pub reference_error: Object,
+    /// `%ReferenceError.prototype%`
+    pub reference_error_prototype: Object,
+    /// `%SyntaxError%`
+    pub syntax_error: Object,
+    /// `%SyntaxError.prototype%`
+    pub syntax_error_prototype: Object,
+    /// `%TypeError%`
+    pub type_error: Object,
+    /// `%TypeError.prototype%`
+    pub type_error_prototype: Object,
+    /// `%Promise%`
+    pub promise: Object,
+    /// `%Promise.prototype%`
+    pub promise_prototype: Object,
+    /// `%Proxy%`
+    pub proxy: Object,
+    /// `%Reflect%`
+    pub reflect: Object,
+}
+
+impl Intrinsics {
+    /// <https://262.ecma-international.org/14.0/#sec-createintrinsics>
+    fn new() -> Self {
+        let object_prototype = Object::new(None);
+        let inherit = || Some(object_prototype.clone());
+        let function_prototype = Object::native_function(inherit(), |_, _, _| Ok(Value::Undefined));
+        let method = |function: fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError>| {
+            Value::Object(Object::native_function(Some(function_prototype.clone()), function))
+        };
+
+        let array_prototype = Object::new_array(inherit(), Vec::new());
+        let array_prototype_values = method(ArrayIterator::values);
+        define_method(&array_prototype, "values", array_prototype_values.clone());
+        define_method(&array_prototype, Symbol::well_known(WellKnownSymbol::Iterator), array_prototype_values.clone());
+
+        let iterator_prototype = Object::new(inherit());
+        let iterator = method(|_, this, _| Ok(this.clone()));
+        define_method(&iterator_prototype, Symbol::well_known(WellKnownSymbol::Iterator), iterator);
+        let array_iterator_prototype = Object::new(Some(iterator_prototype.clone()));
+        define_method(&array_iterator_prototype, "next", method(ArrayIterator::next));
+        let tag = PropertyDescriptor::data(Value::from("Array Iterator"), false, false, true);
+        let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::ToStringTag));
+        assert!(array_iterator_prototype.define_own_property(key.clone(), tag), "a fresh object is extensible");
+
+        let generator_function_prototype = Object::new(Some(function_prototype.clone()));
+        let generator_prototype = Object::new(Some(iterator_prototype.clone()));
+        let prototype = PropertyDescriptor::data(Value::Object(generator_prototype.clone()), false, false, true);
+        assert!(generator_function_prototype.define_own_property(PropertyKey::from("prototype"), prototype), "a fresh object is extensible");
+        let tag = PropertyDescriptor::data(Value::from("GeneratorFunction"), false, false, true);
+        assert!(generator_function_prototype.define_own_property(key.clone(), tag), "a fresh object is extensible");
+        let constructor = PropertyDescriptor::data(Value::Object(generator_function_prototype.clone()), false, false, true);
+        assert!(generator_prototype.define_own_property(PropertyKey::from("constructor"), constructor), "a fresh object is extensible");
+        define_method(&generator_prototype, "next", method(Generator::next));
+        define_method(&generator_prototype, "return", method(Generator::r#return));
+        define_method(&generator_prototype, "throw", method(Generator::throw));
+        let tag = PropertyDescriptor::data(Value::from("Generator"), false, false, true);
+        assert!(generator_prototype.define_own_property(key, tag), "a fresh object is extensible");
+
+        let string_prototype = Object::new_string(inherit(), JsString::new());
+
+        let error_prototype = Object::new(inherit());
+        let error = create_error_constructor(function_prototype.clone(), &error_prototype, "Error");
+        define_method(&error_prototype, "toString", method(error_prototype_to_string));
+        let native_error = |name| {
+            let prototype = Object::new(Some(error_prototype.clone()));
+            (create_error_constructor(error.clone(), &prototype, name), prototype)
+        };
+        let (range_error, range_error_prototype) = native_error("RangeError");
+        let (reference_error, reference_error_prototype) = native_error("ReferenceError");
+        let (syntax_error, syntax_error_prototype) = native_error("SyntaxError");
+        let (type_error, type_error_prototype) = native_error("TypeError");
+
+        let promise_prototype = Object::new(inherit());
+        let promise = create_promise_constructor(&function_prototype, &promise_prototype);
+        let proxy = create_proxy_constructor(&function_prototype);
+        let reflect = create_reflect_object(&object_prototype, &function_prototype);
+
+        let Value::Object(array_prototype_values) = array_prototype_values else {
+            unreachable!("functions are objects");
+        };
+        Self {
+            boolean_prototype: Object::with_kind(inherit(), ObjectKind::Boolean(false)),
+            number_prototype: Object::with_kind(inherit(), ObjectKind::Number(0.0)),
+            symbol_prototype: Object::new(inherit()),
+            bigint_prototype: Object::new(inherit()),
+            string_prototype,
+            array_prototype,
+            array_prototype_values,
+            iterator_prototype,
+            array_iterator_prototype,
+            generator_function_prototype,
+            generator_prototype,
+            range_error,
+            range_error_prototype,
+            reference_error,
+            reference_error_prototype,
+            syntax_error,
+            syntax_error_prototype,
+            type_error,
+            type_error_prototype,
+            promise,
+            promise_prototype,
+            proxy,
+            reflect,
+            error,
+            error_prototype,
+            function_prototype,
+            object_prototype,
+        }
+    }
+
+    /// The prototype of Error objects describing errors of a kind, being
+    /// `%Error.prototype%` for kinds without a native error type.
+    #[must_use]
+    pub const fn error_prototype_of(&self, kind: ErrorKind) -> &Object {
+        match kind {
+            ErrorKind::RangeError | ErrorKind::MemoryLimit => &self.range_error_prototype,
+            ErrorKind::ReferenceError => &self.reference_error_prototype,
+            ErrorKind::SyntaxError => &self.syntax_error_prototype,
+            ErrorKind::TypeError => &self.type_error_prototype,
+            ErrorKind::Thrown | ErrorKind::Unsupported | ErrorKind::OutOfFuel | ErrorKind::Interrupted => &self.error_prototype,
+        }
+    }
+}
+
+/// Define a built-in method property, being writable, configurable and not
+/// enumerable.
+fn define_method(object: &Object, key: impl Into<PropertyKey>, function: Value) {
+    let descriptor = PropertyDescriptor::data(function, true, false, true);
+    assert!(object.define_own_property(key.into(), descriptor), "a fresh object is extensible");
+}
+
+/// Fields of <https://262.ecma-international.org/14.0/#realm-record>.
+struct RealmRecord {
+    intrinsics: Intrinsics,
+    global_object: Object,
+    global_environment: Environment,
+}
+
+/// A Realm Record: intrinsics and a global object all code evaluated in
+/// the realm shares; clones refer to the same realm.
+///
+/// ```
+/// use embedded_ecmascript::runtime::{Context, PropertyKey, Realm, Value};
+///
+/// let realm = Realm::new();
+/// let global = realm.global_object();
+/// let nan = global.get(&mut Context::new(), &PropertyKey::from("NaN"), &Value::Undefined).unwrap();
+/// assert!(matches!(nan, Value::Number(x) if x.is_nan()));
+/// assert_eq!(global.get_prototype_of().as_ref(), Some(&realm.intrinsics().object_prototype));
+/// ```
+#[derive(Clone)]
+pub struct Realm(Rc<RealmRecord>);
+
+impl Realm {
+    /// <https://262.ecma-international.org/14.0/#sec-createrealm> followed
+    /// by <https://262.ecma-international.org/14.0/#sec-setrealmglobalobject>
+    /// with an ordinary global object and
+    /// <https://262.ecma-international.org/14.0/#sec-setdefaultglobalbindings>.
+    #[must_use]
+    pub fn new() -> Self {
+        let intrinsics = Intrinsics::new();
+        let global_object = Object::new(Some(intrinsics.object_prototype.clone()));
+        let global_environment = Environment::new_global(global_object.clone(), global_object.clone());
+        let realm = Self(Rc::new(RealmRecord { intrinsics, global_object, global_environment }));
+        realm.set_default_global_bindings();
+        realm
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-setdefaultglobalbindings>
+    /// for properties of the global object implemented so far.
+    fn set_default_global_bindings(&self) {
+        let global = &self.0.global_object;
+        let this = PropertyDescriptor::data(Value::Object(global.clone()), true, false, true);
+        let constants = [
+            ("globalThis", this),
+            ("Infinity", PropertyDescriptor::data(Value::Number(f64::INFINITY), false, false, false)),
+            ("NaN", PropertyDescriptor::data(Value::Number(f64::NAN), false, false, false)),
+            ("undefined", PropertyDescriptor::data(Value::Undefined, false, false, false)),
+        ];
+        for (name, descriptor) in constants {
+            assert!(global.define_own_property(PropertyKey::from(name), descriptor), "a fresh object is extensible");
+        }
+        let intrinsics = &self.0.intrinsics;
+        let objects = [
+            ("Error", &intrinsics.error),
+            ("RangeError", &intrinsics.range_error),
+            ("ReferenceError", &intrinsics.reference_error),
+            ("SyntaxError", &intrinsics.syntax_error),
+            ("TypeError", &intrinsics.type_error),
+            ("Promise", &intrinsics.promise),
+            ("Proxy", &intrinsics.proxy),
+            ("Reflect", &intrinsics.reflect),
+        ];
+        for (name, object) in objects {
+            define_method(global, name, Value::Object(object.clone()));
+        }
+    }
+
+    #[must_use]
+    pub fn intrinsics(&self) -> &Intrinsics {
+        &self.0.intrinsics
+    }
+
+    /// `[[GlobalObject]]` of the realm.
+    #[must_use]
+    pub fn global_object(&self) -> &Object {
+        &self.0.global_object
+    }
+
+    /// `[[GlobalEnv]]` of the realm, binding properties of the global
+    /// object and top-level lexical declarations of scripts.
+    #[must_use]
+    pub fn global_environment(&self) -> &Environment {
+        &self.0.global_environment
+    }
+}
+
+impl Default for Realm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Realm {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Realm {}
+
+impl fmt::Debug for Realm {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Realm({:p})", Rc::as_ptr(&self.0))
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-execution-contexts> with
+/// state components of ECMAScript code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionContext {
+    /// The function object whose code runs, `None` for a script or
+    /// a module.
+    pub function: Option<Object>,
+    /// The realm the code accesses intrinsics and the global object of.
+    pub realm: Realm,
+    /// `LexicalEnvironment` resolving identifiers, `None` for built-in
+    /// functions.
+    pub lexical_environment: Option<Environment>,
+    /// `VariableEnvironment` holding `var` declarations, `None` for built-in
+    /// functions.
+    pub variable_environment: Option<Environment>,
+    /// `PrivateEnvironment` resolving private names, `None` outside class
+    /// bodies.
+    pub private_environment: Option<PrivateEnvironment>,
+}
+
+impl ExecutionContext {
+    /// A context without environments, like of a built-in function.
+    #[must_use]
+    pub const fn new(function: Option<Object>, realm: Realm) -> Self {
+        Self { function, realm, lexical_environment: None, variable_environment: None, private_environment: None }
+    }
+
+    /// A context of ECMAScript code whose `LexicalEnvironment` and
+    /// `VariableEnvironment` are both `environment`.
+    #[must_use]
+    pub fn with_environment(function: Option<Object>, realm: Realm, environment: Environment) -> Self {
+        Self {
+            function,
+            realm,
+            lexical_environment: Some(environment.clone()),
+            variable_environment: Some(environment),
+            private_environment: None,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! <https://262.ecma-international.org/14.0/#sec-reflect-object>
+
+use super::promise::argument;
+use super::{Context, JsError, Object, PropertyDescriptor, PropertyKey, Symbol, Value, WellKnownSymbol};
+
+/// Create `%Reflect%` with its functions.
+pub(super) fn create_reflect_object(object_prototype: &Object, function_prototype: &Object) -> Object {
+    let reflect = Object::new(Some(object_prototype.clone()));
+    let method = |name: &str, length, function: fn(&mut Context, &Value, &[Value]) -> Result<Value, JsError>| {
+        let function = Object::builtin_function(Some(function_prototype.clone()), name, length, function);
+        let descriptor = PropertyDescriptor::data(Value::Object(function), true, false, true);
+        assert!(reflect.define_own_property(PropertyKey::from(name), descriptor), "a fresh object is extensible");
+    };
+    method("apply", 3, reflect_apply);
+    method("construct", 2, reflect_construct);
+    method("defineProperty", 3, reflect_define_property);
+    method("deleteProperty", 2, reflect_delete_property);
+    method("get", 2, reflect_get);
+    method("getOwnPropertyDescriptor", 2, reflect_get_own_property_descriptor);
+    method("getPrototypeOf", 1, reflect_get_prototype_of);
+    method("has", 2, reflect_has);
+    method("isExtensible", 1, reflect_is_extensible);
+    method("ownKeys", 1, reflect_own_keys);
+    method("preventExtensions", 1, reflect_prevent_extensions);
+    method("set", 3, reflect_set);
+    method("setPrototypeOf", 2, reflect_set_prototype_of);
+    let tag = PropertyDescriptor::data(Value::from("Reflect"), false, false, true);
+    assert!(reflect.define_own_property(Symbol::well_known(WellKnownSymbol::ToStringTag).into(), tag), "a fresh object is extensible");
+    reflect
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.apply>
+fn reflect_apply(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let Some(target) = arguments.first().and_then(Value::as_object).filter(|target| target.is_callable()) else {
+        return Err(JsError::type_error("Reflect.apply target is not a function"));
+    };
+    let list = list_from_array_like(context, &argument(arguments, 2))?;
+    target.call(context, &argument(arguments, 1), &list)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.construct>
+fn reflect_construct(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let Some(target) = arguments.first().and_then(Value::as_object).filter(|target| target.is_constructor()) else {
+        return Err(JsError::type_error("Reflect.construct target is not a constructor"));
+    };
+    let new_target = match arguments.get(2) {
+        None => target,
+        Some(Value::Object(new_target)) if new_target.is_constructor() => new_target,
+        Some(_) => return Err(JsError::type_error("Reflect.construct new target is not a constructor")),
+    };
+    let list = list_from_array_like(context, &argument(arguments, 1))?;
+    Ok(Value::Object(target.construct(context, &list, Some(new_target))?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.defineproperty>
+fn reflect_define_property(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "defineProperty")?;
+    let descriptor = PropertyDescriptor::from_value(context, &argument(arguments, 2))?;
+    Ok(Value::Boolean(target.try_define_own_property(context, key, descriptor)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.deleteproperty>
+fn reflect_delete_property(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "deleteProperty")?;
+    Ok(Value::Boolean(target.try_delete(context, &key)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.get>
+fn reflect_get(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "get")?;
+    let receiver = arguments.get(2).cloned().unwrap_or_else(|| Value::Object(target.clone()));
+    target.get(context, &key, &receiver)
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.getownpropertydescriptor>
+fn reflect_get_own_property_descriptor(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "getOwnPropertyDescriptor")?;
+    let descriptor = target.try_get_own_property(context, &key)?;
+    Ok(descriptor.map_or(Value::Undefined, |descriptor| Value::Object(descriptor.to_object(context))))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.getprototypeof>
+fn reflect_get_prototype_of(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let target = target(arguments, "getPrototypeOf")?;
+    Ok(target.try_get_prototype_of(context)?.map_or(Value::Null, Value::Object))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.has>
+fn reflect_has(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "has")?;
+    Ok(Value::Boolean(target.try_has_property(context, &key)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.isextensible>
+fn reflect_is_extensible(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let target = target(arguments, "isExtensible")?;
+    Ok(Value::Boolean(target.try_is_extensible(context)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.ownkeys>
+fn reflect_own_keys(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let target = target(arguments, "ownKeys")?;
+    let keys = target.try_own_property_keys(context)?.into_iter().map(Value::from).collect();
+    let prototype = context.current_realm().map(|realm| realm.intrinsics().array_prototype.clone());
+    Ok(Value::Object(Object::new_array(prototype, keys)))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.preventextensions>
+fn reflect_prevent_extensions(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let target = target(arguments, "preventExtensions")?;
+    Ok(Value::Boolean(target.try_prevent_extensions(context)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.set>
+fn reflect_set(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let (target, key) = target_and_key(context, arguments, "set")?;
+    let receiver = arguments.get(3).cloned().unwrap_or_else(|| Value::Object(target.clone()));
+    Ok(Value::Boolean(target.set(context, key, argument(arguments, 2), &receiver)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-reflect.setprototypeof>
+fn reflect_set_prototype_of(context: &mut Context, _: &Value, arguments: &[Value]) -> Result<Value, JsError> {
+    let target = target(arguments, "setPrototypeOf")?;
+    let prototype = match argument(arguments, 1) {
+        Value::Object(prototype) => Some(prototype),
+        Value::Null => None,
+        _ => return Err(JsError::type_error("Reflect.setPrototypeOf prototype is neither an object nor null")),
+    };
+    Ok(Value::Boolean(target.try_set_prototype_of(context, prototype)?))
+}
+
+/// The first argument, which must be an object.
+fn target<'a>(arguments: &'a [Value], function: &str) -> Result<&'a Object, JsError> {
+    arguments
+        .first()
+        .and_then(Value::as_object)
+        .ok_or_else(|| JsError::type_error(format!("Reflect.{function} called on a non-object")))
+}
+
+/// The first argument, which must be an object, and the second one as
+/// a property key.
+fn target_and_key<'a>(context: &mut Context, arguments: &'a [Value], function: &str) -> Result<(&'a Object, PropertyKey), JsError> {
+    let target = target(arguments, function)?;
+    Ok((target, argument(arguments, 1).to_property_key(context)?))
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-createlistfromarraylike>
+/// of a value.
+fn list_from_array_like(context: &mut Context, value: &Value) -> Result<Vec<Value>, JsError> {
+    match value {
+        Value::Object(object) => object.create_list_from_array_like(context),
+        _ => Err(JsError::type_error("argument list is not an object")),
+    }
+}
@@ -0,0 +1,584 @@
+//! Conversions between [`Value`] and Rust types implementing `serde`
+//! traits.
+//!
+//! Structs and maps are plain objects with enumerable data properties,
+//! sequences and tuples are arrays, and an enum variant with data is an
+//! object with a single property named after the variant, like JSON
+//! produced by `serde_json`. `None` is **undefined**, so an absent
+//! optional field round-trips, while `()` is **null**.
+
+use super::{BigIntOps, Context, JsError, JsString, Number, Object, PropertyKey, Realm, Value};
+use num_traits::ToPrimitive;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+impl ser::Error for JsError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::type_error(message.to_string())
+    }
+}
+
+impl de::Error for JsError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::type_error(message.to_string())
+    }
+}
+
+/// Convert a Rust value to an ECMAScript one, with objects having
+/// prototypes of the current realm.
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+/// use embedded_ecmascript::runtime::{to_js_value, Value};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Limits {
+///     speed: f64,
+///     zones: Vec<String>,
+/// }
+///
+/// let mut engine = Engine::new();
+/// let limits = Limits { speed: 90.0, zones: vec!["city".to_owned()] };
+/// let limits = to_js_value(&limits, engine.context()).unwrap();
+/// engine.global_object().create_data_property("limits", limits);
+/// assert_eq!(engine.evaluate_script("limits.zones[0] + limits.speed"), Ok(Value::from("city90")));
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` with a `TypeError` if the value fails to serialize,
+/// or has a map key other than a string, a number or a boolean.
+pub fn to_js_value<T: Serialize + ?Sized>(value: &T, context: &Context) -> Result<Value, JsError> {
+    value.serialize(Serializer { context })
+}
+
+/// Convert an ECMAScript value to a Rust one, reading own enumerable
+/// properties of objects.
+///
+/// ```
+/// use embedded_ecmascript::engine::Engine;
+/// use embedded_ecmascript::runtime::from_js_value;
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Sample {
+///     sensor: String,
+///     readings: Vec<f64>,
+///     note: Option<String>,
+/// }
+///
+/// let mut engine = Engine::new();
+/// let value = engine.evaluate_script("({ sensor: 'tire', readings: [2.1, 2.2] })").unwrap();
+/// let sample: Sample = from_js_value(&value, engine.context()).unwrap();
+/// assert_eq!(sample, Sample { sensor: "tire".to_owned(), readings: vec![2.1, 2.2], note: None });
+/// ```
+///
+/// # Errors
+///
+/// Will return `Err` with a `TypeError` if the value does not match `T`,
+/// or with an exception a getter throws.
+pub fn from_js_value<T: DeserializeOwned>(value: &Value, context: &mut Context) -> Result<T, JsError> {
+    T::deserialize(Deserializer { value: value.clone(), context })
+}
+
+/// An object with the prototype of the current realm.
+fn new_object(context: &Context) -> Object {
+    Object::new(context.current_realm().map(|realm| realm.intrinsics().object_prototype.clone()))
+}
+
+/// An array with the prototype of the current realm.
+fn new_array(context: &Context, elements: Vec<Value>) -> Object {
+    Object::new_array(context.current_realm().map(Realm::intrinsics).map(|intrinsics| intrinsics.array_prototype.clone()), elements)
+}
+
+/// An object with a single property of a variant name.
+fn new_variant(context: &Context, variant: &'static str, value: Value) -> Value {
+    let object = new_object(context);
+    object.create_data_property(variant, value);
+    Value::Object(object)
+}
+
+#[derive(Clone, Copy)]
+struct Serializer<'a> {
+    context: &'a Context,
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = Value;
+    type Error = JsError;
+    type SerializeSeq = ArraySerializer<'a>;
+    type SerializeTuple = ArraySerializer<'a>;
+    type SerializeTupleStruct = ArraySerializer<'a>;
+    type SerializeTupleVariant = VariantSerializer<ArraySerializer<'a>>;
+    type SerializeMap = ObjectSerializer<'a>;
+    type SerializeStruct = ObjectSerializer<'a>;
+    type SerializeStructVariant = VariantSerializer<ObjectSerializer<'a>>;
+
+    fn serialize_bool(self, value: bool) -> Result<Value, JsError> {
+        Ok(Value::Boolean(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[allow(clippy::cast_precision_loss)] // numbers are doubles
+    fn serialize_i64(self, value: i64) -> Result<Value, JsError> {
+        Ok(Value::Number(value as f64))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[allow(clippy::cast_precision_loss)] // numbers are doubles
+    fn serialize_u64(self, value: u64) -> Result<Value, JsError> {
+        Ok(Value::Number(value as f64))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Value, JsError> {
+        Ok(Value::Number(value.into()))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Value, JsError> {
+        Ok(Value::Number(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Value, JsError> {
+        Ok(Value::from(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Value, JsError> {
+        Ok(Value::from(value))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value, JsError> {
+        let elements = value.iter().map(|byte| Value::Number((*byte).into())).collect();
+        Ok(Value::Object(new_array(self.context, elements)))
+    }
+
+    fn serialize_none(self) -> Result<Value, JsError> {
+        Ok(Value::Undefined)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, JsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, JsError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, JsError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Value, JsError> {
+        Ok(Value::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Value, JsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, JsError> {
+        Ok(new_variant(self.context, variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<ArraySerializer<'a>, JsError> {
+        Ok(ArraySerializer { context: self.context, elements: Vec::with_capacity(length.unwrap_or_default()) })
+    }
+
+    fn serialize_tuple(self, length: usize) -> Result<ArraySerializer<'a>, JsError> {
+        self.serialize_seq(Some(length))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, length: usize) -> Result<ArraySerializer<'a>, JsError> {
+        self.serialize_seq(Some(length))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<VariantSerializer<ArraySerializer<'a>>, JsError> {
+        Ok(VariantSerializer { variant, inner: self.serialize_seq(Some(length))? })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<ObjectSerializer<'a>, JsError> {
+        Ok(ObjectSerializer { context: self.context, object: new_object(self.context), key: None })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<ObjectSerializer<'a>, JsError> {
+        self.serialize_map(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<VariantSerializer<ObjectSerializer<'a>>, JsError> {
+        Ok(VariantSerializer { variant, inner: self.serialize_map(None)? })
+    }
+}
+
+struct ArraySerializer<'a> {
+    context: &'a Context,
+    elements: Vec<Value>,
+}
+
+impl ArraySerializer<'_> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        self.elements.push(value.serialize(Serializer { context: self.context })?);
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        Value::Object(new_array(self.context, self.elements))
+    }
+}
+
+impl ser::SerializeSeq for ArraySerializer<'_> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for ArraySerializer<'_> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for ArraySerializer<'_> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(self.finish())
+    }
+}
+
+struct ObjectSerializer<'a> {
+    context: &'a Context,
+    object: Object,
+    /// A key waiting for its value.
+    key: Option<PropertyKey>,
+}
+
+impl ObjectSerializer<'_> {
+    fn insert<T: Serialize + ?Sized>(&self, key: PropertyKey, value: &T) -> Result<(), JsError> {
+        self.object.create_data_property(key, value.serialize(Serializer { context: self.context })?);
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for ObjectSerializer<'_> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), JsError> {
+        let key = match key.serialize(Serializer { context: self.context })? {
+            Value::String(key) => key,
+            Value::Number(key) => JsString::from(Number::to_string(key, 10)),
+            Value::Boolean(key) => JsString::from(key.to_string()),
+            _ => return Err(JsError::type_error("map keys must be strings, numbers or booleans")),
+        };
+        self.key = Some(PropertyKey::String(key));
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        let key = self.key.take().ok_or_else(|| JsError::type_error("map value without a key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+impl ser::SerializeStruct for ObjectSerializer<'_> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JsError> {
+        self.insert(PropertyKey::from(key), value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+/// Data of an enum variant, wrapped into an object when complete.
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<ArraySerializer<'_>> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsError> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        let context = self.inner.context;
+        Ok(new_variant(context, self.variant, self.inner.finish()))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<ObjectSerializer<'_>> {
+    type Ok = Value;
+    type Error = JsError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JsError> {
+        self.inner.insert(PropertyKey::from(key), value)
+    }
+
+    fn end(self) -> Result<Value, JsError> {
+        Ok(new_variant(self.inner.context, self.variant, Value::Object(self.inner.object)))
+    }
+}
+
+struct Deserializer<'a> {
+    value: Value,
+    context: &'a mut Context,
+}
+
+impl Deserializer<'_> {
+    /// Own enumerable String-keyed properties of an object with their
+    /// values.
+    fn entries(&mut self, object: &Object) -> Result<Vec<(JsString, Value)>, JsError> {
+        let receiver = Value::Object(object.clone());
+        let mut entries = Vec::new();
+        for key in object.try_own_property_keys(self.context)? {
+            let PropertyKey::String(name) = &key else {
+                continue;
+            };
+            if object.try_get_own_property(self.context, &key)?.is_some_and(|property| property.enumerable == Some(true)) {
+                entries.push((name.clone(), object.get(self.context, &key, &receiver)?));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Elements of an array up to its `length`.
+    fn elements(&mut self, array: &Object) -> Result<Vec<Value>, JsError> {
+        let receiver = Value::Object(array.clone());
+        (0..array.length_of_array_like(self.context)?)
+            .map(|index| array.get(self.context, &PropertyKey::from(index.to_string()), &receiver))
+            .collect()
+    }
+}
+
+/// A String as a Rust one, failing for unpaired surrogates.
+fn std_string(string: &JsString) -> Result<String, JsError> {
+    string.to_std_string().ok_or_else(|| JsError::type_error("string has unpaired surrogates"))
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = JsError;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, JsError> {
+        match &self.value {
+            Value::Undefined | Value::Null => visitor.visit_unit(),
+            Value::Boolean(value) => visitor.visit_bool(*value),
+            #[allow(clippy::cast_possible_truncation)] // checked to be integral and in range
+            Value::Number(value) if self.value.is_integral_number() && value.abs() < 9_007_199_254_740_992.0 => {
+                visitor.visit_i64(*value as i64)
+            },
+            Value::Number(value) => visitor.visit_f64(*value),
+            Value::String(value) => visitor.visit_string(std_string(value)?),
+            Value::BigInt(value) => match (value.to_i64(), value.to_u64()) {
+                (Some(value), _) => visitor.visit_i64(value),
+                (None, Some(value)) => visitor.visit_u64(value),
+                (None, None) => Err(JsError::type_error(format!("{}n does not fit 64 bits", BigIntOps::to_string(value, 10)))),
+            },
+            Value::Symbol(_) => Err(JsError::type_error("cannot deserialize a Symbol")),
+            Value::Object(object) if object.is_callable() => Err(JsError::type_error("cannot deserialize a function")),
+            Value::Object(object) if object.is_array() => {
+                let object = object.clone();
+                let elements = self.elements(&object)?;
+                visitor.visit_seq(SeqAccess { elements: elements.into_iter(), context: self.context })
+            },
+            Value::Object(object) => {
+                let object = object.clone();
+                let entries = self.entries(&object)?;
+                visitor.visit_map(MapAccess { entries: entries.into_iter(), value: None, context: self.context })
+            },
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsError> {
+        match self.value {
+            Value::Undefined | Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, JsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        match &self.value {
+            Value::String(variant) => visitor.visit_enum(std_string(variant)?.into_deserializer()),
+            Value::Object(object) => {
+                let object = object.clone();
+                let mut entries = self.entries(&object)?;
+                if entries.len() != 1 {
+                    return Err(JsError::type_error("expected an object with a single property naming a variant"));
+                }
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(EnumAccess { variant: std_string(&variant)?, value, context: self.context })
+            },
+            _ => Err(JsError::type_error("expected a variant name or an object")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct SeqAccess<'a> {
+    elements: std::vec::IntoIter<Value>,
+    context: &'a mut Context,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = JsError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, JsError> {
+        self.elements
+            .next()
+            .map(|value| seed.deserialize(Deserializer { value, context: self.context }))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct MapAccess<'a> {
+    entries: std::vec::IntoIter<(JsString, Value)>,
+    /// The value of the last key taken.
+    value: Option<Value>,
+    context: &'a mut Context,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = JsError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, JsError> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(std_string(&key)?.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, JsError> {
+        let value = self.value.take().ok_or_else(|| JsError::type_error("map value without a key"))?;
+        seed.deserialize(Deserializer { value, context: self.context })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// An enum variant stored as an object with a single property.
+struct EnumAccess<'a> {
+    variant: String,
+    value: Value,
+    context: &'a mut Context,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = JsError;
+    type Variant = Deserializer<'a>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Deserializer<'a>), JsError> {
+        let variant = seed.deserialize(IntoDeserializer::<JsError>::into_deserializer(self.variant))?;
+        Ok((variant, Deserializer { value: self.value, context: self.context }))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer<'_> {
+    type Error = JsError;
+
+    fn unit_variant(self) -> Result<(), JsError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, JsError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, JsError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, JsError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,438 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types-string-type>
+
+use super::heap;
+use std::cell::{OnceCell, RefCell};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// How many Latin-1 code units a [`JsString`] keeps without allocating.
+const INLINE_CAPACITY: usize = 22;
+
+/// How many code units a concatenation needs to be kept as a rope; shorter
+/// ones are cheaper to copy at once.
+const MIN_ROPE_LENGTH: usize = 64;
+
+/// A String value: an immutable sequence of UTF-16 code units.
+///
+/// Unlike [`str`], it can hold unpaired surrogates that ECMAScript code
+/// creates with `"\uD800"` or by slicing a surrogate pair. Lengths and
+/// indices are in code units, like `length` and `charCodeAt` of ECMAScript.
+///
+/// Strings consisting of code units up to U+00FF, which most strings in
+/// practice are, take one byte per code unit; short ones are stored
+/// inline. Clones share the storage. A long result of [`JsString::concat`]
+/// keeps its parts and copies them into one storage only once its code
+/// units are needed, so a string built with `+=` in a loop takes linear
+/// rather than quadratic time.
+///
+/// ```
+/// use embedded_ecmascript::runtime::JsString;
+///
+/// let string = JsString::from("😀 smile");
+/// assert_eq!(string.len(), 8);
+/// assert_eq!(string.index_of(&JsString::from("smile"), 0), Some(3));
+///
+/// let lone_surrogate = string.substring(0, 1);
+/// assert_eq!(lone_surrogate.code_units().collect::<Vec<_>>(), [0xD83D]);
+/// assert_eq!(lone_surrogate.to_string(), "\u{FFFD}");
+/// ```
+#[derive(Clone)]
+pub struct JsString(Repr);
+
+/// A storage of code units, always the most compact one that fits them, so
+/// equal strings have the same representation once ropes are flattened.
+#[derive(Clone)]
+enum Repr {
+    Inline { length: u8, units: [u8; INLINE_CAPACITY] },
+    Latin1(Rc<[u8]>),
+    Utf16(Rc<[u16]>),
+    Rope(Rc<Rope>),
+}
+
+/// A concatenation of two strings flattened on first access to its code
+/// units.
+struct Rope {
+    length: usize,
+    is_latin1: bool,
+    /// The concatenated strings until the rope is flattened.
+    parts: RefCell<Option<(JsString, JsString)>>,
+    flat: OnceCell<Flat>,
+}
+
+enum Flat {
+    Latin1(Box<[u8]>),
+    Utf16(Box<[u16]>),
+}
+
+impl Rope {
+    fn units(&self) -> Units<'_> {
+        let flat = self.flat.get_or_init(|| {
+            let parts = self.parts.borrow_mut().take().expect("a rope keeps its parts until it is flattened");
+            // Walk nested ropes with an explicit stack since a string built
+            // by `+=` in a loop nests as deep as it has parts.
+            let mut pending = vec![parts.1, parts.0];
+            let mut latin1 = Vec::with_capacity(if self.is_latin1 { self.length } else { 0 });
+            let mut utf16 = Vec::with_capacity(if self.is_latin1 { 0 } else { self.length });
+            while let Some(part) = pending.pop() {
+                if let Repr::Rope(rope) = &part.0 {
+                    if rope.flat.get().is_none() {
+                        let parts = rope.parts.borrow();
+                        let (left, right) = parts.as_ref().expect("a rope keeps its parts until it is flattened");
+                        pending.extend([right.clone(), left.clone()]);
+                        continue;
+                    }
+                }
+                match part.units() {
+                    Units::Latin1(units) if self.is_latin1 => latin1.extend_from_slice(units),
+                    units => utf16.extend((0..part.len()).map(|index| units.get(index))),
+                }
+            }
+            if self.is_latin1 {
+                heap::charge(self.length);
+                Flat::Latin1(latin1.into())
+            } else {
+                heap::charge(self.length * mem::size_of::<u16>());
+                Flat::Utf16(utf16.into())
+            }
+        });
+        match flat {
+            Flat::Latin1(units) => Units::Latin1(units),
+            Flat::Utf16(units) => Units::Utf16(units),
+        }
+    }
+}
+
+impl Drop for Rope {
+    fn drop(&mut self) {
+        // Unlink nested ropes no other string shares one by one instead of
+        // recursing as deep as they nest.
+        let mut pending: Vec<_> = self.parts.get_mut().take().into_iter().flat_map(|(left, right)| [left, right]).collect();
+        while let Some(JsString(part)) = pending.pop() {
+            if let Repr::Rope(rope) = part {
+                if let Some(mut rope) = Rc::into_inner(rope) {
+                    pending.extend(rope.parts.get_mut().take().into_iter().flat_map(|(left, right)| [left, right]));
+                }
+            }
+        }
+    }
+}
+
+/// Code units of a [`JsString`] borrowed from any of its representations.
+#[derive(Clone, Copy)]
+enum Units<'a> {
+    Latin1(&'a [u8]),
+    Utf16(&'a [u16]),
+}
+
+/// An iterator over code units of a [`JsString`].
+#[derive(Clone)]
+pub struct CodeUnits<'a> {
+    units: Units<'a>,
+    indices: Range<usize>,
+}
+
+impl Units<'_> {
+    fn get(self, index: usize) -> u16 {
+        match self {
+            Units::Latin1(units) => u16::from(units[index]),
+            Units::Utf16(units) => units[index],
+        }
+    }
+}
+
+impl Iterator for CodeUnits<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        self.indices.next().map(|index| self.units.get(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CodeUnits<'_> {
+    fn next_back(&mut self) -> Option<u16> {
+        self.indices.next_back().map(|index| self.units.get(index))
+    }
+}
+
+impl ExactSizeIterator for CodeUnits<'_> {}
+
+impl JsString {
+    /// The empty String.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Repr::Inline { length: 0, units: [0; INLINE_CAPACITY] })
+    }
+
+    #[must_use]
+    pub fn from_code_units(units: &[u16]) -> Self {
+        if units.iter().all(|&unit| unit <= 0xFF) {
+            #[allow(clippy::cast_possible_truncation)] // checked above
+            let latin1: Vec<u8> = units.iter().map(|&unit| unit as u8).collect();
+            Self::from_latin1(&latin1)
+        } else {
+            Self::from_utf16(units.into())
+        }
+    }
+
+    fn from_latin1(units: &[u8]) -> Self {
+        if units.len() <= INLINE_CAPACITY {
+            let mut inline = [0; INLINE_CAPACITY];
+            inline[..units.len()].copy_from_slice(units);
+            #[allow(clippy::cast_possible_truncation)] // INLINE_CAPACITY fits
+            Self(Repr::Inline { length: units.len() as u8, units: inline })
+        } else {
+            heap::charge(units.len());
+            Self(Repr::Latin1(units.into()))
+        }
+    }
+
+    fn from_utf16(units: Rc<[u16]>) -> Self {
+        heap::charge(mem::size_of_val(&*units));
+        Self(Repr::Utf16(units))
+    }
+
+    fn units(&self) -> Units<'_> {
+        match &self.0 {
+            Repr::Inline { length, units } => Units::Latin1(&units[..usize::from(*length)]),
+            Repr::Latin1(units) => Units::Latin1(units),
+            Repr::Utf16(units) => Units::Utf16(units),
+            Repr::Rope(rope) => rope.units(),
+        }
+    }
+
+    /// Whether all code units are up to U+00FF, without flattening a rope.
+    fn is_latin1(&self) -> bool {
+        match &self.0 {
+            Repr::Inline { .. } | Repr::Latin1(_) => true,
+            Repr::Utf16(_) => false,
+            Repr::Rope(rope) => rope.is_latin1,
+        }
+    }
+
+    /// The length in code units.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        if let Repr::Rope(rope) = &self.0 {
+            return rope.length;
+        }
+        match self.units() {
+            Units::Latin1(units) => units.len(),
+            Units::Utf16(units) => units.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// A code unit at `index`, `None` if it is out of bounds.
+    #[must_use]
+    pub fn code_unit_at(&self, index: usize) -> Option<u16> {
+        match self.units() {
+            Units::Latin1(units) => units.get(index).map(|&unit| u16::from(unit)),
+            Units::Utf16(units) => units.get(index).copied(),
+        }
+    }
+
+    #[must_use]
+    pub fn code_units(&self) -> CodeUnits<'_> {
+        CodeUnits { units: self.units(), indices: 0..self.len() }
+    }
+
+    /// Code units from `start` to `end` exclusively, clamped to the length.
+    #[must_use]
+    pub fn substring(&self, start: usize, end: usize) -> Self {
+        let end = end.min(self.len());
+        let start = start.min(end);
+        match self.units() {
+            Units::Latin1(units) => Self::from_latin1(&units[start..end]),
+            Units::Utf16(units) => Self::from_code_units(&units[start..end]),
+        }
+    }
+
+    /// The string-concatenation of two Strings.
+    #[must_use]
+    pub fn concat(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            return self.clone();
+        }
+        if self.is_empty() {
+            return other.clone();
+        }
+        let length = self.len() + other.len();
+        if length >= MIN_ROPE_LENGTH {
+            let is_latin1 = self.is_latin1() && other.is_latin1();
+            // Charging the appended code units keeps the length of any string
+            // within the bytes counted so far, so a byte budget still bounds
+            // what flattening a rope may take.
+            let unit_size = if is_latin1 { 1 } else { mem::size_of::<u16>() };
+            heap::charge(mem::size_of::<Rope>() + other.len() * unit_size);
+            return Self(Repr::Rope(Rc::new(Rope {
+                length,
+                is_latin1,
+                parts: RefCell::new(Some((self.clone(), other.clone()))),
+                flat: OnceCell::new(),
+            })));
+        }
+        if let (Units::Latin1(left), Units::Latin1(right)) = (self.units(), other.units()) {
+            return Self::from_latin1(&[left, right].concat());
+        }
+        Self::from_utf16(self.code_units().chain(other.code_units()).collect())
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-stringindexof>, `None`
+    /// standing for -1.
+    ///
+    /// The empty string is found at any `from_index` up to the length.
+    #[must_use]
+    pub fn index_of(&self, search_value: &Self, from_index: usize) -> Option<usize> {
+        let length = self.len();
+        let search_length = search_value.len();
+        if search_length == 0 && from_index <= length {
+            return Some(from_index);
+        }
+        if search_length > length {
+            return None;
+        }
+        (from_index..=length - search_length).find(|&start| {
+            (0..search_length).all(|offset| self.code_unit_at(start + offset) == search_value.code_unit_at(offset))
+        })
+    }
+
+    /// The string without code units matching `is_white_space` at both
+    /// ends, like <https://262.ecma-international.org/14.0/#sec-trimstring>
+    /// with `start+end` does.
+    #[must_use]
+    pub fn trim(&self, is_white_space: impl Fn(char) -> bool) -> Self {
+        let is_trimmed = |unit: u16| char::from_u32(u32::from(unit)).is_some_and(&is_white_space);
+        let start = self.code_units().position(|unit| !is_trimmed(unit)).unwrap_or(self.len());
+        let end = self.len() - self.code_units().rev().position(|unit| !is_trimmed(unit)).unwrap_or(0);
+        self.substring(start, end)
+    }
+
+    /// The string if it has no unpaired surrogates.
+    #[must_use]
+    pub fn to_std_string(&self) -> Option<String> {
+        match self.units() {
+            Units::Latin1(units) => Some(units.iter().map(|&unit| char::from(unit)).collect()),
+            Units::Utf16(units) => String::from_utf16(units).ok(),
+        }
+    }
+}
+
+impl Default for JsString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for JsString {
+    fn from(string: &str) -> Self {
+        if string.chars().all(|codepoint| u32::from(codepoint) <= 0xFF) {
+            #[allow(clippy::cast_possible_truncation)] // checked above
+            let latin1: Vec<u8> = string.chars().map(|codepoint| u32::from(codepoint) as u8).collect();
+            Self::from_latin1(&latin1)
+        } else {
+            Self::from_utf16(string.encode_utf16().collect())
+        }
+    }
+}
+
+impl From<String> for JsString {
+    fn from(string: String) -> Self {
+        Self::from(string.as_str())
+    }
+}
+
+impl From<&[u16]> for JsString {
+    fn from(units: &[u16]) -> Self {
+        Self::from_code_units(units)
+    }
+}
+
+impl From<Vec<u16>> for JsString {
+    fn from(units: Vec<u16>) -> Self {
+        Self::from_code_units(&units)
+    }
+}
+
+impl PartialEq for JsString {
+    fn eq(&self, other: &Self) -> bool {
+        // Representations are canonical, so strings of different ones
+        // differ.
+        match (self.units(), other.units()) {
+            (Units::Latin1(left), Units::Latin1(right)) => left == right,
+            (Units::Utf16(left), Units::Utf16(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for JsString {}
+
+impl PartialEq<str> for JsString {
+    fn eq(&self, other: &str) -> bool {
+        self.code_units().eq(other.encode_utf16())
+    }
+}
+
+impl PartialEq<&str> for JsString {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Hash for JsString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for unit in self.code_units() {
+            state.write_u16(unit);
+        }
+    }
+}
+
+/// Lexicographic order of code units, which
+/// <https://262.ecma-international.org/14.0/#sec-islessthan> uses.
+impl Ord for JsString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.code_units().cmp(other.code_units())
+    }
+}
+
+impl PartialOrd for JsString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Unpaired surrogates are replaced with U+FFFD REPLACEMENT CHARACTER.
+impl fmt::Display for JsString {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        char::decode_utf16(self.code_units())
+            .map(|codepoint| codepoint.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .try_for_each(|codepoint| fmt::Write::write_char(formatter, codepoint))
+    }
+}
+
+/// Quoted like a Rust string, with unpaired surrogates as `\u{d800}`.
+impl fmt::Debug for JsString {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("\"")?;
+        for codepoint in char::decode_utf16(self.code_units()) {
+            match codepoint {
+                Ok(codepoint) => write!(formatter, "{}", codepoint.escape_debug())?,
+                Err(error) => write!(formatter, "\\u{{{:x}}}", error.unpaired_surrogate())?,
+            }
+        }
+        formatter.write_str("\"")
+    }
+}
@@ -0,0 +1,371 @@
+//! <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types>
+
+use super::{JsString, Number, Object};
+use num_bigint::BigInt;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/// A value of one of
+/// <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types>.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(JsString),
+    Symbol(Symbol),
+    BigInt(BigInt),
+    Object(Object),
+}
+
+/// A name of a language type, as the specification's `Type(x)` gives.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Type {
+    Undefined,
+    Null,
+    Boolean,
+    Number,
+    String,
+    Symbol,
+    BigInt,
+    Object,
+}
+
+impl Value {
+    #[must_use]
+    pub const fn r#type(&self) -> Type {
+        match self {
+            Self::Undefined => Type::Undefined,
+            Self::Null => Type::Null,
+            Self::Boolean(_) => Type::Boolean,
+            Self::Number(_) => Type::Number,
+            Self::String(_) => Type::String,
+            Self::Symbol(_) => Type::Symbol,
+            Self::BigInt(_) => Type::BigInt,
+            Self::Object(_) => Type::Object,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_undefined(&self) -> bool {
+        matches!(self, Self::Undefined)
+    }
+
+    /// Whether the value is **undefined** or **null**, which have no
+    /// properties.
+    #[must_use]
+    pub const fn is_nullish(&self) -> bool {
+        matches!(self, Self::Undefined | Self::Null)
+    }
+
+    #[must_use]
+    pub const fn is_object(&self) -> bool {
+        matches!(self, Self::Object(_))
+    }
+
+    /// Whether the value is not an Object.
+    #[must_use]
+    pub const fn is_primitive(&self) -> bool {
+        !self.is_object()
+    }
+
+    #[must_use]
+    pub const fn as_object(&self) -> Option<&Object> {
+        match self {
+            Self::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isintegralnumber>
+    #[must_use]
+    #[allow(clippy::float_cmp)] // truncation is exact
+    pub fn is_integral_number(&self) -> bool {
+        matches!(self, Self::Number(number) if number.is_finite() && number.trunc() == *number)
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-samevalue>
+    #[must_use]
+    pub fn same_value(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Number::same_value(*left, *right),
+            _ => self.same_value_non_number(other),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-samevaluezero>
+    ///
+    /// Unlike [`Self::same_value`], considers +0 and -0 equal.
+    #[must_use]
+    pub fn same_value_zero(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Number::same_value_zero(*left, *right),
+            _ => self.same_value_non_number(other),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-isstrictlyequal>
+    ///
+    /// Unlike [`Self::same_value`], considers NaN unequal to itself.
+    #[must_use]
+    pub fn is_strictly_equal(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(left), Self::Number(right)) => Number::equal(*left, *right),
+            _ => self.same_value_non_number(other),
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-samevaluenonnumber>,
+    /// `false` for values of different types.
+    fn same_value_non_number(&self, other: &Self) -> bool {
+        self.r#type() == other.r#type() && self == other
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<JsString> for Value {
+    fn from(value: JsString) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Symbol> for Value {
+    fn from(value: Symbol) -> Self {
+        Self::Symbol(value)
+    }
+}
+
+impl From<BigInt> for Value {
+    fn from(value: BigInt) -> Self {
+        Self::BigInt(value)
+    }
+}
+
+impl From<Object> for Value {
+    fn from(value: Object) -> Self {
+        Self::Object(value)
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-well-known-symbols>
+///
+/// They are shared by all realms, so they are told apart by their kind
+/// rather than by identity.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WellKnownSymbol {
+    AsyncIterator,
+    HasInstance,
+    IsConcatSpreadable,
+    Iterator,
+    Match,
+    MatchAll,
+    Replace,
+    Search,
+    Species,
+    Split,
+    ToPrimitive,
+    ToStringTag,
+    Unscopables,
+}
+
+impl WellKnownSymbol {
+    /// The `[[Description]]` of the symbol, like `Symbol.iterator`.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::AsyncIterator => "Symbol.asyncIterator",
+            Self::HasInstance => "Symbol.hasInstance",
+            Self::IsConcatSpreadable => "Symbol.isConcatSpreadable",
+            Self::Iterator => "Symbol.iterator",
+            Self::Match => "Symbol.match",
+            Self::MatchAll => "Symbol.matchAll",
+            Self::Replace => "Symbol.replace",
+            Self::Search => "Symbol.search",
+            Self::Species => "Symbol.species",
+            Self::Split => "Symbol.split",
+            Self::ToPrimitive => "Symbol.toPrimitive",
+            Self::ToStringTag => "Symbol.toStringTag",
+            Self::Unscopables => "Symbol.unscopables",
+        }
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-ecmascript-language-types-symbol-type>
+///
+/// Clones of a symbol are the same symbol; each [`Symbol::new`] call
+/// creates a distinct one.
+#[derive(Clone)]
+pub struct Symbol(SymbolIdentity);
+
+#[derive(Clone)]
+enum SymbolIdentity {
+    WellKnown(WellKnownSymbol),
+    Unique(Rc<Option<String>>),
+}
+
+impl Symbol {
+    #[must_use]
+    pub fn new(description: Option<String>) -> Self {
+        Self(SymbolIdentity::Unique(Rc::new(description)))
+    }
+
+    #[must_use]
+    pub const fn well_known(symbol: WellKnownSymbol) -> Self {
+        Self(SymbolIdentity::WellKnown(symbol))
+    }
+
+    /// The `[[Description]]` of the symbol, **undefined** if `None`.
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        match &self.0 {
+            SymbolIdentity::WellKnown(symbol) => Some(symbol.description()),
+            SymbolIdentity::Unique(description) => description.as_deref(),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_well_known(&self) -> Option<WellKnownSymbol> {
+        match self.0 {
+            SymbolIdentity::WellKnown(symbol) => Some(symbol),
+            SymbolIdentity::Unique(_) => None,
+        }
+    }
+
+    /// <https://262.ecma-international.org/14.0/#sec-symboldescriptivestring>
+    #[must_use]
+    pub fn descriptive_string(&self) -> String {
+        format!("Symbol({})", self.description().unwrap_or_default())
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (SymbolIdentity::WellKnown(left), SymbolIdentity::WellKnown(right)) => left == right,
+            (SymbolIdentity::Unique(left), SymbolIdentity::Unique(right)) => Rc::ptr_eq(left, right),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.0 {
+            SymbolIdentity::WellKnown(symbol) => symbol.hash(state),
+            SymbolIdentity::Unique(description) => Rc::as_ptr(description).hash(state),
+        }
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.descriptive_string())
+    }
+}
+
+/// <https://262.ecma-international.org/14.0/#sec-object-type>
+///
+/// Properties are keyed by a String or a Symbol; array indices are
+/// Strings too.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PropertyKey {
+    String(JsString),
+    Symbol(Symbol),
+}
+
+impl PropertyKey {
+    /// <https://262.ecma-international.org/14.0/#sec-object-type> array index
+    /// the key is a canonical numeric string of, if any.
+    #[must_use]
+    pub fn as_array_index(&self) -> Option<u32> {
+        let Self::String(key) = self else {
+            return None;
+        };
+        let index: u32 = key.to_std_string()?.parse().ok()?;
+        // An array index is at most 2^32 - 2 and has no leading zeros.
+        (index != u32::MAX && *key == *index.to_string()).then_some(index)
+    }
+}
+
+/// A String key as is and a Symbol one as its descriptive string, for
+/// messages.
+impl fmt::Display for PropertyKey {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(key) => key.fmt(formatter),
+            Self::Symbol(key) => formatter.write_str(&key.descriptive_string()),
+        }
+    }
+}
+
+impl From<&str> for PropertyKey {
+    fn from(key: &str) -> Self {
+        Self::String(key.into())
+    }
+}
+
+impl From<String> for PropertyKey {
+    fn from(key: String) -> Self {
+        Self::String(key.into())
+    }
+}
+
+impl From<JsString> for PropertyKey {
+    fn from(key: JsString) -> Self {
+        Self::String(key)
+    }
+}
+
+impl From<Symbol> for PropertyKey {
+    fn from(key: Symbol) -> Self {
+        Self::Symbol(key)
+    }
+}
+
+impl From<PropertyKey> for Value {
+    fn from(key: PropertyKey) -> Self {
+        match key {
+            PropertyKey::String(key) => Self::String(key),
+            PropertyKey::Symbol(key) => Self::Symbol(key),
+        }
+    }
+}
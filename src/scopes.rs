@@ -0,0 +1,707 @@
+//! Scopes of a parsed script and names declared in them.
+//!
+//! [`analyze_script`] builds a tree of scopes like those of
+//! <https://262.ecma-international.org/14.0/#sec-environment-records>
+//! without running any code: each scope lists names it declares, and each
+//! identifier reference is resolved to a declaration or left to the global
+//! object. A name used by a function nested into one declaring it is
+//! marked as captured, so an evaluator knows which variables outlive their
+//! function call.
+//!
+//! Scopes are simplified where the difference is invisible to resolution.
+//! Parameters and a body of a function share a scope, and so do
+//! a parameter of `catch` and its block. Names inside `with` are
+//! resolved as if there was no `with`, since its object is known only at
+//! run time.
+
+use crate::grammar::{
+    Argument,
+    ArrayElement,
+    Binding,
+    BindingElement,
+    BindingIdentifier,
+    BindingProperty,
+    Block,
+    CatchClause,
+    Class,
+    ClassElement,
+    ClassElementName,
+    ConciseBody,
+    Declaration,
+    Expression,
+    ForInit,
+    ForTarget,
+    FormalParameters,
+    Function,
+    IdentifierReference,
+    LabelledItem,
+    MemberProperty,
+    MethodDefinition,
+    PropertyDefinition,
+    PropertyName,
+    Script,
+    Statement,
+    StatementListItem,
+    VariableDeclarationList,
+    VariableKind,
+};
+use std::ops::Range;
+
+/// Build a scope tree of a script, or a module if `is_module`.
+///
+/// Skipped bodies of a parser with
+/// [`crate::grammar::Parser::with_deferred_function_bodies`] are seen as
+/// empty.
+#[must_use]
+pub fn analyze_script(script: &Script, is_module: bool) -> ScopeTree {
+    let kind = if is_module { ScopeKind::Module } else { ScopeKind::Global };
+    let mut builder = Builder {
+        scopes: vec![Scope { kind, parent: None, range: script.range.clone(), symbols: Vec::new() }],
+        references: Vec::new(),
+        current: ScopeId(0),
+    };
+    builder.statement_list(&script.body);
+    builder.finish()
+}
+
+/// Index of a scope in a [`ScopeTree`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ScopeId(usize);
+
+impl ScopeId {
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// A declaration of a scope, like `scope.symbols[index]`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SymbolId {
+    pub scope: ScopeId,
+    pub index: usize,
+}
+
+/// Scopes of a script, the outermost one first, and identifier references
+/// in order of appearance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScopeTree {
+    pub scopes: Vec<Scope>,
+    pub references: Vec<Reference>,
+}
+
+impl ScopeTree {
+    /// The scope of the whole script or module.
+    #[must_use]
+    pub const fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    #[must_use]
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    #[must_use]
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.scopes[id.scope.0].symbols[id.index]
+    }
+
+    /// Find a declaration of a name visible from a scope, going outwards.
+    ///
+    /// `None` means a global: a property of the global object or
+    /// an undeclared name.
+    #[must_use]
+    pub fn resolve(&self, mut scope: ScopeId, name: &str) -> Option<SymbolId> {
+        loop {
+            let symbols = &self.scopes[scope.0].symbols;
+            if let Some(index) = symbols.iter().position(|symbol| symbol.name == name) {
+                return Some(SymbolId { scope, index });
+            }
+            scope = self.scopes[scope.0].parent?;
+        }
+    }
+
+    /// The innermost function, arrow function, script or module scope
+    /// containing a scope; `var` declarations of a scope belong to it.
+    #[must_use]
+    pub fn var_scope(&self, scope: ScopeId) -> ScopeId {
+        var_scope(&self.scopes, scope)
+    }
+}
+
+fn var_scope(scopes: &[Scope], mut scope: ScopeId) -> ScopeId {
+    loop {
+        let Scope { kind, parent, .. } = &scopes[scope.0];
+        match parent {
+            Some(parent) if *kind == ScopeKind::Class || *kind == ScopeKind::Block => scope = *parent,
+            _ => return scope,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScopeKind {
+    /// A script, whose top-level `var` and function declarations are
+    /// properties of the global object.
+    Global,
+    Module,
+    /// A function, a method, a class static block or a field initializer.
+    Function,
+    /// An arrow function, which has no `arguments` or `this` of its own.
+    ArrowFunction,
+    /// A class body binding a class name inside it.
+    Class,
+    /// A block, a `switch` case block, a `catch` clause or a `for` loop,
+    /// whose head may declare names for each iteration.
+    Block,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// `None` for the root.
+    pub parent: Option<ScopeId>,
+    /// Byte offsets of the node creating the scope.
+    pub range: Range<usize>,
+    /// Names declared in the scope in order of appearance, each once.
+    pub symbols: Vec<Symbol>,
+}
+
+/// A name declared in a scope.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Byte offsets of the first declaration, or of a function declaring
+    /// its `arguments`.
+    pub range: Range<usize>,
+    /// Whether the name is referenced from a function nested into the one
+    /// declaring it.
+    pub is_captured: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SymbolKind {
+    Var,
+    Let,
+    Const,
+    /// `using` or `await using` of the explicit resource management
+    /// proposal.
+    Using,
+    Function,
+    Class,
+    Parameter,
+    CatchParameter,
+    /// `arguments` of a non-arrow function, declared implicitly.
+    Arguments,
+}
+
+/// An `IdentifierReference`, including a shorthand property like `a` in
+/// `({ a })`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reference {
+    pub name: String,
+    pub range: Range<usize>,
+    /// The innermost scope containing the reference.
+    pub scope: ScopeId,
+    /// `None` for a global, see [`ScopeTree::resolve`].
+    pub symbol: Option<SymbolId>,
+}
+
+/************************************************
+ *
+ * Tree walk
+ *
+ ************************************************/
+
+struct Builder {
+    scopes: Vec<Scope>,
+    references: Vec<Reference>,
+    current: ScopeId,
+}
+
+impl Builder {
+    /// Resolve references after all declarations, hoisted ones included,
+    /// are known.
+    fn finish(self) -> ScopeTree {
+        let mut tree = ScopeTree { scopes: self.scopes, references: self.references };
+        for index in 0..tree.references.len() {
+            let reference = &tree.references[index];
+            let Some(symbol) = tree.resolve(reference.scope, &reference.name) else {
+                continue;
+            };
+            if tree.var_scope(reference.scope) != tree.var_scope(symbol.scope) {
+                tree.scopes[symbol.scope.0].symbols[symbol.index].is_captured = true;
+            }
+            tree.references[index].symbol = Some(symbol);
+        }
+        tree
+    }
+
+    /// Walk a node creating a scope.
+    fn with_scope(&mut self, kind: ScopeKind, range: Range<usize>, walk: impl FnOnce(&mut Self)) {
+        let id = ScopeId(self.scopes.len());
+        self.scopes.push(Scope { kind, parent: Some(self.current), range, symbols: Vec::new() });
+        let outer = std::mem::replace(&mut self.current, id);
+        walk(self);
+        self.current = outer;
+    }
+
+    /// Add a name to a scope unless it is already declared there, like by
+    /// another `var`.
+    fn declare(&mut self, scope: ScopeId, name: &str, kind: SymbolKind, range: Range<usize>) {
+        let symbols = &mut self.scopes[scope.0].symbols;
+        if !symbols.iter().any(|symbol| symbol.name == name) {
+            symbols.push(Symbol { name: name.to_owned(), kind, range, is_captured: false });
+        }
+    }
+
+    fn declare_names(&mut self, scope: ScopeId, names: &[&BindingIdentifier], kind: SymbolKind) {
+        for name in names {
+            self.declare(scope, &name.name, kind, name.range.clone());
+        }
+    }
+
+    /// Where names of a variable declaration go, and how they are declared.
+    fn variable_scope(&self, kind: VariableKind) -> (ScopeId, SymbolKind) {
+        match kind {
+            VariableKind::Var => (var_scope(&self.scopes, self.current), SymbolKind::Var),
+            VariableKind::Let => (self.current, SymbolKind::Let),
+            VariableKind::Const => (self.current, SymbolKind::Const),
+            VariableKind::Using | VariableKind::AwaitUsing => (self.current, SymbolKind::Using),
+        }
+    }
+
+    fn reference(&mut self, reference: &IdentifierReference) {
+        self.references.push(Reference {
+            name: reference.name.clone(),
+            range: reference.range.clone(),
+            scope: self.current,
+            symbol: None,
+        });
+    }
+
+    fn statement_list(&mut self, statements: &[StatementListItem]) {
+        for statement in statements {
+            self.statement_list_item(statement);
+        }
+    }
+
+    fn statement_list_item(&mut self, item: &StatementListItem) {
+        match item {
+            StatementListItem::Statement(statement) => self.statement(statement),
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => {
+                self.function_declaration(function);
+            },
+            StatementListItem::Declaration(Declaration::ClassDeclaration(class)) => self.class(class),
+            StatementListItem::Declaration(Declaration::LexicalDeclaration(list)) => {
+                self.variable_declaration_list(list);
+            },
+        }
+    }
+
+    /// Declare a function in a scope depending on where it is.
+    ///
+    /// Functions directly in a function or a script body are `var`-scoped,
+    /// and ones in blocks are lexically scoped. Annex B also declares
+    /// a legacy block function as `var` unless the name is taken there.
+    fn function_declaration(&mut self, function: &Function) {
+        if let Some(name) = &function.name {
+            self.declare(self.current, &name.name, SymbolKind::Function, name.range.clone());
+            if function.is_legacy_block_function {
+                let scope = var_scope(&self.scopes, self.current);
+                self.declare(scope, &name.name, SymbolKind::Var, name.range.clone());
+            }
+        }
+        self.function(function);
+    }
+
+    /// Walk `var`, `let`, `const` or `using` declarations.
+    fn variable_declaration_list(&mut self, list: &VariableDeclarationList) {
+        let (scope, kind) = self.variable_scope(list.kind);
+        self.declare_names(scope, &list.bound_names(), kind);
+        for declaration in &list.declarations {
+            self.binding(&declaration.target);
+            if let Some(initializer) = &declaration.initializer {
+                self.expression(initializer);
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.with_scope(ScopeKind::Block, block.range.clone(), |builder| builder.statement_list(&block.statements));
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        crate::grow_stack(|| {
+            match statement {
+                Statement::BlockStatement(block) => self.block(block),
+                Statement::VariableStatement(list) => self.variable_declaration_list(list),
+                Statement::EmptyStatement(_)
+                | Statement::DebuggerStatement(_)
+                | Statement::ContinueStatement(_)
+                | Statement::BreakStatement(_) => {},
+                Statement::ExpressionStatement(expression, _) => self.expression(expression),
+                Statement::IfStatement(statement) => {
+                    self.expression(&statement.condition);
+                    self.statement(&statement.consequent);
+                    if let Some(alternative) = &statement.alternative {
+                        self.statement(alternative);
+                    }
+                },
+                Statement::DoWhileStatement(statement) => {
+                    self.statement(&statement.body);
+                    self.expression(&statement.condition);
+                },
+                Statement::WhileStatement(statement) => {
+                    self.expression(&statement.condition);
+                    self.statement(&statement.body);
+                },
+                Statement::ForStatement(statement) => {
+                    self.with_scope(ScopeKind::Block, statement.range.clone(), |builder| {
+                        match &statement.init {
+                            Some(ForInit::VariableDeclarationList(list)) => builder.variable_declaration_list(list),
+                            Some(ForInit::Expression(expression)) => builder.expression(expression),
+                            None => {},
+                        }
+                        if let Some(condition) = &statement.condition {
+                            builder.expression(condition);
+                        }
+                        if let Some(update) = &statement.update {
+                            builder.expression(update);
+                        }
+                        builder.statement(&statement.body);
+                    });
+                },
+                Statement::ForInStatement(statement) | Statement::ForOfStatement(statement) => {
+                    // The source is evaluated with bindings of the head
+                    // declared but not initialized yet.
+                    self.with_scope(ScopeKind::Block, statement.range.clone(), |builder| {
+                        match &statement.target {
+                            ForTarget::Expression(target) => builder.expression(target),
+                            ForTarget::ForDeclaration { kind, binding, .. } => {
+                                let (scope, kind) = builder.variable_scope(*kind);
+                                builder.declare_names(scope, &binding.bound_names(), kind);
+                                builder.binding(binding);
+                            },
+                        }
+                        builder.expression(&statement.source);
+                        builder.statement(&statement.body);
+                    });
+                },
+                Statement::ReturnStatement(statement) => {
+                    if let Some(argument) = &statement.argument {
+                        self.expression(argument);
+                    }
+                },
+                Statement::WithStatement(statement) => {
+                    self.expression(&statement.object);
+                    self.statement(&statement.body);
+                },
+                Statement::SwitchStatement(statement) => {
+                    self.expression(&statement.discriminant);
+                    // Clauses share a single `CaseBlock` scope.
+                    self.with_scope(ScopeKind::Block, statement.range.clone(), |builder| {
+                        for case in &statement.cases {
+                            if let Some(test) = &case.test {
+                                builder.expression(test);
+                            }
+                            builder.statement_list(&case.statements);
+                        }
+                    });
+                },
+                Statement::LabelledStatement(statement) => match &*statement.body {
+                    LabelledItem::Statement(statement) => self.statement(statement),
+                    LabelledItem::FunctionDeclaration(function) => self.function_declaration(function),
+                },
+                Statement::ThrowStatement(statement) => self.expression(&statement.argument),
+                Statement::TryStatement(statement) => {
+                    self.block(&statement.block);
+                    if let Some(handler) = &statement.handler {
+                        self.catch_clause(handler);
+                    }
+                    if let Some(finalizer) = &statement.finalizer {
+                        self.block(finalizer);
+                    }
+                },
+            }
+        });
+    }
+
+    fn catch_clause(&mut self, handler: &CatchClause) {
+        self.with_scope(ScopeKind::Block, handler.range.clone(), |builder| {
+            if let Some(parameter) = &handler.parameter {
+                builder.declare_names(builder.current, &parameter.bound_names(), SymbolKind::CatchParameter);
+                builder.binding(parameter);
+            }
+            builder.statement_list(&handler.body.statements);
+        });
+    }
+
+    /// Walk expressions of a binding pattern: initializers and computed
+    /// property names.
+    fn binding(&mut self, binding: &Binding) {
+        crate::grow_stack(|| {
+            match binding {
+                Binding::BindingIdentifier(_) => {},
+                Binding::ObjectBindingPattern(pattern) => {
+                    for property in &pattern.properties {
+                        match property {
+                            BindingProperty::SingleNameBinding { initializer, .. } => {
+                                if let Some(initializer) = initializer {
+                                    self.expression(initializer);
+                                }
+                            },
+                            BindingProperty::Property { name, value } => {
+                                self.property_name(name);
+                                self.binding_element(value);
+                            },
+                        }
+                    }
+                },
+                Binding::ArrayBindingPattern(pattern) => {
+                    for element in pattern.elements.iter().flatten() {
+                        self.binding_element(element);
+                    }
+                    if let Some(rest) = &pattern.rest {
+                        self.binding(rest);
+                    }
+                },
+            }
+        });
+    }
+
+    fn binding_element(&mut self, element: &BindingElement) {
+        self.binding(&element.target);
+        if let Some(initializer) = &element.initializer {
+            self.expression(initializer);
+        }
+    }
+
+    /// Walk parameters and a body of a function-like node in a new scope,
+    /// also declaring `arguments` there unless it is an arrow function or
+    /// the name is taken.
+    fn function_like(
+        &mut self,
+        kind: ScopeKind,
+        range: Range<usize>,
+        parameters: &FormalParameters,
+        walk_body: impl FnOnce(&mut Self)
+    ) {
+        self.with_scope(kind, range.clone(), |builder| {
+            builder.declare_names(builder.current, &parameters.bound_names(), SymbolKind::Parameter);
+            for parameter in &parameters.parameters {
+                builder.binding_element(parameter);
+            }
+            if let Some(rest) = &parameters.rest {
+                builder.binding(rest);
+            }
+            walk_body(builder);
+            if kind == ScopeKind::Function {
+                builder.declare(builder.current, "arguments", SymbolKind::Arguments, range);
+            }
+        });
+    }
+
+    /// Walk a function, binding a name of a function expression inside it.
+    fn function(&mut self, function: &Function) {
+        self.function_like(ScopeKind::Function, function.range.clone(), &function.parameters, |builder| {
+            builder.statement_list(&function.body);
+            if let (false, Some(name)) = (function.is_declaration, &function.name) {
+                builder.declare(builder.current, &name.name, SymbolKind::Function, name.range.clone());
+            }
+        });
+    }
+
+    fn method_definition(&mut self, method: &MethodDefinition) {
+        if let ClassElementName::PropertyName(name) = &method.name {
+            self.property_name(name);
+        }
+        self.function_like(ScopeKind::Function, method.range.clone(), &method.parameters, |builder| {
+            builder.statement_list(&method.body);
+        });
+    }
+
+    /// Walk a class, binding its name both around it for a declaration and
+    /// inside it.
+    fn class(&mut self, class: &Class) {
+        if let (true, Some(name)) = (class.is_declaration, &class.name) {
+            self.declare(self.current, &name.name, SymbolKind::Class, name.range.clone());
+        }
+        for decorator in &class.decorators {
+            self.expression(&decorator.expression);
+        }
+        self.with_scope(ScopeKind::Class, class.range.clone(), |builder| {
+            if let Some(name) = &class.name {
+                builder.declare(builder.current, &name.name, SymbolKind::Class, name.range.clone());
+            }
+            if let Some(heritage) = &class.heritage {
+                builder.expression(heritage);
+            }
+            for element in &class.elements {
+                match element {
+                    ClassElement::MethodDefinition { decorators, method, .. } => {
+                        for decorator in decorators {
+                            builder.expression(&decorator.expression);
+                        }
+                        builder.method_definition(method);
+                    },
+                    ClassElement::FieldDefinition { decorators, name, initializer, range, .. } => {
+                        for decorator in decorators {
+                            builder.expression(&decorator.expression);
+                        }
+                        if let ClassElementName::PropertyName(name) = name {
+                            builder.property_name(name);
+                        }
+                        // An initializer is evaluated as a method of its own.
+                        if let Some(initializer) = initializer {
+                            builder.with_scope(ScopeKind::Function, range.clone(), |builder| {
+                                builder.expression(initializer);
+                            });
+                        }
+                    },
+                    ClassElement::ClassStaticBlock(statements, range) => {
+                        builder.with_scope(ScopeKind::Function, range.clone(), |builder| {
+                            builder.statement_list(statements);
+                        });
+                    },
+                }
+            }
+        });
+    }
+
+    fn property_name(&mut self, name: &PropertyName) {
+        if let PropertyName::ComputedPropertyName(expression, _) = name {
+            self.expression(expression);
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        crate::grow_stack(|| {
+            match expression {
+                Expression::This(_)
+                | Expression::Literal(..)
+                | Expression::RegularExpressionLiteral(..)
+                | Expression::MetaProperty(..) => {},
+                Expression::IdentifierReference(reference) => self.reference(reference),
+                Expression::ArrayLiteral(array) => {
+                    for element in &array.elements {
+                        match element {
+                            ArrayElement::Elision(_) => {},
+                            ArrayElement::AssignmentExpression(element) | ArrayElement::SpreadElement(element, _) => {
+                                self.expression(element);
+                            },
+                        }
+                    }
+                },
+                Expression::ObjectLiteral(object) => {
+                    for property in &object.properties {
+                        match property {
+                            PropertyDefinition::IdentifierReference(reference) => self.reference(reference),
+                            PropertyDefinition::Property { name, value } => {
+                                self.property_name(name);
+                                self.expression(value);
+                            },
+                            PropertyDefinition::MethodDefinition(method) => self.method_definition(method),
+                            PropertyDefinition::SpreadProperty(value, _) => self.expression(value),
+                            PropertyDefinition::CoverInitializedName { name, initializer } => {
+                                self.reference(name);
+                                self.expression(initializer);
+                            },
+                        }
+                    }
+                },
+                Expression::FunctionExpression(function) => self.function(function),
+                Expression::ClassExpression(class) => self.class(class),
+                Expression::TemplateLiteral(template) => {
+                    for substitution in &template.substitutions {
+                        self.expression(substitution);
+                    }
+                },
+                Expression::ParenthesizedExpression(expression) => self.expression(&expression.expression),
+                Expression::MemberExpression(member) => {
+                    self.expression(&member.object);
+                    if let MemberProperty::Expression(property) = &member.property {
+                        self.expression(property);
+                    }
+                },
+                Expression::SuperProperty(property) => {
+                    if let MemberProperty::Expression(property) = &property.property {
+                        self.expression(property);
+                    }
+                },
+                Expression::TaggedTemplate(tagged) => {
+                    self.expression(&tagged.tag);
+                    for substitution in &tagged.template.substitutions {
+                        self.expression(substitution);
+                    }
+                },
+                Expression::CallExpression(call) => {
+                    self.expression(&call.callee);
+                    for argument in &call.arguments {
+                        self.argument(argument);
+                    }
+                },
+                Expression::SuperCall(call) => {
+                    for argument in &call.arguments {
+                        self.argument(argument);
+                    }
+                },
+                Expression::ImportCall(call) => {
+                    self.expression(&call.specifier);
+                    if let Some(options) = &call.options {
+                        self.expression(options);
+                    }
+                },
+                Expression::OptionalExpression(chain) => self.expression(&chain.expression),
+                Expression::NewExpression(new) => {
+                    self.expression(&new.callee);
+                    for argument in new.arguments.iter().flatten() {
+                        self.argument(argument);
+                    }
+                },
+                Expression::UpdateExpression(update) => self.expression(&update.operand),
+                Expression::UnaryExpression(unary) => self.expression(&unary.operand),
+                Expression::AwaitExpression(expression) => self.expression(&expression.argument),
+                Expression::BinaryExpression(binary) => {
+                    self.expression(&binary.left);
+                    self.expression(&binary.right);
+                },
+                Expression::PrivateInExpression(expression) => self.expression(&expression.object),
+                Expression::ConditionalExpression(conditional) => {
+                    self.expression(&conditional.condition);
+                    self.expression(&conditional.consequent);
+                    self.expression(&conditional.alternative);
+                },
+                Expression::YieldExpression(expression) => {
+                    if let Some(argument) = &expression.argument {
+                        self.expression(argument);
+                    }
+                },
+                Expression::ArrowFunction(function) => {
+                    self.function_like(ScopeKind::ArrowFunction, function.range.clone(), &function.parameters, |builder| {
+                        match &function.body {
+                            ConciseBody::Expression(body) => builder.expression(body),
+                            ConciseBody::FunctionBody(body, _) => builder.statement_list(body),
+                        }
+                    });
+                },
+                Expression::AssignmentExpression(assignment) => {
+                    self.expression(&assignment.target);
+                    self.expression(&assignment.value);
+                },
+                Expression::CommaExpression(comma) => {
+                    for expression in &comma.expressions {
+                        self.expression(expression);
+                    }
+                },
+            }
+        });
+    }
+
+    fn argument(&mut self, argument: &Argument) {
+        let (Argument::AssignmentExpression(expression) | Argument::SpreadElement(expression, _)) = argument;
+        self.expression(expression);
+    }
+}
@@ -0,0 +1,321 @@
+//! Conversion of encoded `.js` and `.mjs` files into source text.
+//!
+//! From <https://262.ecma-international.org/14.0/#sec-source-text>:
+//!
+//! > ECMAScript source text is a sequence of Unicode code points. All Unicode
+//! > code point values from U+0000 to U+10FFFF, including surrogate code
+//! > points, may occur in ECMAScript source text where permitted by
+//! > the ECMAScript grammars. The actual encodings used to store and
+//! > interchange ECMAScript source text is not relevant to this specification.
+//!
+//! The tokenizer accepts `&str` that cannot carry surrogate code points, so
+//! ill-formed input is either rejected or repaired here, as an embedder
+//! chooses.
+
+use crate::lexical_grammar::Lexer;
+use crate::{ErrorCode, SourceCodeError};
+use std::borrow::Cow;
+
+/// What to do with input that is not a well-formed encoding of Unicode text.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InputHandling {
+    /// Stop with an error pointing to the first ill-formed sequence.
+    #[default]
+    Strict,
+    /// Replace each ill-formed sequence with U+FFFD REPLACEMENT CHARACTER and
+    /// continue.
+    Lossy,
+}
+
+/// Decode UTF-8 bytes into source text.
+///
+/// # Errors
+///
+/// In [`InputHandling::Strict`] mode, will return `Err` with
+/// [`SourceCodeError`] for the first ill-formed sequence. Its range refers to
+/// the U+FFFD the sequence would be replaced with in lossy mode.
+pub fn decode_utf8(bytes: &[u8], handling: InputHandling) -> Result<Cow<'_, str>, SourceCodeError> {
+    let decoded = String::from_utf8_lossy(bytes);
+    match (std::str::from_utf8(bytes), handling) {
+        (Ok(_), _) | (Err(_), InputHandling::Lossy) => Ok(decoded),
+        (Err(error), InputHandling::Strict) => {
+            // Offsets of the lossy text match the input up to the first
+            // replacement.
+            let start = error.valid_up_to();
+            Err(ill_formed_input(&decoded, start, "ill-formed UTF-8 byte sequence"))
+        },
+    }
+}
+
+/// Decode UTF-16 code units into source text.
+///
+/// # Errors
+///
+/// In [`InputHandling::Strict`] mode, will return `Err` with
+/// [`SourceCodeError`] for the first unpaired surrogate. Its range refers to
+/// the U+FFFD the surrogate would be replaced with in lossy mode.
+pub fn decode_utf16(units: &[u16], handling: InputHandling) -> Result<String, SourceCodeError> {
+    let mut decoded = String::with_capacity(units.len());
+    let mut first_error = None;
+    for codepoint in char::decode_utf16(units.iter().copied()) {
+        let codepoint = codepoint.unwrap_or_else(|_| {
+            first_error.get_or_insert(decoded.len());
+            char::REPLACEMENT_CHARACTER
+        });
+        decoded.push(codepoint);
+    }
+    match (first_error, handling) {
+        (None, _) | (Some(_), InputHandling::Lossy) => Ok(decoded),
+        (Some(start), InputHandling::Strict) => {
+            Err(ill_formed_input(&decoded, start, "unpaired UTF-16 surrogate"))
+        },
+    }
+}
+
+/// Source text received as UTF-16 code units, with offsets convertible
+/// between the code units and the decoded text.
+///
+/// Unlike [`decode_utf16`], unpaired surrogates are accepted as the
+/// specification allows. The grammars permit them only inside comments and
+/// string, template and regular expression literals, where any code point but
+/// a few delimiters is allowed, so `text` carries U+FFFD REPLACEMENT CHARACTER
+/// in their place for tokenization and [`Utf16Source::code_units`] gives
+/// the original code units back.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Utf16Source {
+    /// Decoded text.
+    pub text: String,
+    /// Offsets in `text` and in code units right after each non-ASCII code
+    /// point.
+    checkpoints: Vec<(usize, usize)>,
+    /// Code unit offsets and values of unpaired surrogates.
+    surrogates: Vec<(usize, u16)>,
+}
+
+impl Utf16Source {
+    #[must_use]
+    pub fn new(units: &[u16]) -> Self {
+        let mut text = String::with_capacity(units.len());
+        let mut checkpoints = Vec::new();
+        let mut surrogates = Vec::new();
+        let mut unit_offset = 0;
+        for codepoint in char::decode_utf16(units.iter().copied()) {
+            let codepoint = codepoint.unwrap_or_else(|error| {
+                surrogates.push((unit_offset, error.unpaired_surrogate()));
+                char::REPLACEMENT_CHARACTER
+            });
+            text.push(codepoint);
+            unit_offset += codepoint.len_utf16();
+            if !codepoint.is_ascii() {
+                checkpoints.push((text.len(), unit_offset));
+            }
+        }
+        Self { text, checkpoints, surrogates }
+    }
+
+    /// Convert a byte offset in `text` into a code unit offset.
+    #[must_use]
+    pub fn utf16_offset(&self, offset: usize) -> usize {
+        let passed = self.checkpoints.partition_point(|&(end, _)| end <= offset);
+        let (byte_start, unit_start) = passed.checked_sub(1).map_or((0, 0), |last| self.checkpoints[last]);
+        unit_start + (offset - byte_start)
+    }
+
+    /// Convert a code unit offset into a byte offset in `text`.
+    ///
+    /// Returns `None` for an offset past the end or between two code units
+    /// of a surrogate pair.
+    #[must_use]
+    pub fn utf8_offset(&self, offset: usize) -> Option<usize> {
+        let passed = self.checkpoints.partition_point(|&(_, end)| end <= offset);
+        let (byte_start, unit_start) = passed.checked_sub(1).map_or((0, 0), |last| self.checkpoints[last]);
+        let byte_offset = byte_start + (offset - unit_start);
+        self.text.is_char_boundary(byte_offset).then_some(byte_offset)
+    }
+
+    /// Convert a byte range in `text` into a code unit range.
+    #[must_use]
+    pub fn utf16_range(&self, range: std::ops::Range<usize>) -> std::ops::Range<usize> {
+        self.utf16_offset(range.start)..self.utf16_offset(range.end)
+    }
+
+    /// Make an error reported for `text` refer to code unit offsets.
+    #[must_use]
+    pub fn utf16_error(&self, mut error: SourceCodeError) -> SourceCodeError {
+        error.range = self.utf16_range(error.range);
+        for label in &mut error.labels {
+            label.range = self.utf16_range(label.range.clone());
+        }
+        error
+    }
+
+    /// Get the original code units of a code unit range, with unpaired
+    /// surrogates restored.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the range lies outside of the text or splits
+    /// a surrogate pair.
+    #[must_use]
+    pub fn code_units(&self, range: std::ops::Range<usize>) -> Vec<u16> {
+        let (Some(start), Some(end)) = (self.utf8_offset(range.start), self.utf8_offset(range.end)) else {
+            panic!("{range:?} does not fall on code point boundaries");
+        };
+        let mut units: Vec<u16> = self.text[start..end].encode_utf16().collect();
+        let first = self.surrogates.partition_point(|&(offset, _)| offset < range.start);
+        for &(offset, surrogate) in self.surrogates[first..].iter().take_while(|(offset, _)| *offset < range.end) {
+            units[offset - range.start] = surrogate;
+        }
+        units
+    }
+}
+
+/// An encoding of a `.js`/`.mjs` file.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16LittleEndian,
+    Utf16BigEndian,
+}
+
+impl Encoding {
+    /// Detect an encoding by a byte order mark, if any, and get its length.
+    ///
+    /// Text without a byte order mark is taken as UTF-8.
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> (Self, usize) {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => (Self::Utf8, 3),
+            [0xFF, 0xFE, ..] => (Self::Utf16LittleEndian, 2),
+            [0xFE, 0xFF, ..] => (Self::Utf16BigEndian, 2),
+            _ => (Self::Utf8, 0),
+        }
+    }
+}
+
+/// Source text of a file loaded as is.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SourceFile {
+    /// Decoded text without a byte order mark.
+    pub text: String,
+    /// An encoding the text was decoded from.
+    pub encoding: Encoding,
+}
+
+impl SourceFile {
+    /// Decode file content, detecting its encoding by a byte order mark.
+    ///
+    /// The byte order mark is dropped so that it is not taken for
+    /// a `WhiteSpace` and does not prevent a `HashbangComment` from starting
+    /// the text.
+    ///
+    /// # Errors
+    ///
+    /// In [`InputHandling::Strict`] mode, will return `Err` with
+    /// [`SourceCodeError`] for the first ill-formed sequence, including a last
+    /// odd byte of UTF-16. Its range refers to the U+FFFD the sequence would
+    /// be replaced with in lossy mode.
+    pub fn from_bytes(bytes: &[u8], handling: InputHandling) -> Result<Self, SourceCodeError> {
+        let (encoding, bom_length) = Encoding::detect(bytes);
+        let content = &bytes[bom_length..];
+        let from_pair = match encoding {
+            Encoding::Utf8 => {
+                let text = decode_utf8(content, handling)?.into_owned();
+                return Ok(Self { text, encoding });
+            },
+            Encoding::Utf16LittleEndian => u16::from_le_bytes,
+            Encoding::Utf16BigEndian => u16::from_be_bytes,
+        };
+        let pairs = content.chunks_exact(2);
+        let is_odd = !pairs.remainder().is_empty();
+        let units: Vec<u16> = pairs.map(|pair| from_pair([pair[0], pair[1]])).collect();
+        let mut text = decode_utf16(&units, handling)?;
+        if is_odd {
+            if handling == InputHandling::Strict {
+                return Err(ill_formed_input(&text, text.len(), "truncated UTF-16 code unit"));
+            }
+            text.push(char::REPLACEMENT_CHARACTER);
+        }
+        Ok(Self { text, encoding })
+    }
+
+    /// Iterate over tokens of the text as a *Script* or *Module*.
+    #[must_use]
+    pub fn tokens(&self) -> Lexer<'_> {
+        Lexer::new(&self.text)
+    }
+}
+
+fn ill_formed_input(decoded: &str, start: usize, message: &str) -> SourceCodeError {
+    let range = start..start + char::REPLACEMENT_CHARACTER.len_utf8();
+    SourceCodeError::new(decoded, range, ErrorCode::IllFormedInput, message.to_owned())
+}
+
+/// Source text with every `LineTerminatorSequence` replaced by U+000A LINE
+/// FEED, for tools that prefer uniform line handling.
+///
+/// Normalization does not change line and column numbers, and offsets can be
+/// mapped back to the original text for diagnostics.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NormalizedSource {
+    /// Text with LF line endings only.
+    pub text: String,
+    /// Offsets in `text` right after each shortened line terminator,
+    /// paired with the total number of bytes removed by then.
+    shifts: Vec<(usize, usize)>,
+}
+
+impl NormalizedSource {
+    /// Replace CR LF, CR, LS, and PS line terminators with LF.
+    #[must_use]
+    pub fn new(source: &str) -> Self {
+        let mut text = String::with_capacity(source.len());
+        let mut shifts = Vec::new();
+        let mut codepoints = source.chars().peekable();
+        while let Some(codepoint) = codepoints.next() {
+            let removed = match codepoint {
+                '\r' if codepoints.next_if_eq(&'\n').is_some() => 1,
+                '\u{2028}' | '\u{2029}' => codepoint.len_utf8() - 1,
+                '\r' => 0,
+                _ => {
+                    text.push(codepoint);
+                    continue;
+                },
+            };
+            text.push('\n');
+            if removed > 0 {
+                let total = shifts.last().map_or(0, |&(_, total)| total);
+                shifts.push((text.len(), total + removed));
+            }
+        }
+        Self { text, shifts }
+    }
+
+    /// Convert a byte offset in the normalized text into a byte offset in
+    /// the original one.
+    #[must_use]
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let passed = self.shifts.partition_point(|&(end, _)| end <= offset);
+        let removed = passed.checked_sub(1).map_or(0, |last| self.shifts[last].1);
+        offset + removed
+    }
+
+    /// Convert a byte range in the normalized text into a byte range in the
+    /// original one.
+    #[must_use]
+    pub fn original_range(&self, range: std::ops::Range<usize>) -> std::ops::Range<usize> {
+        self.original_offset(range.start)..self.original_offset(range.end)
+    }
+
+    /// Make an error reported for the normalized text refer to the original
+    /// one.
+    #[must_use]
+    pub fn original_error(&self, mut error: SourceCodeError) -> SourceCodeError {
+        error.range = self.original_range(error.range);
+        for label in &mut error.labels {
+            label.range = self.original_range(label.range.clone());
+        }
+        error
+    }
+}
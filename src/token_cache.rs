@@ -0,0 +1,159 @@
+//! A binary format for saving spanned token streams and loading them back
+//! without running the tokenizer again.
+//!
+//! The layout, with every number stored as an unsigned LEB128 varint:
+//!
+//! ```plain
+//! "EETS" version
+//! source-length source-hash (FNV-1a of the source text, 8 bytes LE)
+//! kind-count kind*          (TokenKind values in order of first use)
+//! token-count token*
+//! token: kind-index gap-from-previous-end length
+//! ```
+//!
+//! The length and the hash of the source text tie a stream to the text it
+//! was saved for, so a stale cache is rejected instead of yielding spans
+//! into another text.
+
+use crate::lexical_grammar::{CompactToken, Span, TokenKind};
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 4] = b"EETS";
+
+/// The format revision; bumped every time [`TokenKind`] numbering or
+/// the layout changes. `tests/test_token_cache.rs` pins the numbering each
+/// revision was saved with, so reordering kinds without a bump fails there.
+pub const VERSION: u8 = 1;
+
+/// Serialize tokens sorted by their position in a source text.
+///
+/// # Errors
+///
+/// Will return `Err` if the tokens overlap, go out of order or past the end
+/// of `source`, or if `writer` fails.
+pub fn save<W: Write>(source: &str, tokens: &[CompactToken], mut writer: W) -> io::Result<()> {
+    if tokens.last().is_some_and(|token| token.span.end > source.len()) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "tokens go past the end of the source"));
+    }
+    let mut kinds: Vec<TokenKind> = Vec::new();
+    let mut indices = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let index = kinds.iter().position(|&kind| kind == token.kind).unwrap_or_else(|| {
+            kinds.push(token.kind);
+            kinds.len() - 1
+        });
+        indices.push(index);
+    }
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    write_varint(&mut writer, source.len())?;
+    writer.write_all(&hash(source).to_le_bytes())?;
+    write_varint(&mut writer, kinds.len())?;
+    for kind in kinds {
+        write_varint(&mut writer, u16::from(kind).into())?;
+    }
+    write_varint(&mut writer, tokens.len())?;
+    let mut previous_end = 0;
+    for (token, index) in tokens.iter().zip(indices) {
+        let gap = token.span.start.checked_sub(previous_end)
+            .filter(|_| token.span.start <= token.span.end)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "tokens are out of order"))?;
+        write_varint(&mut writer, index)?;
+        write_varint(&mut writer, gap)?;
+        write_varint(&mut writer, token.span.len())?;
+        previous_end = token.span.end;
+    }
+    Ok(())
+}
+
+/// Deserialize tokens written by [`save`] for the same source text.
+///
+/// # Errors
+///
+/// Will return `Err` with [`io::ErrorKind::InvalidData`] if the data is not
+/// a token stream of the current [`VERSION`] or was saved for another
+/// source text, or if `reader` fails.
+pub fn load<R: Read>(source: &str, mut reader: R) -> io::Result<Vec<CompactToken>> {
+    let mut header = [0; 5];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+        return Err(invalid_data("not a token stream"));
+    }
+    if header[4] != VERSION {
+        return Err(invalid_data("unsupported token stream version"));
+    }
+    let length = read_varint(&mut reader)?;
+    let mut source_hash = [0; 8];
+    reader.read_exact(&mut source_hash)?;
+    if length != source.len() || u64::from_le_bytes(source_hash) != hash(source) {
+        return Err(invalid_data("token stream was saved for another source text"));
+    }
+
+    let kind_count = read_varint(&mut reader)?;
+    let kinds = (0..kind_count)
+        .map(|_| {
+            let value = u16::try_from(read_varint(&mut reader)?)
+                .map_err(|_| invalid_data("token kind out of range"))?;
+            TokenKind::try_from(value).map_err(|_| invalid_data("unknown token kind"))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let token_count = read_varint(&mut reader)?;
+    let mut tokens = Vec::new();
+    let mut previous_end: usize = 0;
+    for _ in 0..token_count {
+        let kind = *kinds.get(read_varint(&mut reader)?)
+            .ok_or_else(|| invalid_data("token kind index out of range"))?;
+        let gap = read_varint(&mut reader)?;
+        let length = read_varint(&mut reader)?;
+        let start = previous_end.checked_add(gap);
+        let end = start.and_then(|start| start.checked_add(length));
+        let (Some(start), Some(end)) = (start, end.filter(|&end| end <= source.len())) else {
+            return Err(invalid_data("token span out of range"));
+        };
+        tokens.push(CompactToken { kind, span: Span::new(start, end) });
+        previous_end = end;
+    }
+    Ok(tokens)
+}
+
+/// 64-bit FNV-1a, which stays the same across platforms and Rust releases
+/// unlike hashers of the standard library.
+fn hash(source: &str) -> u64 {
+    source.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: usize) -> io::Result<()> {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<usize> {
+    let mut value: usize = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        let bits = usize::from(byte[0] & 0x7F);
+        if shift >= usize::BITS || (bits << shift) >> shift != bits {
+            return Err(invalid_data("varint overflow"));
+        }
+        value |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
@@ -0,0 +1,430 @@
+//! Tokenization of a source text arriving in chunks.
+//!
+//! [`StreamingLexer`] reads a `.js`/`.mjs` text from [`io::Read`] and keeps
+//! only the text not returned as tokens yet, so a script streamed from flash
+//! or a network does not need to fit into memory at once.
+
+use crate::lexical_grammar::{
+    get_next_token_with_options,
+    CompactToken,
+    GoalSymbols,
+    LexerOptions,
+    Span,
+    TokenKind,
+    Tokenizer,
+};
+use crate::{ErrorCode, LineIndex, SourceCodeError};
+use std::fmt;
+use std::io::{self, Read};
+
+/// A minimal number of bytes requested from a reader at once.
+const CHUNK_SIZE: usize = 4096;
+
+/// A number of characters following a punctuator that decide whether it
+/// is a part of a longer token, as `<` of `<!--` or `.` of `...`.
+const PUNCTUATOR_LOOKAHEAD: usize = 3;
+
+/// A number of characters from a failure after a recognized token that
+/// decide whether more input makes it a part of the token, as `e+5` of
+/// `1e+5`.
+const CONTINUATION_LOOKAHEAD: usize = 3;
+
+/// Text that may end a token at the buffer start, telling when to try
+/// recognizing it again.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TokenEnd {
+    /// `*/` of a multi-line comment.
+    CommentEnd,
+    /// A line terminator after a single-line comment.
+    LineTerminator,
+    /// An unescaped quote or a line terminator in a string literal.
+    Quote(char),
+    /// An unescaped `` ` `` or `${` of a template.
+    Template,
+    /// An unescaped `/` out of a class or a line terminator in a regular
+    /// expression literal.
+    RegularExpression,
+    /// A character other than a space or a tab after white space.
+    WhiteSpace,
+    /// A character other than an ASCII letter, a digit, `_` or `$`, which
+    /// go on in names and numbers.
+    Other,
+}
+
+/// A failure of [`StreamingLexer`].
+#[derive(Debug)]
+pub enum StreamError {
+    /// The reader failed.
+    Io(io::Error),
+    /// The source text is ill-formed; positions are relative to the start of
+    /// the whole stream.
+    Source(SourceCodeError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(formatter, "cannot read source text: {error}"),
+            Self::Source(error) => error.fmt(formatter),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Source(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<SourceCodeError> for StreamError {
+    fn from(error: SourceCodeError) -> Self {
+        Self::Source(error)
+    }
+}
+
+/// An iterator over tokens of a UTF-8 `.js`/`.mjs` text read from
+/// [`io::Read`].
+///
+/// Yields each token in its compact form, spanned relative to the start of
+/// the stream, together with its source text. Goal symbols are selected the
+/// same way [`Tokenizer`] does.
+///
+/// Only an incomplete tail of the text is buffered. Since a longer token may
+/// need more input to be recognized (`/*` starting a comment versus `/`,
+/// `1e5` versus `1`), a token is returned once enough text following it is
+/// read to rule a continuation out or the input ends. A malformed token is
+/// reported as soon as more input cannot make it correct: a code point that
+/// starts no token, a string or a regular expression literal broken by
+/// a line terminator, or a name or a digit a few characters past a numeric
+/// literal. So memory use is bounded by the longest token rather than line.
+///
+/// An incomplete token is recognized again only once text that may end it
+/// arrives or the buffer doubles, with the scan for such text resumed where
+/// the previous read stopped, so a long token costs time linear in its
+/// length however small reads are.
+///
+/// After an error the iterator stops.
+#[derive(Debug)]
+pub struct StreamingLexer<R> {
+    reader: R,
+    /// Decoded text not returned as tokens yet.
+    buffer: String,
+    /// Bytes of a UTF-8 sequence split between reads.
+    undecoded: Vec<u8>,
+    /// Byte offset of the buffer start in the whole stream.
+    offset: usize,
+    /// 1-based line and column of the buffer start.
+    line: usize,
+    column: usize,
+    /// Whether the text returned last ends with `<CR>`, so a `<LF>` at the
+    /// buffer start does not break a line again.
+    after_cr: bool,
+    /// Buffer length at the last attempt to recognize a token at its start.
+    attempted: usize,
+    /// Byte offset in the buffer up to which text was scanned for an end of
+    /// the token at its start, along with the state of the scan there.
+    scanned: usize,
+    is_escaped: bool,
+    is_in_class: bool,
+    tokenizer: Tokenizer,
+    options: LexerOptions,
+    end_of_input: bool,
+    failed: bool,
+}
+
+impl<R: Read> StreamingLexer<R> {
+    /// Tokenize a *Script* or *Module* with [`LexerOptions::default`].
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, LexerOptions::default())
+    }
+
+    #[must_use]
+    pub const fn with_options(reader: R, options: LexerOptions) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            undecoded: Vec::new(),
+            offset: 0,
+            line: 1,
+            column: 1,
+            after_cr: false,
+            attempted: 0,
+            scanned: 0,
+            is_escaped: false,
+            is_in_class: false,
+            tokenizer: Tokenizer::new(),
+            options,
+            end_of_input: false,
+            failed: false,
+        }
+    }
+
+    /// Byte offset of a next token in the whole stream.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn next_token(&mut self) -> Result<Option<(CompactToken, String)>, StreamError> {
+        loop {
+            if self.buffer.is_empty() && self.end_of_input {
+                return Ok(None);
+            }
+            let is_worth_attempt = self.end_of_input || self.may_end() || self.buffer.len() >= 2 * self.attempted;
+            if !self.buffer.is_empty() && is_worth_attempt {
+                self.attempted = self.buffer.len();
+                match get_next_token_with_options(&self.buffer, 0, self.tokenizer.goal(), &self.options) {
+                    Ok((token, range)) if self.end_of_input || self.is_complete(token.kind(), range.end) => {
+                        self.tokenizer.observe(&token);
+                        let kind = token.kind();
+                        let span = Span::new(self.offset, self.offset + range.end);
+                        let text = self.consume(range.end);
+                        return Ok(Some((CompactToken { kind, span }, text)));
+                    },
+                    Err(error) if self.end_of_input || self.is_final(&error) => return Err(self.reposition(error).into()),
+                    _ => {},
+                }
+            }
+            self.fill()?;
+        }
+    }
+
+    /// Check that more input cannot turn a token recognized at the buffer
+    /// start into another one.
+    fn is_complete(&self, kind: TokenKind, end: usize) -> bool {
+        // Unterminated strings, templates and regular expressions fail to
+        // tokenize, but an unterminated comment is taken for `/`.
+        if self.buffer.starts_with("/*") && kind != TokenKind::MultiLineComment {
+            return false;
+        }
+        let mut following = self.buffer[end..].chars();
+        match kind {
+            // Closing delimiters end these tokens.
+            TokenKind::MultiLineComment
+            | TokenKind::StringLiteral
+            | TokenKind::NoSubstitutionTemplate
+            | TokenKind::TemplateHead
+            | TokenKind::TemplateMiddle
+            | TokenKind::TemplateTail => true,
+            _ if kind.is_punctuator() => following.nth(PUNCTUATOR_LOOKAHEAD - 1).is_some(),
+            // Identifiers, numbers, comments, white space and flags of
+            // regular expressions go on while their characters follow,
+            // including escaped ones in identifiers.
+            _ if following.as_str().starts_with('\\') => is_escape_read(following.as_str()),
+            _ => following.next().is_some(),
+        }
+    }
+
+    /// Scan text read since the last attempt to recognize a token at the
+    /// buffer start for text that may end it, resuming the scan where it
+    /// stopped.
+    fn may_end(&mut self) -> bool {
+        let goal = self.tokenizer.goal();
+        let allows_regular_expression = matches!(
+            goal,
+            GoalSymbols::InputElementHashbangOrRegExp | GoalSymbols::InputElementRegExp | GoalSymbols::InputElementRegExpOrTemplateTail
+        );
+        let allows_template_tail = matches!(goal, GoalSymbols::InputElementRegExpOrTemplateTail | GoalSymbols::InputElementTemplateTail);
+        let bytes = self.buffer.as_bytes();
+        let (opening, end) = match bytes {
+            [b'/', b'*', ..] => (2, TokenEnd::CommentEnd),
+            [b'/', b'/', ..] | [b'#', b'!', ..] => (2, TokenEnd::LineTerminator),
+            [b'<', b'!', b'-', b'-', ..] if self.options.html_like_comments => (4, TokenEnd::LineTerminator),
+            [quote @ (b'"' | b'\''), ..] => (1, TokenEnd::Quote(char::from(*quote))),
+            [b'`', ..] => (1, TokenEnd::Template),
+            [b'}', ..] if allows_template_tail => (1, TokenEnd::Template),
+            [b'/', ..] if allows_regular_expression => (1, TokenEnd::RegularExpression),
+            [b' ' | b'\t', ..] => (1, TokenEnd::WhiteSpace),
+            _ => (0, TokenEnd::Other),
+        };
+        let start = self.scanned.max(opening).min(self.buffer.len());
+        for (position, character) in self.buffer[start..].char_indices() {
+            let position = start + position;
+            let is_line_terminator = matches!(character, '\n' | '\r' | '\u{2028}' | '\u{2029}');
+            let was_escaped = std::mem::take(&mut self.is_escaped);
+            let is_end = match end {
+                TokenEnd::CommentEnd => character == '/' && position > opening && bytes[position - 1] == b'*',
+                TokenEnd::LineTerminator => is_line_terminator,
+                // A line terminator after `\` continues a string literal.
+                TokenEnd::Quote(quote) if !was_escaped => {
+                    self.is_escaped = character == '\\';
+                    character == quote || character == '\n' || character == '\r'
+                },
+                TokenEnd::Template if !was_escaped => {
+                    self.is_escaped = character == '\\';
+                    character == '`' || character == '{' && bytes[position - 1] == b'$'
+                },
+                TokenEnd::RegularExpression if !was_escaped => {
+                    self.is_escaped = character == '\\';
+                    match character {
+                        '[' => self.is_in_class = true,
+                        ']' => self.is_in_class = false,
+                        _ => {},
+                    }
+                    character == '/' && !self.is_in_class || is_line_terminator
+                },
+                TokenEnd::RegularExpression => is_line_terminator,
+                TokenEnd::Quote(_) | TokenEnd::Template => false,
+                TokenEnd::WhiteSpace => !matches!(character, ' ' | '\t'),
+                TokenEnd::Other => !(character.is_ascii_alphanumeric() || character == '_' || character == '$'),
+            };
+            if is_end {
+                self.scanned = position + character.len_utf8();
+                return true;
+            }
+        }
+        self.scanned = self.buffer.len();
+        false
+    }
+
+    /// Check that more input cannot turn text failed to be recognized at
+    /// the buffer start into a token.
+    fn is_final(&self, error: &SourceCodeError) -> bool {
+        // A recognized token is followed by a name or a digit, which may
+        // still go on into the token, as `e` into `1e5`.
+        if error.range.start > 0 {
+            return self.buffer[error.range.start..].chars().nth(CONTINUATION_LOOKAHEAD - 1).is_some();
+        }
+        // An unterminated literal is labelled where it stops, which is before
+        // the buffer end only at a line terminator.
+        if let Some(label) = error.labels.first() {
+            return label.range.start < self.buffer.len();
+        }
+        let mut characters = self.buffer.chars();
+        match characters.next() {
+            Some('\\') => is_escape_read(&self.buffer),
+            Some('#') => {
+                let name = characters.as_str();
+                !name.is_empty() && (!name.starts_with('\\') || is_escape_read(name))
+            },
+            Some(first) => {
+                let mut encoded = [0; 4];
+                let first = first.encode_utf8(&mut encoded);
+                let starts_token = get_next_token_with_options(first, 0, self.tokenizer.goal(), &self.options).is_ok();
+                !starts_token || characters.nth(CONTINUATION_LOOKAHEAD - 1).is_some()
+            },
+            None => false,
+        }
+    }
+
+    /// Read more input into the buffer.
+    fn fill(&mut self) -> Result<(), StreamError> {
+        let mut chunk = [0; CHUNK_SIZE];
+        let read = loop {
+            match self.reader.read(&mut chunk) {
+                Ok(read) => break read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {},
+                Err(error) => return Err(error.into()),
+            }
+        };
+        if read == 0 {
+            self.end_of_input = true;
+            if self.undecoded.is_empty() {
+                return Ok(());
+            }
+            return Err(self.ill_formed_input().into());
+        }
+        self.undecoded.extend_from_slice(&chunk[..read]);
+        let (valid, is_ill_formed) = match std::str::from_utf8(&self.undecoded) {
+            Ok(text) => (text.len(), false),
+            Err(error) => (error.valid_up_to(), error.error_len().is_some()),
+        };
+        let text = std::str::from_utf8(&self.undecoded[..valid]).unwrap_or_default();
+        self.buffer.push_str(text);
+        self.undecoded.drain(..valid);
+        if is_ill_formed {
+            return Err(self.ill_formed_input().into());
+        }
+        Ok(())
+    }
+
+    /// Report undecodable bytes following the buffer.
+    fn ill_formed_input(&self) -> SourceCodeError {
+        let message = "ill-formed UTF-8 byte sequence".to_owned();
+        let position = self.buffer.len();
+        let error = SourceCodeError::new(&self.buffer, position..position, ErrorCode::IllFormedInput, message);
+        self.reposition(error)
+    }
+
+    /// Make an error positioned relative to the buffer be relative to the
+    /// whole stream.
+    fn reposition(&self, mut error: SourceCodeError) -> SourceCodeError {
+        if error.line > 1 && self.after_cr && self.buffer.starts_with('\n') {
+            error.line -= 1;
+        }
+        if error.line == 1 {
+            error.column += self.column - 1;
+        }
+        error.line += self.line - 1;
+        error.range = error.range.start + self.offset..error.range.end + self.offset;
+        for label in &mut error.labels {
+            label.range = label.range.start + self.offset..label.range.end + self.offset;
+        }
+        error
+    }
+
+    /// Remove a returned token text from the buffer start.
+    fn consume(&mut self, end: usize) -> String {
+        let text: String = self.buffer.drain(..end).collect();
+        let (mut lines, column) = LineIndex::new(&text).line_column(text.len());
+        if self.after_cr && text.starts_with('\n') {
+            lines -= 1;
+        }
+        if lines == 1 {
+            self.column += column - 1;
+        } else {
+            self.line += lines - 1;
+            self.column = column;
+        }
+        if !text.is_empty() {
+            self.after_cr = text.ends_with('\r');
+        }
+        self.offset += end;
+        self.attempted = 0;
+        self.scanned = 0;
+        self.is_escaped = false;
+        self.is_in_class = false;
+        text
+    }
+}
+
+/// Check that a Unicode escape sequence at the start of a text is read up to
+/// its end.
+fn is_escape_read(text: &str) -> bool {
+    let Some(digits) = text.strip_prefix("\\u") else {
+        return text.len() > 1;
+    };
+    if let Some(digits) = digits.strip_prefix('{') {
+        return digits.contains(|character: char| !character.is_ascii_hexdigit());
+    }
+    digits.chars().nth(3).is_some()
+}
+
+impl<R: Read> Iterator for StreamingLexer<R> {
+    type Item = Result<(CompactToken, String), StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.next_token() {
+            Ok(token) => token.map(Ok),
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            },
+        }
+    }
+}
+
+impl<R: Read> std::iter::FusedIterator for StreamingLexer<R> {}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "annex-b")]
+    use claims::assert_matches;
+    #[cfg(feature = "annex-b")]
+    use embedded_ecmascript::codegen::{generate_script, Formatting};
+    #[cfg(feature = "annex-b")]
+    use embedded_ecmascript::grammar::{Declaration, Statement, StatementListItem};
+    use embedded_ecmascript::grammar::Parser;
+    use embedded_ecmascript::ErrorCode;
+    use rstest::rstest;
+
+    #[cfg(feature = "annex-b")]
+    fn parse(source: &str) -> Vec<StatementListItem> {
+        Parser::new(source).parse_script().unwrap().body
+    }
+
+    /// Names of functions marked for Annex B hoisting.
+    #[cfg(feature = "annex-b")]
+    fn legacy_block_functions(statements: &[StatementListItem]) -> Vec<String> {
+        let mut names = Vec::new();
+        for statement in statements {
+            match statement {
+                StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => {
+                    if function.is_legacy_block_function {
+                        names.extend(function.name.iter().map(|name| name.name.clone()));
+                    }
+                    names.extend(legacy_block_functions(&function.body));
+                },
+                StatementListItem::Statement(Statement::BlockStatement(block)) => {
+                    names.extend(legacy_block_functions(&block.statements));
+                },
+                StatementListItem::Statement(Statement::SwitchStatement(statement)) => {
+                    for case in &statement.cases {
+                        names.extend(legacy_block_functions(&case.statements));
+                    }
+                },
+                StatementListItem::Statement(Statement::IfStatement(statement)) => {
+                    let clauses = std::iter::once(&statement.consequent).chain(&statement.alternative);
+                    for clause in clauses {
+                        if let Statement::BlockStatement(block) = &**clause {
+                            names.extend(legacy_block_functions(&block.statements));
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+        names
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_block_functions() {
+        let body = parse("function a() { { function b() {} function* c() {} async function d() {} } } \
+            switch (e) { case 1: function f() {} default: function g() {} } \
+            try { function h() {} } catch { }");
+        assert_eq!(legacy_block_functions(&body), ["b", "f", "g"]);
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_if_clause_functions() {
+        let body = parse("if (a) function b() {} else function c() {} if (d) ; else function e() {}");
+        assert_matches!(&body[0], StatementListItem::Statement(Statement::IfStatement(statement))
+            if matches!(&*statement.consequent, Statement::BlockStatement(block) if block.range == (7..22)));
+        assert_eq!(legacy_block_functions(&body), ["b", "c", "e"]);
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_labelled_functions() {
+        let script = Parser::new("a: b: function c() {}").parse_script().unwrap();
+        assert_matches!(&script.body[0], StatementListItem::Statement(Statement::LabelledStatement(_)));
+        assert_eq!(generate_script(&script, Formatting::Compact), "a:b:function c(){}");
+    }
+
+    #[cfg(not(feature = "annex-b"))]
+    #[rstest]
+    #[case("a: function b() {}", ErrorCode::EarlyError, 3..18)]
+    #[case("if (a) function b() {}", ErrorCode::UnexpectedInput, 7..15)]
+    fn test_disabled(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[rstest]
+    #[case("while (a) b: function c() {}", 13..28)]
+    #[case("do a: b: function c() {} while (d)", 9..24)]
+    #[case("for (;;) a: function b() {}", 12..27)]
+    #[case("for (a of b) c: function d() {}", 16..31)]
+    #[case("if (a) b: function c() {}", 10..25)]
+    #[case("with (a) b: function c() {}", 12..27)]
+    fn test_labelled_function_bodies(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!(error.code, ErrorCode::EarlyError);
+        // Without Annex B, labelling a function is an error by itself.
+        if cfg!(feature = "annex-b") {
+            assert_eq!(error.range, range);
+        }
+    }
+}
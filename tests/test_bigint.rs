@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::runtime::{string_to_bigint, BigIntOps, Context, ErrorKind, JsError, Value};
+    use num_bigint::BigInt;
+    use rstest::rstest;
+    use std::cmp::Ordering;
+
+    fn big(value: i64) -> BigInt {
+        BigInt::from(value)
+    }
+
+    #[rstest]
+    #[case(7, 2, 3, 1)]
+    #[case(-7, 2, -3, -1)]
+    #[case(7, -2, -3, 1)]
+    #[case(-7, -2, 3, -1)]
+    fn test_division(#[case] x: i64, #[case] y: i64, #[case] quotient: i64, #[case] remainder: i64) {
+        assert_eq!(BigIntOps::divide(&big(x), &big(y)), Ok(big(quotient)));
+        assert_eq!(BigIntOps::remainder(&big(x), &big(y)), Ok(big(remainder)));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        assert_matches!(BigIntOps::divide(&big(1), &big(0)), Err(JsError { kind: ErrorKind::RangeError, .. }));
+        assert_matches!(BigIntOps::remainder(&big(0), &big(0)), Err(JsError { kind: ErrorKind::RangeError, .. }));
+    }
+
+    #[rstest]
+    #[case(0, 0, 1)]
+    #[case(0, 5, 0)]
+    #[case(-1, 3, -1)]
+    #[case(-1, 4, 1)]
+    #[case(-3, 3, -27)]
+    #[case(2, 62, 1 << 62)]
+    fn test_exponentiate(#[case] base: i64, #[case] exponent: i64, #[case] expected: i64) {
+        assert_eq!(BigIntOps::exponentiate(&big(base), &big(exponent)), Ok(big(expected)));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let huge = BigInt::from(u64::MAX);
+        assert_eq!(BigIntOps::exponentiate(&big(1), &huge), Ok(big(1)));
+        assert_eq!(BigIntOps::exponentiate(&big(-1), &huge), Ok(big(-1)));
+        assert_matches!(BigIntOps::exponentiate(&big(2), &huge), Err(JsError { kind: ErrorKind::RangeError, .. }));
+        assert_matches!(BigIntOps::exponentiate(&big(2), &big(-1)), Err(JsError { kind: ErrorKind::RangeError, .. }));
+        assert_matches!(BigIntOps::left_shift(&big(1), &huge), Err(JsError { kind: ErrorKind::RangeError, .. }));
+        assert_eq!(BigIntOps::left_shift(&big(0), &huge), Ok(big(0)));
+    }
+
+    #[rstest]
+    #[case(1, 4, 16)]
+    #[case(-5, 0, -5)]
+    #[case(-5, -1, -3)]
+    #[case(5, -1, 2)]
+    #[case(-5, -100, -1)]
+    #[case(5, -100, 0)]
+    fn test_shifts(#[case] x: i64, #[case] y: i64, #[case] expected: i64) {
+        assert_eq!(BigIntOps::left_shift(&big(x), &big(y)), Ok(big(expected)));
+        assert_eq!(BigIntOps::signed_right_shift(&big(x), &big(-y)), Ok(big(expected)));
+    }
+
+    #[test]
+    fn test_bitwise() {
+        assert_eq!(BigIntOps::bitwise_not(&big(0)), big(-1));
+        assert_eq!(BigIntOps::bitwise_not(&big(-6)), big(5));
+        // Negative numbers behave as infinitely long two's complement.
+        assert_eq!(BigIntOps::bitwise_and(&big(-1), &big(0xFF)), big(0xFF));
+        assert_eq!(BigIntOps::bitwise_or(&big(-256), &big(0x0F)), big(-241));
+        assert_eq!(BigIntOps::bitwise_xor(&big(-1), &big(5)), big(-6));
+        assert_matches!(BigIntOps::unsigned_right_shift(&big(1), &big(0)), Err(JsError { kind: ErrorKind::TypeError, .. }));
+    }
+
+    #[rstest]
+    #[case(0, 10, "0")]
+    #[case(-255, 16, "-ff")]
+    #[case(35, 36, "z")]
+    #[case(5, 2, "101")]
+    fn test_to_string(#[case] x: i64, #[case] radix: u32, #[case] expected: &str) {
+        assert_eq!(BigIntOps::to_string(&big(x), radix), expected);
+    }
+
+    #[rstest]
+    #[case(1, 1.0, Some(Ordering::Equal))]
+    #[case(1, 1.5, Some(Ordering::Less))]
+    #[case(1, 0.5, Some(Ordering::Greater))]
+    #[case(-1, -1.5, Some(Ordering::Greater))]
+    #[case(-1, -0.5, Some(Ordering::Less))]
+    #[case(0, -0.0, Some(Ordering::Equal))]
+    #[case(i64::MAX, 9_223_372_036_854_775_808.0, Some(Ordering::Less))]
+    #[case(0, f64::INFINITY, Some(Ordering::Less))]
+    #[case(0, f64::NEG_INFINITY, Some(Ordering::Greater))]
+    #[case(0, f64::NAN, None)]
+    fn test_compare_to_number(#[case] x: i64, #[case] y: f64, #[case] expected: Option<Ordering>) {
+        assert_eq!(BigIntOps::compare_to_number(&big(x), y), expected);
+    }
+
+    #[rstest]
+    #[case("", Some(0))]
+    #[case(" \n ", Some(0))]
+    #[case(" 12 ", Some(12))]
+    #[case("-12", Some(-12))]
+    #[case("+12", Some(12))]
+    #[case("0x1F", Some(31))]
+    #[case("0b11", Some(3))]
+    #[case("0o17", Some(15))]
+    #[case("-0x1", None)]
+    #[case("0x", None)]
+    #[case("1.0", None)]
+    #[case("1e3", None)]
+    #[case("1n", None)]
+    #[case("1_000", None)]
+    #[case("Infinity", None)]
+    #[case("-", None)]
+    fn test_string_to_bigint(#[case] text: &str, #[case] expected: Option<i64>) {
+        assert_eq!(string_to_bigint(&text.into()), expected.map(big));
+    }
+
+    #[test]
+    fn test_to_bigint() {
+        let mut context = Context::new();
+        assert_eq!(Value::from(true).to_bigint(&mut context), Ok(big(1)));
+        assert_eq!(Value::from("0x10").to_bigint(&mut context), Ok(big(16)));
+        assert_matches!(Value::from("1.5").to_bigint(&mut context), Err(JsError { kind: ErrorKind::SyntaxError, .. }));
+        assert_matches!(Value::Number(1.0).to_bigint(&mut context), Err(JsError { kind: ErrorKind::TypeError, .. }));
+        assert_matches!(Value::Undefined.to_bigint(&mut context), Err(JsError { kind: ErrorKind::TypeError, .. }));
+    }
+}
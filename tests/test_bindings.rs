@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{ErrorKind, FromArguments, IntoJs, JsError, Object, TryFromJs, Value};
+    use std::fmt::Debug;
+
+    /// Convert the completion value of a script.
+    fn convert<T: TryFromJs>(source: &str) -> Result<T, JsError> {
+        let mut engine = Engine::new();
+        let value = engine.evaluate_script(source).unwrap();
+        T::try_from_js(&value, engine.context())
+    }
+
+    fn assert_converts<T: TryFromJs + PartialEq + Debug>(source: &str, expected: T) {
+        assert_eq!(convert::<T>(source), Ok(expected), "{source}");
+    }
+
+    #[test]
+    fn test_from_js() {
+        assert_converts("'0'", true);
+        assert_converts("({ valueOf() { return 2.5; } })", 2.5);
+        assert_converts("-1", -1_i32);
+        assert_converts("-1", u32::MAX);
+        assert_converts("12", "12".to_owned());
+        assert_converts("null", None::<f64>);
+        assert_converts("undefined", None::<f64>);
+        assert_converts("'3'", Some(3.0));
+        assert_converts("[1, '2', true]", vec![1.0, 2.0, 1.0]);
+        assert_converts("({ length: 2, 0: 'a', 1: 'b' })", vec!["a".to_owned(), "b".to_owned()]);
+        assert_converts("[[1, 'a'], [2]]", vec![(1, Some("a".to_owned())), (2, None)]);
+        assert_converts("'x'", Value::from("x"));
+    }
+
+    #[test]
+    fn test_from_js_errors() {
+        assert_matches!(convert::<String>("'\\uD800'"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert::<Vec<f64>>("1"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert::<Object>("'a'"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert::<f64>("1n"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert::<f64>("({ valueOf() { throw 1; } })"), Err(error) if error.kind == ErrorKind::Thrown);
+    }
+
+    #[test]
+    fn test_into_js() {
+        let mut engine = Engine::new();
+        let array = vec![(1, "a"), (2, "b")].into_js(engine.context());
+        engine.global_object().create_data_property("array", array);
+        assert_eq!(engine.evaluate_script("array[1][1] + array.length"), Ok(Value::from("b2")));
+        assert_eq!(Some(true).into_js(engine.context()), Value::Boolean(true));
+        assert_eq!(None::<String>.into_js(engine.context()), Value::Undefined);
+        assert_eq!(().into_js(engine.context()), Value::Undefined);
+        assert_eq!("s".to_owned().into_js(engine.context()), Value::from("s"));
+    }
+
+    #[test]
+    fn test_from_arguments() {
+        let mut engine = Engine::new();
+        let arguments = [Value::from("7"), Value::Null];
+        let converted = <(f64, Option<bool>, Value)>::from_arguments(&arguments, engine.context());
+        assert_eq!(converted, Ok((7.0, None, Value::Undefined)));
+    }
+
+    #[test]
+    fn test_typed_functions() {
+        let mut engine = Engine::new();
+        engine.register_typed_function("add", |_, (a, b): (f64, f64)| Ok(a + b)).unwrap();
+        engine.register_typed_function("split", |_, (text,): (String,)| Ok(text.split(' ').map(str::to_owned).collect::<Vec<_>>())).unwrap();
+        engine.register_typed_function("sum", |_, (values,): (Vec<i32>,)| Ok(values.iter().sum::<i32>())).unwrap();
+        assert_eq!(engine.evaluate_script("add(1, '2')"), Ok(Value::Number(3.0)));
+        assert_eq!(engine.evaluate_script("split('a b c').length"), Ok(Value::Number(3.0)));
+        assert_eq!(engine.evaluate_script("sum([1, 2, 3])"), Ok(Value::Number(6.0)));
+        assert_matches!(engine.evaluate_script("sum(1)"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_eq!(engine.evaluate_script("try { sum(); } catch (e) { 'caught'; }"), Ok(Value::from("caught")));
+    }
+}
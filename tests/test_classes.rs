@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{ErrorKind, Value};
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::grammar::{
+        Class,
+        ClassElement,
+        ClassElementName,
+        Declaration,
+        Expression,
+        MethodKind,
+        Parser,
+        PropertyDefinition,
+        PropertyName,
+        StatementListItem,
+    };
+    use rstest::rstest;
+
+    fn parse_class(source: &str) -> Class {
+        let body = Parser::new(source).parse_script().unwrap().body;
+        let [StatementListItem::Declaration(Declaration::ClassDeclaration(class))] = &body[..] else {
+            panic!("a class declaration is expected");
+        };
+        class.clone()
+    }
+
+    fn parse_expression(source: &str) -> Expression {
+        let mut parser = Parser::new(source);
+        let expression = parser.parse_expression().unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        expression
+    }
+
+    /// Describe a class element as `static? kind name`.
+    fn describe(element: &ClassElement) -> String {
+        fn name(name: &ClassElementName) -> String {
+            match name {
+                ClassElementName::PropertyName(PropertyName::IdentifierName(name, _)) => name.clone(),
+                ClassElementName::PrivateIdentifier(name, _) => name.string_value(),
+                ClassElementName::PropertyName(other) => format!("{other:?}"),
+            }
+        }
+
+        let (is_static, description) = match element {
+            ClassElement::MethodDefinition { is_static, method, .. } => {
+                (*is_static, format!("{:?} {}", method.kind, name(&method.name)))
+            },
+            ClassElement::FieldDefinition { is_static, name: field, initializer, .. } => {
+                let initializer = if initializer.is_some() { " =" } else { "" };
+                (*is_static, format!("Field {}{initializer}", name(field)))
+            },
+            ClassElement::ClassStaticBlock(..) => (true, "Block".to_owned()),
+        };
+        if is_static {
+            format!("static {description}")
+        } else {
+            description
+        }
+    }
+
+    #[test]
+    fn test_class_declarations() {
+        let class = parse_class(
+            "class A extends B.C { ; constructor(a) {} m() {} get a() {} set a(v) {} static s() {} f; g = 1; \
+            static h = 2; #p = 3; #q() {} static { this.#p; } }"
+        );
+        assert!(class.is_declaration);
+        assert_matches!(&class.name, Some(name) if name.name == "A");
+        assert_matches!(class.heritage.as_deref(), Some(Expression::MemberExpression(_)));
+        let elements: Vec<_> = class.elements.iter().map(describe).collect();
+        assert_eq!(elements, [
+            "Method constructor",
+            "Method m",
+            "Getter a",
+            "Setter a",
+            "static Method s",
+            "Field f",
+            "Field g =",
+            "static Field h =",
+            "Field #p =",
+            "Method #q",
+            "static Block",
+        ]);
+    }
+
+    #[test]
+    fn test_contextual_keywords_as_names() {
+        let class = parse_class("class A { static() {} get() {} set; static = 1; get\na() {} static static() {} static get s() {} }");
+        let elements: Vec<_> = class.elements.iter().map(describe).collect();
+        assert_eq!(elements, [
+            "Method static",
+            "Method get",
+            "Field set",
+            "Field static =",
+            "Getter a",
+            "static Method static",
+            "static Getter s",
+        ]);
+    }
+
+    #[test]
+    fn test_class_expressions() {
+        assert_matches!(parse_expression("class {}"), Expression::ClassExpression(class)
+            if !class.is_declaration && class.name.is_none() && class.heritage.is_none());
+        assert_matches!(parse_expression("class extends A {}"), Expression::ClassExpression(class)
+            if class.name.is_none() && class.heritage.is_some());
+        assert_matches!(parse_expression("class A extends f() {}"), Expression::ClassExpression(class)
+            if class.name.is_some() && matches!(class.heritage.as_deref(), Some(Expression::CallExpression(_))));
+    }
+
+    #[test]
+    fn test_private_names() {
+        parse_class("class A { #a; m() { return this.#a + this.#b; } get #b() {} set #b(v) {} }");
+        parse_class("class A { m() { this.#a; } #a; }");
+        parse_class("class A { #a; m() { class B { n() { this.#a; } } } }");
+        parse_class("class A { static get #a() {} static set #a(v) {} }");
+        parse_class("class A { #a; static has(o) { return #a in o && #a in o.b in o; } }");
+        parse_class("class A { #a; m() { class B { n(o) { return #a in o; } } } }");
+    }
+
+    #[test]
+    fn test_object_literal_methods() {
+        let Expression::ObjectLiteral(object) = parse_expression("{ a() {}, get b() {}, set b(v) {}, get: 1, set() {}, get }")
+        else {
+            panic!("an object literal is expected");
+        };
+        let kinds: Vec<_> = object.properties.iter().map(|property| match property {
+            PropertyDefinition::MethodDefinition(method) => format!("{:?}", method.kind),
+            PropertyDefinition::Property { .. } => "Property".to_owned(),
+            PropertyDefinition::IdentifierReference(_) => "IdentifierReference".to_owned(),
+            PropertyDefinition::SpreadProperty(..) => "SpreadProperty".to_owned(),
+            PropertyDefinition::CoverInitializedName { .. } => "CoverInitializedName".to_owned(),
+        }).collect();
+        assert_eq!(kinds, ["Method", "Getter", "Setter", "Property", "Method", "IdentifierReference"]);
+        assert_matches!(&object.properties[2], PropertyDefinition::MethodDefinition(method)
+            if method.kind == MethodKind::Setter && method.parameters.parameters.len() == 1);
+    }
+
+    #[rstest]
+    #[case("class B extends A { constructor() { super(); } }")]
+    #[case("class B extends A { 'constructor'(a) { super(a).b = super.c; } }")]
+    #[case("class B extends A { constructor() { (() => super())(); } }")]
+    #[case("class B extends A { m() { return super.m(); } static s() { super['s'](); } a = super.a; static { super.b; } }")]
+    #[case("class A { constructor() { super.a; } get a() { return () => super.a; } }")]
+    #[case("({ m() { super.m(); }, get a() { return super.a; }, set a(v) { super.a = v; } });")]
+    fn test_super(#[case] source: &str) {
+        assert_matches!(Parser::new(source).parse_script(), Ok(_));
+    }
+
+    #[rstest]
+    #[case("class {}", ErrorCode::UnexpectedInput, 6..7)]
+    #[case("class A extends {}", ErrorCode::IncompleteInput, 18..18)]
+    #[case("class A { a = 1 b }", ErrorCode::UnexpectedInput, 16..17)]
+    #[case("class A { constructor() {} constructor() {} }", ErrorCode::EarlyError, 27..43)]
+    #[case("class A { 'constructor'() {} constructor() {} }", ErrorCode::EarlyError, 29..45)]
+    #[case("class A { get constructor() {} }", ErrorCode::EarlyError, 10..30)]
+    #[case("class A { constructor; }", ErrorCode::EarlyError, 10..22)]
+    #[case("class A { static prototype() {} }", ErrorCode::EarlyError, 10..31)]
+    #[case("class A { static prototype = 1; }", ErrorCode::EarlyError, 10..31)]
+    #[case("class A { #constructor() {} }", ErrorCode::EarlyError, 10..27)]
+    #[case("class A { #a; #a() {} }", ErrorCode::EarlyError, 14..21)]
+    #[case("class A { get #a() {} get #a() {} }", ErrorCode::EarlyError, 22..33)]
+    #[case("class A { static get #a() {} set #a(v) {} }", ErrorCode::EarlyError, 29..41)]
+    #[case("class A { m() { this.#b; } }", ErrorCode::EarlyError, 21..23)]
+    #[case("class A { #a; } this.#a;", ErrorCode::EarlyError, 21..23)]
+    #[case("class A { m(o) { return #b in o; } }", ErrorCode::EarlyError, 24..26)]
+    #[case("class A { #a; } #a in {};", ErrorCode::EarlyError, 16..18)]
+    #[case("class A { #a; m(o) { return 1 + #a in o; } }", ErrorCode::UnexpectedInput, 32..34)]
+    #[case("class A { #a; m(o) { return #a; } }", ErrorCode::UnexpectedInput, 30..31)]
+    #[case("class A { #a; m(o) { for (#a in o;;); } }", ErrorCode::UnexpectedInput, 26..28)]
+    #[case("class A { get a(b) {} }", ErrorCode::UnexpectedInput, 15..18)]
+    #[case("class A { set a() {} }", ErrorCode::UnexpectedInput, 15..17)]
+    #[case("class A { set a(...b) {} }", ErrorCode::UnexpectedInput, 15..21)]
+    #[case("({ #a() {} });", ErrorCode::UnexpectedInput, 3..5)]
+    #[case("({ get a(b) {} });", ErrorCode::UnexpectedInput, 8..11)]
+    #[case("class A { constructor() { super(); } }", ErrorCode::EarlyError, 26..33)]
+    #[case("class B extends A { m() { super(); } }", ErrorCode::EarlyError, 26..33)]
+    #[case("class B extends A { static constructor() { super(); } }", ErrorCode::EarlyError, 43..50)]
+    #[case("class B extends A { a = super(); }", ErrorCode::EarlyError, 24..31)]
+    #[case("class B extends A { constructor() { function f() { super(); } } }", ErrorCode::EarlyError, 51..58)]
+    #[case("({ m() { super(); } });", ErrorCode::EarlyError, 9..16)]
+    #[case("super.a;", ErrorCode::EarlyError, 0..7)]
+    #[case("function f() { super[0]; }", ErrorCode::EarlyError, 15..23)]
+    #[case("class B extends A { constructor() { function f() { super.a; } } }", ErrorCode::EarlyError, 51..58)]
+    #[case("({ a: function () { return () => super.a; } });", ErrorCode::EarlyError, 33..40)]
+    #[case("class B extends super.a {}", ErrorCode::EarlyError, 16..23)]
+    #[case("class A { #a; m() { delete this.#a; } }", ErrorCode::EarlyError, 20..34)]
+    #[case("class A { #a; m() { delete (this?.#a); } }", ErrorCode::EarlyError, 20..37)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[rstest]
+    #[case("class A { constructor(x) { this.x = x; } m() { return this.x; } } new A(3).m()", Value::Number(3.0))]
+    #[case(
+        "class A { constructor(x) { this.x = x; } m() { return 1; } }
+         class B extends A { constructor() { super(5); } m() { return super.m() + 1; } }
+         var b = new B(); b.x + b.m()",
+        Value::Number(7.0)
+    )]
+    #[case("class A { constructor(a, b) { this.s = a + b; } } class B extends A {} new B(1, 2).s", Value::Number(3.0))]
+    #[case("class A {} class B extends A {} var b = new B(); b instanceof B && b instanceof A", Value::Boolean(true))]
+    #[case("class A { static f() { return 'a'; } } class B extends A { static f() { return super.f() + 'b'; } } B.f()", Value::from("ab"))]
+    #[case("class A { a = 1; b = this.a + 1; static c = 3; } new A().b + A.c", Value::Number(5.0))]
+    #[case("class A { a = 1; } class B extends A { b = this.a + 1; } new B().b", Value::Number(2.0))]
+    #[case("var log = ''; class A { static { log += this.name; } } log", Value::from("A"))]
+    #[case("class A { f = function () {}; } new A().f.name", Value::from("f"))]
+    #[case("class A { get v() { return 2; } set v(x) { this.w = x; } } var a = new A(); a.v = 3; a.v + a.w", Value::Number(5.0))]
+    #[case("class A { m() {} } Reflect.getOwnPropertyDescriptor(A.prototype, 'm').enumerable", Value::Boolean(false))]
+    #[case("class A {} Reflect.getOwnPropertyDescriptor(A, 'prototype').writable", Value::Boolean(false))]
+    #[case("class A {} A.prototype.constructor === A", Value::Boolean(true))]
+    #[case("var C = class {}; C.name", Value::from("C"))]
+    #[case("var C = class D { m() { return D; } }; new C().m() === C", Value::Boolean(true))]
+    #[case("class A { ['x' + 1]() { return 4; } } new A().x1()", Value::Number(4.0))]
+    #[case("class B extends null {} Reflect.getPrototypeOf(B.prototype)", Value::Null)]
+    #[case("class A { constructor() { return { o: 1 }; } } new A().o", Value::Number(1.0))]
+    #[case("class A {} class B extends A { constructor() { return { o: 2 }; } } new B().o", Value::Number(2.0))]
+    #[case("class A { constructor() { this.t = new.target; } } class B extends A {} new B().t === B", Value::Boolean(true))]
+    #[case("class A { m() { return 'a'; } } class B extends A { m() { return (() => super.m())(); } } new B().m()", Value::from("a"))]
+    #[case("class A {} class B extends A { constructor() { var f = () => super(); f(); } } new B() instanceof A", Value::Boolean(true))]
+    #[case("class A { x = 1; } class B extends A { constructor() { super(); super.x = 2; } } new B().x", Value::Number(2.0))]
+    #[case("var o = { m() { return super.m() + 1; } }; Reflect.setPrototypeOf(o, { m() { return 1; } }); o.m()", Value::Number(2.0))]
+    #[case("var p = { get v() { return this.w; } }; var o = { w: 3, m() { return super.v; } }; Reflect.setPrototypeOf(o, p); o.m()", Value::Number(3.0))]
+    #[case("class A { #x = 1; get() { return this.#x; } set(v) { this.#x = v; } } var a = new A(); a.set(5); a.get()", Value::Number(5.0))]
+    #[case("class A { #x = 1; m() { this.#x += 4; this.#x++; return this.#x; } } new A().m()", Value::Number(6.0))]
+    #[case("class A { #x; m() { [this.#x] = [2]; ({ a: this.#x } = { a: this.#x + 1 }); return this.#x; } } new A().m()", Value::Number(3.0))]
+    #[case("class A { #m() { return 42; } n() { return this.#m(); } } new A().n()", Value::Number(42.0))]
+    #[case("class A { get #v() { return this.w * 2; } set #v(x) { this.w = x; } m() { this.#v = 4; return this.#v; } } new A().m()", Value::Number(8.0))]
+    #[case("class A { static #n = 3; static inc() { return ++A.#n; } } A.inc()", Value::Number(4.0))]
+    #[case("class A { static #s() {} static n() { return A.#s.name; } } A.n()", Value::from("#s"))]
+    #[case("class A { #x = 7; m() { return (() => this.#x)(); } } new A().m()", Value::Number(7.0))]
+    #[case("class A { #x = 1; m(o) { return o?.#x; } } new A().m(null)", Value::Undefined)]
+    #[case("class A { #x = 1; a = this.#x + 1; } new A().a", Value::Number(2.0))]
+    #[case("class A { #x = 1; } Reflect.ownKeys(new A()).length", Value::Number(0.0))]
+    #[case("class A { #p; static has(o) { return #p in o; } } A.has(new A()) && !A.has({})", Value::Boolean(true))]
+    #[case("class A { #m() {} static has(o) { return #m in o; } } A.has(new A())", Value::Boolean(true))]
+    #[case("class A { static #s = 1; static has(o) { return #s in o; } } A.has(A) && !A.has(new A())", Value::Boolean(true))]
+    #[case("class A { #p; static *g(o) { return #p in (yield 1); } } var g = A.g(); g.next(); g.next(new A()).value", Value::Boolean(true))]
+    #[case(
+        "class B { constructor(o) { return o; } } class S extends B { #id = 9; static id(o) { return o.#id; } }
+         var o = {}; new S(o); S.id(o)",
+        Value::Number(9.0)
+    )]
+    #[case("class A { #x; *g() { this.#x = yield 1; return this.#x; } } var g = new A().g(); g.next(); g.next(5).value", Value::Number(5.0))]
+    fn test_evaluation(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[rstest]
+    #[case("class A {} A()", ErrorKind::TypeError)]
+    #[case("class B extends 1 {}", ErrorKind::TypeError)]
+    #[case("function F() {} F.prototype = 1; class B extends F {}", ErrorKind::TypeError)]
+    #[case("class A {} class B extends A { constructor() { this.a = 1; super(); } } new B()", ErrorKind::ReferenceError)]
+    #[case("class A {} class B extends A { constructor() {} } new B()", ErrorKind::ReferenceError)]
+    #[case("class A {} class B extends A { constructor() { super(); super(); } } new B()", ErrorKind::ReferenceError)]
+    #[case("class A {} class B extends A { constructor() { return 1; } } new B()", ErrorKind::TypeError)]
+    #[case("class B extends null {} new B()", ErrorKind::TypeError)]
+    #[case("class A { m() { delete super.m; } } new A().m()", ErrorKind::ReferenceError)]
+    #[case("class A { static ['prototype']() {} }", ErrorKind::TypeError)]
+    #[case("new A(); class A {}", ErrorKind::ReferenceError)]
+    #[case("class A { #x = 1; static read(o) { return o.#x; } } A.read({})", ErrorKind::TypeError)]
+    #[case("class A { #x = 1; static read(o) { return o.#x; } } A.read(1)", ErrorKind::TypeError)]
+    #[case("class A { #x; static has(o) { return #x in o; } } A.has(1)", ErrorKind::TypeError)]
+    #[case("class A { #m() {} m() { this.#m = 1; } } new A().m()", ErrorKind::TypeError)]
+    #[case("class A { get #g() {} m() { this.#g = 1; } } new A().m()", ErrorKind::TypeError)]
+    #[case("class A { set #s(v) {} m() { return this.#s; } } new A().m()", ErrorKind::TypeError)]
+    #[case("class A { #x; static m() { class B { #x; static r(o) { return o.#x; } } return B.r(new A()); } } A.m()", ErrorKind::TypeError)]
+    #[case("class B { constructor(o) { return o; } } class S extends B { #id; } var o = {}; new S(o); new S(o)", ErrorKind::TypeError)]
+    fn test_evaluation_errors(#[case] source: &str, #[case] kind: ErrorKind) {
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == kind, "{source}");
+    }
+}
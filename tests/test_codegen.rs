@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::codegen::{generate_expression, generate_script, Formatting};
+    use embedded_ecmascript::grammar::{
+        BinaryExpression,
+        BinaryOperator,
+        Expression,
+        Parser,
+        Script,
+        Statement,
+        StatementListItem,
+    };
+    use rstest::rstest;
+
+    fn parse_expression(source: &str) -> Expression {
+        let mut parser = Parser::new(source);
+        let expression = parser.parse_expression().unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        expression
+    }
+
+    fn compact(source: &str) -> String {
+        generate_script(&Parser::new(source).parse_script().unwrap(), Formatting::Compact)
+    }
+
+    fn readable(source: &str) -> String {
+        generate_script(&Parser::new(source).parse_script().unwrap(), Formatting::default())
+    }
+
+    #[rstest]
+    #[case("a = b + c * d;", "a=b+c*d;")]
+    #[case("typeof a in b;", "typeof a in b;")]
+    #[case("a + +b; a - -b; a++ + b; - --a;", "a+ +b;a- -b;a++ +b;- --a;")]
+    #[case("a / /b/g;", "a/ /b/g;")]
+    #[case("var a = 1, [b, , ...c] = d, { e, f: g = 2, ...h } = i;", "var a=1,[b,,...c]=d,{e,f:g=2,...h}=i;")]
+    #[case("[, a, ...b, , ];", "[,a,...b,,];")]
+    #[case("1..a; 1.5.a; (1).a;", "1..a;1.5.a;(1).a;")]
+    #[case("if (a) b; else if (c) d; else { e; }", "if(a)b;else if(c)d;else{e;}")]
+    #[case("for (var i = 0; i < n; i++) {}", "for(var i=0;i<n;i++){}")]
+    #[case("for (const [a] of b) ; for (a in b) ;", "for(const[a]of b);for(a in b);")]
+    #[case("do a(); while (b)", "do a();while(b);")]
+    #[case("a: for (;;) { continue a; break; }", "a:for(;;){continue a;break;}")]
+    #[case("switch (a) { case 1: b; default: }", "switch(a){case 1:b;default:}")]
+    #[case("try { a; } catch { } finally { b; }", "try{a;}catch{}finally{b;}")]
+    #[case("try {} catch ({ a }) {}", "try{}catch({a}){}")]
+    #[case("function* f(a, b = 1, ...c) { yield* a; }", "function*f(a,b=1,...c){yield*a;}")]
+    #[case("async function f() { await a; }", "async function f(){await a;}")]
+    #[case("(function () {});", "(function(){});")]
+    #[case("({ a } = b);", "({a}=b);")]
+    #[case("a = { b, c: 1, [d]: 2, ...e, f() {}, get g() {}, set g(v) {}, async *h() {} };",
+        "a={b,c:1,[d]:2,...e,f(){},get g(){},set g(v){},async*h(){}};")]
+    #[case("a = async (b) => ({ c: b, d });", "a=async(b)=>({c:b,d});")]
+    #[case("class A extends B { static a = 1; #b; get c() {} static { d; } }",
+        "class A extends B{static a=1;#b;get c(){}static{d;}}")]
+    #[case("class A extends B { constructor() { super(a); super.b[c] = super[d]; } }",
+        "class A extends B{constructor(){super(a);super.b[c]=super[d];}}")]
+    #[case("class A { #a; m() { #a in b && c; } }", "class A{#a;m(){#a in b&&c;}}")]
+    #[case("a?.b?.[c]?.(d).e;", "a?.b?.[c]?.(d).e;")]
+    #[case("new a.b(c); new (a())(); new a;", "new a.b(c);new(a())();new a;")]
+    #[case("`a${b}c${`d`}`;", "`a${b}c${`d`}`;")]
+    #[case("a`b\\u{`;", "a`b\\u{`;")]
+    #[case("x = 'a\\n' + \"b\";", "x='a\\n'+\"b\";")]
+    #[case("debugger; ;", "debugger;;")]
+    fn test_compact(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(compact(source), expected);
+    }
+
+    #[test]
+    fn test_readable() {
+        let source = "function f(a) { if (a) { return a + 1; } else return; } \
+            switch (b) { case 1: c(); break; default: d = { e: 1 }; } \
+            class A { m() { for (;;) {} } } do {} while (a)";
+        assert_eq!(readable(source), "\
+function f(a) {
+    if (a) {
+        return a + 1;
+    } else return;
+}
+switch (b) {
+    case 1:
+        c();
+        break;
+    default:
+        d = { e: 1 };
+}
+class A {
+    m() {
+        for (;;) {}
+    }
+}
+do {} while (a);
+");
+        assert_eq!(generate_script(&Parser::new("if (a) b;\nelse c;").parse_script().unwrap(), Formatting::Readable {
+            indent: 2,
+        }), "if (a) b;\nelse c;\n");
+        assert_eq!(readable(""), "");
+    }
+
+    /// Debug output of a tree with source ranges masked.
+    fn shape(script: &Script) -> String {
+        let debug = format!("{script:?}");
+        let mut shape = String::with_capacity(debug.len());
+        let mut rest = debug.as_str();
+        while let Some(start) = rest.find(|codepoint: char| codepoint.is_ascii_digit()) {
+            shape.push_str(&rest[..start]);
+            let digits = |text: &str| text.find(|codepoint: char| !codepoint.is_ascii_digit()).unwrap_or(text.len());
+            let end = start + digits(&rest[start..]);
+            match rest[end..].strip_prefix("..") {
+                Some(tail) if tail.starts_with(|codepoint: char| codepoint.is_ascii_digit()) => {
+                    shape.push('_');
+                    rest = &tail[digits(tail)..];
+                },
+                _ => {
+                    shape.push_str(&rest[start..end]);
+                    rest = &rest[end..];
+                },
+            }
+        }
+        shape.push_str(rest);
+        shape
+    }
+
+    /// Printed source parses back into the same tree.
+    #[rstest]
+    #[case("a, (b, c); (a, b) => c; a ? b : c ? d : e; (a ? b : c) ? d : e;")]
+    #[case("a ?? (b || c); (a && b) ?? c; (-a) ** b; a ** -b; (a ** b) ** c;")]
+    #[case("(a = b) + c; a = b = c; a += (b, c); (a => b)(c); (async () => {})();")]
+    #[case("(new a)(); (new a).b; new (a.b().c)(); new new a()(); (a?.b).c; (a?.b)`c`;")]
+    #[case("let a; (let[a] = b); (let); for ((let)[a] of b); for ((async) of b); for (let in a);")]
+    #[case("(class {}).a; (function* () {})(); ({}).a = 1; (async function () {});")]
+    #[case("for ((a in b);;); for (var a = (b in c);;); for (a = [b in c];;);")]
+    #[case("if (a) { if (b) c; } else d; if (a) while (b) if (c) d; else e;")]
+    #[case("a: b: if (c) { break a; } x = y\n(z)")]
+    #[case("class A { static async *[a]() {} get; set = 1; static static() {} 'b'() {} 1() {} }")]
+    #[case("a = { 'b': c, 1: d, e = f } = g; [a, b] = [, , c, , ];")]
+    #[case("async function f() { for await (const a of b) { await (a, b); } }")]
+    #[case("function* g() { yield; yield a, b; (yield a) + b; yield yield; }")]
+    #[case("a = function () { return new.target; }; import(a); import.meta.b;")]
+    #[case("class A { #a; m() { #a in b + c; (#a in b) < c; for (var d = (#a in b);;); } }")]
+    fn test_round_trip(
+        #[case] source: &str,
+        #[values(Formatting::Compact, Formatting::default())] formatting: Formatting
+    ) {
+        let new_parser = if source.contains("import.meta") { Parser::new_module } else { Parser::new };
+        let script = new_parser(source).parse_script().unwrap();
+        let printed = generate_script(&script, formatting);
+        let reparsed = new_parser(&printed).parse_script().unwrap_or_else(|error| panic!("{printed}: {error:?}"));
+        assert_eq!(shape(&reparsed), shape(&script), "{printed}");
+    }
+
+    #[test]
+    fn test_transformed_tree() {
+        // Swap operands of `a * (b + c)` losing the parentheses.
+        let Expression::BinaryExpression(product) = parse_expression("a * (b + c)") else {
+            panic!("a binary expression is expected");
+        };
+        let Expression::ParenthesizedExpression(sum) = *product.right else {
+            panic!("a parenthesized expression is expected");
+        };
+        let swapped = Expression::BinaryExpression(BinaryExpression {
+            operator: BinaryOperator::Multiplication,
+            left: sum.expression,
+            right: product.left,
+            range: 0..0,
+        });
+        assert_eq!(generate_expression(&swapped, Formatting::default()), "(b + c) * a");
+        assert_eq!(generate_expression(&swapped, Formatting::Compact), "(b+c)*a");
+
+        // An object literal moved to the start of a statement.
+        let statement = Statement::ExpressionStatement(parse_expression("{ a: 1, b }.a"), 0..0);
+        let script = Script {
+            body: vec![StatementListItem::Statement(statement)],
+            is_strict: false,
+            range: 0..0,
+        };
+        assert_eq!(generate_script(&script, Formatting::Compact), "({a:1,b}.a);");
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::runtime::{
+        string_to_number,
+        Context,
+        ErrorKind,
+        JsError,
+        Object,
+        PreferredType,
+        PropertyDescriptor,
+        PropertyKey,
+        Symbol,
+        Value,
+        WellKnownSymbol,
+    };
+    use num_bigint::BigInt;
+    use rstest::rstest;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case("", 0.0)]
+    #[case("  \n  ", 0.0)]
+    #[case("\u{feff}\u{2028} 12 \t", 12.0)]
+    #[case("0x10", 16.0)]
+    #[case("0X1f", 31.0)]
+    #[case("0o17", 15.0)]
+    #[case("0b101", 5.0)]
+    #[case("+1.5e3", 1500.0)]
+    #[case(".5", 0.5)]
+    #[case("5.", 5.0)]
+    #[case("-Infinity", f64::NEG_INFINITY)]
+    #[case("1e1000", f64::INFINITY)]
+    #[case("0x1fffffffffffff1", 144_115_188_075_855_860.0)]
+    fn test_string_to_number(#[case] text: &str, #[case] expected: f64) {
+        assert_eq!(string_to_number(&text.into()).to_bits(), expected.to_bits());
+    }
+
+    #[rstest]
+    #[case("-0x10")]
+    #[case("0x")]
+    #[case("0b2")]
+    #[case("1_0")]
+    #[case("1e")]
+    #[case(".")]
+    #[case("inf")]
+    #[case("infinity")]
+    #[case("1n")]
+    #[case("1 2")]
+    fn test_string_to_nan(#[case] text: &str) {
+        assert!(string_to_number(&text.into()).is_nan());
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        let mut context = Context::new();
+        assert!(string_to_number(&"-0".into()).is_sign_negative());
+        assert!(string_to_number(&" -0.0e5 ".into()).is_sign_negative());
+        assert_eq!(Value::Number(-0.0).to_string(&mut context), Ok("0".into()));
+        assert!(!Value::Number(-0.0).to_boolean());
+    }
+
+    #[rstest]
+    #[case(Value::Undefined, false)]
+    #[case(Value::Null, false)]
+    #[case(Value::Number(f64::NAN), false)]
+    #[case(Value::from(""), false)]
+    #[case(Value::from(" "), true)]
+    #[case(Value::BigInt(BigInt::ZERO), false)]
+    #[case(Value::BigInt(BigInt::from(-1)), true)]
+    #[case(Value::Symbol(Symbol::new(None)), true)]
+    #[case(Value::Object(Object::new(None)), true)]
+    fn test_to_boolean(#[case] value: Value, #[case] expected: bool) {
+        assert_eq!(value.to_boolean(), expected);
+    }
+
+    #[rstest]
+    #[case(Value::Undefined, "undefined")]
+    #[case(Value::Null, "null")]
+    #[case(Value::from(false), "false")]
+    #[case(Value::Number(f64::NEG_INFINITY), "-Infinity")]
+    #[case(Value::Number(f64::NAN), "NaN")]
+    #[case(Value::Number(1.5), "1.5")]
+    #[case(Value::BigInt(BigInt::from(-12)), "-12")]
+    fn test_to_string(#[case] value: Value, #[case] expected: &str) {
+        assert_eq!(value.to_string(&mut Context::new()), Ok(expected.into()));
+    }
+
+    #[test]
+    fn test_unconvertible_primitives() {
+        let mut context = Context::new();
+        let unique = Symbol::new(None);
+        let symbol = Value::Symbol(unique.clone());
+        let bigint = Value::BigInt(BigInt::from(1));
+        assert_matches!(symbol.to_number(&mut context), Err(JsError { kind: ErrorKind::TypeError, .. }));
+        assert_matches!(symbol.to_string(&mut context), Err(JsError { kind: ErrorKind::TypeError, .. }));
+        assert_matches!(bigint.to_number(&mut context), Err(JsError { kind: ErrorKind::TypeError, .. }));
+        assert_eq!(bigint.to_numeric(&mut context), Ok(bigint.clone()));
+        assert_eq!(symbol.to_property_key(&mut context), Ok(PropertyKey::Symbol(unique)));
+    }
+
+    /// An object with a native method that records hints it gets.
+    fn object_with_to_primitive(result: Value) -> (Object, Rc<RefCell<Vec<Value>>>) {
+        let hints = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&hints);
+        let method = Object::native_function(None, move |_, _, arguments| {
+            recorded.borrow_mut().push(arguments[0].clone());
+            Ok(result.clone())
+        });
+        let object = Object::new(None);
+        assert!(object.create_data_property(Symbol::well_known(WellKnownSymbol::ToPrimitive), method));
+        (object, hints)
+    }
+
+    #[test]
+    fn test_to_primitive_dispatch() {
+        let mut context = Context::new();
+        let (object, hints) = object_with_to_primitive(Value::from("42"));
+        let value = Value::Object(object);
+        assert_eq!(value.to_primitive(&mut context, None), Ok(Value::from("42")));
+        assert_eq!(value.to_number(&mut context), Ok(42.0));
+        assert_eq!(value.to_string(&mut context), Ok("42".into()));
+        assert_eq!(value.to_primitive(&mut context, Some(PreferredType::String)), Ok(Value::from("42")));
+        assert_eq!(*hints.borrow(), [Value::from("default"), Value::from("number"), Value::from("string"), Value::from("string")]);
+
+        let (object, _) = object_with_to_primitive(Value::Object(Object::new(None)));
+        let error = Value::Object(object).to_primitive(&mut context, None);
+        assert_matches!(error, Err(JsError { kind: ErrorKind::TypeError, .. }));
+    }
+
+    #[test]
+    fn test_ordinary_to_primitive() {
+        let mut context = Context::new();
+        let object = Object::new(None);
+        let value = Value::Object(object.clone());
+        assert_matches!(value.to_primitive(&mut context, None), Err(JsError { kind: ErrorKind::TypeError, .. }));
+
+        let value_of = Object::native_function(None, |_, _, _| Ok(Value::Number(1.0)));
+        let to_string = Object::native_function(None, |_, this, _| {
+            assert!(this.is_object());
+            Ok(Value::from("text"))
+        });
+        assert!(object.create_data_property("valueOf", value_of));
+        assert!(object.create_data_property("toString", to_string));
+        assert_eq!(value.to_primitive(&mut context, None), Ok(Value::Number(1.0)));
+        assert_eq!(value.to_primitive(&mut context, Some(PreferredType::Number)), Ok(Value::Number(1.0)));
+        assert_eq!(value.to_string(&mut context), Ok("text".into()));
+        assert_eq!(value.to_property_key(&mut context), Ok(PropertyKey::from("text")));
+
+        // A method returning an object is skipped for the next one.
+        let inherited = Object::new(Some(object));
+        let to_string = Object::native_function(None, |_, this, _| Ok(this.clone()));
+        assert!(inherited.create_data_property("toString", to_string));
+        assert_eq!(Value::Object(inherited).to_string(&mut context), Ok("1".into()));
+    }
+
+    #[test]
+    fn test_accessor_methods() {
+        let mut context = Context::new();
+        let method = Object::native_function(None, |_, _, _| Ok(Value::from(true)));
+        let getter = Object::native_function(None, move |_, _, _| Ok(Value::Object(method.clone())));
+        let object = Object::new(None);
+        let descriptor = PropertyDescriptor::accessor(Value::Object(getter), Value::Undefined, false, true);
+        assert!(object.define_own_property(PropertyKey::from("valueOf"), descriptor));
+        assert_eq!(Value::Object(object).to_number(&mut context), Ok(1.0));
+    }
+
+    #[test]
+    fn test_throwing_conversion() {
+        let mut context = Context::new();
+        let object = Object::new(None);
+        let value_of = Object::native_function(None, |_, _, _| Err(JsError::new(ErrorKind::RangeError, "thrown")));
+        assert!(object.create_data_property("valueOf", value_of));
+        let error = Value::Object(object).to_number(&mut context);
+        assert_eq!(error, Err(JsError::new(ErrorKind::RangeError, "thrown").through("<anonymous>".to_owned())));
+    }
+
+    #[test]
+    fn test_call_depth() {
+        let object = Object::new(None);
+        let recursive = Object::native_function(None, |context, this, _| this.to_number(context).map(Value::Number));
+        assert!(object.create_data_property("valueOf", recursive));
+        let error = Value::Object(object).to_number(&mut Context::new());
+        assert_matches!(error, Err(JsError { kind: ErrorKind::RangeError, .. }));
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::early_errors::check_script;
+    use embedded_ecmascript::grammar::{Declaration, Parser, StatementListItem};
+    use embedded_ecmascript::{Diagnostics, ErrorCode};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("let a; let a;", 11..12)]
+    #[case("let a; var a;", 11..12)]
+    #[case("var a; const a = 1;", 13..14)]
+    #[case("class a {} function a() {}", 20..21)]
+    #[case("{ let a; { var a; } }", 15..16)]
+    #[case("{ function a() {} let a; }", 22..23)]
+    #[case("{ function* a() {} function a() {} }", 28..29)]
+    #[case("'use strict'; { function a() {} function a() {} }", 41..42)]
+    #[case("switch (a) { case 1: let b; default: let b; }", 41..42)]
+    #[case("let let = 1;", 4..7)]
+    #[case("for (let let of a);", 9..12)]
+    #[case("for (let [a, a] of b);", 13..14)]
+    #[case("for (let a; ; ) { var a; }", 22..23)]
+    #[case("for (const a of b) { var a; }", 25..26)]
+    #[case("try {} catch (a) { let a; }", 23..24)]
+    #[case("try {} catch ([a, a]) {}", 18..19)]
+    #[case("try {} catch ([a]) { var a; }", 25..26)]
+    #[case("try {} catch (a) { for (var a of b); }", 28..29)]
+    #[case("function f(a) { let a; }", 20..21)]
+    #[case("(a) => { const a = 1; };", 15..16)]
+    #[case("1 = 2;", 0..1)]
+    #[case("(a, b) = c;", 1..5)]
+    #[case("f() = 1;", 0..3)]
+    #[case("f() += 1;", 0..3)]
+    #[case("++f();", 2..5)]
+    #[case("a?.b = 1;", 0..4)]
+    #[case("({ a: 1 } = b);", 6..7)]
+    #[case("[...a, b] = c;", 1..5)]
+    #[case("({ ...{ a } } = b);", 6..11)]
+    #[case("({a}) = 1;", 1..4)]
+    #[case("for (f() of a);", 5..8)]
+    #[case("'use strict'; eval = 1;", 14..18)]
+    #[case("'use strict'; [arguments] = a;", 15..24)]
+    #[case("class A { m() { arguments++; } }", 16..25)]
+    fn test_errors(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, range), "{}", error.message);
+    }
+
+    #[rstest]
+    #[case("a: a: ;", 3..7)]
+    #[case("a: { a: while (b) ; }", 5..19)]
+    #[case("break;", 0..6)]
+    #[case("continue;", 0..9)]
+    #[case("a: { continue a; }", 5..16)]
+    #[case("while (a) { break b; }", 12..20)]
+    #[case("a: while (b) { c: { continue c; } }", 20..31)]
+    #[case("switch (a) { case 1: continue; }", 21..30)]
+    #[case("while (a) { function f() { break; } }", 27..33)]
+    #[case("a: while (b) { (() => { continue a; }); }", 24..35)]
+    #[case("while (a) { class A { static { break; } } }", 31..37)]
+    fn test_labels(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, range), "{}", error.message);
+    }
+
+    #[rstest]
+    #[case("var a; var a; function a() {}")]
+    #[case("function a(b) { var b; function b() {} }")]
+    #[case("{ let a; } { let a; }")]
+    #[case("let a; { let a; }")]
+    #[case("for (let a of b) { let a; }")]
+    #[case("[a, b.c, ...d] = e; ({ a, b: c.d = 1, ...e } = f); (a) = 1; a.b++;")]
+    #[case("let [a, { b }] = c;")]
+    #[case("function f() { 'use strict'; } eval = 1;")]
+    #[case("a: b: while (c) { continue a; } a: { break a; }")]
+    #[case("a: for (;;) { switch (b) { case 1: continue a; default: break; } }")]
+    #[case("a: while (b) { (function () { a: do continue a; while (c); }); }")]
+    fn test_correct(#[case] source: &str) {
+        Parser::new(source).parse_script().unwrap();
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[rstest]
+    #[case("{ function a() {} function a() {} }")]
+    #[case("switch (a) { case 1: function b() {} default: function b() {} }")]
+    #[case("try {} catch (a) { var a; }")]
+    #[case("a: function b() {} var b;")]
+    fn test_annex_b(#[case] source: &str) {
+        Parser::new(source).parse_script().unwrap();
+    }
+
+    #[test]
+    fn test_modules() {
+        let error = Parser::new_module("function a() {} var a;").parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, 20..21));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let source = "let a; let a;\nlet let = 1;";
+        let mut diagnostics = Diagnostics::new();
+        let script = Parser::new(source).parse_script_with_diagnostics(&mut diagnostics);
+        let ranges: Vec<_> = diagnostics.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [11..12, 18..21]);
+        let first = diagnostics.iter().next().unwrap();
+        assert_eq!(first.labels[0].range, 4..5);
+
+        // The tree is kept, so the check can run on it again.
+        let mut again = Diagnostics::new();
+        check_script(source, &script, false, &mut again);
+        assert_eq!(again, diagnostics);
+    }
+
+    #[test]
+    fn test_deferred_bodies() {
+        let source = "function a() { let b; var b; }";
+        let script = Parser::new(source).with_deferred_function_bodies().parse_script().unwrap();
+        let StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) = &script.body[0] else {
+            panic!("a function declaration is expected");
+        };
+        let deferred = function.deferred_body.as_ref().unwrap();
+        let error = Parser::new(source).parse_deferred_body(deferred).unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, 26..27));
+    }
+}
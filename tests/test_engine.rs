@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{
+        Context,
+        ErrorKind,
+        ExecutionContext,
+        JsError,
+        Object,
+        PropertyDescriptor,
+        PropertyKey,
+        Realm,
+        Value,
+    };
+    use rstest::rstest;
+    use std::cell::{Cell, RefCell};
+    use std::ops::ControlFlow;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_default_global_bindings() {
+        let mut engine = Engine::new();
+        let global = engine.global_object().clone();
+        let this = global.get(engine.context(), &PropertyKey::from("globalThis"), &Value::Undefined);
+        assert_eq!(this, Ok(Value::Object(global.clone())));
+        assert_eq!(
+            global.get_own_property(&PropertyKey::from("undefined")),
+            Some(PropertyDescriptor::data(Value::Undefined, false, false, false)),
+        );
+        assert!(!global.create_data_property("Infinity", 0.0));
+        assert!(global.delete(&PropertyKey::from("globalThis")));
+    }
+
+    #[test]
+    fn test_intrinsics() {
+        let engine = Engine::new();
+        let intrinsics = engine.intrinsics();
+        assert_eq!(intrinsics.object_prototype.get_prototype_of(), None);
+        assert_eq!(intrinsics.function_prototype.get_prototype_of(), Some(intrinsics.object_prototype.clone()));
+        assert!(intrinsics.function_prototype.is_callable());
+        assert_eq!(engine.global_object().get_prototype_of(), Some(intrinsics.object_prototype.clone()));
+    }
+
+    #[test]
+    fn test_separate_realms() {
+        let first = Engine::new();
+        let second = Engine::new();
+        assert_ne!(first.realm(), second.realm());
+        assert_ne!(first.global_object(), second.global_object());
+        assert_ne!(first.intrinsics().object_prototype, second.intrinsics().object_prototype);
+        assert_eq!(first.realm(), &first.realm().clone());
+    }
+
+    #[test]
+    fn test_execution_context_stack() {
+        let mut engine = Engine::new();
+        let realm = engine.realm().clone();
+        let running = engine.context().running_execution_context().cloned().unwrap();
+        assert_eq!(running.function, None);
+        assert_eq!(running.realm, realm);
+
+        // A called function runs in its own execution context.
+        let observed = Rc::new(RefCell::new(None));
+        let recorded = Rc::clone(&observed);
+        let function = Object::native_function(None, move |context, _, _| {
+            *recorded.borrow_mut() = context.active_function_object().cloned();
+            Ok(Value::Undefined)
+        });
+        assert_eq!(function.call(engine.context(), &Value::Undefined, &[]), Ok(Value::Undefined));
+        assert_eq!(*observed.borrow(), Some(function));
+        assert_eq!(engine.context().running_execution_context(), Some(&running));
+    }
+
+    #[test]
+    fn test_context_without_realm() {
+        let mut context = Context::new();
+        assert_eq!(context.current_realm(), None);
+        let realm = Realm::new();
+        context.push_execution_context(ExecutionContext::new(None, realm.clone()));
+        assert_eq!(context.current_realm(), Some(&realm));
+        assert!(context.pop_execution_context().is_some());
+        assert_eq!(context.pop_execution_context(), None);
+    }
+
+    #[test]
+    fn test_registered_functions() {
+        let mut engine = Engine::new();
+        engine
+            .register_function("sum", |context, arguments| {
+                let mut sum = 0.0;
+                for argument in arguments {
+                    sum += argument.to_number(context)?;
+                }
+                Ok(Value::Number(sum))
+            })
+            .unwrap();
+        engine.register_function("fail", |_, _| Err(JsError::type_error("host failure"))).unwrap();
+        assert_eq!(engine.evaluate_script("sum(1, 2, '3')"), Ok(Value::Number(6.0)));
+        assert_eq!(engine.evaluate_script("typeof sum + sum.name + sum.length"), Ok(Value::from("functionsum0")));
+        assert_eq!(engine.evaluate_script("try { fail(); } catch (e) { 'caught'; }"), Ok(Value::from("caught")));
+        assert_matches!(engine.evaluate_script("fail()"), Err(error) if error.message == "host failure");
+        assert_matches!(engine.evaluate_script("new sum()"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(engine.register_function("undefined", |_, _| Ok(Value::Undefined)), Err(error) if error.kind == ErrorKind::TypeError);
+    }
+
+    #[test]
+    fn test_host_methods() {
+        let mut engine = Engine::new();
+        let speed = Rc::new(Cell::new(0.0));
+        let vehicle = Object::new(Some(engine.intrinsics().object_prototype.clone()));
+        let current = Rc::clone(&speed);
+        engine.define_method(&vehicle, "getSpeed", move |_, _, _| Ok(Value::Number(current.get()))).unwrap();
+        let target = Rc::clone(&speed);
+        engine
+            .define_method(&vehicle, "accelerate", move |context, this, arguments| {
+                target.set(target.get() + arguments.first().unwrap_or(&Value::Undefined).to_number(context)?);
+                Ok(this.clone())
+            })
+            .unwrap();
+        engine.global_object().create_data_property("vehicle", vehicle.clone());
+        assert_eq!(engine.evaluate_script("vehicle.accelerate(30).accelerate(20).getSpeed()"), Ok(Value::Number(50.0)));
+        assert!((speed.get() - 50.0).abs() < f64::EPSILON);
+        let descriptor = vehicle.get_own_property(&PropertyKey::from("getSpeed")).unwrap();
+        assert_eq!((descriptor.writable, descriptor.enumerable, descriptor.configurable), (Some(true), Some(false), Some(true)));
+        let function = engine.create_function("f", 2, |_, _, arguments| Ok(arguments.first().cloned().unwrap_or(Value::Undefined)));
+        assert_eq!(function.call(engine.context(), &Value::Undefined, &[Value::Null]), Ok(Value::Null));
+        assert!(vehicle.prevent_extensions());
+        assert!(engine.define_method(&vehicle, "stop", |_, _, _| Ok(Value::Undefined)).is_err());
+    }
+
+    #[rstest]
+    #[case("while (true);")]
+    #[case("do ; while (true);")]
+    #[case("for (var i = 0; ; i++);")]
+    #[case("function f(n) { return n && f(n - 1); } for (;;) try { f(10); } catch {}")]
+    #[case("for (;;) try { for (;;); } catch { } finally { for (;;); }")]
+    #[case("var o = { a: 1 }; for (;;) for (var key in o);")]
+    fn test_out_of_fuel(#[case] source: &str) {
+        let mut engine = Engine::new();
+        assert_matches!(engine.evaluate_with_fuel(source, 1000), Err(error) if error.kind == ErrorKind::OutOfFuel);
+        assert_eq!(engine.context().fuel(), None);
+        assert_eq!(engine.evaluate_script("var n = 0; while (n < 5000) n++; n"), Ok(Value::Number(5000.0)));
+    }
+
+    #[test]
+    fn test_fuel() {
+        let mut engine = Engine::new();
+        let source = "function f(n) { return n; } var sum = 0; for (var i = 0; i < 10; i++) sum += f(i); sum";
+        assert_eq!(engine.evaluate_with_fuel(source, 21), Ok(Value::Number(45.0)));
+        assert_matches!(engine.evaluate_with_fuel(source, 15), Err(error) if error.kind == ErrorKind::OutOfFuel);
+        assert_eq!(engine.evaluate_script("sum"), Ok(Value::Number(21.0)));
+    }
+
+    #[test]
+    fn test_interrupt_handler() {
+        let mut engine = Engine::new();
+        let polls = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&polls);
+        engine.set_interrupt_handler(move || {
+            counter.set(counter.get() + 1);
+            if counter.get() > 100 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+        let source = "var n = 0; try { for (;;) n++; } catch { n = -1; } finally { n = -2; }";
+        assert_matches!(engine.evaluate_script(source), Err(error) if error.kind == ErrorKind::Interrupted);
+        assert_eq!(polls.get(), 101);
+        engine.clear_interrupt_handler();
+        assert_eq!(engine.evaluate_script("n"), Ok(Value::Number(100.0)));
+        assert_eq!(engine.evaluate_script("for (n = 0; n < 500; n++); n"), Ok(Value::Number(500.0)));
+    }
+
+    #[rstest]
+    #[case("function f(x) { return x * 2; } f(21)", Value::Number(42.0))]
+    #[case("function f() { function g() { return 'inner'; } return g(); } f()", Value::from("inner"))]
+    #[case("var add = (a) => { return (b) => { return a + b; }; }; add(40)(2)", Value::Number(42.0))]
+    #[case("var o = { m() { return this.v; }, v: 7 }; o.m()", Value::Number(7.0))]
+    #[case("function* g() { yield 1; yield 2; } var i = g(); i.next().value + i.next().value", Value::Number(3.0))]
+    #[case("function f() { 'use strict'; return this; } f()", Value::Undefined)]
+    #[case("'use strict'; function f() { return this; } f()", Value::Undefined)]
+    #[case("function f() { return typeof g; } function g() {} f() + f()", Value::from("functionfunction"))]
+    fn test_deferred_function_bodies(#[case] source: &str, #[case] expected: Value) {
+        let mut engine = Engine::builder().defer_function_bodies().build();
+        assert_eq!(engine.evaluate_script(source), Ok(expected.clone()), "{source}");
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[rstest]
+    #[case("function f() { return 1 + ; }", "f()")]
+    #[case("function f(a) { let a; }", "f()")]
+    #[case("var f = () => { break; };", "f()")]
+    #[case("function f() { return () => { yield 1 + ; }; }", "f()()")]
+    fn test_deferred_function_body_errors(#[case] source: &str, #[case] call: &str) {
+        let mut engine = Engine::builder().defer_function_bodies().build();
+        assert_eq!(engine.evaluate_script(source), Ok(Value::Undefined), "{source}");
+        assert_matches!(engine.evaluate_script(call), Err(error) if error.kind == ErrorKind::SyntaxError);
+        assert_matches!(engine.evaluate_script(call), Err(error) if error.kind == ErrorKind::SyntaxError);
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == ErrorKind::SyntaxError);
+    }
+
+    #[test]
+    fn test_deferred_module_function_bodies() {
+        let mut engine = Engine::builder().defer_function_bodies().build();
+        let source = "export function double(x) { return x * 2; } export const answer = double(21);";
+        let namespace = engine.evaluate_module("main", source).unwrap();
+        let answer = namespace.get(engine.context(), &PropertyKey::from("answer"), &Value::Undefined);
+        assert_eq!(answer, Ok(Value::Number(42.0)));
+    }
+
+    /// Expose `later(callback)` enqueueing a job that calls the callback.
+    fn register_later(engine: &mut Engine) {
+        engine
+            .register_typed_function("later", |context, (callback,): (Object,)| {
+                let realm = context.current_realm().cloned();
+                let job = move |context: &mut Context| callback.call(context, &Value::Undefined, &[]).map(drop);
+                context.enqueue_promise_job(Box::new(job), realm);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_jobs() {
+        let mut engine = Engine::new();
+        register_later(&mut engine);
+        let source = "var log = [];
+                      later(function () { log[log.length] = 'b'; later(function () { log[log.length] = 'd'; }); });
+                      later(function () { log[log.length] = 'c'; });
+                      log[log.length] = 'a';";
+        engine.evaluate_script(source).unwrap();
+        assert!(engine.context().has_pending_jobs());
+        assert_eq!(engine.evaluate_script("log.length"), Ok(Value::Number(1.0)));
+        assert_eq!(engine.run_jobs(), Ok(()));
+        assert!(!engine.context().has_pending_jobs());
+        assert_eq!(engine.evaluate_script("log[0] + log[1] + log[2] + log[3]"), Ok(Value::from("abcd")));
+    }
+
+    #[test]
+    fn test_failing_jobs() {
+        let mut engine = Engine::new();
+        register_later(&mut engine);
+        let source = "var done = false;
+                      later(function () { throw new TypeError('job failed'); });
+                      later(function () { done = true; });";
+        engine.evaluate_script(source).unwrap();
+        assert_matches!(engine.run_jobs(), Err(error) if error.message == "TypeError: job failed");
+        assert_eq!(engine.evaluate_script("done"), Ok(Value::Boolean(false)));
+        assert_eq!(engine.run_jobs(), Ok(()));
+        assert_eq!(engine.evaluate_script("done"), Ok(Value::Boolean(true)));
+        assert!(engine.context().running_execution_context().is_some());
+    }
+}
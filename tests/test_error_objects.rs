@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{ErrorKind, JsError, ObjectKind, Value};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("new Error('m').message", Value::from("m"))]
+    #[case("Error('m') instanceof Error", Value::Boolean(true))]
+    #[case("new Error().message", Value::from(""))]
+    #[case("'message' in new Error() && !new Error().hasOwnProperty", Value::Boolean(true))]
+    #[case("new TypeError('t') instanceof Error", Value::Boolean(true))]
+    #[case("RangeError('r') instanceof RangeError", Value::Boolean(true))]
+    #[case("'' + new ReferenceError('x')", Value::from("ReferenceError: x"))]
+    #[case("new SyntaxError().toString()", Value::from("SyntaxError"))]
+    #[case("new Error('a', { cause: 1 }).cause", Value::Number(1.0))]
+    #[case("'cause' in new Error('a', {})", Value::Boolean(false))]
+    #[case("({ name: '', message: 'm', toString: Error.prototype.toString }).toString()", Value::from("m"))]
+    #[case("TypeError.prototype.name + TypeError.length + TypeError.name", Value::from("TypeError1TypeError"))]
+    #[case("TypeError.prototype.constructor === TypeError", Value::Boolean(true))]
+    #[case("var k = []; for (var p in new Error('m')) k[k.length] = p; k.length", Value::Number(0.0))]
+    #[case("try { null.x; } catch (e) { e instanceof TypeError && e.name; }", Value::from("TypeError"))]
+    #[case("try { missing; } catch (e) { e.constructor === ReferenceError; }", Value::Boolean(true))]
+    fn test_evaluation(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[test]
+    fn test_call_depth_error() {
+        // Unoptimized builds take more native stack per call than test
+        // threads have by default.
+        let thread = std::thread::Builder::new().stack_size(16 << 20).spawn(|| {
+            let source = "function f() { f(); } try { f(); } catch (e) { e instanceof RangeError; }";
+            assert_eq!(Engine::new().evaluate_script(source), Ok(Value::Boolean(true)));
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    #[test]
+    fn test_thrown_errors() {
+        let error = Engine::new().evaluate_script("throw new TypeError('bad input');").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Thrown);
+        assert_eq!(error.message, "TypeError: bad input");
+        let value = error.value.as_ref().and_then(Value::as_object).unwrap();
+        assert!(matches!(value.kind(), ObjectKind::Error));
+        let error: Box<dyn std::error::Error> = Box::new(error);
+        assert_eq!(error.to_string(), "Thrown: TypeError: bad input");
+    }
+
+    #[test]
+    fn test_stack() {
+        let source = "function inner() { throw new Error('deep'); } var outer = function () { inner(); }; outer();";
+        let error = Engine::new().evaluate_script(source).unwrap_err();
+        assert_eq!(error.stack, ["inner", "outer"]);
+        let error = Engine::new().evaluate_script("(() => undefined.x)()").unwrap_err();
+        assert_eq!((error.kind, error.stack), (ErrorKind::TypeError, vec!["<anonymous>".to_owned()]));
+    }
+
+    #[test]
+    fn test_host_errors() {
+        let mut engine = Engine::new();
+        engine.register_function("fail", |_, _| Err(JsError::new(ErrorKind::RangeError, "out of range"))).unwrap();
+        let caught = "try { fail(); } catch (e) { e instanceof RangeError && e.message; }";
+        assert_eq!(engine.evaluate_script(caught), Ok(Value::from("out of range")));
+        assert_eq!(engine.evaluate_script("fail()").unwrap_err().stack, ["fail"]);
+    }
+}
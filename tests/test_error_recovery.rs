@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::{Diagnostics, ErrorCode};
+    use embedded_ecmascript::grammar::{
+        validate,
+        Declaration,
+        Parser,
+        Script,
+        SourceType,
+        Statement,
+        StatementListItem,
+        ValidationOptions,
+    };
+    use rstest::rstest;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// The system allocator counting bytes held by each thread, so tests
+    /// running in parallel do not see allocations of each other.
+    struct CountingAllocator;
+
+    thread_local! {
+        static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+        static PEAK_BYTES: Cell<isize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let live = LIVE_BYTES.with(|live| {
+                live.set(live.get() + layout.size() as isize);
+                live.get()
+            });
+            PEAK_BYTES.with(|peak| peak.set(peak.get().max(live)));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            LIVE_BYTES.with(|live| live.set(live.get() - layout.size() as isize));
+            System.dealloc(ptr, layout);
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Bytes allocated at most at once while running `run`, above ones
+    /// held before it.
+    fn peak_bytes(run: impl FnOnce()) -> isize {
+        let start = LIVE_BYTES.with(Cell::get);
+        PEAK_BYTES.with(|peak| peak.set(start));
+        run();
+        PEAK_BYTES.with(Cell::get) - start
+    }
+
+    fn parse(source: &str) -> (Script, Vec<(ErrorCode, std::ops::Range<usize>)>) {
+        let mut diagnostics = Diagnostics::new();
+        let script = Parser::new(source).parse_script_with_diagnostics(&mut diagnostics);
+        let errors = diagnostics.into_iter().map(|error| (error.code, error.range)).collect();
+        (script, errors)
+    }
+
+    fn ranges(statements: &[StatementListItem]) -> Vec<std::ops::Range<usize>> {
+        statements.iter().map(StatementListItem::range).collect()
+    }
+
+    fn starts(statements: &[StatementListItem]) -> Vec<usize> {
+        statements.iter().map(|statement| statement.range().start).collect()
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("a; if (b) { c; } else d;")]
+    #[case("function f() { return a; }")]
+    fn test_correct_scripts(#[case] source: &str) {
+        let (script, errors) = parse(source);
+        assert!(errors.is_empty());
+        assert_eq!(script, Parser::new(source).parse_script().unwrap());
+    }
+
+    #[rstest]
+    #[case("a; b c; d;", &[0, 8], &[5])]
+    #[case("a; } b;", &[0, 5], &[3])]
+    #[case("a; @; b;", &[0, 6], &[3])]
+    #[case("a = ; b = {c: 1 d: 2}; e;", &[21, 23], &[4, 16])]
+    #[case("if (a { b; } c; d;", &[16], &[6])]
+    #[case("let a = #b; class C { #d; m() { this.#e; } } f;", &[45], &[8, 37])]
+    #[case("a +", &[], &[3])]
+    fn test_skipped_statements(
+        #[case] source: &str,
+        #[case] statements: &[usize],
+        #[case] errors: &[usize]
+    ) {
+        let (script, reported) = parse(source);
+        assert_eq!(starts(&script.body), statements);
+        let reported: Vec<_> = reported.into_iter().map(|(_, range)| range.start).collect();
+        assert_eq!(reported, errors);
+    }
+
+    #[test]
+    fn test_nested_statement_lists() {
+        let (script, errors) = parse("function f() { a b; { c d; e; } g; } switch (h) { case 1: i j; k; }");
+        assert_eq!(errors.iter().map(|(_, range)| range.clone()).collect::<Vec<_>>(), [17..18, 24..25, 60..61]);
+        let [StatementListItem::Declaration(Declaration::FunctionDeclaration(function)), switch] = &script.body[..] else {
+            panic!("a function and a switch statement are expected");
+        };
+        assert_eq!(ranges(&function.body), [20..31, 32..34]);
+        assert_matches!(switch, StatementListItem::Statement(Statement::SwitchStatement(statement))
+            if starts(&statement.cases[0].statements) == [63]);
+    }
+
+    #[test]
+    fn test_unfinished_blocks() {
+        let (script, errors) = parse("function f() {\n    a;\n    if (b) {\n        c(");
+        assert_eq!(errors, [
+            (ErrorCode::IncompleteInput, 45..45),
+            (ErrorCode::IncompleteInput, 45..45),
+            (ErrorCode::IncompleteInput, 45..45),
+        ]);
+        assert_matches!(&script.body[..], [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))]
+            if function.body.len() == 2 && function.range == (0..45));
+    }
+
+    #[test]
+    fn test_errors_are_not_recovered_by_default() {
+        let error = Parser::new("a b; c d;").parse_script().unwrap_err();
+        assert_eq!(error.range, 2..3);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("'use strict'; function f(a) { return a; }")]
+    #[case("a; b c; d;")]
+    #[case("let a; var b; let a; var a; function b() {} b c;")]
+    #[case("'use strict'; var a; let a; with (b) {}")]
+    #[case("var a; { let a; let b; var b; } break;")]
+    #[case("a: while (b) { continue c; } let d = 1 = 2;")]
+    fn test_validation(#[case] source: &str) {
+        let (_, errors) = parse(source);
+        let validated = validate(source, ValidationOptions::default()).map_err(|errors| {
+            errors.into_iter().map(|error| (error.code, error.range)).collect::<Vec<_>>()
+        });
+        assert_eq!(validated, if errors.is_empty() { Ok(()) } else { Err(errors) });
+    }
+
+    #[test]
+    fn test_module_validation() {
+        let options = ValidationOptions { source_type: SourceType::Module, ..ValidationOptions::default() };
+        let errors = validate("function a() {} var a; with (b) {}", options).unwrap_err();
+        let ranges: Vec<_> = errors.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [23..27, 20..21]);
+        assert!(validate("function a() {} var a;", ValidationOptions::default()).is_ok());
+    }
+
+    #[rstest]
+    #[case("import a from 'm'; export { a, b as c }; var b;")]
+    #[case("export { a }; export let a;")]
+    #[case("export { a, 'b' }; let c; export default 1; export default function () {}")]
+    #[case("export * as a from 'm'; export { b as a } from 'n'; import { c } from 'o'; let c;")]
+    #[case("export function a() {} var a; with (b) {}")]
+    fn test_module_validation_matches_parsing(#[case] source: &str) {
+        let mut diagnostics = Diagnostics::new();
+        Parser::new_module(source).parse_module_with_diagnostics(&mut diagnostics);
+        let mut parsed: Vec<_> = diagnostics.into_iter().map(|error| (error.range, error.message)).collect();
+        let options = ValidationOptions { source_type: SourceType::Module, ..ValidationOptions::default() };
+        let errors = validate(source, options).err().unwrap_or_default();
+        let mut validated: Vec<_> = errors.into_iter().map(|error| (error.range, error.message)).collect();
+        // Items are checked as they come, so problems are in another order.
+        parsed.sort_by_key(|(range, _)| range.start);
+        validated.sort_by_key(|(range, _)| range.start);
+        assert_eq!(validated, parsed);
+    }
+
+    #[rstest]
+    fn test_deep_validation(#[values(SourceType::Script, SourceType::Module)] source_type: SourceType) {
+        // Hostile input must not overflow a small native stack.
+        let thread = std::thread::Builder::new().stack_size(2 << 20).spawn(move || {
+            let source = "[".repeat(10000) + &"]".repeat(10000);
+            let options = ValidationOptions { source_type, ..ValidationOptions::default() };
+            let errors = validate(&source, options).unwrap_err();
+            assert!(errors.iter().any(|error| error.code == ErrorCode::NestingTooDeep), "{errors:?}");
+            assert!(validate(&("[".repeat(100) + &"]".repeat(100)), options).is_ok());
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    #[rstest]
+    fn test_validation_drops_trees(#[values(SourceType::Script, SourceType::Module)] source_type: SourceType) {
+        let mut source = "a = [b, c + 1, { d: e }];\n".repeat(2000);
+        if source_type == SourceType::Module {
+            // Bound after the export, so checked only at the end.
+            source.push_str("export { f }; var f;");
+        }
+        let options = ValidationOptions { source_type, ..ValidationOptions::default() };
+        let parsed = peak_bytes(|| {
+            let mut diagnostics = Diagnostics::new();
+            if source_type == SourceType::Module {
+                drop(Parser::new_module(&source).parse_module_with_diagnostics(&mut diagnostics));
+            } else {
+                drop(Parser::new(&source).parse_script_with_diagnostics(&mut diagnostics));
+            }
+        });
+        let validated = peak_bytes(|| assert!(validate(&source, options).is_ok()));
+        // Trees of all statements at once take much more than one of them.
+        assert!(validated * 10 < parsed, "validated: {validated}, parsed: {parsed}");
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::lexical_grammar::{get_next_token_with_range, GoalSymbols};
+    use embedded_ecmascript::{Diagnostics, ErrorCode, Label, LineIndex, Severity, SourceCodeError};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", 0, (1, 1))]
+    #[case("abc", 2, (1, 3))]
+    #[case("abc", 100, (1, 4))]
+    #[case("a\nb", 2, (2, 1))]
+    #[case("a\r\nb", 3, (2, 1))]
+    #[case("a\rb", 2, (2, 1))]
+    #[case("a\u{2028}b\u{2029}c", 5, (2, 2))]
+    #[case("a\u{2028}b\u{2029}c", 8, (3, 1))]
+    #[case("дд\nдд", 7, (2, 2))]
+    fn test_line_index(#[case] source: &str, #[case] offset: usize, #[case] expected: (usize, usize)) {
+        assert_eq!(LineIndex::new(source).line_column(offset), expected);
+    }
+
+    #[test]
+    fn test_source_code_error() {
+        let error = SourceCodeError::new("a;\n  b", 5..6, ErrorCode::UnexpectedInput, "unexpected b".to_owned());
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.severity, Severity::Error);
+        assert!(error.labels.is_empty());
+        assert!(error.expected.is_empty());
+        assert_eq!(error.to_string(), "error: unexpected b\n --> 2:3");
+    }
+
+    #[rstest]
+    #[case("a\nbc", 1, 0..1)]
+    #[case("a\r\nbc", 2, 3..5)]
+    #[case("a\u{2028}", 1, 0..1)]
+    #[case("a\n", 2, 2..2)]
+    #[case("a", 5, 0..1)]
+    fn test_line_range(#[case] source: &str, #[case] line: usize, #[case] expected: std::ops::Range<usize>) {
+        assert_eq!(LineIndex::new(source).line_range(line), expected);
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "let a = 'abc";
+        let error = get_next_token_with_range(source, 8, GoalSymbols::InputElementDiv).unwrap_err();
+        assert_eq!(error.render(source), [
+            "error: unterminated string literal",
+            " --> 1:9",
+            "  |",
+            "1 | let a = 'abc",
+            "  |         ^",
+            "  |             - expected `'` here",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_render_multiple_lines() {
+        let source = "a\n\tb  c\r\n\n\n\n\n\n\n\n\nd";
+        let mut error = SourceCodeError::new(source, 3..7, ErrorCode::UnexpectedInput, "unexpected b".to_owned());
+        error.labels.push(Label { range: 17..18, message: "see d".to_owned() });
+        error.labels.push(Label { range: 0..0, message: "see a".to_owned() });
+        assert_eq!(error.render(source), [
+            "error: unexpected b",
+            "  --> 2:2",
+            "   |",
+            " 1 | a",
+            "   | - see a",
+            " 2 | \tb  c",
+            "   | \t^^^^",
+            "11 | d",
+            "   | - see d",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let source = "a\nb";
+        let mut diagnostics = Diagnostics::new();
+        assert!(diagnostics.is_empty() && !diagnostics.has_errors());
+
+        let mut warning = SourceCodeError::new(source, 0..1, ErrorCode::EarlyError, "deprecated a".to_owned());
+        warning.severity = Severity::Warning;
+        diagnostics.report(warning);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(!diagnostics.has_errors());
+
+        diagnostics.extend([SourceCodeError::new(source, 2..3, ErrorCode::UnexpectedInput, "unexpected b".to_owned())]);
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.render(source), [
+            "warning: deprecated a",
+            " --> 1:1",
+            "  |",
+            "1 | a",
+            "  | ^",
+            "",
+            "error: unexpected b",
+            " --> 2:1",
+            "  |",
+            "2 | b",
+            "  | ^",
+        ].join("\n"));
+        let messages: Vec<_> = diagnostics.into_iter().map(|report| report.message).collect();
+        assert_eq!(messages, ["deprecated a", "unexpected b"]);
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_miette_diagnostic() {
+        use miette::Diagnostic;
+
+        let error = get_next_token_with_range("a = 'b", 4, GoalSymbols::InputElementDiv).unwrap_err();
+        assert_eq!(error.code().map(|code| code.to_string()).as_deref(), Some("embedded_ecmascript::UnexpectedInput"));
+        assert_eq!(error.severity(), Some(miette::Severity::Error));
+        assert_eq!(error.help().map(|help| help.to_string()).as_deref(), Some("expected `'`"));
+        let labels: Vec<_> = error.labels().unwrap().collect();
+        assert_eq!(labels, [
+            miette::LabeledSpan::new_primary_with_span(None, 4..5),
+            miette::LabeledSpan::new_with_span(Some("expected `'` here".to_owned()), 6..6),
+        ]);
+    }
+}
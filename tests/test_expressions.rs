@@ -0,0 +1,443 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::grammar::{
+        Argument,
+        ArrayElement,
+        AssignmentTargetType,
+        Expression,
+        Literal,
+        MemberProperty,
+        MetaProperty,
+        Parser,
+        PropertyDefinition,
+        PropertyName,
+        TemplateLiteral,
+    };
+    use rstest::rstest;
+
+    fn parse(source: &str) -> Expression {
+        let mut parser = Parser::new(source);
+        let expression = parser.parse_expression().unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        expression
+    }
+
+    /// Render an expression as a Lisp-like list so tests can check grouping
+    /// without spelling whole trees.
+    fn sexp(expression: &Expression) -> String {
+        fn list(items: &[Argument]) -> String {
+            let items: Vec<_> = items.iter().map(|item| match item {
+                Argument::AssignmentExpression(item) => sexp(item),
+                Argument::SpreadElement(item, _) => format!("...{}", sexp(item)),
+            }).collect();
+            items.join(" ")
+        }
+
+        /// Raw strings and substitutions of a template in order.
+        fn template(template: &TemplateLiteral) -> String {
+            let mut parts = vec![format!("{:?}", template.strings[0].raw)];
+            for (substitution, string) in template.substitutions.iter().zip(&template.strings[1..]) {
+                parts.push(sexp(substitution));
+                parts.push(format!("{:?}", string.raw));
+            }
+            format!("(` {})", parts.join(" "))
+        }
+
+        match expression {
+            Expression::This(_) => "this".to_owned(),
+            Expression::IdentifierReference(reference) => reference.name.clone(),
+            Expression::Literal(Literal::NullLiteral, _) => "null".to_owned(),
+            Expression::Literal(Literal::BooleanLiteral(value), _) => value.to_string(),
+            Expression::Literal(Literal::NumericLiteral(literal), _) => literal.to_string(),
+            Expression::Literal(Literal::StringLiteral(literal), _) => literal.string_value(),
+            Expression::ParenthesizedExpression(parenthesized) => format!("({})", sexp(&parenthesized.expression)),
+            Expression::MemberExpression(member) => {
+                let optional = if member.optional { "?" } else { "" };
+                match &member.property {
+                    MemberProperty::IdentifierName(name, _) => format!("({optional}. {} {name})", sexp(&member.object)),
+                    MemberProperty::Expression(property) => {
+                        format!("({optional}[] {} {})", sexp(&member.object), sexp(property))
+                    },
+                    MemberProperty::PrivateIdentifier(name, _) => format!("({optional}. {} {name})", sexp(&member.object)),
+                }
+            },
+            Expression::MetaProperty(MetaProperty::NewTarget, _) => "new.target".to_owned(),
+            Expression::MetaProperty(MetaProperty::ImportMeta, _) => "import.meta".to_owned(),
+            Expression::TemplateLiteral(literal) => template(literal),
+            Expression::TaggedTemplate(tagged) => format!("(tag {} {})", sexp(&tagged.tag), template(&tagged.template)),
+            Expression::CallExpression(call) => {
+                let optional = if call.optional { "?" } else { "" };
+                format!("({optional}call {} [{}])", sexp(&call.callee), list(&call.arguments))
+            },
+            Expression::ImportCall(call) => format!("(import {})", sexp(&call.specifier)),
+            Expression::OptionalExpression(chain) => format!("(chain {})", sexp(&chain.expression)),
+            Expression::NewExpression(new) => {
+                let arguments = new.arguments.as_ref().map(|arguments| format!(" [{}]", list(arguments)));
+                format!("(new {}{})", sexp(&new.callee), arguments.unwrap_or_default())
+            },
+            Expression::UpdateExpression(update) if update.prefix => {
+                format!("(pre{:?} {})", update.operator, sexp(&update.operand))
+            },
+            Expression::UpdateExpression(update) => format!("(post{:?} {})", update.operator, sexp(&update.operand)),
+            Expression::UnaryExpression(unary) => format!("({:?} {})", unary.operator, sexp(&unary.operand)),
+            Expression::BinaryExpression(binary) => {
+                format!("({:?} {} {})", binary.operator, sexp(&binary.left), sexp(&binary.right))
+            },
+            Expression::ConditionalExpression(conditional) => format!(
+                "(? {} {} {})",
+                sexp(&conditional.condition),
+                sexp(&conditional.consequent),
+                sexp(&conditional.alternative)
+            ),
+            Expression::AssignmentExpression(assignment) => {
+                format!("({:?} {} {})", assignment.operator, sexp(&assignment.target), sexp(&assignment.value))
+            },
+            Expression::CommaExpression(comma) => {
+                let expressions: Vec<_> = comma.expressions.iter().map(sexp).collect();
+                format!("(, {})", expressions.join(" "))
+            },
+            other => format!("{other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case("a + b * c", "(Addition a (Multiplication b c))")]
+    #[case("a * b + c", "(Addition (Multiplication a b) c)")]
+    #[case("a - b - c", "(Subtraction (Subtraction a b) c)")]
+    #[case("(a + b) * c", "(Multiplication ((Addition a b)) c)")]
+    #[case("a ** b ** c", "(Exponentiation a (Exponentiation b c))")]
+    #[case("(-a) ** b", "(Exponentiation ((Minus a)) b)")]
+    #[case("a++ ** b", "(Exponentiation (postIncrement a) b)")]
+    #[case("a || b && c | d ^ e & f", "(Or a (And b (BitOr c (BitXor d (BitAnd e f)))))")]
+    #[case("a ?? b ?? c", "(NullishCoalescence (NullishCoalescence a b) c)")]
+    #[case("a ?? b | c", "(NullishCoalescence a (BitOr b c))")]
+    #[case("(a || b) ?? (c && d)", "(NullishCoalescence ((Or a b)) ((And c d)))")]
+    #[case("a ?? b ? c : d", "(? (NullishCoalescence a b) c d)")]
+    #[case("a == b < c << d", "(LooseEquality a (Less b (LeftShift c d)))")]
+    #[case("a instanceof b in c", "(In (Instanceof a b) c)")]
+    #[case("a !== b === c", "(StrictEquality (StrictInequality a b) c)")]
+    #[case("a >>> b >> c", "(RightShift (UnsignedRightShift a b) c)")]
+    #[case("!-~a", "(Not (Minus (BitNot a)))")]
+    #[case("typeof void delete a.b", "(Typeof (Void (Delete (. a b))))")]
+    #[case("++a", "(preIncrement a)")]
+    #[case("a--", "(postDecrement a)")]
+    #[case("- --a", "(Minus (preDecrement a))")]
+    #[case("a ? b : c ? d : e", "(? a b (? c d e))")]
+    #[case("a ? b = c : d", "(? a (Assignment b c) d)")]
+    #[case("a = b = c", "(Assignment a (Assignment b c))")]
+    #[case("a += b ||= c", "(AdditionAssignment a (OrAssignment b c))")]
+    #[case("a **= b >>>= c", "(ExponentiationAssignment a (UnsignedRightShiftAssignment b c))")]
+    #[case("a, b = c, d", "(, a (Assignment b c) d)")]
+    fn test_operator_precedence(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(sexp(&parse(source)), expected);
+    }
+
+    #[rstest]
+    #[case("a.b.c", "(. (. a b) c)")]
+    #[case("a[b][c]", "([] ([] a b) c)")]
+    #[case("a.if.class", "(. (. a if) class)")]
+    #[case("a(b)(c, ...d,)", "(call (call a [b]) [c ...d])")]
+    #[case("a.b(c).d", "(. (call (. a b) [c]) d)")]
+    #[case("new a", "(new a)")]
+    #[case("new a.b(c)", "(new (. a b) [c])")]
+    #[case("new a().b", "(. (new a []) b)")]
+    #[case("new a()()", "(call (new a []) [])")]
+    #[case("new new a()()", "(new (new a []) [])")]
+    #[case("new new a", "(new (new a))")]
+    #[case("new a[b]", "(new ([] a b))")]
+    #[case("this.a", "(. this a)")]
+    #[case("a?.b", "(chain (?. a b))")]
+    #[case("a?.[b]", "(chain (?[] a b))")]
+    #[case("a?.(b)", "(chain (?call a [b]))")]
+    #[case("a?.b.c(d)[e]", "(chain ([] (call (. (?. a b) c) [d]) e))")]
+    #[case("a?.b?.c", "(chain (?. (?. a b) c))")]
+    #[case("new a()?.()", "(chain (?call (new a []) []))")]
+    #[case("(a?.b).c", "(. ((chain (?. a b))) c)")]
+    #[case("a ? .5 : b", "(? a .5 b)")]
+    #[case("import(a + b).then(c)", "(call (. (import (Addition a b)) then) [c])")]
+    #[case("yield + await", "(Addition yield await)")]
+    fn test_left_hand_side_expressions(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(sexp(&parse(source)), expected);
+    }
+
+    #[test]
+    fn test_super() {
+        let source = "class A extends B { constructor() { super(a, ...b).c; } m() { super.d(super[e]); new super.f(); super.g`h`; } } \
+            ({ m() { return super.if; }, get a() { return () => super.a; } });";
+        assert_matches!(Parser::new(source).parse_script(), Ok(_));
+    }
+
+    #[test]
+    fn test_meta_properties() {
+        let mut parser = Parser::new_module("import.meta.url");
+        assert_eq!(sexp(&parser.parse_expression().unwrap()), "(. import.meta url)");
+        assert_eq!(parser.finish(), Ok(()));
+
+        let source = "function f() { new new.target(); () => new.target; } \
+            class A { a = new.target; static { new.target; } m(b = new.target) {} } import('a');";
+        assert_matches!(Parser::new(source).parse_script(), Ok(_));
+    }
+
+    #[rstest]
+    #[case("null", "null")]
+    #[case("true", "true")]
+    #[case("false", "false")]
+    #[case("0x1F", "0x1F")]
+    #[case("'a\\x62'", "ab")]
+    #[case("\\u0061", "a")]
+    fn test_literals(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(sexp(&parse(source)), expected);
+    }
+
+    #[test]
+    fn test_regular_expressions() {
+        let Expression::RegularExpressionLiteral(literal, _) = parse("/[/]+/g") else {
+            panic!("a regular expression literal is expected");
+        };
+        assert_eq!((literal.body_text(), literal.flag_text()), ("[/]+", "g"));
+
+        assert_eq!(sexp(&parse("a / b / c")), "(Division (Division a b) c)");
+        assert_eq!(sexp(&parse("a /= b")), "(DivisionAssignment a b)");
+        assert_matches!(parse("a = /=/"), Expression::AssignmentExpression(assignment)
+            if matches!(*assignment.value, Expression::RegularExpressionLiteral(..)));
+        assert_matches!(parse("a(/b/)"), Expression::CallExpression(call)
+            if matches!(&call.arguments[..], [Argument::AssignmentExpression(Expression::RegularExpressionLiteral(..))]));
+    }
+
+    #[test]
+    fn test_templates() {
+        let Expression::TemplateLiteral(template) = parse("`a\\n`") else {
+            panic!("a template literal is expected");
+        };
+        assert!(template.substitutions.is_empty());
+        assert_eq!(template.strings.len(), 1);
+        assert_eq!(template.strings[0].raw, "a\\n");
+        assert_eq!(template.strings[0].cooked, Some(vec![0x61, 0x0A]));
+
+        // A `}` inside a substitution does not end it.
+        assert_matches!(parse("`${/}/}`"), Expression::TemplateLiteral(template)
+            if matches!(template.substitutions[..], [Expression::RegularExpressionLiteral(..)]));
+    }
+
+    #[rstest]
+    #[case("`a${b}c`", r#"(` "a" b "c")"#)]
+    #[case("`${a}${b + c}`", r#"(` "" a "" (Addition b c) "")"#)]
+    #[case("`a${ { b } }c`", r#"(` "a" ObjectLiteral(ObjectLiteral { properties: [IdentifierReference(IdentifierReference { name: "b", range: 7..8 })], range: 5..10 }) "c")"#)]
+    #[case("`a${`b${c}`}d`", r#"(` "a" (` "b" c "") "d")"#)]
+    #[case("a`b`", r#"(tag a (` "b"))"#)]
+    #[case("a.b`c${d}e`.f", r#"(. (tag (. a b) (` "c" d "e")) f)"#)]
+    #[case("a()`b`", r#"(tag (call a []) (` "b"))"#)]
+    #[case("new a`b`", r#"(new (tag a (` "b")))"#)]
+    #[case("a`\\unicode`", r#"(tag a (` "\\unicode"))"#)]
+    fn test_substitution_templates(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(sexp(&parse(source)), expected);
+    }
+
+    #[test]
+    fn test_tagged_template_cooked_strings() {
+        let Expression::TaggedTemplate(tagged) = parse("a`\\u{`") else {
+            panic!("a tagged template is expected");
+        };
+        assert_eq!(tagged.template.strings[0].raw, "\\u{");
+        assert_eq!(tagged.template.strings[0].cooked, None);
+    }
+
+    #[test]
+    fn test_array_literals() {
+        let Expression::ArrayLiteral(array) = parse("[, a, ...b, , ]") else {
+            panic!("an array literal is expected");
+        };
+        assert_matches!(&array.elements[..], [
+            ArrayElement::Elision(_),
+            ArrayElement::AssignmentExpression(Expression::IdentifierReference(_)),
+            ArrayElement::SpreadElement(Expression::IdentifierReference(_), _),
+            ArrayElement::Elision(_),
+        ]);
+        assert_matches!(parse("[]"), Expression::ArrayLiteral(array) if array.elements.is_empty());
+        assert_matches!(parse("[a]"), Expression::ArrayLiteral(array) if array.elements.len() == 1);
+    }
+
+    #[test]
+    fn test_object_literals() {
+        let Expression::ObjectLiteral(object) = parse("{ a, b: 1, if: 2, 'c': 3, 4: 5, [d]: 6, ...e, }") else {
+            panic!("an object literal is expected");
+        };
+        assert_eq!(object.properties.len(), 7);
+        assert_matches!(&object.properties[0], PropertyDefinition::IdentifierReference(reference)
+            if reference.name == "a");
+        assert_matches!(&object.properties[1], PropertyDefinition::Property {
+            name: PropertyName::IdentifierName(name, _),
+            value: Expression::Literal(Literal::NumericLiteral(_), _),
+        } if name == "b");
+        assert_matches!(&object.properties[2], PropertyDefinition::Property {
+            name: PropertyName::IdentifierName(name, _),
+            ..
+        } if name == "if");
+        assert_matches!(&object.properties[3], PropertyDefinition::Property {
+            name: PropertyName::StringLiteral(..),
+            ..
+        });
+        assert_matches!(&object.properties[4], PropertyDefinition::Property {
+            name: PropertyName::NumericLiteral(..),
+            ..
+        });
+        assert_matches!(&object.properties[5], PropertyDefinition::Property {
+            name: PropertyName::ComputedPropertyName(..),
+            ..
+        });
+        assert_matches!(&object.properties[6], PropertyDefinition::SpreadProperty(..));
+        assert_matches!(parse("{}"), Expression::ObjectLiteral(object) if object.properties.is_empty());
+    }
+
+    #[test]
+    fn test_comments_and_line_terminators() {
+        assert_eq!(sexp(&parse("a /* b */ +\n// c\nd")), "(Addition a d)");
+    }
+
+    #[test]
+    fn test_partial_input() {
+        let mut parser = Parser::new("a + b; c");
+        assert_eq!(sexp(&parser.parse_expression().unwrap()), "(Addition a b)");
+        assert!(!parser.is_at_end());
+        assert_matches!(parser.finish(), Err(error) if error.range == (5..6));
+    }
+
+    #[rstest]
+    #[case(" a ", 1..2)]
+    #[case("a + b * c", 0..9)]
+    #[case("(a, b)", 0..6)]
+    #[case("a.b?.[c](d)", 0..11)]
+    #[case("new a", 0..5)]
+    #[case("a++", 0..3)]
+    #[case("-a", 0..2)]
+    #[case("a ? b : c", 0..9)]
+    #[case("a = b", 0..5)]
+    #[case("async (a) => a", 0..14)]
+    #[case("async(a)", 0..8)]
+    #[case("{ a: [b] }", 0..10)]
+    #[case("`a${b}c`", 0..8)]
+    #[case("function () { /* a */ }", 0..23)]
+    fn test_ranges(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        assert_eq!(parse(source).range(), range);
+    }
+
+    #[rstest]
+    #[case("a", AssignmentTargetType::Simple, AssignmentTargetType::Simple)]
+    #[case("eval", AssignmentTargetType::Simple, AssignmentTargetType::Invalid)]
+    #[case("((arguments))", AssignmentTargetType::Simple, AssignmentTargetType::Invalid)]
+    #[case("a.b[c]", AssignmentTargetType::Simple, AssignmentTargetType::Simple)]
+    #[case("(a.b)", AssignmentTargetType::Simple, AssignmentTargetType::Simple)]
+    #[case("a?.b", AssignmentTargetType::Invalid, AssignmentTargetType::Invalid)]
+    #[case("f()", AssignmentTargetType::Invalid, AssignmentTargetType::Invalid)]
+    #[case("(a, b)", AssignmentTargetType::Invalid, AssignmentTargetType::Invalid)]
+    #[case("[a]", AssignmentTargetType::Invalid, AssignmentTargetType::Invalid)]
+    #[case("1", AssignmentTargetType::Invalid, AssignmentTargetType::Invalid)]
+    fn test_assignment_target_type(
+        #[case] source: &str,
+        #[case] sloppy: AssignmentTargetType,
+        #[case] strict: AssignmentTargetType
+    ) {
+        let expression = parse(source);
+        assert_eq!((expression.assignment_target_type(false), expression.assignment_target_type(true)), (sloppy, strict));
+    }
+
+    #[rstest]
+    #[case("function () {}", true)]
+    #[case("(class {})", true)]
+    #[case("async a => a", true)]
+    #[case("((() => {}))", true)]
+    #[case("a", false)]
+    #[case("(function () {}, a)", false)]
+    #[case("function () {}()", false)]
+    fn test_is_function_definition(#[case] source: &str, #[case] expected: bool) {
+        assert_eq!(parse(source).is_function_definition(), expected);
+    }
+
+    #[test]
+    fn test_nested_ranges() {
+        let Expression::BinaryExpression(addition) = parse("a + (b * c)") else {
+            panic!("a binary expression is expected");
+        };
+        assert_eq!(addition.left.range(), 0..1);
+        let Expression::ParenthesizedExpression(parenthesized) = &*addition.right else {
+            panic!("a parenthesized expression is expected");
+        };
+        assert_eq!(parenthesized.range, 4..11);
+        assert_eq!(parenthesized.expression.range(), 5..10);
+
+        let Expression::ObjectLiteral(object) = parse("{ [a]: 1, ...b }") else {
+            panic!("an object literal is expected");
+        };
+        assert_matches!(&object.properties[0], PropertyDefinition::Property { name, value }
+            if name.range() == (2..5) && value.range() == (7..8));
+        assert_eq!(object.properties[1].range(), 10..14);
+    }
+
+    #[rstest]
+    #[case("-a ** b", ErrorCode::UnexpectedInput, 0..2)]
+    #[case("typeof a ** b", ErrorCode::UnexpectedInput, 0..8)]
+    #[case("a +", ErrorCode::IncompleteInput, 3..3)]
+    #[case("(a", ErrorCode::IncompleteInput, 2..2)]
+    #[case("(a;", ErrorCode::UnexpectedInput, 2..3)]
+    #[case("a + b = c", ErrorCode::UnexpectedInput, 0..5)]
+    #[case("a ? b", ErrorCode::IncompleteInput, 5..5)]
+    #[case("++a++", ErrorCode::UnexpectedInput, 2..5)]
+    #[case("++-a", ErrorCode::UnexpectedInput, 2..4)]
+    #[case("{ if }", ErrorCode::UnexpectedInput, 5..6)]
+    #[case("{ \\u0069f }", ErrorCode::EarlyError, 2..9)]
+    #[case("\\u0069f", ErrorCode::EarlyError, 0..7)]
+    #[case("a.#b", ErrorCode::EarlyError, 2..4)]
+    #[case("a @ b", ErrorCode::UnexpectedInput, 2..3)]
+    #[case("'a", ErrorCode::UnexpectedInput, 0..1)]
+    #[case("a ?? b || c", ErrorCode::UnexpectedInput, 7..9)]
+    #[case("a || b ?? c", ErrorCode::UnexpectedInput, 7..9)]
+    #[case("a ?? b && c", ErrorCode::UnexpectedInput, 7..9)]
+    #[case("a && b ?? c", ErrorCode::UnexpectedInput, 7..9)]
+    #[case("a?.b`c`", ErrorCode::EarlyError, 4..7)]
+    #[case("a?.`b`", ErrorCode::EarlyError, 3..6)]
+    #[case("new a?.b", ErrorCode::UnexpectedInput, 5..7)]
+    #[case("a?.", ErrorCode::IncompleteInput, 3..3)]
+    #[case("new.target", ErrorCode::EarlyError, 0..10)]
+    #[case("() => new.target", ErrorCode::EarlyError, 6..16)]
+    #[case("class A extends new.target {}", ErrorCode::EarlyError, 16..26)]
+    #[case("new.tar\\u0067et", ErrorCode::UnexpectedInput, 4..15)]
+    #[case("import.meta", ErrorCode::EarlyError, 0..11)]
+    #[case("import.foo", ErrorCode::UnexpectedInput, 7..10)]
+    #[case("import", ErrorCode::IncompleteInput, 6..6)]
+    #[case("import(a, b)", ErrorCode::UnexpectedInput, 8..9)]
+    #[case("new import(a)", ErrorCode::UnexpectedInput, 4..10)]
+    #[case("new super(a)", ErrorCode::UnexpectedInput, 4..9)]
+    #[case("super", ErrorCode::IncompleteInput, 5..5)]
+    #[case("super?.a", ErrorCode::UnexpectedInput, 5..7)]
+    #[case("super.#a", ErrorCode::UnexpectedInput, 6..8)]
+    #[case("`a${b`", ErrorCode::UnexpectedInput, 5..6)]
+    #[case("`a${b c}`", ErrorCode::UnexpectedInput, 6..7)]
+    #[case("`a${}`", ErrorCode::UnexpectedInput, 4..5)]
+    #[case("`\\u{`", ErrorCode::EarlyError, 0..5)]
+    #[case("`a${b}\\u{`", ErrorCode::EarlyError, 5..10)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let mut parser = Parser::new(source);
+        let error = parser.parse_expression().and_then(|_| parser.finish()).unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[test]
+    fn test_expected_input() {
+        let error = Parser::new("f(a b)").parse_expression().unwrap_err();
+        assert_eq!(error.message, "expected `,`, found `b`");
+        assert_eq!(error.expected, ["`,`"]);
+    }
+
+    #[rstest]
+    #[case("a \\ b", "expected a token, found `\\`")]
+    #[case("a + @", "expected a token, found `@`")]
+    #[case("a \u{200B}", "expected a token, found U+200B")]
+    fn test_unexpected_character(#[case] source: &str, #[case] message: &str) {
+        let mut parser = Parser::new(source);
+        let error = parser.parse_expression().and_then(|_| parser.finish()).unwrap_err();
+        assert_eq!(error.message, message);
+        assert_eq!(error.expected, ["a token"]);
+    }
+}
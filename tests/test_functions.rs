@@ -0,0 +1,310 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::grammar::{
+        ArrowFunction,
+        Binding,
+        ClassElement,
+        ConciseBody,
+        Declaration,
+        Expression,
+        Function,
+        Parser,
+        PropertyDefinition,
+        Statement,
+        StatementListItem,
+    };
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    fn parse(source: &str) -> Vec<StatementListItem> {
+        Parser::new(source).parse_script().unwrap().body
+    }
+
+    fn parse_expression(source: &str) -> Expression {
+        let mut parser = Parser::new(source);
+        let expression = parser.parse_expression().unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        expression
+    }
+
+    fn parse_arrow_function(source: &str) -> ArrowFunction {
+        let Expression::ArrowFunction(arrow_function) = parse_expression(source) else {
+            panic!("an arrow function is expected");
+        };
+        Rc::unwrap_or_clone(arrow_function)
+    }
+
+    #[test]
+    fn test_function_declarations() {
+        let body = parse("function f(a, b = 1, [c], ...{ d }) { return a; }");
+        let [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))] = &body[..] else {
+            panic!("a function declaration is expected");
+        };
+        assert!(function.is_declaration);
+        assert_matches!(&function.name, Some(name) if name.name == "f");
+        assert_eq!(function.parameters.parameters.len(), 3);
+        assert!(function.parameters.parameters[1].initializer.is_some());
+        assert_matches!(&function.parameters.parameters[2].target, Binding::ArrayBindingPattern(_));
+        assert_matches!(&function.parameters.rest, Some(Binding::ObjectBindingPattern(_)));
+        assert_matches!(&function.body[..], [StatementListItem::Statement(Statement::ReturnStatement(_))]);
+    }
+
+    #[test]
+    fn test_function_expressions() {
+        assert_matches!(parse_expression("function () {}"), Expression::FunctionExpression(function)
+            if !function.is_declaration && function.name.is_none() && function.body.is_empty());
+        assert_matches!(parse_expression("function f(a,) { f; }"), Expression::FunctionExpression(function)
+            if function.name.is_some() && function.parameters.parameters.len() == 1 && function.body.len() == 1);
+        assert_matches!(parse_expression("function () {}()"), Expression::CallExpression(_));
+        assert_matches!(
+            &parse("(function () {});")[..],
+            [StatementListItem::Statement(Statement::ExpressionStatement(Expression::ParenthesizedExpression(_), _))]
+        );
+    }
+
+    #[test]
+    fn test_nested_functions() {
+        let body = parse("function f() { function g() { return () => { return; }; } }");
+        assert_matches!(&body[..], [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))]
+            if matches!(&function.body[..], [StatementListItem::Declaration(Declaration::FunctionDeclaration(_))]));
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_labelled_functions() {
+        let body = parse("a: function f() {}");
+        assert_matches!(&body[..], [StatementListItem::Statement(Statement::LabelledStatement(statement))]
+            if matches!(*statement.body, embedded_ecmascript::grammar::LabelledItem::FunctionDeclaration(_)));
+    }
+
+    #[rstest]
+    #[case("a => a", 1, false)]
+    #[case("yield => 1", 1, false)]
+    #[case("() => {}", 0, false)]
+    #[case("(a) => a", 1, false)]
+    #[case("(a, b,) => { return a; }", 2, false)]
+    #[case("(a = 1, { b }, [c] = [], ...d) => 0", 3, true)]
+    #[case("(...[a]) => a", 0, true)]
+    #[case("([a, , ...b], { c = 1, d: [e] = [], ...f }) => a", 2, false)]
+    #[case("({ a = 1 } = {}) => a", 1, false)]
+    fn test_arrow_parameters(#[case] source: &str, #[case] count: usize, #[case] has_rest: bool) {
+        let arrow_function = parse_arrow_function(source);
+        assert_eq!(arrow_function.parameters.parameters.len(), count);
+        assert_eq!(arrow_function.parameters.rest.is_some(), has_rest);
+    }
+
+    #[test]
+    fn test_arrow_bodies() {
+        assert_matches!(parse_arrow_function("a => b => c").body, ConciseBody::Expression(body)
+            if matches!(*body, Expression::ArrowFunction(_)));
+        assert_matches!(parse_arrow_function("a => b = c").body, ConciseBody::Expression(body)
+            if matches!(*body, Expression::AssignmentExpression(_)));
+        assert_matches!(parse_arrow_function("a => ({})").body, ConciseBody::Expression(body)
+            if matches!(*body, Expression::ParenthesizedExpression(_)));
+        assert_matches!(parse_arrow_function("a => { b; }").body, ConciseBody::FunctionBody(body, _) if body.len() == 1);
+    }
+
+    #[test]
+    fn test_async_arrow_functions() {
+        assert_matches!(parse_expression("async a => a"), Expression::ArrowFunction(arrow_function)
+            if arrow_function.is_async && arrow_function.parameters.parameters.len() == 1);
+        assert_matches!(parse_expression("async (a, ...b) => a"), Expression::ArrowFunction(arrow_function)
+            if arrow_function.is_async && arrow_function.parameters.rest.is_some());
+        assert_matches!(parse_expression("async => async"), Expression::ArrowFunction(arrow_function)
+            if !arrow_function.is_async);
+        assert_matches!(parse_expression("async (a, ...b)"), Expression::CallExpression(call)
+            if call.arguments.len() == 2);
+        assert_matches!(parse_expression("async(a).b"), Expression::MemberExpression(_));
+        assert_matches!(parse_expression("async"), Expression::IdentifierReference(_));
+        assert_matches!(
+            &parse("async\na => a")[..],
+            [StatementListItem::Statement(_), StatementListItem::Statement(Statement::ExpressionStatement(
+                Expression::ArrowFunction(_)
+            , _))]
+        );
+    }
+
+    #[rstest]
+    #[case("(a)", "ParenthesizedExpression")]
+    #[case("(a, b)", "ParenthesizedExpression")]
+    #[case("(a = b)", "ParenthesizedExpression")]
+    #[case("(a) + b", "BinaryExpression")]
+    #[case("a => b, c", "CommaExpression")]
+    #[case("a = () => b", "AssignmentExpression")]
+    #[case("a ? b => c : d => e", "ConditionalExpression")]
+    #[case("f(a => b)", "CallExpression")]
+    #[case("({ a = 1 } = b)", "ParenthesizedExpression")]
+    #[case("[{ a = 1 }] = b", "AssignmentExpression")]
+    fn test_arrow_function_contexts(#[case] source: &str, #[case] kind: &str) {
+        let expression = format!("{:?}", parse_expression(source));
+        assert!(expression.starts_with(kind), "{expression}");
+    }
+
+    #[rstest]
+    #[case("function () {}", ErrorCode::UnexpectedInput, 9..10)]
+    #[case("function f(a {}", ErrorCode::UnexpectedInput, 13..14)]
+    #[case("function f(...a, b) {}", ErrorCode::UnexpectedInput, 15..16)]
+    #[case("function f() { return a b }", ErrorCode::UnexpectedInput, 24..25)]
+    #[case("function f() {", ErrorCode::IncompleteInput, 14..14)]
+    #[case("if (a) function* f() {}", ErrorCode::UnexpectedInput, 7..15)]
+    #[case("() => { return; }; return;", ErrorCode::UnexpectedInput, 19..25)]
+    #[case("a\n=> b;", ErrorCode::UnexpectedInput, 2..4)]
+    #[case("(a)\n=> b;", ErrorCode::UnexpectedInput, 4..6)]
+    #[case("(a + b) => c;", ErrorCode::UnexpectedInput, 0..7)]
+    #[case("(...a, b) => c;", ErrorCode::UnexpectedInput, 0..9)]
+    #[case("a || b => c;", ErrorCode::UnexpectedInput, 7..9)]
+    #[case("() => {} = a;", ErrorCode::UnexpectedInput, 9..10)]
+    #[case("();", ErrorCode::UnexpectedInput, 1..2)]
+    #[case("(a,);", ErrorCode::UnexpectedInput, 3..4)]
+    #[case("(...a);", ErrorCode::UnexpectedInput, 0..6)]
+    #[case("() => {} + 1;", ErrorCode::UnexpectedInput, 9..10)]
+    #[case("({ a = 1 });", ErrorCode::EarlyError, 3..8)]
+    #[case("f({ a = 1 });", ErrorCode::EarlyError, 4..9)]
+    #[case("({ a = 1 }).b = c;", ErrorCode::EarlyError, 3..8)]
+    #[case("([a.b]) => c;", ErrorCode::UnexpectedInput, 0..7)]
+    #[case("async (a)\n=> b;", ErrorCode::UnexpectedInput, 10..12)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    fn parse_deferred_function(source: &str) -> Function {
+        let mut body = Parser::new(source).with_deferred_function_bodies().parse_script().unwrap().body;
+        let StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) = body.swap_remove(0) else {
+            panic!("a function declaration is expected");
+        };
+        Rc::unwrap_or_clone(function)
+    }
+
+    #[test]
+    fn test_deferred_bodies() {
+        let source = "function* f(a) { if (a) { yield /}/; } g = () => { `${ { b: '}' } }`; }; } c;";
+        let function = parse_deferred_function(source);
+        assert!(function.body.is_empty());
+        assert_matches!(&function.deferred_body, Some(body) if body.range == (15..74));
+        assert_eq!(function.range, 0..74);
+
+        let mut parser = Parser::new(source).with_deferred_function_bodies();
+        let body = parser.parse_deferred_body(function.deferred_body.as_ref().unwrap()).unwrap();
+        let [_, StatementListItem::Statement(Statement::ExpressionStatement(Expression::AssignmentExpression(assignment), _))] =
+            &body[..]
+        else {
+            panic!("an assignment is expected");
+        };
+        assert_matches!(&*assignment.value, Expression::ArrowFunction(arrow)
+            if arrow.deferred_body.as_ref().is_some_and(|body| body.range == (49..71)));
+
+        let eager = parse(source);
+        let StatementListItem::Declaration(Declaration::FunctionDeclaration(eager)) = &eager[0] else {
+            panic!("a function declaration is expected");
+        };
+        assert_eq!(Parser::new(source).parse_deferred_body(function.deferred_body.as_ref().unwrap()), Ok(eager.body.clone()));
+    }
+
+    #[rstest]
+    #[case("function f() { return a b; }", ErrorCode::UnexpectedInput, 24..25)]
+    #[case("async function f() { await; }", ErrorCode::UnexpectedInput, 26..27)]
+    #[case("function* f() { var yield; }", ErrorCode::UnexpectedInput, 20..25)]
+    fn test_deferred_body_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let function = parse_deferred_function(source);
+        let error = Parser::new(source).parse_deferred_body(function.deferred_body.as_ref().unwrap()).unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[test]
+    fn test_deferred_body_context() {
+        let source = "a => { new.target; }";
+        let mut parser = Parser::new(source).with_deferred_function_bodies();
+        let Expression::ArrowFunction(arrow) = parser.parse_expression().unwrap() else {
+            panic!("an arrow function is expected");
+        };
+        assert!(Parser::new(source).parse_deferred_body(arrow.deferred_body.as_ref().unwrap()).is_err());
+
+        let source = "a => { super.a; }";
+        let Expression::ArrowFunction(arrow) = Parser::new(source).with_deferred_function_bodies().parse_expression().unwrap() else {
+            panic!("an arrow function is expected");
+        };
+        assert!(Parser::new(source).parse_deferred_body(arrow.deferred_body.as_ref().unwrap()).is_err());
+
+        let source = "{ m() { return () => { super.a; }; } }";
+        let Expression::ObjectLiteral(object) = Parser::new(source).with_deferred_function_bodies().parse_expression().unwrap() else {
+            panic!("an object literal is expected");
+        };
+        let [PropertyDefinition::MethodDefinition(method)] = &object.properties[..] else {
+            panic!("a method is expected");
+        };
+        assert_matches!(Parser::new(source).parse_deferred_body(method.deferred_body.as_ref().unwrap()), Ok(_));
+
+        let function = parse_deferred_function("async function f() { return () => { new.target; }; }");
+        assert!(function.deferred_body.is_some());
+
+        // Private names are checked against enclosing classes right away.
+        let script = Parser::new("class A { m() { return this.#a; } #a; }").with_deferred_function_bodies().parse_script();
+        assert_matches!(&script.unwrap().body[0], StatementListItem::Declaration(Declaration::ClassDeclaration(class))
+            if matches!(&class.elements[0], ClassElement::MethodDefinition { method, .. }
+                if method.deferred_body.is_none() && method.body.len() == 1));
+    }
+
+    #[rstest]
+    #[case("", false, false, 0, false)]
+    #[case("a, [b], c = 1,", false, false, 3, false)]
+    #[case("a, ...b", false, false, 1, true)]
+    #[case("a = yield", false, false, 1, false)]
+    #[case("a = await", true, false, 1, false)]
+    fn test_formal_parameter_fragments(
+        #[case] source: &str,
+        #[case] is_generator: bool,
+        #[case] is_async: bool,
+        #[case] count: usize,
+        #[case] has_rest: bool
+    ) {
+        let mut parser = Parser::new(source);
+        let parameters = parser.parse_formal_parameters(is_generator, is_async).unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        assert_eq!((parameters.parameters.len(), parameters.rest.is_some()), (count, has_rest));
+        assert_eq!(parameters.range.end, source.len());
+    }
+
+    #[rstest]
+    #[case("a = yield", true, false, ErrorCode::EarlyError, 4..9)]
+    #[case("a = await 1", false, true, ErrorCode::EarlyError, 4..11)]
+    #[case("[a], a", false, false, ErrorCode::EarlyError, 5..6)]
+    #[case("...a, b", false, false, ErrorCode::UnexpectedInput, 4..5)]
+    fn test_formal_parameter_fragment_errors(
+        #[case] source: &str,
+        #[case] is_generator: bool,
+        #[case] is_async: bool,
+        #[case] code: ErrorCode,
+        #[case] range: std::ops::Range<usize>
+    ) {
+        let mut parser = Parser::new(source);
+        let error = parser.parse_formal_parameters(is_generator, is_async).and_then(|_| parser.finish()).unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[test]
+    fn test_function_body_fragments() {
+        let mut parser = Parser::new("'use strict'; return new.target;");
+        let (body, has_use_strict) = parser.parse_function_body(false, false).unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        assert_eq!(body.len(), 2);
+        assert!(has_use_strict);
+
+        let (body, has_use_strict) = Parser::new("yield a; await b;").parse_function_body(true, true).unwrap();
+        assert_matches!(&body[0], StatementListItem::Statement(Statement::ExpressionStatement(
+            Expression::YieldExpression(_),
+            _
+        )));
+        assert!(!has_use_strict);
+
+        let error = Parser::new("'use strict'; with (a) {}").parse_function_body(false, false).unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, 14..18));
+        let error = Parser::new("let a; var a;").parse_function_body(false, false).unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, 11..12));
+        let mut parser = Parser::new("a; } b;");
+        assert_eq!(parser.parse_function_body(false, false).unwrap().0.len(), 1);
+        assert!(parser.finish().is_err());
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::grammar::{
+        ClassElement,
+        Declaration,
+        Expression,
+        Function,
+        MethodKind,
+        Parser,
+        PropertyDefinition,
+        Statement,
+        StatementListItem,
+    };
+    use embedded_ecmascript::runtime::{ErrorKind, Value};
+    use rstest::rstest;
+
+    fn parse(source: &str) -> Vec<StatementListItem> {
+        Parser::new(source).parse_script().unwrap().body
+    }
+
+    fn parse_function(source: &str) -> Function {
+        let body = parse(source);
+        let [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))] = &body[..] else {
+            panic!("a function declaration is expected");
+        };
+        (**function).clone()
+    }
+
+    fn parse_expression(source: &str) -> Expression {
+        let mut parser = Parser::new(source);
+        let expression = parser.parse_expression().unwrap();
+        assert_eq!(parser.finish(), Ok(()));
+        expression
+    }
+
+    /// Unwrap an expression of an expression statement.
+    fn expression(item: &StatementListItem) -> &Expression {
+        let StatementListItem::Statement(Statement::ExpressionStatement(expression, _)) = item else {
+            panic!("an expression statement is expected");
+        };
+        expression
+    }
+
+    #[rstest]
+    #[case("function f() {}", false, false)]
+    #[case("function* f() {}", true, false)]
+    #[case("async function f() {}", false, true)]
+    #[case("async function* f() {}", true, true)]
+    fn test_function_kinds(#[case] source: &str, #[case] is_generator: bool, #[case] is_async: bool) {
+        let function = parse_function(source);
+        assert_eq!((function.is_generator, function.is_async), (is_generator, is_async));
+    }
+
+    #[test]
+    fn test_yield_expressions() {
+        let function = parse_function("function* g() { yield; yield a, b; yield* c; yield\na; yield /d/g; }");
+        let yields: Vec<_> = function.body.iter().map(expression).collect();
+        assert_matches!(yields[0], Expression::YieldExpression(expression)
+            if !expression.is_delegate && expression.argument.is_none());
+        assert_matches!(yields[1], Expression::CommaExpression(_));
+        assert_matches!(yields[2], Expression::YieldExpression(expression)
+            if expression.is_delegate && expression.argument.is_some());
+        assert_matches!(yields[3], Expression::YieldExpression(expression) if expression.argument.is_none());
+        assert_matches!(yields[4], Expression::IdentifierReference(_));
+        assert_matches!(yields[5], Expression::YieldExpression(expression)
+            if matches!(expression.argument.as_deref(), Some(Expression::RegularExpressionLiteral(..))));
+    }
+
+    #[test]
+    fn test_await_expressions() {
+        let function = parse_function("async function f() { await a; await b === c; (await d) ** 2; }");
+        assert_matches!(expression(&function.body[0]), Expression::AwaitExpression(_));
+        assert_matches!(expression(&function.body[1]), Expression::BinaryExpression(_));
+
+        let function = parse_function("async function* f() { for await (const a of b) yield await a; }");
+        assert_matches!(&function.body[0], StatementListItem::Statement(Statement::ForOfStatement(statement))
+            if statement.is_await);
+    }
+
+    #[test]
+    fn test_contextual_identifiers() {
+        // Outside of generators and async functions `yield` and `await`
+        // are plain identifiers.
+        assert_matches!(parse_expression("yield = await"), Expression::AssignmentExpression(_));
+        assert_matches!(parse_expression("function yield(await) { await; }"), Expression::FunctionExpression(_));
+        // A nested function does not inherit the kind of an enclosing one.
+        parse_function("function* g() { function f(yield) {} }");
+        parse_function("async function f() { function g() { var await; } }");
+        // A declaration name belongs to the enclosing scope.
+        parse_function("function* yield() {}");
+        assert_matches!(&parse("async\nfunction f() {}")[..], [
+            StatementListItem::Statement(Statement::ExpressionStatement(Expression::IdentifierReference(_), _)),
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(_)),
+        ]);
+    }
+
+    #[test]
+    fn test_function_expressions() {
+        assert_matches!(parse_expression("function* () {}"), Expression::FunctionExpression(function)
+            if function.is_generator && !function.is_async);
+        assert_matches!(parse_expression("async function () {}"), Expression::FunctionExpression(function)
+            if !function.is_generator && function.is_async);
+        assert_matches!(parse_expression("async () => await a"), Expression::ArrowFunction(function)
+            if function.is_async);
+        assert_matches!(parse_expression("async a => { await a; }"), Expression::ArrowFunction(function)
+            if function.is_async);
+    }
+
+    #[test]
+    fn test_methods() {
+        let Expression::ObjectLiteral(object) =
+            parse_expression("{ *a() { yield; }, async b() { await c; }, async *d() {}, async: 1, async() {}, async }")
+        else {
+            panic!("an object literal is expected");
+        };
+        let kinds: Vec<_> = object.properties.iter().map(|property| match property {
+            PropertyDefinition::MethodDefinition(method) => Some(method.kind),
+            _ => None,
+        }).collect();
+        assert_eq!(kinds, [
+            Some(MethodKind::Generator),
+            Some(MethodKind::Async),
+            Some(MethodKind::AsyncGenerator),
+            None,
+            Some(MethodKind::Method),
+            None,
+        ]);
+
+        let body = parse("class A { static async *a() {} async\nb() {} *[c]() {} }");
+        let [StatementListItem::Declaration(Declaration::ClassDeclaration(class))] = &body[..] else {
+            panic!("a class declaration is expected");
+        };
+        assert_matches!(&class.elements[..], [
+            ClassElement::MethodDefinition { is_static: true, method: first, .. },
+            ClassElement::FieldDefinition { is_static: false, .. },
+            ClassElement::MethodDefinition { is_static: false, method: second, .. },
+            ClassElement::MethodDefinition { is_static: false, method: third, .. },
+        ] if first.kind == MethodKind::AsyncGenerator
+            && second.kind == MethodKind::Method
+            && third.kind == MethodKind::Generator);
+    }
+
+    #[rstest]
+    #[case("function* g() { var yield; }", ErrorCode::UnexpectedInput, 20..25)]
+    #[case("function* g(a = yield) {}", ErrorCode::EarlyError, 16..21)]
+    #[case("async function f(a = await b) {}", ErrorCode::EarlyError, 21..28)]
+    #[case("async function f() { var await; }", ErrorCode::UnexpectedInput, 25..30)]
+    #[case("(function* yield() {});", ErrorCode::UnexpectedInput, 11..16)]
+    #[case("function* g() { (a = yield) => a; }", ErrorCode::EarlyError, 21..26)]
+    #[case("async function f() { async (a = await b) => a; }", ErrorCode::EarlyError, 32..39)]
+    #[case("async await => 1;", ErrorCode::UnexpectedInput, 6..11)]
+    #[case("async function f() { await a ** 2; }", ErrorCode::UnexpectedInput, 21..28)]
+    #[case("function* g() { yield\n* a; }", ErrorCode::UnexpectedInput, 22..23)]
+    #[case("class A { static { await a; } }", ErrorCode::EarlyError, 19..26)]
+    #[case("for await (a of b);", ErrorCode::UnexpectedInput, 4..9)]
+    #[case("async function f() { for await (a in b); }", ErrorCode::UnexpectedInput, 25..30)]
+    #[case("a: function* g() {}", ErrorCode::UnexpectedInput, 3..11)]
+    #[case("if (a) async function f() {}", ErrorCode::UnexpectedInput, 7..12)]
+    #[case("class A { async constructor() {} }", ErrorCode::EarlyError, 10..32)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    /// Join values of an iterable with commas, as arrays have no `join`.
+    const JOIN: &str = "function join(values) { var s = '', first = true; for (var x of values) { s += (first ? '' : ',') + x; first = false; } return s; }";
+
+    #[rstest]
+    #[case("function* g() { yield 1; yield 2; } var i = g(); '' + i.next().value + i.next().value + i.next().done", "12true")]
+    #[case("function* g() { var x = yield 1; return x * 2; } var i = g(); i.next(); '' + i.next(21).value", "42")]
+    #[case("function* g(a) { yield a; } var r = g('x').next(); r.value + r.done", "xfalse")]
+    #[case("function* g() { return 'r'; } var r = g().next(); r.value + r.done", "rtrue")]
+    #[case("function* g() { yield 1; } var i = g(); i.next(); i.next(); var r = i.next(); '' + r.value + r.done", "undefinedtrue")]
+    #[case("var log = ''; function* g() { log += 's'; yield; } var i = g(); log += 'c'; i.next(); log", "cs")]
+    #[case("var o = { *g() { yield this.a; }, a: 'm' }; o.g().next().value", "m")]
+    #[case("var g = function* () { yield 1 + (yield 2); }; var i = g(); '' + i.next().value + i.next(3).value", "24")]
+    #[case("function* g() { for (let i = 0; i < 3; i++) yield i; } var s = ''; for (var x of g()) s += x; s", "012")]
+    #[case("function* g() { var i = 0; while (true) { if (i > 2) break; yield i++; } } var s = ''; for (var x of g()) s += x; s", "012")]
+    #[case("function* g() { var i = 0; do { yield i; } while (++i < 3); } join(g())", "0,1,2")]
+    #[case("function* g() { yield* [1, 2]; yield 3; } join(g())", "1,2,3")]
+    #[case("function* h() { yield 'a'; return 'b'; } function* g() { yield* h(); } var i = g(); i.next().value + i.next().done", "atrue")]
+    #[case("function* h() { yield 1; return 2; } function* g() { var r = yield* h(); yield r; } join(g())", "1,2")]
+    #[case("function* g() { for (var k in { a: 1, b: 2 }) yield k; } join(g())", "a,b")]
+    #[case("function* g() { for (const [a, b] of [[1, 2], [3, 4]]) yield a + b; } join(g())", "3,7")]
+    #[case(
+        "function* g() { switch (yield 0) { case 1: yield 'one'; break; default: yield 'other'; } } var i = g(); i.next(); i.next(1).value",
+        "one"
+    )]
+    #[case("function* g() { try { yield 1; } catch (e) { yield 'caught ' + e; } } var i = g(); i.next(); i.throw('x').value", "caught x")]
+    #[case(
+        "var log = ''; function* g() { try { yield 1; } finally { log += 'f'; } } var i = g(); i.next(); var r = i.return(5); log + r.value + r.done",
+        "f5true"
+    )]
+    #[case("function* g() { try { yield 1; } finally { yield 2; } } var i = g(); i.next(); '' + i.return(5).value + i.next().value", "25")]
+    #[case("function* g() { yield 1; } var i = g(); var r = i.return(7); '' + r.value + r.done + i.next().done", "7truetrue")]
+    #[case("function* g() { let a = yield; { let b = a + 1; yield () => a + b; } } var i = g(); i.next(); '' + i.next(1).value()", "3")]
+    #[case(
+        "function* g() { var f = []; for (let i = 0; i < 2; i++) { f[i] = () => i; yield; } yield f[0]() + f[1](); } '' + [...g()][2]",
+        "1"
+    )]
+    #[case(
+        "function* g() { var o = { a: yield, b: [yield, ...(yield)] }; return o.a + join(o.b); } var i = g(); i.next(); i.next(1); i.next(2); i.next([3, 4]).value",
+        "12,3,4"
+    )]
+    #[case("function* g() { return `${yield}-${yield}`; } var i = g(); i.next(); i.next('a'); i.next('b').value", "a-b")]
+    #[case(
+        "function* g() { outer: for (var x of [1, 2]) { for (var y of [3, 4]) { yield x + y; continue outer; } } } join(g())",
+        "4,5"
+    )]
+    #[case("var log = ''; function* g() { try { return 'r'; } finally { log += 'f'; } } g().next().value + log", "rf")]
+    #[case("function* g() { var x = 0; x += yield; x ||= yield; return x; } var i = g(); i.next(); i.next(0); '' + i.next(5).value", "5")]
+    #[case("function* g() { var o = {}; o[yield] = yield; return o.k; } var i = g(); i.next(); i.next('k'); i.next('v').value", "v")]
+    #[case("function* g() {} typeof g().next + typeof g.prototype + (g.prototype.constructor === g)", "functionobjectfalse")]
+    #[case("function* g() {} var i = g(); '' + (i instanceof g)", "true")]
+    #[case("function* g() { yield 1; } var i = g(); '' + (i.next === g().next) + typeof i.return + typeof i.throw", "truefunctionfunction")]
+    #[case("function* g() { var { a, b: [c, d = yield 'd'], ...r } = { a: 1, b: [2], e: 5, f: 6 }; var k = ''; for (var x in r) k += x; return '' + a + c + d + k; } var i = g(); i.next().value + i.next(4).value", "d124ef")]
+    #[case("function* g() { let [a, , b = yield, ...c] = [1, 2, undefined, 4, 5]; return '' + a + b + join(c); } var i = g(); i.next(); i.next(3).value", "134,5")]
+    #[case("function* g() { var a, o = {}; ({ a, [yield]: o.x = yield } = { k: undefined }); return o.x; } var i = g(); i.next(); i.next('k'); i.next('v').value", "v")]
+    #[case("function* g() { var o = {}; [o[yield], (yield).y] = [1, 2]; return o.k; } var o2 = {}; var i = g(); i.next(); i.next('k'); '' + i.next(o2).value + o2.y", "12")]
+    #[case("function* g() { for (const { a = yield } of [{}, { a: 2 }]) yield a; } join(g())", "undefined,undefined,2")]
+    #[case("function* g() { try { throw {}; } catch ({ a = yield 'c' }) { return a; } } var i = g(); i.next().value + i.next('x').value", "cx")]
+    #[case("var log = ''; function* it() { try { yield undefined; yield 2; } finally { log += 'r'; } } function* g() { var [a = yield] = it(); return a; } var i = g(); i.next(); i.return(5); log", "r")]
+    #[case("var log = ''; function* it() { try { yield undefined; } finally { log += 'r'; } } function* g() { try { var [a = yield] = it(); } catch (e) { return e + log; } } var i = g(); i.next(); i.throw('t').value", "tr")]
+    #[case("function* g() { var o = { x: 1 }; (yield o).x++; ++(yield o)[yield 'k']; return o.x; } var i = g(); var o = i.next().value; i.next(o); i.next(o); '' + i.next('x').value", "3")]
+    #[case("function* g() { var o = { x: 1 }; var r = delete (yield o).x; return '' + r + ('x' in o); } var i = g(); var o = i.next().value; i.next(o).value", "truefalse")]
+    #[case("function* g() { var o = null; return '' + (yield o)?.x.y + (yield o)?.[yield]; } var i = g(); i.next(); i.next(null); i.next({ k: 4 }); i.next('k').value", "undefined4")]
+    #[case("function* g() { class C { #x = 1; static get(o) { return o.#x; } static inc(o) { return ++o.#x; } } var c = new C(); return '' + C.inc(yield c) + C.get(yield c); } var i = g(); var c = i.next().value; i.next(c); i.next(c).value", "22")]
+    #[case("function* g() { class C { [yield 'k']() { return 'm'; } static [yield]= 2; } return C; } var i = g(); i.next(); i.next('m'); var C = i.next('s').value; new C().m() + C.s", "m2")]
+    #[case("function* g() { class C extends (yield) { [yield]() { return super.f() + 1; } } return new C(); } class B { f() { return 1; } } var i = g(); i.next(); i.next(B); '' + i.next('h').value.h()", "2")]
+    #[case("function* g() { let C = class { static [yield]() {} }; return C.name; } var i = g(); i.next(); i.next('k').value", "C")]
+    #[case("function* g() { class C { #x = 5; static f(o) { return (function* () { return (yield o).#x; })(); } } var i = C.f(new C()); var o = i.next().value; return i.next(o).value; } '' + g().next().value", "5")]
+    #[case("class B { get v() { return 'b'; } } class D extends B { *g() { return super[yield]; } } var i = new D().g(); i.next(); i.next('v').value", "b")]
+    #[case("var o = {}; function* g() { for ((yield).x of [1, 2]) ; } var i = g(); i.next(); i.next(o); i.next(o); '' + o.x", "2")]
+    #[case("function* g() { try { class C { [yield]() {} [(() => { throw 'e'; })()]() {} } } catch (e) { class D { #a = 1; static f(o) { return o.#a === 1; } } return '' + D.f(new D()); } } var i = g(); i.next(); i.next('k').value", "true")]
+    #[case("function* g() { try { class C extends 1 { [yield 'k']() {} } } catch (e) { return e instanceof TypeError; } } '' + g().next().value", "true")]
+    fn test_evaluation(#[case] source: &str, #[case] expected: &str) {
+        let mut engine = Engine::new();
+        engine.evaluate_script(JOIN).unwrap();
+        assert_eq!(engine.evaluate_script(source), Ok(Value::from(expected)), "{source}");
+    }
+
+    #[rstest]
+    #[case("function* g() { null.a; } g().next()", ErrorKind::TypeError)]
+    #[case("function* g() { yield 1; } var i = g(); i.throw(new RangeError())", ErrorKind::Thrown)]
+    #[case("function* g() { g.i.next(); yield; } g.i = g(); g.i.next()", ErrorKind::TypeError)]
+    #[case("function* g() {} new g()", ErrorKind::TypeError)]
+    #[case("function* g() {} var o = { next: g().next }; o.next()", ErrorKind::TypeError)]
+    #[case("function* g() { yield* 1; } g().next()", ErrorKind::TypeError)]
+    #[case("async function* g() {} g()", ErrorKind::Unsupported)]
+    fn test_evaluation_errors(#[case] source: &str, #[case] kind: ErrorKind) {
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == kind, "{source}");
+    }
+
+    #[test]
+    fn test_errors_after_completion() {
+        let mut engine = Engine::new();
+        engine.evaluate_script("function* g() { yield 1; throw 'x'; } var i = g(); i.next();").unwrap();
+        assert_matches!(engine.evaluate_script("i.next()"), Err(error) if error.kind == ErrorKind::Thrown);
+        assert_eq!(engine.evaluate_script("'' + i.next().done"), Ok(Value::from("true")));
+    }
+}
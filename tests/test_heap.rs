@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{ErrorKind, Object, PropertyKey, Value};
+    use rstest::rstest;
+
+    #[test]
+    fn test_cycles() {
+        let mut engine = Engine::new();
+        engine.collect_garbage();
+        let before = engine.heap().size();
+        let source = "for (var i = 0; i < 50; i++) { var a = {}; var b = { a: a }; a.b = b; } a = b = undefined;";
+        engine.evaluate_script(source).unwrap();
+        assert!(engine.heap().size() >= before + 100);
+        let statistics = engine.collect_garbage();
+        assert!(statistics.collected >= 100, "{statistics:?}");
+        assert!(engine.heap().size() <= before + 2, "{} > {before} + 2", engine.heap().size());
+    }
+
+    #[test]
+    fn test_closures() {
+        let mut engine = Engine::new();
+        let source = "function counter() { var n = 0; function next() { return ++n; } next.self = next; return next; }
+                      var kept = counter(); kept(); for (var i = 0; i < 20; i++) counter()();";
+        engine.evaluate_script(source).unwrap();
+        assert!(engine.collect_garbage().collected >= 40);
+        assert_eq!(engine.evaluate_script("kept() + kept.self()"), Ok(Value::Number(5.0)));
+        assert_eq!(engine.collect_garbage().collected, 0);
+    }
+
+    #[test]
+    fn test_generators() {
+        let mut engine = Engine::new();
+        let source = "function* g() { var self = yield; var list = [self]; yield list.length; }
+                      var kept = g(); kept.next(); kept.next(kept);
+                      for (var i = 0; i < 20; i++) { var it = g(); it.next(); it.next(it); } it = undefined;";
+        engine.evaluate_script(source).unwrap();
+        assert!(engine.collect_garbage().collected >= 40);
+        assert_eq!(engine.evaluate_script("kept.next().done"), Ok(Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_host_handles() {
+        let mut engine = Engine::new();
+        let object = engine.evaluate_script("var o = { v: 1 }; o.self = o; o").unwrap();
+        engine.evaluate_script("o = undefined;").unwrap();
+        let function = engine.evaluate_script("(function f() { return f; })").unwrap();
+        engine.collect_garbage();
+        let object = object.as_object().unwrap();
+        let receiver = Value::Object(object.clone());
+        assert_eq!(object.get(engine.context(), &PropertyKey::from("v"), &receiver), Ok(Value::Number(1.0)));
+        let result = function.as_object().unwrap().call(engine.context(), &Value::Undefined, &[]);
+        assert_eq!(result, Ok(function));
+    }
+
+    #[test]
+    fn test_rooted_handles() {
+        let mut engine = Engine::new();
+        let object = engine.evaluate_script("var o = { v: 1 }; o.self = o; o").unwrap();
+        let handle = engine.heap().root(object);
+        let scope = engine.heap().handle_scope();
+        scope.root(engine.evaluate_script("var a = [o]; a[1] = a; a").unwrap());
+        engine.evaluate_script("o = a = undefined;").unwrap();
+        assert_eq!(engine.collect_garbage().collected, 0);
+        let copy = handle.clone();
+        drop(handle);
+        assert_eq!(engine.collect_garbage().collected, 0);
+        drop(scope);
+        assert_eq!(engine.collect_garbage().collected, 1);
+        let receiver = Value::clone(&copy);
+        assert_eq!(copy.as_object().unwrap().get(engine.context(), &PropertyKey::from("v"), &receiver), Ok(Value::Number(1.0)));
+        drop((copy, receiver));
+        assert_eq!(engine.collect_garbage().collected, 1);
+    }
+
+    #[test]
+    fn test_separate_heaps() {
+        let mut engine = Engine::new();
+        let mut other = Engine::new();
+        let source = "for (var i = 0; i < 50; i++) { var a = {}; a.self = a; } a = undefined;";
+        engine.evaluate_script(source).unwrap();
+        other.evaluate_script(source).unwrap();
+        let shared = engine.evaluate_script("var s = {}; s.self = s; s").unwrap();
+        other.global_object().create_data_property("s", shared);
+        engine.evaluate_script("s = undefined;").unwrap();
+        assert_eq!(other.collect_garbage().collected, 50);
+        assert_eq!(engine.collect_garbage().collected, 50);
+        assert_eq!(other.evaluate_script("s.self === s"), Ok(Value::Boolean(true)));
+        assert_eq!(engine.heap().size(), engine.collect_garbage().live);
+    }
+
+    #[test]
+    fn test_native_functions() {
+        let mut engine = Engine::new();
+        let captured = Object::new(None);
+        let inner = captured.clone();
+        engine.register_function("get", move |_, _| Ok(Value::Object(inner.clone()))).unwrap();
+        captured.create_data_property("self", captured.clone());
+        drop(captured);
+        engine.collect_garbage();
+        assert_eq!(engine.evaluate_script("get().self === get()"), Ok(Value::Boolean(true)));
+    }
+
+    #[rstest]
+    #[case("while (true) list = { next: list };")]
+    #[case("do list = [list]; while (true);")]
+    #[case("for (let i = 0; ; i++) list = { next: list };")]
+    #[case("function grow(list) { return { next: list }; } for (;;) list = grow(list);")]
+    #[case("for (var x in { a: 1 }) for (;;) list = { next: list };")]
+    fn test_allocation_budget(#[case] source: &str) {
+        let mut engine = Engine::builder().allocation_budget(500).build();
+        engine.evaluate_script("var list = null;").unwrap();
+        let error = engine.evaluate_script(source).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MemoryLimit, "{error}");
+        assert_eq!(engine.evaluate_script("for (var i = 0; i < 10; i++) ({}); i"), Ok(Value::Number(10.0)));
+    }
+
+    #[rstest]
+    #[case("var s = 'ab'; for (var i = 0; i < 26; i++) s = s + s;")]
+    #[case("var s = '\\u{1F600}'; for (var i = 0; i < 26; i++) s = s + s;")]
+    #[case("var a = []; for (var i = 0; i < 100000; i++) a[i] = i;")]
+    #[case("var o = {}; for (var i = 0; i < 100000; i++) o['k' + i] = i;")]
+    fn test_byte_budget(#[case] source: &str) {
+        // Neither takes many objects.
+        let mut engine = Engine::builder().max_object_count(2000).allocation_budget(5000).byte_budget(1 << 20).build();
+        let error = engine.evaluate_script(source).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MemoryLimit, "{error}");
+        assert_eq!(engine.evaluate_script("var t = 'ab'; for (var i = 0; i < 10; i++) t = t + t; t.length"), Ok(Value::Number(2048.0)));
+    }
+
+    #[test]
+    fn test_byte_budget_of_code() {
+        /// Functions called `depth` times one in another, about 550 bytes
+        /// of source text each, which create almost nothing when run.
+        fn nested_calls(depth: usize) -> String {
+            let open = "(function () { if (0) { ".to_owned() + &"var a = [1, 2, 3], b = { c: a, d: 'e' + a.length }; ".repeat(10) + "} ";
+            let close = " return {}; })();";
+            format!("{}0;{}", open.repeat(depth), close.repeat(depth))
+        }
+
+        let limits = Engine::builder().byte_budget(1 << 20).allocation_budget(10_000).max_object_count(10_000);
+        let mut engine = limits.build();
+        let error = engine.evaluate_script(&nested_calls(200)).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MemoryLimit, "{error}");
+        let error = engine.evaluate_module("nested", &nested_calls(200)).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MemoryLimit, "{error}");
+        assert_eq!(engine.evaluate_script(&nested_calls(10)).map(|value| value.is_object()), Ok(true));
+    }
+
+    #[test]
+    fn test_caught_memory_limit() {
+        let mut engine = Engine::builder().allocation_budget(500).build();
+        let source = "var list = null;
+                      try { for (;;) list = { next: list }; } catch (error) { error instanceof RangeError && error.name }";
+        assert_eq!(engine.evaluate_script(source), Ok(Value::from("RangeError")));
+    }
+
+    #[test]
+    fn test_max_object_count() {
+        let mut other = Engine::new();
+        let mut engine = Engine::builder().max_object_count(other.heap().size() + 1000).build();
+        // Objects of other engines do not count.
+        other.evaluate_script("var list = null; for (var i = 0; i < 5000; i++) list = { next: list };").unwrap();
+        let garbage = "for (var i = 0; i < 5000; i++) { var a = {}; a.self = a; }";
+        assert!(engine.evaluate_script(garbage).is_ok());
+        let error = engine.evaluate_script("var list = null; for (;;) list = { next: list };").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MemoryLimit);
+        engine.evaluate_script("list = null;").unwrap();
+        assert_eq!(engine.evaluate_script("for (var i = 0; i < 5000; i++) ({}); i"), Ok(Value::Number(5000.0)));
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::grammar::Parser;
+    use embedded_ecmascript::interpreter::Interpreter;
+    use embedded_ecmascript::runtime::{ErrorKind, JsError, Object, Symbol, Value};
+    use rstest::rstest;
+
+    fn evaluate(interpreter: &mut Interpreter, source: &str) -> Result<Value, JsError> {
+        let expression = Parser::new(source).parse_expression().unwrap();
+        interpreter.evaluate(&expression)
+    }
+
+    #[rstest]
+    #[case("1 + 2 * 3", Value::Number(7.0))]
+    #[case("(1 + 2) * 3", Value::Number(9.0))]
+    #[case("2 ** 3 ** 2", Value::Number(512.0))]
+    #[case("-7 % 3", Value::Number(-1.0))]
+    #[case("1 / 0", Value::Number(f64::INFINITY))]
+    #[case("1 ** NaN", Value::Number(f64::NAN))]
+    #[case("(-1) ** -Infinity", Value::Number(f64::NAN))]
+    #[case("'' + 1e21 + 1e-7 + 0.000001", Value::from("1e+211e-70.000001"))]
+    #[case("2n ** 64n", Value::BigInt("18446744073709551616".parse().unwrap()))]
+    #[case("-7n / 2n + -7n % 2n", Value::BigInt((-4).into()))]
+    #[case("~5n & 255n | 1n << 8n", Value::BigInt(506.into()))]
+    #[case("-256n >> 4n", Value::BigInt((-16).into()))]
+    #[case("(a = 1n, a++, --a, a++, a)", Value::BigInt(2.into()))]
+    #[case("`${2n ** 70n}`", Value::from("1180591620717411303424"))]
+    #[case("1n < 1.5 && 2n > '1' && '0x10' <= 16n", Value::Boolean(true))]
+    #[case("1n == 1 && 1n == '1' && 1n != 1.5 && 0n == ''", Value::Boolean(true))]
+    #[case("1n == 'x' || 1n < 'x' || 1n >= 'x' || 1n < NaN", Value::Boolean(false))]
+    #[case("'a' + 1 + 2", Value::from("a12"))]
+    #[case("'\\uD83D' + '\\uDE00' === '😀'", Value::Boolean(true))]
+    #[case("'\\uD83D' < '\\uFFFD'", Value::Boolean(true))]
+    #[case("1 + 2 + 'a'", Value::from("3a"))]
+    #[case("'3' * '4'", Value::Number(12.0))]
+    #[case("' 12 ' - 2", Value::Number(10.0))]
+    #[case("'' - 1", Value::Number(-1.0))]
+    #[case("true + null", Value::Number(1.0))]
+    #[case("`a${1 + 1}b${null}`", Value::from("a2bnull"))]
+    #[case("1 << 31", Value::Number(-2_147_483_648.0))]
+    #[case("-1 >>> 28", Value::Number(15.0))]
+    #[case("-16 >> 2", Value::Number(-4.0))]
+    #[case("5 & 3 | 8 ^ 1", Value::Number(9.0))]
+    #[case("~0", Value::Number(-1.0))]
+    #[case("4294967297 | 0", Value::Number(1.0))]
+    #[case("1 < 2", Value::Boolean(true))]
+    #[case("'b' > 'a'", Value::Boolean(true))]
+    #[case("'10' < '9'", Value::Boolean(true))]
+    #[case("'10' < 9", Value::Boolean(false))]
+    #[case("NaN <= NaN", Value::Boolean(false))]
+    #[case("null >= 0", Value::Boolean(true))]
+    #[case("null == undefined", Value::Boolean(true))]
+    #[case("null == 0", Value::Boolean(false))]
+    #[case("'1' == 1", Value::Boolean(true))]
+    #[case("true == '1'", Value::Boolean(true))]
+    #[case("'1' === 1", Value::Boolean(false))]
+    #[case("NaN !== NaN", Value::Boolean(true))]
+    #[case("0 === -0", Value::Boolean(true))]
+    #[case("0 || 'a'", Value::from("a"))]
+    #[case("'' && a", Value::from(""))]
+    #[case("0 ?? a", Value::Number(0.0))]
+    #[case("!''", Value::Boolean(true))]
+    #[case("typeof null", Value::from("object"))]
+    #[case("typeof a", Value::from("undefined"))]
+    #[case("void 1", Value::Undefined)]
+    #[case("+'0x'", Value::Number(f64::NAN))]
+    #[case("1 ? 'a' : b", Value::from("a"))]
+    #[case("(1, 2, 3)", Value::Number(3.0))]
+    fn test_operators(#[case] source: &str, #[case] expected: Value) {
+        let result = evaluate(&mut Interpreter::new(), source);
+        match expected {
+            Value::Number(number) if number.is_nan() => {
+                assert_matches!(result, Ok(Value::Number(value)) if value.is_nan());
+            },
+            _ => assert_eq!(result, Ok(expected), "{source}"),
+        }
+    }
+
+    #[test]
+    fn test_variables() {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_variable("x", Value::Number(3.0));
+        assert_eq!(evaluate(&mut interpreter, "1 + 2 * x"), Ok(Value::Number(7.0)));
+        assert_eq!(evaluate(&mut interpreter, "x += 2"), Ok(Value::Number(5.0)));
+        assert_eq!(evaluate(&mut interpreter, "x++"), Ok(Value::Number(5.0)));
+        assert_eq!(evaluate(&mut interpreter, "--x"), Ok(Value::Number(5.0)));
+        assert_eq!(evaluate(&mut interpreter, "y = x * 2"), Ok(Value::Number(10.0)));
+        assert_eq!(interpreter.variable("y"), Some(Value::Number(10.0)));
+        assert_eq!(evaluate(&mut interpreter, "z ||= 'a'"), Err(JsError {
+            kind: ErrorKind::ReferenceError,
+            message: "z is not defined".to_owned(),
+            range: Some(0..1),
+            value: None,
+            stack: Vec::new(),
+        }));
+        assert_eq!(evaluate(&mut interpreter, "y &&= 0"), Ok(Value::Number(0.0)));
+        assert_eq!(evaluate(&mut interpreter, "y ??= 1"), Ok(Value::Number(0.0)));
+        assert_eq!(evaluate(&mut interpreter, "delete y"), Ok(Value::Boolean(true)));
+        assert_eq!(interpreter.variable("y"), None);
+
+        // Value properties of the global object cannot be changed.
+        assert_eq!(evaluate(&mut interpreter, "undefined = 1"), Ok(Value::Number(1.0)));
+        assert_eq!(evaluate(&mut interpreter, "undefined"), Ok(Value::Undefined));
+        assert_eq!(evaluate(&mut interpreter, "delete NaN"), Ok(Value::Boolean(false)));
+    }
+
+    #[test]
+    fn test_host_values() {
+        let mut interpreter = Interpreter::new();
+        let object = Object::new(None);
+        interpreter.set_variable("o", Value::Object(object.clone()));
+        interpreter.set_variable("p", Value::Object(object));
+        interpreter.set_variable("q", Value::Object(Object::new(None)));
+        interpreter.set_variable("s", Value::Symbol(Symbol::new(Some("s".to_owned()))));
+        assert_eq!(evaluate(&mut interpreter, "typeof s + typeof o + typeof 1n"), Ok(Value::from("symbolobjectbigint")));
+        assert_eq!(evaluate(&mut interpreter, "o === p && o != q && s == s"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate(&mut interpreter, "`${1n}` + !!s + !0n"), Ok(Value::from("1truetrue")));
+        assert_matches!(evaluate(&mut interpreter, "s + ''"), Err(error) if error.kind == ErrorKind::TypeError);
+        // An ordinary object without `valueOf` and `toString` has no primitive value.
+        assert_matches!(evaluate(&mut interpreter, "o + 1"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_eq!(evaluate(&mut interpreter, "'x' in o"), Ok(Value::Boolean(false)));
+    }
+
+    #[rstest]
+    #[case("a + 1", ErrorKind::ReferenceError, 0..1)]
+    #[case("1 + (2, a)", ErrorKind::ReferenceError, 8..9)]
+    #[case("'a' in 'b'", ErrorKind::TypeError, 0..10)]
+    #[case("1 instanceof 2", ErrorKind::TypeError, 0..14)]
+    #[case("a.b", ErrorKind::ReferenceError, 0..1)]
+    #[case("f(1)", ErrorKind::ReferenceError, 0..1)]
+    #[case("null.b", ErrorKind::TypeError, 0..6)]
+    #[case("(1)()", ErrorKind::TypeError, 0..5)]
+    #[case("new 1", ErrorKind::TypeError, 0..5)]
+    #[case("(class {})()", ErrorKind::TypeError, 0..12)]
+    #[case("1n + 1", ErrorKind::TypeError, 0..6)]
+    #[case("+1n", ErrorKind::TypeError, 0..3)]
+    #[case("(1n / 0n)", ErrorKind::RangeError, 1..8)]
+    #[case("2n ** -1n", ErrorKind::RangeError, 0..9)]
+    #[case("1n >>> 0n", ErrorKind::TypeError, 0..9)]
+    fn test_errors(#[case] source: &str, #[case] kind: ErrorKind, #[case] range: std::ops::Range<usize>) {
+        assert_matches!(evaluate(&mut Interpreter::new(), source), Err(error) if error.kind == kind && error.range == Some(range));
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::grammar::{validate, ExportDeclaration, ImportName, ModuleItem, Parser, SourceType, ValidationOptions};
+    use embedded_ecmascript::runtime::{ErrorKind, JsError, Object, PropertyKey, Value};
+    use embedded_ecmascript::ErrorCode;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parsing() {
+        let source = "import a, * as b from 'm'; import { c, 'd' as e } from 'n'; export { a as default, c }; export * from 'o';";
+        let module = Parser::new_module(source).parse_module().unwrap();
+        assert_eq!(module.module_requests(), ["m", "n", "o"]);
+        let imports: Vec<_> = module.import_entries().into_iter().map(|entry| (entry.import_name, entry.local_name)).collect();
+        assert_eq!(imports, [
+            (ImportName::Name("default".to_owned()), "a".to_owned()),
+            (ImportName::NamespaceObject, "b".to_owned()),
+            (ImportName::Name("c".to_owned()), "c".to_owned()),
+            (ImportName::Name("d".to_owned()), "e".to_owned()),
+        ]);
+        let exports: Vec<_> = module.export_entries().into_iter().map(|entry| entry.export_name).collect();
+        assert_eq!(exports, [Some("default".to_owned()), Some("c".to_owned()), None]);
+        assert_matches!(&module.body[3], ModuleItem::ExportDeclaration(ExportDeclaration::ExportAll { exported: None, .. }));
+    }
+
+    #[rstest]
+    #[case("export default function () {}")]
+    #[case("export default class {}")]
+    #[case("export default async function f() {}")]
+    #[case("export default 1 + 2;")]
+    #[case("export var a = 1, b;")]
+    #[case("export { a }; let a;")]
+    #[case("export * as 'a b' from 'm';")]
+    #[case("import 'm'; import.meta;")]
+    #[case("export { default } from 'm';")]
+    fn test_valid(#[case] source: &str) {
+        assert_matches!(Parser::new_module(source).parse_module(), Ok(_), "{source}");
+    }
+
+    #[rstest]
+    #[case("import a from 'm'; let a;", 23..24)]
+    #[case("import { a, b as a } from 'm';", 17..18)]
+    #[case("export let a; export { a };", 23..24)]
+    #[case("export default 1; export default 2;", 18..35)]
+    #[case("export { a };", 9..10)]
+    #[case("export { 'a' }; var a;", 9..12)]
+    #[case("export { if };", 9..11)]
+    #[case("export 1;", 7..8)]
+    #[case("import { if } from 'm';", 9..11)]
+    #[case("export { '\\uD800' as a } from 'm';", 9..17)]
+    #[case("function f() { import a from 'm'; }", 22..23)]
+    fn test_early_errors(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new_module(source).parse_module().unwrap_err();
+        assert_eq!(error.range, range, "{source}");
+        assert_matches!(error.code, ErrorCode::EarlyError | ErrorCode::UnexpectedInput);
+    }
+
+    #[test]
+    fn test_validation() {
+        let options = ValidationOptions { source_type: SourceType::Module, ..ValidationOptions::default() };
+        assert!(validate("export { a }; let a;", options).is_ok());
+        assert_eq!(validate("export { a };", options).unwrap_err().len(), 1);
+    }
+
+    /// An engine loading modules from a map of sources.
+    fn engine(modules: &[(&str, &str)]) -> Engine {
+        let sources: HashMap<String, String> =
+            modules.iter().map(|(specifier, source)| ((*specifier).to_owned(), (*source).to_owned())).collect();
+        let mut engine = Engine::new();
+        engine.set_module_loader(move |specifier, _| {
+            sources.get(specifier).cloned().ok_or_else(|| JsError::type_error(format!("unknown module {specifier}")))
+        });
+        engine
+    }
+
+    fn get(engine: &mut Engine, namespace: &Object, name: &str) -> Result<Value, JsError> {
+        namespace.get(engine.context(), &PropertyKey::from(name), &Value::Object(namespace.clone()))
+    }
+
+    #[rstest]
+    #[case(&[("a", "export const x = 1;")], "import { x } from 'a'; export const r = x + 1;", Value::Number(2.0))]
+    #[case(&[("a", "export default function () { return 3; }")], "import f from 'a'; export const r = f() + f.name;", Value::from("3default"))]
+    #[case(&[("a", "export default 2 * 3;")], "import d from 'a'; export const r = d;", Value::Number(6.0))]
+    #[case(&[("a", "export let n = 0; export function inc() { n++; }")], "import { n, inc } from 'a'; inc(); inc(); export const r = n;", Value::Number(2.0))]
+    #[case(&[("a", "export * from 'b'; export const y = 2;"), ("b", "export const x = 1; export default 0;")], "import * as ns from 'a'; export const r = ns.x + ns.y + typeof ns.default;", Value::from("3undefined"))]
+    #[case(&[("a", "export * as b from 'b';"), ("b", "export const x = 4;")], "import { b } from 'a'; export const r = b.x;", Value::Number(4.0))]
+    #[case(&[("a", "import { b } from 'b'; export const a = 1; export function f() { return b(); }"), ("b", "import { a } from 'a'; export const b = () => a + 1;")], "import { f } from 'a'; export const r = f();", Value::Number(2.0))]
+    #[case(&[("a", "import { b } from 'main'; export function f() { return b; }")], "import { f } from 'a'; export const b = 5; export const r = f();", Value::Number(5.0))]
+    #[case(&[("a", "export { x as y } from 'b';"), ("b", "export var x = 7;")], "import { y } from 'a'; export const r = y;", Value::Number(7.0))]
+    #[case(&[("a", "globalThis.order = (globalThis.order || '') + 'a';"), ("b", "import 'a'; globalThis.order += 'b';")], "import 'b'; import 'a'; export const r = globalThis.order;", Value::from("ab"))]
+    #[case(&[], "export const r = this;", Value::Undefined)]
+    #[case(&[("a", "export default class { m() { return 8; } }")], "import C from 'a'; export const r = new C().m() + C.name;", Value::from("8default"))]
+    fn test_evaluation(#[case] modules: &[(&str, &str)], #[case] source: &str, #[case] expected: Value) {
+        let mut engine = engine(modules);
+        let namespace = engine.evaluate_module("main", source).unwrap();
+        assert_eq!(get(&mut engine, &namespace, "r"), Ok(expected), "{source}");
+    }
+
+    #[rstest]
+    #[case(&[], "import a from 'missing';", ErrorKind::TypeError)]
+    #[case(&[("a", "export const x = 1;")], "import { y } from 'a';", ErrorKind::SyntaxError)]
+    #[case(&[("a", "export * from 'b'; export * from 'c';"), ("b", "export const x = 1;"), ("c", "export const x = 2;")], "import { x } from 'a';", ErrorKind::SyntaxError)]
+    #[case(&[("a", "export const x = ;")], "import 'a';", ErrorKind::SyntaxError)]
+    #[case(&[("a", "throw 1;")], "import 'a';", ErrorKind::Thrown)]
+    #[case(&[("a", "export const x = 1;")], "import { x } from 'a'; x = 2;", ErrorKind::TypeError)]
+    #[case(&[("a", "import { b } from 'main'; export const a = b;")], "import 'a'; export const b = 1;", ErrorKind::ReferenceError)]
+    #[case(&[], "export default /a/;", ErrorKind::Unsupported)]
+    fn test_errors(#[case] modules: &[(&str, &str)], #[case] source: &str, #[case] kind: ErrorKind) {
+        let result = engine(modules).evaluate_module("main", source);
+        assert_matches!(result, Err(error) if error.kind == kind, "{source}");
+    }
+
+    #[test]
+    fn test_namespace_object() {
+        let mut engine = engine(&[("a", "export let b = 1, a = 2; export function setB(v) { b = v; }")]);
+        let namespace = engine.evaluate_module("main", "export * from 'a';").unwrap();
+        let keys: Vec<_> = namespace.own_property_keys().into_iter().take(3).collect();
+        assert_eq!(keys, [PropertyKey::from("a"), PropertyKey::from("b"), PropertyKey::from("setB")]);
+        assert_eq!(namespace.get_prototype_of(), None);
+        assert!(!namespace.is_extensible());
+        let receiver = Value::Object(namespace.clone());
+        assert_eq!(namespace.set(engine.context(), PropertyKey::from("b"), Value::Number(3.0), &receiver), Ok(false));
+        assert!(!namespace.delete(&PropertyKey::from("b")));
+        assert!(namespace.delete(&PropertyKey::from("c")));
+        let set_b = get(&mut engine, &namespace, "setB").unwrap();
+        set_b.as_object().unwrap().call(engine.context(), &Value::Undefined, &[Value::Number(4.0)]).unwrap();
+        assert_eq!(get(&mut engine, &namespace, "b"), Ok(Value::Number(4.0)));
+    }
+
+    #[test]
+    fn test_module_map() {
+        let mut engine = engine(&[("a", "globalThis.count = (globalThis.count || 0) + 1; export const x = 1;")]);
+        engine.evaluate_module("main", "import 'a';").unwrap();
+        engine.evaluate_module("other", "import { x } from 'a';").unwrap();
+        assert_eq!(engine.evaluate_script("count"), Ok(Value::Number(1.0)));
+        assert_matches!(engine.evaluate_module("main", ""), Err(error) if error.kind == ErrorKind::TypeError);
+    }
+
+    #[test]
+    fn test_evaluation_error() {
+        // A module that failed to evaluate keeps failing.
+        let mut engine = engine(&[("bad", "throw 1;")]);
+        assert_matches!(engine.evaluate_module("first", "import 'bad';"), Err(error) if error.kind == ErrorKind::Thrown);
+        assert_matches!(engine.evaluate_module("second", "import 'bad';"), Err(error) if error.kind == ErrorKind::Thrown);
+        assert_matches!(engine.evaluate_module("first", ""), Err(error) if error.kind == ErrorKind::Thrown);
+    }
+
+    #[test]
+    fn test_link_error() {
+        // Modules that failed to link are dropped, so they load again.
+        let mut engine = engine(&[("a", "export const x = 1;"), ("b", "import { y } from 'a'; export const z = 2;")]);
+        let source = "import { z } from 'b'; export const r = z;";
+        assert_matches!(engine.evaluate_module("main", source), Err(error) if error.kind == ErrorKind::SyntaxError);
+        assert_matches!(engine.evaluate_module("main", source), Err(error) if error.kind == ErrorKind::SyntaxError);
+        let namespace = engine.evaluate_module("main", "import { x } from 'a'; export const r = x;").unwrap();
+        assert_eq!(get(&mut engine, &namespace, "r"), Ok(Value::Number(1.0)));
+    }
+}
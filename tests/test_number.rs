@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::runtime::Number;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1.0, f64::NAN, f64::NAN)]
+    #[case(-1.0, f64::INFINITY, f64::NAN)]
+    #[case(1.0, f64::NEG_INFINITY, f64::NAN)]
+    #[case(f64::NAN, 0.0, 1.0)]
+    #[case(f64::NAN, -0.0, 1.0)]
+    #[case(0.5, f64::INFINITY, 0.0)]
+    #[case(-0.0, -1.0, f64::NEG_INFINITY)]
+    #[case(-0.0, 3.0, -0.0)]
+    #[case(-8.0, 1.0 / 3.0, f64::NAN)]
+    #[case(2.0, -1074.0, 5e-324)]
+    fn test_exponentiate(#[case] base: f64, #[case] exponent: f64, #[case] expected: f64) {
+        assert!(Number::same_value(Number::exponentiate(base, exponent), expected));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert!(Number::same_value(Number::remainder(-1.0, 2.0), -1.0));
+        assert!(Number::same_value(Number::remainder(-0.0, 2.0), -0.0));
+        assert!(Number::same_value(Number::remainder(5.5, f64::INFINITY), 5.5));
+        assert!(Number::remainder(f64::INFINITY, 2.0).is_nan());
+        assert!(Number::same_value(Number::add(-0.0, -0.0), -0.0));
+        assert!(Number::same_value(Number::subtract(0.0, 0.0), 0.0));
+        assert!(Number::same_value(Number::unary_minus(0.0), -0.0));
+        assert!(Number::same_value(Number::divide(-1.0, 0.0), f64::NEG_INFINITY));
+        assert!(Number::same_value(Number::multiply(-0.0, 5.0), -0.0));
+        assert_eq!(Number::less_than(f64::NAN, 1.0), None);
+        assert_eq!(Number::less_than(-0.0, 0.0), Some(false));
+        assert!(Number::equal(-0.0, 0.0));
+        assert!(!Number::same_value(-0.0, 0.0));
+        assert!(Number::same_value_zero(-0.0, 0.0));
+        assert!(Number::same_value_zero(f64::NAN, f64::NAN));
+        assert!(!Number::equal(f64::NAN, f64::NAN));
+    }
+
+    #[rstest]
+    #[case(0.0, 0, 0)]
+    #[case(-0.5, 0, 0)]
+    #[case(f64::NAN, 0, 0)]
+    #[case(f64::NEG_INFINITY, 0, 0)]
+    #[case(-1.0, -1, 4_294_967_295)]
+    #[case(2_147_483_648.0, -2_147_483_648, 2_147_483_648)]
+    #[case(4_294_967_296.5, 0, 0)]
+    #[case(-4_294_967_297.9, -1, 4_294_967_295)]
+    #[case(1e20, 1_661_992_960, 1_661_992_960)]
+    fn test_integer_conversions(#[case] number: f64, #[case] int32: i32, #[case] uint32: u32) {
+        assert_eq!(Number::to_int32(number), int32);
+        assert_eq!(Number::to_uint32(number), uint32);
+    }
+
+    #[test]
+    fn test_bitwise() {
+        assert!(Number::same_value(Number::left_shift(1.0, 32.0), 1.0));
+        assert!(Number::same_value(Number::left_shift(1.0, 31.0), -2_147_483_648.0));
+        assert!(Number::same_value(Number::signed_right_shift(-8.0, 33.0), -4.0));
+        assert!(Number::same_value(Number::unsigned_right_shift(-1.0, 0.0), 4_294_967_295.0));
+        assert!(Number::same_value(Number::unsigned_right_shift(-0.0, 0.0), 0.0));
+        assert!(Number::same_value(Number::bitwise_not(-0.0), -1.0));
+        assert!(Number::same_value(Number::bitwise_and(f64::NAN, -1.0), 0.0));
+        assert!(Number::same_value(Number::bitwise_or(2_147_483_648.0, 0.0), -2_147_483_648.0));
+        assert!(Number::same_value(Number::bitwise_xor(5.0, 3.0), 6.0));
+    }
+
+    #[rstest]
+    #[case(0.0, "0")]
+    #[case(-0.0, "0")]
+    #[case(f64::NAN, "NaN")]
+    #[case(f64::NEG_INFINITY, "-Infinity")]
+    #[case(100.0, "100")]
+    #[case(-1.5, "-1.5")]
+    #[case(0.1 + 0.2, "0.30000000000000004")]
+    #[case(123_456_789_012_345_680_000.0, "123456789012345680000")]
+    #[case(1e21, "1e+21")]
+    #[case(1.5e300, "1.5e+300")]
+    #[case(0.000_001, "0.000001")]
+    #[case(0.000_001_5, "0.0000015")]
+    #[case(1e-7, "1e-7")]
+    #[case(1.23e-18, "1.23e-18")]
+    #[case(5e-324, "5e-324")]
+    #[case(f64::MAX, "1.7976931348623157e+308")]
+    #[case(9_007_199_254_740_993.0, "9007199254740992")]
+    fn test_decimal_to_string(#[case] number: f64, #[case] expected: &str) {
+        assert_eq!(Number::to_string(number, 10), expected);
+    }
+
+    #[rstest]
+    #[case(255.0, 16, "ff")]
+    #[case(-255.5, 16, "-ff.8")]
+    #[case(35.0, 36, "z")]
+    #[case(0.5, 36, "0.i")]
+    #[case(0.5, 2, "0.1")]
+    #[case(0.1, 2, "0.0001100110011001100110011001100110011001100110011001101")]
+    #[case(9_007_199_254_740_992.0, 2, "100000000000000000000000000000000000000000000000000000")]
+    #[case(f64::INFINITY, 2, "Infinity")]
+    #[case(-0.0, 8, "0")]
+    fn test_radix_to_string(#[case] number: f64, #[case] radix: u32, #[case] expected: &str) {
+        assert_eq!(Number::to_string(number, radix), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "radix 37 is out of the 2..=36 range")]
+    fn test_invalid_radix() {
+        let _ = Number::to_string(1.0, 37);
+    }
+}
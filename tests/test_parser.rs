@@ -1,10 +1,223 @@
 #[cfg(test)]
 mod tests {
-    use embedded_ecmascript::grammar::parse;
+    use claims::assert_matches;
+    use embedded_ecmascript::{Diagnostics, ErrorCode, Severity};
+    use embedded_ecmascript::grammar::{
+        detect_source_type,
+        parse,
+        parse_collecting_errors,
+        parse_with_diagnostics,
+        Parser,
+        ParserOptions,
+        SourceType,
+        TriviaAttachment,
+        TriviaPolicy,
+        DEFAULT_MAX_NESTING_DEPTH,
+    };
+    use embedded_ecmascript::lexical_grammar::{tokenize_with_diagnostics, GoalSymbols, TokenKind};
     use rstest::rstest;
 
     #[rstest]
     fn test_simple_statements(#[values(false, true)] is_module: bool) {
         assert_eq!(parse(";", is_module), Ok(()));
     }
+
+    #[rstest]
+    fn test_parse_errors(#[values(false, true)] is_module: bool) {
+        assert_eq!(parse("x;", is_module), Ok(()));
+        assert_matches!(
+            parse("x y;", is_module),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == (2..3)
+        );
+    }
+
+    #[rstest]
+    fn test_multiple_errors(#[values(false, true)] is_module: bool) {
+        assert_eq!(parse(";;", is_module), Ok(()));
+        assert_eq!(parse_collecting_errors(";", is_module), Ok(()));
+
+        assert_eq!(parse_collecting_errors("a;bc;;д", is_module), Ok(()));
+
+        let errors = parse_collecting_errors("a b; c; d e; f;", is_module).unwrap_err();
+        let ranges: Vec<_> = errors.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [2..3, 10..11]);
+        assert!(errors.iter().all(|error| error.code == ErrorCode::UnexpectedInput));
+        assert_eq!((errors[1].line, errors[1].column), (1, 11));
+    }
+
+    #[rstest]
+    fn test_tokens(#[values(false, true)] is_module: bool) {
+        assert_eq!(parse(" ;\n// a\n/* b */;", is_module), Ok(()));
+
+        let errors = parse_collecting_errors("a b; 'c;' + /;/ d", is_module).unwrap_err();
+        let ranges: Vec<_> = errors.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [2..3, 16..17]);
+    }
+
+    #[rstest]
+    fn test_shared_diagnostics(#[values(false, true)] is_module: bool) {
+        let source = "a;@;";
+        let mut diagnostics = Diagnostics::new();
+        tokenize_with_diagnostics(source, GoalSymbols::InputElementHashbangOrRegExp, &mut diagnostics);
+        parse_with_diagnostics(source, is_module, &mut diagnostics);
+        let ranges: Vec<_> = diagnostics.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [2..3, 2..3]);
+        assert!(diagnostics.has_errors());
+
+        let mut diagnostics = Diagnostics::new();
+        parse_with_diagnostics(";", is_module, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[rstest]
+    #[case("with (a) b;", 0..4)]
+    #[case("a = 010;", 4..7)]
+    #[case("a = '\\01';", 5..8)]
+    fn test_deprecation_warnings(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let mut diagnostics = Diagnostics::new();
+        parse_with_diagnostics(source, false, &mut diagnostics);
+        assert_matches!(
+            diagnostics.iter().collect::<Vec<_>>().as_slice(),
+            [warning] if warning.range == range
+                && warning.severity == Severity::Warning
+                && warning.code == ErrorCode::DeprecatedSyntax
+        );
+        let mut parser = Parser::new(source);
+        parser.parse_script().unwrap();
+        assert_eq!(parser.warnings().len(), 1);
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[rstest]
+    #[case("a; <!-- b c", 3..11)]
+    #[case("a;\n  --> b c", 5..12)]
+    #[case("--> a\nb;", 0..5)]
+    fn test_html_like_comments(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let mut diagnostics = Diagnostics::new();
+        parse_with_diagnostics(source, false, &mut diagnostics);
+        assert_matches!(
+            diagnostics.iter().collect::<Vec<_>>().as_slice(),
+            [warning] if warning.range == range && warning.severity == Severity::Warning
+        );
+        // Modules do not have HTML-like comments.
+        assert!(parse(source, true).is_err());
+    }
+
+    #[rstest]
+    fn test_hashbang(#[values(false, true)] is_module: bool) {
+        assert_eq!(parse("#!/usr/bin/env node\n;", is_module), Ok(()));
+        assert_eq!(parse("#!", is_module), Ok(()));
+        assert!(parse(" #!a\n;", is_module).is_err());
+        assert!(parse(";\n#!a", is_module).is_err());
+
+        let new = if is_module { Parser::new_module } else { Parser::new };
+        let script = new("#!/usr/bin/env node\n/a/.test(b)\nc").parse_script().unwrap();
+        assert_eq!(script.body.len(), 2);
+        assert_eq!(script.body[0].range(), 20..31);
+        assert!(new("#!").parse_script().unwrap().body.is_empty());
+        assert!(new(" #!a\n;").parse_script().is_err());
+    }
+
+    #[rstest]
+    #[case("import a from 'b';", SourceType::Module)]
+    #[case("import 'a';", SourceType::Module)]
+    #[case("export default 1;", SourceType::Module)]
+    #[case("a; /* b */ export { a };", SourceType::Module)]
+    #[case("console.log(import.meta.url);", SourceType::Script)]
+    #[case("import.meta;", SourceType::Module)]
+    #[case("import('a');", SourceType::Script)]
+    #[case("a.import(b); a?.export;", SourceType::Script)]
+    #[case("({ import: 1, export() {} });", SourceType::Script)]
+    #[case("`${ { export: 1 } }`; export", SourceType::Module)]
+    #[case("'export'; // import a from 'b'", SourceType::Script)]
+    #[case("", SourceType::Script)]
+    fn test_source_type_detection(#[case] source: &str, #[case] expected: SourceType) {
+        assert_eq!(detect_source_type(source, SourceType::Auto), expected);
+        assert_eq!(SourceType::Auto.is_module(source), expected == SourceType::Module);
+    }
+
+    #[rstest]
+    #[case("export {};", [false, true, true])]
+    #[case("import 'a';", [false, true, true])]
+    #[case("with (a) {}", [true, false, true])]
+    #[case("var let = 1;", [true, false, true])]
+    #[case("export {}; with (a) {}", [false, false, false])]
+    #[case("import('a'); with (a) {}", [true, false, true])]
+    fn test_source_types(#[case] source: &str, #[case] expected: [bool; 3]) {
+        let source_types = [SourceType::Script, SourceType::Module, SourceType::Auto];
+        for (source_type, expected) in source_types.into_iter().zip(expected) {
+            assert_eq!(parse(source, source_type).is_ok(), expected, "{source_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_source_type_defaults() {
+        assert_eq!(detect_source_type("a;", SourceType::Module), SourceType::Module);
+        assert_eq!(detect_source_type("export {};", SourceType::Script), SourceType::Module);
+        assert!(!SourceType::Script.is_module("export {};"));
+        assert_eq!(SourceType::from(true), SourceType::Module);
+        assert_eq!(parse(";", SourceType::Auto), Ok(()));
+    }
+
+    #[rstest]
+    #[case("[", "]")]
+    #[case("(", ")")]
+    #[case("{", "}")]
+    #[case("!", "")]
+    #[case("a = ", "")]
+    #[case("x => ", "")]
+    #[case("if (a) ", "")]
+    #[case("[", "] = a")]
+    #[case("[{ a: ", "}] = a")]
+    #[case("function f() {", "}")]
+    #[case("class A { static {", "} }")]
+    fn test_nesting_depth(#[case] open: &'static str, #[case] close: &'static str) {
+        // The depth must not depend on the native stack of the host thread,
+        // so parse on a small one.
+        let thread = std::thread::Builder::new().stack_size(2 << 20).spawn(move || {
+            let nested = |depth| format!("{}x{}", open.repeat(depth), close.repeat(depth));
+            assert_matches!(Parser::new(&nested(DEFAULT_MAX_NESTING_DEPTH / 4)).parse_script(), Ok(_));
+            assert_matches!(
+                Parser::new(&nested(10000)).parse_script(),
+                Err(error) if error.code == ErrorCode::NestingTooDeep
+            );
+            let options = ParserOptions { max_nesting_depth: 10, ..ParserOptions::default() };
+            assert_matches!(
+                Parser::new(&nested(11)).with_options(options).parse_script(),
+                Err(error) if error.code == ErrorCode::NestingTooDeep
+            );
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    #[rstest]
+    #[case(TriviaPolicy::ByLine, "let a; // end\n/* own */ b;", &["// end trails ;", "/* own */ leads b"])]
+    #[case(TriviaPolicy::Leading, "let a; // end\n/* own */ b;", &["// end leads b", "/* own */ leads b"])]
+    #[case(TriviaPolicy::Trailing, "let a; // end\n/* own */ b;", &["// end trails ;", "/* own */ trails ;"])]
+    #[case(TriviaPolicy::ByLine, "#!node\n// a\nx // b\n// c\n", &["#!node leads x", "// a leads x", "// b trails x", "// c trails x"])]
+    #[case(TriviaPolicy::ByLine, "a = b / c /* d */ / e", &["/* d */ trails c"])]
+    #[case(TriviaPolicy::ByLine, "a = /* r */ /r/ // t", &["/* r */ trails =", "// t trails /"])]
+    #[case(TriviaPolicy::Leading, "f(a /* x */, b)", &["/* x */ leads ,"])]
+    #[case(TriviaPolicy::Trailing, "// only", &["// only leads end of input"])]
+    fn test_trivia(#[case] policy: TriviaPolicy, #[case] source: &str, #[case] expected: &[&str]) {
+        let options = ParserOptions { trivia: Some(policy), ..ParserOptions::default() };
+        let mut parser = Parser::new(source).with_options(options);
+        parser.parse_script().unwrap();
+        let trivia = parser.trivia();
+        // Pieces of trivia come in source order.
+        assert!(trivia.iter().all(|trivia| trivia.kind.is_trivia()));
+        assert!(trivia.windows(2).all(|pair| pair[0].range.end <= pair[1].range.start));
+        let comments: Vec<String> = trivia
+            .iter()
+            .filter(|trivia| !matches!(trivia.kind, TokenKind::WhiteSpace | TokenKind::LineTerminator))
+            .map(|trivia| {
+                let token = match trivia.attachment {
+                    TriviaAttachment::Leading(start) => format!("leads {}", source.get(start..=start).unwrap_or("end of input")),
+                    TriviaAttachment::Trailing(end) => format!("trails {}", &source[end - 1..end]),
+                };
+                format!("{} {token}", &source[trivia.range.clone()])
+            })
+            .collect();
+        assert_eq!(comments, expected);
+    }
 }
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{ErrorKind, Object, ObjectKind, PromiseState, PropertyKey, Symbol, Value, WellKnownSymbol};
+    use rstest::rstest;
+
+    /// Run a script and its jobs, getting the value of `log` afterwards.
+    fn log_after_jobs(source: &str) -> Value {
+        let mut engine = Engine::new();
+        engine.evaluate_script("var log = '';").unwrap();
+        engine.evaluate_script(source).unwrap();
+        engine.run_jobs().unwrap();
+        engine.evaluate_script("log").unwrap()
+    }
+
+    #[rstest]
+    #[case("Promise.resolve(1).then(function (x) { log += x; }); log += 0;", "01")]
+    #[case("new Promise(function (resolve) { log += 'a'; resolve('c'); }).then(function (x) { log += x; }); log += 'b';", "abc")]
+    #[case(
+        "var p = Promise.resolve();
+         p.then(function () { log += 1; }).then(function () { log += 3; });
+         p.then(function () { log += 2; }).then(function () { log += 4; });",
+        "1234"
+    )]
+    #[case("Promise.resolve(1).then(function (x) { return x + 1; }).then(function (x) { log += x; });", "2")]
+    #[case("Promise.resolve(1).then().then(function (x) { log += x; });", "1")]
+    #[case("Promise.reject('e').then(function () { log += 'no'; }).catch(function (e) { log += e; });", "e")]
+    #[case("Promise.resolve().then(function () { throw 'thrown'; }).catch(function (e) { log += e; });", "thrown")]
+    #[case("new Promise(function () { throw new TypeError('bad'); }).catch(function (e) { log += e.name; });", "TypeError")]
+    #[case("new Promise(function (resolve, reject) { resolve(1); reject(2); resolve(3); }).then(function (x) { log += x; });", "1")]
+    #[case("var p = Promise.resolve(); log += (Promise.resolve(p) === p);", "true")]
+    #[case("Promise.resolve({ then: function (resolve) { resolve('thenable'); } }).then(function (x) { log += x; });", "thenable")]
+    #[case(
+        "var resolve; var p = new Promise(function (r) { resolve = r; }); resolve(p);
+         p.catch(function (e) { log += e instanceof TypeError; });",
+        "true"
+    )]
+    #[case(
+        "Promise.resolve(1).finally(function () { log += 'f'; return 2; }).then(function (x) { log += x; });",
+        "f1"
+    )]
+    #[case(
+        "Promise.reject(1).finally(function () { log += 'f'; }).catch(function (x) { log += x; });",
+        "f1"
+    )]
+    #[case(
+        "Promise.resolve(1).finally(function () { throw 'f'; }).catch(function (x) { log += x; });",
+        "f"
+    )]
+    #[case(
+        "Promise.all([1, Promise.resolve(2), { then: function (resolve) { resolve(3); } }])
+             .then(function (values) { log += values.length + ':' + values[0] + values[1] + values[2]; });",
+        "3:123"
+    )]
+    #[case("Promise.all([]).then(function (values) { log += values.length; });", "0")]
+    #[case(
+        "Promise.all([Promise.resolve(1), Promise.reject('r'), Promise.reject('s')]).catch(function (e) { log += e; });",
+        "r"
+    )]
+    #[case("Promise.all(1).catch(function (e) { log += e instanceof TypeError; });", "true")]
+    #[case(
+        "Promise.race([new Promise(function () {}), Promise.resolve('b'), Promise.resolve('c')]).then(function (x) { log += x; });",
+        "b"
+    )]
+    #[case("Promise.race([Promise.reject('r'), 1]).catch(function (x) { log += x; });", "r")]
+    fn test_promises(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(log_after_jobs(source), Value::from(expected));
+    }
+
+    #[rstest]
+    #[case("Promise()")]
+    #[case("new Promise(1)")]
+    #[case("({ then: Promise.prototype.then }).then()")]
+    #[case("var resolve = Promise.resolve; resolve(1)")]
+    #[case("var p = Promise.resolve(); p.constructor = 1; p.then()")]
+    fn test_type_errors(#[case] source: &str) {
+        let mut engine = Engine::new();
+        let error = engine.evaluate_script(&format!("try {{ {source}; }} catch (e) {{ e instanceof TypeError }}"));
+        assert_eq!(error, Ok(Value::Boolean(true)));
+    }
+
+    #[rstest]
+    #[case("async function f() { log += 'a'; return 'c'; } f().then(function (x) { log += x; }); log += 'b';", "abc")]
+    #[case("var f = async function () { throw 'e'; }; f().catch(function (e) { log += e; });", "e")]
+    #[case("var f = async () => 1; log += f() instanceof Promise;", "true")]
+    #[case("var o = { async m() { return this === o; } }; o.m().then(function (x) { log += x; });", "true")]
+    #[case("async function f(x = y) {} f().catch(function (e) { log += e.name; });", "ReferenceError")]
+    #[case("async function f() {} log += typeof f.prototype;", "undefined")]
+    fn test_async_functions(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(log_after_jobs(source), Value::from(expected));
+    }
+
+    #[rstest]
+    #[case("async function f() { log += 'a'; var x = await 'c'; log += x; } f(); log += 'b';", "abc")]
+    #[case("async function f() { return await Promise.resolve(1) + 1; } f().then(function (x) { log += x; });", "2")]
+    #[case(
+        "async function f() { log += 'a'; await undefined; log += 'c'; }
+         f();
+         Promise.resolve().then(function () { log += 'd'; });
+         log += 'b';",
+        "abcd"
+    )]
+    #[case(
+        "var p = Promise.resolve();
+         async function f() { await p; log += 1; await p; log += 3; }
+         f();
+         p.then(function () { log += 2; }).then(function () { log += 4; });",
+        "1234"
+    )]
+    #[case(
+        "async function f() { try { await Promise.reject('e'); } catch (e) { log += e; } finally { log += 'f'; } }
+         f();",
+        "ef"
+    )]
+    #[case("async function f() { await Promise.reject('e'); log += 'x'; } f().catch(function (e) { log += e; });", "e")]
+    #[case("var f = async (x) => await x + 1; f(1).then(function (x) { log += x; });", "2")]
+    #[case("async function f() { for (var i = 0; i < 3; i++) { log = log + i + await i; } } f();", "001122")]
+    #[case(
+        "var o = { then: function (resolve) { log += 't'; resolve('v'); } };
+         async function f() { var x = await o; log += x; }
+         f();",
+        "tv"
+    )]
+    fn test_await(#[case] source: &str, #[case] expected: &str) {
+        assert_eq!(log_after_jobs(source), Value::from(expected));
+    }
+
+    #[rstest]
+    #[case("async function f() { for await (var x of [1, Promise.resolve(2), 3]) log += x; } f();", "123")]
+    #[case("async function f() { for await (const x of [1, 2]) { log += x; await null; } log += 'e'; } f();", "12e")]
+    #[case("async function f() { try { for await (var x of [Promise.reject('r')]); } catch (e) { log += e; } } f();", "r")]
+    #[case("async function f() { for await (let x of it) log += x; } f();", "012")]
+    #[case("async function f() { for await (var x of it) { log += x; break; } log += 'e'; } f();", "0re")]
+    #[case("async function f() { for await (var x of it) return x; } f().then(function (x) { log += 'v' + x; });", "rv0")]
+    #[case("async function f() { try { for await (var x of it) throw 't'; } catch (e) { log += e; } } f();", "rt")]
+    #[case("async function f() { try { for await (var x of bad) break; } catch (e) { log += e.name; } } f();", "TypeError")]
+    #[case("async function f() { try { for await (var x of 1); } catch (e) { log += e.name; } } f();", "TypeError")]
+    fn test_for_await(#[case] source: &str, #[case] expected: &str) {
+        let mut engine = Engine::new();
+        let iterators = "var log = '';
+                         var it = {
+                           i: 0,
+                           next: function () { return Promise.resolve({ value: this.i, done: this.i++ == 3 }); },
+                           return: function () { log += 'r'; return Promise.resolve({}); },
+                         };
+                         var bad = { next: it.next, return: function () { return Promise.resolve(1); } };";
+        engine.evaluate_script(iterators).unwrap();
+        // Scripts have no `Symbol` to make async iterators with.
+        let key = PropertyKey::Symbol(Symbol::well_known(WellKnownSymbol::AsyncIterator));
+        for name in ["it", "bad"] {
+            let Ok(Value::Object(iterator)) = engine.evaluate_script(name) else {
+                panic!("an object is expected");
+            };
+            let method = engine.evaluate_script("(function () { return this; })").unwrap();
+            assert!(iterator.create_data_property(key.clone(), method));
+        }
+        engine.evaluate_script(source).unwrap();
+        engine.run_jobs().unwrap();
+        assert_eq!(engine.evaluate_script("log"), Ok(Value::from(expected)));
+    }
+
+    #[test]
+    fn test_top_level_for_await() {
+        let mut engine = Engine::new();
+        let result = engine.evaluate_module("main", "for await (var x of []);");
+        assert_matches!(result, Err(error) if error.kind == ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_promise_state() {
+        let mut engine = Engine::new();
+        let state = |value: &Value| match value.as_object().map(Object::kind) {
+            Some(ObjectKind::Promise(promise)) => promise.state(),
+            _ => panic!("a promise is expected"),
+        };
+        let promise = engine.evaluate_script("Promise.resolve(1).then(function (x) { return x + 1; })").unwrap();
+        assert_eq!(state(&promise), PromiseState::Pending);
+        engine.run_jobs().unwrap();
+        assert_eq!(state(&promise), PromiseState::Fulfilled(Value::Number(2.0)));
+        let promise = engine.evaluate_script("Promise.reject('no')").unwrap();
+        assert_eq!(state(&promise), PromiseState::Rejected(Value::from("no")));
+    }
+
+    #[test]
+    fn test_uncatchable_errors() {
+        let mut engine = Engine::new();
+        engine.evaluate_script("var done = false; Promise.resolve().then(function () { for (;;); }).finally(function () { done = true; });").unwrap();
+        engine.context().set_fuel(Some(1000));
+        assert_matches!(engine.run_jobs(), Err(error) if error.kind == ErrorKind::OutOfFuel);
+        engine.context().set_fuel(None);
+        assert_eq!(engine.run_jobs(), Ok(()));
+        assert_eq!(engine.evaluate_script("done"), Ok(Value::Boolean(false)));
+    }
+}
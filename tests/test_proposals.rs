@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::codegen::{generate_script, Formatting};
+    use embedded_ecmascript::grammar::{
+        ClassElement,
+        Declaration,
+        Expression,
+        ForTarget,
+        Parser,
+        ParserOptions,
+        Proposals,
+        Script,
+        Statement,
+        StatementListItem,
+        VariableKind,
+    };
+    use embedded_ecmascript::ErrorCode;
+    use rstest::rstest;
+
+    const ALL: Proposals = Proposals { decorators: true, import_attributes: true, explicit_resource_management: true };
+
+    fn parse_with(source: &str, proposals: Proposals) -> Result<Script, embedded_ecmascript::SourceCodeError> {
+        Parser::new(source).with_options(ParserOptions { proposals, ..ParserOptions::default() }).parse_script()
+    }
+
+    fn parse(source: &str) -> Script {
+        parse_with(source, ALL).unwrap()
+    }
+
+    #[rstest]
+    #[case("@a class A {}")]
+    #[case("class A { @a m() {} }")]
+    #[case("import(a, b);")]
+    #[case("{ using a = b; }")]
+    fn test_disabled(#[case] source: &str) {
+        assert!(Parser::new(source).parse_script().is_err());
+        assert!(parse_with(source, ALL).is_ok());
+    }
+
+    #[test]
+    fn test_decorators() {
+        let body = parse("@a @b.c.d @e(f) @(g, h) class A { @i m() {} @j static k = 1; static {} }").body;
+        let StatementListItem::Declaration(Declaration::ClassDeclaration(class)) = &body[0] else {
+            panic!("a class declaration is expected");
+        };
+        assert_eq!(class.range.start, 0);
+        let decorators: Vec<_> = class.decorators.iter().map(|decorator| decorator.range.clone()).collect();
+        assert_eq!(decorators, [0..2, 3..9, 10..15, 16..23]);
+        assert_matches!(&class.decorators[1].expression, Expression::MemberExpression(_));
+        assert_matches!(&class.decorators[2].expression, Expression::CallExpression(_));
+        assert_matches!(&class.decorators[3].expression, Expression::ParenthesizedExpression(_));
+        assert_matches!(&class.elements[0], ClassElement::MethodDefinition { decorators, .. } if decorators.len() == 1);
+        assert_matches!(&class.elements[1], ClassElement::FieldDefinition { decorators, is_static: true, .. }
+            if decorators.len() == 1);
+
+        assert_matches!(&parse("x = @a class {};").body[0], StatementListItem::Statement(Statement::ExpressionStatement(
+            Expression::AssignmentExpression(_),
+            _
+        )));
+        assert_eq!(parse("class A { static #b; m() { x = @A.#b class {}; } }").body.len(), 1);
+    }
+
+    #[test]
+    fn test_import_attributes() {
+        let body = parse("import(a, { with: { type: 'json' } }); import(a,); import(a, b,);").body;
+        let options: Vec<_> = body
+            .iter()
+            .map(|item| match item {
+                StatementListItem::Statement(Statement::ExpressionStatement(Expression::ImportCall(call), _)) => {
+                    call.options.is_some()
+                },
+                _ => panic!("an import call is expected"),
+            })
+            .collect();
+        assert_eq!(options, [true, false, true]);
+    }
+
+    #[test]
+    fn test_using_declarations() {
+        let body = parse("{ using a = b, c = d; } async function f() { await using e = g; } using;").body;
+        let StatementListItem::Statement(Statement::BlockStatement(block)) = &body[0] else {
+            panic!("a block is expected");
+        };
+        assert_matches!(&block.statements[0], StatementListItem::Declaration(Declaration::LexicalDeclaration(list))
+            if list.kind == VariableKind::Using && list.declarations.len() == 2);
+        let StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) = &body[1] else {
+            panic!("a function declaration is expected");
+        };
+        assert_matches!(&function.body[0], StatementListItem::Declaration(Declaration::LexicalDeclaration(list))
+            if list.kind == VariableKind::AwaitUsing);
+        assert_matches!(&body[2], StatementListItem::Statement(Statement::ExpressionStatement(..)));
+
+        let body = parse("{ for (using a of b); for (using of b); using\na = b; }").body;
+        let StatementListItem::Statement(Statement::BlockStatement(block)) = &body[0] else {
+            panic!("a block is expected");
+        };
+        assert_matches!(&block.statements[0], StatementListItem::Statement(Statement::ForOfStatement(statement))
+            if matches!(statement.target, ForTarget::ForDeclaration { kind: VariableKind::Using, .. }));
+        assert_matches!(&block.statements[1], StatementListItem::Statement(Statement::ForOfStatement(statement))
+            if matches!(statement.target, ForTarget::Expression(_)));
+        assert_eq!(block.statements.len(), 4);
+        assert_matches!(&parse("{ using [a] = b; }").body[0], StatementListItem::Statement(Statement::BlockStatement(block))
+            if matches!(block.statements[0], StatementListItem::Statement(Statement::ExpressionStatement(..))));
+
+        let module = Parser::new_module("using a = b;").with_options(ParserOptions { proposals: ALL, ..ParserOptions::default() }).parse_script();
+        assert!(module.is_ok());
+    }
+
+    #[rstest]
+    #[case("@a function f() {}", ErrorCode::UnexpectedInput, 3..11)]
+    #[case("class A { @a static {} }", ErrorCode::UnexpectedInput, 20..21)]
+    #[case("@a[b] class A {}", ErrorCode::UnexpectedInput, 2..3)]
+    #[case("using a = b;", ErrorCode::EarlyError, 0..12)]
+    #[case("{ using a; }", ErrorCode::UnexpectedInput, 9..10)]
+    #[case("{ for (using a in b); }", ErrorCode::UnexpectedInput, 7..14)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = parse_with(source, ALL).unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    #[rstest]
+    #[case("@a @b.c(d) @(e + f) class A { @g m() {} @h static i; }", "@a@b.c(d)@(e+f)class A{@g m(){}@h static i;}")]
+    #[case("x = @a class {};", "x=@a class{};")]
+    #[case("import(a, { with: { type: b } });", "import(a,{with:{type:b}});")]
+    #[case("async function f() { using a = b; await using c = d; }", "async function f(){using a=b;await using c=d;}")]
+    fn test_codegen(#[case] source: &str, #[case] expected: &str) {
+        let printed = generate_script(&parse(source), Formatting::Compact);
+        assert_eq!(printed, expected);
+        assert!(parse_with(&printed, ALL).is_ok(), "{printed}");
+    }
+}
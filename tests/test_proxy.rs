@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{Context, Object, ObjectKind, PropertyKey, Proxy, Value};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("new Proxy({ a: 1 }, {}).a", Value::Number(1.0))]
+    #[case("new Proxy({}, { get: function (target, key) { return key + '!'; } }).x", Value::from("x!"))]
+    #[case("var t = {}; var p = new Proxy(t, {}); p.x = 2; t.x", Value::Number(2.0))]
+    #[case(
+        "var log = ''; var p = new Proxy({}, { set: function (t, key, value) { log += key + value; return true; } }); p.a = 1; log",
+        Value::from("a1")
+    )]
+    #[case("'a' in new Proxy({}, { has: function () { return true; } })", Value::Boolean(true))]
+    #[case("delete new Proxy({ a: 1 }, { deleteProperty: function () { return false; } }).a", Value::Boolean(false))]
+    #[case("new Proxy(function () { return 1; }, { apply: function (t, self, args) { return args.length; } })(1, 2)", Value::Number(2.0))]
+    #[case("new Proxy(function () { return 1; }, {})()", Value::Number(1.0))]
+    #[case("new (new Proxy(function () {}, { construct: function () { return { x: 3 }; } }))().x", Value::Number(3.0))]
+    #[case("function F() { this.y = 4; } new (new Proxy(F, {}))().y", Value::Number(4.0))]
+    #[case("typeof new Proxy(function () {}, {})", Value::from("function"))]
+    #[case("typeof new Proxy({}, {})", Value::from("object"))]
+    #[case(
+        "function F() {} var p = new Proxy({}, { getPrototypeOf: function () { return F.prototype; } }); p instanceof F",
+        Value::Boolean(true)
+    )]
+    #[case("Reflect.ownKeys({ ...new Proxy({ a: 1, b: 2 }, { ownKeys: function () { return ['b']; } }) }).length", Value::Number(1.0))]
+    #[case("var o = {}; Reflect.setPrototypeOf(o, new Proxy({}, { get: function (t, key) { return key; } })); o.zzz", Value::from("zzz"))]
+    #[case("var s = ''; for (var k in new Proxy({ a: 1 }, {})) s += k; s", Value::from("a"))]
+    #[case(
+        "var d = Reflect.getOwnPropertyDescriptor(new Proxy({}, { getOwnPropertyDescriptor: function () {
+             return { value: 5, configurable: true };
+         } }), 'a');
+         d.value + ':' + d.writable + d.enumerable + d.configurable",
+        Value::from("5:falsefalsetrue")
+    )]
+    #[case(
+        "var log = ''; var p = new Proxy({}, { defineProperty: function (t, key, d) { log += key + d.value + d.enumerable; return true; } });
+         p.a = 1; log",
+        Value::from("a1true")
+    )]
+    #[case(
+        "var r = Proxy.revocable({}, {}); r.revoke(); r.revoke();
+         try { r.proxy.a; } catch (e) { e instanceof TypeError }",
+        Value::Boolean(true)
+    )]
+    #[case("var r = Proxy.revocable({ a: 1 }, {}); r.proxy.a", Value::Number(1.0))]
+    #[case("var p = new Proxy({}, {}); var q = new Proxy(p, { get: function () { return 6; } }); new Proxy(q, {}).a", Value::Number(6.0))]
+    #[case("var a = 'outer'; with (new Proxy({ a: 'inner' }, { has: function () { return false; } })) a", Value::from("outer"))]
+    #[case(
+        "var log = ''; var p = new Proxy({ a: 1 }, { has: function (t, key) { log += key; return key in t; } }); with (p) a; log",
+        // HasBinding and GetBindingValue both ask.
+        Value::from("aa")
+    )]
+    #[case(
+        "var a = 'outer'; var handler = { has: function () { return true; }, get: function (t, key) { return typeof key === 'string' ? 'trapped' : undefined; } };
+         with (new Proxy({}, handler)) a",
+        Value::from("trapped")
+    )]
+    #[case(
+        "var log = ''; with (new Proxy({ a: 1 }, { deleteProperty: function (t, key) { log += key; return true; } })) delete a; log",
+        Value::from("a")
+    )]
+    #[case(
+        "var s = ''; var handler = {
+             ownKeys: function () { return ['x', 'y']; },
+             getOwnPropertyDescriptor: function (t, key) { return { value: 1, enumerable: key === 'y', configurable: true }; }
+         };
+         for (var k in new Proxy({}, handler)) s += k; s",
+        Value::from("y")
+    )]
+    #[case(
+        "var s = ''; for (var k in new Proxy({ a: 1 }, { getPrototypeOf: function () { return { b: 2 }; } })) s += k; s",
+        Value::from("ab")
+    )]
+    #[case("try { for (var k in new Proxy({}, { ownKeys: function () { throw 'x'; } })) ; } catch (e) { e }", Value::from("x"))]
+    fn test_proxy(#[case] source: &str, #[case] expected: Value) {
+        let mut engine = Engine::new();
+        assert_eq!(engine.evaluate_script(source), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("Proxy({}, {})")]
+    #[case("new Proxy(1, {})")]
+    #[case("new Proxy({}, null)")]
+    #[case("new Proxy({}, { get: 1 }).a")]
+    #[case("new Proxy({}, {})()")]
+    #[case("new (new Proxy({}, {}))()")]
+    #[case("new (new Proxy(function () {}, { construct: function () { return 1; } }))()")]
+    #[case("var t = {}; Reflect.defineProperty(t, 'a', { value: 1 }); new Proxy(t, { get: function () { return 2; } }).a")]
+    #[case("var t = {}; Reflect.defineProperty(t, 'a', { value: 1 }); Reflect.set(new Proxy(t, { set: function () { return true; } }), 'a', 2)")]
+    #[case("var t = {}; Reflect.defineProperty(t, 'a', { value: 1 }); 'a' in new Proxy(t, { has: function () { return false; } })")]
+    #[case("var t = { a: 1 }; Reflect.preventExtensions(t); 'a' in new Proxy(t, { has: function () { return false; } })")]
+    #[case(
+        "var t = {}; Reflect.defineProperty(t, 'a', { value: 1 });
+         Reflect.deleteProperty(new Proxy(t, { deleteProperty: function () { return true; } }), 'a')"
+    )]
+    #[case("var t = {}; Reflect.preventExtensions(t); Reflect.isExtensible(new Proxy(t, { isExtensible: function () { return true; } }))")]
+    #[case("Reflect.preventExtensions(new Proxy({}, { preventExtensions: function () { return true; } }))")]
+    #[case("var t = {}; Reflect.preventExtensions(t); Reflect.getPrototypeOf(new Proxy(t, { getPrototypeOf: function () { return null; } }))")]
+    #[case("Reflect.getPrototypeOf(new Proxy({}, { getPrototypeOf: function () { return 1; } }))")]
+    #[case(
+        "var t = {}; Reflect.preventExtensions(t);
+         Reflect.setPrototypeOf(new Proxy(t, { setPrototypeOf: function () { return true; } }), null)"
+    )]
+    #[case(
+        "Reflect.getOwnPropertyDescriptor(new Proxy({}, { getOwnPropertyDescriptor: function () {
+             return { value: 1, configurable: false };
+         } }), 'a')"
+    )]
+    #[case(
+        "var t = {}; Reflect.defineProperty(t, 'a', { value: 1 });
+         Reflect.getOwnPropertyDescriptor(new Proxy(t, { getOwnPropertyDescriptor: function () {} }), 'a')"
+    )]
+    #[case("Reflect.getOwnPropertyDescriptor(new Proxy({}, { getOwnPropertyDescriptor: function () { return 1; } }), 'a')")]
+    #[case(
+        "var t = {}; Reflect.preventExtensions(t);
+         Reflect.defineProperty(new Proxy(t, { defineProperty: function () { return true; } }), 'a', { value: 1 })"
+    )]
+    #[case("Reflect.defineProperty(new Proxy({}, { defineProperty: function () { return true; } }), 'a', { configurable: false })")]
+    #[case("var t = {}; Reflect.defineProperty(t, 'a', { value: 1 }); Reflect.ownKeys(new Proxy(t, { ownKeys: function () { return []; } }))")]
+    #[case("Reflect.ownKeys(new Proxy({}, { ownKeys: function () { return ['a', 'a']; } }))")]
+    #[case("Reflect.ownKeys(new Proxy({}, { ownKeys: function () { return [1]; } }))")]
+    #[case("Reflect.ownKeys(new Proxy({}, { ownKeys: function () { return 1; } }))")]
+    #[case("var t = {}; Reflect.preventExtensions(t); Reflect.ownKeys(new Proxy(t, { ownKeys: function () { return ['a']; } }))")]
+    #[case("var t = { a: 1 }; Reflect.preventExtensions(t); Reflect.ownKeys(new Proxy(t, { ownKeys: function () { return []; } }))")]
+    fn test_type_errors(#[case] source: &str) {
+        let mut engine = Engine::new();
+        let error = engine.evaluate_script(&format!("try {{ {source}; }} catch (e) {{ e instanceof TypeError }}"));
+        assert_eq!(error, Ok(Value::Boolean(true)));
+    }
+
+    #[rstest]
+    #[case("Reflect.apply(function (a, b) { return this.x + a + b; }, { x: 1 }, [2, 3])", Value::Number(6.0))]
+    #[case("Reflect.construct(function (a) { this.a = a; }, [5]).a", Value::Number(5.0))]
+    #[case("function F() {} function G() {} Reflect.getPrototypeOf(Reflect.construct(F, [], G)) === G.prototype", Value::Boolean(true))]
+    #[case("var o = {}; Reflect.defineProperty(o, 'a', { value: 1 }) && !Reflect.defineProperty(o, 'a', { value: 2 })", Value::Boolean(true))]
+    #[case("var o = { a: 1 }; Reflect.deleteProperty(o, 'a') && !('a' in o)", Value::Boolean(true))]
+    #[case("Reflect.get({ get a() { 'use strict'; return this; } }, 'a', 7)", Value::Number(7.0))]
+    #[case(
+        "var d = Reflect.getOwnPropertyDescriptor({ a: 1 }, 'a'); d.value + ':' + d.writable + d.enumerable + d.configurable",
+        Value::from("1:truetruetrue")
+    )]
+    #[case("Reflect.getOwnPropertyDescriptor({}, 'a')", Value::Undefined)]
+    #[case("Reflect.has({ a: 1 }, 'a')", Value::Boolean(true))]
+    #[case("var o = {}; Reflect.preventExtensions(o) && !Reflect.isExtensible(o)", Value::Boolean(true))]
+    #[case("var k = Reflect.ownKeys({ b: 1, 0: 2, a: 3 }); k.length + k[0] + k[1] + k[2]", Value::from("30ba"))]
+    #[case("var o = {}; Reflect.set(o, 'a', 1) && o.a", Value::Number(1.0))]
+    #[case("var o = {}; var r = {}; Reflect.set({ set a(v) { this.v = v; } }, 'a', 2, r); r.v", Value::Number(2.0))]
+    #[case("var p = {}; var o = {}; Reflect.setPrototypeOf(o, p) && Reflect.getPrototypeOf(o) === p", Value::Boolean(true))]
+    #[case("Reflect.getPrototypeOf(Reflect) === Reflect.getPrototypeOf({})", Value::Boolean(true))]
+    fn test_reflect(#[case] source: &str, #[case] expected: Value) {
+        let mut engine = Engine::new();
+        assert_eq!(engine.evaluate_script(source), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("Reflect.apply(1, null, [])")]
+    #[case("Reflect.apply(function () {}, null, 1)")]
+    #[case("Reflect.construct(function () {}, [], 1)")]
+    #[case("Reflect.construct(function () {}, [], undefined)")]
+    #[case("Reflect.get(1, 'a')")]
+    #[case("Reflect.defineProperty({}, 'a', 1)")]
+    #[case("Reflect.defineProperty({}, 'a', { get: 1 })")]
+    #[case("Reflect.defineProperty({}, 'a', { get: function () {}, value: 1 })")]
+    #[case("Reflect.setPrototypeOf({}, 1)")]
+    fn test_reflect_type_errors(#[case] source: &str) {
+        let mut engine = Engine::new();
+        let error = engine.evaluate_script(&format!("try {{ {source}; }} catch (e) {{ e instanceof TypeError }}"));
+        assert_eq!(error, Ok(Value::Boolean(true)));
+    }
+
+    #[rstest]
+    #[case("p.x")]
+    #[case("p.x = 1")]
+    #[case("'x' in p")]
+    #[case("delete p.x")]
+    #[case("Reflect.getPrototypeOf(p)")]
+    #[case("Reflect.ownKeys(p)")]
+    #[case("p()")]
+    fn test_nested_proxies(#[case] source: &'static str) {
+        // Proxies of proxies nest deeper than any native stack fits.
+        let thread = std::thread::Builder::new().stack_size(2 << 20).spawn(move || {
+            let mut engine = Engine::new();
+            let setup = "var p = function () {}; for (var i = 0; i < 10000; i++) p = new Proxy(p, {});";
+            let result = engine.evaluate_script(&format!("{setup} try {{ {source}; }} catch (e) {{ e instanceof RangeError }}"));
+            assert_eq!(result, Ok(Value::Boolean(true)));
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    #[test]
+    fn test_host_proxy() {
+        let mut context = Context::new();
+        let target = Object::new(None);
+        assert!(target.create_data_property("a", 1.0));
+        let proxy = Proxy::create(&Value::Object(target.clone()), &Value::Object(Object::new(None))).unwrap();
+        let ObjectKind::Proxy(slots) = proxy.kind() else {
+            panic!("a proxy is expected");
+        };
+        assert_eq!(slots.target(), Some(target));
+        let key = PropertyKey::from("a");
+        assert_eq!(proxy.get(&mut context, &key, &Value::Object(proxy.clone())), Ok(Value::Number(1.0)));
+        assert_eq!(proxy.try_has_property(&mut context, &key), Ok(true));
+        slots.revoke();
+        assert!(proxy.try_has_property(&mut context, &key).is_err());
+        assert!(!proxy.is_extensible());
+    }
+}
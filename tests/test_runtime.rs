@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::runtime::{
+        Object,
+        PropertyDescriptor,
+        PropertyKey,
+        Symbol,
+        Type,
+        Value,
+        WellKnownSymbol,
+    };
+    use num_bigint::BigInt;
+    use rstest::rstest;
+
+    #[test]
+    fn test_types() {
+        let values = [
+            (Value::Undefined, Type::Undefined),
+            (Value::Null, Type::Null),
+            (Value::from(true), Type::Boolean),
+            (Value::from(1), Type::Number),
+            (Value::from("a"), Type::String),
+            (Value::from(Symbol::new(None)), Type::Symbol),
+            (Value::from(BigInt::from(1)), Type::BigInt),
+            (Value::from(Object::new(None)), Type::Object),
+        ];
+        for (value, expected) in values {
+            assert_eq!(value.r#type(), expected);
+            assert_eq!(value.is_nullish(), matches!(expected, Type::Undefined | Type::Null));
+            assert_eq!(value.is_primitive(), expected != Type::Object);
+        }
+        assert!(Value::from(-3.0).is_integral_number());
+        assert!(!Value::from(0.5).is_integral_number());
+        assert!(!Value::from(f64::INFINITY).is_integral_number());
+    }
+
+    #[rstest]
+    #[case(Value::from(f64::NAN), Value::from(f64::NAN), true, true, false)]
+    #[case(Value::from(0.0), Value::from(-0.0), false, true, true)]
+    #[case(Value::from(1), Value::from(1.0), true, true, true)]
+    #[case(Value::from("1"), Value::from(1), false, false, false)]
+    #[case(Value::from("a"), Value::from("a"), true, true, true)]
+    #[case(Value::Null, Value::Undefined, false, false, false)]
+    fn test_equality(
+        #[case] left: Value,
+        #[case] right: Value,
+        #[case] same_value: bool,
+        #[case] same_value_zero: bool,
+        #[case] strictly_equal: bool,
+    ) {
+        assert_eq!(left.same_value(&right), same_value);
+        assert_eq!(left.same_value_zero(&right), same_value_zero);
+        assert_eq!(left.is_strictly_equal(&right), strictly_equal);
+    }
+
+    #[test]
+    fn test_identity() {
+        let object = Object::new(None);
+        assert!(Value::from(object.clone()).same_value(&Value::from(object)));
+        assert!(!Value::from(Object::new(None)).same_value(&Value::from(Object::new(None))));
+
+        let symbol = Symbol::new(Some("a".to_owned()));
+        assert_eq!(symbol.clone(), symbol);
+        assert_ne!(Symbol::new(Some("a".to_owned())), symbol);
+        assert_eq!(Symbol::well_known(WellKnownSymbol::Iterator), Symbol::well_known(WellKnownSymbol::Iterator));
+        assert_eq!(Symbol::well_known(WellKnownSymbol::Iterator).descriptive_string(), "Symbol(Symbol.iterator)");
+        assert_eq!(Symbol::new(None).description(), None);
+    }
+
+    #[test]
+    fn test_property_keys() {
+        assert_eq!(PropertyKey::from("0").as_array_index(), Some(0));
+        assert_eq!(PropertyKey::from("4294967294").as_array_index(), Some(4_294_967_294));
+        assert_eq!(PropertyKey::from("4294967295").as_array_index(), None);
+        assert_eq!(PropertyKey::from("01").as_array_index(), None);
+        assert_eq!(PropertyKey::from("-1").as_array_index(), None);
+
+        let object = Object::new(None);
+        let symbol = Symbol::new(None);
+        for key in [PropertyKey::from("b"), PropertyKey::from(symbol.clone()), "10".into(), "a".into(), "2".into()] {
+            assert!(object.create_data_property(key, 1));
+        }
+        assert_eq!(object.own_property_keys(), [
+            PropertyKey::from("2"),
+            "10".into(),
+            "b".into(),
+            "a".into(),
+            symbol.into(),
+        ]);
+        assert!(object.delete(&"b".into()));
+        assert!(!object.has_property(&"b".into()));
+    }
+
+    #[test]
+    fn test_define_own_property() {
+        let object = Object::new(None);
+        let key = PropertyKey::from("a");
+        let partial = PropertyDescriptor { value: Some(Value::from(1)), ..PropertyDescriptor::default() };
+        assert!(object.define_own_property(key.clone(), partial));
+        // Absent attributes of a new property default to `false`.
+        assert_eq!(object.get_own_property(&key), Some(PropertyDescriptor::data(Value::from(1), false, false, false)));
+
+        // A non-configurable non-writable property keeps its value.
+        let same = PropertyDescriptor { value: Some(Value::from(1.0)), ..PropertyDescriptor::default() };
+        assert!(object.define_own_property(key.clone(), same));
+        assert!(!object.define_own_property(key.clone(), PropertyDescriptor::data(Value::from(2), false, false, false)));
+        let enumerable = PropertyDescriptor { enumerable: Some(true), ..PropertyDescriptor::default() };
+        assert!(!object.define_own_property(key.clone(), enumerable));
+        assert!(!object.delete(&key));
+
+        // A configurable property can turn into an accessor one.
+        assert!(object.define_own_property("b".into(), PropertyDescriptor::data(Value::from(1), true, true, true)));
+        let getter = PropertyDescriptor { get: Some(Value::Undefined), ..PropertyDescriptor::default() };
+        assert!(object.define_own_property("b".into(), getter));
+        assert_eq!(
+            object.get_own_property(&"b".into()),
+            Some(PropertyDescriptor::accessor(Value::Undefined, Value::Undefined, true, true)),
+        );
+
+        assert!(object.prevent_extensions());
+        assert!(!object.create_data_property("c", 1));
+    }
+
+    #[test]
+    fn test_prototypes() {
+        let prototype = Object::new(None);
+        prototype.create_data_property("a", 1);
+        let object = Object::new(Some(prototype.clone()));
+        assert!(object.has_property(&"a".into()));
+        assert!(object.get_own_property(&"a".into()).is_none());
+        // Prototype chains cannot be circular.
+        assert!(!prototype.set_prototype_of(Some(object.clone())));
+        assert!(object.set_prototype_of(None));
+        assert!(!object.has_property(&"a".into()));
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::grammar::Parser;
+    use embedded_ecmascript::scopes::{analyze_script, ScopeKind, ScopeTree, SymbolKind};
+    use rstest::rstest;
+
+    fn analyze(source: &str) -> ScopeTree {
+        analyze_script(&Parser::new(source).parse_script().unwrap(), false)
+    }
+
+    /// Names declared by each scope, outermost first.
+    fn symbols(tree: &ScopeTree) -> Vec<(ScopeKind, Vec<(&str, SymbolKind)>)> {
+        tree.scopes
+            .iter()
+            .map(|scope| (scope.kind, scope.symbols.iter().map(|symbol| (symbol.name.as_str(), symbol.kind)).collect()))
+            .collect()
+    }
+
+    /// Each reference with the kind of a scope declaring it, if any.
+    fn resolutions(tree: &ScopeTree) -> Vec<(&str, Option<ScopeKind>)> {
+        tree.references
+            .iter()
+            .map(|reference| (reference.name.as_str(), reference.symbol.map(|symbol| tree.scope(symbol.scope).kind)))
+            .collect()
+    }
+
+    #[test]
+    fn test_declarations() {
+        let tree = analyze("var a; let b; { var c; const d = 1; function* e() {} class F {} } \
+            function g(h, [i]) { var a; } try {} catch ({ j }) { var k; }");
+        assert_eq!(symbols(&tree), [
+            (ScopeKind::Global, vec![
+                ("a", SymbolKind::Var),
+                ("b", SymbolKind::Let),
+                ("c", SymbolKind::Var),
+                ("g", SymbolKind::Function),
+                ("k", SymbolKind::Var),
+            ]),
+            (ScopeKind::Block, vec![("d", SymbolKind::Const), ("e", SymbolKind::Function), ("F", SymbolKind::Class)]),
+            (ScopeKind::Function, vec![("arguments", SymbolKind::Arguments)]),
+            (ScopeKind::Class, vec![("F", SymbolKind::Class)]),
+            (ScopeKind::Function, vec![
+                ("h", SymbolKind::Parameter),
+                ("i", SymbolKind::Parameter),
+                ("a", SymbolKind::Var),
+                ("arguments", SymbolKind::Arguments),
+            ]),
+            (ScopeKind::Block, vec![]),
+            (ScopeKind::Block, vec![("j", SymbolKind::CatchParameter)]),
+        ]);
+        assert_eq!(tree.scope(tree.root()).symbols[0].range, 4..5);
+    }
+
+    #[rstest]
+    #[case("a; var a;", &[("a", Some(ScopeKind::Global))])]
+    #[case("a; { let a; }", &[("a", None)])]
+    #[case("let a; (function () { a; arguments; });", &[
+        ("a", Some(ScopeKind::Global)),
+        ("arguments", Some(ScopeKind::Function)),
+    ])]
+    #[case("(a => () => a + arguments);", &[("a", Some(ScopeKind::ArrowFunction)), ("arguments", None)])]
+    #[case("for (let a of a) {}", &[("a", Some(ScopeKind::Block))])]
+    #[case("(function a() { a; });", &[("a", Some(ScopeKind::Function))])]
+    #[case("(class A extends A {});", &[("A", Some(ScopeKind::Class))])]
+    #[case("({ a, b: c = d } = e);", &[("a", None), ("c", None), ("d", None), ("e", None)])]
+    #[case("switch (a) { case b: let a; }", &[("a", None), ("b", None)])]
+    fn test_references(#[case] source: &str, #[case] expected: &[(&str, Option<ScopeKind>)]) {
+        assert_eq!(resolutions(&analyze(source)), expected);
+    }
+
+    #[test]
+    fn test_captures() {
+        let tree = analyze("function f(a, b) { let c; { let d; d; } () => b; class A { e = c; } return a; }");
+        let function = &tree.scopes[1];
+        let captured: Vec<_> = function.symbols.iter().map(|symbol| (symbol.name.as_str(), symbol.is_captured)).collect();
+        assert_eq!(captured, [("a", false), ("b", true), ("c", true), ("A", false), ("arguments", false)]);
+        assert!(!tree.scopes[2].symbols[0].is_captured);
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_legacy_block_functions() {
+        let tree = analyze("function f() { { function g() {} } g; }");
+        assert_eq!(symbols(&tree)[1].1, [("g", SymbolKind::Var), ("arguments", SymbolKind::Arguments)]);
+        assert_eq!(resolutions(&tree), [("g", Some(ScopeKind::Function))]);
+    }
+
+    #[test]
+    fn test_modules() {
+        let script = Parser::new_module("function a() {} a;").parse_script().unwrap();
+        let tree = analyze_script(&script, true);
+        assert_eq!(symbols(&tree)[0], (ScopeKind::Module, vec![("a", SymbolKind::Function)]));
+        assert_eq!(tree.resolve(tree.root(), "a").map(|symbol| tree.symbol(symbol).range.clone()), Some(9..10));
+    }
+}
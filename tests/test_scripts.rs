@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::grammar::DEFAULT_MAX_NESTING_DEPTH;
+    use embedded_ecmascript::runtime::{ErrorKind, Value};
+    use rstest::rstest;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    /// The system allocator counting bytes held by each thread, so tests
+    /// running in parallel do not see allocations of each other.
+    struct CountingAllocator;
+
+    thread_local! {
+        static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+        static PEAK_BYTES: Cell<isize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let live = LIVE_BYTES.with(|live| {
+                live.set(live.get() + layout.size() as isize);
+                live.get()
+            });
+            PEAK_BYTES.with(|peak| peak.set(peak.get().max(live)));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            LIVE_BYTES.with(|live| live.set(live.get() - layout.size() as isize));
+            System.dealloc(ptr, layout);
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Bytes allocated at most at once while running `run`, above ones
+    /// held before it.
+    fn peak_bytes(run: impl FnOnce()) -> isize {
+        let start = LIVE_BYTES.with(Cell::get);
+        PEAK_BYTES.with(|peak| peak.set(start));
+        run();
+        PEAK_BYTES.with(Cell::get) - start
+    }
+
+    #[rstest]
+    #[case("", Value::Undefined)]
+    #[case("1; 2; var x;", Value::Number(2.0))]
+    #[case("var a = 1, b = a + 1; a + b", Value::Number(3.0))]
+    #[case("let a = 1; { let a = 2; } a", Value::Number(1.0))]
+    #[case("if (0) 1; else 2", Value::Number(2.0))]
+    #[case("1; if (0) 2;", Value::Undefined)]
+    #[case("var s = 0; for (var i = 0; i < 5; i++) s += i; s", Value::Number(10.0))]
+    #[case("var i = 0; while (i < 3) i++; i", Value::Number(3.0))]
+    #[case("var i = 0; do i++; while (i > 10); i", Value::Number(1.0))]
+    #[case("var n = 0; outer: for (;;) { for (;;) { n++; if (n > 2) break outer; continue outer; } } n", Value::Number(3.0))]
+    #[case("var s = ''; for (var k in {a: 1, b: 2}) s += k; s", Value::from("ab"))]
+    #[case("var s = 0; for (const v of [1, 2, 3]) s += v; s", Value::Number(6.0))]
+    #[case("switch (2) { case 1: 'a'; case 2: 'b'; case 3: 'c'; break; default: 'd'; }", Value::from("c"))]
+    #[case("switch (5) { default: 'd'; case 1: 'a'; }", Value::from("a"))]
+    #[case("function f(x) { return x * 2; } f(21)", Value::Number(42.0))]
+    #[case("f(1); function f(x) { return x; }", Value::Number(1.0))]
+    #[case("function fib(n) { return n < 2 ? n : fib(n - 1) + fib(n - 2); } fib(15)", Value::Number(610.0))]
+    #[case("function counter() { let n = 0; return () => ++n; } var c = counter(); c(); c()", Value::Number(2.0))]
+    #[case("var fs = []; for (let i = 0; i < 3; i++) fs[i] = () => i; fs[0]() + fs[2]()", Value::Number(2.0))]
+    #[case("(function (a, b = a + 1, ...rest) { return a + b + rest.length; })(1)", Value::Number(3.0))]
+    #[case("(function () { return arguments.length; })(1, 2, 3)", Value::Number(3.0))]
+    #[case("function f(a) { arguments[0] = 2; return a; } f(1)", Value::Number(2.0))]
+    #[case("function f(a) { a = 2; return arguments[0]; } f(1)", Value::Number(2.0))]
+    #[case("function f(a, a) { a = 3; return '' + arguments[0] + arguments[1]; } f(1, 2)", Value::from("13"))]
+    #[case("function f(a) { delete arguments[0]; arguments[0] = 2; return a; } f(1)", Value::Number(1.0))]
+    #[case("function f(a, b) { arguments[1] = 2; return b; } f(1)", Value::Undefined)]
+    #[case("function f(a) { return arguments.callee === f; } f()", Value::Boolean(true))]
+    #[case("function f(a) { 'use strict'; arguments[0] = 2; return a; } f(1)", Value::Number(1.0))]
+    #[case("function f(a = 0) { arguments[0] = 2; return a; } f(1)", Value::Number(1.0))]
+    #[case("var o = { m() { return this === o; } }; (o?.m)()", Value::Boolean(true))]
+    #[case("var o = { m() { return this === o; } }; (o?.['m'])()", Value::Boolean(true))]
+    #[case("var f = function g() { return typeof g; }; f()", Value::from("function"))]
+    #[case("var f = function () {}; f.name", Value::from("f"))]
+    #[case("function F(x) { this.x = x; } new F(7).x", Value::Number(7.0))]
+    #[case("function F() {} F.prototype.y = 1; new F().y", Value::Number(1.0))]
+    #[case("function F() { return {z: 2}; } new F().z", Value::Number(2.0))]
+    #[case("var o = {v: 3, get() { return this.v; }}; o.get()", Value::Number(3.0))]
+    #[case("var o = {get v() { return 4; }, set v(x) { this.w = x; }}; o.v = 5; o.v + o.w", Value::Number(9.0))]
+    #[case("(function () { 'use strict'; return this; })()", Value::Undefined)]
+    #[case("var { a, b: [c, d = 5], ...rest } = { a: 1, b: [2], e: 3 }; a + c + d + rest.e", Value::Number(11.0))]
+    #[case("var a, b; [a, b] = [1, 2]; [a, b] = [b, a]; a * 10 + b", Value::Number(21.0))]
+    #[case("var [x, , ...y] = [1, 2, 3, 4]; x + y.length * 10 + y[1]", Value::Number(25.0))]
+    #[case("try { throw 1; } catch (e) { e + 1; }", Value::Number(2.0))]
+    #[case("try { throw {m: 'x'}; } catch ({ m }) { m; }", Value::from("x"))]
+    #[case("var s = ''; try { s += 'a'; } finally { s += 'b'; } s", Value::from("ab"))]
+    #[case("function f() { try { return 1; } finally { return 2; } } f()", Value::Number(2.0))]
+    #[case("function f() { try { undefined.x; } catch { return 'caught'; } } f()", Value::from("caught"))]
+    #[case("var o = {}; o[1 + 1] = 'x'; o['2']", Value::from("x"))]
+    #[case("[1, , 3].length", Value::Number(3.0))]
+    #[case("[...[1, 2], , ...[3]].length", Value::Number(4.0))]
+    #[case("var a = [1, 2]; a.length = 1; a[1]", Value::Undefined)]
+    #[case("'abc'.length + 'abc'[1]", Value::from("3b"))]
+    #[case("var o = {a: 1}; delete o.a; 'a' in o", Value::Boolean(false))]
+    #[case("function F() {} new F() instanceof F", Value::Boolean(true))]
+    #[case("var o = null; o?.a.b", Value::Undefined)]
+    #[case("var x = 1; with ({x: 2}) x", Value::Number(2.0))]
+    fn test_evaluation(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[rstest]
+    #[case("'use strict'; { function f() {} } typeof f", Value::from("undefined"))]
+    #[case("'use strict'; { function f() { return 1; } f() }", Value::Number(1.0))]
+    #[case("{ let f = 1; { function f() {} } } typeof f", Value::from("undefined"))]
+    fn test_block_functions(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[rstest]
+    #[case("{ function f() { return 1; } } f()", Value::Number(1.0))]
+    #[case("typeof g; if (1) function g() {} typeof g", Value::from("function"))]
+    fn test_legacy_block_functions(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(source), Ok(expected), "{source}");
+    }
+
+    #[cfg(not(feature = "annex-b"))]
+    #[rstest]
+    #[case("{ function f() { return 1; } } f()", ErrorKind::ReferenceError)]
+    #[case("typeof g; if (1) function g() {} typeof g", ErrorKind::SyntaxError)]
+    fn test_legacy_block_functions(#[case] source: &str, #[case] kind: ErrorKind) {
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == kind);
+    }
+
+    #[rstest]
+    #[case("let a = 1; let a = 2;", ErrorKind::SyntaxError)]
+    #[case("a; let a = 1;", ErrorKind::ReferenceError)]
+    #[case("const a = 1; a = 2;", ErrorKind::TypeError)]
+    #[case("undefined()", ErrorKind::TypeError)]
+    #[case("'use strict'; x = 1;", ErrorKind::ReferenceError)]
+    #[case("var [a] = 1;", ErrorKind::TypeError)]
+    #[case("var { a } = null;", ErrorKind::TypeError)]
+    #[case("new (() => {})", ErrorKind::TypeError)]
+    #[case("async function* g() {} g()", ErrorKind::Unsupported)]
+    #[case("var o = null; (o?.m)()", ErrorKind::TypeError)]
+    fn test_errors(#[case] source: &str, #[case] kind: ErrorKind) {
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == kind);
+    }
+
+    #[test]
+    fn test_call_depth() {
+        // Every call allowed must fit whatever stack the host thread has.
+        let thread = std::thread::Builder::new().stack_size(2 << 20).spawn(|| {
+            let mut engine = Engine::new();
+            let result = engine.evaluate_script("function f() { f(); } f()");
+            assert_matches!(result, Err(error) if error.kind == ErrorKind::RangeError);
+            assert_eq!(engine.evaluate_script("function g(n) { return n && g(n - 1) + 1; } g(200)"), Ok(Value::Number(200.0)));
+            let source = "function h(n) { return n && Reflect.apply(h, null, [n - 1]) + 1; } h(100)";
+            assert_eq!(engine.evaluate_script(source), Ok(Value::Number(100.0)));
+            let source = "function* i(n) { if (n) yield* i(n - 1); yield n; } [...i(100)].length";
+            assert_eq!(engine.evaluate_script(source), Ok(Value::Number(101.0)));
+            let result = engine.evaluate_script("class C { constructor() { new C(); } } new C()");
+            assert_matches!(result, Err(error) if error.kind == ErrorKind::RangeError);
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    // Calls in tail position of strict code do not nest, so recursion in
+    // tail position goes deeper than the call depth limit.
+    #[rstest]
+    #[case("function f(n) { return n ? f(n - 1) : 'done'; } f(10000)", Value::from("done"))]
+    #[case("var f = n => n === 0 || f(n - 1); f(10000)", Value::Boolean(true))]
+    #[case("function f(n, s) { if (n === 0) return s; return (0, f)(n - 1, s + n); } f(10000, 0)", Value::Number(50_005_000.0))]
+    #[case("function even(n) { return n === 0 || odd(n - 1); } function odd(n) { return n !== 0 && even(n - 1); } even(10001)", Value::Boolean(false))]
+    #[case("var o = { f(n) { return n ? this.f(n - 1) : this === o; } }; o.f(10000)", Value::Boolean(true))]
+    #[case("function f(n) { switch (n) { case 0: return 'done'; default: { return f(n - 1); } } } f(10000)", Value::from("done"))]
+    #[case("function f(n) { try { throw n; } catch (e) { return e ? f(e - 1) : 'done'; } } f(10000)", Value::from("done"))]
+    #[case("function g() { return this; } function f() { return g(); } f()", Value::Undefined)]
+    #[case("function g() { return { a: 1 }; } function F() { return g(); } new F().a", Value::Number(1.0))]
+    #[case("function g() { throw 1; } function f() { try { return g(); } catch (e) { return 'caught'; } } f()", Value::from("caught"))]
+    #[case("var log = ''; function g() { log += 'call '; } function f() { try { return g(); } finally { log += 'finally'; } } f(); log", Value::from("call finally"))]
+    #[case(
+        "var log = ''; function g() { log += 'call '; } function* items() { try { yield 1; } finally { log += 'close'; } }
+         function f() { for (var x of items()) return g(); } f(); log",
+        Value::from("call close")
+    )]
+    fn test_tail_calls(#[case] source: &str, #[case] expected: Value) {
+        assert_eq!(Engine::new().evaluate_script(&format!("'use strict'; {source}")), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("function f(n) { return n ? f(n - 1) : 'done'; } f(10000)")]
+    #[case("'use strict'; function f(n) { return n ? 1 + f(n - 1) : 0; } f(10000)")]
+    #[case("'use strict'; function f(n) { try { return n ? f(n - 1) : 'done'; } finally {} } f(10000)")]
+    #[case("'use strict'; function f(n) { for (var x of [1]) return n ? f(n - 1) : 'done'; } f(10000)")]
+    #[case("'use strict'; function* f(n) { return n ? yield* f(n - 1) : 'done'; } f(10000).next()")]
+    fn test_calls_not_in_tail_position(#[case] source: &str) {
+        assert_matches!(Engine::new().evaluate_script(source), Err(error) if error.kind == ErrorKind::RangeError);
+    }
+
+    #[rstest]
+    #[case("[", "]")]
+    #[case("(", ")")]
+    #[case("x => ", "")]
+    #[case("function f() {", "}")]
+    fn test_nesting_depth(#[case] open: &'static str, #[case] close: &'static str) {
+        // Walks over a syntax tree must not overflow a small native stack
+        // at any depth the parser allows.
+        let thread = std::thread::Builder::new().stack_size(2 << 20).spawn(move || {
+            let depth = DEFAULT_MAX_NESTING_DEPTH / 2;
+            let nested = format!("{}0{}", open.repeat(depth), close.repeat(depth));
+            assert_matches!(Engine::new().evaluate_script(&nested), Ok(_));
+            // Lowering of a generator body copies the tree.
+            let source = format!("function* g() {{ yield {nested} }} g().next()");
+            assert_matches!(Engine::new().evaluate_script(&source), Ok(_));
+        });
+        thread.unwrap().join().unwrap();
+    }
+
+    #[test]
+    fn test_thrown_values() {
+        let error = Engine::new().evaluate_script("throw 'boom';").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Thrown);
+        assert_eq!(error.value, Some(Value::from("boom")));
+        assert_eq!(error.range, Some(0..13));
+    }
+
+    #[test]
+    fn test_global_declarations() {
+        let mut engine = Engine::new();
+        assert_eq!(engine.evaluate_script("var v = 1; let l = 2; function f() { return v + l; }"), Ok(Value::Undefined));
+        assert_eq!(engine.evaluate_script("f()"), Ok(Value::Number(3.0)));
+        assert_eq!(engine.evaluate_script("'v' in globalThis && !('l' in globalThis)"), Ok(Value::Boolean(true)));
+        assert_matches!(engine.evaluate_script("var l;"), Err(error) if error.kind == ErrorKind::SyntaxError);
+        // A failed script declares nothing.
+        assert_matches!(engine.evaluate_script("let w = 1; let l = 2;"), Err(error) if error.kind == ErrorKind::SyntaxError);
+        assert_matches!(engine.evaluate_script("w"), Err(error) if error.kind == ErrorKind::ReferenceError);
+    }
+
+    #[test]
+    fn test_nested_functions_share_code() {
+        /// Peak memory of calling functions nested `depth` times.
+        fn nested_calls(depth: usize) -> isize {
+            let open = "(function () { var a = [1, 2, 3], b = { c: a, d: 'e' + a.length }; ";
+            let close = " return b; })();";
+            let source = format!("{}0;{}", open.repeat(depth), close.repeat(depth));
+            peak_bytes(|| assert_matches!(Engine::new().evaluate_script(&source), Ok(_)))
+        }
+
+        // Code of each function must not copy the functions nested into it,
+        // which grows memory with the square of the depth.
+        let (shallow, deep) = (nested_calls(50), nested_calls(200));
+        assert!(deep < shallow * 6, "depth 50: {shallow} bytes, depth 200: {deep} bytes");
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::grammar::{
+        ClassElement,
+        Declaration,
+        Expression,
+        Parser,
+        Statement,
+        StatementListItem,
+    };
+    use rstest::rstest;
+
+    fn parse(source: &str) -> Vec<StatementListItem> {
+        Parser::new(source).parse_script().unwrap().body
+    }
+
+    /// Name a node by its variant, like `ReturnStatement`, or by a variant
+    /// of an expression for expression statements.
+    fn describe(item: &StatementListItem) -> String {
+        let debug = match item {
+            StatementListItem::Statement(Statement::ExpressionStatement(expression, _)) => format!("{expression:?}"),
+            StatementListItem::Statement(statement) => format!("{statement:?}"),
+            StatementListItem::Declaration(declaration) => format!("{declaration:?}"),
+        };
+        let end = debug.find(['(', ' ']).unwrap_or(debug.len());
+        debug[..end].to_owned()
+    }
+
+    fn describe_all(items: &[StatementListItem]) -> Vec<String> {
+        items.iter().map(describe).collect()
+    }
+
+    #[rstest]
+    #[case("a = 1", &["AssignmentExpression"])]
+    #[case("a = 1\nb = 2", &["AssignmentExpression", "AssignmentExpression"])]
+    #[case("var a = 1\nlet b = 2\nconst c = 3", &["VariableStatement", "LexicalDeclaration", "LexicalDeclaration"])]
+    #[case("{ 1\n2 } 3", &["BlockStatement", "Literal"])]
+    #[case("a\n++b", &["IdentifierReference", "UpdateExpression"])]
+    #[case("a\n++\nb", &["IdentifierReference", "UpdateExpression"])]
+    #[case("a /* \n */ ++b", &["IdentifierReference", "UpdateExpression"])]
+    #[case("a++\nb", &["UpdateExpression", "IdentifierReference"])]
+    #[case("do {} while (a) b", &["DoWhileStatement", "IdentifierReference"])]
+    #[case("do ; while (a)", &["DoWhileStatement"])]
+    #[case("debugger\na", &["DebuggerStatement", "IdentifierReference"])]
+    #[case("throw a\nb", &["ThrowStatement", "IdentifierReference"])]
+    #[case("let\na = 1", &["LexicalDeclaration"])]
+    #[case("a => {}\n(b)", &["ArrowFunction", "ParenthesizedExpression"])]
+    #[case("(a) => {}\n(b)", &["ArrowFunction", "ParenthesizedExpression"])]
+    fn test_inserted(#[case] source: &str, #[case] expected: &[&str]) {
+        assert_eq!(describe_all(&parse(source)), expected);
+    }
+
+    /// A line terminator alone does not end a statement if the next line
+    /// continues it.
+    #[rstest]
+    #[case("a = b\n(c)", "AssignmentExpression", "CallExpression")]
+    #[case("a = b\n[c]", "AssignmentExpression", "MemberExpression")]
+    #[case("a = b\n/c/g.exec(d)", "AssignmentExpression", "BinaryExpression")]
+    #[case("a = b\n`c`", "AssignmentExpression", "TaggedTemplate")]
+    #[case("a = b + c\n(d + e).f()", "AssignmentExpression", "BinaryExpression")]
+    #[case("a = function () {}\n(function () {})()", "AssignmentExpression", "CallExpression")]
+    #[case("a\n.b", "MemberExpression", "")]
+    #[case("a\n? b\n: c", "ConditionalExpression", "")]
+    fn test_not_inserted(#[case] source: &str, #[case] statement: &str, #[case] value: &str) {
+        let body = parse(source);
+        assert_eq!(describe_all(&body), [statement]);
+        if let [StatementListItem::Statement(Statement::ExpressionStatement(Expression::AssignmentExpression(assignment), _))] =
+            &body[..]
+        {
+            let actual = format!("{:?}", assignment.value);
+            assert!(actual.starts_with(value), "{actual}");
+        }
+    }
+
+    #[test]
+    fn test_restricted_return() {
+        let body = parse("function f() { return\na }");
+        let [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))] = &body[..] else {
+            panic!("a function declaration is expected");
+        };
+        assert_eq!(describe_all(&function.body), ["ReturnStatement", "IdentifierReference"]);
+        assert_matches!(&function.body[0], StatementListItem::Statement(Statement::ReturnStatement(statement))
+            if statement.argument.is_none());
+
+        let body = parse("function f() { return /* a */ b }");
+        let [StatementListItem::Declaration(Declaration::FunctionDeclaration(function))] = &body[..] else {
+            panic!("a function declaration is expected");
+        };
+        assert_matches!(&function.body[..], [StatementListItem::Statement(Statement::ReturnStatement(statement))]
+            if statement.argument.is_some());
+    }
+
+    #[test]
+    fn test_restricted_labels() {
+        let body = parse("a: while (b) { continue\na; break\na }");
+        let [StatementListItem::Statement(Statement::LabelledStatement(_))] = &body[..] else {
+            panic!("a labelled statement is expected");
+        };
+        let source = "while (b) { continue\na; break\na }";
+        let [StatementListItem::Statement(Statement::WhileStatement(statement))] = &parse(source)[..] else {
+            panic!("a while statement is expected");
+        };
+        let Statement::BlockStatement(block) = &*statement.body else {
+            panic!("a block is expected");
+        };
+        assert_eq!(describe_all(&block.statements), [
+            "ContinueStatement",
+            "IdentifierReference",
+            "BreakStatement",
+            "IdentifierReference",
+        ]);
+        assert_matches!(&block.statements[0], StatementListItem::Statement(Statement::ContinueStatement(statement))
+            if statement.label.is_none());
+    }
+
+    #[test]
+    fn test_class_fields() {
+        let body = parse("class A { a\nb = c\n['d']\ne\n['f'] }");
+        let [StatementListItem::Declaration(Declaration::ClassDeclaration(class))] = &body[..] else {
+            panic!("a class declaration is expected");
+        };
+        // `c\n['d']` continues the initializer, while a computed name cannot
+        // follow a field name `e` without an initializer.
+        assert_eq!(class.elements.len(), 4);
+        let ClassElement::FieldDefinition { initializer: Some(initializer), .. } = &class.elements[1] else {
+            panic!("a field with an initializer is expected");
+        };
+        assert_matches!(&**initializer, Expression::MemberExpression(_));
+    }
+
+    #[rstest]
+    #[case("a b", ErrorCode::UnexpectedInput, 2..3)]
+    #[case("{ 1 2 } 3", ErrorCode::UnexpectedInput, 4..5)]
+    #[case("throw\na", ErrorCode::UnexpectedInput, 6..7)]
+    #[case("for (a; b\n) {}", ErrorCode::UnexpectedInput, 10..11)]
+    #[case("for (a\nb) {}", ErrorCode::UnexpectedInput, 7..8)]
+    #[case("if (a)\nelse b", ErrorCode::UnexpectedInput, 7..11)]
+    #[case("a\n=> b", ErrorCode::UnexpectedInput, 2..4)]
+    #[case("do a while (b)", ErrorCode::UnexpectedInput, 5..10)]
+    #[case("a = b\n++", ErrorCode::IncompleteInput, 8..8)]
+    #[case("class A { a b }", ErrorCode::UnexpectedInput, 12..13)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::engine::Engine;
+    use embedded_ecmascript::runtime::{from_js_value, to_js_value, ErrorKind, JsError, Value};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Mode {
+        Eco,
+        Boost(u8),
+        Limited { speed: f64 },
+        Pair(i32, i32),
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Config {
+        name: String,
+        enabled: bool,
+        retries: u32,
+        ratio: f32,
+        tags: Vec<String>,
+        limit: Option<i64>,
+        modes: Vec<Mode>,
+        thresholds: BTreeMap<String, f64>,
+        pair: (u8, char),
+        nothing: (),
+    }
+
+    fn config() -> Config {
+        Config {
+            name: "vehicle".to_owned(),
+            enabled: true,
+            retries: 3,
+            ratio: 0.5,
+            tags: vec!["a".to_owned(), "b".to_owned()],
+            limit: None,
+            modes: vec![Mode::Eco, Mode::Boost(2), Mode::Limited { speed: 30.0 }, Mode::Pair(-1, 1)],
+            thresholds: BTreeMap::from([("low".to_owned(), 0.1), ("high".to_owned(), 0.9)]),
+            pair: (7, 'x'),
+            nothing: (),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut engine = Engine::new();
+        let value = to_js_value(&config(), engine.context()).unwrap();
+        assert_eq!(from_js_value::<Config>(&value, engine.context()), Ok(config()));
+    }
+
+    #[test]
+    fn test_to_js_value() {
+        let mut engine = Engine::new();
+        let value = to_js_value(&config(), engine.context()).unwrap();
+        engine.global_object().create_data_property("config", value);
+        assert_eq!(engine.evaluate_script("config.name + config.limit + config.nothing + config.modes[0]"), Ok(Value::from("vehicleundefinednullEco")));
+        assert_eq!(engine.evaluate_script("config.modes[1].Boost + config.modes[2].Limited.speed + config.modes[3].Pair[0] + config.pair[1]"), Ok(Value::from("31x")));
+        let keyed = to_js_value(&BTreeMap::from([(1, "one")]), engine.context()).unwrap();
+        engine.global_object().create_data_property("keyed", keyed);
+        assert_eq!(engine.evaluate_script("keyed['1']"), Ok(Value::from("one")));
+    }
+
+    #[test]
+    fn test_from_js_value() {
+        let mut engine = Engine::new();
+        let mut convert = |source: &str| {
+            let value = engine.evaluate_script(source).unwrap();
+            from_js_value::<BTreeMap<String, Vec<u8>>>(&value, engine.context())
+        };
+        let expected = BTreeMap::from([("a".to_owned(), vec![1, 2]), ("b".to_owned(), vec![])]);
+        assert_eq!(convert("({ a: [1, 2], b: [] })"), Ok(expected));
+        let inherited = BTreeMap::from([("own".to_owned(), vec![])]);
+        assert_eq!(convert("function F() {} F.prototype.inherited = [1]; var o = new F(); o.own = []; o"), Ok(inherited));
+        assert_matches!(convert("({ a: [256] })"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert("({ a: [1.5] })"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert("({ a: () => 1 })"), Err(error) if error.kind == ErrorKind::TypeError);
+        assert_matches!(convert("({ get a() { throw 1; } })"), Err(error) if error.kind == ErrorKind::Thrown);
+    }
+
+    #[test]
+    fn test_from_js_value_errors() {
+        let mut engine = Engine::new();
+        let mut convert = |source: &str| -> Result<Config, JsError> {
+            let value = engine.evaluate_script(source).unwrap();
+            from_js_value(&value, engine.context())
+        };
+        let error = convert("({ name: 'x' })").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::TypeError);
+        assert!(error.message.contains("missing field"), "{}", error.message);
+        let value = Engine::new().evaluate_script("({ Boost: 1, Eco: 2 })").unwrap();
+        assert_matches!(from_js_value::<Mode>(&value, Engine::new().context()), Err(error) if error.kind == ErrorKind::TypeError);
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use claims::{assert_matches, assert_ok_eq};
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::lexical_grammar::{get_next_token_utf16, GoalSymbols, TokenKind};
+    use embedded_ecmascript::source_text::{
+        decode_utf16,
+        decode_utf8,
+        Encoding,
+        InputHandling,
+        NormalizedSource,
+        SourceFile,
+        Utf16Source,
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_well_formed_input(#[values(InputHandling::Strict, InputHandling::Lossy)] handling: InputHandling) {
+        let text = "a;\n// д 😀";
+        let units: Vec<u16> = text.encode_utf16().collect();
+        assert_ok_eq!(decode_utf8(text.as_bytes(), handling), text);
+        assert_ok_eq!(decode_utf16(&units, handling), text);
+    }
+
+    #[test]
+    fn test_ill_formed_utf8() {
+        let bytes = b"a;\n\xFFb";
+        assert_ok_eq!(decode_utf8(bytes, InputHandling::Lossy), "a;\n\u{FFFD}b");
+        assert_matches!(
+            decode_utf8(bytes, InputHandling::Strict),
+            Err(error) if error.code == ErrorCode::IllFormedInput && error.range == (3..6) && error.line == 2
+        );
+    }
+
+    #[test]
+    fn test_unpaired_surrogates() {
+        let units = [0x0061, 0xD800, 0x0062, 0xDC00];
+        assert_ok_eq!(decode_utf16(&units, InputHandling::Lossy), "a\u{FFFD}b\u{FFFD}");
+        assert_matches!(
+            decode_utf16(&units, InputHandling::Strict),
+            Err(error) if error.code == ErrorCode::IllFormedInput && error.range == (1..4) && error.column == 2
+        );
+    }
+
+    #[rstest]
+    #[case("a\r\nb", "a\nb", &[(0, 0), (1, 1), (2, 3), (3, 4)])]
+    #[case("a\rb", "a\nb", &[(1, 1), (2, 2)])]
+    #[case("a\u{2028}\u{2029}b", "a\n\nb", &[(1, 1), (2, 4), (3, 7), (4, 8)])]
+    #[case("\r\r\n\n", "\n\n\n", &[(1, 1), (2, 3), (3, 4)])]
+    fn test_line_ending_normalization(
+        #[case] source: &str,
+        #[case] expected: &str,
+        #[case] offsets: &[(usize, usize)],
+    ) {
+        let normalized = NormalizedSource::new(source);
+        assert_eq!(normalized.text, expected);
+        for &(offset, original) in offsets {
+            assert_eq!(normalized.original_offset(offset), original);
+        }
+    }
+
+    #[test]
+    fn test_normalized_error_mapping() {
+        let source = ";\r\n\u{2028})";
+        let normalized = NormalizedSource::new(source);
+        let error = embedded_ecmascript::grammar::parse(&normalized.text, false).unwrap_err();
+        let error = normalized.original_error(error);
+        assert_eq!(&source[error.range.clone()], ")");
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    fn test_utf16_offsets() {
+        let units: Vec<u16> = "aд😀b".encode_utf16().collect();
+        let source = Utf16Source::new(&units);
+        assert_eq!(source.text, "aд😀b");
+        for (unit_offset, byte_offset) in [(0, 0), (1, 1), (2, 3), (4, 7), (5, 8)] {
+            assert_eq!(source.utf8_offset(unit_offset), Some(byte_offset));
+            assert_eq!(source.utf16_offset(byte_offset), unit_offset);
+        }
+        assert_eq!(source.utf8_offset(3), None);
+        assert_eq!(source.utf8_offset(6), None);
+    }
+
+    #[test]
+    fn test_utf16_tokens() {
+        // `x = '<lone surrogate>' + 😀` with the surrogate kept in the literal.
+        let mut units: Vec<u16> = "x = '".encode_utf16().collect();
+        units.push(0xD800);
+        units.extend("' /* 😀 */ +".encode_utf16());
+        let source = Utf16Source::new(&units);
+        let mut offset = 0;
+        let mut tokens = Vec::new();
+        while offset < units.len() {
+            let (token, range) = get_next_token_utf16(&source, offset, GoalSymbols::InputElementDiv).unwrap();
+            tokens.push((token.kind(), range.clone()));
+            offset = range.end;
+        }
+        assert_eq!(tokens[4], (TokenKind::StringLiteral, 4..7));
+        assert_eq!(tokens[6], (TokenKind::MultiLineComment, 8..16));
+        assert_eq!(tokens.last(), Some(&(TokenKind::Addition, 17..18)));
+        assert_eq!(source.code_units(4..7), [0x0027, 0xD800, 0x0027]);
+    }
+
+    #[test]
+    fn test_utf16_errors() {
+        let units: Vec<u16> = "😀 @".encode_utf16().collect();
+        let source = Utf16Source::new(&units);
+        assert_matches!(
+            get_next_token_utf16(&source, 0, GoalSymbols::InputElementDiv),
+            Err(error) if error.range.start == 0 && error.code == ErrorCode::UnexpectedInput
+        );
+        assert_matches!(
+            get_next_token_utf16(&source, 3, GoalSymbols::InputElementDiv),
+            Err(error) if error.range.start == 3 && error.column == 3
+        );
+    }
+
+    #[rstest]
+    #[case(b"#!x\n1", Encoding::Utf8)]
+    #[case(b"\xEF\xBB\xBF#!x\n1", Encoding::Utf8)]
+    #[case(b"\xFF\xFE#\x00!\x00x\x00\n\x001\x00", Encoding::Utf16LittleEndian)]
+    #[case(b"\xFE\xFF\x00#\x00!\x00x\x00\n\x001", Encoding::Utf16BigEndian)]
+    fn test_source_file_encodings(#[case] bytes: &[u8], #[case] encoding: Encoding) {
+        let file = SourceFile::from_bytes(bytes, InputHandling::Strict).unwrap();
+        assert_eq!(file, SourceFile { text: "#!x\n1".to_owned(), encoding });
+        let kinds: Vec<_> = file.tokens().map(|token| token.unwrap().0.kind()).collect();
+        assert_eq!(kinds, [TokenKind::HashbangComment, TokenKind::LineTerminator, TokenKind::NumericLiteral]);
+    }
+
+    #[test]
+    fn test_source_file_truncated_utf16() {
+        let bytes = b"\xFF\xFEa\x00b";
+        assert_ok_eq!(
+            SourceFile::from_bytes(bytes, InputHandling::Lossy),
+            SourceFile { text: "a\u{FFFD}".to_owned(), encoding: Encoding::Utf16LittleEndian }
+        );
+        assert_matches!(
+            SourceFile::from_bytes(bytes, InputHandling::Strict),
+            Err(error) if error.code == ErrorCode::IllFormedInput && error.range == (1..4)
+        );
+    }
+}
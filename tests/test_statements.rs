@@ -0,0 +1,263 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::ErrorCode;
+    use embedded_ecmascript::grammar::{
+        Binding,
+        BindingIdentifier,
+        BindingProperty,
+        Declaration,
+        Expression,
+        ForInit,
+        ForTarget,
+        LabelledItem,
+        Parser,
+        Statement,
+        StatementListItem,
+        VariableKind,
+    };
+    use rstest::rstest;
+
+    fn parse(source: &str) -> Vec<StatementListItem> {
+        Parser::new(source).parse_script().unwrap().body
+    }
+
+    fn parse_statement(source: &str) -> Statement {
+        let mut body = parse(source);
+        assert_eq!(body.len(), 1);
+        match body.swap_remove(0) {
+            StatementListItem::Statement(statement) => statement,
+            StatementListItem::Declaration(declaration) => panic!("unexpected declaration {declaration:?}"),
+        }
+    }
+
+    #[test]
+    fn test_empty_script() {
+        assert!(parse("").is_empty());
+        assert!(parse(" /* a */ \n").is_empty());
+        assert_eq!(parse(";;").len(), 2);
+    }
+
+    #[rstest]
+    #[case("{}", "BlockStatement")]
+    #[case(";", "EmptyStatement")]
+    #[case("a;", "ExpressionStatement")]
+    #[case("var a;", "VariableStatement")]
+    #[case("if (a) b;", "IfStatement")]
+    #[case("do a; while (b);", "DoWhileStatement")]
+    #[case("while (a) b;", "WhileStatement")]
+    #[case("for (;;) a;", "ForStatement")]
+    #[case("for (a in b) c;", "ForInStatement")]
+    #[case("for (a of b) c;", "ForOfStatement")]
+    #[case("while (a) continue;", "WhileStatement")]
+    #[case("with (a) b;", "WithStatement")]
+    #[case("switch (a) {}", "SwitchStatement")]
+    #[case("a: b;", "LabelledStatement")]
+    #[case("throw a;", "ThrowStatement")]
+    #[case("try {} catch {}", "TryStatement")]
+    #[case("debugger;", "DebuggerStatement")]
+    fn test_statement_kinds(#[case] source: &str, #[case] kind: &str) {
+        let statement = format!("{:?}", parse_statement(source));
+        assert!(statement.starts_with(kind), "{statement}");
+    }
+
+    #[test]
+    fn test_if_statements() {
+        let Statement::IfStatement(statement) = parse_statement("if (a) if (b) c; else d;") else {
+            panic!("an if statement is expected");
+        };
+        assert!(statement.alternative.is_none());
+        assert_matches!(*statement.consequent, Statement::IfStatement(inner) if inner.alternative.is_some());
+    }
+
+    #[test]
+    fn test_variable_declarations() {
+        let Statement::VariableStatement(list) = parse_statement("var a, b = 1, [c, , ...d] = e, { f, g: h = 2, ...i } = j;")
+        else {
+            panic!("a variable statement is expected");
+        };
+        assert_eq!(list.kind, VariableKind::Var);
+        assert_eq!(list.declarations.len(), 4);
+        assert!(list.declarations[0].initializer.is_none());
+        assert!(list.declarations[1].initializer.is_some());
+        assert_matches!(&list.declarations[2].target, Binding::ArrayBindingPattern(pattern)
+            if pattern.elements.len() == 2 && pattern.elements[1].is_none() && pattern.rest.is_some());
+        assert_matches!(&list.declarations[3].target, Binding::ObjectBindingPattern(pattern)
+            if pattern.properties.len() == 2 && pattern.rest.as_ref().is_some_and(|rest| rest.name == "i"));
+        let Binding::ObjectBindingPattern(pattern) = &list.declarations[3].target else {
+            unreachable!();
+        };
+        assert_matches!(&pattern.properties[0], BindingProperty::SingleNameBinding { name, initializer: None }
+            if name.name == "f");
+        assert_matches!(&pattern.properties[1], BindingProperty::Property { value, .. }
+            if value.initializer.is_some());
+    }
+
+    #[test]
+    fn test_lexical_declarations() {
+        let body = parse("let a = 1; const b = 2, c = 3; let = 4; let [d] = e;");
+        assert_matches!(&body[0], StatementListItem::Declaration(Declaration::LexicalDeclaration(list))
+            if list.kind == VariableKind::Let);
+        assert_matches!(&body[1], StatementListItem::Declaration(Declaration::LexicalDeclaration(list))
+            if list.kind == VariableKind::Const && list.declarations.len() == 2);
+        assert_matches!(&body[2], StatementListItem::Statement(Statement::ExpressionStatement(
+            Expression::AssignmentExpression(_),
+            _
+        )));
+        assert_matches!(&body[3], StatementListItem::Declaration(_));
+    }
+
+    #[test]
+    fn test_for_statements() {
+        let Statement::ForStatement(statement) = parse_statement("for (var i = 0, j; i < 10; i++) {}") else {
+            panic!("a for statement is expected");
+        };
+        assert_matches!(&statement.init, Some(ForInit::VariableDeclarationList(list)) if list.declarations.len() == 2);
+        assert!(statement.condition.is_some() && statement.update.is_some());
+
+        let Statement::ForStatement(statement) = parse_statement("for (a = (b in c) ? 1 : 2;;);") else {
+            panic!("a for statement is expected");
+        };
+        assert_matches!(statement.init, Some(ForInit::Expression(Expression::AssignmentExpression(_))));
+        assert!(statement.condition.is_none() && statement.update.is_none());
+
+        assert_matches!(parse_statement("for ((a in b);;);"), Statement::ForStatement(_));
+        assert_matches!(parse_statement("for (let;;);"), Statement::ForStatement(_));
+    }
+
+    #[test]
+    fn test_for_in_of_statements() {
+        let Statement::ForOfStatement(statement) = parse_statement("for (const [a, b] of c) ;") else {
+            panic!("a for-of statement is expected");
+        };
+        assert_matches!(&statement.target, ForTarget::ForDeclaration {
+            kind: VariableKind::Const,
+            binding: Binding::ArrayBindingPattern(_),
+            ..
+        });
+
+        let Statement::ForInStatement(statement) = parse_statement("for (a.b in c, d) ;") else {
+            panic!("a for-in statement is expected");
+        };
+        assert_matches!(&statement.target, ForTarget::Expression(Expression::MemberExpression(_)));
+        assert_matches!(&statement.source, Expression::CommaExpression(_));
+
+        assert_matches!(parse_statement("for (var a in b);"), Statement::ForInStatement(_));
+        assert_matches!(parse_statement("for (let in a);"), Statement::ForInStatement(_));
+        assert_matches!(parse_statement("for (let of of a);"), Statement::ForOfStatement(_));
+    }
+
+    #[test]
+    fn test_switch_statements() {
+        let Statement::SwitchStatement(statement) = parse_statement("switch (a) { case 1: case 2: b; break; default: c; }")
+        else {
+            panic!("a switch statement is expected");
+        };
+        let statements: Vec<_> = statement.cases.iter().map(|case| case.statements.len()).collect();
+        assert_eq!(statements, [0, 2, 1]);
+        assert!(statement.cases[2].test.is_none());
+    }
+
+    #[test]
+    fn test_try_statements() {
+        let Statement::TryStatement(statement) = parse_statement("try { a; } catch ({ b }) {} finally { c; }") else {
+            panic!("a try statement is expected");
+        };
+        assert_eq!(statement.block.statements.len(), 1);
+        assert_matches!(&statement.handler, Some(handler)
+            if matches!(handler.parameter, Some(Binding::ObjectBindingPattern(_))));
+        assert!(statement.finalizer.is_some());
+
+        assert_matches!(parse_statement("try {} finally {}"), Statement::TryStatement(statement)
+            if statement.handler.is_none() && statement.finalizer.is_some());
+    }
+
+    #[test]
+    fn test_jump_statements() {
+        assert_matches!(parse_statement("a: for (;;) { continue a; }"), Statement::LabelledStatement(statement)
+            if statement.label == "a");
+        assert_matches!(parse_statement("a: { break a; }"), Statement::LabelledStatement(statement)
+            if matches!(&*statement.body, LabelledItem::Statement(Statement::BlockStatement(block))
+                if matches!(&block.statements[0], StatementListItem::Statement(Statement::BreakStatement(statement))
+                    if statement.label.as_deref() == Some("a"))));
+    }
+
+    #[rstest]
+    #[case("a;", 0..2)]
+    #[case("a\n", 0..1)]
+    #[case(" ; ", 1..2)]
+    #[case("if (a) b; else { c }", 0..20)]
+    #[case("for (const a of b) {}", 0..21)]
+    #[case("try {} catch (a) {} finally {}", 0..30)]
+    #[case("a: b", 0..4)]
+    #[case("let a = 1, b;", 0..13)]
+    #[case("function f() {}", 0..15)]
+    #[case("class A { static {} }", 0..21)]
+    fn test_ranges(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let body = parse(source);
+        assert_eq!(body[0].range(), range);
+    }
+
+    #[test]
+    fn test_script_range() {
+        let script = Parser::new(" a; /* b */ ").parse_script().unwrap();
+        assert_eq!(script.range, 0..12);
+        let Statement::ForOfStatement(statement) = parse_statement("for (let [a] of b);") else {
+            panic!("a for-of statement is expected");
+        };
+        assert_eq!(statement.target.range(), 5..12);
+        assert_matches!(&statement.target, ForTarget::ForDeclaration { binding, .. } if binding.range() == (9..12));
+    }
+
+    #[rstest]
+    #[case("a +", ErrorCode::IncompleteInput, 3..3)]
+    #[case("{", ErrorCode::IncompleteInput, 1..1)]
+    #[case("if a;", ErrorCode::UnexpectedInput, 3..4)]
+    #[case("return;", ErrorCode::UnexpectedInput, 0..6)]
+    #[case("const a;", ErrorCode::UnexpectedInput, 7..8)]
+    #[case("let [a];", ErrorCode::UnexpectedInput, 7..8)]
+    #[case("if (a) let [b] = c;", ErrorCode::UnexpectedInput, 7..10)]
+    #[case("for (var a = 1 in b);", ErrorCode::UnexpectedInput, 15..17)]
+    #[case("for (var a, b of c);", ErrorCode::UnexpectedInput, 14..16)]
+    #[case("for (a + b in c);", ErrorCode::UnexpectedInput, 5..10)]
+    #[case("for (a = b in c;;);", ErrorCode::UnexpectedInput, 5..10)]
+    #[case("for (let.a of b);", ErrorCode::UnexpectedInput, 5..10)]
+    #[case("for (a of b, c);", ErrorCode::UnexpectedInput, 11..12)]
+    #[case("switch (a) { default: default: }", ErrorCode::UnexpectedInput, 22..29)]
+    #[case("try {}", ErrorCode::IncompleteInput, 6..6)]
+    #[case("do ; while (a", ErrorCode::IncompleteInput, 13..13)]
+    fn test_errors(#[case] source: &str, #[case] code: ErrorCode, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (code, range));
+    }
+
+    fn names<'tree>(identifiers: &[&'tree BindingIdentifier]) -> Vec<&'tree str> {
+        identifiers.iter().map(|identifier| identifier.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_declared_names() {
+        let source = "var a, [b, { c, d: e, ...f }] = g; let h; function i() { var j; } class k {} \
+            { let l; var m; function n() {} } for (var o of p) { var q; } try {} catch (r) { var s; } \
+            t: u: var v; switch (w) { case 1: var x; let y; }";
+        let script = Parser::new(source).parse_script().unwrap();
+        assert_eq!(names(&script.lexically_declared_names()), ["h", "k"]);
+        assert_eq!(names(&script.var_declared_names()), ["a", "b", "c", "e", "f", "i", "m", "o", "q", "s", "v", "x"]);
+
+        let StatementListItem::Statement(Statement::BlockStatement(block)) = &script.body[4] else {
+            panic!("a block is expected");
+        };
+        assert_eq!(names(&block.lexically_declared_names()), ["l", "n"]);
+        assert_eq!(names(&block.var_declared_names()), ["m"]);
+        assert!(script.body[7].lexically_declared_names().is_empty());
+        assert_eq!(names(&script.body[7].top_level_var_declared_names()), ["v"]);
+        let StatementListItem::Statement(Statement::SwitchStatement(statement)) = &script.body[8] else {
+            panic!("a switch statement is expected");
+        };
+        assert_eq!(names(&statement.lexically_declared_names()), ["y"]);
+        let StatementListItem::Declaration(declaration) = &script.body[1] else {
+            panic!("a declaration is expected");
+        };
+        assert_eq!(names(&declaration.bound_names()), ["h"]);
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::grammar::{
+        ClassElement,
+        ConciseBody,
+        Declaration,
+        Expression,
+        Function,
+        Parser,
+        Statement,
+        StatementListItem,
+    };
+    use embedded_ecmascript::ErrorCode;
+    use rstest::rstest;
+
+    fn function(item: &StatementListItem) -> &Function {
+        match item {
+            StatementListItem::Declaration(Declaration::FunctionDeclaration(function)) => function,
+            _ => panic!("a function declaration is expected"),
+        }
+    }
+
+    #[rstest]
+    #[case("'use strict';", true)]
+    #[case("\"use strict\"", true)]
+    #[case("'a'; 'use strict'; b;", true)]
+    #[case("'use\\x20strict';", false)]
+    #[case("'use strict' + 1;", false)]
+    #[case("a; 'use strict';", false)]
+    #[case("('use strict');", false)]
+    #[case("", false)]
+    fn test_directive_prologue(#[case] source: &str, #[case] is_strict: bool) {
+        assert_eq!(Parser::new(source).parse_script().unwrap().is_strict, is_strict);
+    }
+
+    #[test]
+    fn test_modules() {
+        assert!(Parser::new_module("").parse_script().unwrap().is_strict);
+        assert_eq!(Parser::new_module("with (a) {}").parse_script().unwrap_err().code, ErrorCode::EarlyError);
+    }
+
+    #[test]
+    fn test_functions() {
+        let body = Parser::new("function a() { 'use strict'; function b() {} } function c() { (() => {}); }")
+            .parse_script()
+            .unwrap()
+            .body;
+        let outer = function(&body[0]);
+        assert!(outer.is_strict);
+        assert!(function(&outer.body[1]).is_strict);
+        let sloppy = function(&body[1]);
+        assert!(!sloppy.is_strict);
+        assert_matches!(&sloppy.body[0], StatementListItem::Statement(Statement::ExpressionStatement(
+            Expression::ParenthesizedExpression(expression),
+            _
+        )) if matches!(&*expression.expression, Expression::ArrowFunction(arrow) if !arrow.is_strict));
+
+        // The directive affects nothing after the body.
+        let script = Parser::new("function a() { 'use strict' } with (b) {}").parse_script().unwrap();
+        assert!(!script.is_strict);
+        assert_eq!(script.body.len(), 2);
+    }
+
+    #[test]
+    fn test_classes() {
+        let body = Parser::new("class A { m() {} } with (a) {}").parse_script().unwrap().body;
+        let StatementListItem::Declaration(Declaration::ClassDeclaration(class)) = &body[0] else {
+            panic!("a class declaration is expected");
+        };
+        assert_matches!(&class.elements[0], ClassElement::MethodDefinition { method, .. } if method.is_strict);
+        assert_eq!(body.len(), 2);
+    }
+
+    #[test]
+    fn test_arrow_functions() {
+        let body = Parser::new("a => { 'use strict'; };").parse_script().unwrap().body;
+        assert_matches!(&body[0], StatementListItem::Statement(Statement::ExpressionStatement(
+            Expression::ArrowFunction(arrow),
+            _
+        )) if arrow.is_strict && matches!(&arrow.body, ConciseBody::FunctionBody(statements, _) if statements.len() == 1));
+    }
+
+    #[test]
+    fn test_deferred_bodies() {
+        let source = "'use strict'; function a() { with (b) {} }";
+        let mut parser = Parser::new(source).with_deferred_function_bodies();
+        let body = parser.parse_script().unwrap().body;
+        let deferred = function(&body[1]).deferred_body.clone().unwrap();
+        let error = Parser::new(source).parse_deferred_body(&deferred).unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, 29..33));
+
+        // A body starting with a directive is parsed at once.
+        let mut parser = Parser::new("function a() { 'use strict'; }").with_deferred_function_bodies();
+        let body = parser.parse_script().unwrap().body;
+        assert!(function(&body[0]).deferred_body.is_none());
+        assert!(function(&body[0]).is_strict);
+    }
+
+    #[cfg(feature = "annex-b")]
+    #[test]
+    fn test_no_legacy_block_functions() {
+        let body = Parser::new("'use strict'; { function a() {} }").parse_script().unwrap().body;
+        assert_matches!(&body[1], StatementListItem::Statement(Statement::BlockStatement(block))
+            if !function(&block.statements[0]).is_legacy_block_function);
+        assert!(Parser::new("'use strict'; if (a) function b() {}").parse_script().is_err());
+    }
+
+    #[rstest]
+    #[case("'use strict'; with (a) {}", 14..18)]
+    #[case("'use strict'; delete a;", 14..22)]
+    #[case("'use strict'; delete ((a));", 14..26)]
+    #[case("'use strict'; var eval;", 18..22)]
+    #[case("'use strict'; let arguments;", 18..27)]
+    #[case("'use strict'; try {} catch (eval) {}", 28..32)]
+    #[case("'use strict'; var public;", 18..24)]
+    #[case("'use strict'; a = static;", 18..24)]
+    #[case("'use strict'; function a(b, b) {}", 28..29)]
+    #[case("function a(b, b) { 'use strict'; }", 14..15)]
+    #[case("function eval() { 'use strict'; }", 9..13)]
+    #[case("function a(interface) { 'use strict'; }", 11..20)]
+    #[case("function a(b = 1) { 'use strict'; }", 10..17)]
+    #[case("(a = 1) => { 'use strict'; };", 0..7)]
+    #[case("(a, a) => 1;", 4..5)]
+    #[case("({ m(a, a) {} });", 8..9)]
+    #[case("function a(b, [b]) {}", 15..16)]
+    #[case("class eval {}", 6..10)]
+    #[case("class A { m() { with (a) {} } }", 16..20)]
+    #[case("'use strict'; a: function b() {}", 17..32)]
+    fn test_early_errors(#[case] source: &str, #[case] range: std::ops::Range<usize>) {
+        let error = Parser::new(source).parse_script().unwrap_err();
+        assert_eq!((error.code, error.range), (ErrorCode::EarlyError, range));
+    }
+
+    #[rstest]
+    #[case("'use strict'; 010;")]
+    #[case("'use strict'; '\\07';")]
+    #[case("'\\07'; 'use strict';")]
+    #[case("function a() { 'use strict'; 08; }")]
+    fn test_strict_tokens(#[case] source: &str) {
+        assert!(Parser::new(source).parse_script().is_err());
+        let sloppy = source.replace("'use strict';", "");
+        assert!(Parser::new(&sloppy).parse_script().is_ok(), "{sloppy}");
+    }
+
+    #[rstest]
+    #[case("function a(b, b) {}")]
+    #[case("var eval; delete a; with (a) {}")]
+    #[case("function a() { 'use strict'; } var public;")]
+    #[case("({ 'use strict': 1 }); with (a) {}")]
+    fn test_non_strict(#[case] source: &str) {
+        assert!(Parser::new(source).parse_script().is_ok());
+    }
+}
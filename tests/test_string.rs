@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use embedded_ecmascript::runtime::JsString;
+    use rstest::rstest;
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    #[rstest]
+    #[case("")]
+    #[case("short")]
+    #[case("Latin-1 only, but too long to be stored inline: ÿ")]
+    #[case("ŝ")]
+    #[case("a mix of ASCII and 😀")]
+    fn test_round_trip(#[case] text: &str) {
+        let string = JsString::from(text);
+        let units: Vec<u16> = text.encode_utf16().collect();
+        assert_eq!(string.len(), units.len());
+        assert_eq!(string.code_units().collect::<Vec<_>>(), units);
+        assert_eq!(string.to_std_string().as_deref(), Some(text));
+        assert_eq!(string.to_string(), text);
+        assert_eq!(JsString::from_code_units(&units), string);
+        assert_eq!(string, text);
+    }
+
+    #[test]
+    fn test_unpaired_surrogates() {
+        let string = JsString::from_code_units(&[0x61, 0xD800, 0x62]);
+        assert_eq!(string.len(), 3);
+        assert_eq!(string.code_unit_at(1), Some(0xD800));
+        assert_eq!(string.code_unit_at(3), None);
+        assert_eq!(string.to_std_string(), None);
+        assert_eq!(string.to_string(), "a\u{FFFD}b");
+        assert_eq!(format!("{string:?}"), "\"a\\u{d800}b\"");
+        assert_ne!(string, JsString::from("a\u{FFFD}b"));
+
+        let pair = JsString::from("😀");
+        let high = pair.substring(0, 1);
+        let low = pair.substring(1, 2);
+        assert_eq!(high.concat(&low), pair);
+    }
+
+    #[test]
+    fn test_representations() {
+        // Strings are equal and hash the same regardless of how they are
+        // built.
+        let long = "x".repeat(100);
+        let built = JsString::from(&long[..50]).concat(&JsString::from(&long[50..]));
+        let narrowed = JsString::from(format!("{long}😀").as_str()).substring(0, 100);
+        assert_eq!(built, JsString::from(long.as_str()));
+        assert_eq!(narrowed, built);
+        assert_eq!(HashSet::from([built, narrowed]).len(), 1);
+        assert!(JsString::new().is_empty());
+        assert_eq!(JsString::default(), JsString::from(""));
+    }
+
+    #[rstest]
+    #[case("abcabc", "c", 0, Some(2))]
+    #[case("abcabc", "c", 3, Some(5))]
+    #[case("abcabc", "bc", 5, None)]
+    #[case("abc", "", 3, Some(3))]
+    #[case("abc", "", 4, None)]
+    #[case("ab", "abc", 0, None)]
+    #[case("😀a", "a", 0, Some(2))]
+    fn test_index_of(#[case] string: &str, #[case] search: &str, #[case] from: usize, #[case] expected: Option<usize>) {
+        assert_eq!(JsString::from(string).index_of(&JsString::from(search), from), expected);
+    }
+
+    // Code units are compared, not code points.
+    #[rstest]
+    #[case("😀", "\u{FFFD}")]
+    #[case("10", "9")]
+    #[case("a", "ab")]
+    #[case("ÿ", "ŝ")]
+    fn test_order(#[case] less: &str, #[case] greater: &str) {
+        assert_eq!(JsString::from(less).cmp(&JsString::from(greater)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_substring_and_trim() {
+        let string = JsString::from("  text\u{3000}");
+        assert_eq!(string.substring(2, 6), "text");
+        assert_eq!(string.substring(5, 100), "t\u{3000}");
+        assert_eq!(string.substring(4, 2), "");
+        assert_eq!(string.trim(char::is_whitespace), "text");
+        assert_eq!(JsString::from("   ").trim(char::is_whitespace), "");
+    }
+
+    // A string built by repeated concatenation is kept as nested ropes,
+    // which must neither flatten nor drop recursively.
+    #[rstest]
+    #[case("x", "x")]
+    #[case("x", "ŝ")]
+    #[case("😀", "a")]
+    fn test_repeated_concat(#[case] part: &str, #[case] last: &str) {
+        const COUNT: usize = 100_000;
+        let part = JsString::from(part);
+        let mut string = JsString::default();
+        for _ in 0..COUNT {
+            string = string.concat(&part);
+        }
+        let prefix = string.clone();
+        drop(string.concat(&part));
+        string = string.concat(&JsString::from(last));
+        assert_eq!(string.len(), COUNT * part.len() + JsString::from(last).len());
+
+        let expected = JsString::from(format!("{}{last}", part.to_string().repeat(COUNT)));
+        assert_eq!(string.code_unit_at(string.len() - 1), expected.code_unit_at(expected.len() - 1));
+        assert_eq!(string, expected);
+        assert_eq!(HashSet::from([string.clone(), expected]).len(), 1);
+        assert_eq!(prefix.substring(0, part.len()), part);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use claims::{assert_err, assert_ok, assert_ok_eq};
+    use embedded_ecmascript::lexical_grammar::{get_next_compact_token, CompactToken, GoalSymbols, Span, TokenKind};
+    use embedded_ecmascript::token_cache::{load, save, VERSION};
+    use rstest::rstest;
+    use std::io::ErrorKind;
+
+    fn tokenize(source: &str) -> Vec<CompactToken> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        while offset < source.len() {
+            let token = get_next_compact_token(source, offset, GoalSymbols::InputElementDiv).unwrap();
+            offset = token.span.end;
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_token_kind_numbering() {
+        for (index, kind) in TokenKind::ALL.iter().enumerate() {
+            assert_eq!(usize::from(u16::from(*kind)), index);
+            assert_ok_eq!(TokenKind::try_from(u16::from(*kind)), *kind);
+        }
+        assert_err!(TokenKind::try_from(u16::MAX));
+    }
+
+    /// 64-bit FNV-1a of `TokenKind` names in numbering order for each
+    /// [`VERSION`]; a kind added, removed or moved changes it.
+    const KIND_NUMBERING_HASHES: [(u8, u64); 1] = [(1, 0xf2b9_f343_71ce_7b8a)];
+
+    #[test]
+    fn test_token_kind_numbering_matches_version() {
+        let hash = TokenKind::ALL.iter().flat_map(|kind| format!("{kind:?}\n").into_bytes()).fold(
+            0xcbf2_9ce4_8422_2325,
+            |hash: u64, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3),
+        );
+        let pinned = KIND_NUMBERING_HASHES.iter().find(|(version, _)| *version == VERSION);
+        assert_eq!(pinned, Some(&(VERSION, hash)), "TokenKind numbering changed; bump VERSION and pin the new hash");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = "if (a) {\n  b /= c; // comment\n}";
+        let tokens = tokenize(source);
+        let mut buffer = Vec::new();
+        assert_ok!(save(source, &tokens, &mut buffer));
+        assert_ok_eq!(load(source, buffer.as_slice()), tokens);
+    }
+
+    #[rstest]
+    #[case("if (b) {\n  b /= c; // comment\n}")]
+    #[case("if (a) {\n  b /= c; // comment\n}\n")]
+    #[case("")]
+    fn test_stale_streams(#[case] changed: &str) {
+        let source = "if (a) {\n  b /= c; // comment\n}";
+        let mut buffer = Vec::new();
+        save(source, &tokenize(source), &mut buffer).unwrap();
+        assert_eq!(load(changed, buffer.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_invalid_streams() {
+        let out_of_order = [
+            CompactToken { kind: TokenKind::Dot, span: Span::new(5, 6) },
+            CompactToken { kind: TokenKind::Dot, span: Span::new(0, 1) },
+        ];
+        let source = "a.b.c.d";
+        let error = save(source, &out_of_order, Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        let error = save("a", &out_of_order[..1], Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let mut buffer = Vec::new();
+        save(source, &out_of_order[..1], &mut buffer).unwrap();
+        let mut wrong_version = buffer.clone();
+        wrong_version[4] = u8::MAX;
+        assert_eq!(load(source, wrong_version.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+        assert_eq!(load(source, &b"JUNK"[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(load(source, &buffer[..buffer.len() - 1]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use claims::assert_matches;
+    use embedded_ecmascript::lexical_grammar::{CompactToken, Lexer, Span, TokenKind};
+    use embedded_ecmascript::token_stream::{StreamError, StreamingLexer};
+    use embedded_ecmascript::ErrorCode;
+    use rstest::rstest;
+    use std::io::{self, Read};
+
+    /// A reader returning a given number of bytes at most per call.
+    struct Trickle<'data> {
+        data: &'data [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let count = self.step.min(buffer.len()).min(self.data.len());
+            buffer[..count].copy_from_slice(&self.data[..count]);
+            self.data = &self.data[count..];
+            Ok(count)
+        }
+    }
+
+    /// A reader of a text followed by a byte repeated up to a given total
+    /// length, counting bytes read.
+    struct Padded {
+        head: &'static str,
+        filler: u8,
+        length: usize,
+        read: usize,
+    }
+
+    impl Read for Padded {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let count = buffer.len().min(self.length - self.read);
+            for (index, byte) in buffer[..count].iter_mut().enumerate() {
+                *byte = self.head.as_bytes().get(self.read + index).copied().unwrap_or(self.filler);
+            }
+            self.read += count;
+            Ok(count)
+        }
+    }
+
+    fn stream(source: &[u8], step: usize) -> Vec<Result<(CompactToken, String), StreamError>> {
+        StreamingLexer::new(Trickle { data: source, step }).collect()
+    }
+
+    #[rstest]
+    #[case("a = b / c;\nx = /re/g.test(y)")]
+    #[case("/* multi\nline */ a\r\nb // tail")]
+    #[case("1e5 + 0x1F - 1..toString() + a...b")]
+    #[case("`a${ {b: `c${d}`} }e` / 2")]
+    #[case("'str\\\ning' + \"\\u{1F600}\" + ಠ_ಠ")]
+    #[case("#!/usr/bin/env node\nlet   \t x = 1")]
+    #[case("a\\u0062c\\u{64}")]
+    #[case("a <!-- b --> c")]
+    #[case("if\\u0061 = 'a b' + /c/gi")]
+    fn test_same_as_lexer(#[case] source: &str, #[values(1, 2, 3, 7, 4096)] step: usize) {
+        let expected: Vec<_> = Lexer::new(source)
+            .map(|token| {
+                let (token, span) = token.unwrap();
+                (CompactToken { kind: token.kind(), span }, span.source_text(source).to_owned())
+            })
+            .collect();
+        let streamed: Vec<_> = stream(source.as_bytes(), step)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_long_line() {
+        let source = "a += 1.5 / b...c; ".repeat(1000);
+        let mut reader = Trickle { data: source.as_bytes(), step: 3 };
+        let mut lexer = StreamingLexer::new(&mut reader);
+        for expected in ["a", " ", "+=", " ", "1.5", " ", "/", " ", "b", "...", "c", ";"] {
+            assert_matches!(lexer.next(), Some(Ok((_, text))) if text == expected);
+        }
+        drop(lexer);
+        // Tokens come out as soon as the text after them rules out longer ones
+        // rather than at the end of the line.
+        assert!(reader.data.len() > source.len() - 30);
+    }
+
+    #[test]
+    fn test_split_utf8_sequence() {
+        let streamed = stream("é".as_bytes(), 1);
+        assert_matches!(
+            streamed.as_slice(),
+            [Ok((CompactToken { kind: TokenKind::IdentifierName, span: Span { start: 0, end: 2 } }, text))] if text == "é"
+        );
+    }
+
+    #[rstest]
+    #[case(b"a\n\xFF", 2, (2, 1))]
+    #[case(b"ab\xC3", 2, (1, 3))]
+    fn test_ill_formed_utf8(#[case] source: &[u8], #[case] position: usize, #[case] line_column: (usize, usize)) {
+        let streamed = stream(source, 1);
+        assert_matches!(
+            streamed.last(),
+            Some(Err(StreamError::Source(error)))
+                if error.code == ErrorCode::IllFormedInput
+                    && error.range.start == position
+                    && (error.line, error.column) == line_column
+        );
+    }
+
+    #[rstest]
+    #[case("a\r\n  @", 5, (2, 3))]
+    #[case("x = 1;\n\"unterminated", 7, (2, 1))]
+    fn test_error_positions(#[case] source: &str, #[case] position: usize, #[case] line_column: (usize, usize)) {
+        let mut lexer = StreamingLexer::new(Trickle { data: source.as_bytes(), step: 1 });
+        let error = lexer.find_map(Result::err);
+        assert_matches!(
+            error,
+            Some(StreamError::Source(error))
+                if error.code == ErrorCode::UnexpectedInput
+                    && error.range.start == position
+                    && (error.line, error.column) == line_column
+        );
+        assert!(lexer.next().is_none());
+    }
+
+    #[rstest]
+    #[case("var a = @", b' ')]
+    #[case("a = 😀", b'a')]
+    #[case("x = 'abc\n", b'a')]
+    #[case("x = /abc\n", b'a')]
+    #[case("x = 1ex", b'1')]
+    #[case("x = \\x", b'a')]
+    #[case("x = #-", b'a')]
+    #[case("x = 'ab\\x4'", b' ')]
+    fn test_error_before_input_end(#[case] head: &'static str, #[case] filler: u8) {
+        let mut reader = Padded { head, filler, length: 16 << 20, read: 0 };
+        let error = StreamingLexer::new(&mut reader).find_map(Result::err);
+        assert_matches!(error, Some(StreamError::Source(error)) if error.code == ErrorCode::UnexpectedInput);
+        // The error comes out before the rest of the input is buffered.
+        assert!(reader.read <= 2 * 4096, "{} bytes read", reader.read);
+    }
+
+    #[rstest]
+    #[case("/*", b'*')]
+    #[case("'", b'\\')]
+    #[case("`", b'$')]
+    #[case("a", b'b')]
+    #[case(" ", b' ')]
+    fn test_long_token(#[case] head: &str, #[case] filler: u8) {
+        // Read a byte at a time, rescanning the token from its start at
+        // every read would take too long to finish.
+        let mut source = head.as_bytes().to_vec();
+        source.resize(1 << 18, filler);
+        assert_matches!(stream(&source, 1).as_slice(), [_, ..]);
+    }
+
+    #[test]
+    fn test_reader_failure() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("device lost"))
+            }
+        }
+        let mut lexer = StreamingLexer::new(Failing);
+        assert_matches!(lexer.next(), Some(Err(StreamError::Io(_))));
+        assert!(lexer.next().is_none());
+    }
+}
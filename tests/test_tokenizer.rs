@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use claims::{assert_err, assert_matches, assert_ok_eq};
+    use claims::{assert_err, assert_matches, assert_ok, assert_ok_eq};
+    use embedded_ecmascript::{Diagnostics, ErrorCode, LineIndex, Severity, SourceCodeError};
+    use num_bigint::BigInt;
     use embedded_ecmascript::lexical_grammar::{
         Addition,
         AdditionAssignment,
@@ -18,7 +20,10 @@ mod tests {
         ClosingParenthesis,
         Colon,
         Comma,
+        Comment,
         CommonToken,
+        CompactToken,
+        ContextualKeyword,
         Decrement,
         Division,
         DivisionAssignment,
@@ -28,13 +33,21 @@ mod tests {
         Exponentiation,
         ExponentiationAssignment,
         FunctionArrow,
+        get_next_compact_token,
+        get_next_positioned_token,
         get_next_token,
+        get_next_token_with_grammar,
+        get_next_token_with_options,
+        get_next_token_with_range,
         GoalSymbols,
         Increment,
         LeftShift,
         LeftShiftAssignment,
         Less,
         LessOrEqual,
+        Lexer,
+        LosslessToken,
+        LexerOptions,
         LineTerminator,
         LooseEquality,
         LooseInequality,
@@ -46,7 +59,10 @@ mod tests {
         MultiplicationAssignment,
         Not,
         NullishCoalescence,
+        NonDecimalIntegerLiteral,
         NullishCoalescenceAssignment,
+        NumericLiteral,
+        NumericValue,
         OpeningBrace,
         OpeningBracket,
         OpeningParenthesis,
@@ -63,8 +79,17 @@ mod tests {
         SubtractionAssignment,
         QuestionMark,
         Semicolon,
+        Span,
+        StringLiteral,
         Subtraction,
+        Template,
+        TemplateSubstitutionTail,
         Token,
+        tokenize,
+        tokenize_lossless,
+        tokenize_with_diagnostics,
+        Tokenizer,
+        TokenKind,
         UnsignedRightShift,
         UnsignedRightShiftAssignment,
         WhiteSpace,
@@ -82,7 +107,38 @@ mod tests {
         )]
         mode: GoalSymbols,
     ) {
-        assert_matches!(get_next_token("`", mode), Err(message) if !message.is_empty());
+        assert_matches!(
+            get_next_token("`", mode),
+            Err(error) if !error.message.is_empty() && error.range == (0..1) && (error.line, error.column) == (1, 1)
+        );
+    }
+
+    #[rstest]
+    #[case(GoalSymbols::InputElementDiv, "'ab\ncd'", 0..1, 3, &["`'`"])]
+    #[case(GoalSymbols::InputElementDiv, "\"a\\\"", 0..1, 4, &["`\"`"])]
+    #[case(GoalSymbols::InputElementDiv, "`a\\`", 0..1, 4, &["`` ` ``", "`${`"])]
+    #[case(GoalSymbols::InputElementTemplateTail, "}a$", 0..1, 3, &["`` ` ``", "`${`"])]
+    #[case(GoalSymbols::InputElementRegExp, "/* a", 0..2, 4, &["`*/`"])]
+    #[case(GoalSymbols::InputElementRegExp, "/[/]a\n/", 0..1, 5, &["`/`"])]
+    fn test_unterminated_tokens(
+        #[case] mode: GoalSymbols,
+        #[case] tested: &str,
+        #[case] range: std::ops::Range<usize>,
+        #[case] end: usize,
+        #[case] expected: &[&str],
+    ) {
+        let error = get_next_token(tested, mode).unwrap_err();
+        assert_eq!(error.range, range);
+        assert_eq!(error.expected, expected);
+        assert_matches!(error.labels.as_slice(), [label] if label.range == (end..end));
+    }
+
+    #[test]
+    fn test_expected_token() {
+        assert_matches!(
+            get_next_token("@", GoalSymbols::InputElementDiv),
+            Err(error) if error.message == "expected a token, found `@`" && error.expected == ["a token"] && error.labels.is_empty()
+        );
     }
 
     #[rstest]
@@ -142,7 +198,7 @@ mod tests {
 
     #[rstest]
     fn test_identifier_name(
-        #[values("X", "d", "д", "大", "$")]
+        #[values("X", "d", "д", "大", "$", "\u{1C5}", "\u{1D4D0}", "\u{323AF}")]
         tested: &str,
         #[values(
             GoalSymbols::InputElementHashbangOrRegExp,
@@ -175,6 +231,167 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("a\u{301}")]
+    #[case("x\u{663}")]
+    #[case("_\u{E01EF}")]
+    fn test_identifier_part(#[case] tested: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), "")) if name.string_value() == tested
+        );
+    }
+
+    #[rstest]
+    #[case("\u{301}")]
+    #[case("\u{663}")]
+    #[case("\u{1F600}")]
+    fn test_not_identifier_start(#[case] tested: &str) {
+        assert_matches!(get_next_token(tested, GoalSymbols::InputElementDiv), Err(_));
+    }
+
+    #[rstest]
+    #[case(r"abc", "abc")]
+    #[case(r"\u0061bc", "abc")]
+    #[case(r"a\u{62}c", "abc")]
+    #[case(r"\u{0000000061}", "a")]
+    #[case(r"$_", "$_")]
+    #[case(r"\u{1D4D0}x", "𝓐x")]
+    #[case(r"a\u200C\u{200D}", "a\u{200C}\u{200D}")]
+    #[case(r"\u0069f", "if")]
+    fn test_identifier_name_escapes(#[case] tested: &str, #[case] expected: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), "")) if name.string_value() == expected
+        );
+        let private = "#".to_owned() + tested;
+        assert_matches!(
+            get_next_token(&private, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::PrivateIdentifier(name)), "")) if name.string_value() == "#".to_owned() + expected
+        );
+    }
+
+    #[rstest]
+    #[case(r"\u0031", 0..6)]
+    #[case(r"\u200C", 0..6)]
+    #[case(r"a\u002D", 1..7)]
+    #[case(r"a\u{20}b", 1..7)]
+    #[case(r"a\uD800", 1..7)]
+    #[case(r"#\u0030", 1..7)]
+    fn test_invalid_identifier_name_escapes(#[case] tested: &str, #[case] range: std::ops::Range<usize>) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == range
+        );
+    }
+
+    #[rstest]
+    #[case(r"\u006")]
+    #[case(r"\x61")]
+    #[case(r"\u{110000}")]
+    fn test_malformed_identifier_name_escapes(#[case] tested: &str) {
+        assert_err!(get_next_token(tested, GoalSymbols::InputElementDiv));
+    }
+
+    #[rstest]
+    #[case("ಠ_ಠ")]
+    // Astral plane letters: DESERET CAPITAL LETTER LONG I, MATHEMATICAL ITALIC SMALL X
+    #[case("\u{10400}\u{1D465}")]
+    // Other_ID_Start: SCRIPT CAPITAL P
+    #[case("\u{2118}")]
+    // Other_ID_Continue: MIDDLE DOT; combining marks; non-ASCII digits
+    #[case("a\u{B7}b")]
+    #[case("e\u{301}")]
+    #[case("x\u{967}")]
+    // CJK Unified Ideographs Extension B
+    #[case("\u{20000}")]
+    fn test_unicode_identifier_names(#[case] tested: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), "")) if name.string_value() == tested
+        );
+    }
+
+    #[rstest]
+    // ID_Continue but not ID_Start: a combining mark, a non-ASCII digit
+    #[case("\u{301}")]
+    #[case("\u{967}")]
+    // Neither: an emoji, a symbol
+    #[case("\u{1F600}")]
+    #[case("\u{20AC}")]
+    fn test_non_identifier_start_code_points(#[case] tested: &str) {
+        assert_err!(get_next_token(tested, GoalSymbols::InputElementDiv));
+    }
+
+    #[rstest]
+    #[case("as", Some(ContextualKeyword::As))]
+    #[case("async", Some(ContextualKeyword::Async))]
+    #[case("from", Some(ContextualKeyword::From))]
+    #[case("get", Some(ContextualKeyword::Get))]
+    #[case("let", Some(ContextualKeyword::Let))]
+    #[case("meta", Some(ContextualKeyword::Meta))]
+    #[case("of", Some(ContextualKeyword::Of))]
+    #[case("set", Some(ContextualKeyword::Set))]
+    #[case("static", Some(ContextualKeyword::Static))]
+    #[case("target", Some(ContextualKeyword::Target))]
+    #[case("letter", None)]
+    #[case("asyncx", None)]
+    #[case("x", None)]
+    #[case(r"l\u0065t", None)]
+    #[case(r"\u{61}sync", None)]
+    fn test_contextual_keywords(#[case] tested: &str, #[case] expected: Option<ContextualKeyword>) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), "")) if name.keyword_kind() == expected
+        );
+    }
+
+    #[rstest]
+    #[case("implements", true)]
+    #[case("interface", true)]
+    #[case("let", true)]
+    #[case("package", true)]
+    #[case("private", true)]
+    #[case("protected", true)]
+    #[case("public", true)]
+    #[case("static", true)]
+    #[case(r"yi\u0065ld", true)]
+    #[case(r"l\u0065t", true)]
+    #[case("async", false)]
+    #[case("publics", false)]
+    fn test_strict_mode_reserved_words(#[case] tested: &str, #[case] expected: bool) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), ""))
+                if name.is_reserved_in_strict_mode() == expected
+        );
+    }
+
+    #[rstest]
+    #[case("iffy")]
+    #[case("inner")]
+    #[case("instanceofx")]
+    #[case("do_")]
+    #[case("new$")]
+    #[case("true1")]
+    #[case("awaitable")]
+    fn test_identifiers_starting_with_reserved_words(
+        #[case] tested: &str,
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        assert_matches!(
+            get_next_token(tested, mode),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(name)), "")) if name.string_value() == tested
+        );
+    }
+
     #[rstest]
     fn testreserved_word(
         #[values(
@@ -383,6 +600,486 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("0", Some("0"), None, None, "")]
+    #[case("1234567890", Some("1234567890"), None, None, "")]
+    #[case("1.", Some("1"), None, None, "")]
+    #[case("1.5", Some("1"), Some("5"), None, "")]
+    #[case(".5", None, Some("5"), None, "")]
+    #[case("0.25e10", Some("0"), Some("25"), Some("10"), "")]
+    #[case("1E+3", Some("1"), None, Some("+3"), "")]
+    #[case(".5e-3", None, Some("5"), Some("-3"), "")]
+    #[case("1.e2", Some("1"), None, Some("2"), "")]
+    #[case("1..x", Some("1"), None, None, ".x")]
+    #[case("1.5.5", Some("1"), Some("5"), None, ".5")]
+    #[case("1+2", Some("1"), None, None, "+2")]
+    fn test_decimal_literals(
+        #[case] tested: &str,
+        #[case] integer_part: Option<&str>,
+        #[case] fraction: Option<&str>,
+        #[case] exponent: Option<&str>,
+        #[case] tail: &str,
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        assert_matches!(
+            get_next_token(tested, mode),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::DecimalLiteral(literal))), rest))
+                if literal.integer_part() == integer_part
+                    && literal.fraction() == fraction
+                    && literal.exponent() == exponent
+                    && rest == tail
+        );
+    }
+
+    #[rstest]
+    #[case("0b0", "0", "")]
+    #[case("0B1010", "1010", "")]
+    fn test_binary_integer_literals(#[case] tested: &str, #[case] digits: &str, #[case] tail: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::BinaryIntegerLiteral(literal)
+            ))), rest)) if literal.digits() == digits && rest == tail
+        );
+    }
+
+    #[rstest]
+    #[case("0o0", "0", "")]
+    #[case("0O17", "17", "")]
+    fn test_octal_integer_literals(#[case] tested: &str, #[case] digits: &str, #[case] tail: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::OctalIntegerLiteral(literal)
+            ))), rest)) if literal.digits() == digits && rest == tail
+        );
+    }
+
+    #[rstest]
+    #[case("0x0", "0", "")]
+    #[case("0XdeadBEEF", "deadBEEF", "")]
+    fn test_hex_integer_literals(#[case] tested: &str, #[case] digits: &str, #[case] tail: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::HexIntegerLiteral(literal)
+            ))), rest)) if literal.digits() == digits && rest == tail
+        );
+    }
+
+    #[rstest]
+    #[case("0x")]
+    #[case("0o")]
+    #[case("0b")]
+    fn test_non_decimal_prefix_without_digits(#[case] tested: &str) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == (1..2)
+        );
+    }
+
+    #[rstest]
+    #[case("1_000_000", Some("1_000_000"), None, None)]
+    #[case("1_0.0_1e1_0", Some("1_0"), Some("0_1"), Some("1_0"))]
+    #[case(".1_1", None, Some("1_1"), None)]
+    #[case("1e-1_0", Some("1"), None, Some("-1_0"))]
+    fn test_numeric_separators(
+        #[case] tested: &str,
+        #[case] integer_part: Option<&str>,
+        #[case] fraction: Option<&str>,
+        #[case] exponent: Option<&str>,
+    ) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::DecimalLiteral(literal))), ""))
+                if literal.integer_part() == integer_part
+                    && literal.fraction() == fraction
+                    && literal.exponent() == exponent
+        );
+    }
+
+    #[test]
+    fn test_non_decimal_numeric_separators() {
+        assert_matches!(
+            get_next_token("0xFF_FF", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::HexIntegerLiteral(literal)
+            ))), "")) if literal.digits() == "FF_FF"
+        );
+        assert_matches!(
+            get_next_token("0b1_0", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::BinaryIntegerLiteral(literal)
+            ))), "")) if literal.digits() == "1_0"
+        );
+        assert_matches!(
+            get_next_token("0o7_7", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalIntegerLiteral(
+                NonDecimalIntegerLiteral::OctalIntegerLiteral(literal)
+            ))), "")) if literal.digits() == "7_7"
+        );
+    }
+
+    #[test]
+    fn test_bigint_literals() {
+        assert_matches!(
+            get_next_token("1_000n", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::DecimalBigIntegerLiteral(literal))), ""))
+                if literal.digits() == "1_000"
+        );
+        assert_matches!(
+            get_next_token("0n", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::DecimalBigIntegerLiteral(literal))), ""))
+                if literal.digits() == "0"
+        );
+        assert_matches!(
+            get_next_token("0x1Fn", GoalSymbols::InputElementDiv),
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(NumericLiteral::NonDecimalBigIntegerLiteral(literal))), ""))
+                if matches!(literal.integer(), NonDecimalIntegerLiteral::HexIntegerLiteral(hex) if hex.digits() == "1F")
+        );
+    }
+
+    #[rstest]
+    #[case("1_", 1)]
+    #[case("1__0", 1)]
+    #[case("0_1", 1)]
+    #[case("1_.5", 1)]
+    #[case("1._5", 2)]
+    #[case("1.5_", 3)]
+    #[case("0x_1", 2)]
+    #[case("0xF_", 3)]
+    #[case("1e_5", 2)]
+    #[case("1e+_5", 3)]
+    #[case("1_n", 1)]
+    #[case("1n_", 2)]
+    fn test_misplaced_numeric_separators(#[case] tested: &str, #[case] position: usize) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == (position..position + 1)
+        );
+    }
+
+    #[rstest]
+    #[case("3in x", 1..2)]
+    #[case("1e", 1..2)]
+    #[case("1.5n", 3..4)]
+    #[case("0b12", 3..4)]
+    #[case("0o78", 3..4)]
+    #[case("0x1g", 3..4)]
+    #[case("1n1", 2..3)]
+    #[case("07n", 2..3)]
+    #[case("08n", 2..3)]
+    #[case("1$", 1..2)]
+    #[case(r"1\u0061", 1..2)]
+    #[case("1π", 1..3)]
+    fn test_numeric_literal_continuations(#[case] tested: &str, #[case] range: std::ops::Range<usize>) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == range
+        );
+    }
+
+    #[rstest]
+    #[case("07.5", ".5")]
+    #[case("08_1", "_1")]
+    fn test_legacy_integer_literal_tails(#[case] tested: &str, #[case] tail: &str) {
+        let result = get_next_token(tested, GoalSymbols::InputElementDiv);
+        if tail.starts_with('_') {
+            assert_err!(result);
+        } else {
+            assert_matches!(result, Ok((Token::CommonToken(CommonToken::NumericLiteral(_)), rest)) if rest == tail);
+        }
+    }
+
+    #[rstest]
+    #[case("010", 0..3)]
+    #[case("08.5", 0..2)]
+    #[case("0718e1", 0..4)]
+    #[case(r"'a\01'", 2..5)]
+    #[case(r"'\\\7'", 3..5)]
+    #[case(r"'\377'", 1..5)]
+    #[case(r"'\400'", 1..4)]
+    #[case(r"'\08'", 1..3)]
+    #[case(r"'\9'", 1..3)]
+    fn test_strict_mode_errors(#[case] tested: &str, #[case] range: std::ops::Range<usize>) {
+        let strict = LexerOptions { strict: true, ..LexerOptions::default() };
+        assert_ok!(get_next_token_with_options(tested, 0, GoalSymbols::InputElementDiv, &LexerOptions::default()));
+        assert_matches!(
+            get_next_token_with_options(tested, 0, GoalSymbols::InputElementDiv, &strict),
+            Err(error) if error.code == ErrorCode::EarlyError && error.range == range
+        );
+    }
+
+    #[rstest]
+    #[case("0")]
+    #[case("0.5")]
+    #[case("10n")]
+    #[case(r"'\0'")]
+    #[case(r"'\\1'")]
+    #[case(r"'\x01'")]
+    #[case(r"`\0`")]
+    fn test_strict_mode_allowed(#[case] tested: &str) {
+        let strict = LexerOptions { strict: true, ..LexerOptions::default() };
+        assert_ok!(get_next_token_with_options(tested, 0, GoalSymbols::InputElementDiv, &strict));
+    }
+
+    fn numeric_value(input: &str) -> NumericValue {
+        match get_next_token(input, GoalSymbols::InputElementDiv) {
+            Ok((Token::CommonToken(CommonToken::NumericLiteral(literal)), "")) => literal.numeric_value(),
+            other => panic!("{input:?} is not a single numeric literal: {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case("0", 0.0)]
+    #[case("1_000", 1000.0)]
+    #[case("1.5", 1.5)]
+    #[case(".5e1", 5.0)]
+    #[case("1.e2", 100.0)]
+    #[case("25e-2", 0.25)]
+    #[case("0.1", 0.1)]
+    #[case("0xFF", 255.0)]
+    #[case("0b1_0", 2.0)]
+    #[case("0o17", 15.0)]
+    #[case("9007199254740993", 9_007_199_254_740_992.0)]
+    #[case("1e400", f64::INFINITY)]
+    #[case("1e-400", 0.0)]
+    #[case("0x10000000000000000", 18_446_744_073_709_551_616.0)]
+    #[case("010", 8.0)]
+    #[case("00", 0.0)]
+    #[case("08", 8.0)]
+    #[case("0718", 718.0)]
+    #[case("09.5e1", 95.0)]
+    fn test_number_values(#[case] tested: &str, #[case] expected: f64) {
+        assert_eq!(numeric_value(tested), NumericValue::Number(expected));
+    }
+
+    #[rstest]
+    #[case("0n", "0", 10)]
+    #[case("1_000n", "1000", 10)]
+    #[case("123456789012345678901234567890n", "123456789012345678901234567890", 10)]
+    #[case("0x1_0000_0000_0000_0000n", "10000000000000000", 16)]
+    #[case("0b11n", "11", 2)]
+    #[case("0o777n", "777", 8)]
+    fn test_bigint_values(#[case] tested: &str, #[case] digits: &str, #[case] radix: u32) {
+        assert_eq!(
+            numeric_value(tested),
+            NumericValue::BigInt(BigInt::parse_bytes(digits.as_bytes(), radix).unwrap())
+        );
+    }
+
+    fn string_literal(input: &str) -> StringLiteral {
+        match get_next_token(input, GoalSymbols::InputElementDiv) {
+            Ok((Token::CommonToken(CommonToken::StringLiteral(literal)), "")) => literal,
+            other => panic!("{input:?} is not a single string literal: {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case(r#""""#, "")]
+    #[case("''", "")]
+    #[case(r#""a'b""#, "a'b")]
+    #[case(r#"'a"b'"#, "a\"b")]
+    #[case("'д大😀'", "д大😀")]
+    #[case("'\u{2028}\u{2029}'", "\u{2028}\u{2029}")]
+    // Table 66: String Single Character Escape Sequences
+    #[case(r"'\b\t\n\v\f\r'", "\u{8}\t\n\u{B}\u{C}\r")]
+    #[case(r#"'\"\'\\'"#, "\"'\\")]
+    // NonEscapeCharacter and NonOctalDecimalEscapeSequence
+    #[case(r"'\a\д\8\9'", "aд89")]
+    #[case(r"'\x41\x7e'", "A~")]
+    #[case(r"'\u{41}\u{1F600}\u{0000010FFFF}'", "A😀\u{10FFFF}")]
+    #[case(r"'\u0041\u00E9'", "Aé")]
+    #[case(r"'\uD83D\uDE00'", "😀")]
+    #[case(r"'\0'", "\0")]
+    #[case(r"'\08'", "\u{0}8")]
+    #[case(r"'\7\77\101\400'", "\u{7}?A\u{20}0")]
+    #[case("'a\\\nb\\\r\nc\\\rd\\\u{2028}e'", "abcde")]
+    fn test_string_literals(#[case] tested: &str, #[case] expected: &str) {
+        assert_eq!(string_literal(tested).string_value(), expected);
+    }
+
+    #[test]
+    fn test_string_literal_surrogates() {
+        assert_eq!(string_literal(r"'\uD800x'").code_units(), [0xD800, u16::from(b'x')]);
+        assert_eq!(string_literal(r"'\u{DC00}'").string_value(), "\u{FFFD}");
+    }
+
+    #[rstest]
+    #[case("'")]
+    #[case("'abc")]
+    #[case("'a\nb'")]
+    #[case("\"a\rb\"")]
+    #[case(r"'\x4'")]
+    #[case(r"'\u004'")]
+    #[case(r"'\u{}'")]
+    #[case(r"'\u{110000}'")]
+    #[case(r"'\u{10FFFFF}'")]
+    #[case("'\\")]
+    fn test_string_literal_errors(#[case] tested: &str) {
+        assert_err!(get_next_token(tested, GoalSymbols::InputElementDiv));
+    }
+
+    #[rstest]
+    fn test_templates(
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        assert_matches!(
+            get_next_token("`a$b`c", mode),
+            Ok((Token::CommonToken(CommonToken::Template(Template::NoSubstitutionTemplate(template))), "c"))
+                if template.raw_value() == "a$b"
+        );
+        assert_matches!(
+            get_next_token("``", mode),
+            Ok((Token::CommonToken(CommonToken::Template(Template::NoSubstitutionTemplate(template))), ""))
+                if template.raw_value().is_empty() && template.cooked_value() == Some(vec![])
+        );
+        assert_matches!(
+            get_next_token("`a${b}`", mode),
+            Ok((Token::CommonToken(CommonToken::Template(Template::TemplateHead(template))), "b}`"))
+                if template.raw_value() == "a"
+        );
+    }
+
+    #[rstest]
+    fn test_template_substitution_tails(
+        #[values(GoalSymbols::InputElementRegExpOrTemplateTail, GoalSymbols::InputElementTemplateTail)]
+        mode: GoalSymbols,
+    ) {
+        assert_matches!(
+            get_next_token("} a ${b", mode),
+            Ok((Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateMiddle(middle)), "b"))
+                if middle.raw_value() == " a "
+        );
+        assert_matches!(
+            get_next_token("}`;", mode),
+            Ok((Token::TemplateSubstitutionTail(TemplateSubstitutionTail::TemplateTail(tail)), ";"))
+                if tail.raw_value().is_empty()
+        );
+        assert_err!(get_next_token("}", mode));
+    }
+
+    #[rstest]
+    fn test_right_brace_outside_templates(
+        #[values(GoalSymbols::InputElementRegExp, GoalSymbols::InputElementDiv)]
+        mode: GoalSymbols,
+    ) {
+        assert_ok_eq!(get_next_token("}`", mode), (Token::RightBracePunctuator(RightBracePunctuator), "`"));
+    }
+
+    #[rstest]
+    #[case(r"`\n\x41\u{1F600}\``", Some("\nA😀`"), r"\n\x41\u{1F600}\`")]
+    #[case("`a\r\nb\rc`", Some("a\nb\nc"), "a\nb\nc")]
+    #[case("`a\\\r\nb`", Some("ab"), "a\\\nb")]
+    #[case(r"`\0`", Some("\0"), r"\0")]
+    #[case(r"`\01`", None, r"\01")]
+    #[case(r"`\1`", None, r"\1")]
+    #[case(r"`\xg`", None, r"\xg")]
+    #[case(r"`\u12`", None, r"\u12")]
+    #[case(r"`\u{110000}`", None, r"\u{110000}")]
+    #[case(r"`\u{12`", None, r"\u{12")]
+    #[case(r"`\$\{`", Some("${"), r"\$\{")]
+    fn test_template_values(#[case] tested: &str, #[case] cooked: Option<&str>, #[case] raw: &str) {
+        let Ok((Token::CommonToken(CommonToken::Template(Template::NoSubstitutionTemplate(template))), "")) =
+            get_next_token(tested, GoalSymbols::InputElementDiv)
+        else {
+            panic!("{tested:?} is not a single template");
+        };
+        assert_eq!(template.cooked_value(), cooked.map(|cooked| cooked.encode_utf16().collect()));
+        assert_eq!(template.raw_value(), raw);
+    }
+
+    #[rstest]
+    #[case("/a/", "a", "", "")]
+    #[case("/a/gimsuyd;", "a", "gimsuyd", ";")]
+    #[case(r"/[/\]]+\/x/v", r"[/\]]+\/x", "v", "")]
+    #[case("/a/ g", "a", "", " g")]
+    fn test_regular_expression_literals(
+        #[case] tested: &str,
+        #[case] body: &str,
+        #[case] flags: &str,
+        #[case] tail: &str,
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+        )]
+        mode: GoalSymbols,
+    ) {
+        assert_matches!(
+            get_next_token(tested, mode),
+            Ok((Token::RegularExpressionLiteral(literal), rest))
+                if literal.body_text() == body && literal.flag_text() == flags && rest == tail
+        );
+    }
+
+    #[rstest]
+    #[case("/a/gg", 4..5)]
+    #[case("/a/q", 3..4)]
+    #[case("/a/uv", 4..5)]
+    #[case("/a/gд", 4..6)]
+    fn test_invalid_regular_expression_flags(#[case] tested: &str, #[case] range: std::ops::Range<usize>) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementRegExp),
+            Err(error) if error.code == ErrorCode::UnexpectedInput && error.range == range
+        );
+    }
+
+    #[rstest]
+    #[case("/a\n/")]
+    #[case("/[/")]
+    #[case(r"/\")]
+    fn test_unterminated_regular_expression_literals(#[case] tested: &str) {
+        assert_err!(get_next_token(tested, GoalSymbols::InputElementRegExp));
+    }
+
+    #[rstest]
+    fn test_regular_expression_goals(
+        #[values(GoalSymbols::InputElementDiv, GoalSymbols::InputElementTemplateTail)]
+        mode: GoalSymbols,
+    ) {
+        assert_ok_eq!(
+            get_next_token("/a/g", mode),
+            (Token::DivPunctuator(DivPunctuator::Division(Division)), "a/g")
+        );
+        assert_matches!(get_next_token("/**/", GoalSymbols::InputElementRegExp), Ok((Token::Comment(_), "")));
+    }
+
+    #[rstest]
+    fn test_dot_is_not_a_number(
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        assert_ok_eq!(
+            get_next_token(".x", mode),
+            (Token::CommonToken(CommonToken::Punctuator(Punctuator::OtherPunctuator(OtherPunctuator::Dot(Dot)))), "x")
+        );
+        assert_ok_eq!(
+            get_next_token("...", mode),
+            (Token::CommonToken(CommonToken::Punctuator(Punctuator::OtherPunctuator(OtherPunctuator::Ellipsis(Ellipsis)))), "")
+        );
+    }
+
     #[rstest]
     fn test_multiline_comments(
         #[values(
@@ -420,9 +1117,42 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("/**/", "", false)]
+    #[case("/* a */", " a ", false)]
+    #[case("/*/**/", "/*", false)]
+    #[case("/***/", "*", false)]
+    #[case("/*\n*/", "\n", true)]
+    #[case("/*a\r\nb*/", "a\r\nb", true)]
+    #[case("/*\u{2028}*/", "\u{2028}", true)]
+    #[case("/*\u{2029}*/", "\u{2029}", true)]
+    #[case("//", "", false)]
+    #[case("// a /* b */", " a /* b */", false)]
+    fn test_comment_content(#[case] tested: &str, #[case] content: &str, #[case] has_line_terminator: bool) {
+        assert_matches!(
+            get_next_token(tested, GoalSymbols::InputElementDiv),
+            Ok((Token::Comment(comment), ""))
+                if comment.string_value() == content
+                    && comment.contains_line_terminator() == has_line_terminator
+        );
+    }
+
+    #[test]
+    fn test_single_line_comment_content_excludes_line_terminator() {
+        assert_matches!(
+            get_next_token("//a\u{2028}", GoalSymbols::InputElementDiv),
+            Ok((Token::Comment(Comment::SingleLineComment(comment)), "\u{2028}")) if comment.string_value() == "a"
+        );
+        assert_matches!(
+            get_next_token("/*a\nb*/c", GoalSymbols::InputElementDiv),
+            Ok((Token::Comment(Comment::MultiLineComment(comment)), "c"))
+                if comment.string_value() == "a\nb" && comment.contains_line_terminator()
+        );
+    }
+
     #[test]
     fn test_hashbang_comments() {
-        fn get_token(input: &str) -> Result<(Token, &str), String> {
+        fn get_token(input: &str) -> Result<(Token<'_>, &str), SourceCodeError> {
             get_next_token(input, GoalSymbols::InputElementHashbangOrRegExp)
         }
 
@@ -471,4 +1201,624 @@ mod tests {
         assert_err!(get_next_token("#!", mode));
         assert_err!(get_next_token("#!\n", mode));
     }
+
+    #[test]
+    fn test_token_kind_texts() {
+        for &kind in TokenKind::ALL {
+            let Some(text) = kind.punctuator_text().or_else(|| kind.keyword_text()) else {
+                continue;
+            };
+            let (token, tail) = get_next_token(text, GoalSymbols::InputElementDiv).unwrap();
+            assert_eq!((token.kind(), tail), (kind, ""));
+            assert_eq!(token.punctuator_text().is_some(), token.is_punctuator());
+            assert_eq!(token.keyword_text().is_some(), token.is_keyword());
+            assert!(!token.is_trivia());
+        }
+        let punctuators = TokenKind::ALL.iter().filter(|kind| kind.is_punctuator()).count();
+        let keywords = TokenKind::ALL.iter().filter(|kind| kind.is_keyword()).count();
+        assert_eq!((punctuators, keywords), (57, 38));
+    }
+
+    #[rstest]
+    #[case(" ", true)]
+    #[case("\n", true)]
+    #[case("/* a */", true)]
+    #[case("// a", true)]
+    #[case("#!a", true)]
+    #[case("a", false)]
+    #[case("'a'", false)]
+    #[case("/a/", false)]
+    fn test_trivia(#[case] tested: &str, #[case] expected: bool) {
+        let (token, _) = get_next_token(tested, GoalSymbols::InputElementHashbangOrRegExp).unwrap();
+        assert_eq!(token.is_trivia(), expected);
+        assert!(!token.is_punctuator() && !token.is_keyword());
+    }
+
+    #[test]
+    fn test_compact_tokens() {
+        let source = "a += #b;//c";
+        let mode = GoalSymbols::InputElementDiv;
+
+        let first = get_next_compact_token(source, 0, mode);
+        assert_ok_eq!(first, CompactToken { kind: TokenKind::IdentifierName, span: Span::new(0, 1) });
+        let second = get_next_compact_token(source, 1, mode);
+        assert_ok_eq!(second, CompactToken { kind: TokenKind::WhiteSpace, span: Span::new(1, 2) });
+        let third = get_next_compact_token(source, 2, mode);
+        assert_ok_eq!(third, CompactToken { kind: TokenKind::AdditionAssignment, span: Span::new(2, 4) });
+        let fifth = get_next_compact_token(source, 5, mode);
+        assert_ok_eq!(fifth, CompactToken { kind: TokenKind::PrivateIdentifier, span: Span::new(5, 7) });
+        let last = get_next_compact_token(source, 8, mode);
+        assert_ok_eq!(last, CompactToken { kind: TokenKind::SingleLineComment, span: Span::new(8, 11) });
+
+        assert_matches!(
+            fifth.unwrap().typed(source),
+            Ok(Token::CommonToken(CommonToken::PrivateIdentifier(name))) if name.string_value() == "#b"
+        );
+        assert_ok_eq!(
+            third.unwrap().typed(source),
+            Token::CommonToken(CommonToken::Punctuator(Punctuator::OtherPunctuator(OtherPunctuator::AdditionAssignment(AdditionAssignment))))
+        );
+        assert_err!(CompactToken { kind: TokenKind::Semicolon, span: Span::new(0, 1) }.typed(source));
+    }
+
+    #[rstest]
+    #[case("a", Span::new(10, 13))]
+    #[case("a", Span::new(0, 3))]
+    #[case("\u{434}a", Span::new(1, 3))]
+    #[case("\u{434}a", Span::new(0, 1))]
+    fn test_compact_tokens_outside_source(#[case] source: &str, #[case] span: Span) {
+        assert_err!(CompactToken { kind: TokenKind::IdentifierName, span }.typed(source));
+    }
+
+    #[test]
+    fn test_coalesced_compact_tokens() {
+        let source = "a    b";
+        let mut offset = 0;
+        while offset < source.len() {
+            let (token, range) = get_next_token_with_options(source, offset, GoalSymbols::InputElementDiv, &LexerOptions::default()).unwrap();
+            let compact = CompactToken { kind: token.kind(), span: range.clone().into() };
+            assert_ok_eq!(compact.typed(source), token);
+            offset = range.end;
+        }
+        assert_eq!(offset, source.len());
+        assert_err!(CompactToken { kind: TokenKind::WhiteSpace, span: Span::new(1, 3) }.typed(source));
+    }
+
+    #[test]
+    fn test_positioned_tokens() {
+        let source = "a\r\n  b\u{2028}\u{E9}c";
+        let lines = LineIndex::new(source);
+        let mode = GoalSymbols::InputElementDiv;
+
+        let first = get_next_positioned_token(&lines, 0, mode).unwrap();
+        assert_eq!((first.span, first.line, first.column), (Span::new(0, 1), 1, 1));
+        let line_break = get_next_positioned_token(&lines, 1, mode).unwrap();
+        assert_eq!((line_break.span, line_break.line, line_break.column), (Span::new(1, 2), 1, 2));
+        assert_eq!(line_break.value, Token::LineTerminator(LineTerminator));
+        let second = get_next_positioned_token(&lines, 5, mode).unwrap();
+        assert_eq!((second.span, second.line, second.column), (Span::new(5, 6), 2, 3));
+        let third = get_next_positioned_token(&lines, 9, mode).unwrap();
+        assert_eq!((third.span, third.line, third.column), (Span::new(9, 12), 3, 1));
+        assert_matches!(
+            third.value,
+            Token::CommonToken(CommonToken::IdentifierName(name)) if name.string_value() == "\u{E9}c"
+        );
+
+        assert_matches!(
+            get_next_positioned_token(&LineIndex::new("a\n@"), 2, mode),
+            Err(error) if error.range.start == 2 && (error.line, error.column) == (2, 1)
+        );
+    }
+
+    fn lexer_kinds(source: &str) -> Vec<TokenKind> {
+        Lexer::new(source)
+            .map(|token| token.map(|(token, _)| token.kind()))
+            .filter(|kind| kind != &Ok(TokenKind::WhiteSpace))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_lexer_spans() {
+        let tokens: Vec<_> = Lexer::new("a  +=\tb")
+            .map(|token| token.map(|(token, span)| (token.kind(), span)))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                Ok((TokenKind::IdentifierName, Span::new(0, 1))),
+                Ok((TokenKind::WhiteSpace, Span::new(1, 3))),
+                Ok((TokenKind::AdditionAssignment, Span::new(3, 5))),
+                Ok((TokenKind::WhiteSpace, Span::new(5, 6))),
+                Ok((TokenKind::IdentifierName, Span::new(6, 7))),
+            ]
+        );
+        assert_eq!(Lexer::new("").count(), 0);
+    }
+
+    #[rstest]
+    #[case("a / b / c", &[TokenKind::IdentifierName, TokenKind::Division, TokenKind::IdentifierName, TokenKind::Division, TokenKind::IdentifierName])]
+    #[case("x = /b/g", &[TokenKind::IdentifierName, TokenKind::Assignment, TokenKind::RegularExpressionLiteral])]
+    #[case("/a/.test(b)", &[
+        TokenKind::RegularExpressionLiteral, TokenKind::Dot, TokenKind::IdentifierName,
+        TokenKind::OpeningParenthesis, TokenKind::IdentifierName, TokenKind::ClosingParenthesis,
+    ])]
+    #[case("(a) / 2", &[
+        TokenKind::OpeningParenthesis, TokenKind::IdentifierName, TokenKind::ClosingParenthesis,
+        TokenKind::Division, TokenKind::NumericLiteral,
+    ])]
+    #[case("#!node\n/a/", &[TokenKind::HashbangComment, TokenKind::LineTerminator, TokenKind::RegularExpressionLiteral])]
+    #[case("`a${b}c${d}e`", &[
+        TokenKind::TemplateHead, TokenKind::IdentifierName, TokenKind::TemplateMiddle,
+        TokenKind::IdentifierName, TokenKind::TemplateTail,
+    ])]
+    #[case("`${ {a} }`", &[
+        TokenKind::TemplateHead, TokenKind::OpeningBrace, TokenKind::IdentifierName,
+        TokenKind::RightBracePunctuator, TokenKind::TemplateTail,
+    ])]
+    #[case("`${`${a}`}` / 2", &[
+        TokenKind::TemplateHead, TokenKind::TemplateHead, TokenKind::IdentifierName,
+        TokenKind::TemplateTail, TokenKind::TemplateTail, TokenKind::Division, TokenKind::NumericLiteral,
+    ])]
+    #[case("{ } /a/", &[TokenKind::OpeningBrace, TokenKind::RightBracePunctuator, TokenKind::RegularExpressionLiteral])]
+    fn test_lexer_goal_symbols(#[case] source: &str, #[case] expected: &[TokenKind]) {
+        assert_eq!(lexer_kinds(source), expected);
+    }
+
+    #[test]
+    fn test_lexer_stops_after_error() {
+        let mut lexer = Lexer::new(" #!x");
+        assert_matches!(lexer.next(), Some(Ok((Token::WhiteSpace(_), _))));
+        assert_matches!(lexer.next(), Some(Err(error)) if error.range.start == 2);
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("#!/usr/bin/env node\r\n/* a\u{2028}b */  \u{FEFF}x //c\n")]
+    #[case("`${a}`\t\t\u{A0};")]
+    fn test_lossless_tokens(#[case] source: &str) {
+        let tokens = tokenize_lossless(source).unwrap();
+        let reconstructed: String = tokens.iter().map(|token| token.text).collect();
+        assert_eq!(reconstructed, source);
+        for token in &tokens {
+            assert_eq!(token.span.source_text(source), token.text);
+        }
+    }
+
+    #[test]
+    fn test_lossless_trivia() {
+        let tokens = tokenize_lossless("a \t/*b*/\r\n").unwrap();
+        assert_matches!(
+            tokens.as_slice(),
+            [
+                LosslessToken { token: Token::CommonToken(CommonToken::IdentifierName(_)), text: "a", .. },
+                LosslessToken { token: Token::WhiteSpace(_), text: " \t", .. },
+                LosslessToken { token: Token::Comment(_), text: "/*b*/", .. },
+                LosslessToken { token: Token::LineTerminator(_), text: "\r", .. },
+                LosslessToken { token: Token::LineTerminator(_), text: "\n", .. },
+            ]
+        );
+        assert_err!(tokenize_lossless("a @"));
+    }
+
+    #[rstest]
+    #[case(GoalSymbols::InputElementHashbangOrRegExp, "/a/ / b", &[
+        TokenKind::RegularExpressionLiteral, TokenKind::WhiteSpace, TokenKind::Division,
+        TokenKind::WhiteSpace, TokenKind::IdentifierName,
+    ])]
+    #[case(GoalSymbols::InputElementDiv, "/a/ 2", &[
+        TokenKind::Division, TokenKind::IdentifierName, TokenKind::Division, TokenKind::WhiteSpace,
+        TokenKind::NumericLiteral,
+    ])]
+    #[case(GoalSymbols::InputElementTemplateTail, "}a`", &[TokenKind::TemplateTail])]
+    #[case(GoalSymbols::InputElementDiv, "", &[])]
+    fn test_tokenize(#[case] goal: GoalSymbols, #[case] source: &str, #[case] expected: &[TokenKind]) {
+        let tokens = tokenize(source, goal).unwrap();
+        let kinds: Vec<_> = tokens.iter().map(|(token, _)| token.kind()).collect();
+        assert_eq!(kinds, expected);
+        assert_eq!(tokens.last().map_or(0, |(_, span)| span.end), source.len());
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_matches!(
+            tokenize("a\n@", GoalSymbols::InputElementHashbangOrRegExp),
+            Err(error) if error.range.start == 2
+        );
+    }
+
+    #[test]
+    fn test_tokenize_with_diagnostics() {
+        let source = "a @ 'b\nx = /c/zz;";
+        let mut diagnostics = Diagnostics::new();
+        let tokens = tokenize_with_diagnostics(source, GoalSymbols::InputElementHashbangOrRegExp, &mut diagnostics);
+        let ranges: Vec<_> = diagnostics.iter().map(|error| error.range.clone()).collect();
+        assert_eq!(ranges, [2..3, 4..5, 14..15]);
+        assert_eq!(tokens[2], (Token::Unknown('@'), Span::new(2, 3)));
+        assert_eq!(tokens[4], (Token::Unknown('\''), Span::new(4, 5)));
+        assert_eq!(tokens.last().map(|(_, span)| span.end), Some(source.len()));
+
+        let mut diagnostics = Diagnostics::new();
+        let tokens = tokenize_with_diagnostics("a + b", GoalSymbols::InputElementHashbangOrRegExp, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+        assert_eq!(tokens, tokenize("a + b", GoalSymbols::InputElementHashbangOrRegExp).unwrap());
+    }
+
+    #[rstest]
+    #[case("010", 0..3)]
+    #[case("08.5", 0..2)]
+    #[case("'\\01'", 1..4)]
+    #[case("a <!-- b", 2..6)]
+    #[case("a\n  --> b", 4..7)]
+    fn test_legacy_syntax_warnings(#[case] tested: &str, #[case] range: std::ops::Range<usize>) {
+        let mut diagnostics = Diagnostics::new();
+        tokenize_with_diagnostics(tested, GoalSymbols::InputElementHashbangOrRegExp, &mut diagnostics);
+        assert_matches!(
+            diagnostics.iter().collect::<Vec<_>>().as_slice(),
+            [warning] if warning.range == range
+                && warning.severity == Severity::Warning
+                && warning.code == ErrorCode::DeprecatedSyntax
+        );
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[rstest]
+    #[case("<!-- a\nb", 0, 0..6)]
+    #[case("a <!-- b", 2, 2..8)]
+    #[case("a\n  --> b", 4, 4..9)]
+    #[case("--> b", 0, 0..5)]
+    fn test_html_like_comments(#[case] tested: &str, #[case] offset: usize, #[case] range: std::ops::Range<usize>) {
+        let options = LexerOptions { html_like_comments: true, ..LexerOptions::default() };
+        let (token, recognized) = get_next_token_with_options(tested, offset, GoalSymbols::InputElementDiv, &options).unwrap();
+        assert_matches!(token, Token::Comment(Comment::SingleLineComment(_)));
+        assert_eq!(recognized, range);
+        assert_matches!(
+            get_next_token_with_options(tested, offset, GoalSymbols::InputElementDiv, &LexerOptions::default()),
+            Ok((Token::CommonToken(_), _))
+        );
+    }
+
+    #[rstest]
+    #[case("a --> b", 2)]
+    #[case("a <!- b", 2)]
+    fn test_no_html_like_comments(#[case] tested: &str, #[case] offset: usize) {
+        let options = LexerOptions { html_like_comments: true, ..LexerOptions::default() };
+        assert_matches!(
+            get_next_token_with_options(tested, offset, GoalSymbols::InputElementDiv, &options),
+            Ok((Token::CommonToken(_), _))
+        );
+    }
+
+    #[rstest]
+    #[case("a-->b")]
+    #[case("a < !--b")]
+    #[case("0.5 + 0 + 0n")]
+    #[case("'\\0' + '\\x41'")]
+    fn test_no_legacy_syntax_warnings(#[case] tested: &str) {
+        let mut diagnostics = Diagnostics::new();
+        tokenize_with_diagnostics(tested, GoalSymbols::InputElementHashbangOrRegExp, &mut diagnostics);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[rstest]
+    #[case("abc", "abc")]
+    #[case("\\u0069f", "\\u{69}f")]
+    #[case("a\\u0062", "\\u{61}b")]
+    #[case("#\\u{61}b", "#\\u{61}b")]
+    #[case("0X1_F", "0x1_F")]
+    #[case("0O17n", "0o17n")]
+    #[case("1E+5", "1e+5")]
+    #[case("5.", "5.")]
+    #[case(".5e-3", ".5e-3")]
+    #[case("1_000n", "1_000n")]
+    #[case("017", "017")]
+    #[case("'a\\n\"'", "'a\\n\"'")]
+    #[case("`a\r\n${", "`a\r\n${")]
+    #[case("/[/]a/gu", "/[/]a/gu")]
+    #[case("/* c\n */", "/* c\n */")]
+    #[case("// c", "// c")]
+    #[case("#!x", "#!x")]
+    #[case("\t\u{FEFF}", " ")]
+    #[case("\u{2028}", "\n")]
+    #[case(">>>=", ">>>=")]
+    #[case("instanceof", "instanceof")]
+    fn test_token_display(#[case] tested: &str, #[case] expected: &str) {
+        let (token, _) = get_next_token(tested, GoalSymbols::InputElementHashbangOrRegExp).unwrap();
+        assert_eq!(token.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("#!/usr/bin/env node\r\nlet a = 5..toString() + 0XFFn;")]
+    #[case("x = `a${ {b: /}/g} }c${\\u0069f}d` /* e */ / 2 // f")]
+    #[case("class A { #\\u{62} = '\\x41'; static { this.#b **= 1E3 } }")]
+    #[case("a\t\u{A0}?.\u{2029}[.5e-1_0]")]
+    fn test_display_round_trip(#[case] source: &str) {
+        let goal = GoalSymbols::InputElementHashbangOrRegExp;
+        let tokens: Vec<_> = tokenize(source, goal).unwrap().into_iter().map(|(token, _)| token).collect();
+        let reserialized: String = tokens.iter().map(ToString::to_string).collect();
+        let retokenized: Vec<_> = tokenize(&reserialized, goal).unwrap().into_iter().map(|(token, _)| token).collect();
+        assert_eq!(retokenized, tokens);
+    }
+
+    #[test]
+    fn test_display_without_comments() {
+        let source = "a/*b*/=c//d\n";
+        let stripped: String = tokenize(source, GoalSymbols::InputElementHashbangOrRegExp)
+            .unwrap()
+            .iter()
+            .filter(|(token, _)| token.kind() != TokenKind::MultiLineComment && token.kind() != TokenKind::SingleLineComment)
+            .map(|(token, _)| token.to_string())
+            .collect();
+        assert_eq!(stripped, "a=c\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let source = "#!a\na /*b*/ += `c${0x1n}` / /d/g; '\\'' // e";
+        let tokens = tokenize(source, GoalSymbols::InputElementHashbangOrRegExp).unwrap();
+        let json = serde_json::to_string(&tokens).unwrap();
+        let restored: Vec<(Token<'_>, Span)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, tokens);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_compact_tokens() {
+        let token = CompactToken { kind: TokenKind::Semicolon, span: Span::new(3, 4) };
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, r#"{"kind":"Semicolon","span":{"start":3,"end":4}}"#);
+        assert_ok_eq!(serde_json::from_str::<CompactToken>(&json), token);
+    }
+
+    #[test]
+    fn test_tokenizer_goals() {
+        let source = "a/b/`${/c/}`";
+        let mut tokenizer = Tokenizer::new();
+        let mut goals = Vec::new();
+        let mut kinds = Vec::new();
+        let mut offset = 0;
+        while offset < source.len() {
+            goals.push(tokenizer.goal());
+            let (token, range) = tokenizer.next_token(source, offset).unwrap();
+            kinds.push(token.kind());
+            offset = range.end;
+        }
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::IdentifierName, TokenKind::Division, TokenKind::IdentifierName, TokenKind::Division,
+                TokenKind::TemplateHead, TokenKind::RegularExpressionLiteral, TokenKind::TemplateTail,
+            ]
+        );
+        assert_eq!(
+            goals,
+            [
+                GoalSymbols::InputElementHashbangOrRegExp,
+                GoalSymbols::InputElementDiv,
+                GoalSymbols::InputElementRegExp,
+                GoalSymbols::InputElementDiv,
+                GoalSymbols::InputElementRegExp,
+                GoalSymbols::InputElementRegExpOrTemplateTail,
+                GoalSymbols::InputElementTemplateTail,
+            ]
+        );
+        assert_eq!(tokenizer.goal(), GoalSymbols::InputElementDiv);
+    }
+
+    #[test]
+    fn test_tokenizer_keeps_state_on_error() {
+        let mut tokenizer = Tokenizer::default();
+        assert_err!(tokenizer.next_token("@", 0));
+        assert_eq!(tokenizer, Tokenizer::new());
+
+        let (token, _) = get_next_token_with_range("x", 0, GoalSymbols::InputElementDiv).unwrap();
+        tokenizer.observe(&token);
+        assert_eq!(tokenizer.goal(), GoalSymbols::InputElementDiv);
+    }
+
+    #[test]
+    fn test_compact_token_kinds() {
+        let kind_of = |input, mode| get_next_token(input, mode).map(|(token, _)| token.kind());
+
+        assert_ok_eq!(kind_of("#!x", GoalSymbols::InputElementHashbangOrRegExp), TokenKind::HashbangComment);
+        assert_ok_eq!(kind_of("/**/", GoalSymbols::InputElementDiv), TokenKind::MultiLineComment);
+        assert_ok_eq!(kind_of("/=", GoalSymbols::InputElementDiv), TokenKind::DivisionAssignment);
+        assert_ok_eq!(kind_of("}", GoalSymbols::InputElementDiv), TokenKind::RightBracePunctuator);
+        assert_ok_eq!(kind_of("?.", GoalSymbols::InputElementDiv), TokenKind::OptionalChainingPunctuator);
+        assert_ok_eq!(kind_of("instanceof", GoalSymbols::InputElementDiv), TokenKind::Instanceof);
+        assert_ok_eq!(kind_of(".5", GoalSymbols::InputElementDiv), TokenKind::NumericLiteral);
+        assert_ok_eq!(kind_of("'a'", GoalSymbols::InputElementDiv), TokenKind::StringLiteral);
+        assert_ok_eq!(kind_of("`${", GoalSymbols::InputElementDiv), TokenKind::TemplateHead);
+        assert_ok_eq!(kind_of("}${", GoalSymbols::InputElementTemplateTail), TokenKind::TemplateMiddle);
+        assert_ok_eq!(kind_of("/=/", GoalSymbols::InputElementRegExp), TokenKind::RegularExpressionLiteral);
+        assert_ne!(u16::from(TokenKind::Yield), u16::from(TokenKind::WhiteSpace));
+    }
+
+    #[rstest]
+    #[case(None, false, GoalSymbols::InputElementHashbangOrRegExp)]
+    #[case(Some("x"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some(")"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some("]"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some("1"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some("this"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some("++"), false, GoalSymbols::InputElementDiv)]
+    #[case(Some("("), false, GoalSymbols::InputElementRegExp)]
+    #[case(Some("="), false, GoalSymbols::InputElementRegExp)]
+    #[case(Some("return"), false, GoalSymbols::InputElementRegExp)]
+    #[case(Some("}"), false, GoalSymbols::InputElementRegExp)]
+    #[case(Some("x"), true, GoalSymbols::InputElementTemplateTail)]
+    #[case(Some("+"), true, GoalSymbols::InputElementRegExpOrTemplateTail)]
+    fn test_goal_symbol_inference(
+        #[case] previous: Option<&str>,
+        #[case] in_template_substitution: bool,
+        #[case] expected: GoalSymbols,
+    ) {
+        let previous = previous.map(|text| get_next_token(text, GoalSymbols::InputElementRegExp).unwrap().0);
+        assert_eq!(GoalSymbols::infer(previous.as_ref(), in_template_substitution), expected);
+    }
+
+    #[test]
+    fn test_token_ranges() {
+        let source = "x\n/*д*/}";
+        let mode = GoalSymbols::InputElementRegExp;
+
+        assert_matches!(
+            get_next_token_with_range(source, 0, mode),
+            Ok((Token::CommonToken(CommonToken::IdentifierName(_)), range)) if range == (0..1)
+        );
+        assert_ok_eq!(get_next_token_with_range(source, 1, mode), (Token::LineTerminator(LineTerminator), 1..2));
+        assert_matches!(get_next_token_with_range(source, 2, mode), Ok((Token::Comment(_), range)) if range == (2..8));
+        assert_ok_eq!(get_next_token_with_range(source, 8, mode), (Token::RightBracePunctuator(RightBracePunctuator), 8..9));
+        assert_matches!(
+            get_next_token_with_range("a `", 2, mode),
+            Err(error) if error.range == (2..3) && error.column == 3
+        );
+        assert_eq!(Span::from(3..5), Span::new(3, 5));
+    }
+
+    #[rstest]
+    fn test_white_space_coalescing(
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        let source = "x \t\u{3000}\u{FEFF} \n  y";
+        let coalescing = LexerOptions::default();
+        let separate = LexerOptions::per_input_element();
+
+        assert_ok_eq!(get_next_token_with_options(source, 1, mode, &coalescing), (Token::WhiteSpace(WhiteSpace), 1..10));
+        assert_ok_eq!(get_next_token_with_options(source, 1, mode, &separate), (Token::WhiteSpace(WhiteSpace), 1..2));
+        assert_ok_eq!(get_next_token_with_options(source, 10, mode, &coalescing), (Token::LineTerminator(LineTerminator), 10..11));
+        assert_ok_eq!(get_next_token_with_options(source, 11, mode, &coalescing), (Token::WhiteSpace(WhiteSpace), 11..13));
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let options = LexerOptions { recover_errors: true, ..LexerOptions::default() };
+        let tokens: Vec<_> = Lexer::with_options("a @ b\u{1F600}#", options)
+            .map(|token| token.map(|(token, span)| (token.kind(), span)))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                Ok((TokenKind::IdentifierName, Span::new(0, 1))),
+                Ok((TokenKind::WhiteSpace, Span::new(1, 2))),
+                Ok((TokenKind::Unknown, Span::new(2, 3))),
+                Ok((TokenKind::WhiteSpace, Span::new(3, 4))),
+                Ok((TokenKind::IdentifierName, Span::new(4, 5))),
+                Ok((TokenKind::Unknown, Span::new(5, 9))),
+                Ok((TokenKind::Unknown, Span::new(9, 10))),
+            ]
+        );
+        assert_ok_eq!(
+            get_next_token_with_options("\u{1F600}", 0, GoalSymbols::InputElementDiv, &options),
+            (Token::Unknown('\u{1F600}'), 0..4)
+        );
+        assert_err!(get_next_token_with_options("@", 0, GoalSymbols::InputElementDiv, &LexerOptions::default()));
+
+        let unknown = CompactToken { kind: TokenKind::Unknown, span: Span::new(2, 3) };
+        assert_ok_eq!(unknown.typed("a @"), Token::Unknown('@'));
+        assert_err!(CompactToken { kind: TokenKind::Unknown, span: Span::new(0, 2) }.typed("a @"));
+    }
+
+    #[test]
+    fn test_error_recovery_of_unterminated_literals() {
+        let options = LexerOptions { recover_errors: true, ..LexerOptions::default() };
+        let kinds: Vec<_> = Lexer::with_options("'ab", options)
+            .map(|token| token.map(|(token, _)| token.kind()))
+            .collect();
+        assert_eq!(kinds, [Ok(TokenKind::Unknown), Ok(TokenKind::IdentifierName)]);
+    }
+
+    /// Pieces that start or end productions, to reach deep into the grammar.
+    const ALPHABET: &[&str] = &[
+        "/", "*", "#", "!", "?", ".", "=", ">", "<", "&", "|", "+", "-",
+        "{", "}", "`", "\\", "\"", "'", "0", "9", "a", "in", "if", "_",
+        "$", "\r", "\n", "\r\n", "\u{2028}", " ", "\t", "\u{FEFF}",
+        "\u{200C}", "д", "\u{1F600}", "\u{0}",
+    ];
+
+    /// Deterministic xorshift generator so failures are reproducible.
+    fn next_random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[rstest]
+    fn test_fuzzed_input_does_not_panic(
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        let mut state = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..2000 {
+            let length = next_random(&mut state) % 12;
+            let input: String = (0..length)
+                .map(|_| ALPHABET[usize::try_from(next_random(&mut state)).unwrap() % ALPHABET.len()])
+                .collect();
+            match get_next_token(&input, mode) {
+                Ok((_, tail)) => assert!(input.ends_with(tail) && tail.len() < input.len(), "{input:?}"),
+                Err(error) => {
+                    assert!(error.range.end <= input.len(), "{input:?}");
+                    assert_ne!(error.code, ErrorCode::InternalError, "{input:?}: {error}");
+                },
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_scanner_matches_grammar(
+        #[values(
+            GoalSymbols::InputElementHashbangOrRegExp,
+            GoalSymbols::InputElementRegExpOrTemplateTail,
+            GoalSymbols::InputElementRegExp,
+            GoalSymbols::InputElementTemplateTail,
+            GoalSymbols::InputElementDiv,
+        )]
+        mode: GoalSymbols,
+    ) {
+        // Pieces of escape sequences, numbers and regular expressions.
+        const EXTRA: &[&str] = &[
+            "u", "x", "{", "[", "]", "1", "4", "8", "e", "n", "b", "o", "F",
+            "10", "0x", "${", "*/", "\\u", "\\x", "\\u{",
+        ];
+        const CORPUS: &[&str] = &[
+            "await", "awaits", "aw\\u0061it", "#private", "\\u{1F600}",
+            "0b1_0n", "0O7", "0xF_Fn", "1_000n", "0n", "01", "1.5e-3", ".5E+1",
+            "5.", "1__0", "1_", "0_1", "'\\0'", "'\\08'", "'\\377'", "'\\400'",
+            "'\\8'", "'a\\\r\nb'", "'\u{2028}'", "\"\\u{10FFFF}\"", "'\\u{110000}'",
+            "`a${", "`\\u{`", "`\\u{0000000110000}`", "`\\u{10FFFF`", "`\\1`",
+            "`\\xg`", "`\\u12`", "}a`", "}${", "/[/]\\//gimsuyd", "/a\\\n/",
+            "010", "0178", "08n", "07n", "09.5e1", "07.5", "00",
+            "?.5", "?.a", "...", "**=", ">>>=", "??=", "/* a */", "// a\r", "#!x\n",
+        ];
+        let mut state = 0x9E37_79B9_7F4A_7C15;
+        let random = (0..5000).map(|_| {
+            let length = next_random(&mut state) % 16;
+            (0..length)
+                .map(|_| {
+                    let index = usize::try_from(next_random(&mut state)).unwrap() % (ALPHABET.len() + EXTRA.len());
+                    ALPHABET.get(index).unwrap_or_else(|| &EXTRA[index - ALPHABET.len()])
+                })
+                .copied()
+                .collect::<String>()
+        });
+        for input in CORPUS.iter().map(|input| (*input).to_owned()).chain(random) {
+            assert_eq!(get_next_token(&input, mode), get_next_token_with_grammar(&input, mode), "{input:?}");
+        }
+    }
 }